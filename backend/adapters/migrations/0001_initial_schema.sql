@@ -1,8 +1,5 @@
 -- TODO use left join instead of inner join in queries
 
-DROP SCHEMA IF EXISTS public CASCADE;
-CREATE SCHEMA public;
-
 CREATE DOMAIN seconds_from_unix_epoch bigint CHECK (value > 0);
 
 CREATE TYPE gender AS ENUM ('male', 'female');
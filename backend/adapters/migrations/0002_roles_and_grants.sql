@@ -0,0 +1,34 @@
+-- changes made to the schema before it was tracked by migrations,
+-- databases created from db.sql are baselined at 0001 and get them here
+
+CREATE TYPE user_role AS enum ('admin', 'university_staff', 'department_head', 'teacher', 'student');
+
+ALTER TABLE users
+    ADD COLUMN role user_role NOT NULL DEFAULT 'student';
+
+ALTER TABLE subdivisions
+    ADD COLUMN parent_id integer references subdivisions;
+
+create table user_grants
+(
+  id serial primary key,
+  user_id serial not null references users,
+  role user_role not null,
+  university_id integer references universities,
+  subdivision_id integer references subdivisions,
+  study_group_id integer references study_groups,
+
+  check (num_nonnulls(university_id, subdivision_id, study_group_id) <= 1),
+  unique nulls not distinct (user_id, role, university_id, subdivision_id, study_group_id)
+);
+
+-- passports hold calendar dates, the time of day was always midnight
+ALTER TABLE passports
+    ALTER COLUMN date_of_birth TYPE date,
+    ALTER COLUMN date_of_issue TYPE date;
+
+-- durations are whole hours, fractions are rounded up to keep them positive
+ALTER TABLE attestations
+    ALTER COLUMN duration_in_hours TYPE integer USING ceil(duration_in_hours);
+ALTER TABLE classes
+    ALTER COLUMN duration_in_hours TYPE integer USING ceil(duration_in_hours);
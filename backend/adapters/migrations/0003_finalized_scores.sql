@@ -0,0 +1,3 @@
+-- finalized scores can no longer be amended by examiners
+ALTER TABLE student_attestations
+    ADD COLUMN is_final boolean NOT NULL DEFAULT false;
@@ -0,0 +1,19 @@
+CREATE TYPE grade_notation AS enum ('numeric', 'letter');
+
+-- universities without a row here use the default scale of the application
+CREATE TABLE grading_scales
+(
+    id serial primary key,
+    university_id integer NOT NULL unique references universities,
+    pass_score integer NOT NULL
+        CHECK (pass_score >= 0 AND pass_score <= 100),
+    satisfactory_score integer NOT NULL
+        CHECK (satisfactory_score >= 0 AND satisfactory_score <= 100),
+    good_score integer NOT NULL
+        CHECK (good_score >= 0 AND good_score <= 100),
+    excellent_score integer NOT NULL
+        CHECK (excellent_score >= 0 AND excellent_score <= 100),
+    notation grade_notation NOT NULL DEFAULT 'numeric',
+
+    CHECK (satisfactory_score <= good_score AND good_score <= excellent_score)
+);
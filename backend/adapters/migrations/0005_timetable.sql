@@ -0,0 +1,37 @@
+CREATE TABLE rooms
+(
+    id serial primary key,
+    university_id integer NOT NULL references universities,
+    name varchar(64) NOT NULL,
+    capacity integer NOT NULL
+        CHECK (capacity > 0),
+
+    unique (university_id, name)
+);
+
+CREATE TYPE recurrence AS enum ('weekly', 'bi_weekly');
+
+-- bi-weekly entries take place on the week of valid_from and every second week after it
+CREATE TABLE schedule_entries
+(
+    id serial primary key,
+    class_id integer NOT NULL references classes,
+    study_group_id integer NOT NULL references study_groups,
+    teacher_id integer NOT NULL references teachers,
+    room_id integer NOT NULL references rooms,
+    -- ISO 8601 day of the week, monday is 1
+    weekday integer NOT NULL
+        CHECK (weekday >= 1 AND weekday <= 7),
+    starts_at time NOT NULL,
+    ends_at time NOT NULL,
+    recurrence recurrence NOT NULL,
+    valid_from date NOT NULL,
+    valid_until date NOT NULL,
+
+    CHECK (starts_at < ends_at),
+    CHECK (valid_from <= valid_until)
+);
+
+CREATE INDEX schedule_entries_study_group_id_idx ON schedule_entries (study_group_id);
+CREATE INDEX schedule_entries_teacher_id_idx ON schedule_entries (teacher_id);
+CREATE INDEX schedule_entries_room_id_idx ON schedule_entries (room_id);
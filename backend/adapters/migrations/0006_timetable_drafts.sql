@@ -0,0 +1,65 @@
+-- windows of the week a teacher prefers to teach in, no rows means no preference
+CREATE TABLE teacher_availability
+(
+    teacher_id integer NOT NULL references teachers ON DELETE CASCADE,
+    weekday integer NOT NULL
+        CHECK (weekday >= 1 AND weekday <= 7),
+    starts_at time NOT NULL,
+    ends_at time NOT NULL,
+
+    CHECK (starts_at < ends_at)
+);
+
+CREATE INDEX teacher_availability_teacher_id_idx ON teacher_availability (teacher_id);
+
+CREATE TYPE timetable_draft_status AS enum ('pending', 'ready', 'failed', 'applied');
+
+CREATE TYPE unplaced_reason AS enum ('unassigned', 'no_room', 'no_slot');
+
+-- drafts are snapshots, the rows they mention are checked again when a draft is applied
+CREATE TABLE timetable_drafts
+(
+    id serial primary key,
+    author_id integer NOT NULL references users ON DELETE CASCADE,
+    semester integer NOT NULL
+        CHECK (semester > 0),
+    valid_from date NOT NULL,
+    valid_until date NOT NULL,
+    status timetable_draft_status NOT NULL,
+    score integer,
+
+    CHECK (valid_from <= valid_until)
+);
+
+CREATE TABLE timetable_draft_study_groups
+(
+    draft_id integer NOT NULL references timetable_drafts ON DELETE CASCADE,
+    study_group_id integer NOT NULL,
+
+    PRIMARY KEY (draft_id, study_group_id)
+);
+
+CREATE TABLE timetable_draft_entries
+(
+    id serial primary key,
+    draft_id integer NOT NULL references timetable_drafts ON DELETE CASCADE,
+    class_id integer NOT NULL,
+    study_group_id integer NOT NULL,
+    teacher_id integer NOT NULL,
+    room_id integer NOT NULL,
+    weekday integer NOT NULL
+        CHECK (weekday >= 1 AND weekday <= 7),
+    starts_at time NOT NULL,
+    ends_at time NOT NULL,
+    recurrence recurrence NOT NULL,
+    valid_from date NOT NULL
+);
+
+CREATE TABLE timetable_draft_unplaced
+(
+    id serial primary key,
+    draft_id integer NOT NULL references timetable_drafts ON DELETE CASCADE,
+    class_id integer NOT NULL,
+    study_group_id integer NOT NULL,
+    reason unplaced_reason NOT NULL
+);
@@ -0,0 +1,30 @@
+-- wall clock times of the timetable are local to the university, calendar feeds convert them to UTC
+ALTER TABLE universities
+    ADD COLUMN utc_offset_minutes integer NOT NULL DEFAULT 180
+        CHECK (utc_offset_minutes >= -720 AND utc_offset_minutes <= 840);
+
+CREATE TABLE attestation_sessions
+(
+    id serial primary key,
+    attestation_id integer NOT NULL references attestations,
+    study_group_id integer NOT NULL references study_groups,
+    -- remote sessions have no room
+    room_id integer references rooms,
+    date date NOT NULL,
+    starts_at time NOT NULL,
+    ends_at time NOT NULL,
+
+    CHECK (starts_at < ends_at)
+);
+
+CREATE INDEX attestation_sessions_attestation_id_idx ON attestation_sessions (attestation_id);
+CREATE INDEX attestation_sessions_study_group_id_idx ON attestation_sessions (study_group_id);
+CREATE INDEX attestation_sessions_room_id_idx ON attestation_sessions (room_id);
+
+-- the token is the only credential of a feed, calendar apps fetch it without logging in
+CREATE TABLE calendar_feeds
+(
+    id serial primary key,
+    person_id integer NOT NULL UNIQUE references persons ON DELETE CASCADE,
+    token varchar(64) NOT NULL UNIQUE
+);
@@ -0,0 +1,13 @@
+-- planned hours per semester expected from teachers of a kind,
+-- kinds without a row here are not checked
+CREATE TABLE load_limits
+(
+    id serial primary key,
+    university_id integer NOT NULL references universities,
+    teacher_kind teacher_kind NOT NULL,
+    min_hours integer NOT NULL CHECK (min_hours >= 0),
+    max_hours integer NOT NULL,
+
+    unique (university_id, teacher_kind),
+    CHECK (min_hours <= max_hours)
+);
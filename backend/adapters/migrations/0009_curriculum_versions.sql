@@ -0,0 +1,15 @@
+CREATE TYPE curriculum_status AS enum ('draft', 'published', 'archived');
+
+-- versions of a curriculum share its name, only drafts can be changed
+ALTER TABLE curriculums
+    ADD COLUMN version integer NOT NULL DEFAULT 1
+        CHECK (version > 0),
+    ADD COLUMN status curriculum_status NOT NULL DEFAULT 'draft';
+
+-- curriculums already taught to study groups keep being taught as they are
+UPDATE curriculums SET status = 'published'
+WHERE id IN (SELECT curriculum_id FROM study_group_curriculums);
+
+ALTER TABLE curriculums
+    DROP CONSTRAINT curriculums_name_key,
+    ADD CONSTRAINT curriculums_name_version_key UNIQUE (name, version);
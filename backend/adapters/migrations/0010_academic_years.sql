@@ -0,0 +1,27 @@
+CREATE TABLE academic_years
+(
+    id serial primary key,
+    university_id integer NOT NULL references universities,
+    -- calendar year the academic year starts in, 2023 for 2023/2024
+    start_year integer NOT NULL,
+
+    unique (university_id, start_year)
+);
+
+-- semesters are numbered in the order they are studied within the year
+CREATE TABLE academic_year_semesters
+(
+    academic_year_id integer NOT NULL references academic_years ON DELETE CASCADE,
+    number integer NOT NULL
+        CHECK (number > 0),
+    starts_on date NOT NULL,
+    ends_on date NOT NULL,
+
+    primary key (academic_year_id, number),
+    CHECK (starts_on <= ends_on)
+);
+
+-- the first semester of a group is in the academic year of its enrollment
+ALTER TABLE study_groups
+    ADD COLUMN enrollment_year integer
+        CHECK (enrollment_year > 0);
@@ -0,0 +1,31 @@
+CREATE TYPE student_status AS enum ('active', 'on_leave', 'expelled', 'graduated');
+CREATE TYPE student_order_kind AS enum (
+    'enrollment', 'transfer', 'academic_leave', 'return_from_leave', 'expulsion', 'graduation'
+);
+
+ALTER TABLE students
+    ADD COLUMN status student_status NOT NULL DEFAULT 'active';
+
+-- study group and status of a student at any date are replayed from the orders
+CREATE TABLE student_orders
+(
+    id serial primary key,
+    student_id integer NOT NULL references students,
+    kind student_order_kind NOT NULL,
+    -- study group of the student after the order
+    study_group_id integer NOT NULL references study_groups,
+    date date NOT NULL,
+    reason text NOT NULL,
+    author_id integer references users ON DELETE SET NULL
+);
+
+CREATE INDEX student_orders_student_id_idx ON student_orders (student_id);
+CREATE INDEX student_orders_study_group_id_idx ON student_orders (study_group_id);
+
+-- students recorded before orders are assumed to be enrolled with their study group
+INSERT INTO student_orders (student_id, kind, study_group_id, date, reason)
+SELECT s.id, 'enrollment', s.study_group_id,
+       COALESCE(make_date(sg.enrollment_year, 9, 1), current_date),
+       'recorded before student orders were introduced'
+FROM students s
+         JOIN study_groups sg ON sg.id = s.study_group_id;
@@ -0,0 +1,15 @@
+ALTER TABLE users
+    ADD COLUMN email_verified boolean NOT NULL DEFAULT false;
+
+CREATE TYPE account_token_purpose AS enum ('email_verification', 'password_reset');
+
+-- tokens sent by mail are signed references to these rows, a row is deleted once its token is used
+CREATE TABLE account_tokens
+(
+    id serial primary key,
+    user_id integer NOT NULL references users ON DELETE CASCADE,
+    purpose account_token_purpose NOT NULL,
+    expires_at seconds_from_unix_epoch NOT NULL
+);
+
+CREATE INDEX account_tokens_user_id_idx ON account_tokens (user_id);
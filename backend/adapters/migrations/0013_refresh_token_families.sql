@@ -0,0 +1,29 @@
+-- only sha-256 hashes of refresh tokens are stored, existing tokens are hashed in place
+ALTER TABLE user_sessions
+    RENAME COLUMN refresh_token TO refresh_token_hash;
+UPDATE user_sessions
+    SET refresh_token_hash = encode(sha256(convert_to(refresh_token_hash, 'UTF8')), 'hex');
+ALTER TABLE user_sessions
+    ALTER COLUMN refresh_token_hash TYPE varchar(64);
+
+-- hash of the token issued at login, tokens rotated from it share the family
+ALTER TABLE user_sessions
+    ADD COLUMN family_id varchar(64);
+UPDATE user_sessions
+    SET family_id = refresh_token_hash;
+ALTER TABLE user_sessions
+    ALTER COLUMN family_id SET NOT NULL;
+
+-- tokens replaced by rotation, presenting one of them again revokes the session
+CREATE TABLE user_session_rotated_tokens
+(
+    token_hash varchar(64) primary key,
+    user_id integer NOT NULL,
+    metadata varchar(1024) NOT NULL,
+    family_id varchar(64) NOT NULL,
+
+    FOREIGN KEY (user_id, metadata) REFERENCES user_sessions ON DELETE CASCADE
+);
+
+CREATE INDEX user_session_rotated_tokens_session_idx
+    ON user_session_rotated_tokens (user_id, metadata);
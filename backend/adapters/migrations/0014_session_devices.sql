@@ -0,0 +1,22 @@
+-- sessions are keyed by a device id the client keeps instead of the user agent,
+-- sessions keyed by a user agent can't be matched to a device, their users log in again
+DELETE FROM user_sessions;
+
+ALTER TABLE user_sessions
+    RENAME COLUMN metadata TO device_id;
+ALTER TABLE user_sessions
+    ALTER COLUMN device_id TYPE varchar(64);
+
+-- describe the device in the session list, updated on every login and refresh
+ALTER TABLE user_sessions
+    ADD COLUMN user_agent varchar(1024),
+    ADD COLUMN ip varchar(45),
+    ADD COLUMN last_seen_at seconds_from_unix_epoch NOT NULL;
+
+ALTER TABLE user_session_rotated_tokens
+    RENAME COLUMN metadata TO device_id;
+ALTER TABLE user_session_rotated_tokens
+    ALTER COLUMN device_id TYPE varchar(64);
+ALTER TABLE user_session_rotated_tokens
+    RENAME CONSTRAINT user_session_rotated_tokens_user_id_metadata_fkey
+        TO user_session_rotated_tokens_user_id_device_id_fkey;
@@ -0,0 +1,7 @@
+-- only sha-256 hashes of calendar feed tokens are stored, existing tokens are hashed in place
+ALTER TABLE calendar_feeds
+    RENAME COLUMN token TO token_hash;
+ALTER TABLE calendar_feeds
+    RENAME CONSTRAINT calendar_feeds_token_key TO calendar_feeds_token_hash_key;
+UPDATE calendar_feeds
+    SET token_hash = encode(sha256(convert_to(token_hash, 'UTF8')), 'hex');
@@ -0,0 +1,16 @@
+-- department authoring the curriculum, its managers are the ones to change it
+ALTER TABLE curriculums
+    ADD COLUMN department_id integer REFERENCES subdivisions;
+
+-- existing curriculums are attributed to the department of their disciplines or study groups,
+-- the ones with neither are left without a department and only admins can change them
+UPDATE curriculums c SET department_id = coalesce(
+    (SELECT min(d.department_id)
+     FROM curriculum_modules m
+     JOIN disciplines d ON d.id = m.discipline_id
+     WHERE m.curriculum_id = c.id),
+    (SELECT min(sg.department_id)
+     FROM study_group_curriculums sgc
+     JOIN study_groups sg ON sg.id = sgc.study_group_id
+     WHERE sgc.curriculum_id = c.id)
+);
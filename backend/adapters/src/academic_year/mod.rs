@@ -0,0 +1,177 @@
+mod model;
+
+use app::{
+    academic_year::{self, Entity, EntityId},
+    university,
+};
+use sea_query::{Asterisk, Expr, Order, Query, SimpleExpr};
+use std::sync::Arc;
+use tokio::sync::Mutex;
+
+use crate::{execute, fetch_all, fetch_one, fetch_optional, PgTransaction};
+
+use self::model::{
+    AcademicYearSemesters, AcademicYearSemestersIden, AcademicYears, AcademicYearsIden,
+};
+
+pub struct PgAcademicYearRepo {
+    pub txn: Arc<Mutex<PgTransaction<'static>>>,
+}
+
+impl PgAcademicYearRepo {
+    fn values(entity: &Entity) -> [(AcademicYearsIden, SimpleExpr); 2] {
+        [
+            (
+                AcademicYearsIden::UniversityId,
+                entity.university_id.value.into(),
+            ),
+            (AcademicYearsIden::StartYear, entity.start_year.into()),
+        ]
+    }
+
+    async fn insert(&self, entity: &Entity) -> Result<AcademicYears, anyhow::Error> {
+        let (columns, values): (Vec<_>, Vec<_>) = Self::values(entity).into_iter().unzip();
+
+        let mut query = Query::insert();
+        let query = query
+            .into_table(AcademicYearsIden::Table)
+            .columns(columns)
+            .values_panic(values)
+            .returning_all();
+
+        fetch_one(&self.txn, query).await
+    }
+
+    async fn update(&self, entity: &Entity) -> Result<AcademicYears, anyhow::Error> {
+        let mut query = Query::update();
+        let query = query
+            .table(AcademicYearsIden::Table)
+            .values(Self::values(entity))
+            .and_where(Expr::col(AcademicYearsIden::Id).eq(entity.id.value))
+            .returning_all();
+
+        fetch_one(&self.txn, query).await
+    }
+
+    async fn delete_semesters(&self, id: i32) -> Result<(), anyhow::Error> {
+        execute(
+            &self.txn,
+            Query::delete()
+                .from_table(AcademicYearSemestersIden::Table)
+                .and_where(Expr::col(AcademicYearSemestersIden::AcademicYearId).eq(id)),
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    async fn insert_semesters(&self, id: i32, entity: &Entity) -> Result<(), anyhow::Error> {
+        for (number, semester) in (1..).zip(entity.semesters.iter()) {
+            execute(
+                &self.txn,
+                Query::insert()
+                    .into_table(AcademicYearSemestersIden::Table)
+                    .columns([
+                        AcademicYearSemestersIden::AcademicYearId,
+                        AcademicYearSemestersIden::Number,
+                        AcademicYearSemestersIden::StartsOn,
+                        AcademicYearSemestersIden::EndsOn,
+                    ])
+                    .values_panic([
+                        id.into(),
+                        number.into(),
+                        semester.starts_on.into(),
+                        semester.ends_on.into(),
+                    ]),
+            )
+            .await?;
+        }
+
+        Ok(())
+    }
+
+    async fn with_semesters(&self, model: AcademicYears) -> Result<Entity, anyhow::Error> {
+        let semesters = fetch_all::<AcademicYearSemesters>(
+            &self.txn,
+            Query::select()
+                .from(AcademicYearSemestersIden::Table)
+                .column(Asterisk)
+                .and_where(Expr::col(AcademicYearSemestersIden::AcademicYearId).eq(model.id))
+                .order_by(AcademicYearSemestersIden::Number, Order::Asc),
+        )
+        .await?;
+
+        Ok(model.into_entity(semesters))
+    }
+}
+
+#[async_trait::async_trait]
+impl academic_year::Repo for PgAcademicYearRepo {
+    async fn save(&mut self, entity: Entity) -> Result<Entity, anyhow::Error> {
+        let model = if self.find(entity.id).await?.is_some() {
+            self.update(&entity).await?
+        } else {
+            self.insert(&entity).await?
+        };
+
+        self.delete_semesters(model.id).await?;
+        self.insert_semesters(model.id, &entity).await?;
+
+        Ok(Entity {
+            id: EntityId::new(model.id),
+            ..entity
+        })
+    }
+
+    async fn delete(&mut self, entity: &Entity) -> Result<(), anyhow::Error> {
+        self.delete_semesters(entity.id.value).await?;
+
+        execute(
+            &self.txn,
+            Query::delete()
+                .from_table(AcademicYearsIden::Table)
+                .and_where(Expr::col(AcademicYearsIden::Id).eq(entity.id.value)),
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    async fn find(&self, id: EntityId) -> Result<Option<Entity>, anyhow::Error> {
+        let Some(model) = fetch_optional::<AcademicYears>(
+            &self.txn,
+            Query::select()
+                .from(AcademicYearsIden::Table)
+                .column(Asterisk)
+                .and_where(Expr::col(AcademicYearsIden::Id).eq(id.value)),
+        )
+        .await?
+        else {
+            return Ok(None);
+        };
+
+        Ok(Some(self.with_semesters(model).await?))
+    }
+
+    async fn list_by_university(
+        &self,
+        university_id: university::EntityId,
+    ) -> Result<Vec<Entity>, anyhow::Error> {
+        let models = fetch_all::<AcademicYears>(
+            &self.txn,
+            Query::select()
+                .from(AcademicYearsIden::Table)
+                .column(Asterisk)
+                .and_where(Expr::col(AcademicYearsIden::UniversityId).eq(university_id.value))
+                .order_by(AcademicYearsIden::StartYear, Order::Asc),
+        )
+        .await?;
+
+        let mut entities = vec![];
+        for model in models {
+            entities.push(self.with_semesters(model).await?);
+        }
+
+        Ok(entities)
+    }
+}
@@ -0,0 +1,37 @@
+use app::academic_year::{self, SemesterRange};
+use sqlx::FromRow;
+use utils::entity::Id;
+
+#[derive(Clone, Debug, FromRow)]
+#[sea_query::enum_def]
+pub struct AcademicYears {
+    pub id: i32,
+    pub university_id: i32,
+    pub start_year: i32,
+}
+
+#[derive(Clone, Debug, FromRow)]
+#[sea_query::enum_def]
+pub struct AcademicYearSemesters {
+    pub academic_year_id: i32,
+    pub number: i32,
+    pub starts_on: time::Date,
+    pub ends_on: time::Date,
+}
+
+impl AcademicYears {
+    pub fn into_entity(self, semesters: Vec<AcademicYearSemesters>) -> academic_year::Entity {
+        academic_year::Entity {
+            id: Id::new(self.id),
+            university_id: Id::new(self.university_id),
+            start_year: self.start_year,
+            semesters: semesters
+                .into_iter()
+                .map(|v| SemesterRange {
+                    starts_on: v.starts_on,
+                    ends_on: v.ends_on,
+                })
+                .collect(),
+        }
+    }
+}
@@ -6,10 +6,15 @@ use anyhow::Context;
 use jsonwebtoken::{Algorithm, Header, Validation};
 
 use app::token::{AccessTokenEngine, Claims};
+use app::user::Role;
 use app::user_session::{Seconds, SecondsFromUnixEpoch};
 
 const JWT_ALGORITHM: Algorithm = Algorithm::HS256;
-// const ADMIN_ROLE_IDENT: &str = "ADMIN";
+const ADMIN_ROLE_IDENT: &str = "ADMIN";
+const UNIVERSITY_STAFF_ROLE_IDENT: &str = "UNIVERSITY_STAFF";
+const DEPARTMENT_HEAD_ROLE_IDENT: &str = "DEPARTMENT_HEAD";
+const TEACHER_ROLE_IDENT: &str = "TEACHER";
+const STUDENT_ROLE_IDENT: &str = "STUDENT";
 
 #[derive(Clone)]
 pub struct JwtKeys(pub jsonwebtoken::EncodingKey, pub jsonwebtoken::DecodingKey);
@@ -28,8 +33,9 @@ pub struct JwtAccessTokenEngine {
 pub(crate) struct JwtClaims {
     pub user_id: i32,
     pub email: String,
+    #[serde(rename = "exp")]
     pub expires_at: u64,
-    // pub role: String,
+    pub role: String,
 }
 
 #[async_trait::async_trait]
@@ -66,10 +72,14 @@ impl TryFrom<JwtClaims> for Claims {
                     val: value.expires_at,
                 },
             },
-            // role: match &value.role[..] {
-            //     ADMIN_ROLE_IDENT => Role::Admin,
-            //     _ => return Err(ConvertJwtClaimsError),
-            // },
+            role: match &value.role[..] {
+                ADMIN_ROLE_IDENT => Role::Admin,
+                UNIVERSITY_STAFF_ROLE_IDENT => Role::UniversityStaff,
+                DEPARTMENT_HEAD_ROLE_IDENT => Role::DepartmentHead,
+                TEACHER_ROLE_IDENT => Role::Teacher,
+                STUDENT_ROLE_IDENT => Role::Student,
+                _ => return Err(ConvertJwtClaimsError),
+            },
         })
     }
 }
@@ -80,9 +90,14 @@ impl From<Claims> for JwtClaims {
             user_id: value.user_id,
             email: value.email,
             expires_at: value.expires_at.seconds.val,
-            // role: match value.role {
-            //     Role::Admin => ADMIN_ROLE_IDENT.to_owned(),
-            // },
+            role: match value.role {
+                Role::Admin => ADMIN_ROLE_IDENT,
+                Role::UniversityStaff => UNIVERSITY_STAFF_ROLE_IDENT,
+                Role::DepartmentHead => DEPARTMENT_HEAD_ROLE_IDENT,
+                Role::Teacher => TEACHER_ROLE_IDENT,
+                Role::Student => STUDENT_ROLE_IDENT,
+            }
+            .to_owned(),
         }
     }
 }
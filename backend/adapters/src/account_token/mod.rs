@@ -0,0 +1,116 @@
+mod model;
+
+use app::{
+    account_token::{self, Entity, EntityId, Purpose},
+    user,
+};
+use sea_query::{Asterisk, Expr, Query};
+use std::sync::Arc;
+use tokio::sync::Mutex;
+
+use crate::{execute, fetch_one, fetch_optional, PgEnum, PgTransaction};
+
+use self::model::{AccountTokens, AccountTokensIden, PgPurpose};
+
+pub struct PgAccountTokenRepo {
+    pub txn: Arc<Mutex<PgTransaction<'static>>>,
+}
+
+impl PgAccountTokenRepo {
+    async fn insert(&self, entity: Entity) -> Result<AccountTokens, anyhow::Error> {
+        let mut query = Query::insert();
+        let query = query
+            .into_table(AccountTokensIden::Table)
+            .columns([
+                AccountTokensIden::UserId,
+                AccountTokensIden::Purpose,
+                AccountTokensIden::ExpiresAt,
+            ])
+            .values_panic([
+                entity.user_id.value.into(),
+                PgPurpose::from(entity.purpose).into_expr(),
+                entity.expires_at.seconds.val.into(),
+            ])
+            .returning_all();
+
+        fetch_one(&self.txn, query).await
+    }
+
+    async fn update(&self, entity: Entity) -> Result<AccountTokens, anyhow::Error> {
+        let mut query = Query::update();
+        let query = query
+            .table(AccountTokensIden::Table)
+            .values([
+                (AccountTokensIden::UserId, entity.user_id.value.into()),
+                (
+                    AccountTokensIden::Purpose,
+                    PgPurpose::from(entity.purpose).into_expr(),
+                ),
+                (
+                    AccountTokensIden::ExpiresAt,
+                    entity.expires_at.seconds.val.into(),
+                ),
+            ])
+            .and_where(Expr::col(AccountTokensIden::Id).eq(entity.id.value))
+            .returning_all();
+
+        fetch_one(&self.txn, query).await
+    }
+}
+
+#[async_trait::async_trait]
+impl account_token::Repo for PgAccountTokenRepo {
+    async fn save(&mut self, entity: Entity) -> Result<Entity, anyhow::Error> {
+        let model = if self.find(entity.id).await?.is_some() {
+            self.update(entity).await?
+        } else {
+            self.insert(entity).await?
+        };
+
+        Ok(model.into())
+    }
+
+    async fn delete(&mut self, entity: &Entity) -> Result<(), anyhow::Error> {
+        execute(
+            &self.txn,
+            Query::delete()
+                .from_table(AccountTokensIden::Table)
+                .and_where(Expr::col(AccountTokensIden::Id).eq(entity.id.value)),
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    async fn find(&self, id: EntityId) -> Result<Option<Entity>, anyhow::Error> {
+        let model = fetch_optional::<AccountTokens>(
+            &self.txn,
+            Query::select()
+                .from(AccountTokensIden::Table)
+                .column(Asterisk)
+                .and_where(Expr::col(AccountTokensIden::Id).eq(id.value)),
+        )
+        .await?;
+
+        Ok(model.map(Into::into))
+    }
+
+    async fn delete_by_user(
+        &mut self,
+        user_id: user::EntityId,
+        purpose: Purpose,
+    ) -> Result<(), anyhow::Error> {
+        execute(
+            &self.txn,
+            Query::delete()
+                .from_table(AccountTokensIden::Table)
+                .and_where(Expr::col(AccountTokensIden::UserId).eq(user_id.value))
+                .and_where(
+                    Expr::col(AccountTokensIden::Purpose).eq(PgPurpose::from(purpose).into_expr()),
+                ),
+        )
+        .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,74 @@
+use std::fmt::Display;
+
+use app::{
+    account_token::{self, Purpose},
+    user_session::SecondsFromUnixEpoch,
+};
+use sqlx::FromRow;
+use utils::entity::Id;
+
+use crate::PgEnum;
+
+#[derive(Clone, Debug, FromRow)]
+#[sea_query::enum_def]
+pub struct AccountTokens {
+    pub id: i32,
+    pub user_id: i32,
+    pub purpose: PgPurpose,
+    pub expires_at: i64,
+}
+
+impl From<AccountTokens> for account_token::Entity {
+    fn from(value: AccountTokens) -> Self {
+        account_token::Entity {
+            id: Id::new(value.id),
+            user_id: Id::new(value.user_id),
+            purpose: value.purpose.into(),
+            // the column domain only allows positive values
+            expires_at: SecondsFromUnixEpoch::from(value.expires_at as u64),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, sqlx::Type)]
+#[sqlx(type_name = "account_token_purpose")]
+#[sqlx(rename_all = "snake_case")]
+pub enum PgPurpose {
+    EmailVerification,
+    PasswordReset,
+}
+
+impl From<Purpose> for PgPurpose {
+    fn from(value: Purpose) -> Self {
+        match value {
+            Purpose::EmailVerification => PgPurpose::EmailVerification,
+            Purpose::PasswordReset => PgPurpose::PasswordReset,
+        }
+    }
+}
+
+impl From<PgPurpose> for Purpose {
+    fn from(value: PgPurpose) -> Self {
+        match value {
+            PgPurpose::EmailVerification => Purpose::EmailVerification,
+            PgPurpose::PasswordReset => Purpose::PasswordReset,
+        }
+    }
+}
+
+impl Display for PgPurpose {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::EmailVerification => "email_verification",
+                Self::PasswordReset => "password_reset",
+            }
+        )
+    }
+}
+
+impl PgEnum for PgPurpose {
+    const TYPE_NAME: &'static str = "account_token_purpose";
+}
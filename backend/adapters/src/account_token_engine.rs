@@ -0,0 +1,84 @@
+use serde::{Deserialize, Serialize};
+
+use std::sync::Arc;
+
+use anyhow::Context;
+use jsonwebtoken::{Algorithm, Header, Validation};
+
+use app::account_token::Purpose;
+use app::token::{AccountTokenClaims, AccountTokenEngine};
+use app::user_session::SecondsFromUnixEpoch;
+
+use crate::access_token::JwtKeys;
+
+const JWT_ALGORITHM: Algorithm = Algorithm::HS256;
+const ACCOUNT_TOKEN_TYPE: &str = "ACCOUNT";
+const EMAIL_VERIFICATION_PURPOSE_IDENT: &str = "EMAIL_VERIFICATION";
+const PASSWORD_RESET_PURPOSE_IDENT: &str = "PASSWORD_RESET";
+
+/// Signs account tokens with the access token keys, the `typ` claim which
+/// access tokens don't have keeps one from being passed off as the other
+pub struct JwtAccountTokenEngine {
+    pub(crate) keys: Arc<JwtKeys>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct JwtAccountTokenClaims {
+    typ: String,
+    #[serde(rename = "jti")]
+    token_id: i32,
+    #[serde(rename = "sub")]
+    user_id: i32,
+    purpose: String,
+    #[serde(rename = "exp")]
+    expires_at: u64,
+}
+
+#[async_trait::async_trait]
+impl AccountTokenEngine for JwtAccountTokenEngine {
+    async fn encode(&self, claims: AccountTokenClaims) -> Result<String, anyhow::Error> {
+        let claims = JwtAccountTokenClaims {
+            typ: ACCOUNT_TOKEN_TYPE.to_owned(),
+            token_id: claims.token_id,
+            user_id: claims.user_id,
+            purpose: match claims.purpose {
+                Purpose::EmailVerification => EMAIL_VERIFICATION_PURPOSE_IDENT,
+                Purpose::PasswordReset => PASSWORD_RESET_PURPOSE_IDENT,
+            }
+            .to_owned(),
+            expires_at: claims.expires_at.seconds.val,
+        };
+
+        jsonwebtoken::encode(&Header::new(JWT_ALGORITHM), &claims, &self.keys.0)
+            .context("failed to encode account token claims")
+    }
+
+    async fn decode(&self, token: &str) -> Result<Option<AccountTokenClaims>, anyhow::Error> {
+        // expired tokens are told apart from invalid ones by the service
+        let mut validation = Validation::new(JWT_ALGORITHM);
+        validation.validate_exp = false;
+
+        let Ok(data) =
+            jsonwebtoken::decode::<JwtAccountTokenClaims>(token, &self.keys.1, &validation)
+        else {
+            return Ok(None);
+        };
+        let claims = data.claims;
+        if claims.typ != ACCOUNT_TOKEN_TYPE {
+            return Ok(None);
+        }
+
+        let purpose = match &claims.purpose[..] {
+            EMAIL_VERIFICATION_PURPOSE_IDENT => Purpose::EmailVerification,
+            PASSWORD_RESET_PURPOSE_IDENT => Purpose::PasswordReset,
+            _ => return Ok(None),
+        };
+
+        Ok(Some(AccountTokenClaims {
+            token_id: claims.token_id,
+            user_id: claims.user_id,
+            purpose,
+            expires_at: SecondsFromUnixEpoch::from(claims.expires_at),
+        }))
+    }
+}
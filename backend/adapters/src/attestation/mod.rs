@@ -6,12 +6,12 @@ use app::{
 };
 use sea_query::{Asterisk, Condition, Expr, IntoCondition, JoinType, Query};
 use std::{
-    collections::{HashMap, HashSet},
+    collections::{BTreeMap, HashMap, HashSet},
     sync::Arc,
 };
 use tokio::sync::Mutex;
 
-use crate::{fetch_all, fetch_one, PgTransaction};
+use crate::{execute, fetch_all, fetch_one, PgEnum, PgTransaction};
 
 use self::model::{
     AttestationExaminers, AttestationExaminersIden, Attestations, AttestationsIden, JoinRow,
@@ -30,11 +30,11 @@ impl PgAttestationRepo {
             .columns([
                 AttestationsIden::CurriculumModuleId,
                 AttestationsIden::Kind,
-                AttestationsIden::Duration,
+                AttestationsIden::DurationInHours,
             ])
             .values_panic([
                 entity.curriculum_module_id.value.into(),
-                PgAttestationKind::from(entity.kind).to_string().into(),
+                PgAttestationKind::from(entity.kind).into_expr(),
                 entity.duration.0.into(),
             ])
             .returning_all();
@@ -53,9 +53,9 @@ impl PgAttestationRepo {
                 ),
                 (
                     AttestationsIden::Kind,
-                    PgAttestationKind::from(entity.kind).to_string().into(),
+                    PgAttestationKind::from(entity.kind).into_expr(),
                 ),
-                (AttestationsIden::Duration, entity.duration.0.into()),
+                (AttestationsIden::DurationInHours, entity.duration.0.into()),
             ])
             .and_where(Expr::col(AttestationsIden::Id).eq(entity.id.value))
             .returning_all();
@@ -76,8 +76,9 @@ impl PgAttestationRepo {
         let mut query = Query::select();
         query
             .from(attestation_table)
-            .column(Asterisk)
-            .join(JoinType::InnerJoin, examiner_table, on)
+            .column((attestation_table, Asterisk))
+            .column((examiner_table, AttestationExaminersIden::ExaminerId))
+            .join(JoinType::LeftJoin, examiner_table, on)
             .cond_where(cond);
 
         fetch_all(&self.txn, &query).await
@@ -86,13 +87,22 @@ impl PgAttestationRepo {
     fn entity_from_select(select: Vec<JoinRow>) -> Option<Entity> {
         let (models, examiners) = select
             .into_iter()
-            .map(|v| (v.attestation, v.examiner))
+            .map(|v| (v.attestation, v.examiner_id))
             .unzip::<_, _, Vec<_>, Vec<_>>();
 
         let Some(model) = models.into_iter().take(1).next() else {
             return None;
         };
 
+        let examiners = examiners
+            .into_iter()
+            .flatten()
+            .map(|examiner_id| AttestationExaminers {
+                attestation_id: model.id,
+                examiner_id,
+            })
+            .collect();
+
         Some(model.into_entity(examiners))
     }
 
@@ -102,7 +112,7 @@ impl PgAttestationRepo {
             .from_table(AttestationExaminersIden::Table)
             .and_where(Expr::col(AttestationExaminersIden::AttestationId).eq(id));
 
-        fetch_one::<()>(&self.txn, &query).await
+        execute(&self.txn, &query).await
     }
 
     async fn insert_examiners(
@@ -154,7 +164,7 @@ impl attestation::Repo for PgAttestationRepo {
 
         self.delete_examiners(entity.id.value).await?;
 
-        fetch_one::<()>(&self.txn, &query).await?;
+        execute(&self.txn, &query).await?;
         Ok(())
     }
 
@@ -185,6 +195,36 @@ impl attestation::Repo for PgAttestationRepo {
         Ok(entity)
     }
 
+    async fn list_by_curriculum_modules(
+        &self,
+        curriculum_modules_ids: HashSet<curriculum_module::EntityId>,
+    ) -> Result<Vec<Entity>, anyhow::Error> {
+        let select = self
+            .select(
+                Expr::col((
+                    AttestationsIden::Table,
+                    AttestationsIden::CurriculumModuleId,
+                ))
+                .is_in(curriculum_modules_ids.into_iter().map(|id| id.value)),
+            )
+            .await?;
+
+        let mut groups = BTreeMap::<i32, Vec<JoinRow>>::new();
+        for join_row in select {
+            groups
+                .entry(join_row.attestation.id)
+                .or_default()
+                .push(join_row);
+        }
+
+        let entities = groups
+            .into_values()
+            .filter_map(Self::entity_from_select)
+            .collect();
+
+        Ok(entities)
+    }
+
     async fn list_by_examiners(
         &self,
         examiners_ids: HashSet<teacher::EntityId>,
@@ -4,13 +4,15 @@ use app::attestation::{self, AttestationKind};
 use sqlx::FromRow;
 use utils::entity::Id;
 
+use crate::PgEnum;
+
 #[derive(Clone, Debug, FromRow)]
 #[sea_query::enum_def]
 pub struct Attestations {
     pub id: i32,
     pub curriculum_module_id: i32,
     pub kind: PgAttestationKind,
-    pub duration: i32,
+    pub duration_in_hours: i32,
 }
 
 impl Attestations {
@@ -19,7 +21,7 @@ impl Attestations {
             id: Id::new(self.id),
             curriculum_module_id: Id::new(self.curriculum_module_id),
             kind: self.kind.into(),
-            duration: attestation::Hours(self.duration),
+            duration: attestation::Hours(self.duration_in_hours),
             examiners: examiners
                 .into_iter()
                 .map(|v| v.examiner_id)
@@ -40,13 +42,12 @@ pub struct AttestationExaminers {
 pub struct JoinRow {
     #[sqlx(flatten)]
     pub attestation: Attestations,
-    #[sqlx(flatten)]
-    pub examiner: AttestationExaminers,
+    pub examiner_id: Option<i32>,
 }
 
 #[derive(Debug, Clone, sqlx::Type)]
 #[sqlx(type_name = "attestation_kind")]
-#[sqlx(rename_all = "lowercase")]
+#[sqlx(rename_all = "snake_case")]
 pub enum PgAttestationKind {
     Test,
     DiffTest,
@@ -86,3 +87,7 @@ impl Display for PgAttestationKind {
         )
     }
 }
+
+impl PgEnum for PgAttestationKind {
+    const TYPE_NAME: &'static str = "attestation_kind";
+}
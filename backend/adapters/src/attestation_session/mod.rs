@@ -0,0 +1,155 @@
+mod model;
+
+use std::collections::HashSet;
+
+use app::{
+    attestation,
+    attestation_session::{self, Entity, EntityId},
+    room, study_group,
+};
+use sea_query::{Asterisk, Expr, Query, SimpleExpr};
+use std::sync::Arc;
+use tokio::sync::Mutex;
+
+use crate::{execute, fetch_all, fetch_one, fetch_optional, PgTransaction};
+
+use self::model::{AttestationSessions, AttestationSessionsIden};
+
+pub struct PgAttestationSessionRepo {
+    pub txn: Arc<Mutex<PgTransaction<'static>>>,
+}
+
+impl PgAttestationSessionRepo {
+    fn values(entity: Entity) -> [(AttestationSessionsIden, SimpleExpr); 6] {
+        [
+            (
+                AttestationSessionsIden::AttestationId,
+                entity.attestation_id.value.into(),
+            ),
+            (
+                AttestationSessionsIden::StudyGroupId,
+                entity.study_group_id.value.into(),
+            ),
+            (
+                AttestationSessionsIden::RoomId,
+                entity.room_id.map(|v| v.value).into(),
+            ),
+            (AttestationSessionsIden::Date, entity.date.into()),
+            (AttestationSessionsIden::StartsAt, entity.starts_at.into()),
+            (AttestationSessionsIden::EndsAt, entity.ends_at.into()),
+        ]
+    }
+
+    async fn insert(&self, entity: Entity) -> Result<AttestationSessions, anyhow::Error> {
+        let (columns, values): (Vec<_>, Vec<_>) = Self::values(entity).into_iter().unzip();
+
+        let mut query = Query::insert();
+        let query = query
+            .into_table(AttestationSessionsIden::Table)
+            .columns(columns)
+            .values_panic(values)
+            .returning_all();
+
+        fetch_one(&self.txn, query).await
+    }
+
+    async fn update(&self, entity: Entity) -> Result<AttestationSessions, anyhow::Error> {
+        let id = entity.id;
+
+        let mut query = Query::update();
+        let query = query
+            .table(AttestationSessionsIden::Table)
+            .values(Self::values(entity))
+            .and_where(Expr::col(AttestationSessionsIden::Id).eq(id.value))
+            .returning_all();
+
+        fetch_one(&self.txn, query).await
+    }
+
+    async fn list_by(
+        &self,
+        column: AttestationSessionsIden,
+        value: i32,
+    ) -> Result<Vec<Entity>, anyhow::Error> {
+        let models = fetch_all::<AttestationSessions>(
+            &self.txn,
+            Query::select()
+                .from(AttestationSessionsIden::Table)
+                .column(Asterisk)
+                .and_where(Expr::col(column).eq(value)),
+        )
+        .await?;
+
+        Ok(models.into_iter().map(Into::into).collect())
+    }
+}
+
+#[async_trait::async_trait]
+impl attestation_session::Repo for PgAttestationSessionRepo {
+    async fn save(&mut self, entity: Entity) -> Result<Entity, anyhow::Error> {
+        let model = if self.find(entity.id).await?.is_some() {
+            self.update(entity).await?
+        } else {
+            self.insert(entity).await?
+        };
+
+        Ok(model.into())
+    }
+
+    async fn delete(&mut self, entity: &Entity) -> Result<(), anyhow::Error> {
+        execute(
+            &self.txn,
+            Query::delete()
+                .from_table(AttestationSessionsIden::Table)
+                .and_where(Expr::col(AttestationSessionsIden::Id).eq(entity.id.value)),
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    async fn find(&self, id: EntityId) -> Result<Option<Entity>, anyhow::Error> {
+        let model = fetch_optional::<AttestationSessions>(
+            &self.txn,
+            Query::select()
+                .from(AttestationSessionsIden::Table)
+                .column(Asterisk)
+                .and_where(Expr::col(AttestationSessionsIden::Id).eq(id.value)),
+        )
+        .await?;
+
+        Ok(model.map(Into::into))
+    }
+
+    async fn list_by_attestations(
+        &self,
+        attestation_ids: HashSet<attestation::EntityId>,
+    ) -> Result<Vec<Entity>, anyhow::Error> {
+        let models = fetch_all::<AttestationSessions>(
+            &self.txn,
+            Query::select()
+                .from(AttestationSessionsIden::Table)
+                .column(Asterisk)
+                .and_where(
+                    Expr::col(AttestationSessionsIden::AttestationId)
+                        .is_in(attestation_ids.into_iter().map(|id| id.value)),
+                ),
+        )
+        .await?;
+
+        Ok(models.into_iter().map(Into::into).collect())
+    }
+
+    async fn list_by_study_group(
+        &self,
+        study_group_id: study_group::EntityId,
+    ) -> Result<Vec<Entity>, anyhow::Error> {
+        self.list_by(AttestationSessionsIden::StudyGroupId, study_group_id.value)
+            .await
+    }
+
+    async fn list_by_room(&self, room_id: room::EntityId) -> Result<Vec<Entity>, anyhow::Error> {
+        self.list_by(AttestationSessionsIden::RoomId, room_id.value)
+            .await
+    }
+}
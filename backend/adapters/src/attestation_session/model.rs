@@ -0,0 +1,29 @@
+use app::attestation_session;
+use sqlx::FromRow;
+use utils::entity::Id;
+
+#[derive(Clone, Debug, FromRow)]
+#[sea_query::enum_def]
+pub struct AttestationSessions {
+    pub id: i32,
+    pub attestation_id: i32,
+    pub study_group_id: i32,
+    pub room_id: Option<i32>,
+    pub date: time::Date,
+    pub starts_at: time::Time,
+    pub ends_at: time::Time,
+}
+
+impl From<AttestationSessions> for attestation_session::Entity {
+    fn from(value: AttestationSessions) -> Self {
+        attestation_session::Entity {
+            id: Id::new(value.id),
+            attestation_id: Id::new(value.attestation_id),
+            study_group_id: Id::new(value.study_group_id),
+            room_id: value.room_id.map(Id::new),
+            date: value.date,
+            starts_at: value.starts_at,
+            ends_at: value.ends_at,
+        }
+    }
+}
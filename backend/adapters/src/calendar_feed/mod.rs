@@ -0,0 +1,103 @@
+mod model;
+
+use app::{
+    calendar_feed::{self, Entity},
+    person,
+};
+use sea_query::{Asterisk, Expr, Query, SimpleExpr};
+use std::sync::Arc;
+use tokio::sync::Mutex;
+
+use crate::{execute, fetch_one, fetch_optional, PgTransaction};
+
+use self::model::{CalendarFeeds, CalendarFeedsIden};
+
+pub struct PgCalendarFeedRepo {
+    pub txn: Arc<Mutex<PgTransaction<'static>>>,
+}
+
+impl PgCalendarFeedRepo {
+    async fn insert(&self, entity: Entity) -> Result<CalendarFeeds, anyhow::Error> {
+        let mut query = Query::insert();
+        let query = query
+            .into_table(CalendarFeedsIden::Table)
+            .columns([CalendarFeedsIden::PersonId, CalendarFeedsIden::TokenHash])
+            .values_panic([entity.person_id.value.into(), entity.token_hash.into()])
+            .returning_all();
+
+        fetch_one(&self.txn, query).await
+    }
+
+    async fn update(&self, entity: Entity) -> Result<CalendarFeeds, anyhow::Error> {
+        let mut query = Query::update();
+        let query = query
+            .table(CalendarFeedsIden::Table)
+            .values([
+                (CalendarFeedsIden::PersonId, entity.person_id.value.into()),
+                (CalendarFeedsIden::TokenHash, entity.token_hash.into()),
+            ])
+            .and_where(Expr::col(CalendarFeedsIden::Id).eq(entity.id.value))
+            .returning_all();
+
+        fetch_one(&self.txn, query).await
+    }
+
+    async fn find(&self, id: calendar_feed::EntityId) -> Result<Option<Entity>, anyhow::Error> {
+        self.find_by(Expr::col(CalendarFeedsIden::Id).eq(id.value))
+            .await
+    }
+
+    async fn find_by(&self, condition: SimpleExpr) -> Result<Option<Entity>, anyhow::Error> {
+        let model = fetch_optional::<CalendarFeeds>(
+            &self.txn,
+            Query::select()
+                .from(CalendarFeedsIden::Table)
+                .column(Asterisk)
+                .and_where(condition),
+        )
+        .await?;
+
+        Ok(model.map(Into::into))
+    }
+}
+
+#[async_trait::async_trait]
+impl calendar_feed::Repo for PgCalendarFeedRepo {
+    async fn save(&mut self, entity: Entity) -> Result<Entity, anyhow::Error> {
+        let model = if self.find(entity.id).await?.is_some() {
+            self.update(entity).await?
+        } else {
+            self.insert(entity).await?
+        };
+
+        Ok(model.into())
+    }
+
+    async fn delete(&mut self, entity: &Entity) -> Result<(), anyhow::Error> {
+        execute(
+            &self.txn,
+            Query::delete()
+                .from_table(CalendarFeedsIden::Table)
+                .and_where(Expr::col(CalendarFeedsIden::Id).eq(entity.id.value)),
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    async fn find_by_person(
+        &self,
+        person_id: person::EntityId,
+    ) -> Result<Option<Entity>, anyhow::Error> {
+        self.find_by(Expr::col(CalendarFeedsIden::PersonId).eq(person_id.value))
+            .await
+    }
+
+    async fn find_by_token_hash(
+        &self,
+        token_hash: String,
+    ) -> Result<Option<Entity>, anyhow::Error> {
+        self.find_by(Expr::col(CalendarFeedsIden::TokenHash).eq(token_hash))
+            .await
+    }
+}
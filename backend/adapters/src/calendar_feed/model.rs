@@ -0,0 +1,21 @@
+use app::calendar_feed;
+use sqlx::FromRow;
+use utils::entity::Id;
+
+#[derive(Clone, Debug, FromRow)]
+#[sea_query::enum_def]
+pub struct CalendarFeeds {
+    pub id: i32,
+    pub person_id: i32,
+    pub token_hash: String,
+}
+
+impl From<CalendarFeeds> for calendar_feed::Entity {
+    fn from(value: CalendarFeeds) -> Self {
+        calendar_feed::Entity {
+            id: Id::new(value.id),
+            person_id: Id::new(value.person_id),
+            token_hash: value.token_hash,
+        }
+    }
+}
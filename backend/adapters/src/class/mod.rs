@@ -2,13 +2,13 @@ mod model;
 
 use app::{
     class::{self, Entity, EntityId},
-    curriculum_module,
+    class_kind, curriculum_module,
 };
 use sea_query::{Asterisk, Expr, Query};
-use std::sync::Arc;
+use std::{collections::HashSet, sync::Arc};
 use tokio::sync::Mutex;
 
-use crate::{fetch_all, fetch_one, fetch_optional, PgTransaction};
+use crate::{execute, fetch_all, fetch_one, fetch_optional, PgTransaction};
 
 use self::model::{Classes, ClassesIden};
 
@@ -21,10 +21,15 @@ impl PgClassRepo {
         let mut query = Query::insert();
         query
             .into_table(ClassesIden::Table)
-            .columns([ClassesIden::CurriculumModuleId, ClassesIden::KindName])
+            .columns([
+                ClassesIden::CurriculumModuleId,
+                ClassesIden::KindName,
+                ClassesIden::DurationInHours,
+            ])
             .values_panic([
                 entity.curriculum_module_id.value.into(),
                 entity.kind_name.value.into(),
+                entity.duration.0.into(),
             ])
             .returning_all();
 
@@ -41,6 +46,7 @@ impl PgClassRepo {
                     entity.curriculum_module_id.value.into(),
                 ),
                 (ClassesIden::KindName, entity.kind_name.value.into()),
+                (ClassesIden::DurationInHours, entity.duration.0.into()),
             ])
             .and_where(Expr::col(ClassesIden::Id).eq(entity.id.value))
             .returning_all();
@@ -67,7 +73,7 @@ impl class::Repo for PgClassRepo {
             .from_table(ClassesIden::Table)
             .and_where(Expr::col(ClassesIden::Id).eq(entity.id.value));
 
-        fetch_one::<()>(&self.txn, &query).await?;
+        execute(&self.txn, &query).await?;
         Ok(())
     }
 
@@ -97,4 +103,36 @@ impl class::Repo for PgClassRepo {
 
         Ok(entities)
     }
+
+    async fn list_by_curriculum_modules(
+        &self,
+        curriculum_modules_ids: HashSet<curriculum_module::EntityId>,
+    ) -> Result<Vec<Entity>, anyhow::Error> {
+        let mut query = Query::select();
+        query.from(ClassesIden::Table).column(Asterisk).and_where(
+            Expr::col(ClassesIden::CurriculumModuleId)
+                .is_in(curriculum_modules_ids.into_iter().map(|id| id.value)),
+        );
+
+        let models = fetch_all::<Classes>(&self.txn, &query).await?;
+        let entities = models.into_iter().map(Into::into).collect();
+
+        Ok(entities)
+    }
+
+    async fn list_by_kind(
+        &self,
+        kind_name: class_kind::EntityId,
+    ) -> Result<Vec<Entity>, anyhow::Error> {
+        let mut query = Query::select();
+        query
+            .from(ClassesIden::Table)
+            .column(Asterisk)
+            .and_where(Expr::col(ClassesIden::KindName).eq(kind_name.value));
+
+        let models = fetch_all::<Classes>(&self.txn, &query).await?;
+        let entities = models.into_iter().map(Into::into).collect();
+
+        Ok(entities)
+    }
 }
@@ -1,4 +1,4 @@
-use app::class;
+use app::{attestation, class};
 use sqlx::FromRow;
 use utils::entity::Id;
 
@@ -8,6 +8,7 @@ pub struct Classes {
     pub id: i32,
     pub curriculum_module_id: i32,
     pub kind_name: String,
+    pub duration_in_hours: i32,
 }
 
 impl From<Classes> for class::Entity {
@@ -16,6 +17,7 @@ impl From<Classes> for class::Entity {
             id: Id::new(value.id),
             curriculum_module_id: Id::new(value.curriculum_module_id),
             kind_name: Id::new(value.kind_name),
+            duration: attestation::Hours(value.duration_in_hours),
         }
     }
 }
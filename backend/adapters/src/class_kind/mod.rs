@@ -5,7 +5,7 @@ use sea_query::{Asterisk, Expr, Query};
 use std::sync::Arc;
 use tokio::sync::Mutex;
 
-use crate::{fetch_one, fetch_optional, PgTransaction};
+use crate::{execute, fetch_one, fetch_optional, PgTransaction};
 
 use self::model::{ClassKinds, ClassKindsIden};
 
@@ -29,7 +29,8 @@ impl PgClassKindRepo {
         let mut query = Query::update();
         let query = query
             .table(ClassKindsIden::Table)
-            .values([(ClassKindsIden::Name, entity.name.value.into())])
+            .values([(ClassKindsIden::Name, entity.name.value.clone().into())])
+            .and_where(Expr::col(ClassKindsIden::Name).eq(entity.name.value))
             .returning_all();
 
         fetch_one(&self.txn, query).await
@@ -49,11 +50,11 @@ impl class_kind::Repo for PgClassKindRepo {
     }
 
     async fn delete(&mut self, entity: &Entity) -> Result<(), anyhow::Error> {
-        fetch_one::<()>(
+        execute(
             &self.txn,
             Query::delete()
                 .from_table(ClassKindsIden::Table)
-                .and_where(Expr::col(ClassKindsIden::Table).eq(entity.name.value.clone())),
+                .and_where(Expr::col(ClassKindsIden::Name).eq(entity.name.value.clone())),
         )
         .await?;
 
@@ -66,7 +67,7 @@ impl class_kind::Repo for PgClassKindRepo {
             Query::select()
                 .from(ClassKindsIden::Table)
                 .column(Asterisk)
-                .and_where(Expr::col(ClassKindsIden::Table).eq(id.value)),
+                .and_where(Expr::col(ClassKindsIden::Name).eq(id.value)),
         )
         .await?;
 
@@ -7,7 +7,12 @@ use utils::di::{Module, Provide};
 // pub use crate::adapters::tokens::{JwtKeys, RefreshTokenLength};
 pub use crate::access_token::JwtKeys;
 pub use crate::hasher::Argon2Params;
+pub use crate::mail::{MailSettings, MailTransport, SmtpSecurity, SmtpSettings};
 pub use crate::refresh_token::RefreshTokenLength;
+pub use app::account_token::{EmailVerificationTTL, PasswordResetTTL};
+pub use app::curriculum::SemesterHoursBounds;
+pub use app::login_attempt::LoginThrottling;
+pub use app::mail::LinkBaseUrl;
 pub use app::token::AccessTokenTTL;
 pub use app::user_session::{SessionTTL, SessionsMaxNumber};
 
@@ -28,6 +33,12 @@ pub trait ConfigModule:
     + Provide<AccessTokenTTL>
     + Provide<SessionTTL>
     + Provide<SessionsMaxNumber>
+    + Provide<LoginThrottling>
+    + Provide<SemesterHoursBounds>
+    + Provide<EmailVerificationTTL>
+    + Provide<PasswordResetTTL>
+    + Provide<LinkBaseUrl>
+    + Provide<Arc<MailSettings>>
     + Provide<PgHost>
     + Provide<PgPort>
     + Provide<PgUserName>
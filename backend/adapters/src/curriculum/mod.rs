@@ -1,15 +1,19 @@
 mod model;
 
-use app::curriculum::{self, Entity, EntityId};
-use sea_query::{Asterisk, Expr, Query};
-use std::sync::Arc;
+use app::{
+    curriculum::{self, Entity, EntityId},
+    subdivision,
+};
+use sea_query::{Asterisk, Expr, OnConflict, Order, Query};
+use std::{collections::HashSet, sync::Arc};
 use tokio::sync::Mutex;
 
 use crate::{
-    curriculum::model::CurriculumsIden, fetch_all, fetch_one, fetch_optional, PgTransaction,
+    curriculum::model::CurriculumsIden, execute, fetch_all, fetch_one, fetch_optional, PgEnum,
+    PgTransaction,
 };
 
-use self::model::Curriculums;
+use self::model::{Curriculums, PgCurriculumStatus};
 
 pub struct PgCurriculumRepo {
     pub txn: Arc<Mutex<PgTransaction<'static>>>,
@@ -20,8 +24,18 @@ impl PgCurriculumRepo {
         let mut query = Query::insert();
         query
             .into_table(CurriculumsIden::Table)
-            .columns([CurriculumsIden::Name])
-            .values_panic([entity.name.into()])
+            .columns([
+                CurriculumsIden::Name,
+                CurriculumsIden::Version,
+                CurriculumsIden::Status,
+                CurriculumsIden::DepartmentId,
+            ])
+            .values_panic([
+                entity.name.into(),
+                entity.version.into(),
+                PgCurriculumStatus::from(entity.status).into_expr(),
+                entity.department_id.map(|v| v.value).into(),
+            ])
             .returning_all();
 
         fetch_one::<Curriculums>(&self.txn, &query).await
@@ -31,7 +45,18 @@ impl PgCurriculumRepo {
         let mut query = Query::update();
         query
             .table(CurriculumsIden::Table)
-            .values([(CurriculumsIden::Name, entity.name.into())])
+            .values([
+                (CurriculumsIden::Name, entity.name.into()),
+                (CurriculumsIden::Version, entity.version.into()),
+                (
+                    CurriculumsIden::Status,
+                    PgCurriculumStatus::from(entity.status).into_expr(),
+                ),
+                (
+                    CurriculumsIden::DepartmentId,
+                    entity.department_id.map(|v| v.value).into(),
+                ),
+            ])
             .and_where(Expr::col(CurriculumsIden::Id).eq(entity.id.value))
             .returning_all();
 
@@ -51,13 +76,43 @@ impl curriculum::Repo for PgCurriculumRepo {
         Ok(model.into())
     }
 
+    async fn insert_version(&mut self, entity: Entity) -> Result<Option<Entity>, anyhow::Error> {
+        let mut query = Query::insert();
+        query
+            .into_table(CurriculumsIden::Table)
+            .columns([
+                CurriculumsIden::Name,
+                CurriculumsIden::Version,
+                CurriculumsIden::Status,
+                CurriculumsIden::DepartmentId,
+            ])
+            .values_panic([
+                entity.name.into(),
+                entity.version.into(),
+                PgCurriculumStatus::from(entity.status).into_expr(),
+                entity.department_id.map(|v| v.value).into(),
+            ])
+            .on_conflict(
+                OnConflict::columns([CurriculumsIden::Name, CurriculumsIden::Version])
+                    .do_nothing()
+                    .to_owned(),
+            )
+            .returning_all();
+
+        let entity = fetch_optional::<Curriculums>(&self.txn, &query)
+            .await?
+            .map(Into::into);
+
+        Ok(entity)
+    }
+
     async fn delete(&mut self, entity: &Entity) -> Result<(), anyhow::Error> {
         let mut query = Query::delete();
         query
             .from_table(CurriculumsIden::Table)
             .and_where(Expr::col(CurriculumsIden::Id).eq(entity.id.value));
 
-        fetch_one::<()>(&self.txn, &query).await?;
+        execute(&self.txn, &query).await?;
         Ok(())
     }
 
@@ -74,6 +129,24 @@ impl curriculum::Repo for PgCurriculumRepo {
 
         Ok(entity)
     }
+
+    async fn find_many(&self, ids: HashSet<EntityId>) -> Result<Vec<Entity>, anyhow::Error> {
+        let mut query = Query::select();
+        query
+            .from(CurriculumsIden::Table)
+            .column(Asterisk)
+            .and_where(Expr::col(CurriculumsIden::Id).is_in(ids.into_iter().map(|id| id.value)))
+            .order_by(CurriculumsIden::Id, Order::Asc);
+
+        let entities = fetch_all::<Curriculums>(&self.txn, &query)
+            .await?
+            .into_iter()
+            .map(Into::into)
+            .collect();
+
+        Ok(entities)
+    }
+
     async fn list(&self) -> Result<Vec<Entity>, anyhow::Error> {
         let mut query = Query::select();
         query.from(CurriculumsIden::Table).column(Asterisk);
@@ -87,17 +160,39 @@ impl curriculum::Repo for PgCurriculumRepo {
         Ok(entities)
     }
 
-    async fn find_by_name(&self, name: String) -> Result<Option<Entity>, anyhow::Error> {
+    async fn list_by_name(&self, name: String) -> Result<Vec<Entity>, anyhow::Error> {
         let mut query = Query::select();
         query
             .from(CurriculumsIden::Table)
             .column(Asterisk)
-            .and_where(Expr::col(CurriculumsIden::Name).eq(name));
+            .and_where(Expr::col(CurriculumsIden::Name).eq(name))
+            .order_by(CurriculumsIden::Version, Order::Asc);
 
-        let entity = fetch_optional::<Curriculums>(&self.txn, &query)
+        let entities = fetch_all::<Curriculums>(&self.txn, &query)
             .await?
-            .map(Into::into);
+            .into_iter()
+            .map(Into::into)
+            .collect();
 
-        Ok(entity)
+        Ok(entities)
+    }
+
+    async fn list_by_department_id(
+        &self,
+        department_id: subdivision::EntityId,
+    ) -> Result<Vec<Entity>, anyhow::Error> {
+        let mut query = Query::select();
+        query
+            .from(CurriculumsIden::Table)
+            .column(Asterisk)
+            .and_where(Expr::col(CurriculumsIden::DepartmentId).eq(department_id.value));
+
+        let entities = fetch_all::<Curriculums>(&self.txn, &query)
+            .await?
+            .into_iter()
+            .map(Into::into)
+            .collect();
+
+        Ok(entities)
     }
 }
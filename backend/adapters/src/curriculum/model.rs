@@ -1,12 +1,19 @@
-use app::curriculum;
+use std::fmt::Display;
+
+use app::curriculum::{self, CurriculumStatus};
 use sqlx::FromRow;
 use utils::entity::Id;
 
+use crate::PgEnum;
+
 #[derive(Debug, Clone, FromRow)]
 #[sea_query::enum_def]
 pub struct Curriculums {
     pub id: i32,
     pub name: String,
+    pub version: i32,
+    pub status: PgCurriculumStatus,
+    pub department_id: Option<i32>,
 }
 
 impl From<Curriculums> for curriculum::Entity {
@@ -14,6 +21,56 @@ impl From<Curriculums> for curriculum::Entity {
         curriculum::Entity {
             id: Id::new(value.id),
             name: value.name,
+            version: value.version,
+            status: value.status.into(),
+            department_id: value.department_id.map(Id::new),
         }
     }
 }
+
+#[derive(Debug, Clone, sqlx::Type)]
+#[sqlx(type_name = "curriculum_status")]
+#[sqlx(rename_all = "snake_case")]
+pub enum PgCurriculumStatus {
+    Draft,
+    Published,
+    Archived,
+}
+
+impl From<CurriculumStatus> for PgCurriculumStatus {
+    fn from(value: CurriculumStatus) -> Self {
+        match value {
+            CurriculumStatus::Draft => PgCurriculumStatus::Draft,
+            CurriculumStatus::Published => PgCurriculumStatus::Published,
+            CurriculumStatus::Archived => PgCurriculumStatus::Archived,
+        }
+    }
+}
+
+impl From<PgCurriculumStatus> for CurriculumStatus {
+    fn from(value: PgCurriculumStatus) -> Self {
+        match value {
+            PgCurriculumStatus::Draft => CurriculumStatus::Draft,
+            PgCurriculumStatus::Published => CurriculumStatus::Published,
+            PgCurriculumStatus::Archived => CurriculumStatus::Archived,
+        }
+    }
+}
+
+impl Display for PgCurriculumStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::Draft => "draft",
+                Self::Published => "published",
+                Self::Archived => "archived",
+            }
+        )
+    }
+}
+
+impl PgEnum for PgCurriculumStatus {
+    const TYPE_NAME: &'static str = "curriculum_status";
+}
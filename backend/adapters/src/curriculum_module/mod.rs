@@ -6,10 +6,10 @@ use app::{
     discipline,
 };
 use sea_query::{Asterisk, Expr, Query};
-use std::sync::Arc;
+use std::{collections::HashSet, sync::Arc};
 use tokio::sync::Mutex;
 
-use crate::{fetch_all, fetch_one, fetch_optional, PgTransaction};
+use crate::{execute, fetch_all, fetch_one, fetch_optional, PgTransaction};
 
 use self::model::{CurriculumModules, CurriculumModulesIden};
 
@@ -77,7 +77,7 @@ impl curriculum_module::Repo for PgCurriculumModuleRepo {
             .from_table(CurriculumModulesIden::Table)
             .and_where(Expr::col(CurriculumModulesIden::Id).eq(entity.id.value));
 
-        fetch_one::<()>(&self.txn, &query).await?;
+        execute(&self.txn, &query).await?;
         Ok(())
     }
 
@@ -123,4 +123,23 @@ impl curriculum_module::Repo for PgCurriculumModuleRepo {
 
         Ok(entities)
     }
+
+    async fn list_by_curriculums(
+        &self,
+        curriculums_ids: HashSet<curriculum::EntityId>,
+    ) -> Result<Vec<Entity>, anyhow::Error> {
+        let mut query = Query::select();
+        query
+            .from(CurriculumModulesIden::Table)
+            .column(Asterisk)
+            .and_where(
+                Expr::col(CurriculumModulesIden::CurriculumId)
+                    .is_in(curriculums_ids.into_iter().map(|id| id.value)),
+            );
+
+        let models = fetch_all::<CurriculumModules>(&self.txn, &query).await?;
+        let entities = models.into_iter().map(Into::into).collect();
+
+        Ok(entities)
+    }
 }
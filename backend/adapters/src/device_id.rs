@@ -0,0 +1,12 @@
+use app::user_session::DeviceIdGenerator;
+
+/// Random ids are enough, the device id only tells the sessions of a user apart
+const DEVICE_ID_LENGTH: usize = 32;
+
+pub struct NanoIdDeviceIdGenerator;
+
+impl DeviceIdGenerator for NanoIdDeviceIdGenerator {
+    fn generate(&self) -> String {
+        nanoid::nanoid!(DEVICE_ID_LENGTH)
+    }
+}
@@ -5,10 +5,10 @@ use app::{
     subdivision,
 };
 use sea_query::{Asterisk, Expr, Query};
-use std::sync::Arc;
+use std::{collections::HashSet, sync::Arc};
 use tokio::sync::Mutex;
 
-use crate::{fetch_all, fetch_one, fetch_optional, PgTransaction};
+use crate::{execute, fetch_all, fetch_one, fetch_optional, PgTransaction};
 
 use self::model::{Disciplines, DisciplinesIden};
 
@@ -64,7 +64,7 @@ impl discipline::Repo for PgDisciplineRepo {
             .from_table(DisciplinesIden::Table)
             .and_where(Expr::col(DisciplinesIden::Id).eq(entity.id.value));
 
-        fetch_one::<()>(&self.txn, &query).await?;
+        execute(&self.txn, &query).await?;
         Ok(())
     }
 
@@ -79,6 +79,19 @@ impl discipline::Repo for PgDisciplineRepo {
         Ok(model.map(Into::into))
     }
 
+    async fn find_many(&self, ids: HashSet<EntityId>) -> Result<Vec<Entity>, anyhow::Error> {
+        let mut query = Query::select();
+        query
+            .from(DisciplinesIden::Table)
+            .column(Asterisk)
+            .and_where(Expr::col(DisciplinesIden::Id).is_in(ids.into_iter().map(|id| id.value)));
+
+        let models = fetch_all::<Disciplines>(&self.txn, &query).await?;
+        let entities = models.into_iter().map(Into::into).collect();
+
+        Ok(entities)
+    }
+
     async fn find_by_name(&self, name: String) -> Result<Option<Entity>, anyhow::Error> {
         let mut query = Query::select();
         query
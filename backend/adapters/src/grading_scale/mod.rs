@@ -0,0 +1,124 @@
+mod model;
+
+use app::{
+    grading_scale::{self, Entity, EntityId},
+    university,
+};
+use sea_query::{Asterisk, Expr, Query, SimpleExpr};
+use std::sync::Arc;
+use tokio::sync::Mutex;
+
+use crate::{execute, fetch_one, fetch_optional, PgEnum, PgTransaction};
+
+use self::model::{GradingScales, GradingScalesIden, PgGradeNotation};
+
+pub struct PgGradingScaleRepo {
+    pub txn: Arc<Mutex<PgTransaction<'static>>>,
+}
+
+impl PgGradingScaleRepo {
+    fn values(entity: Entity) -> [(GradingScalesIden, SimpleExpr); 6] {
+        let thresholds = entity.scale.thresholds;
+
+        [
+            (
+                GradingScalesIden::UniversityId,
+                entity.university_id.value.into(),
+            ),
+            (GradingScalesIden::PassScore, thresholds.pass.into()),
+            (
+                GradingScalesIden::SatisfactoryScore,
+                thresholds.satisfactory.into(),
+            ),
+            (GradingScalesIden::GoodScore, thresholds.good.into()),
+            (
+                GradingScalesIden::ExcellentScore,
+                thresholds.excellent.into(),
+            ),
+            (
+                GradingScalesIden::Notation,
+                PgGradeNotation::from(entity.scale.notation).into_expr(),
+            ),
+        ]
+    }
+
+    async fn insert(&self, entity: Entity) -> Result<GradingScales, anyhow::Error> {
+        let (columns, values): (Vec<_>, Vec<_>) = Self::values(entity).into_iter().unzip();
+
+        let mut query = Query::insert();
+        let query = query
+            .into_table(GradingScalesIden::Table)
+            .columns(columns)
+            .values_panic(values)
+            .returning_all();
+
+        fetch_one(&self.txn, query).await
+    }
+
+    async fn update(&self, entity: Entity) -> Result<GradingScales, anyhow::Error> {
+        let id = entity.id;
+
+        let mut query = Query::update();
+        let query = query
+            .table(GradingScalesIden::Table)
+            .values(Self::values(entity))
+            .and_where(Expr::col(GradingScalesIden::Id).eq(id.value))
+            .returning_all();
+
+        fetch_one(&self.txn, query).await
+    }
+}
+
+#[async_trait::async_trait]
+impl grading_scale::Repo for PgGradingScaleRepo {
+    async fn save(&mut self, entity: Entity) -> Result<Entity, anyhow::Error> {
+        let model = if self.find(entity.id).await?.is_some() {
+            self.update(entity).await?
+        } else {
+            self.insert(entity).await?
+        };
+
+        Ok(model.into())
+    }
+
+    async fn delete(&mut self, entity: &Entity) -> Result<(), anyhow::Error> {
+        execute(
+            &self.txn,
+            Query::delete()
+                .from_table(GradingScalesIden::Table)
+                .and_where(Expr::col(GradingScalesIden::Id).eq(entity.id.value)),
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    async fn find(&self, id: EntityId) -> Result<Option<Entity>, anyhow::Error> {
+        let model = fetch_optional::<GradingScales>(
+            &self.txn,
+            Query::select()
+                .from(GradingScalesIden::Table)
+                .column(Asterisk)
+                .and_where(Expr::col(GradingScalesIden::Id).eq(id.value)),
+        )
+        .await?;
+
+        Ok(model.map(Into::into))
+    }
+
+    async fn find_by_university(
+        &self,
+        university_id: university::EntityId,
+    ) -> Result<Option<Entity>, anyhow::Error> {
+        let model = fetch_optional::<GradingScales>(
+            &self.txn,
+            Query::select()
+                .from(GradingScalesIden::Table)
+                .column(Asterisk)
+                .and_where(Expr::col(GradingScalesIden::UniversityId).eq(university_id.value)),
+        )
+        .await?;
+
+        Ok(model.map(Into::into))
+    }
+}
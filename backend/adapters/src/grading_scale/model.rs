@@ -0,0 +1,80 @@
+use std::fmt::Display;
+
+use app::grading_scale::{self, Notation, Scale, Thresholds};
+use sqlx::FromRow;
+use utils::entity::Id;
+
+use crate::PgEnum;
+
+#[derive(Clone, Debug, FromRow)]
+#[sea_query::enum_def]
+pub struct GradingScales {
+    pub id: i32,
+    pub university_id: i32,
+    pub pass_score: i32,
+    pub satisfactory_score: i32,
+    pub good_score: i32,
+    pub excellent_score: i32,
+    pub notation: PgGradeNotation,
+}
+
+impl From<GradingScales> for grading_scale::Entity {
+    fn from(value: GradingScales) -> Self {
+        grading_scale::Entity {
+            id: Id::new(value.id),
+            university_id: Id::new(value.university_id),
+            scale: Scale {
+                thresholds: Thresholds {
+                    pass: value.pass_score,
+                    satisfactory: value.satisfactory_score,
+                    good: value.good_score,
+                    excellent: value.excellent_score,
+                },
+                notation: value.notation.into(),
+            },
+        }
+    }
+}
+
+#[derive(Debug, Clone, sqlx::Type)]
+#[sqlx(type_name = "grade_notation")]
+#[sqlx(rename_all = "snake_case")]
+pub enum PgGradeNotation {
+    Numeric,
+    Letter,
+}
+
+impl From<Notation> for PgGradeNotation {
+    fn from(value: Notation) -> Self {
+        match value {
+            Notation::Numeric => PgGradeNotation::Numeric,
+            Notation::Letter => PgGradeNotation::Letter,
+        }
+    }
+}
+
+impl From<PgGradeNotation> for Notation {
+    fn from(value: PgGradeNotation) -> Self {
+        match value {
+            PgGradeNotation::Numeric => Notation::Numeric,
+            PgGradeNotation::Letter => Notation::Letter,
+        }
+    }
+}
+
+impl Display for PgGradeNotation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::Numeric => "numeric",
+                Self::Letter => "letter",
+            }
+        )
+    }
+}
+
+impl PgEnum for PgGradeNotation {
+    const TYPE_NAME: &'static str = "grade_notation";
+}
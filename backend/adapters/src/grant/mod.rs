@@ -0,0 +1,164 @@
+mod models;
+
+use app::{
+    grant::{self, Entity, EntityId, Scope},
+    user,
+};
+use sea_query::{Asterisk, Condition, Expr, Query};
+use std::sync::Arc;
+use tokio::sync::Mutex;
+
+use crate::{execute, fetch_all, fetch_one, fetch_optional, user::PgRole, PgEnum, PgTransaction};
+
+use self::models::{ScopeColumns, UserGrants, UserGrantsIden};
+
+pub struct PgGrantRepo {
+    pub txn: Arc<Mutex<PgTransaction<'static>>>,
+}
+
+impl PgGrantRepo {
+    async fn insert(&self, entity: Entity) -> Result<UserGrants, anyhow::Error> {
+        let scope = ScopeColumns::from(entity.scope);
+
+        let mut query = Query::insert();
+        let query = query
+            .into_table(UserGrantsIden::Table)
+            .columns([
+                UserGrantsIden::UserId,
+                UserGrantsIden::Role,
+                UserGrantsIden::UniversityId,
+                UserGrantsIden::SubdivisionId,
+                UserGrantsIden::StudyGroupId,
+            ])
+            .values_panic([
+                entity.user_id.value.into(),
+                PgRole::from(entity.role).into_expr(),
+                scope.university_id.into(),
+                scope.subdivision_id.into(),
+                scope.study_group_id.into(),
+            ])
+            .returning_all();
+
+        fetch_one(&self.txn, query).await
+    }
+
+    async fn update(&self, entity: Entity) -> Result<UserGrants, anyhow::Error> {
+        let scope = ScopeColumns::from(entity.scope);
+
+        let mut query = Query::update();
+        let query = query
+            .table(UserGrantsIden::Table)
+            .values([
+                (UserGrantsIden::UserId, entity.user_id.value.into()),
+                (UserGrantsIden::Role, PgRole::from(entity.role).into_expr()),
+                (UserGrantsIden::UniversityId, scope.university_id.into()),
+                (UserGrantsIden::SubdivisionId, scope.subdivision_id.into()),
+                (UserGrantsIden::StudyGroupId, scope.study_group_id.into()),
+            ])
+            .and_where(Expr::col(UserGrantsIden::Id).eq(entity.id.value))
+            .returning_all();
+
+        fetch_one(&self.txn, query).await
+    }
+
+    fn scope_cond(scope: Scope) -> Condition {
+        let scope = ScopeColumns::from(scope);
+
+        let column_cond = |column: UserGrantsIden, value: Option<i32>| match value {
+            Some(value) => Expr::col(column).eq(value),
+            None => Expr::col(column).is_null(),
+        };
+
+        Condition::all()
+            .add(column_cond(
+                UserGrantsIden::UniversityId,
+                scope.university_id,
+            ))
+            .add(column_cond(
+                UserGrantsIden::SubdivisionId,
+                scope.subdivision_id,
+            ))
+            .add(column_cond(
+                UserGrantsIden::StudyGroupId,
+                scope.study_group_id,
+            ))
+    }
+}
+
+#[async_trait::async_trait]
+impl grant::Repo for PgGrantRepo {
+    async fn save(&mut self, entity: Entity) -> Result<Entity, anyhow::Error> {
+        let model = if self.find(entity.id).await?.is_some() {
+            self.update(entity).await?
+        } else {
+            self.insert(entity).await?
+        };
+
+        Ok(model.into())
+    }
+
+    async fn delete(&mut self, entity: &Entity) -> Result<(), anyhow::Error> {
+        execute(
+            &self.txn,
+            Query::delete()
+                .from_table(UserGrantsIden::Table)
+                .and_where(Expr::col(UserGrantsIden::Id).eq(entity.id.value)),
+        )
+        .await
+    }
+
+    async fn delete_by_scope(&mut self, scope: Scope) -> Result<(), anyhow::Error> {
+        execute(
+            &self.txn,
+            Query::delete()
+                .from_table(UserGrantsIden::Table)
+                .cond_where(Self::scope_cond(scope)),
+        )
+        .await
+    }
+
+    async fn find(&self, id: EntityId) -> Result<Option<Entity>, anyhow::Error> {
+        let model = fetch_optional::<UserGrants>(
+            &self.txn,
+            Query::select()
+                .from(UserGrantsIden::Table)
+                .column(Asterisk)
+                .and_where(Expr::col(UserGrantsIden::Id).eq(id.value)),
+        )
+        .await?;
+
+        Ok(model.map(Into::into))
+    }
+
+    async fn list_by_user(&self, user_id: user::EntityId) -> Result<Vec<Entity>, anyhow::Error> {
+        let entities = fetch_all::<UserGrants>(
+            &self.txn,
+            Query::select()
+                .from(UserGrantsIden::Table)
+                .column(Asterisk)
+                .and_where(Expr::col(UserGrantsIden::UserId).eq(user_id.value)),
+        )
+        .await?
+        .into_iter()
+        .map(Into::into)
+        .collect();
+
+        Ok(entities)
+    }
+
+    async fn list_by_scope(&self, scope: Scope) -> Result<Vec<Entity>, anyhow::Error> {
+        let entities = fetch_all::<UserGrants>(
+            &self.txn,
+            Query::select()
+                .from(UserGrantsIden::Table)
+                .column(Asterisk)
+                .cond_where(Self::scope_cond(scope)),
+        )
+        .await?
+        .into_iter()
+        .map(Into::into)
+        .collect();
+
+        Ok(entities)
+    }
+}
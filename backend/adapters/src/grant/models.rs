@@ -0,0 +1,63 @@
+use app::grant::{self, Scope};
+use sqlx::FromRow;
+use utils::entity::Id;
+
+use crate::user::PgRole;
+
+#[derive(Clone, Debug, FromRow)]
+#[sea_query::enum_def]
+pub struct UserGrants {
+    pub id: i32,
+    pub user_id: i32,
+    pub role: PgRole,
+    pub university_id: Option<i32>,
+    pub subdivision_id: Option<i32>,
+    pub study_group_id: Option<i32>,
+}
+
+impl From<UserGrants> for grant::Entity {
+    fn from(value: UserGrants) -> Self {
+        let scope = match (
+            value.university_id,
+            value.subdivision_id,
+            value.study_group_id,
+        ) {
+            (Some(id), _, _) => Scope::University(Id::new(id)),
+            (_, Some(id), _) => Scope::Subdivision(Id::new(id)),
+            (_, _, Some(id)) => Scope::StudyGroup(Id::new(id)),
+            (None, None, None) => Scope::Global,
+        };
+
+        grant::Entity {
+            id: Id::new(value.id),
+            user_id: Id::new(value.user_id),
+            role: value.role.into(),
+            scope,
+        }
+    }
+}
+
+pub struct ScopeColumns {
+    pub university_id: Option<i32>,
+    pub subdivision_id: Option<i32>,
+    pub study_group_id: Option<i32>,
+}
+
+impl From<Scope> for ScopeColumns {
+    fn from(value: Scope) -> Self {
+        let mut columns = ScopeColumns {
+            university_id: None,
+            subdivision_id: None,
+            study_group_id: None,
+        };
+
+        match value {
+            Scope::Global => (),
+            Scope::University(id) => columns.university_id = Some(id.value),
+            Scope::Subdivision(id) => columns.subdivision_id = Some(id.value),
+            Scope::StudyGroup(id) => columns.study_group_id = Some(id.value),
+        }
+
+        columns
+    }
+}
@@ -2,36 +2,56 @@
 
 use std::sync::Arc;
 
-use sea_query::PostgresQueryBuilder;
+use app::paging::SortOrder;
+use sea_query::{Alias, Expr, LikeExpr, Order, PostgresQueryBuilder, SimpleExpr};
 use sea_query_binder::SqlxBinder;
 use sqlx::{postgres::PgRow, FromRow, PgPool};
 use tokio::sync::Mutex;
 
+mod academic_year;
 mod access_token;
+mod account_token;
+mod account_token_engine;
 mod attestation;
+mod attestation_session;
+mod calendar_feed;
 mod class;
 mod class_kind;
 mod curriculum;
 mod curriculum_module;
+mod device_id;
 mod discipline;
+mod grading_scale;
+mod grant;
 mod hasher;
+mod load_limit;
+mod login_attempt;
+mod mail;
 mod passport;
 mod person;
 mod refresh_token;
+mod room;
+mod schedule;
 mod student;
+mod student_order;
 mod study_group;
 mod subdivision;
 mod tag;
 mod teacher;
+mod timetable_draft;
 mod university;
 mod user;
 mod user_session;
 
 pub mod config;
+pub mod memory;
+pub mod migrations;
 mod pg;
+pub mod spreadsheet;
 mod transaction_module;
 
 use config::ConfigModule;
+use login_attempt::LoginAttemptCounters;
 use pg::init_pg_conn_pool;
 pub use transaction_module::TransactionModule;
 
@@ -41,13 +61,23 @@ type PgTransaction<'c> = sqlx::Transaction<'c, sqlx::Postgres>;
 pub struct AdaptersModule<C> {
     pub config: C,
     pub(crate) conn: PgPool,
+    pub(crate) login_attempts: LoginAttemptCounters,
 }
 
 impl<C: ConfigModule> AdaptersModule<C> {
     pub async fn new(config: C) -> Result<Self, anyhow::Error> {
         let conn = init_pg_conn_pool(&config).await?;
 
-        Ok(Self { config, conn })
+        Ok(Self {
+            config,
+            conn,
+            login_attempts: Arc::default(),
+        })
+    }
+
+    /// Brings the database schema up to date, fails if the database was migrated by a newer version
+    pub async fn migrate(&self) -> Result<(), anyhow::Error> {
+        migrations::run(&self.conn).await
     }
 
     pub async fn begin_transaction_scope(&self) -> Result<TransactionModule<C>, anyhow::Error> {
@@ -55,6 +85,7 @@ impl<C: ConfigModule> AdaptersModule<C> {
         let txn_module = TransactionModule {
             txn,
             config: self.config.clone(),
+            login_attempts: Arc::clone(&self.login_attempts),
         };
 
         Ok(txn_module)
@@ -85,6 +116,18 @@ async fn fetch_all<M: for<'r> FromRow<'r, PgRow> + Send + Unpin>(
     Ok(models)
 }
 
+async fn execute(
+    txn: &Arc<Mutex<PgTransaction<'static>>>,
+    query: &(impl SqlxBinder + Send),
+) -> Result<(), anyhow::Error> {
+    let (sql, args) = query.build_sqlx(PostgresQueryBuilder);
+    sqlx::query_with(&sql, args)
+        .execute(txn.lock().await.as_mut())
+        .await?;
+
+    Ok(())
+}
+
 async fn fetch_optional<M: for<'r> FromRow<'r, PgRow> + Send + Unpin>(
     txn: &Arc<Mutex<PgTransaction<'static>>>,
     query: &(impl SqlxBinder + Send),
@@ -96,3 +139,50 @@ async fn fetch_optional<M: for<'r> FromRow<'r, PgRow> + Send + Unpin>(
 
     Ok(models)
 }
+
+async fn fetch_count(
+    txn: &Arc<Mutex<PgTransaction<'static>>>,
+    query: &(impl SqlxBinder + Send),
+) -> Result<u64, anyhow::Error> {
+    let (sql, args) = query.build_sqlx(PostgresQueryBuilder);
+    let count = sqlx::query_scalar_with::<_, i64, _>(&sql, args)
+        .fetch_one(txn.lock().await.as_mut())
+        .await?;
+
+    Ok(count.try_into()?)
+}
+
+fn order_of(order: SortOrder) -> Order {
+    match order {
+        SortOrder::Asc => Order::Asc,
+        SortOrder::Desc => Order::Desc,
+    }
+}
+
+/// Matches strings starting with `prefix`, wildcards in the prefix itself are escaped with
+/// a backslash, postgres default escape character for `like` patterns
+fn prefix_pattern(prefix: &str) -> LikeExpr {
+    let mut pattern = String::with_capacity(prefix.len() + 1);
+    for c in prefix.chars() {
+        if matches!(c, '\\' | '%' | '_') {
+            pattern.push('\\');
+        }
+        pattern.push(c);
+    }
+    pattern.push('%');
+
+    LikeExpr::new(pattern)
+}
+
+/// Postgres does not cast text parameters to enum types implicitly,
+/// so enum values are bound with an explicit cast to their type
+trait PgEnum: std::fmt::Display {
+    const TYPE_NAME: &'static str;
+
+    fn into_expr(self) -> SimpleExpr
+    where
+        Self: Sized,
+    {
+        Expr::val(self.to_string()).as_enum(Alias::new(Self::TYPE_NAME))
+    }
+}
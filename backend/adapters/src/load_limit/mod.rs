@@ -0,0 +1,116 @@
+mod model;
+
+use app::{
+    load_limit::{self, Entity, EntityId},
+    university,
+};
+use sea_query::{Asterisk, Expr, Query, SimpleExpr};
+use std::sync::Arc;
+use tokio::sync::Mutex;
+
+use crate::{
+    execute, fetch_all, fetch_one, fetch_optional, teacher::PgTeacherKind, PgEnum, PgTransaction,
+};
+
+use self::model::{LoadLimits, LoadLimitsIden};
+
+pub struct PgLoadLimitRepo {
+    pub txn: Arc<Mutex<PgTransaction<'static>>>,
+}
+
+impl PgLoadLimitRepo {
+    fn values(entity: Entity) -> [(LoadLimitsIden, SimpleExpr); 4] {
+        [
+            (
+                LoadLimitsIden::UniversityId,
+                entity.university_id.value.into(),
+            ),
+            (
+                LoadLimitsIden::TeacherKind,
+                PgTeacherKind::from(entity.teacher_kind).into_expr(),
+            ),
+            (LoadLimitsIden::MinHours, entity.min_hours.0.into()),
+            (LoadLimitsIden::MaxHours, entity.max_hours.0.into()),
+        ]
+    }
+
+    async fn insert(&self, entity: Entity) -> Result<LoadLimits, anyhow::Error> {
+        let (columns, values): (Vec<_>, Vec<_>) = Self::values(entity).into_iter().unzip();
+
+        let mut query = Query::insert();
+        let query = query
+            .into_table(LoadLimitsIden::Table)
+            .columns(columns)
+            .values_panic(values)
+            .returning_all();
+
+        fetch_one(&self.txn, query).await
+    }
+
+    async fn update(&self, entity: Entity) -> Result<LoadLimits, anyhow::Error> {
+        let id = entity.id;
+
+        let mut query = Query::update();
+        let query = query
+            .table(LoadLimitsIden::Table)
+            .values(Self::values(entity))
+            .and_where(Expr::col(LoadLimitsIden::Id).eq(id.value))
+            .returning_all();
+
+        fetch_one(&self.txn, query).await
+    }
+}
+
+#[async_trait::async_trait]
+impl load_limit::Repo for PgLoadLimitRepo {
+    async fn save(&mut self, entity: Entity) -> Result<Entity, anyhow::Error> {
+        let model = if self.find(entity.id).await?.is_some() {
+            self.update(entity).await?
+        } else {
+            self.insert(entity).await?
+        };
+
+        Ok(model.into())
+    }
+
+    async fn delete(&mut self, entity: &Entity) -> Result<(), anyhow::Error> {
+        execute(
+            &self.txn,
+            Query::delete()
+                .from_table(LoadLimitsIden::Table)
+                .and_where(Expr::col(LoadLimitsIden::Id).eq(entity.id.value)),
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    async fn find(&self, id: EntityId) -> Result<Option<Entity>, anyhow::Error> {
+        let model = fetch_optional::<LoadLimits>(
+            &self.txn,
+            Query::select()
+                .from(LoadLimitsIden::Table)
+                .column(Asterisk)
+                .and_where(Expr::col(LoadLimitsIden::Id).eq(id.value)),
+        )
+        .await?;
+
+        Ok(model.map(Into::into))
+    }
+
+    async fn list_by_university(
+        &self,
+        university_id: university::EntityId,
+    ) -> Result<Vec<Entity>, anyhow::Error> {
+        let models = fetch_all::<LoadLimits>(
+            &self.txn,
+            Query::select()
+                .from(LoadLimitsIden::Table)
+                .column(Asterisk)
+                .and_where(Expr::col(LoadLimitsIden::UniversityId).eq(university_id.value)),
+        )
+        .await?;
+
+        Ok(models.into_iter().map(Into::into).collect())
+    }
+}
@@ -0,0 +1,27 @@
+use app::{attestation::Hours, load_limit};
+use sqlx::FromRow;
+use utils::entity::Id;
+
+use crate::teacher::PgTeacherKind;
+
+#[derive(Clone, Debug, FromRow)]
+#[sea_query::enum_def]
+pub struct LoadLimits {
+    pub id: i32,
+    pub university_id: i32,
+    pub teacher_kind: PgTeacherKind,
+    pub min_hours: i32,
+    pub max_hours: i32,
+}
+
+impl From<LoadLimits> for load_limit::Entity {
+    fn from(value: LoadLimits) -> Self {
+        load_limit::Entity {
+            id: Id::new(value.id),
+            university_id: Id::new(value.university_id),
+            teacher_kind: value.teacher_kind.into(),
+            min_hours: Hours(value.min_hours),
+            max_hours: Hours(value.max_hours),
+        }
+    }
+}
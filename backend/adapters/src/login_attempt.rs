@@ -0,0 +1,135 @@
+use std::{collections::HashMap, sync::Arc};
+
+use app::{
+    login_attempt::{self, Counter, Increment, Key, Lockout},
+    user_session::SecondsFromUnixEpoch,
+};
+use tokio::sync::Mutex;
+
+/// Shared by every request scope, lives as long as the adapters module
+pub(crate) type LoginAttemptCounters = Arc<Mutex<HashMap<Key, Counter>>>;
+
+/// Counters are lost on restart and are not shared between instances,
+/// which is enough for a single node
+pub struct MemoryLoginAttemptStore {
+    pub(crate) counters: LoginAttemptCounters,
+}
+
+#[async_trait::async_trait]
+impl login_attempt::Store for MemoryLoginAttemptStore {
+    async fn increment(
+        &mut self,
+        key: Key,
+        now: SecondsFromUnixEpoch,
+        lockout: Lockout,
+    ) -> Result<Increment, anyhow::Error> {
+        let mut counters = self.counters.lock().await;
+        let counter = counters.entry(key).or_insert(Counter {
+            failures: 0,
+            last_failure_at: now,
+            locked_until: None,
+        });
+
+        if let Some(until) = counter.locked_until.filter(|v| *v > now) {
+            return Ok(Increment::Locked { until });
+        }
+
+        counter.failures += 1;
+        counter.last_failure_at = now;
+        if let Some(lockout) = lockout.after(counter.failures) {
+            counter.locked_until = Some((now.seconds.val + lockout.val).into());
+        }
+
+        Ok(Increment::Counted(*counter))
+    }
+
+    async fn decrement(&mut self, key: &Key, unlock: bool) -> Result<(), anyhow::Error> {
+        if let Some(counter) = self.counters.lock().await.get_mut(key) {
+            counter.failures = counter.failures.saturating_sub(1);
+            if unlock {
+                counter.locked_until = None;
+            }
+        }
+        Ok(())
+    }
+
+    async fn delete(&mut self, key: &Key) -> Result<(), anyhow::Error> {
+        self.counters.lock().await.remove(key);
+        Ok(())
+    }
+
+    async fn delete_stale(&mut self, before: SecondsFromUnixEpoch) -> Result<(), anyhow::Error> {
+        self.counters
+            .lock()
+            .await
+            .retain(|_, v| v.last_failure_at >= before);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use app::login_attempt::Store;
+
+    use super::*;
+
+    fn lockout() -> Lockout {
+        Lockout {
+            max_failures: 2,
+            base: 60.into(),
+            max: 3600.into(),
+        }
+    }
+
+    fn key() -> Key {
+        Key::Account("d.churikov@stud.nstu.ru".to_owned())
+    }
+
+    async fn increment(store: &mut MemoryLoginAttemptStore, now: u64) -> Result<u32, u64> {
+        match store.increment(key(), now.into(), lockout()).await.unwrap() {
+            Increment::Counted(counter) => Ok(counter.failures),
+            Increment::Locked { until } => Err(until.seconds.val),
+        }
+    }
+
+    #[tokio::test]
+    async fn attempts_are_not_counted_while_locked() {
+        let mut store = MemoryLoginAttemptStore {
+            counters: Arc::default(),
+        };
+
+        assert_eq!(increment(&mut store, 1000).await, Ok(1));
+        assert_eq!(increment(&mut store, 1001).await, Ok(2));
+        assert_eq!(increment(&mut store, 1002).await, Err(1061));
+        assert_eq!(increment(&mut store, 1060).await, Err(1061));
+
+        // the next failure after the lockout doubles it
+        assert_eq!(increment(&mut store, 1061).await, Ok(3));
+        assert_eq!(increment(&mut store, 1062).await, Err(1181));
+    }
+
+    #[tokio::test]
+    async fn given_back_attempt_can_lift_its_lock() {
+        let mut store = MemoryLoginAttemptStore {
+            counters: Arc::default(),
+        };
+
+        assert_eq!(increment(&mut store, 1000).await, Ok(1));
+        assert_eq!(increment(&mut store, 1001).await, Ok(2));
+        store.decrement(&key(), true).await.unwrap();
+
+        assert_eq!(increment(&mut store, 1002).await, Ok(2));
+    }
+
+    #[tokio::test]
+    async fn stale_counters_are_deleted() {
+        let mut store = MemoryLoginAttemptStore {
+            counters: Arc::default(),
+        };
+
+        assert_eq!(increment(&mut store, 1000).await, Ok(1));
+        store.delete_stale(1001.into()).await.unwrap();
+
+        assert_eq!(increment(&mut store, 1002).await, Ok(1));
+    }
+}
@@ -0,0 +1,136 @@
+use std::{
+    path::PathBuf,
+    sync::Arc,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use anyhow::Context;
+use app::mail::{Mail, MailSender};
+use lettre::{
+    message::header::ContentType, transport::smtp::authentication::Credentials, AsyncSmtpTransport,
+    AsyncTransport, Message, Tokio1Executor,
+};
+
+#[derive(Debug, Clone)]
+pub struct MailSettings {
+    /// Address the letters are sent from, may include a name: `Деканат <noreply@example.com>`
+    pub from: Arc<str>,
+    pub transport: MailTransport,
+}
+
+#[derive(Debug, Clone)]
+pub enum MailTransport {
+    Smtp(SmtpSettings),
+    /// Letters are written to files in the directory instead of being sent, for local runs
+    Directory(Arc<str>),
+}
+
+#[derive(Clone)]
+pub struct SmtpSettings {
+    pub host: Arc<str>,
+    /// Default port of the security mode when not set
+    pub port: Option<u16>,
+    pub security: SmtpSecurity,
+    pub username: Option<Arc<str>>,
+    pub password: Option<Arc<str>>,
+}
+
+impl std::fmt::Debug for SmtpSettings {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SmtpSettings")
+            .field("host", &self.host)
+            .field("port", &self.port)
+            .field("security", &self.security)
+            .field("username", &self.username)
+            .field("password", &"?")
+            .finish()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SmtpSecurity {
+    None,
+    StartTls,
+    Tls,
+}
+
+pub struct SmtpMailSender {
+    pub(crate) from: Arc<str>,
+    pub(crate) settings: SmtpSettings,
+}
+
+impl SmtpMailSender {
+    /// A connection is opened for every letter, letters are rare enough to not keep a pool
+    fn transport(&self) -> Result<AsyncSmtpTransport<Tokio1Executor>, anyhow::Error> {
+        let host = &*self.settings.host;
+        let mut builder = match self.settings.security {
+            SmtpSecurity::None => AsyncSmtpTransport::<Tokio1Executor>::builder_dangerous(host),
+            SmtpSecurity::StartTls => AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(host)?,
+            SmtpSecurity::Tls => AsyncSmtpTransport::<Tokio1Executor>::relay(host)?,
+        };
+
+        if let Some(port) = self.settings.port {
+            builder = builder.port(port);
+        }
+
+        if let (Some(username), Some(password)) = (&self.settings.username, &self.settings.password)
+        {
+            builder =
+                builder.credentials(Credentials::new(username.to_string(), password.to_string()));
+        }
+
+        Ok(builder.build())
+    }
+}
+
+#[async_trait::async_trait]
+impl MailSender for SmtpMailSender {
+    async fn send(&self, mail: Mail) -> Result<(), anyhow::Error> {
+        let message = Message::builder()
+            .from(self.from.parse().context("invalid mail from address")?)
+            .to(mail.to.parse().context("invalid mail recipient address")?)
+            .subject(mail.subject)
+            .header(ContentType::TEXT_PLAIN)
+            .body(mail.body)?;
+
+        self.transport()?
+            .send(message)
+            .await
+            .context("failed to send mail")?;
+
+        Ok(())
+    }
+}
+
+/// Writes every letter into its own text file, links in them can be followed by hand
+pub struct FileMailSender {
+    pub(crate) from: Arc<str>,
+    pub(crate) dir: Arc<str>,
+}
+
+#[async_trait::async_trait]
+impl MailSender for FileMailSender {
+    async fn send(&self, mail: Mail) -> Result<(), anyhow::Error> {
+        let dir = PathBuf::from(&*self.dir);
+        tokio::fs::create_dir_all(&dir)
+            .await
+            .with_context(|| format!("failed to create mail directory {}", dir.display()))?;
+
+        let sent_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .context("failed to get duration since unix epoch")?
+            .as_millis();
+        let path = dir.join(format!("{sent_at}-{}.txt", nanoid::nanoid!(6)));
+
+        let content = format!(
+            "From: {}\nTo: {}\nSubject: {}\n\n{}\n",
+            self.from, mail.to, mail.subject, mail.body
+        );
+        tokio::fs::write(&path, content)
+            .await
+            .with_context(|| format!("failed to write mail to {}", path.display()))?;
+
+        tracing::info!(to = %mail.to, path = %path.display(), "mail written to file");
+        Ok(())
+    }
+}
@@ -0,0 +1,72 @@
+use std::sync::Arc;
+
+use app::{
+    academic_year::{self, Entity, EntityId},
+    university,
+};
+use tokio::sync::Mutex;
+use utils::entity::Id;
+
+use super::tables::{check, ConstraintViolation, Tables};
+
+pub struct MemoryAcademicYearRepo {
+    pub(crate) tables: Arc<Mutex<Tables>>,
+}
+
+#[async_trait::async_trait]
+impl academic_year::Repo for MemoryAcademicYearRepo {
+    async fn save(&mut self, mut entity: Entity) -> Result<Entity, anyhow::Error> {
+        let mut tables = self.tables.lock().await;
+        entity.id = Id::new(tables.academic_years.resolve_id(entity.id.value));
+
+        check(
+            tables.universities.contains(&entity.university_id.value),
+            ConstraintViolation::ForeignKey("academic_years_university_id_fkey"),
+        )?;
+        check(
+            entity.semesters.iter().all(|v| v.starts_on <= v.ends_on),
+            ConstraintViolation::Check("academic_year_semesters_check"),
+        )?;
+        check(
+            !tables.academic_years.values().any(|v| {
+                v.id != entity.id
+                    && v.university_id == entity.university_id
+                    && v.start_year == entity.start_year
+            }),
+            ConstraintViolation::Unique("academic_years_university_id_start_year_key"),
+        )?;
+
+        tables
+            .academic_years
+            .rows
+            .insert(entity.id.value, entity.clone());
+        Ok(entity)
+    }
+
+    async fn delete(&mut self, entity: &Entity) -> Result<(), anyhow::Error> {
+        let mut tables = self.tables.lock().await;
+        tables.academic_years.rows.remove(&entity.id.value);
+        Ok(())
+    }
+
+    async fn find(&self, id: EntityId) -> Result<Option<Entity>, anyhow::Error> {
+        let tables = self.tables.lock().await;
+        Ok(tables.academic_years.rows.get(&id.value).cloned())
+    }
+
+    async fn list_by_university(
+        &self,
+        university_id: university::EntityId,
+    ) -> Result<Vec<Entity>, anyhow::Error> {
+        let tables = self.tables.lock().await;
+        let mut entities = tables
+            .academic_years
+            .values()
+            .filter(|v| v.university_id == university_id)
+            .cloned()
+            .collect::<Vec<_>>();
+        entities.sort_by_key(|v| v.start_year);
+
+        Ok(entities)
+    }
+}
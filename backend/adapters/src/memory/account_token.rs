@@ -0,0 +1,57 @@
+use std::sync::Arc;
+
+use app::{
+    account_token::{self, Entity, EntityId, Purpose},
+    user,
+};
+use tokio::sync::Mutex;
+use utils::entity::Id;
+
+use super::tables::{check, ConstraintViolation, Tables};
+
+pub struct MemoryAccountTokenRepo {
+    pub(crate) tables: Arc<Mutex<Tables>>,
+}
+
+#[async_trait::async_trait]
+impl account_token::Repo for MemoryAccountTokenRepo {
+    async fn save(&mut self, mut entity: Entity) -> Result<Entity, anyhow::Error> {
+        let mut tables = self.tables.lock().await;
+        entity.id = Id::new(tables.account_tokens.resolve_id(entity.id.value));
+
+        check(
+            tables.users.contains(&entity.user_id.value),
+            ConstraintViolation::ForeignKey("account_tokens_user_id_fkey"),
+        )?;
+
+        tables
+            .account_tokens
+            .rows
+            .insert(entity.id.value, entity.clone());
+        Ok(entity)
+    }
+
+    async fn delete(&mut self, entity: &Entity) -> Result<(), anyhow::Error> {
+        let mut tables = self.tables.lock().await;
+        tables.account_tokens.rows.remove(&entity.id.value);
+        Ok(())
+    }
+
+    async fn find(&self, id: EntityId) -> Result<Option<Entity>, anyhow::Error> {
+        let tables = self.tables.lock().await;
+        Ok(tables.account_tokens.rows.get(&id.value).cloned())
+    }
+
+    async fn delete_by_user(
+        &mut self,
+        user_id: user::EntityId,
+        purpose: Purpose,
+    ) -> Result<(), anyhow::Error> {
+        let mut tables = self.tables.lock().await;
+        tables
+            .account_tokens
+            .rows
+            .retain(|_, v| v.user_id != user_id || v.purpose != purpose);
+        Ok(())
+    }
+}
@@ -0,0 +1,124 @@
+use std::{collections::HashSet, sync::Arc};
+
+use app::{
+    attestation::{self, Entity, EntityId, Hours},
+    curriculum_module, teacher,
+};
+use tokio::sync::Mutex;
+use utils::entity::Id;
+
+use super::tables::{check, ConstraintViolation, Tables};
+
+pub struct MemoryAttestationRepo {
+    pub(crate) tables: Arc<Mutex<Tables>>,
+}
+
+#[async_trait::async_trait]
+impl attestation::Repo for MemoryAttestationRepo {
+    async fn save(&mut self, mut entity: Entity) -> Result<Entity, anyhow::Error> {
+        let mut tables = self.tables.lock().await;
+        entity.id = Id::new(tables.attestations.resolve_id(entity.id.value));
+
+        check(
+            tables
+                .curriculum_modules
+                .contains(&entity.curriculum_module_id.value),
+            ConstraintViolation::ForeignKey("attestations_curriculum_module_id_fkey"),
+        )?;
+        check(
+            !tables.attestations.values().any(|v| {
+                v.id != entity.id && v.curriculum_module_id == entity.curriculum_module_id
+            }),
+            ConstraintViolation::Unique("attestations_curriculum_module_id_key"),
+        )?;
+
+        let Hours(duration) = entity.duration;
+        check(
+            duration > 0,
+            ConstraintViolation::Check("attestations_duration_in_hours_check"),
+        )?;
+        check(
+            entity
+                .examiners
+                .iter()
+                .all(|e| tables.teachers.contains(&e.value)),
+            ConstraintViolation::ForeignKey("attestation_examiners_examiner_id_fkey"),
+        )?;
+
+        tables
+            .attestations
+            .rows
+            .insert(entity.id.value, entity.clone());
+        Ok(entity)
+    }
+
+    async fn delete(&mut self, entity: &Entity) -> Result<(), anyhow::Error> {
+        let mut tables = self.tables.lock().await;
+        let id = entity.id;
+
+        check(
+            !tables
+                .students
+                .values()
+                .any(|v| v.attestations.iter().any(|a| a.attestation_id == id)),
+            ConstraintViolation::ForeignKey("student_attestations_attestation_id_fkey"),
+        )?;
+        check(
+            !tables
+                .attestation_sessions
+                .values()
+                .any(|v| v.attestation_id == id),
+            ConstraintViolation::ForeignKey("attestation_sessions_attestation_id_fkey"),
+        )?;
+
+        tables.attestations.rows.remove(&id.value);
+        Ok(())
+    }
+
+    async fn find(&self, id: EntityId) -> Result<Option<Entity>, anyhow::Error> {
+        let tables = self.tables.lock().await;
+        Ok(tables.attestations.rows.get(&id.value).cloned())
+    }
+
+    async fn find_by_curriculum_module(
+        &self,
+        curriculum_module_id: curriculum_module::EntityId,
+    ) -> Result<Option<Entity>, anyhow::Error> {
+        let tables = self.tables.lock().await;
+        Ok(tables
+            .attestations
+            .values()
+            .find(|v| v.curriculum_module_id == curriculum_module_id)
+            .cloned())
+    }
+
+    async fn list_by_curriculum_modules(
+        &self,
+        curriculum_modules_ids: HashSet<curriculum_module::EntityId>,
+    ) -> Result<Vec<Entity>, anyhow::Error> {
+        let tables = self.tables.lock().await;
+        let entities = tables
+            .attestations
+            .values()
+            .filter(|v| curriculum_modules_ids.contains(&v.curriculum_module_id))
+            .cloned()
+            .collect();
+
+        Ok(entities)
+    }
+
+    async fn list_by_examiners(
+        &self,
+        examiners_ids: HashSet<teacher::EntityId>,
+    ) -> Result<Vec<Entity>, anyhow::Error> {
+        let tables = self.tables.lock().await;
+        let entities = tables
+            .attestations
+            .values()
+            .filter(|v| !v.examiners.is_disjoint(&examiners_ids))
+            .cloned()
+            .collect();
+
+        Ok(entities)
+    }
+}
@@ -0,0 +1,91 @@
+use std::{collections::HashSet, sync::Arc};
+
+use app::{
+    attestation,
+    attestation_session::{self, Entity, EntityId},
+    room, study_group,
+};
+use tokio::sync::Mutex;
+use utils::entity::Id;
+
+use super::tables::{check, ConstraintViolation, Tables};
+
+pub struct MemoryAttestationSessionRepo {
+    pub(crate) tables: Arc<Mutex<Tables>>,
+}
+
+impl MemoryAttestationSessionRepo {
+    async fn list_by(&self, predicate: impl Fn(&Entity) -> bool) -> Vec<Entity> {
+        let tables = self.tables.lock().await;
+        tables
+            .attestation_sessions
+            .values()
+            .filter(|v| predicate(v))
+            .cloned()
+            .collect()
+    }
+}
+
+#[async_trait::async_trait]
+impl attestation_session::Repo for MemoryAttestationSessionRepo {
+    async fn save(&mut self, mut entity: Entity) -> Result<Entity, anyhow::Error> {
+        let mut tables = self.tables.lock().await;
+        entity.id = Id::new(tables.attestation_sessions.resolve_id(entity.id.value));
+
+        check(
+            tables.attestations.contains(&entity.attestation_id.value),
+            ConstraintViolation::ForeignKey("attestation_sessions_attestation_id_fkey"),
+        )?;
+        check(
+            tables.study_groups.contains(&entity.study_group_id.value),
+            ConstraintViolation::ForeignKey("attestation_sessions_study_group_id_fkey"),
+        )?;
+        check(
+            entity
+                .room_id
+                .map_or(true, |v| tables.rooms.contains(&v.value)),
+            ConstraintViolation::ForeignKey("attestation_sessions_room_id_fkey"),
+        )?;
+        check(
+            entity.is_valid_time(),
+            ConstraintViolation::Check("attestation_sessions_check"),
+        )?;
+
+        tables
+            .attestation_sessions
+            .rows
+            .insert(entity.id.value, entity.clone());
+        Ok(entity)
+    }
+
+    async fn delete(&mut self, entity: &Entity) -> Result<(), anyhow::Error> {
+        let mut tables = self.tables.lock().await;
+        tables.attestation_sessions.rows.remove(&entity.id.value);
+        Ok(())
+    }
+
+    async fn find(&self, id: EntityId) -> Result<Option<Entity>, anyhow::Error> {
+        let tables = self.tables.lock().await;
+        Ok(tables.attestation_sessions.rows.get(&id.value).cloned())
+    }
+
+    async fn list_by_attestations(
+        &self,
+        attestation_ids: HashSet<attestation::EntityId>,
+    ) -> Result<Vec<Entity>, anyhow::Error> {
+        Ok(self
+            .list_by(|v| attestation_ids.contains(&v.attestation_id))
+            .await)
+    }
+
+    async fn list_by_study_group(
+        &self,
+        study_group_id: study_group::EntityId,
+    ) -> Result<Vec<Entity>, anyhow::Error> {
+        Ok(self.list_by(|v| v.study_group_id == study_group_id).await)
+    }
+
+    async fn list_by_room(&self, room_id: room::EntityId) -> Result<Vec<Entity>, anyhow::Error> {
+        Ok(self.list_by(|v| v.room_id == Some(room_id)).await)
+    }
+}
@@ -0,0 +1,77 @@
+use std::sync::Arc;
+
+use app::{
+    calendar_feed::{self, Entity},
+    person,
+};
+use tokio::sync::Mutex;
+use utils::entity::Id;
+
+use super::tables::{check, ConstraintViolation, Tables};
+
+pub struct MemoryCalendarFeedRepo {
+    pub(crate) tables: Arc<Mutex<Tables>>,
+}
+
+#[async_trait::async_trait]
+impl calendar_feed::Repo for MemoryCalendarFeedRepo {
+    async fn save(&mut self, mut entity: Entity) -> Result<Entity, anyhow::Error> {
+        let mut tables = self.tables.lock().await;
+        entity.id = Id::new(tables.calendar_feeds.resolve_id(entity.id.value));
+
+        check(
+            tables.persons.contains(&entity.person_id.value),
+            ConstraintViolation::ForeignKey("calendar_feeds_person_id_fkey"),
+        )?;
+        check(
+            !tables
+                .calendar_feeds
+                .values()
+                .any(|v| v.id != entity.id && v.person_id == entity.person_id),
+            ConstraintViolation::Unique("calendar_feeds_person_id_key"),
+        )?;
+        check(
+            !tables
+                .calendar_feeds
+                .values()
+                .any(|v| v.id != entity.id && v.token_hash == entity.token_hash),
+            ConstraintViolation::Unique("calendar_feeds_token_hash_key"),
+        )?;
+
+        tables
+            .calendar_feeds
+            .rows
+            .insert(entity.id.value, entity.clone());
+        Ok(entity)
+    }
+
+    async fn delete(&mut self, entity: &Entity) -> Result<(), anyhow::Error> {
+        let mut tables = self.tables.lock().await;
+        tables.calendar_feeds.rows.remove(&entity.id.value);
+        Ok(())
+    }
+
+    async fn find_by_person(
+        &self,
+        person_id: person::EntityId,
+    ) -> Result<Option<Entity>, anyhow::Error> {
+        let tables = self.tables.lock().await;
+        Ok(tables
+            .calendar_feeds
+            .values()
+            .find(|v| v.person_id == person_id)
+            .cloned())
+    }
+
+    async fn find_by_token_hash(
+        &self,
+        token_hash: String,
+    ) -> Result<Option<Entity>, anyhow::Error> {
+        let tables = self.tables.lock().await;
+        Ok(tables
+            .calendar_feeds
+            .values()
+            .find(|v| v.token_hash == token_hash)
+            .cloned())
+    }
+}
@@ -0,0 +1,113 @@
+use std::{collections::HashSet, sync::Arc};
+
+use app::{
+    attestation::Hours,
+    class::{self, Entity, EntityId},
+    class_kind, curriculum_module,
+};
+use tokio::sync::Mutex;
+use utils::entity::Id;
+
+use super::tables::{check, ConstraintViolation, Tables};
+
+pub struct MemoryClassRepo {
+    pub(crate) tables: Arc<Mutex<Tables>>,
+}
+
+#[async_trait::async_trait]
+impl class::Repo for MemoryClassRepo {
+    async fn save(&mut self, mut entity: Entity) -> Result<Entity, anyhow::Error> {
+        let mut tables = self.tables.lock().await;
+        entity.id = Id::new(tables.classes.resolve_id(entity.id.value));
+
+        check(
+            tables
+                .curriculum_modules
+                .contains(&entity.curriculum_module_id.value),
+            ConstraintViolation::ForeignKey("classes_curriculum_module_id_fkey"),
+        )?;
+        check(
+            tables.class_kinds.contains(&entity.kind_name.value),
+            ConstraintViolation::ForeignKey("classes_kind_name_fkey"),
+        )?;
+
+        let Hours(duration) = entity.duration;
+        check(
+            duration > 0,
+            ConstraintViolation::Check("classes_duration_in_hours_check"),
+        )?;
+
+        tables.classes.rows.insert(entity.id.value, entity.clone());
+        Ok(entity)
+    }
+
+    async fn delete(&mut self, entity: &Entity) -> Result<(), anyhow::Error> {
+        let mut tables = self.tables.lock().await;
+        let id = entity.id;
+
+        check(
+            !tables
+                .teachers
+                .values()
+                .any(|v| v.classes.iter().any(|c| c.class_id == id)),
+            ConstraintViolation::ForeignKey("class_teachers_class_id_fkey"),
+        )?;
+        check(
+            !tables.schedules.values().any(|v| v.class_id == id),
+            ConstraintViolation::ForeignKey("schedule_entries_class_id_fkey"),
+        )?;
+
+        tables.classes.rows.remove(&id.value);
+        Ok(())
+    }
+
+    async fn find(&self, id: EntityId) -> Result<Option<Entity>, anyhow::Error> {
+        let tables = self.tables.lock().await;
+        Ok(tables.classes.rows.get(&id.value).cloned())
+    }
+
+    async fn list_by_curriculum_module(
+        &self,
+        curriculum_module_id: curriculum_module::EntityId,
+    ) -> Result<Vec<Entity>, anyhow::Error> {
+        let tables = self.tables.lock().await;
+        let entities = tables
+            .classes
+            .values()
+            .filter(|v| v.curriculum_module_id == curriculum_module_id)
+            .cloned()
+            .collect();
+
+        Ok(entities)
+    }
+
+    async fn list_by_curriculum_modules(
+        &self,
+        curriculum_modules_ids: HashSet<curriculum_module::EntityId>,
+    ) -> Result<Vec<Entity>, anyhow::Error> {
+        let tables = self.tables.lock().await;
+        let entities = tables
+            .classes
+            .values()
+            .filter(|v| curriculum_modules_ids.contains(&v.curriculum_module_id))
+            .cloned()
+            .collect();
+
+        Ok(entities)
+    }
+
+    async fn list_by_kind(
+        &self,
+        kind_name: class_kind::EntityId,
+    ) -> Result<Vec<Entity>, anyhow::Error> {
+        let tables = self.tables.lock().await;
+        let entities = tables
+            .classes
+            .values()
+            .filter(|v| v.kind_name == kind_name)
+            .cloned()
+            .collect();
+
+        Ok(entities)
+    }
+}
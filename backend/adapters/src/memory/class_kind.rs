@@ -0,0 +1,40 @@
+use std::sync::Arc;
+
+use app::class_kind::{self, Entity, EntityId};
+use tokio::sync::Mutex;
+
+use super::tables::{check, ConstraintViolation, Tables};
+
+pub struct MemoryClassKindRepo {
+    pub(crate) tables: Arc<Mutex<Tables>>,
+}
+
+#[async_trait::async_trait]
+impl class_kind::Repo for MemoryClassKindRepo {
+    async fn save(&mut self, entity: Entity) -> Result<Entity, anyhow::Error> {
+        let mut tables = self.tables.lock().await;
+        tables
+            .class_kinds
+            .rows
+            .insert(entity.name.value.clone(), entity.clone());
+
+        Ok(entity)
+    }
+
+    async fn delete(&mut self, entity: &Entity) -> Result<(), anyhow::Error> {
+        let mut tables = self.tables.lock().await;
+
+        check(
+            !tables.classes.values().any(|v| v.kind_name == entity.name),
+            ConstraintViolation::ForeignKey("classes_kind_name_fkey"),
+        )?;
+
+        tables.class_kinds.rows.remove(&entity.name.value);
+        Ok(())
+    }
+
+    async fn find(&self, id: EntityId) -> Result<Option<Entity>, anyhow::Error> {
+        let tables = self.tables.lock().await;
+        Ok(tables.class_kinds.rows.get(&id.value).cloned())
+    }
+}
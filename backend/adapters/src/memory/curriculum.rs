@@ -0,0 +1,130 @@
+use std::{collections::HashSet, sync::Arc};
+
+use app::{
+    curriculum::{self, Entity, EntityId},
+    subdivision,
+};
+use tokio::sync::Mutex;
+use utils::entity::Id;
+
+use super::tables::{check, ConstraintViolation, Tables};
+
+pub struct MemoryCurriculumRepo {
+    pub(crate) tables: Arc<Mutex<Tables>>,
+}
+
+#[async_trait::async_trait]
+impl curriculum::Repo for MemoryCurriculumRepo {
+    async fn save(&mut self, mut entity: Entity) -> Result<Entity, anyhow::Error> {
+        let mut tables = self.tables.lock().await;
+        entity.id = Id::new(tables.curriculums.resolve_id(entity.id.value));
+
+        check(
+            !tables
+                .curriculums
+                .values()
+                .any(|v| v.id != entity.id && v.name == entity.name && v.version == entity.version),
+            ConstraintViolation::Unique("curriculums_name_version_key"),
+        )?;
+
+        tables
+            .curriculums
+            .rows
+            .insert(entity.id.value, entity.clone());
+        Ok(entity)
+    }
+
+    async fn insert_version(
+        &mut self,
+        mut entity: Entity,
+    ) -> Result<Option<Entity>, anyhow::Error> {
+        let mut tables = self.tables.lock().await;
+        if tables
+            .curriculums
+            .values()
+            .any(|v| v.name == entity.name && v.version == entity.version)
+        {
+            return Ok(None);
+        }
+
+        entity.id = Id::new(tables.curriculums.resolve_id(Default::default()));
+        tables
+            .curriculums
+            .rows
+            .insert(entity.id.value, entity.clone());
+        Ok(Some(entity))
+    }
+
+    async fn delete(&mut self, entity: &Entity) -> Result<(), anyhow::Error> {
+        let mut tables = self.tables.lock().await;
+        let id = entity.id;
+
+        check(
+            !tables
+                .study_groups
+                .values()
+                .any(|v| v.curriculums.contains(&id)),
+            ConstraintViolation::ForeignKey("study_group_curriculums_curriculum_id_fkey"),
+        )?;
+        check(
+            !tables
+                .curriculum_modules
+                .values()
+                .any(|v| v.curriculum_id == id),
+            ConstraintViolation::ForeignKey("curriculum_modules_curriculum_id_fkey"),
+        )?;
+
+        tables.curriculums.rows.remove(&id.value);
+        Ok(())
+    }
+
+    async fn find(&self, id: EntityId) -> Result<Option<Entity>, anyhow::Error> {
+        let tables = self.tables.lock().await;
+        Ok(tables.curriculums.rows.get(&id.value).cloned())
+    }
+
+    async fn find_many(&self, ids: HashSet<EntityId>) -> Result<Vec<Entity>, anyhow::Error> {
+        let tables = self.tables.lock().await;
+        let entities = tables
+            .curriculums
+            .values()
+            .filter(|v| ids.contains(&v.id))
+            .cloned()
+            .collect();
+
+        Ok(entities)
+    }
+
+    async fn list(&self) -> Result<Vec<Entity>, anyhow::Error> {
+        let tables = self.tables.lock().await;
+        Ok(tables.curriculums.values().cloned().collect())
+    }
+
+    async fn list_by_name(&self, name: String) -> Result<Vec<Entity>, anyhow::Error> {
+        let tables = self.tables.lock().await;
+        let mut entities = tables
+            .curriculums
+            .values()
+            .filter(|v| v.name == name)
+            .cloned()
+            .collect::<Vec<_>>();
+        entities.sort_by_key(|v| v.version);
+
+        Ok(entities)
+    }
+
+    async fn list_by_department_id(
+        &self,
+        department_id: subdivision::EntityId,
+    ) -> Result<Vec<Entity>, anyhow::Error> {
+        let tables = self.tables.lock().await;
+        let entities = tables
+            .curriculums
+            .values()
+            .filter(|v| v.department_id == Some(department_id))
+            .cloned()
+            .collect();
+
+        Ok(entities)
+    }
+}
@@ -0,0 +1,115 @@
+use std::{collections::HashSet, sync::Arc};
+
+use app::{
+    curriculum,
+    curriculum_module::{self, Entity, EntityId},
+    discipline,
+};
+use tokio::sync::Mutex;
+use utils::entity::Id;
+
+use super::tables::{check, ConstraintViolation, Tables};
+
+pub struct MemoryCurriculumModuleRepo {
+    pub(crate) tables: Arc<Mutex<Tables>>,
+}
+
+#[async_trait::async_trait]
+impl curriculum_module::Repo for MemoryCurriculumModuleRepo {
+    async fn save(&mut self, mut entity: Entity) -> Result<Entity, anyhow::Error> {
+        let mut tables = self.tables.lock().await;
+        entity.id = Id::new(tables.curriculum_modules.resolve_id(entity.id.value));
+
+        check(
+            tables.curriculums.contains(&entity.curriculum_id.value),
+            ConstraintViolation::ForeignKey("curriculum_modules_curriculum_id_fkey"),
+        )?;
+        check(
+            tables.disciplines.contains(&entity.discipline_id.value),
+            ConstraintViolation::ForeignKey("curriculum_modules_discipline_id_fkey"),
+        )?;
+        check(
+            entity.semester > 0,
+            ConstraintViolation::Check("curriculum_modules_semester_check"),
+        )?;
+
+        tables
+            .curriculum_modules
+            .rows
+            .insert(entity.id.value, entity.clone());
+        Ok(entity)
+    }
+
+    async fn delete(&mut self, entity: &Entity) -> Result<(), anyhow::Error> {
+        let mut tables = self.tables.lock().await;
+        let id = entity.id;
+
+        check(
+            !tables
+                .attestations
+                .values()
+                .any(|v| v.curriculum_module_id == id),
+            ConstraintViolation::ForeignKey("attestations_curriculum_module_id_fkey"),
+        )?;
+        check(
+            !tables
+                .classes
+                .values()
+                .any(|v| v.curriculum_module_id == id),
+            ConstraintViolation::ForeignKey("classes_curriculum_module_id_fkey"),
+        )?;
+
+        tables.curriculum_modules.rows.remove(&id.value);
+        Ok(())
+    }
+
+    async fn find(&self, id: EntityId) -> Result<Option<Entity>, anyhow::Error> {
+        let tables = self.tables.lock().await;
+        Ok(tables.curriculum_modules.rows.get(&id.value).cloned())
+    }
+
+    async fn list_by_discipline_id(
+        &self,
+        discipline_id: discipline::EntityId,
+    ) -> Result<Vec<Entity>, anyhow::Error> {
+        let tables = self.tables.lock().await;
+        let entities = tables
+            .curriculum_modules
+            .values()
+            .filter(|v| v.discipline_id == discipline_id)
+            .cloned()
+            .collect();
+
+        Ok(entities)
+    }
+
+    async fn list_by_curriculum_id(
+        &self,
+        curriculum_id: curriculum::EntityId,
+    ) -> Result<Vec<Entity>, anyhow::Error> {
+        let tables = self.tables.lock().await;
+        let entities = tables
+            .curriculum_modules
+            .values()
+            .filter(|v| v.curriculum_id == curriculum_id)
+            .cloned()
+            .collect();
+
+        Ok(entities)
+    }
+
+    async fn list_by_curriculums(
+        &self,
+        curriculums_ids: HashSet<curriculum::EntityId>,
+    ) -> Result<Vec<Entity>, anyhow::Error> {
+        let tables = self.tables.lock().await;
+        let entities = tables
+            .curriculum_modules
+            .values()
+            .filter(|v| curriculums_ids.contains(&v.curriculum_id))
+            .cloned()
+            .collect();
+
+        Ok(entities)
+    }
+}
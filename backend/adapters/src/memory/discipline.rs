@@ -0,0 +1,90 @@
+use std::{collections::HashSet, sync::Arc};
+
+use app::{
+    discipline::{self, Entity, EntityId},
+    subdivision,
+};
+use tokio::sync::Mutex;
+use utils::entity::Id;
+
+use super::tables::{check, ConstraintViolation, Tables};
+
+pub struct MemoryDisciplineRepo {
+    pub(crate) tables: Arc<Mutex<Tables>>,
+}
+
+#[async_trait::async_trait]
+impl discipline::Repo for MemoryDisciplineRepo {
+    async fn save(&mut self, mut entity: Entity) -> Result<Entity, anyhow::Error> {
+        let mut tables = self.tables.lock().await;
+        entity.id = Id::new(tables.disciplines.resolve_id(entity.id.value));
+
+        check(
+            tables.subdivisions.contains(&entity.department_id.value),
+            ConstraintViolation::ForeignKey("disciplines_department_id_fkey"),
+        )?;
+
+        tables
+            .disciplines
+            .rows
+            .insert(entity.id.value, entity.clone());
+        Ok(entity)
+    }
+
+    async fn delete(&mut self, entity: &Entity) -> Result<(), anyhow::Error> {
+        let mut tables = self.tables.lock().await;
+        let id = entity.id;
+
+        check(
+            !tables
+                .curriculum_modules
+                .values()
+                .any(|v| v.discipline_id == id),
+            ConstraintViolation::ForeignKey("curriculum_modules_discipline_id_fkey"),
+        )?;
+
+        tables.disciplines.rows.remove(&id.value);
+        Ok(())
+    }
+
+    async fn find(&self, id: EntityId) -> Result<Option<Entity>, anyhow::Error> {
+        let tables = self.tables.lock().await;
+        Ok(tables.disciplines.rows.get(&id.value).cloned())
+    }
+
+    async fn find_many(&self, ids: HashSet<EntityId>) -> Result<Vec<Entity>, anyhow::Error> {
+        let tables = self.tables.lock().await;
+        let entities = tables
+            .disciplines
+            .values()
+            .filter(|v| ids.contains(&v.id))
+            .cloned()
+            .collect();
+
+        Ok(entities)
+    }
+
+    async fn find_by_name(&self, name: String) -> Result<Option<Entity>, anyhow::Error> {
+        let tables = self.tables.lock().await;
+        Ok(tables
+            .disciplines
+            .values()
+            .find(|v| v.name == name)
+            .cloned())
+    }
+
+    async fn list_by_department_id(
+        &self,
+        department_id: subdivision::EntityId,
+    ) -> Result<Vec<Entity>, anyhow::Error> {
+        let tables = self.tables.lock().await;
+        let entities = tables
+            .disciplines
+            .values()
+            .filter(|v| v.department_id == department_id)
+            .cloned()
+            .collect();
+
+        Ok(entities)
+    }
+}
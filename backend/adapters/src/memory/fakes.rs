@@ -0,0 +1,208 @@
+use std::sync::{
+    atomic::{AtomicU64, Ordering},
+    Arc,
+};
+
+use anyhow::Context;
+use app::{
+    account_token::Purpose,
+    hasher::{HashedPassword, PasswordHasher},
+    mail::{Mail, MailSender},
+    token::{
+        AccessTokenEngine, AccountTokenClaims, AccountTokenEngine, Claims, RefreshTokenGenerator,
+        RefreshTokenHasher,
+    },
+    user_session::DeviceIdGenerator,
+};
+use tokio::sync::Mutex;
+
+use crate::access_token::JwtClaims;
+
+const FAKE_HASH_PREFIX: &str = "fake$";
+const FAKE_TOKEN_PREFIX: &str = "fake";
+const FAKE_ACCOUNT_TOKEN_PREFIX: &str = "fake-account";
+
+/// Stores passwords with a marker prefix instead of hashing them
+#[derive(Debug, Clone, Default)]
+pub struct FakePasswordHasher;
+
+#[async_trait::async_trait]
+impl PasswordHasher for FakePasswordHasher {
+    async fn hash(&self, password: String) -> Result<HashedPassword, anyhow::Error> {
+        Ok(HashedPassword {
+            value: format!("{FAKE_HASH_PREFIX}{password}"),
+        })
+    }
+
+    async fn is_matches(
+        &self,
+        password: &str,
+        hashed_password: &HashedPassword,
+    ) -> Result<bool, anyhow::Error> {
+        let stored = hashed_password
+            .value
+            .strip_prefix(FAKE_HASH_PREFIX)
+            .context("invalid hashed password value")?;
+
+        Ok(stored == password)
+    }
+}
+
+/// Encodes claims as plain dot separated fields, expired tokens are rejected like jwt ones
+#[derive(Debug, Clone, Default)]
+pub struct FakeAccessTokenEngine;
+
+#[async_trait::async_trait]
+impl AccessTokenEngine for FakeAccessTokenEngine {
+    async fn encode(&self, claims: Claims) -> Result<String, anyhow::Error> {
+        let JwtClaims {
+            user_id,
+            email,
+            expires_at,
+            role,
+        } = claims.into();
+
+        Ok(format!(
+            "{FAKE_TOKEN_PREFIX}.{user_id}.{role}.{expires_at}.{email}"
+        ))
+    }
+
+    async fn decode(&self, token: &str) -> Result<Claims, anyhow::Error> {
+        let mut parts = token.splitn(5, '.');
+        let (Some(FAKE_TOKEN_PREFIX), Some(user_id), Some(role), Some(expires_at), Some(email)) = (
+            parts.next(),
+            parts.next(),
+            parts.next(),
+            parts.next(),
+            parts.next(),
+        ) else {
+            anyhow::bail!("malformed fake access token");
+        };
+
+        let claims: Claims = JwtClaims {
+            user_id: user_id.parse().context("invalid user id in fake token")?,
+            email: email.to_owned(),
+            expires_at: expires_at
+                .parse()
+                .context("invalid expiration in fake token")?,
+            role: role.to_owned(),
+        }
+        .try_into()?;
+
+        if claims.expires_at.is_expired()? {
+            anyhow::bail!("fake access token is expired");
+        }
+
+        Ok(claims)
+    }
+}
+
+/// Yields `refresh-1`, `refresh-2`, ..., the counter is shared by every generator of a module
+#[derive(Debug, Clone, Default)]
+pub struct FakeRefreshTokenGenerator {
+    pub(crate) counter: Arc<AtomicU64>,
+}
+
+#[async_trait::async_trait]
+impl RefreshTokenGenerator for FakeRefreshTokenGenerator {
+    async fn generate(&self) -> Result<String, anyhow::Error> {
+        let n = self.counter.fetch_add(1, Ordering::Relaxed) + 1;
+        Ok(format!("refresh-{n}"))
+    }
+}
+
+/// Yields `device-1`, `device-2`, ..., the counter is shared by every generator of a module
+#[derive(Debug, Clone, Default)]
+pub struct FakeDeviceIdGenerator {
+    pub(crate) counter: Arc<AtomicU64>,
+}
+
+impl DeviceIdGenerator for FakeDeviceIdGenerator {
+    fn generate(&self) -> String {
+        let n = self.counter.fetch_add(1, Ordering::Relaxed) + 1;
+        format!("device-{n}")
+    }
+}
+
+/// Keeps the token readable behind a marker prefix, like the fake password hasher
+#[derive(Debug, Clone, Default)]
+pub struct FakeRefreshTokenHasher;
+
+impl RefreshTokenHasher for FakeRefreshTokenHasher {
+    fn hash(&self, token: &str) -> String {
+        format!("{FAKE_HASH_PREFIX}{token}")
+    }
+}
+
+/// Encodes claims as plain dot separated fields, only the prefix is checked instead of a signature
+#[derive(Debug, Clone, Default)]
+pub struct FakeAccountTokenEngine;
+
+#[async_trait::async_trait]
+impl AccountTokenEngine for FakeAccountTokenEngine {
+    async fn encode(&self, claims: AccountTokenClaims) -> Result<String, anyhow::Error> {
+        let purpose = match claims.purpose {
+            Purpose::EmailVerification => "email",
+            Purpose::PasswordReset => "password",
+        };
+
+        Ok(format!(
+            "{FAKE_ACCOUNT_TOKEN_PREFIX}.{}.{}.{purpose}.{}",
+            claims.token_id, claims.user_id, claims.expires_at.seconds.val
+        ))
+    }
+
+    async fn decode(&self, token: &str) -> Result<Option<AccountTokenClaims>, anyhow::Error> {
+        let mut parts = token.split('.');
+        let (
+            Some(FAKE_ACCOUNT_TOKEN_PREFIX),
+            Some(token_id),
+            Some(user_id),
+            Some(purpose),
+            Some(expires_at),
+            None,
+        ) = (
+            parts.next(),
+            parts.next(),
+            parts.next(),
+            parts.next(),
+            parts.next(),
+            parts.next(),
+        )
+        else {
+            return Ok(None);
+        };
+
+        let purpose = match purpose {
+            "email" => Purpose::EmailVerification,
+            "password" => Purpose::PasswordReset,
+            _ => return Ok(None),
+        };
+        let (Ok(token_id), Ok(user_id), Ok(expires_at)) =
+            (token_id.parse(), user_id.parse(), expires_at.parse::<u64>())
+        else {
+            return Ok(None);
+        };
+
+        Ok(Some(AccountTokenClaims {
+            token_id,
+            user_id,
+            purpose,
+            expires_at: expires_at.into(),
+        }))
+    }
+}
+
+/// Keeps the letters instead of sending them, the outbox is shared by every sender of a module
+#[derive(Debug, Clone, Default)]
+pub struct FakeMailSender {
+    pub(crate) outbox: Arc<Mutex<Vec<Mail>>>,
+}
+
+#[async_trait::async_trait]
+impl MailSender for FakeMailSender {
+    async fn send(&self, mail: Mail) -> Result<(), anyhow::Error> {
+        self.outbox.lock().await.push(mail);
+        Ok(())
+    }
+}
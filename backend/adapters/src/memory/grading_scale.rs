@@ -0,0 +1,63 @@
+use std::sync::Arc;
+
+use app::{
+    grading_scale::{self, Entity, EntityId},
+    university,
+};
+use tokio::sync::Mutex;
+use utils::entity::Id;
+
+use super::tables::{check, ConstraintViolation, Tables};
+
+pub struct MemoryGradingScaleRepo {
+    pub(crate) tables: Arc<Mutex<Tables>>,
+}
+
+#[async_trait::async_trait]
+impl grading_scale::Repo for MemoryGradingScaleRepo {
+    async fn save(&mut self, mut entity: Entity) -> Result<Entity, anyhow::Error> {
+        let mut tables = self.tables.lock().await;
+        entity.id = Id::new(tables.grading_scales.resolve_id(entity.id.value));
+
+        check(
+            tables.universities.contains(&entity.university_id.value),
+            ConstraintViolation::ForeignKey("grading_scales_university_id_fkey"),
+        )?;
+        check(
+            !tables
+                .grading_scales
+                .values()
+                .any(|v| v.id != entity.id && v.university_id == entity.university_id),
+            ConstraintViolation::Unique("grading_scales_university_id_key"),
+        )?;
+
+        tables
+            .grading_scales
+            .rows
+            .insert(entity.id.value, entity.clone());
+        Ok(entity)
+    }
+
+    async fn delete(&mut self, entity: &Entity) -> Result<(), anyhow::Error> {
+        let mut tables = self.tables.lock().await;
+        tables.grading_scales.rows.remove(&entity.id.value);
+        Ok(())
+    }
+
+    async fn find(&self, id: EntityId) -> Result<Option<Entity>, anyhow::Error> {
+        let tables = self.tables.lock().await;
+        Ok(tables.grading_scales.rows.get(&id.value).cloned())
+    }
+
+    async fn find_by_university(
+        &self,
+        university_id: university::EntityId,
+    ) -> Result<Option<Entity>, anyhow::Error> {
+        let tables = self.tables.lock().await;
+        Ok(tables
+            .grading_scales
+            .values()
+            .find(|v| v.university_id == university_id)
+            .cloned())
+    }
+}
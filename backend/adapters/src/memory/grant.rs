@@ -0,0 +1,103 @@
+use std::sync::Arc;
+
+use app::{
+    grant::{self, Entity, EntityId, Scope},
+    user,
+};
+use tokio::sync::Mutex;
+use utils::entity::Id;
+
+use super::tables::{check, ConstraintViolation, Tables};
+
+pub struct MemoryGrantRepo {
+    pub(crate) tables: Arc<Mutex<Tables>>,
+}
+
+#[async_trait::async_trait]
+impl grant::Repo for MemoryGrantRepo {
+    async fn save(&mut self, mut entity: Entity) -> Result<Entity, anyhow::Error> {
+        let mut tables = self.tables.lock().await;
+        entity.id = Id::new(tables.grants.resolve_id(entity.id.value));
+
+        check(
+            tables.users.contains(&entity.user_id.value),
+            ConstraintViolation::ForeignKey("user_grants_user_id_fkey"),
+        )?;
+
+        let (is_scope_present, constraint) = match entity.scope {
+            Scope::Global => (true, ""),
+            Scope::University(id) => (
+                tables.universities.contains(&id.value),
+                "user_grants_university_id_fkey",
+            ),
+            Scope::Subdivision(id) => (
+                tables.subdivisions.contains(&id.value),
+                "user_grants_subdivision_id_fkey",
+            ),
+            Scope::StudyGroup(id) => (
+                tables.study_groups.contains(&id.value),
+                "user_grants_study_group_id_fkey",
+            ),
+        };
+        check(
+            is_scope_present,
+            ConstraintViolation::ForeignKey(constraint),
+        )?;
+
+        check(
+            !tables.grants.values().any(|v| {
+                v.id != entity.id
+                    && v.user_id == entity.user_id
+                    && v.role == entity.role
+                    && v.scope == entity.scope
+            }),
+            ConstraintViolation::Unique(
+                "user_grants_user_id_role_university_id_subdivision_id_study_key",
+            ),
+        )?;
+
+        tables.grants.rows.insert(entity.id.value, entity.clone());
+        Ok(entity)
+    }
+
+    async fn delete(&mut self, entity: &Entity) -> Result<(), anyhow::Error> {
+        let mut tables = self.tables.lock().await;
+        tables.grants.rows.remove(&entity.id.value);
+        Ok(())
+    }
+
+    async fn delete_by_scope(&mut self, scope: Scope) -> Result<(), anyhow::Error> {
+        let mut tables = self.tables.lock().await;
+        tables.grants.rows.retain(|_, v| v.scope != scope);
+        Ok(())
+    }
+
+    async fn find(&self, id: EntityId) -> Result<Option<Entity>, anyhow::Error> {
+        let tables = self.tables.lock().await;
+        Ok(tables.grants.rows.get(&id.value).cloned())
+    }
+
+    async fn list_by_user(&self, user_id: user::EntityId) -> Result<Vec<Entity>, anyhow::Error> {
+        let tables = self.tables.lock().await;
+        let entities = tables
+            .grants
+            .values()
+            .filter(|v| v.user_id == user_id)
+            .cloned()
+            .collect();
+
+        Ok(entities)
+    }
+
+    async fn list_by_scope(&self, scope: Scope) -> Result<Vec<Entity>, anyhow::Error> {
+        let tables = self.tables.lock().await;
+        let entities = tables
+            .grants
+            .values()
+            .filter(|v| v.scope == scope)
+            .cloned()
+            .collect();
+
+        Ok(entities)
+    }
+}
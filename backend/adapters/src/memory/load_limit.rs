@@ -0,0 +1,69 @@
+use std::sync::Arc;
+
+use app::{
+    load_limit::{self, Entity, EntityId},
+    university,
+};
+use tokio::sync::Mutex;
+use utils::entity::Id;
+
+use super::tables::{check, ConstraintViolation, Tables};
+
+pub struct MemoryLoadLimitRepo {
+    pub(crate) tables: Arc<Mutex<Tables>>,
+}
+
+#[async_trait::async_trait]
+impl load_limit::Repo for MemoryLoadLimitRepo {
+    async fn save(&mut self, mut entity: Entity) -> Result<Entity, anyhow::Error> {
+        let mut tables = self.tables.lock().await;
+        entity.id = Id::new(tables.load_limits.resolve_id(entity.id.value));
+
+        check(
+            tables.universities.contains(&entity.university_id.value),
+            ConstraintViolation::ForeignKey("load_limits_university_id_fkey"),
+        )?;
+        check(
+            entity.is_valid(),
+            ConstraintViolation::Check("load_limits_check"),
+        )?;
+        check(
+            !tables.load_limits.values().any(|v| {
+                v.id != entity.id
+                    && v.university_id == entity.university_id
+                    && v.teacher_kind == entity.teacher_kind
+            }),
+            ConstraintViolation::Unique("load_limits_university_id_teacher_kind_key"),
+        )?;
+
+        tables
+            .load_limits
+            .rows
+            .insert(entity.id.value, entity.clone());
+        Ok(entity)
+    }
+
+    async fn delete(&mut self, entity: &Entity) -> Result<(), anyhow::Error> {
+        let mut tables = self.tables.lock().await;
+        tables.load_limits.rows.remove(&entity.id.value);
+        Ok(())
+    }
+
+    async fn find(&self, id: EntityId) -> Result<Option<Entity>, anyhow::Error> {
+        let tables = self.tables.lock().await;
+        Ok(tables.load_limits.rows.get(&id.value).cloned())
+    }
+
+    async fn list_by_university(
+        &self,
+        university_id: university::EntityId,
+    ) -> Result<Vec<Entity>, anyhow::Error> {
+        let tables = self.tables.lock().await;
+        Ok(tables
+            .load_limits
+            .values()
+            .filter(|v| v.university_id == university_id)
+            .cloned()
+            .collect())
+    }
+}
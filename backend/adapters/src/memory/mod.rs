@@ -0,0 +1,482 @@
+//! In-memory adapters for running the application services without a database
+
+mod academic_year;
+mod account_token;
+mod attestation;
+mod attestation_session;
+mod calendar_feed;
+mod class;
+mod class_kind;
+mod curriculum;
+mod curriculum_module;
+mod discipline;
+mod fakes;
+mod grading_scale;
+mod grant;
+mod load_limit;
+mod passport;
+mod person;
+mod room;
+mod schedule;
+mod student;
+mod student_order;
+mod study_group;
+mod subdivision;
+mod tables;
+mod tag;
+mod teacher;
+mod timetable_draft;
+mod university;
+mod user;
+mod user_session;
+
+use std::sync::{atomic::AtomicU64, Arc};
+
+use app::{
+    account_token::{EmailVerificationTTL, PasswordResetTTL},
+    curriculum::SemesterHoursBounds,
+    login_attempt::LoginThrottling,
+    mail::{LinkBaseUrl, Mail},
+    token::AccessTokenTTL,
+    user_session::{SessionTTL, SessionsMaxNumber},
+};
+use tokio::sync::Mutex;
+use utils::di::{Module, Provide};
+
+use crate::login_attempt::{LoginAttemptCounters, MemoryLoginAttemptStore};
+
+use academic_year::MemoryAcademicYearRepo;
+use account_token::MemoryAccountTokenRepo;
+use attestation::MemoryAttestationRepo;
+use attestation_session::MemoryAttestationSessionRepo;
+use calendar_feed::MemoryCalendarFeedRepo;
+use class::MemoryClassRepo;
+use class_kind::MemoryClassKindRepo;
+use curriculum::MemoryCurriculumRepo;
+use curriculum_module::MemoryCurriculumModuleRepo;
+use discipline::MemoryDisciplineRepo;
+use grading_scale::MemoryGradingScaleRepo;
+use grant::MemoryGrantRepo;
+use load_limit::MemoryLoadLimitRepo;
+use passport::MemoryPassportRepo;
+use person::MemoryPersonRepo;
+use room::MemoryRoomRepo;
+use schedule::MemoryScheduleRepo;
+use student::MemoryStudentRepo;
+use student_order::MemoryStudentOrderRepo;
+use study_group::MemoryStudyGroupRepo;
+use subdivision::MemorySubdivisionRepo;
+use tables::Tables;
+use tag::MemoryTagRepo;
+use teacher::MemoryTeacherRepo;
+use timetable_draft::MemoryTimetableDraftRepo;
+use university::MemoryUniversityRepo;
+use user::MemoryUserRepo;
+use user_session::MemoryUserSessionRepo;
+
+pub use fakes::{
+    FakeAccessTokenEngine, FakeAccountTokenEngine, FakeDeviceIdGenerator, FakeMailSender,
+    FakePasswordHasher, FakeRefreshTokenGenerator, FakeRefreshTokenHasher,
+};
+pub use tables::ConstraintViolation;
+
+const DEFAULT_ACCESS_TOKEN_TTL_SECS: u64 = 15 * 60;
+const DEFAULT_SESSION_TTL_SECS: u64 = 30 * 24 * 60 * 60;
+const DEFAULT_SESSIONS_MAX_NUMBER: i64 = 5;
+const DEFAULT_LOGIN_ACCOUNT_MAX_FAILURES: u32 = 5;
+const DEFAULT_LOGIN_IP_MAX_FAILURES: u32 = 20;
+const DEFAULT_LOGIN_LOCKOUT_SECS: u64 = 30;
+const DEFAULT_LOGIN_MAX_LOCKOUT_SECS: u64 = 60 * 60;
+const DEFAULT_EMAIL_VERIFICATION_TTL_SECS: u64 = 24 * 60 * 60;
+const DEFAULT_PASSWORD_RESET_TTL_SECS: u64 = 60 * 60;
+const DEFAULT_LINK_BASE_URL: &str = "http://localhost";
+/// 30 credit units of 36 hours
+const DEFAULT_SEMESTER_MAX_HOURS: i32 = 30 * 36;
+
+/// Adapters keeping all rows in memory, clones share the same tables
+///
+/// Writes are applied immediately, there are no transactions to commit or roll back
+#[derive(Debug, Clone)]
+pub struct MemoryModule {
+    pub access_token_ttl: AccessTokenTTL,
+    pub session_ttl: SessionTTL,
+    pub sessions_max_number: SessionsMaxNumber,
+    pub login_throttling: LoginThrottling,
+    pub semester_hours_bounds: SemesterHoursBounds,
+    pub email_verification_ttl: EmailVerificationTTL,
+    pub password_reset_ttl: PasswordResetTTL,
+    pub link_base_url: LinkBaseUrl,
+    tables: Arc<Mutex<Tables>>,
+    refresh_tokens: Arc<AtomicU64>,
+    device_ids: Arc<AtomicU64>,
+    login_attempts: LoginAttemptCounters,
+    outbox: Arc<Mutex<Vec<Mail>>>,
+}
+
+impl MemoryModule {
+    pub fn new() -> Self {
+        Self {
+            access_token_ttl: AccessTokenTTL(DEFAULT_ACCESS_TOKEN_TTL_SECS.into()),
+            session_ttl: SessionTTL(DEFAULT_SESSION_TTL_SECS.into()),
+            sessions_max_number: SessionsMaxNumber(DEFAULT_SESSIONS_MAX_NUMBER),
+            login_throttling: LoginThrottling {
+                account_max_failures: DEFAULT_LOGIN_ACCOUNT_MAX_FAILURES,
+                ip_max_failures: DEFAULT_LOGIN_IP_MAX_FAILURES,
+                lockout: DEFAULT_LOGIN_LOCKOUT_SECS.into(),
+                max_lockout: DEFAULT_LOGIN_MAX_LOCKOUT_SECS.into(),
+            },
+            semester_hours_bounds: SemesterHoursBounds {
+                min: 0,
+                max: DEFAULT_SEMESTER_MAX_HOURS,
+            },
+            email_verification_ttl: EmailVerificationTTL(
+                DEFAULT_EMAIL_VERIFICATION_TTL_SECS.into(),
+            ),
+            password_reset_ttl: PasswordResetTTL(DEFAULT_PASSWORD_RESET_TTL_SECS.into()),
+            link_base_url: LinkBaseUrl(DEFAULT_LINK_BASE_URL.into()),
+            tables: Arc::default(),
+            refresh_tokens: Arc::default(),
+            device_ids: Arc::default(),
+            login_attempts: Arc::default(),
+            outbox: Arc::default(),
+        }
+    }
+
+    /// Letters sent through the module so far, oldest first
+    pub async fn sent_mails(&self) -> Vec<Mail> {
+        self.outbox.lock().await.clone()
+    }
+}
+
+impl Default for MemoryModule {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Module for MemoryModule {}
+
+impl app::AdaptersModule for MemoryModule {}
+
+impl Provide<app::attestation::BoxedRepo> for MemoryModule {
+    fn provide(&self) -> app::attestation::BoxedRepo {
+        Box::new(MemoryAttestationRepo {
+            tables: Arc::clone(&self.tables),
+        })
+    }
+}
+
+impl Provide<app::class::BoxedRepo> for MemoryModule {
+    fn provide(&self) -> app::class::BoxedRepo {
+        Box::new(MemoryClassRepo {
+            tables: Arc::clone(&self.tables),
+        })
+    }
+}
+
+impl Provide<app::class_kind::BoxedRepo> for MemoryModule {
+    fn provide(&self) -> app::class_kind::BoxedRepo {
+        Box::new(MemoryClassKindRepo {
+            tables: Arc::clone(&self.tables),
+        })
+    }
+}
+
+impl Provide<app::curriculum::BoxedRepo> for MemoryModule {
+    fn provide(&self) -> app::curriculum::BoxedRepo {
+        Box::new(MemoryCurriculumRepo {
+            tables: Arc::clone(&self.tables),
+        })
+    }
+}
+
+impl Provide<app::curriculum_module::BoxedRepo> for MemoryModule {
+    fn provide(&self) -> app::curriculum_module::BoxedRepo {
+        Box::new(MemoryCurriculumModuleRepo {
+            tables: Arc::clone(&self.tables),
+        })
+    }
+}
+
+impl Provide<app::discipline::BoxedRepo> for MemoryModule {
+    fn provide(&self) -> app::discipline::BoxedRepo {
+        Box::new(MemoryDisciplineRepo {
+            tables: Arc::clone(&self.tables),
+        })
+    }
+}
+
+impl Provide<app::grading_scale::BoxedRepo> for MemoryModule {
+    fn provide(&self) -> app::grading_scale::BoxedRepo {
+        Box::new(MemoryGradingScaleRepo {
+            tables: Arc::clone(&self.tables),
+        })
+    }
+}
+
+impl Provide<app::load_limit::BoxedRepo> for MemoryModule {
+    fn provide(&self) -> app::load_limit::BoxedRepo {
+        Box::new(MemoryLoadLimitRepo {
+            tables: Arc::clone(&self.tables),
+        })
+    }
+}
+
+impl Provide<app::academic_year::BoxedRepo> for MemoryModule {
+    fn provide(&self) -> app::academic_year::BoxedRepo {
+        Box::new(MemoryAcademicYearRepo {
+            tables: Arc::clone(&self.tables),
+        })
+    }
+}
+
+impl Provide<app::student_order::BoxedRepo> for MemoryModule {
+    fn provide(&self) -> app::student_order::BoxedRepo {
+        Box::new(MemoryStudentOrderRepo {
+            tables: Arc::clone(&self.tables),
+        })
+    }
+}
+
+impl Provide<app::account_token::BoxedRepo> for MemoryModule {
+    fn provide(&self) -> app::account_token::BoxedRepo {
+        Box::new(MemoryAccountTokenRepo {
+            tables: Arc::clone(&self.tables),
+        })
+    }
+}
+
+impl Provide<app::room::BoxedRepo> for MemoryModule {
+    fn provide(&self) -> app::room::BoxedRepo {
+        Box::new(MemoryRoomRepo {
+            tables: Arc::clone(&self.tables),
+        })
+    }
+}
+
+impl Provide<app::schedule::BoxedRepo> for MemoryModule {
+    fn provide(&self) -> app::schedule::BoxedRepo {
+        Box::new(MemoryScheduleRepo {
+            tables: Arc::clone(&self.tables),
+        })
+    }
+}
+
+impl Provide<app::timetable_draft::BoxedRepo> for MemoryModule {
+    fn provide(&self) -> app::timetable_draft::BoxedRepo {
+        Box::new(MemoryTimetableDraftRepo {
+            tables: Arc::clone(&self.tables),
+        })
+    }
+}
+
+impl Provide<app::attestation_session::BoxedRepo> for MemoryModule {
+    fn provide(&self) -> app::attestation_session::BoxedRepo {
+        Box::new(MemoryAttestationSessionRepo {
+            tables: Arc::clone(&self.tables),
+        })
+    }
+}
+
+impl Provide<app::calendar_feed::BoxedRepo> for MemoryModule {
+    fn provide(&self) -> app::calendar_feed::BoxedRepo {
+        Box::new(MemoryCalendarFeedRepo {
+            tables: Arc::clone(&self.tables),
+        })
+    }
+}
+
+impl Provide<app::grant::BoxedRepo> for MemoryModule {
+    fn provide(&self) -> app::grant::BoxedRepo {
+        Box::new(MemoryGrantRepo {
+            tables: Arc::clone(&self.tables),
+        })
+    }
+}
+
+impl Provide<app::passport::BoxedRepo> for MemoryModule {
+    fn provide(&self) -> app::passport::BoxedRepo {
+        Box::new(MemoryPassportRepo {
+            tables: Arc::clone(&self.tables),
+        })
+    }
+}
+
+impl Provide<app::person::BoxedRepo> for MemoryModule {
+    fn provide(&self) -> app::person::BoxedRepo {
+        Box::new(MemoryPersonRepo {
+            tables: Arc::clone(&self.tables),
+        })
+    }
+}
+
+impl Provide<app::student::BoxedRepo> for MemoryModule {
+    fn provide(&self) -> app::student::BoxedRepo {
+        Box::new(MemoryStudentRepo {
+            tables: Arc::clone(&self.tables),
+        })
+    }
+}
+
+impl Provide<app::study_group::BoxedRepo> for MemoryModule {
+    fn provide(&self) -> app::study_group::BoxedRepo {
+        Box::new(MemoryStudyGroupRepo {
+            tables: Arc::clone(&self.tables),
+        })
+    }
+}
+
+impl Provide<app::subdivision::BoxedRepo> for MemoryModule {
+    fn provide(&self) -> app::subdivision::BoxedRepo {
+        Box::new(MemorySubdivisionRepo {
+            tables: Arc::clone(&self.tables),
+        })
+    }
+}
+
+impl Provide<app::tag::BoxedRepo> for MemoryModule {
+    fn provide(&self) -> app::tag::BoxedRepo {
+        Box::new(MemoryTagRepo {
+            tables: Arc::clone(&self.tables),
+        })
+    }
+}
+
+impl Provide<app::teacher::BoxedRepo> for MemoryModule {
+    fn provide(&self) -> app::teacher::BoxedRepo {
+        Box::new(MemoryTeacherRepo {
+            tables: Arc::clone(&self.tables),
+        })
+    }
+}
+
+impl Provide<app::university::BoxedRepo> for MemoryModule {
+    fn provide(&self) -> app::university::BoxedRepo {
+        Box::new(MemoryUniversityRepo {
+            tables: Arc::clone(&self.tables),
+        })
+    }
+}
+
+impl Provide<app::user::BoxedRepo> for MemoryModule {
+    fn provide(&self) -> app::user::BoxedRepo {
+        Box::new(MemoryUserRepo {
+            tables: Arc::clone(&self.tables),
+        })
+    }
+}
+
+impl Provide<app::user_session::BoxedRepo> for MemoryModule {
+    fn provide(&self) -> app::user_session::BoxedRepo {
+        Box::new(MemoryUserSessionRepo {
+            tables: Arc::clone(&self.tables),
+        })
+    }
+}
+
+impl Provide<app::hasher::BoxedPasswordHasher> for MemoryModule {
+    fn provide(&self) -> app::hasher::BoxedPasswordHasher {
+        Box::new(FakePasswordHasher)
+    }
+}
+
+impl Provide<app::token::BoxedAccessTokenEngine> for MemoryModule {
+    fn provide(&self) -> app::token::BoxedAccessTokenEngine {
+        Box::new(FakeAccessTokenEngine)
+    }
+}
+
+impl Provide<app::token::BoxedRefreshTokenGenerator> for MemoryModule {
+    fn provide(&self) -> app::token::BoxedRefreshTokenGenerator {
+        Box::new(FakeRefreshTokenGenerator {
+            counter: Arc::clone(&self.refresh_tokens),
+        })
+    }
+}
+
+impl Provide<app::user_session::BoxedDeviceIdGenerator> for MemoryModule {
+    fn provide(&self) -> app::user_session::BoxedDeviceIdGenerator {
+        Box::new(FakeDeviceIdGenerator {
+            counter: Arc::clone(&self.device_ids),
+        })
+    }
+}
+
+impl Provide<app::token::BoxedRefreshTokenHasher> for MemoryModule {
+    fn provide(&self) -> app::token::BoxedRefreshTokenHasher {
+        Box::new(FakeRefreshTokenHasher)
+    }
+}
+
+impl Provide<app::token::BoxedAccountTokenEngine> for MemoryModule {
+    fn provide(&self) -> app::token::BoxedAccountTokenEngine {
+        Box::new(FakeAccountTokenEngine)
+    }
+}
+
+impl Provide<app::mail::BoxedMailSender> for MemoryModule {
+    fn provide(&self) -> app::mail::BoxedMailSender {
+        Box::new(FakeMailSender {
+            outbox: Arc::clone(&self.outbox),
+        })
+    }
+}
+
+impl Provide<AccessTokenTTL> for MemoryModule {
+    fn provide(&self) -> AccessTokenTTL {
+        self.access_token_ttl
+    }
+}
+
+impl Provide<SessionTTL> for MemoryModule {
+    fn provide(&self) -> SessionTTL {
+        self.session_ttl
+    }
+}
+
+impl Provide<SessionsMaxNumber> for MemoryModule {
+    fn provide(&self) -> SessionsMaxNumber {
+        self.sessions_max_number
+    }
+}
+
+impl Provide<LoginThrottling> for MemoryModule {
+    fn provide(&self) -> LoginThrottling {
+        self.login_throttling
+    }
+}
+
+impl Provide<app::login_attempt::BoxedStore> for MemoryModule {
+    fn provide(&self) -> app::login_attempt::BoxedStore {
+        Box::new(MemoryLoginAttemptStore {
+            counters: Arc::clone(&self.login_attempts),
+        })
+    }
+}
+
+impl Provide<SemesterHoursBounds> for MemoryModule {
+    fn provide(&self) -> SemesterHoursBounds {
+        self.semester_hours_bounds
+    }
+}
+
+impl Provide<EmailVerificationTTL> for MemoryModule {
+    fn provide(&self) -> EmailVerificationTTL {
+        self.email_verification_ttl
+    }
+}
+
+impl Provide<PasswordResetTTL> for MemoryModule {
+    fn provide(&self) -> PasswordResetTTL {
+        self.password_reset_ttl
+    }
+}
+
+impl Provide<LinkBaseUrl> for MemoryModule {
+    fn provide(&self) -> LinkBaseUrl {
+        self.link_base_url.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,78 @@
+use std::sync::Arc;
+
+use app::{
+    passport::{self, Entity, EntityId, PassportNumber, PassportSeries},
+    person,
+};
+use tokio::sync::Mutex;
+use utils::entity::Id;
+
+use super::tables::{check, ConstraintViolation, Tables};
+
+pub struct MemoryPassportRepo {
+    pub(crate) tables: Arc<Mutex<Tables>>,
+}
+
+#[async_trait::async_trait]
+impl passport::Repo for MemoryPassportRepo {
+    async fn save(&mut self, mut entity: Entity) -> Result<Entity, anyhow::Error> {
+        let mut tables = self.tables.lock().await;
+        entity.id = Id::new(tables.passports.resolve_id(entity.id.value));
+
+        check(
+            tables.persons.contains(&entity.person_id.value),
+            ConstraintViolation::ForeignKey("passports_person_id_fkey"),
+        )?;
+        check(
+            !tables.passports.values().any(|v| {
+                v.id != entity.id && v.number == entity.number && v.series == entity.series
+            }),
+            ConstraintViolation::Unique("passports_number_series_key"),
+        )?;
+
+        tables
+            .passports
+            .rows
+            .insert(entity.id.value, entity.clone());
+        Ok(entity)
+    }
+
+    async fn delete(&mut self, entity: &Entity) -> Result<(), anyhow::Error> {
+        let mut tables = self.tables.lock().await;
+        tables.passports.rows.remove(&entity.id.value);
+        Ok(())
+    }
+
+    async fn find(&self, id: EntityId) -> Result<Option<Entity>, anyhow::Error> {
+        let tables = self.tables.lock().await;
+        Ok(tables.passports.rows.get(&id.value).cloned())
+    }
+
+    async fn find_by_number_series(
+        &self,
+        number: PassportNumber,
+        series: PassportSeries,
+    ) -> Result<Option<Entity>, anyhow::Error> {
+        let tables = self.tables.lock().await;
+        Ok(tables
+            .passports
+            .values()
+            .find(|v| v.number == number && v.series == series)
+            .cloned())
+    }
+
+    async fn list_by_person_id(
+        &self,
+        person_id: person::EntityId,
+    ) -> Result<Vec<Entity>, anyhow::Error> {
+        let tables = self.tables.lock().await;
+        let entities = tables
+            .passports
+            .values()
+            .filter(|v| v.person_id == person_id)
+            .cloned()
+            .collect();
+
+        Ok(entities)
+    }
+}
@@ -0,0 +1,143 @@
+use std::{collections::HashSet, sync::Arc};
+
+use app::{
+    paging::{Page, Paginated, Sort},
+    person::{self, Entity, EntityId, ListFilter, SortKey},
+    user,
+};
+use tokio::sync::Mutex;
+use utils::entity::Id;
+
+use super::tables::{check, has_prefix, paginate, sort_order, ConstraintViolation, Tables};
+
+pub struct MemoryPersonRepo {
+    pub(crate) tables: Arc<Mutex<Tables>>,
+}
+
+#[async_trait::async_trait]
+impl person::Repo for MemoryPersonRepo {
+    async fn save(&mut self, mut entity: Entity) -> Result<Entity, anyhow::Error> {
+        let mut tables = self.tables.lock().await;
+        entity.id = Id::new(tables.persons.resolve_id(entity.id.value));
+
+        check(
+            tables.users.contains(&entity.user_id.value),
+            ConstraintViolation::ForeignKey("persons_user_id_fkey"),
+        )?;
+        check(
+            !tables
+                .persons
+                .values()
+                .any(|v| v.id != entity.id && v.user_id == entity.user_id),
+            ConstraintViolation::Unique("persons_user_id_key"),
+        )?;
+
+        tables.persons.rows.insert(entity.id.value, entity.clone());
+        Ok(entity)
+    }
+
+    async fn delete(&mut self, entity: &Entity) -> Result<(), anyhow::Error> {
+        let mut tables = self.tables.lock().await;
+        let id = entity.id;
+
+        check(
+            !tables.passports.values().any(|v| v.person_id == id),
+            ConstraintViolation::ForeignKey("passports_person_id_fkey"),
+        )?;
+        check(
+            !tables
+                .subdivisions
+                .values()
+                .any(|v| v.members.iter().any(|m| m.person_id == id)),
+            ConstraintViolation::ForeignKey("subdivision_members_person_id_fkey"),
+        )?;
+        check(
+            !tables.teachers.values().any(|v| v.person_id == id),
+            ConstraintViolation::ForeignKey("teachers_person_id_fkey"),
+        )?;
+        check(
+            !tables.students.values().any(|v| v.person_id == id),
+            ConstraintViolation::ForeignKey("students_person_id_fkey"),
+        )?;
+
+        tables.calendar_feeds.rows.retain(|_, v| v.person_id != id);
+        tables.persons.rows.remove(&id.value);
+        Ok(())
+    }
+
+    async fn find(&self, id: EntityId) -> Result<Option<Entity>, anyhow::Error> {
+        let tables = self.tables.lock().await;
+        Ok(tables.persons.rows.get(&id.value).cloned())
+    }
+
+    async fn find_many(&self, ids: HashSet<EntityId>) -> Result<Vec<Entity>, anyhow::Error> {
+        let tables = self.tables.lock().await;
+        let entities = tables
+            .persons
+            .values()
+            .filter(|v| ids.contains(&v.id))
+            .cloned()
+            .collect();
+
+        Ok(entities)
+    }
+
+    async fn list(
+        &self,
+        filter: ListFilter,
+        sort: Sort<SortKey>,
+        page: Page,
+    ) -> Result<Paginated<Entity>, anyhow::Error> {
+        let tables = self.tables.lock().await;
+
+        let within = filter.within.map(|within| {
+            let students = tables
+                .students
+                .values()
+                .filter(|v| within.study_group_ids.contains(&v.study_group_id))
+                .map(|v| v.person_id);
+            let teachers = tables
+                .teachers
+                .values()
+                .filter(|v| within.department_ids.contains(&v.department_id))
+                .map(|v| v.person_id);
+
+            students.chain(teachers).collect::<HashSet<_>>()
+        });
+
+        let mut entities = tables
+            .persons
+            .values()
+            .filter(|v| has_prefix(&v.full_name, &filter.name_prefix))
+            .filter(|v| {
+                within
+                    .as_ref()
+                    .map_or(true, |within| within.contains(&v.id))
+            })
+            .cloned()
+            .collect::<Vec<_>>();
+
+        entities.sort_by(|a, b| {
+            let ordering = match sort.key {
+                SortKey::Id => a.id.value.cmp(&b.id.value),
+                SortKey::FullName => a.full_name.cmp(&b.full_name),
+            };
+
+            sort_order(sort.order, ordering)
+        });
+
+        Ok(paginate(entities, page))
+    }
+
+    async fn find_by_user_id(
+        &self,
+        user_id: user::EntityId,
+    ) -> Result<Option<Entity>, anyhow::Error> {
+        let tables = self.tables.lock().await;
+        Ok(tables
+            .persons
+            .values()
+            .find(|v| v.user_id == user_id)
+            .cloned())
+    }
+}
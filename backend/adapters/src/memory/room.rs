@@ -0,0 +1,93 @@
+use std::sync::Arc;
+
+use app::{
+    room::{self, Entity, EntityId},
+    university,
+};
+use tokio::sync::Mutex;
+use utils::entity::Id;
+
+use super::tables::{check, ConstraintViolation, Tables};
+
+pub struct MemoryRoomRepo {
+    pub(crate) tables: Arc<Mutex<Tables>>,
+}
+
+#[async_trait::async_trait]
+impl room::Repo for MemoryRoomRepo {
+    async fn save(&mut self, mut entity: Entity) -> Result<Entity, anyhow::Error> {
+        let mut tables = self.tables.lock().await;
+        entity.id = Id::new(tables.rooms.resolve_id(entity.id.value));
+
+        check(
+            tables.universities.contains(&entity.university_id.value),
+            ConstraintViolation::ForeignKey("rooms_university_id_fkey"),
+        )?;
+        check(
+            !tables.rooms.values().any(|v| {
+                v.id != entity.id
+                    && v.university_id == entity.university_id
+                    && v.name == entity.name
+            }),
+            ConstraintViolation::Unique("rooms_university_id_name_key"),
+        )?;
+        check(
+            entity.capacity > 0,
+            ConstraintViolation::Check("rooms_capacity_check"),
+        )?;
+
+        tables.rooms.rows.insert(entity.id.value, entity.clone());
+        Ok(entity)
+    }
+
+    async fn delete(&mut self, entity: &Entity) -> Result<(), anyhow::Error> {
+        let mut tables = self.tables.lock().await;
+        let id = entity.id;
+
+        check(
+            !tables.schedules.values().any(|v| v.room_id == id),
+            ConstraintViolation::ForeignKey("schedule_entries_room_id_fkey"),
+        )?;
+        check(
+            !tables
+                .attestation_sessions
+                .values()
+                .any(|v| v.room_id == Some(id)),
+            ConstraintViolation::ForeignKey("attestation_sessions_room_id_fkey"),
+        )?;
+
+        tables.rooms.rows.remove(&id.value);
+        Ok(())
+    }
+
+    async fn find(&self, id: EntityId) -> Result<Option<Entity>, anyhow::Error> {
+        let tables = self.tables.lock().await;
+        Ok(tables.rooms.rows.get(&id.value).cloned())
+    }
+
+    async fn find_by_name(
+        &self,
+        university_id: university::EntityId,
+        name: String,
+    ) -> Result<Option<Entity>, anyhow::Error> {
+        let tables = self.tables.lock().await;
+        Ok(tables
+            .rooms
+            .values()
+            .find(|v| v.university_id == university_id && v.name == name)
+            .cloned())
+    }
+
+    async fn list_by_university(
+        &self,
+        university_id: university::EntityId,
+    ) -> Result<Vec<Entity>, anyhow::Error> {
+        let tables = self.tables.lock().await;
+        Ok(tables
+            .rooms
+            .values()
+            .filter(|v| v.university_id == university_id)
+            .cloned()
+            .collect())
+    }
+}
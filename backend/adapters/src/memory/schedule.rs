@@ -0,0 +1,99 @@
+use std::sync::Arc;
+
+use app::{
+    class, room,
+    schedule::{self, Entity, EntityId},
+    study_group, teacher,
+};
+use tokio::sync::Mutex;
+use utils::entity::Id;
+
+use super::tables::{check, ConstraintViolation, Tables};
+
+pub struct MemoryScheduleRepo {
+    pub(crate) tables: Arc<Mutex<Tables>>,
+}
+
+impl MemoryScheduleRepo {
+    async fn list_by(&self, predicate: impl Fn(&Entity) -> bool) -> Vec<Entity> {
+        let tables = self.tables.lock().await;
+        tables
+            .schedules
+            .values()
+            .filter(|v| predicate(v))
+            .cloned()
+            .collect()
+    }
+}
+
+#[async_trait::async_trait]
+impl schedule::Repo for MemoryScheduleRepo {
+    async fn save(&mut self, mut entity: Entity) -> Result<Entity, anyhow::Error> {
+        let mut tables = self.tables.lock().await;
+        entity.id = Id::new(tables.schedules.resolve_id(entity.id.value));
+
+        check(
+            tables.classes.contains(&entity.class_id.value),
+            ConstraintViolation::ForeignKey("schedule_entries_class_id_fkey"),
+        )?;
+        check(
+            tables.study_groups.contains(&entity.study_group_id.value),
+            ConstraintViolation::ForeignKey("schedule_entries_study_group_id_fkey"),
+        )?;
+        check(
+            tables.teachers.contains(&entity.teacher_id.value),
+            ConstraintViolation::ForeignKey("schedule_entries_teacher_id_fkey"),
+        )?;
+        check(
+            tables.rooms.contains(&entity.room_id.value),
+            ConstraintViolation::ForeignKey("schedule_entries_room_id_fkey"),
+        )?;
+        check(
+            entity.slot.is_valid(),
+            ConstraintViolation::Check("schedule_entries_check"),
+        )?;
+        check(
+            entity.is_valid_period(),
+            ConstraintViolation::Check("schedule_entries_check1"),
+        )?;
+
+        tables
+            .schedules
+            .rows
+            .insert(entity.id.value, entity.clone());
+        Ok(entity)
+    }
+
+    async fn delete(&mut self, entity: &Entity) -> Result<(), anyhow::Error> {
+        let mut tables = self.tables.lock().await;
+        tables.schedules.rows.remove(&entity.id.value);
+        Ok(())
+    }
+
+    async fn find(&self, id: EntityId) -> Result<Option<Entity>, anyhow::Error> {
+        let tables = self.tables.lock().await;
+        Ok(tables.schedules.rows.get(&id.value).cloned())
+    }
+
+    async fn list_by_class(&self, class_id: class::EntityId) -> Result<Vec<Entity>, anyhow::Error> {
+        Ok(self.list_by(|v| v.class_id == class_id).await)
+    }
+
+    async fn list_by_study_group(
+        &self,
+        study_group_id: study_group::EntityId,
+    ) -> Result<Vec<Entity>, anyhow::Error> {
+        Ok(self.list_by(|v| v.study_group_id == study_group_id).await)
+    }
+
+    async fn list_by_teacher(
+        &self,
+        teacher_id: teacher::EntityId,
+    ) -> Result<Vec<Entity>, anyhow::Error> {
+        Ok(self.list_by(|v| v.teacher_id == teacher_id).await)
+    }
+
+    async fn list_by_room(&self, room_id: room::EntityId) -> Result<Vec<Entity>, anyhow::Error> {
+        Ok(self.list_by(|v| v.room_id == room_id).await)
+    }
+}
@@ -0,0 +1,146 @@
+use std::{collections::HashSet, sync::Arc};
+
+use app::{
+    attestation, person,
+    student::{self, Entity, EntityId},
+    study_group,
+};
+use tokio::sync::Mutex;
+use utils::entity::Id;
+
+use super::tables::{check, ConstraintViolation, Tables};
+
+pub struct MemoryStudentRepo {
+    pub(crate) tables: Arc<Mutex<Tables>>,
+}
+
+#[async_trait::async_trait]
+impl student::Repo for MemoryStudentRepo {
+    async fn save(&mut self, mut entity: Entity) -> Result<Entity, anyhow::Error> {
+        let mut tables = self.tables.lock().await;
+        entity.id = Id::new(tables.students.resolve_id(entity.id.value));
+
+        check(
+            tables.persons.contains(&entity.person_id.value),
+            ConstraintViolation::ForeignKey("students_person_id_fkey"),
+        )?;
+        check(
+            tables.study_groups.contains(&entity.study_group_id.value),
+            ConstraintViolation::ForeignKey("students_study_group_id_fkey"),
+        )?;
+        check(
+            !tables.students.values().any(|v| {
+                v.id != entity.id
+                    && v.person_id == entity.person_id
+                    && v.study_group_id == entity.study_group_id
+            }),
+            ConstraintViolation::Unique("students_person_id_study_group_id_key"),
+        )?;
+        check(
+            entity
+                .attestations
+                .iter()
+                .all(|a| tables.attestations.contains(&a.attestation_id.value)),
+            ConstraintViolation::ForeignKey("student_attestations_attestation_id_fkey"),
+        )?;
+        check(
+            entity
+                .attestations
+                .iter()
+                .all(|a| (0..=100).contains(&a.score)),
+            ConstraintViolation::Check("students_attestations_score_check"),
+        )?;
+        check(
+            entity.attestations.len()
+                == entity
+                    .attestations
+                    .iter()
+                    .map(|a| a.attestation_id)
+                    .collect::<HashSet<_>>()
+                    .len(),
+            ConstraintViolation::Unique("student_attestations_pkey"),
+        )?;
+
+        tables.students.rows.insert(entity.id.value, entity.clone());
+        Ok(entity)
+    }
+
+    async fn delete(&mut self, entity: &Entity) -> Result<(), anyhow::Error> {
+        let mut tables = self.tables.lock().await;
+        check(
+            !tables
+                .student_orders
+                .values()
+                .any(|v| v.student_id == entity.id),
+            ConstraintViolation::ForeignKey("student_orders_student_id_fkey"),
+        )?;
+        tables.students.rows.remove(&entity.id.value);
+        Ok(())
+    }
+
+    async fn find(&self, id: EntityId) -> Result<Option<Entity>, anyhow::Error> {
+        let tables = self.tables.lock().await;
+        Ok(tables.students.rows.get(&id.value).cloned())
+    }
+
+    async fn find_many(&self, ids: HashSet<EntityId>) -> Result<Vec<Entity>, anyhow::Error> {
+        let tables = self.tables.lock().await;
+        let entities = tables
+            .students
+            .values()
+            .filter(|v| ids.contains(&v.id))
+            .cloned()
+            .collect();
+
+        Ok(entities)
+    }
+
+    async fn list_by_person(
+        &self,
+        person_id: person::EntityId,
+    ) -> Result<Vec<Entity>, anyhow::Error> {
+        let tables = self.tables.lock().await;
+        let entities = tables
+            .students
+            .values()
+            .filter(|v| v.person_id == person_id)
+            .cloned()
+            .collect();
+
+        Ok(entities)
+    }
+
+    async fn list_by_study_group(
+        &self,
+        study_group_id: study_group::EntityId,
+    ) -> Result<Vec<Entity>, anyhow::Error> {
+        let tables = self.tables.lock().await;
+        let entities = tables
+            .students
+            .values()
+            .filter(|v| v.study_group_id == study_group_id)
+            .cloned()
+            .collect();
+
+        Ok(entities)
+    }
+
+    async fn list_by_attestations(
+        &self,
+        attestations_ids: HashSet<attestation::EntityId>,
+    ) -> Result<Vec<Entity>, anyhow::Error> {
+        let tables = self.tables.lock().await;
+        let entities = tables
+            .students
+            .values()
+            .filter(|v| {
+                v.attestations
+                    .iter()
+                    .any(|a| attestations_ids.contains(&a.attestation_id))
+            })
+            .cloned()
+            .collect();
+
+        Ok(entities)
+    }
+}
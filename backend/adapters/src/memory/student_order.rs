@@ -0,0 +1,113 @@
+use std::{collections::HashSet, sync::Arc};
+
+use app::{
+    student,
+    student_order::{self, Entity, EntityId},
+    study_group,
+};
+use tokio::sync::Mutex;
+use utils::entity::Id;
+
+use super::tables::{check, ConstraintViolation, Tables};
+
+pub struct MemoryStudentOrderRepo {
+    pub(crate) tables: Arc<Mutex<Tables>>,
+}
+
+impl MemoryStudentOrderRepo {
+    fn sorted(mut entities: Vec<Entity>) -> Vec<Entity> {
+        entities.sort_by_key(|v| (v.date, v.id.value));
+        entities
+    }
+}
+
+#[async_trait::async_trait]
+impl student_order::Repo for MemoryStudentOrderRepo {
+    async fn save(&mut self, mut entity: Entity) -> Result<Entity, anyhow::Error> {
+        let mut tables = self.tables.lock().await;
+        entity.id = Id::new(tables.student_orders.resolve_id(entity.id.value));
+
+        check(
+            tables.students.contains(&entity.student_id.value),
+            ConstraintViolation::ForeignKey("student_orders_student_id_fkey"),
+        )?;
+        check(
+            tables.study_groups.contains(&entity.study_group_id.value),
+            ConstraintViolation::ForeignKey("student_orders_study_group_id_fkey"),
+        )?;
+        check(
+            entity
+                .author_id
+                .map_or(true, |v| tables.users.contains(&v.value)),
+            ConstraintViolation::ForeignKey("student_orders_author_id_fkey"),
+        )?;
+
+        tables
+            .student_orders
+            .rows
+            .insert(entity.id.value, entity.clone());
+        Ok(entity)
+    }
+
+    async fn find(&self, id: EntityId) -> Result<Option<Entity>, anyhow::Error> {
+        let tables = self.tables.lock().await;
+        Ok(tables.student_orders.rows.get(&id.value).cloned())
+    }
+
+    async fn list_by_student(
+        &self,
+        student_id: student::EntityId,
+    ) -> Result<Vec<Entity>, anyhow::Error> {
+        let tables = self.tables.lock().await;
+        let entities = tables
+            .student_orders
+            .values()
+            .filter(|v| v.student_id == student_id)
+            .cloned()
+            .collect();
+
+        Ok(Self::sorted(entities))
+    }
+
+    async fn list_by_students(
+        &self,
+        students_ids: HashSet<student::EntityId>,
+    ) -> Result<Vec<Entity>, anyhow::Error> {
+        let tables = self.tables.lock().await;
+        let entities = tables
+            .student_orders
+            .values()
+            .filter(|v| students_ids.contains(&v.student_id))
+            .cloned()
+            .collect();
+
+        Ok(Self::sorted(entities))
+    }
+
+    async fn list_by_study_group(
+        &self,
+        study_group_id: study_group::EntityId,
+    ) -> Result<Vec<Entity>, anyhow::Error> {
+        let tables = self.tables.lock().await;
+        let entities = tables
+            .student_orders
+            .values()
+            .filter(|v| v.study_group_id == study_group_id)
+            .cloned()
+            .collect();
+
+        Ok(Self::sorted(entities))
+    }
+
+    async fn delete_by_student(
+        &mut self,
+        student_id: student::EntityId,
+    ) -> Result<(), anyhow::Error> {
+        let mut tables = self.tables.lock().await;
+        tables
+            .student_orders
+            .rows
+            .retain(|_, v| v.student_id != student_id);
+        Ok(())
+    }
+}
@@ -0,0 +1,195 @@
+use std::{collections::HashSet, sync::Arc};
+
+use app::{
+    curriculum,
+    grant::Scope,
+    paging::{Page, Paginated, Sort},
+    study_group::{self, Entity, EntityId, ListFilter, SortKey},
+    subdivision,
+};
+use tokio::sync::Mutex;
+use utils::entity::Id;
+
+use super::tables::{check, has_prefix, paginate, sort_order, ConstraintViolation, Tables};
+
+pub struct MemoryStudyGroupRepo {
+    pub(crate) tables: Arc<Mutex<Tables>>,
+}
+
+#[async_trait::async_trait]
+impl study_group::Repo for MemoryStudyGroupRepo {
+    async fn save(&mut self, mut entity: Entity) -> Result<Entity, anyhow::Error> {
+        let mut tables = self.tables.lock().await;
+        entity.id = Id::new(tables.study_groups.resolve_id(entity.id.value));
+
+        check(
+            tables.subdivisions.contains(&entity.department_id.value),
+            ConstraintViolation::ForeignKey("study_groups_department_id_fkey"),
+        )?;
+        check(
+            !tables
+                .study_groups
+                .values()
+                .any(|v| v.id != entity.id && v.name == entity.name),
+            ConstraintViolation::Unique("study_groups_name_key"),
+        )?;
+        check(
+            entity
+                .curriculums
+                .iter()
+                .all(|c| tables.curriculums.contains(&c.value)),
+            ConstraintViolation::ForeignKey("study_group_curriculums_curriculum_id_fkey"),
+        )?;
+
+        tables
+            .study_groups
+            .rows
+            .insert(entity.id.value, entity.clone());
+        Ok(entity)
+    }
+
+    async fn delete(&mut self, entity: &Entity) -> Result<(), anyhow::Error> {
+        let mut tables = self.tables.lock().await;
+        let id = entity.id;
+
+        check(
+            !tables
+                .grants
+                .values()
+                .any(|v| v.scope == Scope::StudyGroup(id)),
+            ConstraintViolation::ForeignKey("user_grants_study_group_id_fkey"),
+        )?;
+        check(
+            !tables.students.values().any(|v| v.study_group_id == id),
+            ConstraintViolation::ForeignKey("students_study_group_id_fkey"),
+        )?;
+        check(
+            !tables
+                .student_orders
+                .values()
+                .any(|v| v.study_group_id == id),
+            ConstraintViolation::ForeignKey("student_orders_study_group_id_fkey"),
+        )?;
+        check(
+            !tables
+                .teachers
+                .values()
+                .any(|v| v.classes.iter().any(|c| c.study_group_id == id)),
+            ConstraintViolation::ForeignKey("class_teachers_study_group_id_fkey"),
+        )?;
+        check(
+            !tables.schedules.values().any(|v| v.study_group_id == id),
+            ConstraintViolation::ForeignKey("schedule_entries_study_group_id_fkey"),
+        )?;
+        check(
+            !tables
+                .attestation_sessions
+                .values()
+                .any(|v| v.study_group_id == id),
+            ConstraintViolation::ForeignKey("attestation_sessions_study_group_id_fkey"),
+        )?;
+
+        tables.study_groups.rows.remove(&id.value);
+        Ok(())
+    }
+
+    async fn find(&self, id: EntityId) -> Result<Option<Entity>, anyhow::Error> {
+        let tables = self.tables.lock().await;
+        Ok(tables.study_groups.rows.get(&id.value).cloned())
+    }
+
+    async fn find_many(&self, ids: HashSet<EntityId>) -> Result<Vec<Entity>, anyhow::Error> {
+        let tables = self.tables.lock().await;
+        let entities = tables
+            .study_groups
+            .values()
+            .filter(|v| ids.contains(&v.id))
+            .cloned()
+            .collect();
+
+        Ok(entities)
+    }
+
+    async fn find_by_name(&self, name: String) -> Result<Option<Entity>, anyhow::Error> {
+        let tables = self.tables.lock().await;
+        Ok(tables
+            .study_groups
+            .values()
+            .find(|v| v.name == name)
+            .cloned())
+    }
+
+    async fn list(
+        &self,
+        filter: ListFilter,
+        sort: Sort<SortKey>,
+        page: Page,
+    ) -> Result<Paginated<Entity>, anyhow::Error> {
+        let tables = self.tables.lock().await;
+
+        let mut entities = tables
+            .study_groups
+            .values()
+            .filter(|v| has_prefix(&v.name, &filter.name_prefix))
+            .filter(|v| {
+                filter
+                    .qualification
+                    .as_ref()
+                    .map_or(true, |q| v.studying_qualification == *q)
+            })
+            .filter(|v| {
+                filter
+                    .training_kind
+                    .as_ref()
+                    .map_or(true, |k| v.training_kind == *k)
+            })
+            .filter(|v| {
+                filter
+                    .department_id
+                    .map_or(true, |id| v.department_id == id)
+            })
+            .cloned()
+            .collect::<Vec<_>>();
+
+        entities.sort_by(|a, b| {
+            let ordering = match sort.key {
+                SortKey::Id => a.id.value.cmp(&b.id.value),
+                SortKey::Name => a.name.cmp(&b.name),
+            };
+
+            sort_order(sort.order, ordering)
+        });
+
+        Ok(paginate(entities, page))
+    }
+
+    async fn list_by_department(
+        &self,
+        department_id: subdivision::EntityId,
+    ) -> Result<Vec<Entity>, anyhow::Error> {
+        let tables = self.tables.lock().await;
+        let entities = tables
+            .study_groups
+            .values()
+            .filter(|v| v.department_id == department_id)
+            .cloned()
+            .collect();
+
+        Ok(entities)
+    }
+
+    async fn list_by_curriculums(
+        &self,
+        curriculums_ids: HashSet<curriculum::EntityId>,
+    ) -> Result<Vec<Entity>, anyhow::Error> {
+        let tables = self.tables.lock().await;
+        let entities = tables
+            .study_groups
+            .values()
+            .filter(|v| !v.curriculums.is_disjoint(&curriculums_ids))
+            .cloned()
+            .collect();
+
+        Ok(entities)
+    }
+}
@@ -0,0 +1,199 @@
+use std::{collections::HashSet, sync::Arc};
+
+use app::{
+    grant::Scope,
+    paging::{Page, Paginated, Sort},
+    person,
+    subdivision::{self, Entity, EntityId, ListFilter, SortKey},
+    tag, university,
+};
+use tokio::sync::Mutex;
+use utils::entity::Id;
+
+use super::tables::{check, has_prefix, paginate, sort_order, ConstraintViolation, Tables};
+
+pub struct MemorySubdivisionRepo {
+    pub(crate) tables: Arc<Mutex<Tables>>,
+}
+
+#[async_trait::async_trait]
+impl subdivision::Repo for MemorySubdivisionRepo {
+    async fn save(&mut self, mut entity: Entity) -> Result<Entity, anyhow::Error> {
+        let mut tables = self.tables.lock().await;
+        entity.id = Id::new(tables.subdivisions.resolve_id(entity.id.value));
+
+        check(
+            tables.universities.contains(&entity.university_id.value),
+            ConstraintViolation::ForeignKey("subdivisions_university_id_fkey"),
+        )?;
+        check(
+            entity.parent_id.map_or(true, |parent_id| {
+                parent_id == entity.id || tables.subdivisions.contains(&parent_id.value)
+            }),
+            ConstraintViolation::ForeignKey("subdivisions_parent_id_fkey"),
+        )?;
+        check(
+            !tables.subdivisions.values().any(|v| {
+                v.id != entity.id
+                    && v.university_id == entity.university_id
+                    && v.name == entity.name
+            }),
+            ConstraintViolation::Unique("subdivisions_university_id_name_key"),
+        )?;
+        check(
+            entity.tags.iter().all(|t| tables.tags.contains(&t.value)),
+            ConstraintViolation::ForeignKey("subdivision_tags_tag_name_fkey"),
+        )?;
+        check(
+            entity
+                .members
+                .iter()
+                .all(|m| tables.persons.contains(&m.person_id.value)),
+            ConstraintViolation::ForeignKey("subdivision_members_person_id_fkey"),
+        )?;
+        check(
+            entity.members.len()
+                == entity
+                    .members
+                    .iter()
+                    .map(|m| m.person_id)
+                    .collect::<HashSet<_>>()
+                    .len(),
+            ConstraintViolation::Unique("subdivision_members_pkey"),
+        )?;
+
+        tables
+            .subdivisions
+            .rows
+            .insert(entity.id.value, entity.clone());
+        Ok(entity)
+    }
+
+    async fn delete(&mut self, entity: &Entity) -> Result<(), anyhow::Error> {
+        let mut tables = self.tables.lock().await;
+        let id = entity.id;
+
+        check(
+            !tables
+                .subdivisions
+                .values()
+                .any(|v| v.parent_id == Some(id)),
+            ConstraintViolation::ForeignKey("subdivisions_parent_id_fkey"),
+        )?;
+        check(
+            !tables.study_groups.values().any(|v| v.department_id == id),
+            ConstraintViolation::ForeignKey("study_groups_department_id_fkey"),
+        )?;
+        check(
+            !tables
+                .grants
+                .values()
+                .any(|v| v.scope == Scope::Subdivision(id)),
+            ConstraintViolation::ForeignKey("user_grants_subdivision_id_fkey"),
+        )?;
+        check(
+            !tables.teachers.values().any(|v| v.department_id == id),
+            ConstraintViolation::ForeignKey("teachers_department_id_fkey"),
+        )?;
+        check(
+            !tables.disciplines.values().any(|v| v.department_id == id),
+            ConstraintViolation::ForeignKey("disciplines_department_id_fkey"),
+        )?;
+
+        tables.subdivisions.rows.remove(&id.value);
+        Ok(())
+    }
+
+    async fn find(&self, id: EntityId) -> Result<Option<Entity>, anyhow::Error> {
+        let tables = self.tables.lock().await;
+        Ok(tables.subdivisions.rows.get(&id.value).cloned())
+    }
+
+    async fn find_by_name(&self, name: String) -> Result<Option<Entity>, anyhow::Error> {
+        let tables = self.tables.lock().await;
+        Ok(tables
+            .subdivisions
+            .values()
+            .find(|v| v.name == name)
+            .cloned())
+    }
+
+    async fn list(
+        &self,
+        filter: ListFilter,
+        sort: Sort<SortKey>,
+        page: Page,
+    ) -> Result<Paginated<Entity>, anyhow::Error> {
+        let tables = self.tables.lock().await;
+
+        let mut entities = tables
+            .subdivisions
+            .values()
+            .filter(|v| has_prefix(&v.name, &filter.name_prefix))
+            .filter(|v| {
+                filter
+                    .university_id
+                    .map_or(true, |id| v.university_id == id)
+            })
+            .filter(|v| filter.parent_id.map_or(true, |id| v.parent_id == Some(id)))
+            .filter(|v| filter.tag.as_ref().map_or(true, |tag| v.tags.contains(tag)))
+            .cloned()
+            .collect::<Vec<_>>();
+
+        entities.sort_by(|a, b| {
+            let ordering = match sort.key {
+                SortKey::Id => a.id.value.cmp(&b.id.value),
+                SortKey::Name => a.name.cmp(&b.name),
+            };
+
+            sort_order(sort.order, ordering)
+        });
+
+        Ok(paginate(entities, page))
+    }
+
+    async fn list_by_university(
+        &self,
+        university_id: university::EntityId,
+    ) -> Result<Vec<Entity>, anyhow::Error> {
+        let tables = self.tables.lock().await;
+        let entities = tables
+            .subdivisions
+            .values()
+            .filter(|v| v.university_id == university_id)
+            .cloned()
+            .collect();
+
+        Ok(entities)
+    }
+
+    async fn list_by_tags(
+        &self,
+        tags_ids: HashSet<tag::EntityId>,
+    ) -> Result<Vec<Entity>, anyhow::Error> {
+        let tables = self.tables.lock().await;
+        let entities = tables
+            .subdivisions
+            .values()
+            .filter(|v| !v.tags.is_disjoint(&tags_ids))
+            .cloned()
+            .collect();
+
+        Ok(entities)
+    }
+
+    async fn list_by_members(
+        &self,
+        persons_ids: HashSet<person::EntityId>,
+    ) -> Result<Vec<Entity>, anyhow::Error> {
+        let tables = self.tables.lock().await;
+        let entities = tables
+            .subdivisions
+            .values()
+            .filter(|v| v.members.iter().any(|m| persons_ids.contains(&m.person_id)))
+            .cloned()
+            .collect();
+
+        Ok(entities)
+    }
+}
@@ -0,0 +1,132 @@
+use std::{
+    cmp::Ordering,
+    collections::{btree_map, BTreeMap},
+};
+
+use app::{
+    academic_year, account_token, attestation, attestation_session, calendar_feed, class,
+    class_kind, curriculum, curriculum_module, discipline, grading_scale, grant, load_limit,
+    paging::{Page, Paginated, SortOrder},
+    passport, person, room,
+    schedule::{self, TimeSlot},
+    student, student_order, study_group, subdivision, tag, teacher, timetable_draft, university,
+    user, user_session,
+};
+
+/// Rows of one table, ids of new rows are taken from a sequence like `serial` columns
+#[derive(Debug)]
+pub(crate) struct Table<K, V> {
+    pub rows: BTreeMap<K, V>,
+    last_id: i32,
+}
+
+impl<K, V> Default for Table<K, V> {
+    fn default() -> Self {
+        Self {
+            rows: BTreeMap::new(),
+            last_id: 0,
+        }
+    }
+}
+
+impl<V> Table<i32, V> {
+    /// Existing rows keep their id, new rows get the next one from the sequence
+    pub fn resolve_id(&mut self, id: i32) -> i32 {
+        if self.rows.contains_key(&id) {
+            return id;
+        }
+
+        self.last_id += 1;
+        self.last_id
+    }
+}
+
+impl<K: Ord, V> Table<K, V> {
+    pub fn values(&self) -> btree_map::Values<'_, K, V> {
+        self.rows.values()
+    }
+
+    pub fn contains(&self, key: &K) -> bool {
+        self.rows.contains_key(key)
+    }
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct Tables {
+    pub users: Table<i32, user::Entity>,
+    pub user_sessions: Table<(i32, String), user_session::Entity>,
+    pub user_session_rotated_tokens: Table<String, user_session::RotatedToken>,
+    pub account_tokens: Table<i32, account_token::Entity>,
+    pub persons: Table<i32, person::Entity>,
+    pub passports: Table<i32, passport::Entity>,
+    pub universities: Table<i32, university::Entity>,
+    pub tags: Table<String, tag::Entity>,
+    pub subdivisions: Table<i32, subdivision::Entity>,
+    pub study_groups: Table<i32, study_group::Entity>,
+    pub grants: Table<i32, grant::Entity>,
+    pub teachers: Table<i32, teacher::Entity>,
+    pub students: Table<i32, student::Entity>,
+    pub curriculums: Table<i32, curriculum::Entity>,
+    pub disciplines: Table<i32, discipline::Entity>,
+    pub curriculum_modules: Table<i32, curriculum_module::Entity>,
+    pub attestations: Table<i32, attestation::Entity>,
+    pub class_kinds: Table<String, class_kind::Entity>,
+    pub classes: Table<i32, class::Entity>,
+    pub grading_scales: Table<i32, grading_scale::Entity>,
+    pub rooms: Table<i32, room::Entity>,
+    pub schedules: Table<i32, schedule::Entity>,
+    pub teacher_availability: Table<i32, Vec<TimeSlot>>,
+    pub timetable_drafts: Table<i32, timetable_draft::Entity>,
+    pub attestation_sessions: Table<i32, attestation_session::Entity>,
+    pub calendar_feeds: Table<i32, calendar_feed::Entity>,
+    pub load_limits: Table<i32, load_limit::Entity>,
+    pub academic_years: Table<i32, academic_year::Entity>,
+    pub student_orders: Table<i32, student_order::Entity>,
+}
+
+/// Same constraints as the postgres schema, named the way postgres names them
+#[derive(Debug, Clone, thiserror::Error)]
+pub enum ConstraintViolation {
+    #[error("duplicate key value violates unique constraint \"{0}\"")]
+    Unique(&'static str),
+    #[error("insert, update or delete violates foreign key constraint \"{0}\"")]
+    ForeignKey(&'static str),
+    #[error("new row violates check constraint \"{0}\"")]
+    Check(&'static str),
+}
+
+pub(crate) fn check(
+    is_satisfied: bool,
+    violation: ConstraintViolation,
+) -> Result<(), anyhow::Error> {
+    if is_satisfied {
+        Ok(())
+    } else {
+        Err(violation.into())
+    }
+}
+
+pub(crate) fn sort_order(order: SortOrder, ordering: Ordering) -> Ordering {
+    match order {
+        SortOrder::Asc => ordering,
+        SortOrder::Desc => ordering.reverse(),
+    }
+}
+
+pub(crate) fn has_prefix(value: &str, prefix: &Option<String>) -> bool {
+    prefix.as_ref().map_or(true, |prefix| {
+        value.to_lowercase().starts_with(&prefix.to_lowercase())
+    })
+}
+
+/// Cuts a page out of items that are already filtered and sorted
+pub(crate) fn paginate<T>(items: Vec<T>, page: Page) -> Paginated<T> {
+    let total = items.len() as u64;
+    let items = items
+        .into_iter()
+        .skip(page.offset as usize)
+        .take(page.limit as usize)
+        .collect();
+
+    Paginated { items, total }
+}
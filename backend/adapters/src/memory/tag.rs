@@ -0,0 +1,43 @@
+use std::sync::Arc;
+
+use app::tag::{self, Entity, EntityId};
+use tokio::sync::Mutex;
+
+use super::tables::{check, ConstraintViolation, Tables};
+
+pub struct MemoryTagRepo {
+    pub(crate) tables: Arc<Mutex<Tables>>,
+}
+
+#[async_trait::async_trait]
+impl tag::Repo for MemoryTagRepo {
+    async fn save(&mut self, entity: Entity) -> Result<Entity, anyhow::Error> {
+        let mut tables = self.tables.lock().await;
+        tables
+            .tags
+            .rows
+            .insert(entity.name.value.clone(), entity.clone());
+
+        Ok(entity)
+    }
+
+    async fn delete(&mut self, entity: &Entity) -> Result<(), anyhow::Error> {
+        let mut tables = self.tables.lock().await;
+
+        check(
+            !tables
+                .subdivisions
+                .values()
+                .any(|v| v.tags.iter().any(|t| *t == entity.name)),
+            ConstraintViolation::ForeignKey("subdivision_tags_tag_name_fkey"),
+        )?;
+
+        tables.tags.rows.remove(&entity.name.value);
+        Ok(())
+    }
+
+    async fn find(&self, id: EntityId) -> Result<Option<Entity>, anyhow::Error> {
+        let tables = self.tables.lock().await;
+        Ok(tables.tags.rows.get(&id.value).cloned())
+    }
+}
@@ -0,0 +1,155 @@
+use std::sync::Arc;
+
+use app::{
+    class, person,
+    schedule::TimeSlot,
+    subdivision,
+    teacher::{self, Entity, EntityId},
+};
+use tokio::sync::Mutex;
+use utils::entity::Id;
+
+use super::tables::{check, ConstraintViolation, Tables};
+
+pub struct MemoryTeacherRepo {
+    pub(crate) tables: Arc<Mutex<Tables>>,
+}
+
+#[async_trait::async_trait]
+impl teacher::Repo for MemoryTeacherRepo {
+    async fn save(&mut self, mut entity: Entity) -> Result<Entity, anyhow::Error> {
+        let mut tables = self.tables.lock().await;
+        entity.id = Id::new(tables.teachers.resolve_id(entity.id.value));
+
+        check(
+            tables.persons.contains(&entity.person_id.value),
+            ConstraintViolation::ForeignKey("teachers_person_id_fkey"),
+        )?;
+        check(
+            !tables
+                .teachers
+                .values()
+                .any(|v| v.id != entity.id && v.person_id == entity.person_id),
+            ConstraintViolation::Unique("teachers_person_id_key"),
+        )?;
+        check(
+            tables.subdivisions.contains(&entity.department_id.value),
+            ConstraintViolation::ForeignKey("teachers_department_id_fkey"),
+        )?;
+        check(
+            entity
+                .classes
+                .iter()
+                .all(|c| tables.classes.contains(&c.class_id.value)),
+            ConstraintViolation::ForeignKey("class_teachers_class_id_fkey"),
+        )?;
+        check(
+            entity
+                .classes
+                .iter()
+                .all(|c| tables.study_groups.contains(&c.study_group_id.value)),
+            ConstraintViolation::ForeignKey("class_teachers_study_group_id_fkey"),
+        )?;
+
+        tables.teachers.rows.insert(entity.id.value, entity.clone());
+        Ok(entity)
+    }
+
+    async fn delete(&mut self, entity: &Entity) -> Result<(), anyhow::Error> {
+        let mut tables = self.tables.lock().await;
+        let id = entity.id;
+
+        check(
+            !tables
+                .attestations
+                .values()
+                .any(|v| v.examiners.contains(&id)),
+            ConstraintViolation::ForeignKey("attestation_examiners_examiner_id_fkey"),
+        )?;
+        check(
+            !tables.schedules.values().any(|v| v.teacher_id == id),
+            ConstraintViolation::ForeignKey("schedule_entries_teacher_id_fkey"),
+        )?;
+
+        tables.teacher_availability.rows.remove(&id.value);
+        tables.teachers.rows.remove(&id.value);
+        Ok(())
+    }
+
+    async fn find(&self, id: EntityId) -> Result<Option<Entity>, anyhow::Error> {
+        let tables = self.tables.lock().await;
+        Ok(tables.teachers.rows.get(&id.value).cloned())
+    }
+
+    async fn find_by_person_id(
+        &self,
+        person_id: person::EntityId,
+    ) -> Result<Option<Entity>, anyhow::Error> {
+        let tables = self.tables.lock().await;
+        Ok(tables
+            .teachers
+            .values()
+            .find(|v| v.person_id == person_id)
+            .cloned())
+    }
+
+    async fn list_by_department_id(
+        &self,
+        department_id: subdivision::EntityId,
+    ) -> Result<Vec<Entity>, anyhow::Error> {
+        let tables = self.tables.lock().await;
+        let entities = tables
+            .teachers
+            .values()
+            .filter(|v| v.department_id == department_id)
+            .cloned()
+            .collect();
+
+        Ok(entities)
+    }
+
+    async fn list_by_class(&self, class_id: class::EntityId) -> Result<Vec<Entity>, anyhow::Error> {
+        let tables = self.tables.lock().await;
+        let entities = tables
+            .teachers
+            .values()
+            .filter(|v| v.classes.iter().any(|c| c.class_id == class_id))
+            .cloned()
+            .collect();
+
+        Ok(entities)
+    }
+
+    async fn availability(&self, id: EntityId) -> Result<Vec<TimeSlot>, anyhow::Error> {
+        let tables = self.tables.lock().await;
+        let mut windows = tables
+            .teacher_availability
+            .rows
+            .get(&id.value)
+            .cloned()
+            .unwrap_or_default();
+
+        windows.sort_by_key(|v| (v.weekday.number_from_monday(), v.starts_at));
+        Ok(windows)
+    }
+
+    async fn set_availability(
+        &mut self,
+        id: EntityId,
+        windows: Vec<TimeSlot>,
+    ) -> Result<(), anyhow::Error> {
+        let mut tables = self.tables.lock().await;
+
+        check(
+            tables.teachers.contains(&id.value),
+            ConstraintViolation::ForeignKey("teacher_availability_teacher_id_fkey"),
+        )?;
+        check(
+            windows.iter().all(TimeSlot::is_valid),
+            ConstraintViolation::Check("teacher_availability_check"),
+        )?;
+
+        tables.teacher_availability.rows.insert(id.value, windows);
+        Ok(())
+    }
+}
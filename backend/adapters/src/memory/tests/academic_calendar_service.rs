@@ -0,0 +1,65 @@
+use app::{
+    academic_calendar_service::{AcademicCalendarException, AcademicCalendarService},
+    academic_year::{self, SemesterRange},
+    study_group,
+};
+use time::Month;
+use utils::{di::Module, entity::Id};
+
+use super::{date, ex, ok, Fixture};
+
+#[tokio::test]
+async fn current_semester_is_counted_from_the_enrollment_year() {
+    let fixture = Fixture::new().await;
+
+    let mut years: academic_year::BoxedRepo = fixture.adapters.resolve();
+    // 2024 is missing from the calendar and assumed to have two semesters
+    for start_year in [2023, 2025] {
+        years
+            .save(academic_year::Entity {
+                id: Default::default(),
+                university_id: Id::new(1),
+                start_year,
+                semesters: vec![
+                    SemesterRange {
+                        starts_on: date(start_year, Month::September, 1),
+                        ends_on: date(start_year + 1, Month::January, 31),
+                    },
+                    SemesterRange {
+                        starts_on: date(start_year + 1, Month::February, 9),
+                        ends_on: date(start_year + 1, Month::June, 30),
+                    },
+                ],
+            })
+            .await
+            .unwrap();
+    }
+
+    let service = fixture.service::<AcademicCalendarService>();
+    let semester = |date| service.current_semester(&fixture.study_group, date);
+
+    let first = ok(semester(date(2023, Month::October, 1)).await).unwrap();
+    assert_eq!((first.semester_of_year, first.semester), (1, 1));
+
+    let second = ok(semester(date(2024, Month::March, 1)).await).unwrap();
+    assert_eq!((second.semester_of_year, second.semester), (2, 2));
+
+    let fifth = ok(semester(date(2025, Month::October, 1)).await).unwrap();
+    assert_eq!(fifth.academic_year.start_year, 2025);
+    assert_eq!((fifth.semester_of_year, fifth.semester), (1, 5));
+
+    // holidays and years missing from the calendar have no semester
+    assert!(ok(semester(date(2024, Month::February, 1)).await).is_none());
+    assert!(ok(semester(date(2024, Month::October, 1)).await).is_none());
+
+    let not_enrolled = study_group::Entity {
+        enrollment_year: None,
+        ..fixture.study_group.clone()
+    };
+    assert!(matches!(
+        ex(service
+            .current_semester(&not_enrolled, date(2023, Month::October, 1))
+            .await),
+        AcademicCalendarException::NoEnrollmentYear
+    ));
+}
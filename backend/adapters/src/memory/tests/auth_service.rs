@@ -0,0 +1,52 @@
+use app::{
+    auth_service::{AuthException, AuthService, Tokens},
+    user::Role,
+    user_service::UserException,
+    user_session::ClientInfo,
+};
+use utils::outcome::Outcome;
+
+use super::{ex, ok, Fixture, PASSWORD};
+
+async fn login(service: &mut AuthService, password: &str) -> Outcome<Tokens, AuthException> {
+    service
+        .login(
+            "d.churikov@uis.ru".to_owned(),
+            password.to_owned(),
+            None,
+            ClientInfo {
+                user_agent: None,
+                ip: Some("10.0.0.1".to_owned()),
+            },
+        )
+        .await
+}
+
+#[tokio::test]
+async fn repeated_failed_logins_lock_the_account() {
+    let fixture = Fixture::new().await;
+    fixture.user("d.churikov@uis.ru", Role::Student).await;
+    let mut service = fixture.service::<AuthService>();
+
+    // a successful login clears the failures before it
+    for _ in 0..4 {
+        assert!(matches!(
+            ex(login(&mut service, "wrong").await),
+            AuthException::UserException(UserException::InvalidEmailOrPassword)
+        ));
+    }
+    ok(login(&mut service, PASSWORD).await);
+
+    for _ in 0..fixture.adapters.login_throttling.account_max_failures {
+        assert!(matches!(
+            ex(login(&mut service, "wrong").await),
+            AuthException::UserException(UserException::InvalidEmailOrPassword)
+        ));
+    }
+
+    let retry_after = match ex(login(&mut service, PASSWORD).await) {
+        AuthException::UserException(UserException::AccountLocked { retry_after }) => retry_after,
+        ex => panic!("unexpected exception: {ex:?}"),
+    };
+    assert!(retry_after <= fixture.adapters.login_throttling.lockout.val);
+}
@@ -0,0 +1,164 @@
+use std::collections::HashSet;
+
+use app::{
+    access_policy::{AccessException, Actor},
+    attestation::{AttestationKind, Hours},
+    class_kind,
+    curriculum::{self, CurriculumStatus},
+    curriculum_service::{
+        AttestationDraft, ClassDraft, CurriculumException, CurriculumInfo, CurriculumService,
+        ModuleDraft,
+    },
+    discipline,
+    grant::{self, Scope},
+    user::Role,
+};
+use utils::{di::Module, entity::Id};
+
+use super::{ex, ok, Fixture};
+
+#[tokio::test]
+async fn cloned_curriculum_versions_follow_the_last_one() {
+    let fixture = Fixture::new().await;
+
+    let mut disciplines: discipline::BoxedRepo = fixture.adapters.resolve();
+    let discipline = disciplines
+        .save(discipline::Entity {
+            id: Default::default(),
+            name: "Математический анализ".to_owned(),
+            department_id: fixture.department_id,
+        })
+        .await
+        .unwrap();
+
+    let mut class_kinds: class_kind::BoxedRepo = fixture.adapters.resolve();
+    let lecture = class_kinds
+        .save(class_kind::Entity {
+            name: Id::new("lecture".to_owned()),
+        })
+        .await
+        .unwrap();
+
+    let module = |semester| ModuleDraft {
+        discipline_id: discipline.id,
+        semester,
+        classes: vec![ClassDraft {
+            kind_name: lecture.name.clone(),
+            duration: Hours(32),
+        }],
+        attestation: Some(AttestationDraft {
+            kind: AttestationKind::Exam,
+            duration: Hours(4),
+            examiners: HashSet::new(),
+        }),
+    };
+
+    let mut service = fixture.service::<CurriculumService>();
+    let first = ok(service
+        .create(
+            &fixture.admin,
+            CurriculumInfo {
+                name: "avt-113 2023".to_owned(),
+                department_id: fixture.department_id,
+            },
+        )
+        .await);
+    ok(service
+        .add_module(&fixture.admin, first.id, module(1))
+        .await);
+
+    let second = ok(service.clone_version(&fixture.admin, first.id).await);
+    let third = ok(service.clone_version(&fixture.admin, first.id).await);
+    assert_eq!((second.version, third.version), (2, 3));
+    assert_eq!(third.status, CurriculumStatus::Draft);
+
+    let versions = ok(service.versions(second.id).await);
+    assert_eq!(
+        versions.iter().map(|v| v.version).collect::<Vec<_>>(),
+        vec![1, 2, 3]
+    );
+
+    assert!(ok(service.diff(first.id, second.id).await).is_empty());
+
+    ok(service
+        .add_module(&fixture.admin, third.id, module(2))
+        .await);
+    let diff = ok(service.diff(first.id, third.id).await);
+    assert_eq!(diff.len(), 1);
+    assert_eq!(
+        (diff[0].discipline_id, diff[0].semester),
+        (discipline.id, 2)
+    );
+    assert!(diff[0].before.is_none());
+    assert_eq!(diff[0].after.as_ref().map(|v| v.hours), Some(36));
+
+    // a version taken in between is not overwritten
+    let mut curriculums: curriculum::BoxedRepo = fixture.adapters.resolve();
+    let clash = curriculums
+        .insert_version(curriculum::Entity {
+            id: Default::default(),
+            ..third
+        })
+        .await
+        .unwrap();
+    assert!(clash.is_none());
+}
+
+#[tokio::test]
+async fn curriculums_are_changed_by_managers_of_their_department() {
+    let fixture = Fixture::new().await;
+
+    let other_department = fixture.department("fpmi").await;
+
+    let head = fixture.user("head@uis.ru", Role::DepartmentHead).await;
+    let mut grants: grant::BoxedRepo = fixture.adapters.resolve();
+    grants
+        .save(grant::Entity {
+            id: Default::default(),
+            user_id: head.id,
+            role: Role::DepartmentHead,
+            scope: Scope::Subdivision(fixture.department_id),
+        })
+        .await
+        .unwrap();
+    let head = Actor {
+        user_id: head.id,
+        role: Role::DepartmentHead,
+    };
+
+    let info = |name: &str, department_id| CurriculumInfo {
+        name: name.to_owned(),
+        department_id,
+    };
+    let mut service = fixture.service::<CurriculumService>();
+
+    let own = ok(service
+        .create(&head, info("avt-113 2023", fixture.department_id))
+        .await);
+    assert_eq!(own.department_id, Some(fixture.department_id));
+
+    let other = ok(service
+        .create(&fixture.admin, info("pmi-31 2023", other_department.id))
+        .await);
+    assert!(matches!(
+        ex(service
+            .create(&head, info("pmi-32 2023", other_department.id))
+            .await),
+        CurriculumException::AccessException(AccessException::NoRights)
+    ));
+    assert!(matches!(
+        ex(service
+            .update(&head, own.id, info("avt-113 2023", other_department.id))
+            .await),
+        CurriculumException::AccessException(AccessException::NoRights)
+    ));
+    assert!(matches!(
+        ex(service.delete(&head, other.id).await),
+        CurriculumException::AccessException(AccessException::NoRights)
+    ));
+
+    // the clone stays with the department of the curriculum
+    let copy = ok(service.clone_version(&head, own.id).await);
+    assert_eq!(copy.department_id, Some(fixture.department_id));
+    ok(service.delete(&head, copy.id).await);
+}
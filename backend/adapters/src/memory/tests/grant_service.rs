@@ -0,0 +1,65 @@
+use app::{
+    access_policy::{AccessException, Actor},
+    grant::{self, Scope},
+    grant_service::{GrantException, GrantService},
+    user::Role,
+};
+use utils::{di::Module, entity::Id};
+
+use super::{ex, ok, Fixture};
+
+#[tokio::test]
+async fn department_heads_grant_only_lower_roles() {
+    let fixture = Fixture::new().await;
+    let head = fixture.user("head@uis.ru", Role::DepartmentHead).await;
+    let teacher = fixture.user("teacher@uis.ru", Role::Teacher).await;
+
+    let mut grants: grant::BoxedRepo = fixture.adapters.resolve();
+    let own_grant = grants
+        .save(grant::Entity {
+            id: Default::default(),
+            user_id: head.id,
+            role: Role::DepartmentHead,
+            scope: Scope::Subdivision(fixture.department_id),
+        })
+        .await
+        .unwrap();
+
+    let head = Actor {
+        user_id: head.id,
+        role: Role::DepartmentHead,
+    };
+    let mut service = fixture.service::<GrantService>();
+
+    ok(service
+        .grant(
+            &head,
+            teacher.id,
+            Role::Teacher,
+            Scope::StudyGroup(fixture.study_group.id),
+        )
+        .await);
+
+    for (role, scope) in [
+        (
+            Role::DepartmentHead,
+            Scope::Subdivision(fixture.department_id),
+        ),
+        (
+            Role::UniversityStaff,
+            Scope::Subdivision(fixture.department_id),
+        ),
+        (Role::Admin, Scope::Global),
+        (Role::Teacher, Scope::University(Id::new(1))),
+    ] {
+        assert!(matches!(
+            ex(service.grant(&head, teacher.id, role, scope).await),
+            GrantException::AccessException(AccessException::NoRights)
+        ));
+    }
+
+    assert!(matches!(
+        ex(service.revoke(&head, own_grant.id).await),
+        GrantException::AccessException(AccessException::NoRights)
+    ));
+}
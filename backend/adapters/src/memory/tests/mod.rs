@@ -0,0 +1,237 @@
+//! Services running against the in-memory adapters
+
+use std::collections::HashSet;
+
+use app::{
+    access_policy::Actor,
+    grant::{self, Scope},
+    hasher::HashedPassword,
+    person,
+    study_group::{self, Qualification, TrainingKind},
+    subdivision, university,
+    university_service::{UniversityInfo, UniversityService},
+    user::{self, Role},
+    AppModule,
+};
+use time::{Date, Month, UtcOffset};
+use utils::{
+    di::{Module, Provide},
+    outcome::Outcome,
+};
+
+use super::MemoryModule;
+
+mod academic_calendar_service;
+mod auth_service;
+mod curriculum_service;
+mod grant_service;
+mod person_service;
+mod student_import_service;
+mod student_service;
+mod university_service;
+mod user_service;
+
+const PASSWORD: &str = "password";
+const REASON: &str = "приказ №1";
+
+fn date(year: i32, month: Month, day: u8) -> Date {
+    Date::from_calendar_date(year, month, day).unwrap()
+}
+
+fn ok<T, E: std::fmt::Debug>(outcome: Outcome<T, E>) -> T {
+    match outcome.into_result().unwrap() {
+        Ok(value) => value,
+        Err(ex) => panic!("unexpected exception: {ex:?}"),
+    }
+}
+
+fn ex<T, E>(outcome: Outcome<T, E>) -> E {
+    match outcome.into_result().unwrap() {
+        Ok(_) => panic!("expected an exception"),
+        Err(ex) => ex,
+    }
+}
+
+/// University with a department and the avt-113 study group enrolled in 2023
+struct Fixture {
+    adapters: MemoryModule,
+    admin: Actor,
+    department_id: subdivision::EntityId,
+    study_group: study_group::Entity,
+}
+
+impl Fixture {
+    async fn new() -> Self {
+        let adapters = MemoryModule::new();
+
+        let mut users: user::BoxedRepo = adapters.resolve();
+        let admin = users.save(user("admin@uis.ru", Role::Admin)).await.unwrap();
+
+        let mut universities: university::BoxedRepo = adapters.resolve();
+        let university = universities
+            .save(university::Entity {
+                id: Default::default(),
+                name: "nstu".to_owned(),
+                utc_offset: UtcOffset::from_hms(7, 0, 0).unwrap(),
+            })
+            .await
+            .unwrap();
+
+        let mut subdivisions: subdivision::BoxedRepo = adapters.resolve();
+        let department = subdivisions
+            .save(subdivision::Entity {
+                id: Default::default(),
+                name: "avtf".to_owned(),
+                university_id: university.id,
+                parent_id: None,
+                tags: HashSet::new(),
+                members: HashSet::new(),
+            })
+            .await
+            .unwrap();
+
+        let mut fixture = Self {
+            adapters,
+            admin: Actor {
+                user_id: admin.id,
+                role: Role::Admin,
+            },
+            department_id: department.id,
+            study_group: study_group::Entity {
+                id: Default::default(),
+                name: Default::default(),
+                studying_qualification: Qualification::Bachelor,
+                training_kind: TrainingKind::FullTime,
+                department_id: department.id,
+                curriculums: HashSet::new(),
+                enrollment_year: None,
+            },
+        };
+        fixture.study_group = fixture.study_group("avt-113").await;
+
+        fixture
+    }
+
+    fn service<S>(&self) -> S
+    where
+        AppModule<MemoryModule>: Provide<S>,
+    {
+        AppModule::new(self.adapters.clone()).resolve()
+    }
+
+    async fn user(&self, email: &str, role: Role) -> user::Entity {
+        let mut users: user::BoxedRepo = self.adapters.resolve();
+        users.save(user(email, role)).await.unwrap()
+    }
+
+    /// User acting under a single grant
+    async fn grantee(&self, email: &str, role: Role, scope: Scope) -> Actor {
+        let user = self.user(email, role).await;
+
+        let mut grants: grant::BoxedRepo = self.adapters.resolve();
+        grants
+            .save(grant::Entity {
+                id: Default::default(),
+                user_id: user.id,
+                role,
+                scope,
+            })
+            .await
+            .unwrap();
+
+        Actor {
+            user_id: user.id,
+            role,
+        }
+    }
+
+    /// Another department of the fixture university
+    async fn department(&self, name: &str) -> subdivision::Entity {
+        let mut subdivisions: subdivision::BoxedRepo = self.adapters.resolve();
+        let department = subdivisions
+            .find(self.department_id)
+            .await
+            .unwrap()
+            .unwrap();
+
+        subdivisions
+            .save(subdivision::Entity {
+                id: Default::default(),
+                name: name.to_owned(),
+                ..department
+            })
+            .await
+            .unwrap()
+    }
+
+    async fn study_group(&self, name: &str) -> study_group::Entity {
+        let mut study_groups: study_group::BoxedRepo = self.adapters.resolve();
+        study_groups
+            .save(study_group::Entity {
+                id: Default::default(),
+                name: name.to_owned(),
+                studying_qualification: Qualification::Bachelor,
+                training_kind: TrainingKind::FullTime,
+                department_id: self.department_id,
+                curriculums: HashSet::new(),
+                enrollment_year: Some(2023),
+            })
+            .await
+            .unwrap()
+    }
+
+    async fn person(&self, email: &str) -> person::Entity {
+        let user = self.user(email, Role::Student).await;
+
+        let mut persons: person::BoxedRepo = self.adapters.resolve();
+        persons
+            .save(person::Entity {
+                id: Default::default(),
+                user_id: user.id,
+                full_name: "Чуриков Данил".to_owned(),
+            })
+            .await
+            .unwrap()
+    }
+}
+
+fn user(email: &str, role: Role) -> user::Entity {
+    user::Entity {
+        id: Default::default(),
+        email: email.to_owned(),
+        password: HashedPassword {
+            value: format!("fake${PASSWORD}"),
+        },
+        role,
+        email_verified: true,
+    }
+}
+
+#[tokio::test]
+async fn services_and_repos_share_the_module_tables() {
+    let fixture = Fixture::new().await;
+    let mut service = fixture.service::<UniversityService>();
+
+    let created = ok(service
+        .create(
+            &fixture.admin,
+            UniversityInfo {
+                name: "tpu".to_owned(),
+                utc_offset_minutes: 7 * 60,
+            },
+        )
+        .await);
+
+    let universities: university::BoxedRepo = fixture.adapters.resolve();
+    let found = universities.find(created.id).await.unwrap();
+    assert_eq!(found.map(|v| v.name), Some("tpu".to_owned()));
+
+    let study_groups: study_group::BoxedRepo = fixture.adapters.resolve();
+    let study_group = study_groups
+        .find(fixture.study_group.id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(study_group.name, "avt-113");
+    assert_eq!(study_group.department_id, fixture.department_id);
+}
@@ -0,0 +1,160 @@
+use std::collections::HashSet;
+
+use app::{
+    access_policy::AccessException,
+    grant::Scope,
+    paging::{Page, Paginated, Sort},
+    person::{self, SortKey},
+    person_service::{PersonException, PersonService},
+    student::{self, StudentStatus},
+    study_group, subdivision,
+    teacher::{self, TeacherKind},
+    user::Role,
+};
+use utils::{di::Module, entity::Id};
+
+use super::{ex, ok, Fixture};
+
+async fn student(
+    fixture: &Fixture,
+    email: &str,
+    study_group_id: study_group::EntityId,
+) -> person::Entity {
+    let person = fixture.person(email).await;
+
+    let mut students: student::BoxedRepo = fixture.adapters.resolve();
+    students
+        .save(student::Entity {
+            id: Default::default(),
+            person_id: person.id,
+            study_group_id,
+            status: StudentStatus::Active,
+            attestations: HashSet::new(),
+        })
+        .await
+        .unwrap();
+
+    person
+}
+
+async fn teacher(
+    fixture: &Fixture,
+    email: &str,
+    department_id: subdivision::EntityId,
+) -> person::Entity {
+    let person = fixture.person(email).await;
+
+    let mut teachers: teacher::BoxedRepo = fixture.adapters.resolve();
+    teachers
+        .save(teacher::Entity {
+            id: Default::default(),
+            person_id: person.id,
+            kind: TeacherKind::Professor,
+            department_id,
+            classes: HashSet::new(),
+        })
+        .await
+        .unwrap();
+
+    person
+}
+
+#[tokio::test]
+async fn persons_are_changed_within_their_study_groups_and_departments() {
+    let fixture = Fixture::new().await;
+    let other_department = fixture.department("fpmi").await;
+
+    let head = fixture
+        .grantee(
+            "head@uis.ru",
+            Role::DepartmentHead,
+            Scope::Subdivision(fixture.department_id),
+        )
+        .await;
+
+    let student = student(&fixture, "student@uis.ru", fixture.study_group.id).await;
+    let teacher = teacher(&fixture, "teacher@uis.ru", other_department.id).await;
+    let unattached = fixture.person("new@uis.ru").await;
+    let mut service = fixture.service::<PersonService>();
+
+    let renamed = ok(service
+        .update(&head, student.id, "Чуриков Данил Андреевич".to_owned())
+        .await);
+    assert_eq!(renamed.full_name, "Чуриков Данил Андреевич");
+
+    for id in [teacher.id, unattached.id, Id::new(99)] {
+        assert!(matches!(
+            ex(service.update(&head, id, "Иванов Иван".to_owned()).await),
+            PersonException::AccessException(AccessException::NoRights)
+        ));
+    }
+    assert!(matches!(
+        ex(service.remove_passport(&head, Id::new(99)).await),
+        PersonException::AccessException(AccessException::NoRights)
+    ));
+
+    assert!(matches!(
+        ex(service
+            .update(&fixture.admin, Id::new(99), "Иванов Иван".to_owned())
+            .await),
+        PersonException::NotFound
+    ));
+    ok(service.delete(&fixture.admin, unattached.id).await);
+}
+
+#[tokio::test]
+async fn persons_are_read_within_granted_scopes() {
+    let fixture = Fixture::new().await;
+    let other_department = fixture.department("fpmi").await;
+
+    let mut subdivisions: subdivision::BoxedRepo = fixture.adapters.resolve();
+    let chair = subdivisions
+        .save(subdivision::Entity {
+            id: Default::default(),
+            name: "вт".to_owned(),
+            parent_id: Some(fixture.department_id),
+            ..other_department.clone()
+        })
+        .await
+        .unwrap();
+
+    let reader = fixture
+        .grantee(
+            "teacher@uis.ru",
+            Role::Teacher,
+            Scope::Subdivision(fixture.department_id),
+        )
+        .await;
+
+    let student = student(&fixture, "student@uis.ru", fixture.study_group.id).await;
+    let nested = teacher(&fixture, "chair@uis.ru", chair.id).await;
+    let other = teacher(&fixture, "other@uis.ru", other_department.id).await;
+    fixture.person("new@uis.ru").await;
+
+    let service = fixture.service::<PersonService>();
+    let list = |actor| {
+        let service = &service;
+        async move {
+            let filter = person::ListFilter::default();
+            let sort = Sort {
+                key: SortKey::Id,
+                order: Default::default(),
+            };
+            let Paginated { items, total } = ok(service
+                .list(actor, filter, sort, Page::new(None, None))
+                .await);
+
+            assert_eq!(items.len() as u64, total);
+            items.into_iter().map(|v| v.id).collect::<Vec<_>>()
+        }
+    };
+
+    assert_eq!(list(&reader).await, vec![student.id, nested.id]);
+    assert_eq!(list(&fixture.admin).await.len(), 4);
+
+    ok(service.find(&reader, nested.id).await);
+    assert!(matches!(
+        ex(service.find(&reader, other.id).await),
+        PersonException::AccessException(AccessException::NoRights)
+    ));
+}
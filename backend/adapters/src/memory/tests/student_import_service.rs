@@ -0,0 +1,156 @@
+use std::collections::HashMap;
+
+use app::{
+    access_policy::{AccessException, Actor},
+    grant::{self, Scope},
+    student_import_service::{
+        ImportRow, RowException, StudentImportException, StudentImportService,
+    },
+    user::{self, Role},
+};
+use time::Month;
+use utils::di::Module;
+
+use super::{date, ex, ok, Fixture};
+
+fn row(number: usize, email: &str, passport_number: &str, study_group: &str) -> ImportRow {
+    ImportRow {
+        number,
+        cells: HashMap::from(
+            [
+                ("email", email),
+                ("Фамилия", "Чуриков"),
+                ("Имя", "Данил"),
+                ("пол", "м"),
+                ("date_of_birth", "31.08.2005"),
+                ("passport_series", "5005"),
+                ("passport_number", passport_number),
+                ("date_of_issue", "2019-09-10"),
+                ("группа", study_group),
+            ]
+            .map(|(k, v)| (k.to_owned(), v.to_owned())),
+        ),
+    }
+}
+
+#[tokio::test]
+async fn import_writes_valid_rows_and_reports_invalid_ones() {
+    let fixture = Fixture::new().await;
+
+    let rows = || {
+        vec![
+            row(2, "d.churikov@uis.ru", "123456", "avt-113"),
+            row(3, "not an email", "654321", "avt-113"),
+            row(4, "D.Churikov@uis.ru", "123456", "avt-113"),
+        ]
+    };
+    let today = date(2023, Month::September, 1);
+    let mut service = fixture.service::<StudentImportService>();
+
+    let dry_run = ok(service.import(&fixture.admin, rows(), today, true).await);
+    assert_eq!(dry_run.rows, 3);
+    assert!(dry_run.students.is_empty());
+
+    let report = ok(service.import(&fixture.admin, rows(), today, false).await);
+    assert_eq!(report.rows, 3);
+    assert_eq!(
+        report.students.iter().map(|v| v.row).collect::<Vec<_>>(),
+        vec![2]
+    );
+    assert!(report.students[0].initial_password.is_some());
+    assert_eq!(report.students[0].person.full_name, "Чуриков Данил");
+    assert_eq!(
+        report.students[0].student.study_group_id,
+        fixture.study_group.id
+    );
+
+    let errors = report
+        .errors
+        .iter()
+        .map(|v| (v.row, v.error.clone()))
+        .collect::<Vec<_>>();
+    assert_eq!(errors.len(), dry_run.errors.len());
+    assert!(errors
+        .iter()
+        .any(|(row, error)| *row == 3 && matches!(error, RowException::InvalidEmail)));
+    assert!(errors
+        .iter()
+        .any(|(row, error)| *row == 4 && matches!(error, RowException::DuplicateEmail(2))));
+    assert!(errors
+        .iter()
+        .any(|(row, error)| *row == 4 && matches!(error, RowException::DuplicatePassport(2))));
+
+    let users: user::BoxedRepo = fixture.adapters.resolve();
+    assert!(users
+        .find_by_email("d.churikov@uis.ru".to_owned())
+        .await
+        .unwrap()
+        .is_some());
+}
+
+#[tokio::test]
+async fn existing_users_are_only_looked_up_for_managed_study_groups() {
+    let fixture = Fixture::new().await;
+    fixture.user("d.churikov@uis.ru", Role::Student).await;
+    let other_department = fixture.department("fpmi").await;
+
+    let head = fixture.user("head@uis.ru", Role::DepartmentHead).await;
+    let mut grants: grant::BoxedRepo = fixture.adapters.resolve();
+    grants
+        .save(grant::Entity {
+            id: Default::default(),
+            user_id: head.id,
+            role: Role::DepartmentHead,
+            scope: Scope::Subdivision(other_department.id),
+        })
+        .await
+        .unwrap();
+    let head = Actor {
+        user_id: head.id,
+        role: Role::DepartmentHead,
+    };
+
+    let today = date(2023, Month::September, 1);
+    let mut service = fixture.service::<StudentImportService>();
+
+    assert!(matches!(
+        ex(service
+            .import(
+                &head,
+                vec![row(2, "new@uis.ru", "123456", "avt-113")],
+                today,
+                true
+            )
+            .await),
+        StudentImportException::AccessException(AccessException::NoRights)
+    ));
+
+    let report = ok(service
+        .import(
+            &head,
+            vec![row(2, "d.churikov@uis.ru", "123456", "pmi-31")],
+            today,
+            true,
+        )
+        .await);
+    assert_eq!(report.errors.len(), 1);
+    assert!(matches!(
+        report.errors[0].error,
+        RowException::StudyGroupNotFound(_)
+    ));
+
+    // emails are compared regardless of case
+    let report = ok(service
+        .import(
+            &fixture.admin,
+            vec![row(2, "D.Churikov@uis.ru", "123456", "avt-113")],
+            today,
+            true,
+        )
+        .await);
+    assert_eq!(report.errors.len(), 1);
+    assert!(matches!(
+        report.errors[0].error,
+        RowException::EmailAlreadyInUse
+    ));
+}
@@ -0,0 +1,80 @@
+use app::{
+    student_order::OrderKind,
+    student_service::{EnrollmentInfo, OrderInfo, RosterEntry, StudentInfo, StudentService},
+};
+use time::Month;
+
+use super::{date, ok, Fixture, REASON};
+
+#[tokio::test]
+async fn roster_leaves_out_expelled_and_transferred_students() {
+    let fixture = Fixture::new().await;
+    let other_group = fixture.study_group("avt-013").await;
+    let mut service = fixture.service::<StudentService>();
+
+    let mut students = vec![];
+    for email in ["a@uis.ru", "b@uis.ru", "c@uis.ru"] {
+        let person = fixture.person(email).await;
+        let student = ok(service
+            .create(
+                &fixture.admin,
+                StudentInfo {
+                    person_id: person.id,
+                    study_group_id: fixture.study_group.id,
+                },
+                EnrollmentInfo {
+                    date: date(2023, Month::September, 1),
+                    reason: REASON.to_owned(),
+                },
+            )
+            .await);
+        students.push(student.id);
+    }
+
+    let order = |kind, study_group_id, date| OrderInfo {
+        kind,
+        study_group_id,
+        date,
+        reason: REASON.to_owned(),
+    };
+    ok(service
+        .issue_order(
+            &fixture.admin,
+            students[1],
+            order(OrderKind::Expulsion, None, date(2024, Month::February, 1)),
+        )
+        .await);
+    ok(service
+        .issue_order(
+            &fixture.admin,
+            students[2],
+            order(
+                OrderKind::Transfer,
+                Some(other_group.id),
+                date(2024, Month::February, 1),
+            ),
+        )
+        .await);
+
+    let roster = |entries: Vec<RosterEntry>| {
+        entries
+            .into_iter()
+            .map(|v| v.student.id)
+            .collect::<Vec<_>>()
+    };
+
+    let before = ok(service
+        .roster(fixture.study_group.id, date(2024, Month::January, 31))
+        .await);
+    assert_eq!(roster(before), students);
+
+    let after = ok(service
+        .roster(fixture.study_group.id, date(2024, Month::February, 1))
+        .await);
+    assert_eq!(roster(after), vec![students[0]]);
+
+    let other = ok(service
+        .roster(other_group.id, date(2024, Month::February, 1))
+        .await);
+    assert_eq!(roster(other), vec![students[2]]);
+}
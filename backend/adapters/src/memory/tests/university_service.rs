@@ -0,0 +1,32 @@
+use app::{
+    access_policy::{AccessException, Actor},
+    university_service::{UniversityException, UniversityService},
+    user::Role,
+};
+use utils::entity::Id;
+
+use super::{ex, Fixture};
+
+#[tokio::test]
+async fn missing_universities_are_hidden_from_unauthorized_actors() {
+    let fixture = Fixture::new().await;
+    let teacher = fixture.user("teacher@uis.ru", Role::Teacher).await;
+    let teacher = Actor {
+        user_id: teacher.id,
+        role: Role::Teacher,
+    };
+    let mut service = fixture.service::<UniversityService>();
+
+    assert!(matches!(
+        ex(service.delete(&teacher, Id::new(1)).await),
+        UniversityException::AccessException(AccessException::NoRights)
+    ));
+    assert!(matches!(
+        ex(service.delete(&teacher, Id::new(99)).await),
+        UniversityException::AccessException(AccessException::NoRights)
+    ));
+    assert!(matches!(
+        ex(service.delete(&fixture.admin, Id::new(99)).await),
+        UniversityException::NotFound
+    ));
+}
@@ -0,0 +1,49 @@
+use app::{
+    user::Role,
+    user_service::UserService,
+    user_session::{self, ClientInfo, SecondsFromUnixEpoch},
+};
+use utils::{di::Module, entity::Id};
+
+use super::{ok, Fixture};
+
+fn client() -> ClientInfo {
+    ClientInfo {
+        user_agent: None,
+        ip: Some("10.0.0.1".to_owned()),
+    }
+}
+
+#[tokio::test]
+async fn expired_sessions_do_not_take_up_the_limit() {
+    let fixture = Fixture::new().await;
+    let user = fixture.user("d.churikov@uis.ru", Role::Student).await;
+
+    let now = SecondsFromUnixEpoch::now().unwrap().seconds.val;
+    let mut sessions: user_session::BoxedRepo = fixture.adapters.resolve();
+    let user_session::SessionsMaxNumber(max_number) = fixture.adapters.sessions_max_number;
+    for n in 0..max_number {
+        let device_id = format!("expired-{n}");
+        sessions
+            .save(user_session::Entity {
+                id: Id::new(user_session::Id {
+                    user_id: user.id,
+                    device_id: device_id.clone(),
+                }),
+                family_id: device_id.clone(),
+                refresh_token_hash: device_id,
+                expires_at: (now - 60).into(),
+                client: client(),
+                last_seen_at: (now - 120).into(),
+            })
+            .await
+            .unwrap();
+    }
+
+    let mut service = fixture.service::<UserService>();
+    ok(service.create_session(user.id, None, client()).await);
+
+    let left = sessions.list_by_user_id(user.id).await.unwrap();
+    assert_eq!(left.len(), 1);
+    assert!(!left[0].expires_at.is_expired().unwrap());
+}
@@ -0,0 +1,49 @@
+use std::sync::Arc;
+
+use app::timetable_draft::{self, Entity, EntityId};
+use tokio::sync::Mutex;
+use utils::entity::Id;
+
+use super::tables::{check, ConstraintViolation, Tables};
+
+pub struct MemoryTimetableDraftRepo {
+    pub(crate) tables: Arc<Mutex<Tables>>,
+}
+
+#[async_trait::async_trait]
+impl timetable_draft::Repo for MemoryTimetableDraftRepo {
+    async fn save(&mut self, mut entity: Entity) -> Result<Entity, anyhow::Error> {
+        let mut tables = self.tables.lock().await;
+        entity.id = Id::new(tables.timetable_drafts.resolve_id(entity.id.value));
+
+        check(
+            tables.users.contains(&entity.author_id.value),
+            ConstraintViolation::ForeignKey("timetable_drafts_author_id_fkey"),
+        )?;
+        check(
+            entity.semester > 0,
+            ConstraintViolation::Check("timetable_drafts_semester_check"),
+        )?;
+        check(
+            entity.valid_from <= entity.valid_until,
+            ConstraintViolation::Check("timetable_drafts_check"),
+        )?;
+
+        tables
+            .timetable_drafts
+            .rows
+            .insert(entity.id.value, entity.clone());
+        Ok(entity)
+    }
+
+    async fn delete(&mut self, entity: &Entity) -> Result<(), anyhow::Error> {
+        let mut tables = self.tables.lock().await;
+        tables.timetable_drafts.rows.remove(&entity.id.value);
+        Ok(())
+    }
+
+    async fn find(&self, id: EntityId) -> Result<Option<Entity>, anyhow::Error> {
+        let tables = self.tables.lock().await;
+        Ok(tables.timetable_drafts.rows.get(&id.value).cloned())
+    }
+}
@@ -0,0 +1,101 @@
+use std::sync::Arc;
+
+use app::{
+    grant::Scope,
+    university::{self, Entity, EntityId},
+};
+use tokio::sync::Mutex;
+use utils::entity::Id;
+
+use super::tables::{check, ConstraintViolation, Tables};
+
+pub struct MemoryUniversityRepo {
+    pub(crate) tables: Arc<Mutex<Tables>>,
+}
+
+#[async_trait::async_trait]
+impl university::Repo for MemoryUniversityRepo {
+    async fn save(&mut self, mut entity: Entity) -> Result<Entity, anyhow::Error> {
+        let mut tables = self.tables.lock().await;
+        entity.id = Id::new(tables.universities.resolve_id(entity.id.value));
+
+        check(
+            !tables
+                .universities
+                .values()
+                .any(|v| v.id != entity.id && v.name == entity.name),
+            ConstraintViolation::Unique("universities_name_key"),
+        )?;
+        check(
+            (-12 * 60..=14 * 60).contains(&entity.utc_offset.whole_minutes()),
+            ConstraintViolation::Check("universities_utc_offset_minutes_check"),
+        )?;
+
+        tables
+            .universities
+            .rows
+            .insert(entity.id.value, entity.clone());
+        Ok(entity)
+    }
+
+    async fn delete(&mut self, entity: &Entity) -> Result<(), anyhow::Error> {
+        let mut tables = self.tables.lock().await;
+        let id = entity.id;
+
+        check(
+            !tables.subdivisions.values().any(|v| v.university_id == id),
+            ConstraintViolation::ForeignKey("subdivisions_university_id_fkey"),
+        )?;
+        check(
+            !tables
+                .grants
+                .values()
+                .any(|v| v.scope == Scope::University(id)),
+            ConstraintViolation::ForeignKey("user_grants_university_id_fkey"),
+        )?;
+        check(
+            !tables
+                .grading_scales
+                .values()
+                .any(|v| v.university_id == id),
+            ConstraintViolation::ForeignKey("grading_scales_university_id_fkey"),
+        )?;
+        check(
+            !tables.load_limits.values().any(|v| v.university_id == id),
+            ConstraintViolation::ForeignKey("load_limits_university_id_fkey"),
+        )?;
+        check(
+            !tables
+                .academic_years
+                .values()
+                .any(|v| v.university_id == id),
+            ConstraintViolation::ForeignKey("academic_years_university_id_fkey"),
+        )?;
+        check(
+            !tables.rooms.values().any(|v| v.university_id == id),
+            ConstraintViolation::ForeignKey("rooms_university_id_fkey"),
+        )?;
+
+        tables.universities.rows.remove(&id.value);
+        Ok(())
+    }
+
+    async fn find(&self, id: EntityId) -> Result<Option<Entity>, anyhow::Error> {
+        let tables = self.tables.lock().await;
+        Ok(tables.universities.rows.get(&id.value).cloned())
+    }
+
+    async fn list(&self) -> Result<Vec<Entity>, anyhow::Error> {
+        let tables = self.tables.lock().await;
+        Ok(tables.universities.values().cloned().collect())
+    }
+
+    async fn find_by_name(&self, name: String) -> Result<Option<Entity>, anyhow::Error> {
+        let tables = self.tables.lock().await;
+        Ok(tables
+            .universities
+            .values()
+            .find(|v| v.name == name)
+            .cloned())
+    }
+}
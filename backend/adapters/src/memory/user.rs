@@ -0,0 +1,74 @@
+use std::sync::Arc;
+
+use app::user::{self, Entity, EntityId};
+use tokio::sync::Mutex;
+use utils::entity::Id;
+
+use super::tables::{check, ConstraintViolation, Tables};
+
+pub struct MemoryUserRepo {
+    pub(crate) tables: Arc<Mutex<Tables>>,
+}
+
+#[async_trait::async_trait]
+impl user::Repo for MemoryUserRepo {
+    async fn save(&mut self, mut entity: Entity) -> Result<Entity, anyhow::Error> {
+        let mut tables = self.tables.lock().await;
+        entity.id = Id::new(tables.users.resolve_id(entity.id.value));
+
+        check(
+            !tables
+                .users
+                .values()
+                .any(|v| v.id != entity.id && v.email == entity.email),
+            ConstraintViolation::Unique("users_email_key"),
+        )?;
+
+        tables.users.rows.insert(entity.id.value, entity.clone());
+        Ok(entity)
+    }
+
+    async fn delete(&mut self, entity: &Entity) -> Result<(), anyhow::Error> {
+        let mut tables = self.tables.lock().await;
+        let id = entity.id;
+
+        check(
+            !tables
+                .user_sessions
+                .values()
+                .any(|v| v.id.value.user_id == id),
+            ConstraintViolation::ForeignKey("user_sessions_user_id_fkey"),
+        )?;
+        check(
+            !tables.persons.values().any(|v| v.user_id == id),
+            ConstraintViolation::ForeignKey("persons_user_id_fkey"),
+        )?;
+        check(
+            !tables.grants.values().any(|v| v.user_id == id),
+            ConstraintViolation::ForeignKey("user_grants_user_id_fkey"),
+        )?;
+
+        tables.account_tokens.rows.retain(|_, v| v.user_id != id);
+        tables
+            .timetable_drafts
+            .rows
+            .retain(|_, v| v.author_id != id);
+        for order in tables.student_orders.rows.values_mut() {
+            if order.author_id == Some(id) {
+                order.author_id = None;
+            }
+        }
+        tables.users.rows.remove(&id.value);
+        Ok(())
+    }
+
+    async fn find(&self, id: EntityId) -> Result<Option<Entity>, anyhow::Error> {
+        let tables = self.tables.lock().await;
+        Ok(tables.users.rows.get(&id.value).cloned())
+    }
+
+    async fn find_by_email(&self, email: String) -> Result<Option<Entity>, anyhow::Error> {
+        let tables = self.tables.lock().await;
+        Ok(tables.users.values().find(|v| v.email == email).cloned())
+    }
+}
@@ -0,0 +1,116 @@
+use std::sync::Arc;
+
+use app::{
+    user,
+    user_session::{self, Entity, EntityId, RotatedToken},
+};
+use tokio::sync::Mutex;
+
+use super::tables::{check, ConstraintViolation, Tables};
+
+pub struct MemoryUserSessionRepo {
+    pub(crate) tables: Arc<Mutex<Tables>>,
+}
+
+fn key(id: &EntityId) -> (i32, String) {
+    (id.value.user_id.value, id.value.device_id.clone())
+}
+
+#[async_trait::async_trait]
+impl user_session::Repo for MemoryUserSessionRepo {
+    async fn save(&mut self, entity: Entity) -> Result<Entity, anyhow::Error> {
+        let mut tables = self.tables.lock().await;
+        let key = key(&entity.id);
+
+        check(
+            tables.users.contains(&key.0),
+            ConstraintViolation::ForeignKey("user_sessions_user_id_fkey"),
+        )?;
+        check(
+            !tables
+                .user_sessions
+                .rows
+                .iter()
+                .any(|(k, v)| *k != key && v.refresh_token_hash == entity.refresh_token_hash),
+            ConstraintViolation::Unique("user_sessions_refresh_token_key"),
+        )?;
+
+        tables.user_sessions.rows.insert(key, entity.clone());
+        Ok(entity)
+    }
+
+    async fn delete(&mut self, entity: &Entity) -> Result<(), anyhow::Error> {
+        let mut tables = self.tables.lock().await;
+        let key = key(&entity.id);
+
+        tables
+            .user_session_rotated_tokens
+            .rows
+            .retain(|_, v| self::key(&v.session_id) != key);
+        tables.user_sessions.rows.remove(&key);
+        Ok(())
+    }
+
+    async fn find(&self, id: EntityId) -> Result<Option<Entity>, anyhow::Error> {
+        let tables = self.tables.lock().await;
+        Ok(tables.user_sessions.rows.get(&key(&id)).cloned())
+    }
+
+    async fn list_by_user_id(&self, user_id: user::EntityId) -> Result<Vec<Entity>, anyhow::Error> {
+        let tables = self.tables.lock().await;
+        let entities = tables
+            .user_sessions
+            .values()
+            .filter(|v| v.id.value.user_id == user_id)
+            .cloned()
+            .collect();
+
+        Ok(entities)
+    }
+
+    async fn save_rotated_token(&mut self, token: RotatedToken) -> Result<(), anyhow::Error> {
+        let mut tables = self.tables.lock().await;
+
+        check(
+            tables.user_sessions.contains(&key(&token.session_id)),
+            ConstraintViolation::ForeignKey("user_session_rotated_tokens_user_id_device_id_fkey"),
+        )?;
+        check(
+            !tables
+                .user_session_rotated_tokens
+                .contains(&token.token_hash),
+            ConstraintViolation::Unique("user_session_rotated_tokens_pkey"),
+        )?;
+
+        tables
+            .user_session_rotated_tokens
+            .rows
+            .insert(token.token_hash.clone(), token);
+        Ok(())
+    }
+
+    async fn find_rotated_token(
+        &self,
+        token_hash: String,
+    ) -> Result<Option<RotatedToken>, anyhow::Error> {
+        let tables = self.tables.lock().await;
+        Ok(tables
+            .user_session_rotated_tokens
+            .rows
+            .get(&token_hash)
+            .cloned())
+    }
+
+    async fn count_not_expired(&self, user_id: user::EntityId) -> Result<i64, anyhow::Error> {
+        let tables = self.tables.lock().await;
+
+        let mut count = 0;
+        for session in tables.user_sessions.values() {
+            if session.id.value.user_id == user_id && !session.expires_at.is_expired()? {
+                count += 1;
+            }
+        }
+
+        Ok(count)
+    }
+}
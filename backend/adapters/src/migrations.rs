@@ -0,0 +1,93 @@
+use sqlx::{
+    migrate::{Migrate, Migrator},
+    PgConnection, PgPool,
+};
+
+/// Migrations from `adapters/migrations`, they are only ever applied forward
+static MIGRATOR: Migrator = sqlx::migrate!();
+
+/// Version of the first migration, it is exactly the schema `db.sql` created,
+/// everything changed since then is in the later migrations
+const BASELINE_VERSION: i64 = 1;
+
+#[derive(Debug, Clone, thiserror::Error)]
+#[error(
+    "database schema version {database} is ahead of the application schema version {application}"
+)]
+pub struct SchemaAheadError {
+    pub database: i64,
+    pub application: i64,
+}
+
+pub(crate) async fn run(pool: &PgPool) -> Result<(), anyhow::Error> {
+    let mut conn = pool.acquire().await?;
+
+    baseline_legacy_schema(&mut conn).await?;
+    check_schema_version(&mut conn).await?;
+
+    MIGRATOR.run(&mut *conn).await?;
+    Ok(())
+}
+
+fn application_version() -> i64 {
+    MIGRATOR.iter().map(|m| m.version).max().unwrap_or(0)
+}
+
+/// Refuses to work with a database migrated by a newer binary
+async fn check_schema_version(conn: &mut PgConnection) -> Result<(), anyhow::Error> {
+    conn.ensure_migrations_table().await?;
+
+    let database = conn
+        .list_applied_migrations()
+        .await?
+        .into_iter()
+        .map(|m| m.version)
+        .max()
+        .unwrap_or(0);
+    let application = application_version();
+
+    if database > application {
+        return Err(SchemaAheadError {
+            database,
+            application,
+        }
+        .into());
+    }
+
+    Ok(())
+}
+
+/// Databases created from `db.sql` have the baseline schema but no migrations table,
+/// the baseline migration is recorded as applied so only later migrations run on them
+async fn baseline_legacy_schema(conn: &mut PgConnection) -> Result<(), anyhow::Error> {
+    let (is_tracked, is_legacy) = sqlx::query_as::<_, (bool, bool)>(
+        "select to_regclass('_sqlx_migrations') is not null, to_regclass('users') is not null",
+    )
+    .fetch_one(&mut *conn)
+    .await?;
+
+    if is_tracked || !is_legacy {
+        return Ok(());
+    }
+
+    let Some(baseline) = MIGRATOR.iter().find(|m| m.version == BASELINE_VERSION) else {
+        return Ok(());
+    };
+
+    conn.ensure_migrations_table().await?;
+    sqlx::query(
+        "insert into _sqlx_migrations (version, description, success, checksum, execution_time) \
+         values ($1, $2, true, $3, 0)",
+    )
+    .bind(baseline.version)
+    .bind(&*baseline.description)
+    .bind(&*baseline.checksum)
+    .execute(&mut *conn)
+    .await?;
+
+    tracing::info!(
+        version = baseline.version,
+        "recorded baseline schema migration"
+    );
+    Ok(())
+}
@@ -9,9 +9,9 @@ use std::sync::Arc;
 use tokio::sync::Mutex;
 
 use crate::{
-    fetch_all, fetch_one, fetch_optional,
+    execute, fetch_all, fetch_one, fetch_optional,
     passport::model::{Passports, PgGender},
-    PgTransaction,
+    PgEnum, PgTransaction,
 };
 
 use self::model::PassportsIden;
@@ -45,7 +45,7 @@ impl PgPassportRepo {
                 entity.date_of_issue.into(),
                 entity.number.to_string().into(),
                 entity.series.to_string().into(),
-                PgGender::from(entity.gender).to_string().into(),
+                PgGender::from(entity.gender).into_expr(),
             ])
             .returning_all();
 
@@ -68,7 +68,7 @@ impl PgPassportRepo {
                 (PassportsIden::Series, entity.series.to_string().into()),
                 (
                     PassportsIden::Gender,
-                    PgGender::from(entity.gender).to_string().into(),
+                    PgGender::from(entity.gender).into_expr(),
                 ),
             ])
             .and_where(Expr::col(PassportsIden::Id).eq(entity.id.value))
@@ -97,7 +97,7 @@ impl passport::Repo for PgPassportRepo {
             .from_table(PassportsIden::Table)
             .and_where(Expr::col(PassportsIden::Id).eq(entity.id.value));
 
-        fetch_one::<()>(&self.txn, &query).await?;
+        execute(&self.txn, &query).await?;
         Ok(())
     }
 
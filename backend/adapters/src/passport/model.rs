@@ -4,6 +4,8 @@ use app::passport::{self, Gender};
 use sqlx::{FromRow, Type};
 use utils::entity::Id;
 
+use crate::PgEnum;
+
 #[derive(Clone, Debug, FromRow)]
 #[sea_query::enum_def]
 pub struct Passports {
@@ -74,3 +76,7 @@ impl From<PgGender> for Gender {
         }
     }
 }
+
+impl PgEnum for PgGender {
+    const TYPE_NAME: &'static str = "gender";
+}
@@ -1,13 +1,18 @@
-use crate::{fetch_all, fetch_one, fetch_optional, person::models::PersonsIden, PgTransaction};
+use crate::{
+    execute, fetch_all, fetch_count, fetch_one, fetch_optional, order_of,
+    person::models::PersonsIden, prefix_pattern, student::model::StudentsIden,
+    teacher::model::TeachersIden, PgTransaction,
+};
 
 mod models;
 
 use app::{
-    person::{self, Entity, EntityId},
+    paging::{Page, Paginated, Sort},
+    person::{self, Entity, EntityId, ListFilter, SortKey},
     user,
 };
-use sea_query::{Asterisk, Expr, Query};
-use std::sync::Arc;
+use sea_query::{extension::postgres::PgExpr, Asterisk, Condition, Expr, Order, Query};
+use std::{collections::HashSet, sync::Arc};
 use tokio::sync::Mutex;
 
 use self::models::Persons;
@@ -60,7 +65,7 @@ impl person::Repo for PgPersonRepo {
             .from_table(PersonsIden::Table)
             .and_where(Expr::col(PersonsIden::Id).eq(entity.id.value));
 
-        fetch_one::<()>(&self.txn, query).await
+        execute(&self.txn, query).await
     }
 
     async fn find(&self, id: EntityId) -> Result<Option<Entity>, anyhow::Error> {
@@ -74,6 +79,22 @@ impl person::Repo for PgPersonRepo {
         Ok(model.map(Into::into))
     }
 
+    async fn find_many(&self, ids: HashSet<EntityId>) -> Result<Vec<Entity>, anyhow::Error> {
+        let mut query = Query::select();
+        let query = query
+            .from(PersonsIden::Table)
+            .column(Asterisk)
+            .and_where(Expr::col(PersonsIden::Id).is_in(ids.into_iter().map(|id| id.value)))
+            .order_by(PersonsIden::Id, Order::Asc);
+
+        let entities = fetch_all::<Persons>(&self.txn, query)
+            .await?
+            .into_iter()
+            .map(Into::into)
+            .collect();
+        Ok(entities)
+    }
+
     async fn find_by_user_id(
         &self,
         user_id: user::EntityId,
@@ -88,15 +109,70 @@ impl person::Repo for PgPersonRepo {
         Ok(model.map(Into::into))
     }
 
-    async fn list(&self) -> Result<Vec<Entity>, anyhow::Error> {
+    async fn list(
+        &self,
+        filter: ListFilter,
+        sort: Sort<SortKey>,
+        page: Page,
+    ) -> Result<Paginated<Entity>, anyhow::Error> {
+        let mut cond = Condition::all();
+        if let Some(name_prefix) = filter.name_prefix {
+            cond = cond.add(Expr::col(PersonsIden::FullName).ilike(prefix_pattern(&name_prefix)));
+        }
+        if let Some(within) = filter.within {
+            let mut students = Query::select();
+            students
+                .from(StudentsIden::Table)
+                .column(StudentsIden::PersonId)
+                .and_where(
+                    Expr::col(StudentsIden::StudyGroupId)
+                        .is_in(within.study_group_ids.into_iter().map(|id| id.value)),
+                );
+
+            let mut teachers = Query::select();
+            teachers
+                .from(TeachersIden::Table)
+                .column(TeachersIden::PersonId)
+                .and_where(
+                    Expr::col(TeachersIden::DepartmentId)
+                        .is_in(within.department_ids.into_iter().map(|id| id.value)),
+                );
+
+            cond = cond.add(
+                Condition::any()
+                    .add(Expr::col(PersonsIden::Id).in_subquery(students.take()))
+                    .add(Expr::col(PersonsIden::Id).in_subquery(teachers.take())),
+            );
+        }
+
+        let sort_column = match sort.key {
+            SortKey::Id => PersonsIden::Id,
+            SortKey::FullName => PersonsIden::FullName,
+        };
+
         let mut query = Query::select();
-        let query = query.from(PersonsIden::Table).column(Asterisk);
+        let query = query
+            .from(PersonsIden::Table)
+            .column(Asterisk)
+            .cond_where(cond.clone())
+            .order_by(sort_column, order_of(sort.order))
+            .order_by(PersonsIden::Id, Order::Asc)
+            .limit(page.limit)
+            .offset(page.offset);
 
-        let entities = fetch_all::<Persons>(&self.txn, query)
+        let items = fetch_all::<Persons>(&self.txn, query)
             .await?
             .into_iter()
             .map(Into::into)
             .collect();
-        Ok(entities)
+
+        let mut query = Query::select();
+        let query = query
+            .from(PersonsIden::Table)
+            .expr(Expr::col(Asterisk).count())
+            .cond_where(cond);
+
+        let total = fetch_count(&self.txn, query).await?;
+        Ok(Paginated { items, total })
     }
 }
@@ -1,4 +1,5 @@
-use app::token::RefreshTokenGenerator;
+use app::token::{RefreshTokenGenerator, RefreshTokenHasher};
+use sha2::{Digest, Sha256};
 
 #[derive(Debug, Clone, Copy)]
 pub struct RefreshTokenLength(pub usize);
@@ -14,3 +15,12 @@ impl RefreshTokenGenerator for NanoIdRefreshTokenGenerator {
         Ok(nanoid::nanoid!(length))
     }
 }
+
+/// Hex encoded sha-256, the same as `encode(sha256(...), 'hex')` in postgres
+pub struct Sha256RefreshTokenHasher;
+
+impl RefreshTokenHasher for Sha256RefreshTokenHasher {
+    fn hash(&self, token: &str) -> String {
+        format!("{:x}", Sha256::digest(token.as_bytes()))
+    }
+}
@@ -0,0 +1,125 @@
+mod model;
+
+use app::{
+    room::{self, Entity, EntityId},
+    university,
+};
+use sea_query::{Asterisk, Expr, Query, SimpleExpr};
+use std::sync::Arc;
+use tokio::sync::Mutex;
+
+use crate::{execute, fetch_all, fetch_one, fetch_optional, PgTransaction};
+
+use self::model::{Rooms, RoomsIden};
+
+pub struct PgRoomRepo {
+    pub txn: Arc<Mutex<PgTransaction<'static>>>,
+}
+
+impl PgRoomRepo {
+    fn values(entity: Entity) -> [(RoomsIden, SimpleExpr); 3] {
+        [
+            (RoomsIden::UniversityId, entity.university_id.value.into()),
+            (RoomsIden::Name, entity.name.into()),
+            (RoomsIden::Capacity, entity.capacity.into()),
+        ]
+    }
+
+    async fn insert(&self, entity: Entity) -> Result<Rooms, anyhow::Error> {
+        let (columns, values): (Vec<_>, Vec<_>) = Self::values(entity).into_iter().unzip();
+
+        let mut query = Query::insert();
+        let query = query
+            .into_table(RoomsIden::Table)
+            .columns(columns)
+            .values_panic(values)
+            .returning_all();
+
+        fetch_one(&self.txn, query).await
+    }
+
+    async fn update(&self, entity: Entity) -> Result<Rooms, anyhow::Error> {
+        let id = entity.id;
+
+        let mut query = Query::update();
+        let query = query
+            .table(RoomsIden::Table)
+            .values(Self::values(entity))
+            .and_where(Expr::col(RoomsIden::Id).eq(id.value))
+            .returning_all();
+
+        fetch_one(&self.txn, query).await
+    }
+}
+
+#[async_trait::async_trait]
+impl room::Repo for PgRoomRepo {
+    async fn save(&mut self, entity: Entity) -> Result<Entity, anyhow::Error> {
+        let model = if self.find(entity.id).await?.is_some() {
+            self.update(entity).await?
+        } else {
+            self.insert(entity).await?
+        };
+
+        Ok(model.into())
+    }
+
+    async fn delete(&mut self, entity: &Entity) -> Result<(), anyhow::Error> {
+        execute(
+            &self.txn,
+            Query::delete()
+                .from_table(RoomsIden::Table)
+                .and_where(Expr::col(RoomsIden::Id).eq(entity.id.value)),
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    async fn find(&self, id: EntityId) -> Result<Option<Entity>, anyhow::Error> {
+        let model = fetch_optional::<Rooms>(
+            &self.txn,
+            Query::select()
+                .from(RoomsIden::Table)
+                .column(Asterisk)
+                .and_where(Expr::col(RoomsIden::Id).eq(id.value)),
+        )
+        .await?;
+
+        Ok(model.map(Into::into))
+    }
+
+    async fn find_by_name(
+        &self,
+        university_id: university::EntityId,
+        name: String,
+    ) -> Result<Option<Entity>, anyhow::Error> {
+        let model = fetch_optional::<Rooms>(
+            &self.txn,
+            Query::select()
+                .from(RoomsIden::Table)
+                .column(Asterisk)
+                .and_where(Expr::col(RoomsIden::UniversityId).eq(university_id.value))
+                .and_where(Expr::col(RoomsIden::Name).eq(name)),
+        )
+        .await?;
+
+        Ok(model.map(Into::into))
+    }
+
+    async fn list_by_university(
+        &self,
+        university_id: university::EntityId,
+    ) -> Result<Vec<Entity>, anyhow::Error> {
+        let models = fetch_all::<Rooms>(
+            &self.txn,
+            Query::select()
+                .from(RoomsIden::Table)
+                .column(Asterisk)
+                .and_where(Expr::col(RoomsIden::UniversityId).eq(university_id.value)),
+        )
+        .await?;
+
+        Ok(models.into_iter().map(Into::into).collect())
+    }
+}
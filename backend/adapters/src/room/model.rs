@@ -0,0 +1,23 @@
+use app::room;
+use sqlx::FromRow;
+use utils::entity::Id;
+
+#[derive(Clone, Debug, FromRow)]
+#[sea_query::enum_def]
+pub struct Rooms {
+    pub id: i32,
+    pub university_id: i32,
+    pub name: String,
+    pub capacity: i32,
+}
+
+impl From<Rooms> for room::Entity {
+    fn from(value: Rooms) -> Self {
+        room::Entity {
+            id: Id::new(value.id),
+            university_id: Id::new(value.university_id),
+            name: value.name,
+            capacity: value.capacity,
+        }
+    }
+}
@@ -0,0 +1,156 @@
+mod model;
+
+use app::{
+    class, room,
+    schedule::{self, Entity, EntityId},
+    study_group, teacher,
+};
+use sea_query::{Asterisk, Expr, Query, SimpleExpr};
+use std::sync::Arc;
+use tokio::sync::Mutex;
+
+use crate::{execute, fetch_all, fetch_one, fetch_optional, PgEnum, PgTransaction};
+
+use self::model::{ScheduleEntries, ScheduleEntriesIden};
+
+pub(crate) use self::model::{weekday_from_number, weekday_number, PgRecurrence};
+
+pub struct PgScheduleRepo {
+    pub txn: Arc<Mutex<PgTransaction<'static>>>,
+}
+
+impl PgScheduleRepo {
+    fn values(entity: Entity) -> [(ScheduleEntriesIden, SimpleExpr); 10] {
+        [
+            (ScheduleEntriesIden::ClassId, entity.class_id.value.into()),
+            (
+                ScheduleEntriesIden::StudyGroupId,
+                entity.study_group_id.value.into(),
+            ),
+            (
+                ScheduleEntriesIden::TeacherId,
+                entity.teacher_id.value.into(),
+            ),
+            (ScheduleEntriesIden::RoomId, entity.room_id.value.into()),
+            (
+                ScheduleEntriesIden::Weekday,
+                weekday_number(entity.slot.weekday).into(),
+            ),
+            (ScheduleEntriesIden::StartsAt, entity.slot.starts_at.into()),
+            (ScheduleEntriesIden::EndsAt, entity.slot.ends_at.into()),
+            (
+                ScheduleEntriesIden::Recurrence,
+                PgRecurrence::from(entity.recurrence).into_expr(),
+            ),
+            (ScheduleEntriesIden::ValidFrom, entity.valid_from.into()),
+            (ScheduleEntriesIden::ValidUntil, entity.valid_until.into()),
+        ]
+    }
+
+    async fn insert(&self, entity: Entity) -> Result<ScheduleEntries, anyhow::Error> {
+        let (columns, values): (Vec<_>, Vec<_>) = Self::values(entity).into_iter().unzip();
+
+        let mut query = Query::insert();
+        let query = query
+            .into_table(ScheduleEntriesIden::Table)
+            .columns(columns)
+            .values_panic(values)
+            .returning_all();
+
+        fetch_one(&self.txn, query).await
+    }
+
+    async fn update(&self, entity: Entity) -> Result<ScheduleEntries, anyhow::Error> {
+        let id = entity.id;
+
+        let mut query = Query::update();
+        let query = query
+            .table(ScheduleEntriesIden::Table)
+            .values(Self::values(entity))
+            .and_where(Expr::col(ScheduleEntriesIden::Id).eq(id.value))
+            .returning_all();
+
+        fetch_one(&self.txn, query).await
+    }
+
+    async fn list_by(
+        &self,
+        column: ScheduleEntriesIden,
+        value: i32,
+    ) -> Result<Vec<Entity>, anyhow::Error> {
+        let models = fetch_all::<ScheduleEntries>(
+            &self.txn,
+            Query::select()
+                .from(ScheduleEntriesIden::Table)
+                .column(Asterisk)
+                .and_where(Expr::col(column).eq(value)),
+        )
+        .await?;
+
+        Ok(models.into_iter().map(Into::into).collect())
+    }
+}
+
+#[async_trait::async_trait]
+impl schedule::Repo for PgScheduleRepo {
+    async fn save(&mut self, entity: Entity) -> Result<Entity, anyhow::Error> {
+        let model = if self.find(entity.id).await?.is_some() {
+            self.update(entity).await?
+        } else {
+            self.insert(entity).await?
+        };
+
+        Ok(model.into())
+    }
+
+    async fn delete(&mut self, entity: &Entity) -> Result<(), anyhow::Error> {
+        execute(
+            &self.txn,
+            Query::delete()
+                .from_table(ScheduleEntriesIden::Table)
+                .and_where(Expr::col(ScheduleEntriesIden::Id).eq(entity.id.value)),
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    async fn find(&self, id: EntityId) -> Result<Option<Entity>, anyhow::Error> {
+        let model = fetch_optional::<ScheduleEntries>(
+            &self.txn,
+            Query::select()
+                .from(ScheduleEntriesIden::Table)
+                .column(Asterisk)
+                .and_where(Expr::col(ScheduleEntriesIden::Id).eq(id.value)),
+        )
+        .await?;
+
+        Ok(model.map(Into::into))
+    }
+
+    async fn list_by_class(&self, class_id: class::EntityId) -> Result<Vec<Entity>, anyhow::Error> {
+        self.list_by(ScheduleEntriesIden::ClassId, class_id.value)
+            .await
+    }
+
+    async fn list_by_study_group(
+        &self,
+        study_group_id: study_group::EntityId,
+    ) -> Result<Vec<Entity>, anyhow::Error> {
+        self.list_by(ScheduleEntriesIden::StudyGroupId, study_group_id.value)
+            .await
+    }
+
+    async fn list_by_teacher(
+        &self,
+        teacher_id: teacher::EntityId,
+    ) -> Result<Vec<Entity>, anyhow::Error> {
+        self.list_by(ScheduleEntriesIden::TeacherId, teacher_id.value)
+            .await
+    }
+
+    async fn list_by_room(&self, room_id: room::EntityId) -> Result<Vec<Entity>, anyhow::Error> {
+        self.list_by(ScheduleEntriesIden::RoomId, room_id.value)
+            .await
+    }
+}
@@ -0,0 +1,96 @@
+use std::fmt::Display;
+
+use app::schedule::{self, Recurrence, TimeSlot};
+use sqlx::FromRow;
+use time::Weekday;
+use utils::entity::Id;
+
+use crate::PgEnum;
+
+#[derive(Clone, Debug, FromRow)]
+#[sea_query::enum_def]
+pub struct ScheduleEntries {
+    pub id: i32,
+    pub class_id: i32,
+    pub study_group_id: i32,
+    pub teacher_id: i32,
+    pub room_id: i32,
+    pub weekday: i32,
+    pub starts_at: time::Time,
+    pub ends_at: time::Time,
+    pub recurrence: PgRecurrence,
+    pub valid_from: time::Date,
+    pub valid_until: time::Date,
+}
+
+/// ISO 8601 number of the day, the way the `weekday` column stores it
+pub fn weekday_number(weekday: Weekday) -> i32 {
+    weekday.number_from_monday().into()
+}
+
+pub fn weekday_from_number(number: i32) -> Weekday {
+    Weekday::Sunday.nth_next(number.rem_euclid(7) as u8)
+}
+
+impl From<ScheduleEntries> for schedule::Entity {
+    fn from(value: ScheduleEntries) -> Self {
+        schedule::Entity {
+            id: Id::new(value.id),
+            class_id: Id::new(value.class_id),
+            study_group_id: Id::new(value.study_group_id),
+            teacher_id: Id::new(value.teacher_id),
+            room_id: Id::new(value.room_id),
+            slot: TimeSlot {
+                weekday: weekday_from_number(value.weekday),
+                starts_at: value.starts_at,
+                ends_at: value.ends_at,
+            },
+            recurrence: value.recurrence.into(),
+            valid_from: value.valid_from,
+            valid_until: value.valid_until,
+        }
+    }
+}
+
+#[derive(Debug, Clone, sqlx::Type)]
+#[sqlx(type_name = "recurrence")]
+#[sqlx(rename_all = "snake_case")]
+pub enum PgRecurrence {
+    Weekly,
+    BiWeekly,
+}
+
+impl From<Recurrence> for PgRecurrence {
+    fn from(value: Recurrence) -> Self {
+        match value {
+            Recurrence::Weekly => PgRecurrence::Weekly,
+            Recurrence::BiWeekly => PgRecurrence::BiWeekly,
+        }
+    }
+}
+
+impl From<PgRecurrence> for Recurrence {
+    fn from(value: PgRecurrence) -> Self {
+        match value {
+            PgRecurrence::Weekly => Recurrence::Weekly,
+            PgRecurrence::BiWeekly => Recurrence::BiWeekly,
+        }
+    }
+}
+
+impl Display for PgRecurrence {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::Weekly => "weekly",
+                Self::BiWeekly => "bi_weekly",
+            }
+        )
+    }
+}
+
+impl PgEnum for PgRecurrence {
+    const TYPE_NAME: &'static str = "recurrence";
+}
@@ -0,0 +1,204 @@
+//! Reading of uploaded spreadsheets into rows keyed by the headers of their first row
+
+use std::{collections::HashMap, io::Cursor};
+
+use anyhow::Context;
+use app::student_import_service::ImportRow;
+use calamine::{open_workbook_from_rs, DataType, Reader, Xlsx};
+use time::{Date, Duration};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SpreadsheetFormat {
+    Csv,
+    Xlsx,
+}
+
+impl SpreadsheetFormat {
+    /// Format by a content type, a file name or an extension
+    pub fn detect(value: &str) -> Option<SpreadsheetFormat> {
+        let value = value.split(';').next()?.trim().to_lowercase();
+        let extension = value.rsplit('.').next()?;
+
+        if value == "text/csv" || extension == "csv" {
+            Some(SpreadsheetFormat::Csv)
+        } else if value == "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet"
+            || extension == "xlsx"
+        {
+            Some(SpreadsheetFormat::Xlsx)
+        } else {
+            None
+        }
+    }
+}
+
+/// Rows of a csv file or of the first sheet of a workbook, blank rows are skipped
+pub fn read_rows(format: SpreadsheetFormat, bytes: &[u8]) -> Result<Vec<ImportRow>, anyhow::Error> {
+    let records = match format {
+        SpreadsheetFormat::Csv => read_csv(bytes)?,
+        SpreadsheetFormat::Xlsx => read_xlsx(bytes)?,
+    };
+
+    let mut records = records.into_iter().enumerate();
+    let Some((_, headers)) = records.next() else {
+        return Ok(vec![]);
+    };
+
+    let rows = records
+        .filter(|(_, record)| record.iter().any(|v| !v.trim().is_empty()))
+        .map(|(index, record)| ImportRow {
+            number: index + 1,
+            cells: headers
+                .iter()
+                .cloned()
+                .zip(record)
+                .filter(|(header, _)| !header.trim().is_empty())
+                .collect::<HashMap<_, _>>(),
+        })
+        .collect();
+
+    Ok(rows)
+}
+
+/// Files saved by spreadsheet editors with russian locale use semicolons, others use commas
+fn read_csv(bytes: &[u8]) -> Result<Vec<Vec<String>>, anyhow::Error> {
+    let bytes = bytes.strip_prefix(b"\xEF\xBB\xBF").unwrap_or(bytes);
+    let first_line = bytes.split(|v| *v == b'\n').next().unwrap_or_default();
+    let delimiter = match first_line.contains(&b';') && !first_line.contains(&b',') {
+        true => b';',
+        false => b',',
+    };
+
+    let mut reader = csv::ReaderBuilder::new()
+        .has_headers(false)
+        .flexible(true)
+        .delimiter(delimiter)
+        .from_reader(bytes);
+
+    let mut records = vec![];
+    for record in reader.records() {
+        let record = record.context("malformed csv")?;
+        records.push(record.iter().map(str::to_owned).collect());
+    }
+
+    Ok(records)
+}
+
+fn read_xlsx(bytes: &[u8]) -> Result<Vec<Vec<String>>, anyhow::Error> {
+    let mut workbook: Xlsx<_> =
+        open_workbook_from_rs(Cursor::new(bytes)).context("malformed xlsx workbook")?;
+
+    let Some(range) = workbook.worksheet_range_at(0) else {
+        return Ok(vec![]);
+    };
+    let range = range.context("malformed xlsx worksheet")?;
+
+    let records = range
+        .rows()
+        .map(|row| row.iter().map(cell_to_string).collect())
+        .collect();
+
+    Ok(records)
+}
+
+/// Dates are written the way the import expects them, whole numbers without a fractional part
+fn cell_to_string(cell: &DataType) -> String {
+    match cell {
+        DataType::Empty | DataType::Error(_) => String::new(),
+        DataType::String(v) | DataType::DateTimeIso(v) | DataType::DurationIso(v) => v.clone(),
+        DataType::Int(v) => v.to_string(),
+        DataType::Float(v) if v.fract() == 0.0 => format!("{v:.0}"),
+        DataType::Float(v) | DataType::Duration(v) => v.to_string(),
+        DataType::Bool(v) => v.to_string(),
+        DataType::DateTime(v) => excel_date(*v)
+            .map(|date| date.to_string())
+            .unwrap_or_else(|| v.to_string()),
+    }
+}
+
+/// Excel counts days from 1899-12-30, that accounts for its nonexistent 1900-02-29
+fn excel_date(serial: f64) -> Option<Date> {
+    let epoch = Date::from_calendar_date(1899, time::Month::December, 30).ok()?;
+    epoch.checked_add(Duration::days(serial.floor() as i64))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cells(row: &ImportRow) -> Vec<(&str, &str)> {
+        let mut cells = row
+            .cells
+            .iter()
+            .map(|(k, v)| (k.as_str(), v.as_str()))
+            .collect::<Vec<_>>();
+        cells.sort();
+        cells
+    }
+
+    #[test]
+    fn formats_are_detected_by_content_type_or_extension() {
+        assert_eq!(
+            SpreadsheetFormat::detect("text/csv; charset=utf-8"),
+            Some(SpreadsheetFormat::Csv)
+        );
+        assert_eq!(
+            SpreadsheetFormat::detect("admissions.XLSX"),
+            Some(SpreadsheetFormat::Xlsx)
+        );
+        assert_eq!(
+            SpreadsheetFormat::detect("xlsx"),
+            Some(SpreadsheetFormat::Xlsx)
+        );
+        assert_eq!(SpreadsheetFormat::detect("application/pdf"), None);
+    }
+
+    #[test]
+    fn csv_rows_are_keyed_by_headers_and_numbered_from_the_header() {
+        let csv =
+            "email,группа,\nd.churikov@stud.nstu.ru,avt-113,note\n,,\nreva@corp.nstu.ru,avt-013\n";
+
+        let rows = read_rows(SpreadsheetFormat::Csv, csv.as_bytes()).unwrap();
+
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0].number, 2);
+        assert_eq!(
+            cells(&rows[0]),
+            vec![("email", "d.churikov@stud.nstu.ru"), ("группа", "avt-113")]
+        );
+        // the blank third row is skipped but still counted
+        assert_eq!(rows[1].number, 4);
+        assert_eq!(
+            cells(&rows[1]),
+            vec![("email", "reva@corp.nstu.ru"), ("группа", "avt-013")]
+        );
+    }
+
+    #[test]
+    fn semicolon_csv_with_bom_is_read() {
+        let csv = "\u{feff}фамилия;дата_рождения\r\nЧуриков;31.08.2005\r\n";
+
+        let rows = read_rows(SpreadsheetFormat::Csv, csv.as_bytes()).unwrap();
+
+        assert_eq!(rows.len(), 1);
+        assert_eq!(
+            cells(&rows[0]),
+            vec![("дата_рождения", "31.08.2005"), ("фамилия", "Чуриков")]
+        );
+    }
+
+    #[test]
+    fn empty_spreadsheet_has_no_rows() {
+        assert!(read_rows(SpreadsheetFormat::Csv, b"").unwrap().is_empty());
+        assert!(read_rows(SpreadsheetFormat::Xlsx, b"not a workbook").is_err());
+    }
+
+    #[test]
+    fn excel_serials_are_converted_to_dates() {
+        assert_eq!(
+            excel_date(38595.0),
+            Date::from_calendar_date(2005, time::Month::August, 31).ok()
+        );
+        assert_eq!(cell_to_string(&DataType::Float(2005.0)), "2005");
+        assert_eq!(cell_to_string(&DataType::DateTime(38595.5)), "2005-08-31");
+    }
+}
@@ -8,10 +8,12 @@ use app::{
 use sea_query::{Asterisk, Condition, Expr, IntoCondition, Query};
 use tokio::sync::Mutex;
 
-use self::model::{JoinRow, StudentAttestations, StudentAttestationsIden, Students, StudentsIden};
-use crate::{fetch_all, fetch_one, PgTransaction};
+use self::model::{
+    JoinRow, PgStudentStatus, StudentAttestations, StudentAttestationsIden, Students, StudentsIden,
+};
+use crate::{execute, fetch_all, fetch_one, PgEnum, PgTransaction};
 
-mod model;
+pub(crate) mod model;
 
 pub struct PgStudentRepo {
     pub txn: std::sync::Arc<Mutex<PgTransaction<'static>>>,
@@ -22,10 +24,15 @@ impl PgStudentRepo {
         let mut query = Query::insert();
         query
             .into_table(StudentsIden::Table)
-            .columns([StudentsIden::PersonId, StudentsIden::StudyGroupId])
+            .columns([
+                StudentsIden::PersonId,
+                StudentsIden::StudyGroupId,
+                StudentsIden::Status,
+            ])
             .values_panic([
                 entity.person_id.value.into(),
                 entity.study_group_id.value.into(),
+                PgStudentStatus::from(entity.status).into_expr(),
             ])
             .returning_all();
 
@@ -43,6 +50,10 @@ impl PgStudentRepo {
                     StudentsIden::StudyGroupId,
                     entity.study_group_id.value.into(),
                 ),
+                (
+                    StudentsIden::Status,
+                    PgStudentStatus::from(entity.status).into_expr(),
+                ),
             ])
             .and_where(Expr::col(StudentsIden::Id).eq(entity.id.value))
             .returning_all();
@@ -63,8 +74,13 @@ impl PgStudentRepo {
         let mut query = Query::select();
         query
             .from(student_table)
-            .column(Asterisk)
-            .join(sea_query::JoinType::InnerJoin, attestation_table, on)
+            .column((student_table, Asterisk))
+            .columns([
+                (attestation_table, StudentAttestationsIden::AttestationId),
+                (attestation_table, StudentAttestationsIden::Score),
+                (attestation_table, StudentAttestationsIden::IsFinal),
+            ])
+            .join(sea_query::JoinType::LeftJoin, attestation_table, on)
             .cond_where(cond);
 
         let results = fetch_all::<JoinRow>(&self.txn, &query).await?;
@@ -74,13 +90,26 @@ impl PgStudentRepo {
     fn entity_from_select(select: Vec<JoinRow>) -> Option<Entity> {
         let (models, attestations) = select
             .into_iter()
-            .map(|v| (v.student, v.attestation))
+            .map(|v| (v.student, (v.attestation_id, v.score, v.is_final)))
             .unzip::<_, _, Vec<_>, Vec<_>>();
 
         let Some(model) = models.into_iter().take(1).next() else {
             return None;
         };
 
+        let attestations = attestations
+            .into_iter()
+            .filter_map(|(attestation_id, score, is_final)| {
+                Some((attestation_id?, score?, is_final?))
+            })
+            .map(|(attestation_id, score, is_final)| StudentAttestations {
+                student_id: model.id,
+                attestation_id,
+                score,
+                is_final,
+            })
+            .collect();
+
         Some(model.into_entity(attestations))
     }
 
@@ -90,7 +119,7 @@ impl PgStudentRepo {
             .from_table(StudentAttestationsIden::Table)
             .and_where(Expr::col(StudentAttestationsIden::StudentId).eq(id));
 
-        fetch_one::<()>(&self.txn, &query).await
+        execute(&self.txn, &query).await
     }
 
     async fn insert_attestations(
@@ -108,11 +137,13 @@ impl PgStudentRepo {
                     StudentAttestationsIden::StudentId,
                     StudentAttestationsIden::AttestationId,
                     StudentAttestationsIden::Score,
+                    StudentAttestationsIden::IsFinal,
                 ])
                 .values_panic([
                     id.into(),
                     attestation.attestation_id.value.into(),
                     attestation.score.into(),
+                    attestation.is_final.into(),
                 ])
                 .returning_all();
 
@@ -168,8 +199,8 @@ impl student::Repo for PgStudentRepo {
             .from_table(StudentsIden::Table)
             .and_where(Expr::col(StudentsIden::Id).eq(entity.id.value));
 
-        fetch_one::<()>(&self.txn, &query).await?;
-        let _ = self.delete_attestations(entity.id.value).await?;
+        self.delete_attestations(entity.id.value).await?;
+        execute(&self.txn, &query).await?;
 
         Ok(())
     }
@@ -183,6 +214,14 @@ impl student::Repo for PgStudentRepo {
         Ok(entity)
     }
 
+    async fn find_many(&self, ids: HashSet<EntityId>) -> Result<Vec<Entity>, anyhow::Error> {
+        self.list(
+            Expr::col((StudentsIden::Table, StudentsIden::Id))
+                .is_in(ids.into_iter().map(|id| id.value)),
+        )
+        .await
+    }
+
     async fn list_by_person(
         &self,
         person_id: person::EntityId,
@@ -1,13 +1,18 @@
-use app::student;
+use std::fmt::Display;
+
+use app::student::{self, StudentStatus};
 use sqlx::FromRow;
 use utils::entity::Id;
 
+use crate::PgEnum;
+
 #[derive(Clone, Debug, FromRow)]
 #[sea_query::enum_def]
 pub struct Students {
     pub id: i32,
     pub person_id: i32,
     pub study_group_id: i32,
+    pub status: PgStudentStatus,
 }
 
 impl Students {
@@ -16,11 +21,13 @@ impl Students {
             id: Id::new(self.id),
             person_id: Id::new(self.person_id),
             study_group_id: Id::new(self.study_group_id),
+            status: self.status.into(),
             attestations: attestations
                 .into_iter()
                 .map(|v| student::StudentAttestation {
                     attestation_id: Id::new(v.attestation_id),
                     score: v.score,
+                    is_final: v.is_final,
                 })
                 .collect(),
         }
@@ -33,12 +40,65 @@ pub struct StudentAttestations {
     pub student_id: i32,
     pub attestation_id: i32,
     pub score: i32,
+    pub is_final: bool,
 }
 
 #[derive(Clone, Debug, FromRow)]
 pub struct JoinRow {
     #[sqlx(flatten)]
     pub student: Students,
-    #[sqlx(flatten)]
-    pub attestation: StudentAttestations,
+    pub attestation_id: Option<i32>,
+    pub score: Option<i32>,
+    pub is_final: Option<bool>,
+}
+
+#[derive(Debug, Clone, Copy, sqlx::Type)]
+#[sqlx(type_name = "student_status")]
+#[sqlx(rename_all = "snake_case")]
+pub enum PgStudentStatus {
+    Active,
+    OnLeave,
+    Expelled,
+    Graduated,
+}
+
+impl From<StudentStatus> for PgStudentStatus {
+    fn from(value: StudentStatus) -> Self {
+        match value {
+            StudentStatus::Active => PgStudentStatus::Active,
+            StudentStatus::OnLeave => PgStudentStatus::OnLeave,
+            StudentStatus::Expelled => PgStudentStatus::Expelled,
+            StudentStatus::Graduated => PgStudentStatus::Graduated,
+        }
+    }
+}
+
+impl From<PgStudentStatus> for StudentStatus {
+    fn from(value: PgStudentStatus) -> Self {
+        match value {
+            PgStudentStatus::Active => StudentStatus::Active,
+            PgStudentStatus::OnLeave => StudentStatus::OnLeave,
+            PgStudentStatus::Expelled => StudentStatus::Expelled,
+            PgStudentStatus::Graduated => StudentStatus::Graduated,
+        }
+    }
+}
+
+impl Display for PgStudentStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::Active => "active",
+                Self::OnLeave => "on_leave",
+                Self::Expelled => "expelled",
+                Self::Graduated => "graduated",
+            }
+        )
+    }
+}
+
+impl PgEnum for PgStudentStatus {
+    const TYPE_NAME: &'static str = "student_status";
 }
@@ -0,0 +1,141 @@
+mod model;
+
+use app::{
+    student,
+    student_order::{self, Entity, EntityId},
+    study_group,
+};
+use sea_query::{Asterisk, Expr, Order, Query, SimpleExpr};
+use std::{collections::HashSet, sync::Arc};
+use tokio::sync::Mutex;
+
+use crate::{execute, fetch_all, fetch_one, fetch_optional, PgEnum, PgTransaction};
+
+use self::model::{PgOrderKind, StudentOrders, StudentOrdersIden};
+
+pub struct PgStudentOrderRepo {
+    pub txn: Arc<Mutex<PgTransaction<'static>>>,
+}
+
+impl PgStudentOrderRepo {
+    fn values(entity: &Entity) -> [(StudentOrdersIden, SimpleExpr); 6] {
+        [
+            (StudentOrdersIden::StudentId, entity.student_id.value.into()),
+            (
+                StudentOrdersIden::Kind,
+                PgOrderKind::from(entity.kind).into_expr(),
+            ),
+            (
+                StudentOrdersIden::StudyGroupId,
+                entity.study_group_id.value.into(),
+            ),
+            (StudentOrdersIden::Date, entity.date.into()),
+            (StudentOrdersIden::Reason, entity.reason.clone().into()),
+            (
+                StudentOrdersIden::AuthorId,
+                entity.author_id.map(|v| v.value).into(),
+            ),
+        ]
+    }
+
+    async fn list(&self, cond: SimpleExpr) -> Result<Vec<Entity>, anyhow::Error> {
+        let models = fetch_all::<StudentOrders>(
+            &self.txn,
+            Query::select()
+                .from(StudentOrdersIden::Table)
+                .column(Asterisk)
+                .and_where(cond)
+                .order_by(StudentOrdersIden::Date, Order::Asc)
+                .order_by(StudentOrdersIden::Id, Order::Asc),
+        )
+        .await?;
+
+        Ok(models.into_iter().map(Into::into).collect())
+    }
+}
+
+#[async_trait::async_trait]
+impl student_order::Repo for PgStudentOrderRepo {
+    async fn save(&mut self, entity: Entity) -> Result<Entity, anyhow::Error> {
+        let model = if self.find(entity.id).await?.is_some() {
+            fetch_one::<StudentOrders>(
+                &self.txn,
+                Query::update()
+                    .table(StudentOrdersIden::Table)
+                    .values(Self::values(&entity))
+                    .and_where(Expr::col(StudentOrdersIden::Id).eq(entity.id.value))
+                    .returning_all(),
+            )
+            .await?
+        } else {
+            let (columns, values): (Vec<_>, Vec<_>) = Self::values(&entity).into_iter().unzip();
+
+            fetch_one::<StudentOrders>(
+                &self.txn,
+                Query::insert()
+                    .into_table(StudentOrdersIden::Table)
+                    .columns(columns)
+                    .values_panic(values)
+                    .returning_all(),
+            )
+            .await?
+        };
+
+        Ok(model.into())
+    }
+
+    async fn find(&self, id: EntityId) -> Result<Option<Entity>, anyhow::Error> {
+        let model = fetch_optional::<StudentOrders>(
+            &self.txn,
+            Query::select()
+                .from(StudentOrdersIden::Table)
+                .column(Asterisk)
+                .and_where(Expr::col(StudentOrdersIden::Id).eq(id.value)),
+        )
+        .await?;
+
+        Ok(model.map(Into::into))
+    }
+
+    async fn list_by_student(
+        &self,
+        student_id: student::EntityId,
+    ) -> Result<Vec<Entity>, anyhow::Error> {
+        self.list(Expr::col(StudentOrdersIden::StudentId).eq(student_id.value))
+            .await
+    }
+
+    async fn list_by_students(
+        &self,
+        students_ids: HashSet<student::EntityId>,
+    ) -> Result<Vec<Entity>, anyhow::Error> {
+        self.list(
+            Expr::col(StudentOrdersIden::StudentId)
+                .is_in(students_ids.into_iter().map(|id| id.value)),
+        )
+        .await
+    }
+
+    async fn list_by_study_group(
+        &self,
+        study_group_id: study_group::EntityId,
+    ) -> Result<Vec<Entity>, anyhow::Error> {
+        self.list(Expr::col(StudentOrdersIden::StudyGroupId).eq(study_group_id.value))
+            .await
+    }
+
+    async fn delete_by_student(
+        &mut self,
+        student_id: student::EntityId,
+    ) -> Result<(), anyhow::Error> {
+        execute(
+            &self.txn,
+            Query::delete()
+                .from_table(StudentOrdersIden::Table)
+                .and_where(Expr::col(StudentOrdersIden::StudentId).eq(student_id.value)),
+        )
+        .await?;
+
+        Ok(())
+    }
+}
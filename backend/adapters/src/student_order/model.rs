@@ -0,0 +1,92 @@
+use std::fmt::Display;
+
+use app::student_order::{self, OrderKind};
+use sqlx::FromRow;
+use utils::entity::Id;
+
+use crate::PgEnum;
+
+#[derive(Clone, Debug, FromRow)]
+#[sea_query::enum_def]
+pub struct StudentOrders {
+    pub id: i32,
+    pub student_id: i32,
+    pub kind: PgOrderKind,
+    pub study_group_id: i32,
+    pub date: time::Date,
+    pub reason: String,
+    pub author_id: Option<i32>,
+}
+
+impl From<StudentOrders> for student_order::Entity {
+    fn from(value: StudentOrders) -> Self {
+        student_order::Entity {
+            id: Id::new(value.id),
+            student_id: Id::new(value.student_id),
+            kind: value.kind.into(),
+            study_group_id: Id::new(value.study_group_id),
+            date: value.date,
+            reason: value.reason,
+            author_id: value.author_id.map(Id::new),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, sqlx::Type)]
+#[sqlx(type_name = "student_order_kind")]
+#[sqlx(rename_all = "snake_case")]
+pub enum PgOrderKind {
+    Enrollment,
+    Transfer,
+    AcademicLeave,
+    ReturnFromLeave,
+    Expulsion,
+    Graduation,
+}
+
+impl From<OrderKind> for PgOrderKind {
+    fn from(value: OrderKind) -> Self {
+        match value {
+            OrderKind::Enrollment => PgOrderKind::Enrollment,
+            OrderKind::Transfer => PgOrderKind::Transfer,
+            OrderKind::AcademicLeave => PgOrderKind::AcademicLeave,
+            OrderKind::ReturnFromLeave => PgOrderKind::ReturnFromLeave,
+            OrderKind::Expulsion => PgOrderKind::Expulsion,
+            OrderKind::Graduation => PgOrderKind::Graduation,
+        }
+    }
+}
+
+impl From<PgOrderKind> for OrderKind {
+    fn from(value: PgOrderKind) -> Self {
+        match value {
+            PgOrderKind::Enrollment => OrderKind::Enrollment,
+            PgOrderKind::Transfer => OrderKind::Transfer,
+            PgOrderKind::AcademicLeave => OrderKind::AcademicLeave,
+            PgOrderKind::ReturnFromLeave => OrderKind::ReturnFromLeave,
+            PgOrderKind::Expulsion => OrderKind::Expulsion,
+            PgOrderKind::Graduation => OrderKind::Graduation,
+        }
+    }
+}
+
+impl Display for PgOrderKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::Enrollment => "enrollment",
+                Self::Transfer => "transfer",
+                Self::AcademicLeave => "academic_leave",
+                Self::ReturnFromLeave => "return_from_leave",
+                Self::Expulsion => "expulsion",
+                Self::Graduation => "graduation",
+            }
+        )
+    }
+}
+
+impl PgEnum for PgOrderKind {
+    const TYPE_NAME: &'static str = "student_order_kind";
+}
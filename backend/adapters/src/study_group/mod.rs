@@ -1,17 +1,24 @@
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
 
 use app::{
     curriculum,
-    study_group::{self, Entity, EntityId},
+    paging::{Page, Paginated, Sort},
+    study_group::{self, Entity, EntityId, ListFilter, SortKey},
+    subdivision,
+};
+use sea_query::{
+    extension::postgres::PgExpr, Asterisk, Condition, ConditionalStatement, Expr, IntoCondition,
+    Order, Query, Value,
 };
-use sea_query::{Asterisk, Condition, ConditionalStatement, Expr, IntoCondition, Query, Value};
 use tokio::sync::Mutex;
 
 use self::models::{
     JoinRow, PgQualification, PgTrainingKind, StudyGroupCurriculums, StudyGroupCurriculumsIden,
     StudyGroups, StudyGroupsIden,
 };
-use crate::{fetch_all, fetch_one, PgTransaction};
+use crate::{
+    execute, fetch_all, fetch_count, fetch_one, order_of, prefix_pattern, PgEnum, PgTransaction,
+};
 
 mod models;
 
@@ -29,16 +36,14 @@ impl PgStudyGroupRepo {
                 StudyGroupsIden::StudyingQualification,
                 StudyGroupsIden::TrainingKind,
                 StudyGroupsIden::DepartmentId,
+                StudyGroupsIden::EnrollmentYear,
             ])
             .values_panic([
                 entity.name.into(),
-                PgQualification::from(entity.studying_qualification)
-                    .to_string()
-                    .into(),
-                PgTrainingKind::from(entity.training_kind)
-                    .to_string()
-                    .into(),
+                PgQualification::from(entity.studying_qualification).into_expr(),
+                PgTrainingKind::from(entity.training_kind).into_expr(),
                 entity.department_id.value.into(),
+                entity.enrollment_year.into(),
             ])
             .returning_all();
 
@@ -54,20 +59,20 @@ impl PgStudyGroupRepo {
                 (StudyGroupsIden::Name, entity.name.into()),
                 (
                     StudyGroupsIden::StudyingQualification,
-                    PgQualification::from(entity.studying_qualification)
-                        .to_string()
-                        .into(),
+                    PgQualification::from(entity.studying_qualification).into_expr(),
                 ),
                 (
                     StudyGroupsIden::TrainingKind,
-                    PgTrainingKind::from(entity.training_kind)
-                        .to_string()
-                        .into(),
+                    PgTrainingKind::from(entity.training_kind).into_expr(),
                 ),
                 (
                     StudyGroupsIden::DepartmentId,
                     entity.department_id.value.into(),
                 ),
+                (
+                    StudyGroupsIden::EnrollmentYear,
+                    entity.enrollment_year.into(),
+                ),
             ])
             .and_where(Expr::col(StudyGroupsIden::Id).eq(entity.id.value))
             .returning_all();
@@ -81,7 +86,8 @@ impl PgStudyGroupRepo {
         let study_group_table = StudyGroupsIden::Table;
         let curriculum_table = StudyGroupCurriculumsIden::Table;
         let study_group_id = StudyGroupsIden::Id;
-        let curriculum_study_group_id = StudyGroupCurriculumsIden::CurriculumId;
+        let curriculum_study_group_id = StudyGroupCurriculumsIden::StudyGroupId;
+        let curriculum_id = StudyGroupCurriculumsIden::CurriculumId;
 
         let on = Expr::col((study_group_table, study_group_id))
             .equals((curriculum_table, curriculum_study_group_id));
@@ -89,8 +95,9 @@ impl PgStudyGroupRepo {
         let mut query = Query::select();
         query
             .from(study_group_table)
-            .column(Asterisk)
-            .join(sea_query::JoinType::InnerJoin, curriculum_table, on)
+            .column((study_group_table, Asterisk))
+            .column((curriculum_table, curriculum_id))
+            .join(sea_query::JoinType::LeftJoin, curriculum_table, on)
             .cond_where(cond);
 
         let results = fetch_all::<JoinRow>(&self.txn, &query).await?;
@@ -100,13 +107,22 @@ impl PgStudyGroupRepo {
     fn entity_from_select(select: Vec<JoinRow>) -> Option<Entity> {
         let (models, curriculums) = select
             .into_iter()
-            .map(|v| (v.study_group, v.curriculum))
+            .map(|v| (v.study_group, v.curriculum_id))
             .unzip::<_, _, Vec<_>, Vec<_>>();
 
         let Some(model) = models.into_iter().take(1).next() else {
             return None;
         };
 
+        let curriculums = curriculums
+            .into_iter()
+            .flatten()
+            .map(|curriculum_id| StudyGroupCurriculums {
+                study_group_id: model.id,
+                curriculum_id,
+            })
+            .collect();
+
         Some(model.into_entity(curriculums))
     }
 
@@ -116,7 +132,7 @@ impl PgStudyGroupRepo {
             .from_table(StudyGroupCurriculumsIden::Table)
             .and_where(Expr::col(StudyGroupCurriculumsIden::StudyGroupId).eq(id));
 
-        fetch_one::<()>(&self.txn, &query).await
+        execute(&self.txn, &query).await
     }
 
     async fn insert_curriculums(
@@ -168,8 +184,8 @@ impl study_group::Repo for PgStudyGroupRepo {
             .from_table(StudyGroupsIden::Table)
             .and_where(Expr::col(StudyGroupsIden::Id).eq(entity.id.value));
 
-        fetch_one::<()>(&self.txn, &query).await?;
-        let _ = self.delete_curriculums(entity.id.value).await?;
+        self.delete_curriculums(entity.id.value).await?;
+        execute(&self.txn, &query).await?;
 
         Ok(())
     }
@@ -183,6 +199,30 @@ impl study_group::Repo for PgStudyGroupRepo {
         Ok(entity)
     }
 
+    async fn find_many(&self, ids: HashSet<EntityId>) -> Result<Vec<Entity>, anyhow::Error> {
+        let select = self
+            .select(
+                Expr::col((StudyGroupsIden::Table, StudyGroupsIden::Id))
+                    .is_in(ids.into_iter().map(|id| id.value)),
+            )
+            .await?;
+
+        let mut groups = BTreeMap::<i32, Vec<JoinRow>>::new();
+        for join_row in select {
+            groups
+                .entry(join_row.study_group.id)
+                .or_default()
+                .push(join_row);
+        }
+
+        let entities = groups
+            .into_values()
+            .filter_map(Self::entity_from_select)
+            .collect();
+
+        Ok(entities)
+    }
+
     async fn find_by_name(&self, name: String) -> Result<Option<Entity>, anyhow::Error> {
         let select = self
             .select(Expr::col((StudyGroupsIden::Table, StudyGroupsIden::Name)).eq(name))
@@ -192,8 +232,92 @@ impl study_group::Repo for PgStudyGroupRepo {
         Ok(entity)
     }
 
-    async fn list(&self) -> Result<Vec<Entity>, anyhow::Error> {
-        let select = self.select(Expr::value(true)).await?;
+    async fn list(
+        &self,
+        filter: ListFilter,
+        sort: Sort<SortKey>,
+        page: Page,
+    ) -> Result<Paginated<Entity>, anyhow::Error> {
+        let mut cond = Condition::all();
+        if let Some(name_prefix) = filter.name_prefix {
+            cond = cond.add(Expr::col(StudyGroupsIden::Name).ilike(prefix_pattern(&name_prefix)));
+        }
+        if let Some(qualification) = filter.qualification {
+            cond = cond.add(
+                Expr::col(StudyGroupsIden::StudyingQualification)
+                    .eq(PgQualification::from(qualification).into_expr()),
+            );
+        }
+        if let Some(training_kind) = filter.training_kind {
+            cond = cond.add(
+                Expr::col(StudyGroupsIden::TrainingKind)
+                    .eq(PgTrainingKind::from(training_kind).into_expr()),
+            );
+        }
+        if let Some(department_id) = filter.department_id {
+            cond = cond.add(Expr::col(StudyGroupsIden::DepartmentId).eq(department_id.value));
+        }
+
+        let sort_column = match sort.key {
+            SortKey::Id => StudyGroupsIden::Id,
+            SortKey::Name => StudyGroupsIden::Name,
+        };
+
+        // the page is cut from study groups alone, joined rows would break limit and offset
+        let mut query = Query::select();
+        query
+            .from(StudyGroupsIden::Table)
+            .column(StudyGroupsIden::Id)
+            .cond_where(cond.clone())
+            .order_by(sort_column, order_of(sort.order))
+            .order_by(StudyGroupsIden::Id, Order::Asc)
+            .limit(page.limit)
+            .offset(page.offset);
+
+        let ids = fetch_all::<(i32,)>(&self.txn, &query)
+            .await?
+            .into_iter()
+            .map(|(id,)| id)
+            .collect::<Vec<_>>();
+
+        let select = self
+            .select(Expr::col((StudyGroupsIden::Table, StudyGroupsIden::Id)).is_in(ids.clone()))
+            .await?;
+
+        let mut groups = HashMap::<i32, Vec<JoinRow>>::new();
+        for join_row in select {
+            groups
+                .entry(join_row.study_group.id)
+                .or_default()
+                .push(join_row);
+        }
+
+        let items = ids
+            .into_iter()
+            .filter_map(|id| groups.remove(&id))
+            .filter_map(Self::entity_from_select)
+            .collect();
+
+        let mut query = Query::select();
+        query
+            .from(StudyGroupsIden::Table)
+            .expr(Expr::col(Asterisk).count())
+            .cond_where(cond);
+
+        let total = fetch_count(&self.txn, &query).await?;
+        Ok(Paginated { items, total })
+    }
+
+    async fn list_by_department(
+        &self,
+        department_id: subdivision::EntityId,
+    ) -> Result<Vec<Entity>, anyhow::Error> {
+        let select = self
+            .select(
+                Expr::col((StudyGroupsIden::Table, StudyGroupsIden::DepartmentId))
+                    .eq(department_id.value),
+            )
+            .await?;
 
         let mut groups = HashMap::<i32, Vec<JoinRow>>::new();
         for join_row in select {
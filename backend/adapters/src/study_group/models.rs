@@ -4,6 +4,8 @@ use app::study_group::{self, Qualification, TrainingKind};
 use sqlx::FromRow;
 use utils::entity::Id;
 
+use crate::PgEnum;
+
 #[derive(Clone, Debug, FromRow)]
 #[sea_query::enum_def]
 pub struct StudyGroups {
@@ -12,14 +14,14 @@ pub struct StudyGroups {
     pub studying_qualification: PgQualification,
     pub training_kind: PgTrainingKind,
     pub department_id: i32,
+    pub enrollment_year: Option<i32>,
 }
 
 #[derive(Clone, Debug, FromRow)]
 pub struct JoinRow {
     #[sqlx(flatten)]
     pub study_group: StudyGroups,
-    #[sqlx(flatten)]
-    pub curriculum: StudyGroupCurriculums,
+    pub curriculum_id: Option<i32>,
 }
 
 impl StudyGroups {
@@ -34,6 +36,7 @@ impl StudyGroups {
                 .into_iter()
                 .map(|v| Id::new(v.curriculum_id))
                 .collect(),
+            enrollment_year: self.enrollment_year,
         }
     }
 }
@@ -131,3 +134,11 @@ impl From<PgTrainingKind> for TrainingKind {
         }
     }
 }
+
+impl PgEnum for PgQualification {
+    const TYPE_NAME: &'static str = "qualification";
+}
+
+impl PgEnum for PgTrainingKind {
+    const TYPE_NAME: &'static str = "training_kind";
+}
@@ -1,13 +1,13 @@
 mod models;
 
 use app::{
+    paging::{Page, Paginated, Sort},
     person,
-    subdivision::{self, Entity, EntityId},
+    subdivision::{self, Entity, EntityId, ListFilter, SortKey},
     tag, university,
 };
 use sea_query::{
-    Alias, Asterisk, Condition, DynIden, Expr, IntoCondition, JoinType, Query, SeaRc,
-    SelectStatement,
+    extension::postgres::PgExpr, Asterisk, Condition, Expr, IntoCondition, Order, Query,
 };
 use std::{
     collections::{HashMap, HashSet},
@@ -16,7 +16,7 @@ use std::{
 use tokio::sync::Mutex;
 
 use crate::{
-    fetch_all, fetch_one,
+    execute, fetch_all, fetch_count, fetch_one, order_of, prefix_pattern,
     subdivision::models::{JoinRow, SubdivisionTagsIden, SubdivisionsIden},
     PgTransaction,
 };
@@ -32,8 +32,16 @@ impl PgSubdivisionRepo {
         let mut query = Query::insert();
         let query = query
             .into_table(SubdivisionsIden::Table)
-            .columns([SubdivisionsIden::Name, SubdivisionsIden::UniversityId])
-            .values_panic([entity.name.into(), entity.university_id.value.into()])
+            .columns([
+                SubdivisionsIden::Name,
+                SubdivisionsIden::UniversityId,
+                SubdivisionsIden::ParentId,
+            ])
+            .values_panic([
+                entity.name.into(),
+                entity.university_id.value.into(),
+                entity.parent_id.map(|id| id.value).into(),
+            ])
             .returning_all();
 
         fetch_one::<Subdivisions>(&self.txn, query).await
@@ -49,6 +57,10 @@ impl PgSubdivisionRepo {
                     SubdivisionsIden::UniversityId,
                     entity.university_id.value.into(),
                 ),
+                (
+                    SubdivisionsIden::ParentId,
+                    entity.parent_id.map(|id| id.value).into(),
+                ),
             ])
             .and_where(Expr::col(SubdivisionsIden::Id).eq(entity.id.value))
             .returning_all();
@@ -127,66 +139,61 @@ impl PgSubdivisionRepo {
         Ok(inserted_members)
     }
 
-    // select * from subdivisions as s join (select m.subdivision_id, m.person_id, m.role, t.tag_name from subdivision_members as m full outer join subdivision_tags as t on m.subdivision_id = t.subdivision_id) as r on s.id = r.subdivision_id where s.x = y;
+    // select s.*, t.tag_name, m.person_id, m.role from subdivisions as s left join subdivision_tags as t on s.id = t.subdivision_id left join subdivision_members as m on s.id = m.subdivision_id where s.x = y;
     async fn select(&self, cond: impl IntoCondition) -> Result<Vec<JoinRow>, anyhow::Error> {
         let subdivision_table = SubdivisionsIden::Table;
         let subdivision_id = SubdivisionsIden::Id;
-        let member_subdivision_id = SubdivisionMembersIden::SubdivisionId;
-
-        let mut query = Query::select();
-
-        let (subquery_alias, subquery) = Self::join_members_and_tags();
+        let tag_table = SubdivisionTagsIden::Table;
+        let member_table = SubdivisionMembersIden::Table;
 
-        let on = Expr::col((subquery_alias.clone(), member_subdivision_id))
-            .equals((subdivision_table, subdivision_id));
+        let tag_on = Expr::col((subdivision_table, subdivision_id))
+            .equals((tag_table, SubdivisionTagsIden::SubdivisionId));
+        let member_on = Expr::col((subdivision_table, subdivision_id))
+            .equals((member_table, SubdivisionMembersIden::SubdivisionId));
 
+        let mut query = Query::select();
         query
             .from(subdivision_table)
-            .column(Asterisk)
-            .join_subquery(JoinType::InnerJoin, subquery, subquery_alias.clone(), on)
+            .column((subdivision_table, Asterisk))
+            .column((tag_table, SubdivisionTagsIden::TagName))
+            .columns([
+                (member_table, SubdivisionMembersIden::PersonId),
+                (member_table, SubdivisionMembersIden::Role),
+            ])
+            .left_join(tag_table, tag_on)
+            .left_join(member_table, member_on)
             .cond_where(cond);
 
         fetch_all(&self.txn, &query).await
     }
 
-    fn join_members_and_tags() -> (DynIden, SelectStatement) {
-        let tag_table = SubdivisionTagsIden::Table;
-        let tag_subdivision_id = SubdivisionTagsIden::SubdivisionId;
-        let tag_name = SubdivisionTagsIden::TagName;
-
-        let member_table = SubdivisionMembersIden::Table;
-        let member_role = SubdivisionMembersIden::Role;
-        let member_subdivision_id = SubdivisionMembersIden::SubdivisionId;
-        let member_person_id = SubdivisionMembersIden::PersonId;
-
-        let alias: DynIden = SeaRc::new(Alias::new("members_and_tags"));
-        let mut query = Query::select();
-
-        let on = Expr::col((member_table, member_subdivision_id))
-            .equals((tag_table, tag_subdivision_id));
-
-        query
-            .from(member_table)
-            .column((tag_table, tag_name))
-            .column((member_table, member_role))
-            .column((member_table, member_subdivision_id))
-            .column((member_table, member_person_id))
-            .full_outer_join(tag_table, on);
-
-        (alias, query)
-    }
-
     fn entity_from_select(select: Vec<JoinRow>) -> Option<Entity> {
-        let (models, members_and_tags) = select
-            .into_iter()
-            .map(|v| (v.subdivision, (v.member, v.tag)))
-            .unzip::<_, _, Vec<_>, Vec<_>>();
-
-        let Some(model) = models.into_iter().take(1).next() else {
+        let Some(model) = select.first().map(|v| v.subdivision.clone()) else {
             return None;
         };
 
-        let (members, tags) = members_and_tags.into_iter().unzip::<_, _, Vec<_>, Vec<_>>();
+        let (tags, members) = select
+            .into_iter()
+            .map(|v| {
+                let tag = v.tag_name.map(|tag_name| SubdivisionTags {
+                    subdivision_id: model.id,
+                    tag_name,
+                });
+                let member = v
+                    .person_id
+                    .zip(v.role)
+                    .map(|(person_id, role)| SubdivisionMembers {
+                        subdivision_id: model.id,
+                        person_id,
+                        role,
+                    });
+
+                (tag, member)
+            })
+            .unzip::<_, _, Vec<_>, Vec<_>>();
+
+        let tags = tags.into_iter().flatten().collect();
+        let members = members.into_iter().flatten().collect();
 
         Some(model.into_entity(tags, members))
     }
@@ -216,9 +223,9 @@ impl subdivision::Repo for PgSubdivisionRepo {
             Expr::col((SubdivisionsIden::Table, SubdivisionsIden::Id)).eq(entity.id.value),
         );
 
-        fetch_one::<()>(&self.txn, query).await?;
         let _ = self.delete_tags(entity.id.value).await?;
         let _ = self.delete_members(entity.id.value).await?;
+        execute(&self.txn, query).await?;
 
         Ok(())
     }
@@ -267,24 +274,80 @@ impl subdivision::Repo for PgSubdivisionRepo {
         Ok(entities)
     }
 
-    async fn list(&self) -> Result<Vec<Entity>, anyhow::Error> {
-        let results = self.select(Expr::value(true)).await?;
+    async fn list(
+        &self,
+        filter: ListFilter,
+        sort: Sort<SortKey>,
+        page: Page,
+    ) -> Result<Paginated<Entity>, anyhow::Error> {
+        let mut cond = Condition::all();
+        if let Some(name_prefix) = filter.name_prefix {
+            cond = cond.add(Expr::col(SubdivisionsIden::Name).ilike(prefix_pattern(&name_prefix)));
+        }
+        if let Some(university_id) = filter.university_id {
+            cond = cond.add(Expr::col(SubdivisionsIden::UniversityId).eq(university_id.value));
+        }
+        if let Some(parent_id) = filter.parent_id {
+            cond = cond.add(Expr::col(SubdivisionsIden::ParentId).eq(parent_id.value));
+        }
+        if let Some(tag) = filter.tag {
+            let mut tagged = Query::select();
+            tagged
+                .from(SubdivisionTagsIden::Table)
+                .column(SubdivisionTagsIden::SubdivisionId)
+                .and_where(Expr::col(SubdivisionTagsIden::TagName).eq(tag.value));
+
+            cond = cond.add(Expr::col(SubdivisionsIden::Id).in_subquery(tagged.take()));
+        }
+
+        let sort_column = match sort.key {
+            SortKey::Id => SubdivisionsIden::Id,
+            SortKey::Name => SubdivisionsIden::Name,
+        };
+
+        // the page is cut from subdivisions alone, joined rows would break limit and offset
+        let mut query = Query::select();
+        query
+            .from(SubdivisionsIden::Table)
+            .column(SubdivisionsIden::Id)
+            .cond_where(cond.clone())
+            .order_by(sort_column, order_of(sort.order))
+            .order_by(SubdivisionsIden::Id, Order::Asc)
+            .limit(page.limit)
+            .offset(page.offset);
+
+        let ids = fetch_all::<(i32,)>(&self.txn, &query)
+            .await?
+            .into_iter()
+            .map(|(id,)| id)
+            .collect::<Vec<_>>();
+
+        let results = self
+            .select(Expr::col((SubdivisionsIden::Table, SubdivisionsIden::Id)).is_in(ids.clone()))
+            .await?;
 
         let mut groups = HashMap::<i32, Vec<JoinRow>>::new();
         for result in results {
             groups
                 .entry(result.subdivision.id)
-                .or_insert(vec![])
+                .or_default()
                 .push(result);
         }
 
-        let entities = groups
+        let items = ids
             .into_iter()
-            .map(|(_, v)| Self::entity_from_select(v))
-            .filter_map(|v| v)
+            .filter_map(|id| groups.remove(&id))
+            .filter_map(Self::entity_from_select)
             .collect();
 
-        Ok(entities)
+        let mut query = Query::select();
+        query
+            .from(SubdivisionsIden::Table)
+            .expr(Expr::col(Asterisk).count())
+            .cond_where(cond);
+
+        let total = fetch_count(&self.txn, &query).await?;
+        Ok(Paginated { items, total })
     }
 
     async fn list_by_tags(
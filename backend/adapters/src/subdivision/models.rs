@@ -8,16 +8,16 @@ pub struct Subdivisions {
     pub id: i32,
     pub name: String,
     pub university_id: i32,
+    pub parent_id: Option<i32>,
 }
 
 #[derive(FromRow)]
 pub struct JoinRow {
     #[sqlx(flatten)]
     pub subdivision: Subdivisions,
-    #[sqlx(flatten)]
-    pub tag: SubdivisionTags,
-    #[sqlx(flatten)]
-    pub member: SubdivisionMembers,
+    pub tag_name: Option<String>,
+    pub person_id: Option<i32>,
+    pub role: Option<String>,
 }
 
 #[derive(Clone, Debug, FromRow)]
@@ -45,6 +45,7 @@ impl Subdivisions {
             id: Id::new(self.id),
             name: self.name,
             university_id: Id::new(self.university_id),
+            parent_id: self.parent_id.map(Id::new),
             tags: tags.into_iter().map(|v| Id::new(v.tag_name)).collect(),
             members: members.into_iter().map(Into::into).collect(),
         }
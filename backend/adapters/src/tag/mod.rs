@@ -5,7 +5,7 @@ use sea_query::{Asterisk, Expr, Query};
 use std::sync::Arc;
 use tokio::sync::Mutex;
 
-use crate::{fetch_one, fetch_optional, PgTransaction};
+use crate::{execute, fetch_one, fetch_optional, PgTransaction};
 
 use self::model::{Tags, TagsIden};
 
@@ -29,7 +29,8 @@ impl PgTagRepo {
         let mut query = Query::update();
         let query = query
             .table(TagsIden::Table)
-            .values([(TagsIden::Name, entity.name.value.into())])
+            .values([(TagsIden::Name, entity.name.value.clone().into())])
+            .and_where(Expr::col(TagsIden::Name).eq(entity.name.value))
             .returning_all();
 
         fetch_one(&self.txn, query).await
@@ -49,11 +50,11 @@ impl tag::Repo for PgTagRepo {
     }
 
     async fn delete(&mut self, entity: &Entity) -> Result<(), anyhow::Error> {
-        fetch_one::<()>(
+        execute(
             &self.txn,
             Query::delete()
                 .from_table(TagsIden::Table)
-                .and_where(Expr::col(TagsIden::Table).eq(entity.name.value.clone())),
+                .and_where(Expr::col(TagsIden::Name).eq(entity.name.value.clone())),
         )
         .await?;
 
@@ -66,7 +67,7 @@ impl tag::Repo for PgTagRepo {
             Query::select()
                 .from(TagsIden::Table)
                 .column(Asterisk)
-                .and_where(Expr::col(TagsIden::Table).eq(id.value)),
+                .and_where(Expr::col(TagsIden::Name).eq(id.value)),
         )
         .await?;
 
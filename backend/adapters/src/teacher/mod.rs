@@ -1,20 +1,25 @@
-mod model;
+pub(crate) mod model;
 
 use app::{
-    person, subdivision,
+    class, person,
+    schedule::TimeSlot,
+    subdivision,
     teacher::{self, Entity, EntityId},
 };
-use sea_query::{Asterisk, Expr, IntoCondition, Query};
+use sea_query::{Asterisk, Expr, IntoCondition, Order, Query};
 use std::{
     collections::{HashMap, HashSet},
     sync::Arc,
 };
 use tokio::sync::Mutex;
 
-use crate::{fetch_all, fetch_one, PgTransaction};
+use crate::{execute, fetch_all, fetch_one, schedule::weekday_number, PgEnum, PgTransaction};
+
+pub(crate) use self::model::PgTeacherKind;
 
 use self::model::{
-    ClassTeachers, ClassTeachersIden, JoinRow, PgTeacherKind, Teachers, TeachersIden,
+    ClassTeachers, ClassTeachersIden, JoinRow, TeacherAvailability, TeacherAvailabilityIden,
+    Teachers, TeachersIden,
 };
 
 pub struct PgTeacherRepo {
@@ -33,7 +38,7 @@ impl PgTeacherRepo {
             ])
             .values_panic([
                 entity.person_id.value.into(),
-                PgTeacherKind::from(entity.kind).to_string().into(),
+                PgTeacherKind::from(entity.kind).into_expr(),
                 entity.department_id.value.into(),
             ])
             .returning_all();
@@ -49,7 +54,7 @@ impl PgTeacherRepo {
                 (TeachersIden::PersonId, entity.person_id.value.into()),
                 (
                     TeachersIden::Kind,
-                    PgTeacherKind::from(entity.kind).to_string().into(),
+                    PgTeacherKind::from(entity.kind).into_expr(),
                 ),
                 (
                     TeachersIden::DepartmentId,
@@ -74,8 +79,12 @@ impl PgTeacherRepo {
         let mut query = Query::select();
         query
             .from(teacher_table)
-            .column(Asterisk)
-            .join(sea_query::JoinType::InnerJoin, class_table, on)
+            .column((teacher_table, Asterisk))
+            .columns([
+                (class_table, ClassTeachersIden::ClassId),
+                (class_table, ClassTeachersIden::StudyGroupId),
+            ])
+            .join(sea_query::JoinType::LeftJoin, class_table, on)
             .cond_where(cond);
 
         let results = fetch_all::<JoinRow>(&self.txn, &query).await?;
@@ -83,16 +92,26 @@ impl PgTeacherRepo {
     }
 
     fn entity_from_select(select: Vec<JoinRow>) -> Option<Entity> {
-        let (models, curriculums) = select
+        let (models, classes) = select
             .into_iter()
-            .map(|v| (v.teacher, v.class))
+            .map(|v| (v.teacher, (v.class_id, v.study_group_id)))
             .unzip::<_, _, Vec<_>, Vec<_>>();
 
         let Some(model) = models.into_iter().take(1).next() else {
             return None;
         };
 
-        Some(model.into_entity(curriculums))
+        let classes = classes
+            .into_iter()
+            .filter_map(|(class_id, study_group_id)| Some((class_id?, study_group_id?)))
+            .map(|(class_id, study_group_id)| ClassTeachers {
+                teacher_id: model.id,
+                study_group_id,
+                class_id,
+            })
+            .collect();
+
+        Some(model.into_entity(classes))
     }
 
     async fn delete_classes(&self, id: i32) -> Result<(), anyhow::Error> {
@@ -101,7 +120,7 @@ impl PgTeacherRepo {
             .from_table(ClassTeachersIden::Table)
             .and_where(Expr::col(ClassTeachersIden::TeacherId).eq(id));
 
-        fetch_one::<()>(&self.txn, &query).await
+        execute(&self.txn, &query).await
     }
 
     async fn insert_classes(
@@ -158,7 +177,7 @@ impl teacher::Repo for PgTeacherRepo {
 
         self.delete_classes(entity.id.value).await?;
 
-        fetch_one::<()>(&self.txn, &query).await?;
+        execute(&self.txn, &query).await?;
         Ok(())
     }
 
@@ -210,4 +229,85 @@ impl teacher::Repo for PgTeacherRepo {
 
         Ok(entities)
     }
+
+    async fn list_by_class(&self, class_id: class::EntityId) -> Result<Vec<Entity>, anyhow::Error> {
+        let mut teachers_ids = Query::select();
+        teachers_ids
+            .from(ClassTeachersIden::Table)
+            .column(ClassTeachersIden::TeacherId)
+            .and_where(Expr::col(ClassTeachersIden::ClassId).eq(class_id.value));
+
+        let select = self
+            .select(
+                Expr::col((TeachersIden::Table, TeachersIden::Id)).in_subquery(teachers_ids.take()),
+            )
+            .await?;
+
+        let mut groups = HashMap::<i32, Vec<JoinRow>>::new();
+        for join_row in select {
+            groups
+                .entry(join_row.teacher.id)
+                .or_default()
+                .push(join_row);
+        }
+
+        let entities = groups
+            .into_values()
+            .filter_map(Self::entity_from_select)
+            .collect();
+
+        Ok(entities)
+    }
+
+    async fn availability(&self, id: EntityId) -> Result<Vec<TimeSlot>, anyhow::Error> {
+        let models = fetch_all::<TeacherAvailability>(
+            &self.txn,
+            Query::select()
+                .from(TeacherAvailabilityIden::Table)
+                .column(Asterisk)
+                .and_where(Expr::col(TeacherAvailabilityIden::TeacherId).eq(id.value))
+                .order_by(TeacherAvailabilityIden::Weekday, Order::Asc)
+                .order_by(TeacherAvailabilityIden::StartsAt, Order::Asc),
+        )
+        .await?;
+
+        Ok(models.into_iter().map(Into::into).collect())
+    }
+
+    async fn set_availability(
+        &mut self,
+        id: EntityId,
+        windows: Vec<TimeSlot>,
+    ) -> Result<(), anyhow::Error> {
+        execute(
+            &self.txn,
+            Query::delete()
+                .from_table(TeacherAvailabilityIden::Table)
+                .and_where(Expr::col(TeacherAvailabilityIden::TeacherId).eq(id.value)),
+        )
+        .await?;
+
+        for window in windows {
+            execute(
+                &self.txn,
+                Query::insert()
+                    .into_table(TeacherAvailabilityIden::Table)
+                    .columns([
+                        TeacherAvailabilityIden::TeacherId,
+                        TeacherAvailabilityIden::Weekday,
+                        TeacherAvailabilityIden::StartsAt,
+                        TeacherAvailabilityIden::EndsAt,
+                    ])
+                    .values_panic([
+                        id.value.into(),
+                        weekday_number(window.weekday).into(),
+                        window.starts_at.into(),
+                        window.ends_at.into(),
+                    ]),
+            )
+            .await?;
+        }
+
+        Ok(())
+    }
 }
@@ -1,9 +1,14 @@
 use std::fmt::Display;
 
-use app::teacher::{self, TeacherKind};
+use app::{
+    schedule::TimeSlot,
+    teacher::{self, TeacherKind},
+};
 use sqlx::FromRow;
 use utils::entity::Id;
 
+use crate::{schedule::weekday_from_number, PgEnum};
+
 #[derive(Clone, Debug, FromRow)]
 #[sea_query::enum_def]
 pub struct Teachers {
@@ -21,12 +26,31 @@ pub struct ClassTeachers {
     pub class_id: i32,
 }
 
+#[derive(Clone, Debug, FromRow)]
+#[sea_query::enum_def]
+pub struct TeacherAvailability {
+    pub teacher_id: i32,
+    pub weekday: i32,
+    pub starts_at: time::Time,
+    pub ends_at: time::Time,
+}
+
+impl From<TeacherAvailability> for TimeSlot {
+    fn from(value: TeacherAvailability) -> Self {
+        TimeSlot {
+            weekday: weekday_from_number(value.weekday),
+            starts_at: value.starts_at,
+            ends_at: value.ends_at,
+        }
+    }
+}
+
 #[derive(Clone, Debug, FromRow)]
 pub struct JoinRow {
     #[sqlx(flatten)]
     pub teacher: Teachers,
-    #[sqlx(flatten)]
-    pub class: ClassTeachers,
+    pub class_id: Option<i32>,
+    pub study_group_id: Option<i32>,
 }
 
 impl Teachers {
@@ -97,3 +121,7 @@ impl Display for PgTeacherKind {
         )
     }
 }
+
+impl PgEnum for PgTeacherKind {
+    const TYPE_NAME: &'static str = "teacher_kind";
+}
@@ -0,0 +1,240 @@
+mod model;
+
+use app::timetable_draft::{self, Entity, EntityId};
+use sea_query::{Asterisk, Expr, Order, Query, SimpleExpr};
+use std::sync::Arc;
+use tokio::sync::Mutex;
+
+use crate::{
+    execute, fetch_all, fetch_one, fetch_optional,
+    schedule::{weekday_number, PgRecurrence},
+    PgEnum, PgTransaction,
+};
+
+use self::model::{
+    PgTimetableDraftStatus, PgUnplacedReason, TimetableDraftEntries, TimetableDraftEntriesIden,
+    TimetableDraftStudyGroups, TimetableDraftStudyGroupsIden, TimetableDraftUnplaced,
+    TimetableDraftUnplacedIden, TimetableDrafts, TimetableDraftsIden,
+};
+
+pub struct PgTimetableDraftRepo {
+    pub txn: Arc<Mutex<PgTransaction<'static>>>,
+}
+
+impl PgTimetableDraftRepo {
+    fn values(entity: &Entity) -> [(TimetableDraftsIden, SimpleExpr); 6] {
+        [
+            (TimetableDraftsIden::AuthorId, entity.author_id.value.into()),
+            (TimetableDraftsIden::Semester, entity.semester.into()),
+            (TimetableDraftsIden::ValidFrom, entity.valid_from.into()),
+            (TimetableDraftsIden::ValidUntil, entity.valid_until.into()),
+            (
+                TimetableDraftsIden::Status,
+                PgTimetableDraftStatus::from(entity.status).into_expr(),
+            ),
+            (TimetableDraftsIden::Score, entity.score.into()),
+        ]
+    }
+
+    async fn insert(&self, entity: &Entity) -> Result<TimetableDrafts, anyhow::Error> {
+        let (columns, values): (Vec<_>, Vec<_>) = Self::values(entity).into_iter().unzip();
+
+        let mut query = Query::insert();
+        let query = query
+            .into_table(TimetableDraftsIden::Table)
+            .columns(columns)
+            .values_panic(values)
+            .returning_all();
+
+        fetch_one(&self.txn, query).await
+    }
+
+    async fn update(&self, entity: &Entity) -> Result<TimetableDrafts, anyhow::Error> {
+        let mut query = Query::update();
+        let query = query
+            .table(TimetableDraftsIden::Table)
+            .values(Self::values(entity))
+            .and_where(Expr::col(TimetableDraftsIden::Id).eq(entity.id.value))
+            .returning_all();
+
+        fetch_one(&self.txn, query).await
+    }
+
+    async fn delete_children(&self, id: i32) -> Result<(), anyhow::Error> {
+        execute(
+            &self.txn,
+            Query::delete()
+                .from_table(TimetableDraftStudyGroupsIden::Table)
+                .and_where(Expr::col(TimetableDraftStudyGroupsIden::DraftId).eq(id)),
+        )
+        .await?;
+
+        execute(
+            &self.txn,
+            Query::delete()
+                .from_table(TimetableDraftEntriesIden::Table)
+                .and_where(Expr::col(TimetableDraftEntriesIden::DraftId).eq(id)),
+        )
+        .await?;
+
+        execute(
+            &self.txn,
+            Query::delete()
+                .from_table(TimetableDraftUnplacedIden::Table)
+                .and_where(Expr::col(TimetableDraftUnplacedIden::DraftId).eq(id)),
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    async fn insert_children(&self, id: i32, entity: Entity) -> Result<(), anyhow::Error> {
+        for study_group_id in entity.study_groups {
+            execute(
+                &self.txn,
+                Query::insert()
+                    .into_table(TimetableDraftStudyGroupsIden::Table)
+                    .columns([
+                        TimetableDraftStudyGroupsIden::DraftId,
+                        TimetableDraftStudyGroupsIden::StudyGroupId,
+                    ])
+                    .values_panic([id.into(), study_group_id.value.into()]),
+            )
+            .await?;
+        }
+
+        for entry in entity.entries {
+            execute(
+                &self.txn,
+                Query::insert()
+                    .into_table(TimetableDraftEntriesIden::Table)
+                    .columns([
+                        TimetableDraftEntriesIden::DraftId,
+                        TimetableDraftEntriesIden::ClassId,
+                        TimetableDraftEntriesIden::StudyGroupId,
+                        TimetableDraftEntriesIden::TeacherId,
+                        TimetableDraftEntriesIden::RoomId,
+                        TimetableDraftEntriesIden::Weekday,
+                        TimetableDraftEntriesIden::StartsAt,
+                        TimetableDraftEntriesIden::EndsAt,
+                        TimetableDraftEntriesIden::Recurrence,
+                        TimetableDraftEntriesIden::ValidFrom,
+                    ])
+                    .values_panic([
+                        id.into(),
+                        entry.class_id.value.into(),
+                        entry.study_group_id.value.into(),
+                        entry.teacher_id.value.into(),
+                        entry.room_id.value.into(),
+                        weekday_number(entry.slot.weekday).into(),
+                        entry.slot.starts_at.into(),
+                        entry.slot.ends_at.into(),
+                        PgRecurrence::from(entry.recurrence).into_expr(),
+                        entry.valid_from.into(),
+                    ]),
+            )
+            .await?;
+        }
+
+        for unplaced in entity.unplaced {
+            execute(
+                &self.txn,
+                Query::insert()
+                    .into_table(TimetableDraftUnplacedIden::Table)
+                    .columns([
+                        TimetableDraftUnplacedIden::DraftId,
+                        TimetableDraftUnplacedIden::ClassId,
+                        TimetableDraftUnplacedIden::StudyGroupId,
+                        TimetableDraftUnplacedIden::Reason,
+                    ])
+                    .values_panic([
+                        id.into(),
+                        unplaced.class_id.value.into(),
+                        unplaced.study_group_id.value.into(),
+                        PgUnplacedReason::from(unplaced.reason).into_expr(),
+                    ]),
+            )
+            .await?;
+        }
+
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl timetable_draft::Repo for PgTimetableDraftRepo {
+    async fn save(&mut self, entity: Entity) -> Result<Entity, anyhow::Error> {
+        let model = if self.find(entity.id).await?.is_some() {
+            self.update(&entity).await?
+        } else {
+            self.insert(&entity).await?
+        };
+
+        self.delete_children(model.id).await?;
+        self.insert_children(model.id, entity.clone()).await?;
+
+        Ok(Entity {
+            id: EntityId::new(model.id),
+            ..entity
+        })
+    }
+
+    async fn delete(&mut self, entity: &Entity) -> Result<(), anyhow::Error> {
+        self.delete_children(entity.id.value).await?;
+
+        execute(
+            &self.txn,
+            Query::delete()
+                .from_table(TimetableDraftsIden::Table)
+                .and_where(Expr::col(TimetableDraftsIden::Id).eq(entity.id.value)),
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    async fn find(&self, id: EntityId) -> Result<Option<Entity>, anyhow::Error> {
+        let Some(model) = fetch_optional::<TimetableDrafts>(
+            &self.txn,
+            Query::select()
+                .from(TimetableDraftsIden::Table)
+                .column(Asterisk)
+                .and_where(Expr::col(TimetableDraftsIden::Id).eq(id.value)),
+        )
+        .await?
+        else {
+            return Ok(None);
+        };
+
+        let study_groups = fetch_all::<TimetableDraftStudyGroups>(
+            &self.txn,
+            Query::select()
+                .from(TimetableDraftStudyGroupsIden::Table)
+                .column(Asterisk)
+                .and_where(Expr::col(TimetableDraftStudyGroupsIden::DraftId).eq(id.value)),
+        )
+        .await?;
+
+        let entries = fetch_all::<TimetableDraftEntries>(
+            &self.txn,
+            Query::select()
+                .from(TimetableDraftEntriesIden::Table)
+                .column(Asterisk)
+                .and_where(Expr::col(TimetableDraftEntriesIden::DraftId).eq(id.value))
+                .order_by(TimetableDraftEntriesIden::Id, Order::Asc),
+        )
+        .await?;
+
+        let unplaced = fetch_all::<TimetableDraftUnplaced>(
+            &self.txn,
+            Query::select()
+                .from(TimetableDraftUnplacedIden::Table)
+                .column(Asterisk)
+                .and_where(Expr::col(TimetableDraftUnplacedIden::DraftId).eq(id.value))
+                .order_by(TimetableDraftUnplacedIden::Id, Order::Asc),
+        )
+        .await?;
+
+        Ok(Some(model.into_entity(study_groups, entries, unplaced)))
+    }
+}
@@ -0,0 +1,209 @@
+use std::fmt::Display;
+
+use app::{
+    schedule::TimeSlot,
+    timetable_draft::{self, DraftEntry, DraftStatus, UnplacedClass, UnplacedReason},
+};
+use sqlx::FromRow;
+use utils::entity::Id;
+
+use crate::{
+    schedule::{weekday_from_number, PgRecurrence},
+    PgEnum,
+};
+
+#[derive(Clone, Debug, FromRow)]
+#[sea_query::enum_def]
+pub struct TimetableDrafts {
+    pub id: i32,
+    pub author_id: i32,
+    pub semester: i32,
+    pub valid_from: time::Date,
+    pub valid_until: time::Date,
+    pub status: PgTimetableDraftStatus,
+    pub score: Option<i32>,
+}
+
+#[derive(Clone, Debug, FromRow)]
+#[sea_query::enum_def]
+pub struct TimetableDraftStudyGroups {
+    pub draft_id: i32,
+    pub study_group_id: i32,
+}
+
+#[derive(Clone, Debug, FromRow)]
+#[sea_query::enum_def]
+pub struct TimetableDraftEntries {
+    pub id: i32,
+    pub draft_id: i32,
+    pub class_id: i32,
+    pub study_group_id: i32,
+    pub teacher_id: i32,
+    pub room_id: i32,
+    pub weekday: i32,
+    pub starts_at: time::Time,
+    pub ends_at: time::Time,
+    pub recurrence: PgRecurrence,
+    pub valid_from: time::Date,
+}
+
+#[derive(Clone, Debug, FromRow)]
+#[sea_query::enum_def]
+pub struct TimetableDraftUnplaced {
+    pub id: i32,
+    pub draft_id: i32,
+    pub class_id: i32,
+    pub study_group_id: i32,
+    pub reason: PgUnplacedReason,
+}
+
+impl TimetableDrafts {
+    pub fn into_entity(
+        self,
+        study_groups: Vec<TimetableDraftStudyGroups>,
+        entries: Vec<TimetableDraftEntries>,
+        unplaced: Vec<TimetableDraftUnplaced>,
+    ) -> timetable_draft::Entity {
+        timetable_draft::Entity {
+            id: Id::new(self.id),
+            author_id: Id::new(self.author_id),
+            study_groups: study_groups
+                .into_iter()
+                .map(|v| Id::new(v.study_group_id))
+                .collect(),
+            semester: self.semester,
+            valid_from: self.valid_from,
+            valid_until: self.valid_until,
+            status: self.status.into(),
+            score: self.score,
+            entries: entries.into_iter().map(Into::into).collect(),
+            unplaced: unplaced.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl From<TimetableDraftEntries> for DraftEntry {
+    fn from(value: TimetableDraftEntries) -> Self {
+        DraftEntry {
+            class_id: Id::new(value.class_id),
+            study_group_id: Id::new(value.study_group_id),
+            teacher_id: Id::new(value.teacher_id),
+            room_id: Id::new(value.room_id),
+            slot: TimeSlot {
+                weekday: weekday_from_number(value.weekday),
+                starts_at: value.starts_at,
+                ends_at: value.ends_at,
+            },
+            recurrence: value.recurrence.into(),
+            valid_from: value.valid_from,
+        }
+    }
+}
+
+impl From<TimetableDraftUnplaced> for UnplacedClass {
+    fn from(value: TimetableDraftUnplaced) -> Self {
+        UnplacedClass {
+            class_id: Id::new(value.class_id),
+            study_group_id: Id::new(value.study_group_id),
+            reason: value.reason.into(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, sqlx::Type)]
+#[sqlx(type_name = "timetable_draft_status")]
+#[sqlx(rename_all = "snake_case")]
+pub enum PgTimetableDraftStatus {
+    Pending,
+    Ready,
+    Failed,
+    Applied,
+}
+
+impl From<DraftStatus> for PgTimetableDraftStatus {
+    fn from(value: DraftStatus) -> Self {
+        match value {
+            DraftStatus::Pending => PgTimetableDraftStatus::Pending,
+            DraftStatus::Ready => PgTimetableDraftStatus::Ready,
+            DraftStatus::Failed => PgTimetableDraftStatus::Failed,
+            DraftStatus::Applied => PgTimetableDraftStatus::Applied,
+        }
+    }
+}
+
+impl From<PgTimetableDraftStatus> for DraftStatus {
+    fn from(value: PgTimetableDraftStatus) -> Self {
+        match value {
+            PgTimetableDraftStatus::Pending => DraftStatus::Pending,
+            PgTimetableDraftStatus::Ready => DraftStatus::Ready,
+            PgTimetableDraftStatus::Failed => DraftStatus::Failed,
+            PgTimetableDraftStatus::Applied => DraftStatus::Applied,
+        }
+    }
+}
+
+impl Display for PgTimetableDraftStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::Pending => "pending",
+                Self::Ready => "ready",
+                Self::Failed => "failed",
+                Self::Applied => "applied",
+            }
+        )
+    }
+}
+
+impl PgEnum for PgTimetableDraftStatus {
+    const TYPE_NAME: &'static str = "timetable_draft_status";
+}
+
+#[derive(Debug, Clone, sqlx::Type)]
+#[sqlx(type_name = "unplaced_reason")]
+#[sqlx(rename_all = "snake_case")]
+pub enum PgUnplacedReason {
+    Unassigned,
+    NoRoom,
+    NoSlot,
+}
+
+impl From<UnplacedReason> for PgUnplacedReason {
+    fn from(value: UnplacedReason) -> Self {
+        match value {
+            UnplacedReason::Unassigned => PgUnplacedReason::Unassigned,
+            UnplacedReason::NoRoom => PgUnplacedReason::NoRoom,
+            UnplacedReason::NoSlot => PgUnplacedReason::NoSlot,
+        }
+    }
+}
+
+impl From<PgUnplacedReason> for UnplacedReason {
+    fn from(value: PgUnplacedReason) -> Self {
+        match value {
+            PgUnplacedReason::Unassigned => UnplacedReason::Unassigned,
+            PgUnplacedReason::NoRoom => UnplacedReason::NoRoom,
+            PgUnplacedReason::NoSlot => UnplacedReason::NoSlot,
+        }
+    }
+}
+
+impl Display for PgUnplacedReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::Unassigned => "unassigned",
+                Self::NoRoom => "no_room",
+                Self::NoSlot => "no_slot",
+            }
+        )
+    }
+}
+
+impl PgEnum for PgUnplacedReason {
+    const TYPE_NAME: &'static str = "unplaced_reason";
+}
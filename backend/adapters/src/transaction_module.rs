@@ -4,20 +4,49 @@ use tokio::sync::Mutex;
 use utils::di::{Module, Provide};
 
 use crate::{
-    access_token::JwtAccessTokenEngine, attestation::PgAttestationRepo, class::PgClassRepo,
-    class_kind::PgClassKindRepo, config::ConfigModule, curriculum::PgCurriculumRepo,
-    curriculum_module::PgCurriculumModuleRepo, discipline::PgDisciplineRepo,
-    hasher::Argon2PasswordHasher, passport::PgPassportRepo, person::PgPersonRepo,
-    refresh_token::NanoIdRefreshTokenGenerator, student::PgStudentRepo,
-    study_group::PgStudyGroupRepo, subdivision::PgSubdivisionRepo, tag::PgTagRepo,
-    teacher::PgTeacherRepo, university::PgUniversityRepo, user::PgUserRepo,
-    user_session::PgUserSessionRepo, PgTransaction,
+    academic_year::PgAcademicYearRepo,
+    access_token::JwtAccessTokenEngine,
+    account_token::PgAccountTokenRepo,
+    account_token_engine::JwtAccountTokenEngine,
+    attestation::PgAttestationRepo,
+    attestation_session::PgAttestationSessionRepo,
+    calendar_feed::PgCalendarFeedRepo,
+    class::PgClassRepo,
+    class_kind::PgClassKindRepo,
+    config::ConfigModule,
+    curriculum::PgCurriculumRepo,
+    curriculum_module::PgCurriculumModuleRepo,
+    device_id::NanoIdDeviceIdGenerator,
+    discipline::PgDisciplineRepo,
+    grading_scale::PgGradingScaleRepo,
+    grant::PgGrantRepo,
+    hasher::Argon2PasswordHasher,
+    load_limit::PgLoadLimitRepo,
+    login_attempt::{LoginAttemptCounters, MemoryLoginAttemptStore},
+    mail::{FileMailSender, MailSettings, MailTransport, SmtpMailSender},
+    passport::PgPassportRepo,
+    person::PgPersonRepo,
+    refresh_token::{NanoIdRefreshTokenGenerator, Sha256RefreshTokenHasher},
+    room::PgRoomRepo,
+    schedule::PgScheduleRepo,
+    student::PgStudentRepo,
+    student_order::PgStudentOrderRepo,
+    study_group::PgStudyGroupRepo,
+    subdivision::PgSubdivisionRepo,
+    tag::PgTagRepo,
+    teacher::PgTeacherRepo,
+    timetable_draft::PgTimetableDraftRepo,
+    university::PgUniversityRepo,
+    user::PgUserRepo,
+    user_session::PgUserSessionRepo,
+    PgTransaction,
 };
 
 #[derive(Debug, Clone)]
 pub struct TransactionModule<C: ConfigModule> {
     pub config: C,
     pub(crate) txn: Arc<Mutex<PgTransaction<'static>>>,
+    pub(crate) login_attempts: LoginAttemptCounters,
 }
 
 impl<C: ConfigModule> TransactionModule<C> {
@@ -28,6 +57,14 @@ impl<C: ConfigModule> TransactionModule<C> {
 
         Ok(())
     }
+
+    pub async fn rollback(self) -> Result<(), anyhow::Error> {
+        let txn =
+            Arc::into_inner(self.txn).context("transaction has more than 1 strong reference")?;
+        Mutex::into_inner(txn).rollback().await?;
+
+        Ok(())
+    }
 }
 
 impl<C: ConfigModule> Module for TransactionModule<C> {}
@@ -82,6 +119,94 @@ impl<C: ConfigModule> Provide<app::discipline::BoxedRepo> for TransactionModule<
     }
 }
 
+impl<C: ConfigModule> Provide<app::grading_scale::BoxedRepo> for TransactionModule<C> {
+    fn provide(&self) -> app::grading_scale::BoxedRepo {
+        Box::new(PgGradingScaleRepo {
+            txn: Arc::clone(&self.txn),
+        })
+    }
+}
+
+impl<C: ConfigModule> Provide<app::load_limit::BoxedRepo> for TransactionModule<C> {
+    fn provide(&self) -> app::load_limit::BoxedRepo {
+        Box::new(PgLoadLimitRepo {
+            txn: Arc::clone(&self.txn),
+        })
+    }
+}
+
+impl<C: ConfigModule> Provide<app::academic_year::BoxedRepo> for TransactionModule<C> {
+    fn provide(&self) -> app::academic_year::BoxedRepo {
+        Box::new(PgAcademicYearRepo {
+            txn: Arc::clone(&self.txn),
+        })
+    }
+}
+
+impl<C: ConfigModule> Provide<app::student_order::BoxedRepo> for TransactionModule<C> {
+    fn provide(&self) -> app::student_order::BoxedRepo {
+        Box::new(PgStudentOrderRepo {
+            txn: Arc::clone(&self.txn),
+        })
+    }
+}
+
+impl<C: ConfigModule> Provide<app::account_token::BoxedRepo> for TransactionModule<C> {
+    fn provide(&self) -> app::account_token::BoxedRepo {
+        Box::new(PgAccountTokenRepo {
+            txn: Arc::clone(&self.txn),
+        })
+    }
+}
+
+impl<C: ConfigModule> Provide<app::room::BoxedRepo> for TransactionModule<C> {
+    fn provide(&self) -> app::room::BoxedRepo {
+        Box::new(PgRoomRepo {
+            txn: Arc::clone(&self.txn),
+        })
+    }
+}
+
+impl<C: ConfigModule> Provide<app::schedule::BoxedRepo> for TransactionModule<C> {
+    fn provide(&self) -> app::schedule::BoxedRepo {
+        Box::new(PgScheduleRepo {
+            txn: Arc::clone(&self.txn),
+        })
+    }
+}
+
+impl<C: ConfigModule> Provide<app::timetable_draft::BoxedRepo> for TransactionModule<C> {
+    fn provide(&self) -> app::timetable_draft::BoxedRepo {
+        Box::new(PgTimetableDraftRepo {
+            txn: Arc::clone(&self.txn),
+        })
+    }
+}
+
+impl<C: ConfigModule> Provide<app::attestation_session::BoxedRepo> for TransactionModule<C> {
+    fn provide(&self) -> app::attestation_session::BoxedRepo {
+        Box::new(PgAttestationSessionRepo {
+            txn: Arc::clone(&self.txn),
+        })
+    }
+}
+
+impl<C: ConfigModule> Provide<app::calendar_feed::BoxedRepo> for TransactionModule<C> {
+    fn provide(&self) -> app::calendar_feed::BoxedRepo {
+        Box::new(PgCalendarFeedRepo {
+            txn: Arc::clone(&self.txn),
+        })
+    }
+}
+
+impl<C: ConfigModule> Provide<app::grant::BoxedRepo> for TransactionModule<C> {
+    fn provide(&self) -> app::grant::BoxedRepo {
+        Box::new(PgGrantRepo {
+            txn: Arc::clone(&self.txn),
+        })
+    }
+}
+
 impl<C: ConfigModule> Provide<app::passport::BoxedRepo> for TransactionModule<C> {
     fn provide(&self) -> app::passport::BoxedRepo {
         Box::new(PgPassportRepo {
@@ -176,6 +301,14 @@ impl<C: ConfigModule> Provide<app::token::BoxedAccessTokenEngine> for Transactio
     }
 }
 
+impl<C: ConfigModule> Provide<app::token::BoxedAccountTokenEngine> for TransactionModule<C> {
+    fn provide(&self) -> app::token::BoxedAccountTokenEngine {
+        Box::new(JwtAccountTokenEngine {
+            keys: self.config.resolve(),
+        })
+    }
+}
+
 impl<C: ConfigModule> Provide<app::token::BoxedRefreshTokenGenerator> for TransactionModule<C> {
     fn provide(&self) -> app::token::BoxedRefreshTokenGenerator {
         Box::new(NanoIdRefreshTokenGenerator {
@@ -184,6 +317,12 @@ impl<C: ConfigModule> Provide<app::token::BoxedRefreshTokenGenerator> for Transa
     }
 }
 
+impl<C: ConfigModule> Provide<app::token::BoxedRefreshTokenHasher> for TransactionModule<C> {
+    fn provide(&self) -> app::token::BoxedRefreshTokenHasher {
+        Box::new(Sha256RefreshTokenHasher)
+    }
+}
+
 impl<C: ConfigModule> Provide<app::token::AccessTokenTTL> for TransactionModule<C> {
     fn provide(&self) -> app::token::AccessTokenTTL {
         self.config.resolve()
@@ -201,3 +340,65 @@ impl<C: ConfigModule> Provide<app::user_session::SessionsMaxNumber> for Transact
         self.config.resolve()
     }
 }
+
+impl<C: ConfigModule> Provide<app::login_attempt::BoxedStore> for TransactionModule<C> {
+    fn provide(&self) -> app::login_attempt::BoxedStore {
+        Box::new(MemoryLoginAttemptStore {
+            counters: Arc::clone(&self.login_attempts),
+        })
+    }
+}
+
+impl<C: ConfigModule> Provide<app::login_attempt::LoginThrottling> for TransactionModule<C> {
+    fn provide(&self) -> app::login_attempt::LoginThrottling {
+        self.config.resolve()
+    }
+}
+
+impl<C: ConfigModule> Provide<app::user_session::BoxedDeviceIdGenerator> for TransactionModule<C> {
+    fn provide(&self) -> app::user_session::BoxedDeviceIdGenerator {
+        Box::new(NanoIdDeviceIdGenerator)
+    }
+}
+
+impl<C: ConfigModule> Provide<app::curriculum::SemesterHoursBounds> for TransactionModule<C> {
+    fn provide(&self) -> app::curriculum::SemesterHoursBounds {
+        self.config.resolve()
+    }
+}
+
+impl<C: ConfigModule> Provide<app::account_token::EmailVerificationTTL> for TransactionModule<C> {
+    fn provide(&self) -> app::account_token::EmailVerificationTTL {
+        self.config.resolve()
+    }
+}
+
+impl<C: ConfigModule> Provide<app::account_token::PasswordResetTTL> for TransactionModule<C> {
+    fn provide(&self) -> app::account_token::PasswordResetTTL {
+        self.config.resolve()
+    }
+}
+
+impl<C: ConfigModule> Provide<app::mail::LinkBaseUrl> for TransactionModule<C> {
+    fn provide(&self) -> app::mail::LinkBaseUrl {
+        self.config.resolve()
+    }
+}
+
+impl<C: ConfigModule> Provide<app::mail::BoxedMailSender> for TransactionModule<C> {
+    fn provide(&self) -> app::mail::BoxedMailSender {
+        let settings: Arc<MailSettings> = self.config.resolve();
+        let from = Arc::clone(&settings.from);
+
+        match &settings.transport {
+            MailTransport::Smtp(smtp) => Box::new(SmtpMailSender {
+                from,
+                settings: smtp.clone(),
+            }),
+            MailTransport::Directory(dir) => Box::new(FileMailSender {
+                from,
+                dir: Arc::clone(dir),
+            }),
+        }
+    }
+}
@@ -5,9 +5,9 @@ use sea_query::{Asterisk, Expr, Query};
 use std::sync::Arc;
 use tokio::sync::Mutex;
 
-use crate::{fetch_all, fetch_one, fetch_optional, PgTransaction};
+use crate::{execute, fetch_all, fetch_one, fetch_optional, PgTransaction};
 
-use self::model::{Universities, UniversitiesIden};
+use self::model::{utc_offset_minutes, Universities, UniversitiesIden};
 
 pub struct PgUniversityRepo {
     pub txn: Arc<Mutex<PgTransaction<'static>>>,
@@ -18,8 +18,11 @@ impl PgUniversityRepo {
         let mut query = Query::insert();
         let query = query
             .into_table(UniversitiesIden::Table)
-            .columns([UniversitiesIden::Name])
-            .values_panic([entity.name.into()])
+            .columns([UniversitiesIden::Name, UniversitiesIden::UtcOffsetMinutes])
+            .values_panic([
+                entity.name.into(),
+                utc_offset_minutes(entity.utc_offset).into(),
+            ])
             .returning_all();
 
         fetch_one(&self.txn, query).await
@@ -29,7 +32,13 @@ impl PgUniversityRepo {
         let mut query = Query::update();
         let query = query
             .table(UniversitiesIden::Table)
-            .values([(UniversitiesIden::Name, entity.name.into())])
+            .values([
+                (UniversitiesIden::Name, entity.name.into()),
+                (
+                    UniversitiesIden::UtcOffsetMinutes,
+                    utc_offset_minutes(entity.utc_offset).into(),
+                ),
+            ])
             .and_where(Expr::col(UniversitiesIden::Id).eq(entity.id.value))
             .returning_all();
 
@@ -50,7 +59,7 @@ impl university::Repo for PgUniversityRepo {
     }
 
     async fn delete(&mut self, entity: &Entity) -> Result<(), anyhow::Error> {
-        fetch_one::<()>(
+        execute(
             &self.txn,
             Query::delete()
                 .from_table(UniversitiesIden::Table)
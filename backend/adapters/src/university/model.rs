@@ -1,5 +1,6 @@
 use app::university;
 use sqlx::FromRow;
+use time::UtcOffset;
 use utils::entity::Id;
 
 #[derive(Clone, Debug, FromRow)]
@@ -7,6 +8,7 @@ use utils::entity::Id;
 pub struct Universities {
     pub id: i32,
     pub name: String,
+    pub utc_offset_minutes: i32,
 }
 
 impl From<Universities> for university::Entity {
@@ -14,6 +16,16 @@ impl From<Universities> for university::Entity {
         university::Entity {
             id: Id::new(value.id),
             name: value.name,
+            utc_offset: utc_offset_from_minutes(value.utc_offset_minutes),
         }
     }
 }
+
+pub(crate) fn utc_offset_minutes(offset: UtcOffset) -> i32 {
+    offset.whole_minutes().into()
+}
+
+/// The column is checked to hold a valid offset
+fn utc_offset_from_minutes(minutes: i32) -> UtcOffset {
+    UtcOffset::from_whole_seconds(minutes * 60).unwrap_or(UtcOffset::UTC)
+}
@@ -1,14 +1,16 @@
 mod models;
 
+pub(crate) use models::PgRole;
+
 use app::user::{self, Entity, EntityId};
 use sea_query::{Asterisk, Expr, Query};
 use std::sync::Arc;
 use tokio::sync::Mutex;
 
 use crate::{
-    fetch_one, fetch_optional,
+    execute, fetch_one, fetch_optional,
     user::models::{Users, UsersIden},
-    PgTransaction,
+    PgEnum, PgTransaction,
 };
 
 pub struct PgUserRepo {
@@ -23,6 +25,8 @@ impl PgUserRepo {
             .values([
                 (UsersIden::Email, entity.email.into()),
                 (UsersIden::Password, entity.password.value.into()),
+                (UsersIden::Role, PgRole::from(entity.role).into_expr()),
+                (UsersIden::EmailVerified, entity.email_verified.into()),
             ])
             .and_where(Expr::col(UsersIden::Id).eq(entity.id.value))
             .returning_all();
@@ -35,8 +39,18 @@ impl PgUserRepo {
 
         let query = query
             .into_table(UsersIden::Table)
-            .columns([UsersIden::Email, UsersIden::Password])
-            .values_panic([entity.email.into(), entity.password.value.into()])
+            .columns([
+                UsersIden::Email,
+                UsersIden::Password,
+                UsersIden::Role,
+                UsersIden::EmailVerified,
+            ])
+            .values_panic([
+                entity.email.into(),
+                entity.password.value.into(),
+                PgRole::from(entity.role).into_expr(),
+                entity.email_verified.into(),
+            ])
             .returning_all();
 
         fetch_one(&self.txn, query).await
@@ -56,7 +70,7 @@ impl user::Repo for PgUserRepo {
     }
 
     async fn delete(&mut self, entity: &Entity) -> Result<(), anyhow::Error> {
-        fetch_one::<()>(
+        execute(
             &self.txn,
             Query::delete()
                 .from_table(UsersIden::Table)
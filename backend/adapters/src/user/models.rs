@@ -1,13 +1,19 @@
-use app::user;
+use std::fmt::Display;
+
+use app::user::{self, Role};
 use sqlx::FromRow;
 use utils::entity::Id;
 
+use crate::PgEnum;
+
 #[derive(Clone, Debug, FromRow)]
 #[sea_query::enum_def]
 pub struct Users {
     pub id: i32,
     pub email: String,
     pub password: String,
+    pub role: PgRole,
+    pub email_verified: bool,
 }
 
 impl From<Users> for user::Entity {
@@ -18,6 +24,63 @@ impl From<Users> for user::Entity {
             password: user::HashedPassword {
                 value: value.password.into(),
             },
+            role: value.role.into(),
+            email_verified: value.email_verified,
         }
     }
 }
+
+#[derive(Debug, Clone, sqlx::Type)]
+#[sqlx(type_name = "user_role")]
+#[sqlx(rename_all = "snake_case")]
+pub enum PgRole {
+    Admin,
+    UniversityStaff,
+    DepartmentHead,
+    Teacher,
+    Student,
+}
+
+impl From<Role> for PgRole {
+    fn from(value: Role) -> Self {
+        match value {
+            Role::Admin => PgRole::Admin,
+            Role::UniversityStaff => PgRole::UniversityStaff,
+            Role::DepartmentHead => PgRole::DepartmentHead,
+            Role::Teacher => PgRole::Teacher,
+            Role::Student => PgRole::Student,
+        }
+    }
+}
+
+impl From<PgRole> for Role {
+    fn from(value: PgRole) -> Self {
+        match value {
+            PgRole::Admin => Role::Admin,
+            PgRole::UniversityStaff => Role::UniversityStaff,
+            PgRole::DepartmentHead => Role::DepartmentHead,
+            PgRole::Teacher => Role::Teacher,
+            PgRole::Student => Role::Student,
+        }
+    }
+}
+
+impl Display for PgRole {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::Admin => "admin",
+                Self::UniversityStaff => "university_staff",
+                Self::DepartmentHead => "department_head",
+                Self::Teacher => "teacher",
+                Self::Student => "student",
+            }
+        )
+    }
+}
+
+impl PgEnum for PgRole {
+    const TYPE_NAME: &'static str = "user_role";
+}
@@ -1,13 +1,15 @@
 mod model;
 use app::{
     user,
-    user_session::{self, Entity, EntityId},
+    user_session::{self, Entity, EntityId, RotatedToken, SecondsFromUnixEpoch},
 };
 use sea_query::{Asterisk, Expr, Func, Query};
 use tokio::sync::Mutex;
 
-use self::model::{UserSessions, UserSessionsIden};
-use crate::{fetch_all, fetch_one, fetch_optional, PgTransaction};
+use self::model::{
+    UserSessionRotatedTokens, UserSessionRotatedTokensIden, UserSessions, UserSessionsIden,
+};
+use crate::{execute, fetch_all, fetch_one, fetch_optional, PgTransaction};
 
 pub struct PgUserSessionRepo {
     pub txn: std::sync::Arc<Mutex<PgTransaction<'static>>>,
@@ -20,15 +22,23 @@ impl PgUserSessionRepo {
             .into_table(UserSessionsIden::Table)
             .columns([
                 UserSessionsIden::UserId,
-                UserSessionsIden::Metadata,
-                UserSessionsIden::RefreshToken,
+                UserSessionsIden::DeviceId,
+                UserSessionsIden::RefreshTokenHash,
+                UserSessionsIden::FamilyId,
                 UserSessionsIden::ExpiresAt,
+                UserSessionsIden::UserAgent,
+                UserSessionsIden::Ip,
+                UserSessionsIden::LastSeenAt,
             ])
             .values_panic([
                 entity.id.value.user_id.value.into(),
-                entity.id.value.metadata.into(),
-                entity.refresh_token.into(),
+                entity.id.value.device_id.into(),
+                entity.refresh_token_hash.into(),
+                entity.family_id.into(),
                 entity.expires_at.seconds.val.into(),
+                entity.client.user_agent.into(),
+                entity.client.ip.into(),
+                entity.last_seen_at.seconds.val.into(),
             ])
             .returning_all();
 
@@ -38,7 +48,7 @@ impl PgUserSessionRepo {
     async fn update(&self, entity: Entity) -> Result<UserSessions, anyhow::Error> {
         let cond = Expr::col(UserSessionsIden::UserId)
             .eq(entity.id.value.user_id.value)
-            .and(Expr::col(UserSessionsIden::Metadata).eq(entity.id.value.metadata.clone()));
+            .and(Expr::col(UserSessionsIden::DeviceId).eq(entity.id.value.device_id.clone()));
 
         let mut query = Query::update();
         let query = query
@@ -48,12 +58,22 @@ impl PgUserSessionRepo {
                     UserSessionsIden::UserId,
                     entity.id.value.user_id.value.into(),
                 ),
-                (UserSessionsIden::Metadata, entity.id.value.metadata.into()),
-                (UserSessionsIden::RefreshToken, entity.refresh_token.into()),
+                (UserSessionsIden::DeviceId, entity.id.value.device_id.into()),
+                (
+                    UserSessionsIden::RefreshTokenHash,
+                    entity.refresh_token_hash.into(),
+                ),
+                (UserSessionsIden::FamilyId, entity.family_id.into()),
                 (
                     UserSessionsIden::ExpiresAt,
                     entity.expires_at.seconds.val.into(),
                 ),
+                (UserSessionsIden::UserAgent, entity.client.user_agent.into()),
+                (UserSessionsIden::Ip, entity.client.ip.into()),
+                (
+                    UserSessionsIden::LastSeenAt,
+                    entity.last_seen_at.seconds.val.into(),
+                ),
             ])
             .and_where(cond)
             .returning_all();
@@ -75,7 +95,7 @@ impl user_session::Repo for PgUserSessionRepo {
     }
 
     async fn delete(&mut self, entity: &Entity) -> Result<(), anyhow::Error> {
-        fetch_one::<()>(
+        execute(
             &self.txn,
             Query::delete()
                 .from_table(UserSessionsIden::Table)
@@ -83,10 +103,10 @@ impl user_session::Repo for PgUserSessionRepo {
                     Expr::col(UserSessionsIden::UserId)
                         .eq(entity.id.value.user_id.value)
                         .and(
-                            Expr::col(UserSessionsIden::Metadata).eq(entity
+                            Expr::col(UserSessionsIden::DeviceId).eq(entity
                                 .id
                                 .value
-                                .metadata
+                                .device_id
                                 .clone()),
                         ),
                 ),
@@ -99,7 +119,7 @@ impl user_session::Repo for PgUserSessionRepo {
     async fn find(&self, id: EntityId) -> Result<Option<Entity>, anyhow::Error> {
         let cond = Expr::col(UserSessionsIden::UserId)
             .eq(id.value.user_id.value)
-            .and(Expr::col(UserSessionsIden::Metadata).eq(id.value.metadata.clone()));
+            .and(Expr::col(UserSessionsIden::DeviceId).eq(id.value.device_id.clone()));
 
         let res = fetch_optional::<UserSessions>(
             &self.txn,
@@ -130,16 +150,127 @@ impl user_session::Repo for PgUserSessionRepo {
         Ok(res)
     }
 
+    async fn save_rotated_token(&mut self, token: RotatedToken) -> Result<(), anyhow::Error> {
+        execute(
+            &self.txn,
+            Query::insert()
+                .into_table(UserSessionRotatedTokensIden::Table)
+                .columns([
+                    UserSessionRotatedTokensIden::TokenHash,
+                    UserSessionRotatedTokensIden::UserId,
+                    UserSessionRotatedTokensIden::DeviceId,
+                    UserSessionRotatedTokensIden::FamilyId,
+                ])
+                .values_panic([
+                    token.token_hash.into(),
+                    token.session_id.value.user_id.value.into(),
+                    token.session_id.value.device_id.into(),
+                    token.family_id.into(),
+                ]),
+        )
+        .await
+    }
+
+    async fn find_rotated_token(
+        &self,
+        token_hash: String,
+    ) -> Result<Option<RotatedToken>, anyhow::Error> {
+        let res = fetch_optional::<UserSessionRotatedTokens>(
+            &self.txn,
+            Query::select()
+                .from(UserSessionRotatedTokensIden::Table)
+                .column(Asterisk)
+                .and_where(Expr::col(UserSessionRotatedTokensIden::TokenHash).eq(token_hash)),
+        )
+        .await?
+        .map(Into::into);
+
+        Ok(res)
+    }
+
     async fn count_not_expired(&self, user_id: user::EntityId) -> Result<i64, anyhow::Error> {
+        let now = SecondsFromUnixEpoch::now()?;
+
         let (res,): (i64,) = fetch_one(
             &self.txn,
             Query::select()
                 .from(UserSessionsIden::Table)
                 .expr(Func::count(Expr::col(Asterisk)))
-                .and_where(Expr::col(UserSessionsIden::UserId).eq(user_id.value)),
+                .and_where(Expr::col(UserSessionsIden::UserId).eq(user_id.value))
+                .and_where(Expr::col(UserSessionsIden::ExpiresAt).gte(now.seconds.val)),
         )
         .await?;
 
         Ok(res)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use app::{
+        hasher::HashedPassword,
+        user::{self, Repo as _, Role},
+        user_session::{ClientInfo, Repo as _, SecondsFromUnixEpoch},
+    };
+    use sqlx::PgPool;
+    use tokio::sync::Mutex;
+    use utils::entity::Id;
+
+    use super::*;
+    use crate::{migrations, user::PgUserRepo};
+
+    fn session(user_id: user::EntityId, device_id: &str, expires_at: u64) -> Entity {
+        Entity {
+            id: Id::new(user_session::Id {
+                user_id,
+                device_id: device_id.to_owned(),
+            }),
+            family_id: device_id.to_owned(),
+            refresh_token_hash: device_id.to_owned(),
+            expires_at: expires_at.into(),
+            client: ClientInfo {
+                user_agent: None,
+                ip: None,
+            },
+            last_seen_at: expires_at.into(),
+        }
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a postgres database in DATABASE_URL"]
+    async fn expired_sessions_are_not_counted() {
+        let pool = PgPool::connect(&std::env::var("DATABASE_URL").unwrap())
+            .await
+            .unwrap();
+        migrations::run(&pool).await.unwrap();
+        // dropped without commit, nothing is left in the database
+        let txn = Arc::new(Mutex::new(pool.begin().await.unwrap()));
+
+        let mut users = PgUserRepo { txn: txn.clone() };
+        let user = users
+            .save(user::Entity {
+                id: Default::default(),
+                email: "session-count@uis.ru".to_owned(),
+                password: HashedPassword {
+                    value: "hash".to_owned(),
+                },
+                role: Role::Student,
+                email_verified: true,
+            })
+            .await
+            .unwrap();
+
+        let now = SecondsFromUnixEpoch::now().unwrap().seconds.val;
+        let mut repo = PgUserSessionRepo { txn };
+        for (device_id, expires_at) in [("expired", now - 60), ("active", now + 60)] {
+            repo.save(session(user.id, device_id, expires_at))
+                .await
+                .unwrap();
+        }
+
+        assert_eq!(repo.count_not_expired(user.id).await.unwrap(), 1);
+        assert_eq!(repo.list_by_user_id(user.id).await.unwrap().len(), 2);
+    }
+}
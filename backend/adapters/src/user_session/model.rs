@@ -6,24 +6,60 @@ use utils::entity::Id;
 #[sea_query::enum_def]
 pub struct UserSessions {
     pub user_id: i32,
-    pub metadata: String,
-    pub refresh_token: String,
+    pub device_id: String,
+    pub refresh_token_hash: String,
+    pub family_id: String,
     pub expires_at: i64,
+    pub user_agent: Option<String>,
+    pub ip: Option<String>,
+    pub last_seen_at: i64,
 }
 
 impl From<UserSessions> for user_session::Entity {
     fn from(value: UserSessions) -> Self {
         user_session::Entity {
             id: Id::new(user_session::Id {
-                metadata: value.metadata,
+                device_id: value.device_id,
                 user_id: Id::new(value.user_id),
             }),
-            refresh_token: value.refresh_token,
+            refresh_token_hash: value.refresh_token_hash,
+            family_id: value.family_id,
             expires_at: user_session::SecondsFromUnixEpoch {
                 seconds: user_session::Seconds {
                     val: value.expires_at.try_into().unwrap(),
                 },
             },
+            client: user_session::ClientInfo {
+                user_agent: value.user_agent,
+                ip: value.ip,
+            },
+            last_seen_at: user_session::SecondsFromUnixEpoch {
+                seconds: user_session::Seconds {
+                    val: value.last_seen_at.try_into().unwrap(),
+                },
+            },
+        }
+    }
+}
+
+#[derive(Clone, Debug, FromRow)]
+#[sea_query::enum_def]
+pub struct UserSessionRotatedTokens {
+    pub token_hash: String,
+    pub user_id: i32,
+    pub device_id: String,
+    pub family_id: String,
+}
+
+impl From<UserSessionRotatedTokens> for user_session::RotatedToken {
+    fn from(value: UserSessionRotatedTokens) -> Self {
+        user_session::RotatedToken {
+            token_hash: value.token_hash,
+            session_id: Id::new(user_session::Id {
+                device_id: value.device_id,
+                user_id: Id::new(value.user_id),
+            }),
+            family_id: value.family_id,
         }
     }
 }
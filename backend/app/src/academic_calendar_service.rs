@@ -0,0 +1,315 @@
+use std::collections::HashMap;
+
+use time::Date;
+use utils::{
+    di::{Module, Provide},
+    outcome::Outcome,
+};
+
+use crate::{
+    academic_year::{self, SemesterRange},
+    access_policy::{AccessException, AccessPolicy, Actor},
+    curriculum_module, discipline,
+    grant::Scope,
+    study_group, subdivision, university, AdaptersModule, AppModule,
+};
+
+pub struct AcademicCalendarService {
+    repo: academic_year::BoxedRepo,
+    university_repo: university::BoxedRepo,
+    study_group_repo: study_group::BoxedRepo,
+    subdivision_repo: subdivision::BoxedRepo,
+    curriculum_module_repo: curriculum_module::BoxedRepo,
+    discipline_repo: discipline::BoxedRepo,
+    policy: AccessPolicy,
+}
+
+impl<A: AdaptersModule> Provide<AcademicCalendarService> for AppModule<A> {
+    fn provide(&self) -> AcademicCalendarService {
+        AcademicCalendarService {
+            repo: self.adapters.resolve(),
+            university_repo: self.adapters.resolve(),
+            study_group_repo: self.adapters.resolve(),
+            subdivision_repo: self.adapters.resolve(),
+            curriculum_module_repo: self.adapters.resolve(),
+            discipline_repo: self.adapters.resolve(),
+            policy: self.resolve(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, thiserror::Error)]
+pub enum AcademicCalendarException {
+    #[error(transparent)]
+    AccessException(#[from] AccessException),
+    #[error("academic year not found")]
+    NotFound,
+    #[error("university not found")]
+    UniversityNotFound,
+    #[error("study group not found")]
+    StudyGroupNotFound,
+    #[error("semesters should be date ranges that follow each other without overlapping")]
+    InvalidSemesters,
+    #[error("academic year already exist")]
+    AlreadyExist,
+    #[error("academic year overlaps another academic year")]
+    Overlaps,
+    #[error("study group has no enrollment year")]
+    NoEnrollmentYear,
+}
+
+pub struct AcademicYearInfo {
+    pub university_id: university::EntityId,
+    pub start_year: i32,
+    pub semesters: Vec<SemesterRange>,
+}
+
+/// Semester a study group is in on a date
+pub struct CurrentSemester {
+    pub academic_year: academic_year::Entity,
+    /// Number of the semester within the academic year, from one
+    pub semester_of_year: i32,
+    /// Number of the semester since enrollment, the one curriculum modules are planned for
+    pub semester: i32,
+    pub range: SemesterRange,
+}
+
+/// Curriculum modules a study group studies on a date
+pub struct StudyingNow {
+    pub study_group: study_group::Entity,
+    pub date: Date,
+    /// `None` between semesters, before enrollment and when the university has no calendar for the date
+    pub current: Option<CurrentSemester>,
+    pub modules: Vec<(curriculum_module::Entity, discipline::Entity)>,
+}
+
+impl AcademicCalendarService {
+    pub async fn years(
+        &self,
+        university_id: university::EntityId,
+    ) -> Outcome<Vec<academic_year::Entity>, AcademicCalendarException> {
+        self.ensure_university(university_id).await?;
+
+        let mut years = self.repo.list_by_university(university_id).await?;
+        years.sort_by_key(|v| v.start_year);
+
+        Outcome::Ok(years)
+    }
+
+    pub async fn create(
+        &mut self,
+        actor: &Actor,
+        info: AcademicYearInfo,
+    ) -> Outcome<academic_year::Entity, AcademicCalendarException> {
+        self.ensure_university(info.university_id).await?;
+        self.policy
+            .authorize(actor, Scope::University(info.university_id))
+            .await?;
+
+        let year = academic_year::Entity {
+            id: Default::default(),
+            university_id: info.university_id,
+            start_year: info.start_year,
+            semesters: info.semesters,
+        };
+
+        self.validate(&year).await?;
+
+        let year = self.repo.save(year).await?;
+        Outcome::Ok(year)
+    }
+
+    pub async fn update(
+        &mut self,
+        actor: &Actor,
+        id: academic_year::EntityId,
+        info: AcademicYearInfo,
+    ) -> Outcome<academic_year::Entity, AcademicCalendarException> {
+        let year = self.get(id).await?;
+
+        self.policy
+            .authorize(actor, Scope::University(year.university_id))
+            .await?;
+
+        if year.university_id != info.university_id {
+            self.ensure_university(info.university_id).await?;
+            self.policy
+                .authorize(actor, Scope::University(info.university_id))
+                .await?;
+        }
+
+        let year = academic_year::Entity {
+            id,
+            university_id: info.university_id,
+            start_year: info.start_year,
+            semesters: info.semesters,
+        };
+
+        self.validate(&year).await?;
+
+        let year = self.repo.save(year).await?;
+        Outcome::Ok(year)
+    }
+
+    pub async fn delete(
+        &mut self,
+        actor: &Actor,
+        id: academic_year::EntityId,
+    ) -> Outcome<academic_year::Entity, AcademicCalendarException> {
+        let year = self.get(id).await?;
+
+        self.policy
+            .authorize(actor, Scope::University(year.university_id))
+            .await?;
+
+        self.repo.delete(&year).await?;
+        Outcome::Ok(year)
+    }
+
+    pub async fn get(
+        &self,
+        id: academic_year::EntityId,
+    ) -> Outcome<academic_year::Entity, AcademicCalendarException> {
+        let Some(year) = self.repo.find(id).await? else {
+            return Outcome::Ex(AcademicCalendarException::NotFound);
+        };
+
+        Outcome::Ok(year)
+    }
+
+    /// Semester of the study group on the date counted from its enrollment year,
+    /// years missing from the calendar are assumed to have as many semesters as the current one
+    pub async fn current_semester(
+        &self,
+        study_group: &study_group::Entity,
+        date: Date,
+    ) -> Outcome<Option<CurrentSemester>, AcademicCalendarException> {
+        let Some(enrollment_year) = study_group.enrollment_year else {
+            return Outcome::Ex(AcademicCalendarException::NoEnrollmentYear);
+        };
+
+        let Some(department) = self
+            .subdivision_repo
+            .find(study_group.department_id)
+            .await?
+        else {
+            return Outcome::Ok(None);
+        };
+
+        let years = self
+            .repo
+            .list_by_university(department.university_id)
+            .await?;
+
+        let Some((academic_year, semester_of_year)) = years
+            .iter()
+            .find_map(|v| v.semester_on(date).map(|semester| (v, semester)))
+        else {
+            return Outcome::Ok(None);
+        };
+
+        if academic_year.start_year < enrollment_year {
+            return Outcome::Ok(None);
+        }
+
+        let counts = years
+            .iter()
+            .map(|v| (v.start_year, v.semesters.len() as i32))
+            .collect::<HashMap<_, _>>();
+        let passed = (enrollment_year..academic_year.start_year)
+            .map(|v| {
+                counts
+                    .get(&v)
+                    .copied()
+                    .unwrap_or(academic_year.semesters.len() as i32)
+            })
+            .sum::<i32>();
+
+        Outcome::Ok(Some(CurrentSemester {
+            academic_year: academic_year.clone(),
+            semester_of_year,
+            semester: passed + semester_of_year,
+            range: academic_year.semesters[semester_of_year as usize - 1],
+        }))
+    }
+
+    /// Modules of the study group curriculums planned for its semester on the date
+    pub async fn studying_now(
+        &self,
+        study_group_id: study_group::EntityId,
+        date: Date,
+    ) -> Outcome<StudyingNow, AcademicCalendarException> {
+        let Some(study_group) = self.study_group_repo.find(study_group_id).await? else {
+            return Outcome::Ex(AcademicCalendarException::StudyGroupNotFound);
+        };
+
+        let current = self.current_semester(&study_group, date).await?;
+
+        let mut modules = vec![];
+        if let Some(current) = &current {
+            for curriculum_id in study_group.curriculums.iter() {
+                for curriculum_module in self
+                    .curriculum_module_repo
+                    .list_by_curriculum_id(*curriculum_id)
+                    .await?
+                {
+                    if curriculum_module.semester != current.semester {
+                        continue;
+                    }
+
+                    if let Some(discipline) = self
+                        .discipline_repo
+                        .find(curriculum_module.discipline_id)
+                        .await?
+                    {
+                        modules.push((curriculum_module, discipline));
+                    }
+                }
+            }
+        }
+        modules.sort_by_key(|(v, _)| v.id.value);
+
+        Outcome::Ok(StudyingNow {
+            study_group,
+            date,
+            current,
+            modules,
+        })
+    }
+
+    async fn validate(
+        &self,
+        year: &academic_year::Entity,
+    ) -> Outcome<(), AcademicCalendarException> {
+        if !year.is_valid() {
+            return Outcome::Ex(AcademicCalendarException::InvalidSemesters);
+        }
+
+        for other in self.repo.list_by_university(year.university_id).await? {
+            if other.id == year.id {
+                continue;
+            }
+
+            if other.start_year == year.start_year {
+                return Outcome::Ex(AcademicCalendarException::AlreadyExist);
+            }
+
+            if other.overlaps(year) {
+                return Outcome::Ex(AcademicCalendarException::Overlaps);
+            }
+        }
+
+        Outcome::Ok(())
+    }
+
+    async fn ensure_university(
+        &self,
+        university_id: university::EntityId,
+    ) -> Outcome<(), AcademicCalendarException> {
+        if self.university_repo.find(university_id).await?.is_none() {
+            return Outcome::Ex(AcademicCalendarException::UniversityNotFound);
+        }
+
+        Outcome::Ok(())
+    }
+}
@@ -0,0 +1,117 @@
+mod repo;
+
+use time::Date;
+use utils::entity::entity;
+
+use crate::university;
+
+pub use repo::Repo;
+pub type BoxedRepo = Box<dyn Repo + Send + Sync>;
+
+/// Academic year of a university named by the calendar year it starts in, 2023 for 2023/2024
+#[entity]
+#[derive(Debug, Clone)]
+pub struct Entity {
+    #[id]
+    pub id: i32,
+    pub university_id: university::EntityId,
+    pub start_year: i32,
+    /// Semesters in the order they are studied
+    pub semesters: Vec<SemesterRange>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SemesterRange {
+    pub starts_on: Date,
+    pub ends_on: Date,
+}
+
+impl Entity {
+    /// Semesters should follow each other without overlapping
+    pub fn is_valid(&self) -> bool {
+        !self.semesters.is_empty()
+            && self.semesters.iter().all(|v| v.starts_on <= v.ends_on)
+            && self
+                .semesters
+                .windows(2)
+                .all(|v| v[0].ends_on < v[1].starts_on)
+    }
+
+    /// Number of the semester of the year the date falls into, from one
+    pub fn semester_on(&self, date: Date) -> Option<i32> {
+        self.semesters
+            .iter()
+            .position(|v| v.starts_on <= date && date <= v.ends_on)
+            .map(|v| v as i32 + 1)
+    }
+
+    pub fn overlaps(&self, other: &Entity) -> bool {
+        self.semesters.iter().any(|a| {
+            other
+                .semesters
+                .iter()
+                .any(|b| a.starts_on <= b.ends_on && b.starts_on <= a.ends_on)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use time::Month;
+
+    use super::*;
+
+    fn date(year: i32, month: Month, day: u8) -> Date {
+        Date::from_calendar_date(year, month, day).unwrap()
+    }
+
+    fn year(start_year: i32) -> Entity {
+        Entity {
+            id: Default::default(),
+            university_id: Default::default(),
+            start_year,
+            semesters: vec![
+                SemesterRange {
+                    starts_on: date(start_year, Month::September, 1),
+                    ends_on: date(start_year + 1, Month::January, 31),
+                },
+                SemesterRange {
+                    starts_on: date(start_year + 1, Month::February, 9),
+                    ends_on: date(start_year + 1, Month::June, 30),
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn dates_between_semesters_belong_to_none() {
+        let year = year(2023);
+
+        assert_eq!(year.semester_on(date(2023, Month::September, 1)), Some(1));
+        assert_eq!(year.semester_on(date(2024, Month::January, 31)), Some(1));
+        assert_eq!(year.semester_on(date(2024, Month::February, 1)), None);
+        assert_eq!(year.semester_on(date(2024, Month::February, 9)), Some(2));
+        assert_eq!(year.semester_on(date(2024, Month::July, 1)), None);
+    }
+
+    #[test]
+    fn semesters_must_follow_each_other() {
+        let mut year = year(2023);
+        assert!(year.is_valid());
+
+        year.semesters.swap(0, 1);
+        assert!(!year.is_valid());
+
+        year.semesters.clear();
+        assert!(!year.is_valid());
+    }
+
+    #[test]
+    fn years_overlap_when_any_semesters_do() {
+        let mut next = year(2024);
+        assert!(!year(2023).overlaps(&next));
+
+        next.semesters[0].starts_on = date(2024, Month::June, 1);
+        assert!(year(2023).overlaps(&next));
+    }
+}
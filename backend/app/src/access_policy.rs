@@ -0,0 +1,284 @@
+use std::collections::HashSet;
+
+use utils::{di::Provide, entity::Id, outcome::Outcome};
+
+use crate::{
+    grant::{self, Scope},
+    study_group, subdivision,
+    token::Claims,
+    user::{self, Role},
+    AdaptersModule, AppModule,
+};
+
+/// Roles that allow to mutate entities inside of the granted scope
+const MANAGING_ROLES: [Role; 3] = [Role::Admin, Role::UniversityStaff, Role::DepartmentHead];
+
+/// Roles that allow to read entities inside of the granted scope
+const READING_ROLES: [Role; 4] = [
+    Role::Admin,
+    Role::UniversityStaff,
+    Role::DepartmentHead,
+    Role::Teacher,
+];
+
+pub struct AccessPolicy {
+    grant_repo: grant::BoxedRepo,
+    subdivision_repo: subdivision::BoxedRepo,
+    study_group_repo: study_group::BoxedRepo,
+}
+
+impl<A: AdaptersModule> Provide<AccessPolicy> for AppModule<A> {
+    fn provide(&self) -> AccessPolicy {
+        AccessPolicy {
+            grant_repo: self.adapters.resolve(),
+            subdivision_repo: self.adapters.resolve(),
+            study_group_repo: self.adapters.resolve(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, thiserror::Error)]
+pub enum AccessException {
+    #[error("no rights")]
+    NoRights,
+    #[error("scope target not found")]
+    TargetNotFound,
+}
+
+/// Subdivisions and study groups inside of the scopes granted to the actor
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Readable {
+    pub subdivision_ids: HashSet<subdivision::EntityId>,
+    pub study_group_ids: HashSet<study_group::EntityId>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Actor {
+    pub user_id: user::EntityId,
+    pub role: Role,
+}
+
+impl From<&Claims> for Actor {
+    fn from(value: &Claims) -> Self {
+        Self {
+            user_id: Id::new(value.user_id),
+            role: value.role,
+        }
+    }
+}
+
+impl AccessPolicy {
+    /// Succeeds if the actor is a global admin or holds a managing grant
+    /// on the target scope or on any scope that encloses it
+    pub async fn authorize(&self, actor: &Actor, target: Scope) -> Outcome<(), AccessException> {
+        self.authorize_with(actor, &[target], MANAGING_ROLES.as_slice(), |_| true)
+            .await
+    }
+
+    /// Like [`AccessPolicy::authorize`] for entities belonging to several scopes,
+    /// managing any of them is enough
+    pub async fn authorize_some(
+        &self,
+        actor: &Actor,
+        targets: &[Scope],
+    ) -> Outcome<(), AccessException> {
+        self.authorize_with(actor, targets, MANAGING_ROLES.as_slice(), |_| true)
+            .await
+    }
+
+    /// Like [`AccessPolicy::authorize_some`], but a reading grant is enough
+    pub async fn authorize_read(
+        &self,
+        actor: &Actor,
+        targets: &[Scope],
+    ) -> Outcome<(), AccessException> {
+        self.authorize_with(actor, targets, READING_ROLES.as_slice(), |_| true)
+            .await
+    }
+
+    /// Everything the actor may read, `None` if nothing is hidden from them
+    pub async fn readable(&self, actor: &Actor) -> Outcome<Option<Readable>, AccessException> {
+        if actor.role == Role::Admin {
+            return Outcome::Ok(None);
+        }
+
+        let mut readable = Readable::default();
+        let mut whole_universities = HashSet::new();
+        let mut university_ids = HashSet::new();
+
+        let grants = self.grant_repo.list_by_user(actor.user_id).await?;
+        for grant in grants {
+            if !READING_ROLES.contains(&grant.role) {
+                continue;
+            }
+
+            match grant.scope {
+                Scope::Global => return Outcome::Ok(None),
+                Scope::University(id) => {
+                    whole_universities.insert(id);
+                    university_ids.insert(id);
+                }
+                Scope::Subdivision(id) => {
+                    if let Some(subdivision) = self.subdivision_repo.find(id).await? {
+                        university_ids.insert(subdivision.university_id);
+                        readable.subdivision_ids.insert(id);
+                    }
+                }
+                Scope::StudyGroup(id) => {
+                    readable.study_group_ids.insert(id);
+                }
+            }
+        }
+
+        for university_id in university_ids {
+            let subdivisions = self
+                .subdivision_repo
+                .list_by_university(university_id)
+                .await?;
+
+            if whole_universities.contains(&university_id) {
+                readable
+                    .subdivision_ids
+                    .extend(subdivisions.iter().map(|v| v.id));
+                continue;
+            }
+
+            // nested subdivisions are added once their parent is, until nothing changes
+            loop {
+                let count = readable.subdivision_ids.len();
+                for subdivision in &subdivisions {
+                    if subdivision
+                        .parent_id
+                        .is_some_and(|v| readable.subdivision_ids.contains(&v))
+                    {
+                        readable.subdivision_ids.insert(subdivision.id);
+                    }
+                }
+
+                if readable.subdivision_ids.len() == count {
+                    break;
+                }
+            }
+        }
+
+        for &department_id in &readable.subdivision_ids {
+            let study_groups = self
+                .study_group_repo
+                .list_by_department(department_id)
+                .await?;
+            readable
+                .study_group_ids
+                .extend(study_groups.into_iter().map(|v| v.id));
+        }
+
+        Outcome::Ok(Some(readable))
+    }
+
+    /// Like [`AccessPolicy::authorize`], but the managing grant must also rank
+    /// strictly above the role being granted or revoked
+    pub async fn authorize_grant(
+        &self,
+        actor: &Actor,
+        role: Role,
+        target: Scope,
+    ) -> Outcome<(), AccessException> {
+        self.authorize_with(actor, &[target], MANAGING_ROLES.as_slice(), |granted| {
+            rank(granted) > rank(role)
+        })
+        .await
+    }
+
+    async fn authorize_with(
+        &self,
+        actor: &Actor,
+        targets: &[Scope],
+        roles: &[Role],
+        is_sufficient: impl Fn(Role) -> bool,
+    ) -> Outcome<(), AccessException> {
+        if actor.role == Role::Admin {
+            return Outcome::Ok(());
+        }
+
+        let granted_scopes = self
+            .grant_repo
+            .list_by_user(actor.user_id)
+            .await?
+            .into_iter()
+            .filter(|grant| roles.contains(&grant.role) && is_sufficient(grant.role))
+            .map(|grant| grant.scope)
+            .collect::<HashSet<_>>();
+
+        if granted_scopes.is_empty() {
+            return Outcome::Ex(AccessException::NoRights);
+        }
+
+        for target in targets {
+            let enclosing_scopes = self.enclosing_scopes(*target).await?;
+            if enclosing_scopes
+                .iter()
+                .any(|scope| granted_scopes.contains(scope))
+            {
+                return Outcome::Ok(());
+            }
+        }
+
+        Outcome::Ex(AccessException::NoRights)
+    }
+
+    /// Returns the target itself followed by every scope that contains it,
+    /// walking up the subdivision tree up to the university and the global scope
+    async fn enclosing_scopes(&self, target: Scope) -> Outcome<Vec<Scope>, AccessException> {
+        let mut scopes = vec![];
+        let mut university_id = None;
+
+        let mut next_subdivision_id = match target {
+            Scope::Global => None,
+            Scope::University(id) => {
+                university_id = Some(id);
+                None
+            }
+            Scope::Subdivision(id) => Some(id),
+            Scope::StudyGroup(id) => {
+                let Some(study_group) = self.study_group_repo.find(id).await? else {
+                    return Outcome::Ex(AccessException::TargetNotFound);
+                };
+
+                scopes.push(target);
+                Some(study_group.department_id)
+            }
+        };
+
+        let mut visited = HashSet::new();
+        while let Some(id) = next_subdivision_id {
+            if !visited.insert(id) {
+                break;
+            }
+
+            let Some(subdivision) = self.subdivision_repo.find(id).await? else {
+                return Outcome::Ex(AccessException::TargetNotFound);
+            };
+
+            scopes.push(Scope::Subdivision(id));
+            university_id = Some(subdivision.university_id);
+            next_subdivision_id = subdivision.parent_id;
+        }
+
+        if let Some(id) = university_id {
+            scopes.push(Scope::University(id));
+        }
+
+        scopes.push(Scope::Global);
+        Outcome::Ok(scopes)
+    }
+}
+
+/// Higher ranking roles may grant and revoke the ones below them
+fn rank(role: Role) -> u8 {
+    match role {
+        Role::Admin => 4,
+        Role::UniversityStaff => 3,
+        Role::DepartmentHead => 2,
+        Role::Teacher => 1,
+        Role::Student => 0,
+    }
+}
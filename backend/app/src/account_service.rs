@@ -0,0 +1,218 @@
+use utils::{di::Provide, entity::Id, outcome::Outcome};
+
+use crate::{
+    account_token::{self, EmailVerificationTTL, PasswordResetTTL, Purpose},
+    hasher,
+    mail::{BoxedMailSender, LinkBaseUrl, Mail},
+    token::{AccountTokenClaims, BoxedAccountTokenEngine},
+    user,
+    user_session::{self, SecondsFromUnixEpoch},
+    validation::is_valid_password,
+    AdaptersModule, AppModule,
+};
+
+/// Email verification and password recovery, both prove the ownership of the email
+/// with a single use token sent to it
+pub struct AccountService {
+    user_repo: user::BoxedRepo,
+    session_repo: user_session::BoxedRepo,
+    token_repo: account_token::BoxedRepo,
+    hasher: hasher::BoxedPasswordHasher,
+    token_engine: BoxedAccountTokenEngine,
+    mail_sender: BoxedMailSender,
+    email_verification_ttl: EmailVerificationTTL,
+    password_reset_ttl: PasswordResetTTL,
+    link_base_url: LinkBaseUrl,
+}
+
+impl<A: AdaptersModule> Provide<AccountService> for AppModule<A> {
+    fn provide(&self) -> AccountService {
+        AccountService {
+            user_repo: self.adapters.resolve(),
+            session_repo: self.adapters.resolve(),
+            token_repo: self.adapters.resolve(),
+            hasher: self.adapters.resolve(),
+            token_engine: self.adapters.resolve(),
+            mail_sender: self.adapters.resolve(),
+            email_verification_ttl: self.adapters.resolve(),
+            password_reset_ttl: self.adapters.resolve(),
+            link_base_url: self.adapters.resolve(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, thiserror::Error)]
+pub enum AccountException {
+    #[error("user not found")]
+    UserNotFound,
+    #[error("email already verified")]
+    EmailAlreadyVerified,
+    #[error("invalid token")]
+    InvalidToken,
+    #[error("token expired")]
+    TokenExpired,
+    #[error("password must be from 8 to 256 characters long")]
+    InvalidPassword,
+}
+
+impl AccountService {
+    pub async fn request_email_verification(
+        &mut self,
+        user_id: user::EntityId,
+    ) -> Outcome<(), AccountException> {
+        let Some(user) = self.user_repo.find(user_id).await? else {
+            return Outcome::Ex(AccountException::UserNotFound);
+        };
+
+        if user.email_verified {
+            return Outcome::Ex(AccountException::EmailAlreadyVerified);
+        }
+
+        let EmailVerificationTTL(ttl) = self.email_verification_ttl;
+        let token = self
+            .issue_token(user.id, Purpose::EmailVerification, ttl)
+            .await?;
+
+        self.mail_sender
+            .send(Mail {
+                to: user.email,
+                subject: "Подтверждение адреса электронной почты".to_owned(),
+                body: format!(
+                    "Чтобы подтвердить адрес электронной почты, перейдите по ссылке:\n{}\n\n\
+                     Если вы не регистрировались в системе, просто проигнорируйте это письмо.",
+                    self.link("verify-email", &token),
+                ),
+            })
+            .await?;
+
+        Outcome::Ok(())
+    }
+
+    pub async fn verify_email(&mut self, token: String) -> Outcome<user::Entity, AccountException> {
+        let mut user = self.use_token(&token, Purpose::EmailVerification).await?;
+
+        user.email_verified = true;
+        let user = self.user_repo.save(user).await?;
+
+        Outcome::Ok(user)
+    }
+
+    /// Succeeds for unknown emails as well, so the endpoint can't be used
+    /// to find out which emails are registered
+    pub async fn request_password_reset(&mut self, email: String) -> Outcome<(), AccountException> {
+        let Some(user) = self.user_repo.find_by_email(email).await? else {
+            return Outcome::Ok(());
+        };
+
+        let PasswordResetTTL(ttl) = self.password_reset_ttl;
+        let token = self
+            .issue_token(user.id, Purpose::PasswordReset, ttl)
+            .await?;
+
+        self.mail_sender
+            .send(Mail {
+                to: user.email,
+                subject: "Восстановление пароля".to_owned(),
+                body: format!(
+                    "Чтобы задать новый пароль, перейдите по ссылке:\n{}\n\n\
+                     Если вы не запрашивали восстановление пароля, просто проигнорируйте это письмо.",
+                    self.link("reset-password", &token),
+                ),
+            })
+            .await?;
+
+        Outcome::Ok(())
+    }
+
+    /// Signs out every session of the user, whoever knew the old password is logged out too
+    pub async fn reset_password(
+        &mut self,
+        token: String,
+        password: String,
+    ) -> Outcome<user::Entity, AccountException> {
+        if !is_valid_password(&password) {
+            return Outcome::Ex(AccountException::InvalidPassword);
+        }
+
+        let mut user = self.use_token(&token, Purpose::PasswordReset).await?;
+
+        user.password = self.hasher.hash(password).await?;
+        // the reset link was delivered to the email, so it is proven to be the user's
+        user.email_verified = true;
+        let user = self.user_repo.save(user).await?;
+
+        for session in self.session_repo.list_by_user_id(user.id).await? {
+            self.session_repo.delete(&session).await?;
+        }
+
+        Outcome::Ok(user)
+    }
+
+    /// Replaces the tokens issued before, only the latest letter has a working link
+    async fn issue_token(
+        &mut self,
+        user_id: user::EntityId,
+        purpose: Purpose,
+        ttl: user_session::Seconds,
+    ) -> Result<String, anyhow::Error> {
+        self.token_repo.delete_by_user(user_id, purpose).await?;
+
+        let token = self
+            .token_repo
+            .save(account_token::Entity {
+                id: Default::default(),
+                user_id,
+                purpose,
+                expires_at: SecondsFromUnixEpoch::expired_at_from_ttl(ttl)?,
+            })
+            .await?;
+
+        self.token_engine
+            .encode(AccountTokenClaims {
+                token_id: token.id.value,
+                user_id: user_id.value,
+                purpose,
+                expires_at: token.expires_at,
+            })
+            .await
+    }
+
+    /// Checks the token and deletes its row, the token can't be used again
+    async fn use_token(
+        &mut self,
+        token: &str,
+        purpose: Purpose,
+    ) -> Outcome<user::Entity, AccountException> {
+        let Some(claims) = self.token_engine.decode(token).await? else {
+            return Outcome::Ex(AccountException::InvalidToken);
+        };
+
+        let Some(row) = self.token_repo.find(Id::new(claims.token_id)).await? else {
+            return Outcome::Ex(AccountException::InvalidToken);
+        };
+
+        if claims.purpose != purpose
+            || row.purpose != purpose
+            || row.user_id.value != claims.user_id
+        {
+            return Outcome::Ex(AccountException::InvalidToken);
+        }
+
+        if row.expires_at.is_expired()? {
+            return Outcome::Ex(AccountException::TokenExpired);
+        }
+
+        self.token_repo.delete(&row).await?;
+
+        let Some(user) = self.user_repo.find(row.user_id).await? else {
+            return Outcome::Ex(AccountException::UserNotFound);
+        };
+
+        Outcome::Ok(user)
+    }
+
+    fn link(&self, page: &str, token: &str) -> String {
+        let LinkBaseUrl(base_url) = &self.link_base_url;
+        format!("{}/{page}?token={token}", base_url.trim_end_matches('/'))
+    }
+}
@@ -0,0 +1,35 @@
+mod repo;
+
+use utils::entity::entity;
+
+use crate::{
+    user,
+    user_session::{Seconds, SecondsFromUnixEpoch},
+};
+
+pub use repo::Repo;
+pub type BoxedRepo = Box<dyn Repo + Send + Sync>;
+
+/// Token sent to the user by mail, it can be used while its row exists
+/// and the row is deleted once the token is used
+#[entity]
+#[derive(Debug, Clone)]
+pub struct Entity {
+    #[id]
+    pub id: i32,
+    pub user_id: user::EntityId,
+    pub purpose: Purpose,
+    pub expires_at: SecondsFromUnixEpoch,
+}
+
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub enum Purpose {
+    EmailVerification,
+    PasswordReset,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct EmailVerificationTTL(pub Seconds);
+
+#[derive(Debug, Clone, Copy)]
+pub struct PasswordResetTTL(pub Seconds);
@@ -0,0 +1,18 @@
+use crate::user;
+
+use super::{Entity, EntityId, Purpose};
+
+#[async_trait::async_trait]
+pub trait Repo {
+    async fn save(&mut self, entity: Entity) -> Result<Entity, anyhow::Error>;
+
+    async fn delete(&mut self, entity: &Entity) -> Result<(), anyhow::Error>;
+
+    async fn find(&self, id: EntityId) -> Result<Option<Entity>, anyhow::Error>;
+
+    async fn delete_by_user(
+        &mut self,
+        user_id: user::EntityId,
+        purpose: Purpose,
+    ) -> Result<(), anyhow::Error>;
+}
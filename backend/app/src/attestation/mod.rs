@@ -25,7 +25,7 @@ pub struct Score(pub u8);
 #[derive(Debug, Clone)]
 pub struct Hours(pub i32);
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum AttestationKind {
     Test,
     DiffTest,
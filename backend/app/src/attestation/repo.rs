@@ -17,6 +17,12 @@ pub trait Repo {
         curriculum_module_id: curriculum_module::EntityId,
     ) -> Result<Option<Entity>, anyhow::Error>;
 
+    /// Loads attestations of all the given modules in one round-trip
+    async fn list_by_curriculum_modules(
+        &self,
+        curriculum_modules_ids: HashSet<curriculum_module::EntityId>,
+    ) -> Result<Vec<Entity>, anyhow::Error>;
+
     async fn list_by_examiners(
         &self,
         examiners_ids: HashSet<teacher::EntityId>,
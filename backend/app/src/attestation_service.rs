@@ -0,0 +1,248 @@
+use std::collections::HashSet;
+
+use utils::{
+    di::{Module, Provide},
+    outcome::Outcome,
+};
+
+use crate::{
+    access_policy::{AccessException, AccessPolicy, Actor},
+    attestation::{self, AttestationKind, Hours},
+    attestation_session, curriculum, curriculum_module, discipline,
+    grant::Scope,
+    student, teacher, AdaptersModule, AppModule,
+};
+
+pub struct AttestationService {
+    repo: attestation::BoxedRepo,
+    curriculum_module_repo: curriculum_module::BoxedRepo,
+    curriculum_repo: curriculum::BoxedRepo,
+    discipline_repo: discipline::BoxedRepo,
+    teacher_repo: teacher::BoxedRepo,
+    student_repo: student::BoxedRepo,
+    attestation_session_repo: attestation_session::BoxedRepo,
+    policy: AccessPolicy,
+}
+
+impl<A: AdaptersModule> Provide<AttestationService> for AppModule<A> {
+    fn provide(&self) -> AttestationService {
+        AttestationService {
+            repo: self.adapters.resolve(),
+            curriculum_module_repo: self.adapters.resolve(),
+            curriculum_repo: self.adapters.resolve(),
+            discipline_repo: self.adapters.resolve(),
+            teacher_repo: self.adapters.resolve(),
+            student_repo: self.adapters.resolve(),
+            attestation_session_repo: self.adapters.resolve(),
+            policy: self.resolve(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, thiserror::Error)]
+pub enum AttestationException {
+    #[error(transparent)]
+    AccessException(#[from] AccessException),
+    #[error("attestation not found")]
+    NotFound,
+    #[error("curriculum module already has an attestation")]
+    AlreadyExist,
+    #[error("curriculum module not found")]
+    CurriculumModuleNotFound,
+    #[error("examiner not found")]
+    ExaminerNotFound,
+    #[error("duration should be a positive number of hours")]
+    InvalidDuration,
+    #[error("attestation has students scores")]
+    InUse,
+    #[error("attestation has sessions in the timetable")]
+    Scheduled,
+    #[error("curriculum is not a draft and can't be changed")]
+    CurriculumNotEditable,
+}
+
+pub struct AttestationInfo {
+    pub curriculum_module_id: curriculum_module::EntityId,
+    pub kind: AttestationKind,
+    pub duration: Hours,
+    pub examiners: HashSet<teacher::EntityId>,
+}
+
+impl AttestationInfo {
+    fn into_entity(self, id: attestation::EntityId) -> attestation::Entity {
+        attestation::Entity {
+            id,
+            curriculum_module_id: self.curriculum_module_id,
+            kind: self.kind,
+            duration: self.duration,
+            examiners: self.examiners,
+        }
+    }
+}
+
+impl AttestationService {
+    pub async fn create(
+        &mut self,
+        actor: &Actor,
+        info: AttestationInfo,
+    ) -> Outcome<attestation::Entity, AttestationException> {
+        let scope = self.module_scope(info.curriculum_module_id).await?;
+        self.policy.authorize(actor, scope).await?;
+        self.ensure_editable(info.curriculum_module_id).await?;
+        self.validate(None, &info).await?;
+
+        let attestation = self.repo.save(info.into_entity(Default::default())).await?;
+        Outcome::Ok(attestation)
+    }
+
+    pub async fn update(
+        &mut self,
+        actor: &Actor,
+        id: attestation::EntityId,
+        info: AttestationInfo,
+    ) -> Outcome<attestation::Entity, AttestationException> {
+        let attestation = self.get(id).await?;
+
+        let scope = self.module_scope(attestation.curriculum_module_id).await?;
+        self.policy.authorize(actor, scope).await?;
+        self.ensure_editable(attestation.curriculum_module_id)
+            .await?;
+
+        if attestation.curriculum_module_id != info.curriculum_module_id {
+            let scope = self.module_scope(info.curriculum_module_id).await?;
+            self.policy.authorize(actor, scope).await?;
+            self.ensure_editable(info.curriculum_module_id).await?;
+        }
+
+        self.validate(Some(id), &info).await?;
+
+        let attestation = self.repo.save(info.into_entity(id)).await?;
+        Outcome::Ok(attestation)
+    }
+
+    pub async fn delete(
+        &mut self,
+        actor: &Actor,
+        id: attestation::EntityId,
+    ) -> Outcome<attestation::Entity, AttestationException> {
+        let attestation = self.get(id).await?;
+
+        let scope = self.module_scope(attestation.curriculum_module_id).await?;
+        self.policy.authorize(actor, scope).await?;
+        self.ensure_editable(attestation.curriculum_module_id)
+            .await?;
+
+        let is_in_use = !self
+            .student_repo
+            .list_by_attestations(HashSet::from([id]))
+            .await?
+            .is_empty();
+
+        if is_in_use {
+            return Outcome::Ex(AttestationException::InUse);
+        }
+
+        let is_scheduled = !self
+            .attestation_session_repo
+            .list_by_attestations(HashSet::from([id]))
+            .await?
+            .is_empty();
+
+        if is_scheduled {
+            return Outcome::Ex(AttestationException::Scheduled);
+        }
+
+        self.repo.delete(&attestation).await?;
+        Outcome::Ok(attestation)
+    }
+
+    pub async fn get(
+        &self,
+        id: attestation::EntityId,
+    ) -> Outcome<attestation::Entity, AttestationException> {
+        let Some(attestation) = self.repo.find(id).await? else {
+            return Outcome::Ex(AttestationException::NotFound);
+        };
+
+        Outcome::Ok(attestation)
+    }
+
+    /// Attestations are managed by the department that teaches the module discipline
+    async fn module_scope(
+        &self,
+        curriculum_module_id: curriculum_module::EntityId,
+    ) -> Outcome<Scope, AttestationException> {
+        let Some(curriculum_module) = self
+            .curriculum_module_repo
+            .find(curriculum_module_id)
+            .await?
+        else {
+            return Outcome::Ex(AttestationException::CurriculumModuleNotFound);
+        };
+
+        let Some(discipline) = self
+            .discipline_repo
+            .find(curriculum_module.discipline_id)
+            .await?
+        else {
+            return Outcome::Ex(AttestationException::CurriculumModuleNotFound);
+        };
+
+        Outcome::Ok(Scope::Subdivision(discipline.department_id))
+    }
+
+    /// Only modules of drafts can be changed, published versions are taught as they are
+    async fn ensure_editable(
+        &self,
+        curriculum_module_id: curriculum_module::EntityId,
+    ) -> Outcome<(), AttestationException> {
+        let Some(curriculum_module) = self
+            .curriculum_module_repo
+            .find(curriculum_module_id)
+            .await?
+        else {
+            return Outcome::Ex(AttestationException::CurriculumModuleNotFound);
+        };
+
+        let is_editable = self
+            .curriculum_repo
+            .find(curriculum_module.curriculum_id)
+            .await?
+            .is_some_and(|v| v.is_editable());
+
+        if !is_editable {
+            return Outcome::Ex(AttestationException::CurriculumNotEditable);
+        }
+
+        Outcome::Ok(())
+    }
+
+    async fn validate(
+        &self,
+        id: Option<attestation::EntityId>,
+        info: &AttestationInfo,
+    ) -> Outcome<(), AttestationException> {
+        let Hours(duration) = info.duration;
+        if duration <= 0 {
+            return Outcome::Ex(AttestationException::InvalidDuration);
+        }
+
+        if let Some(attestation) = self
+            .repo
+            .find_by_curriculum_module(info.curriculum_module_id)
+            .await?
+        {
+            if Some(attestation.id) != id {
+                return Outcome::Ex(AttestationException::AlreadyExist);
+            }
+        }
+
+        for examiner_id in info.examiners.iter() {
+            if self.teacher_repo.find(*examiner_id).await?.is_none() {
+                return Outcome::Ex(AttestationException::ExaminerNotFound);
+            }
+        }
+
+        Outcome::Ok(())
+    }
+}
@@ -0,0 +1,44 @@
+use time::{Date, Time};
+use utils::entity::entity;
+
+use crate::{
+    attestation, room,
+    schedule::{self, week_start},
+    study_group,
+};
+
+mod repo;
+
+pub use repo::Repo;
+pub type BoxedRepo = Box<dyn Repo + Send + Sync>;
+
+/// Date and time a study group takes an attestation, the room is unknown for remote ones
+#[entity]
+#[derive(Debug, Clone)]
+pub struct Entity {
+    #[id]
+    pub id: i32,
+    pub attestation_id: attestation::EntityId,
+    pub study_group_id: study_group::EntityId,
+    pub room_id: Option<room::EntityId>,
+    pub date: Date,
+    pub starts_at: Time,
+    pub ends_at: Time,
+}
+
+impl Entity {
+    pub fn is_valid_time(&self) -> bool {
+        self.starts_at < self.ends_at
+    }
+
+    pub fn overlaps(&self, other: &Entity) -> bool {
+        self.date == other.date && self.starts_at < other.ends_at && other.starts_at < self.ends_at
+    }
+
+    /// Whether the class takes place on the day of the session at the same time
+    pub fn conflicts_with(&self, entry: &schedule::Entity) -> bool {
+        entry.occurrence_in_week(week_start(self.date)) == Some(self.date)
+            && entry.slot.starts_at < self.ends_at
+            && self.starts_at < entry.slot.ends_at
+    }
+}
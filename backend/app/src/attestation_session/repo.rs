@@ -0,0 +1,26 @@
+use std::collections::HashSet;
+
+use crate::{attestation, room, study_group};
+
+use super::{Entity, EntityId};
+
+#[async_trait::async_trait]
+pub trait Repo {
+    async fn save(&mut self, entity: Entity) -> Result<Entity, anyhow::Error>;
+
+    async fn delete(&mut self, entity: &Entity) -> Result<(), anyhow::Error>;
+
+    async fn find(&self, id: EntityId) -> Result<Option<Entity>, anyhow::Error>;
+
+    async fn list_by_attestations(
+        &self,
+        attestation_ids: HashSet<attestation::EntityId>,
+    ) -> Result<Vec<Entity>, anyhow::Error>;
+
+    async fn list_by_study_group(
+        &self,
+        study_group_id: study_group::EntityId,
+    ) -> Result<Vec<Entity>, anyhow::Error>;
+
+    async fn list_by_room(&self, room_id: room::EntityId) -> Result<Vec<Entity>, anyhow::Error>;
+}
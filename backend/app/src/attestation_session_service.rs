@@ -0,0 +1,235 @@
+use time::{Date, Time};
+use utils::{
+    di::{Module, Provide},
+    outcome::Outcome,
+};
+
+use crate::{
+    access_policy::{AccessException, AccessPolicy, Actor},
+    attestation, attestation_session, curriculum_module,
+    grant::Scope,
+    room, schedule, study_group, AdaptersModule, AppModule,
+};
+
+pub struct AttestationSessionService {
+    repo: attestation_session::BoxedRepo,
+    attestation_repo: attestation::BoxedRepo,
+    curriculum_module_repo: curriculum_module::BoxedRepo,
+    study_group_repo: study_group::BoxedRepo,
+    room_repo: room::BoxedRepo,
+    schedule_repo: schedule::BoxedRepo,
+    policy: AccessPolicy,
+}
+
+impl<A: AdaptersModule> Provide<AttestationSessionService> for AppModule<A> {
+    fn provide(&self) -> AttestationSessionService {
+        AttestationSessionService {
+            repo: self.adapters.resolve(),
+            attestation_repo: self.adapters.resolve(),
+            curriculum_module_repo: self.adapters.resolve(),
+            study_group_repo: self.adapters.resolve(),
+            room_repo: self.adapters.resolve(),
+            schedule_repo: self.adapters.resolve(),
+            policy: self.resolve(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, thiserror::Error)]
+pub enum AttestationSessionException {
+    #[error(transparent)]
+    AccessException(#[from] AccessException),
+    #[error("attestation session not found")]
+    NotFound,
+    #[error("attestation not found")]
+    AttestationNotFound,
+    #[error("study group not found")]
+    StudyGroupNotFound,
+    #[error("room not found")]
+    RoomNotFound,
+    #[error("attestation is not in the curriculums of the study group")]
+    NotInCurriculum,
+    #[error("session should end after it starts")]
+    InvalidTime,
+    #[error("room is already occupied at this time")]
+    RoomBusy,
+    #[error("study group already has a class or an attestation at this time")]
+    StudyGroupBusy,
+}
+
+pub struct AttestationSessionInfo {
+    pub attestation_id: attestation::EntityId,
+    pub study_group_id: study_group::EntityId,
+    pub room_id: Option<room::EntityId>,
+    pub date: Date,
+    pub starts_at: Time,
+    pub ends_at: Time,
+}
+
+impl AttestationSessionInfo {
+    fn into_entity(self, id: attestation_session::EntityId) -> attestation_session::Entity {
+        attestation_session::Entity {
+            id,
+            attestation_id: self.attestation_id,
+            study_group_id: self.study_group_id,
+            room_id: self.room_id,
+            date: self.date,
+            starts_at: self.starts_at,
+            ends_at: self.ends_at,
+        }
+    }
+}
+
+impl AttestationSessionService {
+    pub async fn create(
+        &mut self,
+        actor: &Actor,
+        info: AttestationSessionInfo,
+    ) -> Outcome<attestation_session::Entity, AttestationSessionException> {
+        self.policy
+            .authorize(actor, Scope::StudyGroup(info.study_group_id))
+            .await?;
+
+        let session = info.into_entity(Default::default());
+        self.validate(&session).await?;
+
+        let session = self.repo.save(session).await?;
+        Outcome::Ok(session)
+    }
+
+    pub async fn update(
+        &mut self,
+        actor: &Actor,
+        id: attestation_session::EntityId,
+        info: AttestationSessionInfo,
+    ) -> Outcome<attestation_session::Entity, AttestationSessionException> {
+        let session = self.get(id).await?;
+
+        self.policy
+            .authorize(actor, Scope::StudyGroup(session.study_group_id))
+            .await?;
+
+        if session.study_group_id != info.study_group_id {
+            self.policy
+                .authorize(actor, Scope::StudyGroup(info.study_group_id))
+                .await?;
+        }
+
+        let session = info.into_entity(id);
+        self.validate(&session).await?;
+
+        let session = self.repo.save(session).await?;
+        Outcome::Ok(session)
+    }
+
+    pub async fn delete(
+        &mut self,
+        actor: &Actor,
+        id: attestation_session::EntityId,
+    ) -> Outcome<attestation_session::Entity, AttestationSessionException> {
+        let session = self.get(id).await?;
+
+        self.policy
+            .authorize(actor, Scope::StudyGroup(session.study_group_id))
+            .await?;
+
+        self.repo.delete(&session).await?;
+        Outcome::Ok(session)
+    }
+
+    pub async fn get(
+        &self,
+        id: attestation_session::EntityId,
+    ) -> Outcome<attestation_session::Entity, AttestationSessionException> {
+        let Some(session) = self.repo.find(id).await? else {
+            return Outcome::Ex(AttestationSessionException::NotFound);
+        };
+
+        Outcome::Ok(session)
+    }
+
+    pub async fn list_by_study_group(
+        &self,
+        study_group_id: study_group::EntityId,
+    ) -> Outcome<Vec<attestation_session::Entity>, AttestationSessionException> {
+        if self.study_group_repo.find(study_group_id).await?.is_none() {
+            return Outcome::Ex(AttestationSessionException::StudyGroupNotFound);
+        }
+
+        let mut sessions = self.repo.list_by_study_group(study_group_id).await?;
+        sessions.sort_by_key(|v| (v.date, v.starts_at, v.id.value));
+
+        Outcome::Ok(sessions)
+    }
+
+    async fn validate(
+        &self,
+        session: &attestation_session::Entity,
+    ) -> Outcome<(), AttestationSessionException> {
+        if !session.is_valid_time() {
+            return Outcome::Ex(AttestationSessionException::InvalidTime);
+        }
+
+        let Some(attestation) = self.attestation_repo.find(session.attestation_id).await? else {
+            return Outcome::Ex(AttestationSessionException::AttestationNotFound);
+        };
+
+        let Some(study_group) = self.study_group_repo.find(session.study_group_id).await? else {
+            return Outcome::Ex(AttestationSessionException::StudyGroupNotFound);
+        };
+
+        let is_in_curriculum = self
+            .curriculum_module_repo
+            .find(attestation.curriculum_module_id)
+            .await?
+            .is_some_and(|v| study_group.curriculums.contains(&v.curriculum_id));
+
+        if !is_in_curriculum {
+            return Outcome::Ex(AttestationSessionException::NotInCurriculum);
+        }
+
+        if let Some(room_id) = session.room_id {
+            if self.room_repo.find(room_id).await?.is_none() {
+                return Outcome::Ex(AttestationSessionException::RoomNotFound);
+            }
+
+            let is_busy = Self::busy(
+                self.repo.list_by_room(room_id).await?,
+                self.schedule_repo.list_by_room(room_id).await?,
+                session,
+            );
+
+            if is_busy {
+                return Outcome::Ex(AttestationSessionException::RoomBusy);
+            }
+        }
+
+        let is_busy = Self::busy(
+            self.repo
+                .list_by_study_group(session.study_group_id)
+                .await?,
+            self.schedule_repo
+                .list_by_study_group(session.study_group_id)
+                .await?,
+            session,
+        );
+
+        if is_busy {
+            return Outcome::Ex(AttestationSessionException::StudyGroupBusy);
+        }
+
+        Outcome::Ok(())
+    }
+
+    /// Whether another session or a class takes place at the time of the session
+    fn busy(
+        sessions: Vec<attestation_session::Entity>,
+        classes: Vec<schedule::Entity>,
+        session: &attestation_session::Entity,
+    ) -> bool {
+        sessions
+            .iter()
+            .any(|v| v.id != session.id && v.overlaps(session))
+            || classes.iter().any(|v| session.conflicts_with(v))
+    }
+}
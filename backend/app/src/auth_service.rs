@@ -5,9 +5,10 @@ use utils::{
 };
 
 use crate::{
+    login_throttle_service::LoginThrottleService,
     token::{AccessTokenTTL, BoxedAccessTokenEngine, Claims},
     user_service::{UserException, UserService},
-    user_session::SecondsFromUnixEpoch,
+    user_session::{ClientInfo, SecondsFromUnixEpoch},
     AdaptersModule, AppModule,
 };
 
@@ -19,6 +20,7 @@ pub enum AuthException {
 
 pub struct AuthService {
     user_service: UserService,
+    login_throttle_service: LoginThrottleService,
     access_token_engine: BoxedAccessTokenEngine,
     access_token_ttl: AccessTokenTTL,
 }
@@ -27,6 +29,7 @@ impl<A: AdaptersModule> Provide<AuthService> for AppModule<A> {
     fn provide(&self) -> AuthService {
         AuthService {
             user_service: self.resolve(),
+            login_throttle_service: self.resolve(),
             access_token_engine: self.adapters.resolve(),
             access_token_ttl: self.adapters.resolve(),
         }
@@ -36,6 +39,9 @@ impl<A: AdaptersModule> Provide<AuthService> for AppModule<A> {
 pub struct Tokens {
     pub access_token: String,
     pub refresh_token: String,
+    /// The client sends it with the refresh token, it is issued at login
+    /// when the client has none yet
+    pub device_id: String,
 }
 
 impl AuthService {
@@ -43,14 +49,22 @@ impl AuthService {
         &mut self,
         email: String,
         password: String,
-        session_metadata: String,
+        device_id: Option<String>,
+        client: ClientInfo,
     ) -> Outcome<Tokens, AuthException> {
         let AccessTokenTTL(access_token_ttl) = self.access_token_ttl;
 
+        let attempt = self
+            .login_throttle_service
+            .begin(&email, client.ip.as_deref())
+            .await?;
+
+        // a failed attempt stays counted
         let user = self.user_service.authenticate(email, password).await?;
+        self.login_throttle_service.record_success(attempt).await?;
         let session = self
             .user_service
-            .create_session(user.id, session_metadata)
+            .create_session(user.id, device_id, client)
             .await?;
 
         let access_token = self
@@ -59,12 +73,14 @@ impl AuthService {
                 user_id: user.id.value,
                 email: user.email,
                 expires_at: SecondsFromUnixEpoch::expired_at_from_ttl(access_token_ttl)?,
+                role: user.role,
             })
             .await?;
 
         Outcome::Ok(Tokens {
             access_token,
             refresh_token: session.refresh_token,
+            device_id: session.session.id.value.device_id,
         })
     }
 
@@ -72,14 +88,15 @@ impl AuthService {
         &mut self,
         user_id: i32,
         refresh_token_to_validate: String,
-        session_metadata: String,
+        device_id: String,
+        client: ClientInfo,
     ) -> Outcome<Tokens, AuthException> {
         let AccessTokenTTL(access_token_ttl) = self.access_token_ttl;
 
         let user = self.user_service.get(Id::new(user_id)).await?;
         let session = self
             .user_service
-            .update_session(user.id, session_metadata, refresh_token_to_validate)
+            .update_session(user.id, device_id, client, refresh_token_to_validate)
             .await?;
 
         let access_token = self
@@ -88,25 +105,27 @@ impl AuthService {
                 user_id: user.id.value,
                 email: user.email,
                 expires_at: SecondsFromUnixEpoch::expired_at_from_ttl(access_token_ttl)?,
+                role: user.role,
             })
             .await?;
 
         Outcome::Ok(Tokens {
             access_token,
             refresh_token: session.refresh_token,
+            device_id: session.session.id.value.device_id,
         })
     }
 
     pub async fn logout(
         &mut self,
         user_id: i32,
-        session_metadata: String,
+        device_id: String,
         refresh_token_to_validate: String,
     ) -> Outcome<(), AuthException> {
         let user = self.user_service.get(Id::new(user_id)).await?;
         let _removed_session = self
             .user_service
-            .remove_session(user.id, session_metadata, refresh_token_to_validate)
+            .remove_session(user.id, device_id, refresh_token_to_validate)
             .await?;
 
         Outcome::Ok(())
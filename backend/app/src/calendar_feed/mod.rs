@@ -0,0 +1,19 @@
+use utils::entity::entity;
+
+use crate::person;
+
+mod repo;
+
+pub use repo::Repo;
+pub type BoxedRepo = Box<dyn Repo + Send + Sync>;
+
+/// Secret token granting access to the calendar feeds, calendar apps can't send credentials
+#[entity]
+#[derive(Debug, Clone)]
+pub struct Entity {
+    #[id]
+    pub id: i32,
+    pub person_id: person::EntityId,
+    /// Only the hash is stored, the token itself is shown once when the feed is issued
+    pub token_hash: String,
+}
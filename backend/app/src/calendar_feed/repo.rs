@@ -0,0 +1,18 @@
+use crate::person;
+
+use super::Entity;
+
+#[async_trait::async_trait]
+pub trait Repo {
+    async fn save(&mut self, entity: Entity) -> Result<Entity, anyhow::Error>;
+
+    async fn delete(&mut self, entity: &Entity) -> Result<(), anyhow::Error>;
+
+    async fn find_by_person(
+        &self,
+        person_id: person::EntityId,
+    ) -> Result<Option<Entity>, anyhow::Error>;
+
+    async fn find_by_token_hash(&self, token_hash: String)
+        -> Result<Option<Entity>, anyhow::Error>;
+}
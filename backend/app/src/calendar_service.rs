@@ -0,0 +1,490 @@
+use std::collections::HashMap;
+
+use time::{Date, OffsetDateTime, PrimitiveDateTime, Time, UtcOffset};
+use utils::{di::Provide, outcome::Outcome};
+
+use crate::{
+    access_policy::Actor,
+    attestation::{self, AttestationKind},
+    attestation_session, calendar_feed, class, class_kind, curriculum_module, discipline, person,
+    room,
+    schedule::{self, Recurrence},
+    student, study_group, subdivision, teacher,
+    token::{BoxedRefreshTokenGenerator, BoxedRefreshTokenHasher},
+    university, AdaptersModule, AppModule,
+};
+
+pub struct CalendarService {
+    feed_repo: calendar_feed::BoxedRepo,
+    person_repo: person::BoxedRepo,
+    student_repo: student::BoxedRepo,
+    teacher_repo: teacher::BoxedRepo,
+    study_group_repo: study_group::BoxedRepo,
+    subdivision_repo: subdivision::BoxedRepo,
+    university_repo: university::BoxedRepo,
+    schedule_repo: schedule::BoxedRepo,
+    attestation_session_repo: attestation_session::BoxedRepo,
+    attestation_repo: attestation::BoxedRepo,
+    class_repo: class::BoxedRepo,
+    curriculum_module_repo: curriculum_module::BoxedRepo,
+    discipline_repo: discipline::BoxedRepo,
+    room_repo: room::BoxedRepo,
+    token_generator: BoxedRefreshTokenGenerator,
+    token_hasher: BoxedRefreshTokenHasher,
+}
+
+impl<A: AdaptersModule> Provide<CalendarService> for AppModule<A> {
+    fn provide(&self) -> CalendarService {
+        CalendarService {
+            feed_repo: self.adapters.resolve(),
+            person_repo: self.adapters.resolve(),
+            student_repo: self.adapters.resolve(),
+            teacher_repo: self.adapters.resolve(),
+            study_group_repo: self.adapters.resolve(),
+            subdivision_repo: self.adapters.resolve(),
+            university_repo: self.adapters.resolve(),
+            schedule_repo: self.adapters.resolve(),
+            attestation_session_repo: self.adapters.resolve(),
+            attestation_repo: self.adapters.resolve(),
+            class_repo: self.adapters.resolve(),
+            curriculum_module_repo: self.adapters.resolve(),
+            discipline_repo: self.adapters.resolve(),
+            room_repo: self.adapters.resolve(),
+            token_generator: self.adapters.resolve(),
+            token_hasher: self.adapters.resolve(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, thiserror::Error)]
+pub enum CalendarException {
+    #[error("calendar feed not found")]
+    FeedNotFound,
+    #[error("user has no person")]
+    PersonNotFound,
+    #[error("study group not found")]
+    StudyGroupNotFound,
+    #[error("teacher not found")]
+    TeacherNotFound,
+}
+
+/// Feed with its token, which can't be recovered from the stored hash later
+pub struct IssuedFeed {
+    pub feed: calendar_feed::Entity,
+    pub token: String,
+}
+
+/// Events of a feed named after the group, teacher or person it belongs to
+pub struct Calendar {
+    pub name: String,
+    pub events: Vec<Event>,
+}
+
+/// Class or attestation session, its times are in the offset of the university
+pub struct Event {
+    pub source: EventSource,
+    pub discipline: String,
+    pub kind: EventKind,
+    pub study_group: String,
+    pub teachers: Vec<String>,
+    pub room: Option<String>,
+    pub starts_at: OffsetDateTime,
+    pub ends_at: OffsetDateTime,
+    pub repeat: Option<Repeat>,
+}
+
+/// Entity the event comes from, it identifies the event across feed updates
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EventSource {
+    Class(schedule::EntityId),
+    AttestationSession(attestation_session::EntityId),
+}
+
+pub enum EventKind {
+    Class(class_kind::EntityId),
+    Attestation(AttestationKind),
+}
+
+/// Weekly repetition of a class after its first occurrence
+pub struct Repeat {
+    pub interval_weeks: u8,
+    pub until: OffsetDateTime,
+}
+
+impl CalendarService {
+    pub async fn get_feed(
+        &self,
+        actor: &Actor,
+    ) -> Outcome<calendar_feed::Entity, CalendarException> {
+        let person = self.actor_person(actor).await?;
+
+        let Some(feed) = self.feed_repo.find_by_person(person.id).await? else {
+            return Outcome::Ex(CalendarException::FeedNotFound);
+        };
+
+        Outcome::Ok(feed)
+    }
+
+    /// Issues a new token, links with the previous one stop working
+    pub async fn issue_feed(&mut self, actor: &Actor) -> Outcome<IssuedFeed, CalendarException> {
+        let person = self.actor_person(actor).await?;
+        let id = match self.feed_repo.find_by_person(person.id).await? {
+            Some(feed) => feed.id,
+            None => Default::default(),
+        };
+
+        let token = self.token_generator.generate().await?;
+        let feed = self
+            .feed_repo
+            .save(calendar_feed::Entity {
+                id,
+                person_id: person.id,
+                token_hash: self.token_hasher.hash(&token),
+            })
+            .await?;
+
+        Outcome::Ok(IssuedFeed { feed, token })
+    }
+
+    pub async fn revoke_feed(
+        &mut self,
+        actor: &Actor,
+    ) -> Outcome<calendar_feed::Entity, CalendarException> {
+        let feed = self.get_feed(actor).await?;
+
+        self.feed_repo.delete(&feed).await?;
+        Outcome::Ok(feed)
+    }
+
+    pub async fn study_group_calendar(
+        &self,
+        token: String,
+        study_group_id: study_group::EntityId,
+    ) -> Outcome<Calendar, CalendarException> {
+        self.authenticate(token).await?;
+
+        let Some(study_group) = self.study_group_repo.find(study_group_id).await? else {
+            return Outcome::Ex(CalendarException::StudyGroupNotFound);
+        };
+
+        let events = self.study_group_events(study_group_id).await?;
+        Outcome::Ok(Calendar {
+            name: study_group.name,
+            events,
+        })
+    }
+
+    pub async fn teacher_calendar(
+        &self,
+        token: String,
+        teacher_id: teacher::EntityId,
+    ) -> Outcome<Calendar, CalendarException> {
+        self.authenticate(token).await?;
+
+        let Some(teacher) = self.teacher_repo.find(teacher_id).await? else {
+            return Outcome::Ex(CalendarException::TeacherNotFound);
+        };
+
+        let name = match self.person_repo.find(teacher.person_id).await? {
+            Some(person) => person.full_name,
+            None => String::new(),
+        };
+
+        let events = self.teacher_events(&teacher).await?;
+        Outcome::Ok(Calendar { name, events })
+    }
+
+    /// Classes and attestations of the person as a student and as a teacher
+    pub async fn person_calendar(&self, token: String) -> Outcome<Calendar, CalendarException> {
+        let feed = self.authenticate(token).await?;
+
+        let Some(person) = self.person_repo.find(feed.person_id).await? else {
+            return Outcome::Ex(CalendarException::FeedNotFound);
+        };
+
+        let mut events = vec![];
+        for student in self.student_repo.list_by_person(person.id).await? {
+            events.extend(self.study_group_events(student.study_group_id).await?);
+        }
+
+        if let Some(teacher) = self.teacher_repo.find_by_person_id(person.id).await? {
+            events.extend(self.teacher_events(&teacher).await?);
+        }
+
+        let mut events = events
+            .into_iter()
+            .map(|v| (v.source, v))
+            .collect::<HashMap<_, _>>()
+            .into_values()
+            .collect::<Vec<_>>();
+        events.sort_by_key(|v| v.starts_at);
+
+        Outcome::Ok(Calendar {
+            name: person.full_name,
+            events,
+        })
+    }
+
+    async fn actor_person(&self, actor: &Actor) -> Outcome<person::Entity, CalendarException> {
+        let Some(person) = self.person_repo.find_by_user_id(actor.user_id).await? else {
+            return Outcome::Ex(CalendarException::PersonNotFound);
+        };
+
+        Outcome::Ok(person)
+    }
+
+    async fn authenticate(
+        &self,
+        token: String,
+    ) -> Outcome<calendar_feed::Entity, CalendarException> {
+        let token_hash = self.token_hasher.hash(&token);
+        let Some(feed) = self.feed_repo.find_by_token_hash(token_hash).await? else {
+            return Outcome::Ex(CalendarException::FeedNotFound);
+        };
+
+        Outcome::Ok(feed)
+    }
+
+    async fn study_group_events(
+        &self,
+        study_group_id: study_group::EntityId,
+    ) -> Result<Vec<Event>, anyhow::Error> {
+        let classes = self
+            .schedule_repo
+            .list_by_study_group(study_group_id)
+            .await?;
+        let sessions = self
+            .attestation_session_repo
+            .list_by_study_group(study_group_id)
+            .await?;
+
+        self.events(classes, sessions).await
+    }
+
+    /// Classes the teacher holds and sessions of the attestations the teacher examines
+    async fn teacher_events(&self, teacher: &teacher::Entity) -> Result<Vec<Event>, anyhow::Error> {
+        let classes = self.schedule_repo.list_by_teacher(teacher.id).await?;
+
+        let attestations = self
+            .attestation_repo
+            .list_by_examiners([teacher.id].into())
+            .await?
+            .into_iter()
+            .map(|v| v.id)
+            .collect();
+        let sessions = self
+            .attestation_session_repo
+            .list_by_attestations(attestations)
+            .await?;
+
+        self.events(classes, sessions).await
+    }
+
+    async fn events(
+        &self,
+        classes: Vec<schedule::Entity>,
+        sessions: Vec<attestation_session::Entity>,
+    ) -> Result<Vec<Event>, anyhow::Error> {
+        let mut names = Names::default();
+        let mut events = vec![];
+
+        for entry in classes {
+            let Some(first) = entry.first_occurrence() else {
+                continue;
+            };
+
+            let Some(class) = self.class_repo.find(entry.class_id).await? else {
+                continue;
+            };
+
+            let utc_offset = self.utc_offset(&mut names, entry.study_group_id).await?;
+            let teacher = self.teacher_name(&mut names, entry.teacher_id).await?;
+
+            events.push(Event {
+                source: EventSource::Class(entry.id),
+                discipline: self
+                    .discipline_name(&mut names, class.curriculum_module_id)
+                    .await?,
+                kind: EventKind::Class(class.kind_name),
+                study_group: self
+                    .study_group_name(&mut names, entry.study_group_id)
+                    .await?,
+                teachers: teacher.into_iter().collect(),
+                room: self.room_name(&mut names, Some(entry.room_id)).await?,
+                starts_at: local(first, entry.slot.starts_at, utc_offset),
+                ends_at: local(first, entry.slot.ends_at, utc_offset),
+                repeat: Some(Repeat {
+                    interval_weeks: match entry.recurrence {
+                        Recurrence::Weekly => 1,
+                        Recurrence::BiWeekly => 2,
+                    },
+                    until: local(entry.valid_until, entry.slot.starts_at, utc_offset),
+                }),
+            });
+        }
+
+        for session in sessions {
+            let Some(attestation) = self.attestation_repo.find(session.attestation_id).await?
+            else {
+                continue;
+            };
+
+            let utc_offset = self.utc_offset(&mut names, session.study_group_id).await?;
+
+            let mut examiners = attestation.examiners.into_iter().collect::<Vec<_>>();
+            examiners.sort_by_key(|v| v.value);
+
+            let mut teachers = vec![];
+            for teacher_id in examiners {
+                teachers.extend(self.teacher_name(&mut names, teacher_id).await?);
+            }
+
+            events.push(Event {
+                source: EventSource::AttestationSession(session.id),
+                discipline: self
+                    .discipline_name(&mut names, attestation.curriculum_module_id)
+                    .await?,
+                kind: EventKind::Attestation(attestation.kind),
+                study_group: self
+                    .study_group_name(&mut names, session.study_group_id)
+                    .await?,
+                teachers,
+                room: self.room_name(&mut names, session.room_id).await?,
+                starts_at: local(session.date, session.starts_at, utc_offset),
+                ends_at: local(session.date, session.ends_at, utc_offset),
+                repeat: None,
+            });
+        }
+
+        events.sort_by_key(|v| v.starts_at);
+        Ok(events)
+    }
+
+    /// Offset of the university the study group belongs to, UTC if it can't be resolved
+    async fn utc_offset(
+        &self,
+        names: &mut Names,
+        study_group_id: study_group::EntityId,
+    ) -> Result<UtcOffset, anyhow::Error> {
+        if let Some(offset) = names.utc_offsets.get(&study_group_id) {
+            return Ok(*offset);
+        }
+
+        let mut offset = UtcOffset::UTC;
+        if let Some(study_group) = self.study_group_repo.find(study_group_id).await? {
+            if let Some(department) = self
+                .subdivision_repo
+                .find(study_group.department_id)
+                .await?
+            {
+                if let Some(university) =
+                    self.university_repo.find(department.university_id).await?
+                {
+                    offset = university.utc_offset;
+                }
+            }
+        }
+
+        names.utc_offsets.insert(study_group_id, offset);
+        Ok(offset)
+    }
+
+    async fn study_group_name(
+        &self,
+        names: &mut Names,
+        id: study_group::EntityId,
+    ) -> Result<String, anyhow::Error> {
+        if let Some(name) = names.study_groups.get(&id) {
+            return Ok(name.clone());
+        }
+
+        let name = self
+            .study_group_repo
+            .find(id)
+            .await?
+            .map(|v| v.name)
+            .unwrap_or_default();
+
+        names.study_groups.insert(id, name.clone());
+        Ok(name)
+    }
+
+    async fn teacher_name(
+        &self,
+        names: &mut Names,
+        id: teacher::EntityId,
+    ) -> Result<Option<String>, anyhow::Error> {
+        if let Some(name) = names.teachers.get(&id) {
+            return Ok(name.clone());
+        }
+
+        let mut name = None;
+        if let Some(teacher) = self.teacher_repo.find(id).await? {
+            name = self
+                .person_repo
+                .find(teacher.person_id)
+                .await?
+                .map(|v| v.full_name);
+        }
+
+        names.teachers.insert(id, name.clone());
+        Ok(name)
+    }
+
+    async fn discipline_name(
+        &self,
+        names: &mut Names,
+        id: curriculum_module::EntityId,
+    ) -> Result<String, anyhow::Error> {
+        if let Some(name) = names.disciplines.get(&id) {
+            return Ok(name.clone());
+        }
+
+        let mut name = String::new();
+        if let Some(curriculum_module) = self.curriculum_module_repo.find(id).await? {
+            if let Some(discipline) = self
+                .discipline_repo
+                .find(curriculum_module.discipline_id)
+                .await?
+            {
+                name = discipline.name;
+            }
+        }
+
+        names.disciplines.insert(id, name.clone());
+        Ok(name)
+    }
+
+    async fn room_name(
+        &self,
+        names: &mut Names,
+        id: Option<room::EntityId>,
+    ) -> Result<Option<String>, anyhow::Error> {
+        let Some(id) = id else {
+            return Ok(None);
+        };
+
+        if let Some(name) = names.rooms.get(&id) {
+            return Ok(name.clone());
+        }
+
+        let name = self.room_repo.find(id).await?.map(|v| v.name);
+
+        names.rooms.insert(id, name.clone());
+        Ok(name)
+    }
+}
+
+/// Names already loaded while building a feed, the same ones repeat in most events
+#[derive(Default)]
+struct Names {
+    utc_offsets: HashMap<study_group::EntityId, UtcOffset>,
+    study_groups: HashMap<study_group::EntityId, String>,
+    teachers: HashMap<teacher::EntityId, Option<String>>,
+    disciplines: HashMap<curriculum_module::EntityId, String>,
+    rooms: HashMap<room::EntityId, Option<String>>,
+}
+
+fn local(date: Date, time: Time, utc_offset: UtcOffset) -> OffsetDateTime {
+    PrimitiveDateTime::new(date, time).assume_offset(utc_offset)
+}
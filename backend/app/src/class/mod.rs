@@ -1,6 +1,6 @@
 use utils::entity::entity;
 
-use crate::{class_kind, curriculum_module};
+use crate::{attestation, class_kind, curriculum_module};
 
 mod repo;
 
@@ -8,9 +8,11 @@ pub use repo::Repo;
 pub type BoxedRepo = Box<dyn Repo + Send + Sync>;
 
 #[entity]
+#[derive(Debug, Clone)]
 pub struct Entity {
     #[id]
     pub id: i32,
     pub curriculum_module_id: curriculum_module::EntityId,
     pub kind_name: class_kind::EntityId,
+    pub duration: attestation::Hours,
 }
@@ -1,4 +1,6 @@
-use crate::curriculum_module;
+use std::collections::HashSet;
+
+use crate::{class_kind, curriculum_module};
 
 use super::{Entity, EntityId};
 
@@ -14,4 +16,15 @@ pub trait Repo {
         &self,
         curriculum_module_id: curriculum_module::EntityId,
     ) -> Result<Vec<Entity>, anyhow::Error>;
+
+    /// Loads classes of all the given modules in one round-trip
+    async fn list_by_curriculum_modules(
+        &self,
+        curriculum_modules_ids: HashSet<curriculum_module::EntityId>,
+    ) -> Result<Vec<Entity>, anyhow::Error>;
+
+    async fn list_by_kind(
+        &self,
+        kind_name: class_kind::EntityId,
+    ) -> Result<Vec<Entity>, anyhow::Error>;
 }
@@ -5,6 +5,7 @@ pub use repo::Repo;
 pub type BoxedRepo = Box<dyn Repo + Send + Sync>;
 
 #[entity]
+#[derive(Debug, Clone)]
 pub struct Entity {
     #[id]
     pub name: String,
@@ -0,0 +1,90 @@
+use utils::{
+    di::{Module, Provide},
+    entity::Id,
+    outcome::Outcome,
+};
+
+use crate::{
+    access_policy::{AccessException, AccessPolicy, Actor},
+    class, class_kind,
+    grant::Scope,
+    validation::is_valid_name,
+    AdaptersModule, AppModule,
+};
+
+const NAME_MAX_LEN: usize = 256;
+
+pub struct ClassKindService {
+    repo: class_kind::BoxedRepo,
+    class_repo: class::BoxedRepo,
+    policy: AccessPolicy,
+}
+
+impl<A: AdaptersModule> Provide<ClassKindService> for AppModule<A> {
+    fn provide(&self) -> ClassKindService {
+        ClassKindService {
+            repo: self.adapters.resolve(),
+            class_repo: self.adapters.resolve(),
+            policy: self.resolve(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, thiserror::Error)]
+pub enum ClassKindException {
+    #[error(transparent)]
+    AccessException(#[from] AccessException),
+    #[error("class kind not found")]
+    NotFound,
+    #[error("invalid class kind name")]
+    InvalidName,
+    #[error("class kind already exist")]
+    AlreadyExist,
+    #[error("class kind is used by classes")]
+    InUse,
+}
+
+/// Class kinds are a dictionary shared by every university, so only global managers can edit it
+impl ClassKindService {
+    pub async fn create(
+        &mut self,
+        actor: &Actor,
+        name: String,
+    ) -> Outcome<class_kind::Entity, ClassKindException> {
+        self.policy.authorize(actor, Scope::Global).await?;
+
+        if !is_valid_name(&name, NAME_MAX_LEN) {
+            return Outcome::Ex(ClassKindException::InvalidName);
+        }
+
+        if self.repo.find(Id::new(name.clone())).await?.is_some() {
+            return Outcome::Ex(ClassKindException::AlreadyExist);
+        }
+
+        let class_kind = class_kind::Entity {
+            name: Id::new(name),
+        };
+
+        let class_kind = self.repo.save(class_kind).await?;
+        Outcome::Ok(class_kind)
+    }
+
+    pub async fn delete(
+        &mut self,
+        actor: &Actor,
+        name: class_kind::EntityId,
+    ) -> Outcome<class_kind::Entity, ClassKindException> {
+        let Some(class_kind) = self.repo.find(name.clone()).await? else {
+            return Outcome::Ex(ClassKindException::NotFound);
+        };
+
+        self.policy.authorize(actor, Scope::Global).await?;
+
+        if !self.class_repo.list_by_kind(name).await?.is_empty() {
+            return Outcome::Ex(ClassKindException::InUse);
+        }
+
+        self.repo.delete(&class_kind).await?;
+        Outcome::Ok(class_kind)
+    }
+}
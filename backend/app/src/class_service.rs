@@ -0,0 +1,214 @@
+use utils::{
+    di::{Module, Provide},
+    outcome::Outcome,
+};
+
+use crate::{
+    access_policy::{AccessException, AccessPolicy, Actor},
+    attestation::Hours,
+    class, class_kind, curriculum, curriculum_module, discipline,
+    grant::Scope,
+    schedule, teacher, AdaptersModule, AppModule,
+};
+
+pub struct ClassService {
+    repo: class::BoxedRepo,
+    class_kind_repo: class_kind::BoxedRepo,
+    curriculum_module_repo: curriculum_module::BoxedRepo,
+    curriculum_repo: curriculum::BoxedRepo,
+    discipline_repo: discipline::BoxedRepo,
+    teacher_repo: teacher::BoxedRepo,
+    schedule_repo: schedule::BoxedRepo,
+    policy: AccessPolicy,
+}
+
+impl<A: AdaptersModule> Provide<ClassService> for AppModule<A> {
+    fn provide(&self) -> ClassService {
+        ClassService {
+            repo: self.adapters.resolve(),
+            class_kind_repo: self.adapters.resolve(),
+            curriculum_module_repo: self.adapters.resolve(),
+            curriculum_repo: self.adapters.resolve(),
+            discipline_repo: self.adapters.resolve(),
+            teacher_repo: self.adapters.resolve(),
+            schedule_repo: self.adapters.resolve(),
+            policy: self.resolve(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, thiserror::Error)]
+pub enum ClassException {
+    #[error(transparent)]
+    AccessException(#[from] AccessException),
+    #[error("class not found")]
+    NotFound,
+    #[error("curriculum module not found")]
+    CurriculumModuleNotFound,
+    #[error("class kind not found")]
+    ClassKindNotFound,
+    #[error("duration should be a positive number of hours")]
+    InvalidDuration,
+    #[error("class is taught by teachers")]
+    InUse,
+    #[error("class is in the timetable")]
+    Scheduled,
+    #[error("curriculum is not a draft and can't be changed")]
+    CurriculumNotEditable,
+}
+
+pub struct ClassInfo {
+    pub curriculum_module_id: curriculum_module::EntityId,
+    pub kind_name: class_kind::EntityId,
+    pub duration: Hours,
+}
+
+impl ClassInfo {
+    fn into_entity(self, id: class::EntityId) -> class::Entity {
+        class::Entity {
+            id,
+            curriculum_module_id: self.curriculum_module_id,
+            kind_name: self.kind_name,
+            duration: self.duration,
+        }
+    }
+}
+
+impl ClassService {
+    pub async fn create(
+        &mut self,
+        actor: &Actor,
+        info: ClassInfo,
+    ) -> Outcome<class::Entity, ClassException> {
+        let scope = self.module_scope(info.curriculum_module_id).await?;
+        self.policy.authorize(actor, scope).await?;
+        self.ensure_editable(info.curriculum_module_id).await?;
+        self.validate(&info).await?;
+
+        let class = self.repo.save(info.into_entity(Default::default())).await?;
+        Outcome::Ok(class)
+    }
+
+    pub async fn update(
+        &mut self,
+        actor: &Actor,
+        id: class::EntityId,
+        info: ClassInfo,
+    ) -> Outcome<class::Entity, ClassException> {
+        let class = self.get(id).await?;
+
+        let scope = self.module_scope(class.curriculum_module_id).await?;
+        self.policy.authorize(actor, scope).await?;
+        self.ensure_editable(class.curriculum_module_id).await?;
+
+        if class.curriculum_module_id != info.curriculum_module_id {
+            let scope = self.module_scope(info.curriculum_module_id).await?;
+            self.policy.authorize(actor, scope).await?;
+            self.ensure_editable(info.curriculum_module_id).await?;
+        }
+
+        self.validate(&info).await?;
+
+        let class = self.repo.save(info.into_entity(id)).await?;
+        Outcome::Ok(class)
+    }
+
+    pub async fn delete(
+        &mut self,
+        actor: &Actor,
+        id: class::EntityId,
+    ) -> Outcome<class::Entity, ClassException> {
+        let class = self.get(id).await?;
+
+        let scope = self.module_scope(class.curriculum_module_id).await?;
+        self.policy.authorize(actor, scope).await?;
+        self.ensure_editable(class.curriculum_module_id).await?;
+
+        if !self.teacher_repo.list_by_class(id).await?.is_empty() {
+            return Outcome::Ex(ClassException::InUse);
+        }
+
+        if !self.schedule_repo.list_by_class(id).await?.is_empty() {
+            return Outcome::Ex(ClassException::Scheduled);
+        }
+
+        self.repo.delete(&class).await?;
+        Outcome::Ok(class)
+    }
+
+    pub async fn get(&self, id: class::EntityId) -> Outcome<class::Entity, ClassException> {
+        let Some(class) = self.repo.find(id).await? else {
+            return Outcome::Ex(ClassException::NotFound);
+        };
+
+        Outcome::Ok(class)
+    }
+
+    /// Classes are managed by the department that teaches the module discipline
+    async fn module_scope(
+        &self,
+        curriculum_module_id: curriculum_module::EntityId,
+    ) -> Outcome<Scope, ClassException> {
+        let Some(curriculum_module) = self
+            .curriculum_module_repo
+            .find(curriculum_module_id)
+            .await?
+        else {
+            return Outcome::Ex(ClassException::CurriculumModuleNotFound);
+        };
+
+        let Some(discipline) = self
+            .discipline_repo
+            .find(curriculum_module.discipline_id)
+            .await?
+        else {
+            return Outcome::Ex(ClassException::CurriculumModuleNotFound);
+        };
+
+        Outcome::Ok(Scope::Subdivision(discipline.department_id))
+    }
+
+    /// Only modules of drafts can be changed, published versions are taught as they are
+    async fn ensure_editable(
+        &self,
+        curriculum_module_id: curriculum_module::EntityId,
+    ) -> Outcome<(), ClassException> {
+        let Some(curriculum_module) = self
+            .curriculum_module_repo
+            .find(curriculum_module_id)
+            .await?
+        else {
+            return Outcome::Ex(ClassException::CurriculumModuleNotFound);
+        };
+
+        let is_editable = self
+            .curriculum_repo
+            .find(curriculum_module.curriculum_id)
+            .await?
+            .is_some_and(|v| v.is_editable());
+
+        if !is_editable {
+            return Outcome::Ex(ClassException::CurriculumNotEditable);
+        }
+
+        Outcome::Ok(())
+    }
+
+    async fn validate(&self, info: &ClassInfo) -> Outcome<(), ClassException> {
+        let Hours(duration) = info.duration;
+        if duration <= 0 {
+            return Outcome::Ex(ClassException::InvalidDuration);
+        }
+
+        if self
+            .class_kind_repo
+            .find(info.kind_name.clone())
+            .await?
+            .is_none()
+        {
+            return Outcome::Ex(ClassException::ClassKindNotFound);
+        }
+
+        Outcome::Ok(())
+    }
+}
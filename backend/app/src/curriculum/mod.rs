@@ -1,13 +1,63 @@
 use utils::entity::entity;
 
+use crate::{curriculum_module, grant::Scope, subdivision};
+
 mod repo;
 
 pub use repo::Repo;
 pub type BoxedRepo = Box<dyn Repo + Send + Sync>;
 
 #[entity]
+#[derive(Debug, Clone)]
 pub struct Entity {
     #[id]
     pub id: i32,
     pub name: String,
+    /// Versions of a curriculum share its name and are numbered from one
+    pub version: i32,
+    pub status: CurriculumStatus,
+    /// Department authoring the curriculum, `None` for curriculums created before departments were recorded
+    pub department_id: Option<subdivision::EntityId>,
+}
+
+impl Entity {
+    /// Published and archived versions are taught to study groups and stay as they were
+    pub fn is_editable(&self) -> bool {
+        self.status == CurriculumStatus::Draft
+    }
+
+    /// Managers of the department change the curriculum, curriculums without one are left to admins
+    pub fn scope(&self) -> Scope {
+        self.department_id.map_or(Scope::Global, Scope::Subdivision)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CurriculumStatus {
+    Draft,
+    Published,
+    Archived,
+}
+
+/// Bounds of the total hours of classes and attestations in a semester of a curriculum
+#[derive(Debug, Clone, Copy)]
+pub struct SemesterHoursBounds {
+    pub min: i32,
+    pub max: i32,
+}
+
+/// Flaw that keeps a curriculum from being assigned to study groups
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Issue {
+    Empty,
+    MissingAttestation {
+        curriculum_module_id: curriculum_module::EntityId,
+    },
+    MissingSemester {
+        semester: i32,
+    },
+    SemesterHours {
+        semester: i32,
+        hours: i32,
+    },
 }
@@ -1,14 +1,30 @@
+use std::collections::HashSet;
+
+use crate::subdivision;
+
 use super::{Entity, EntityId};
 
 #[async_trait::async_trait]
 pub trait Repo {
     async fn save(&mut self, entity: Entity) -> Result<Entity, anyhow::Error>;
 
+    /// Inserts a new version of the curriculum, `None` if a version with the same name and number exists
+    async fn insert_version(&mut self, entity: Entity) -> Result<Option<Entity>, anyhow::Error>;
+
     async fn delete(&mut self, entity: &Entity) -> Result<(), anyhow::Error>;
 
     async fn find(&self, id: EntityId) -> Result<Option<Entity>, anyhow::Error>;
 
+    /// Loads all entities with the given ids in one round-trip, missing ids are skipped
+    async fn find_many(&self, ids: HashSet<EntityId>) -> Result<Vec<Entity>, anyhow::Error>;
+
     async fn list(&self) -> Result<Vec<Entity>, anyhow::Error>;
 
-    async fn find_by_name(&self, name: String) -> Result<Option<Entity>, anyhow::Error>;
+    /// All versions of the curriculum with the given name
+    async fn list_by_name(&self, name: String) -> Result<Vec<Entity>, anyhow::Error>;
+
+    async fn list_by_department_id(
+        &self,
+        department_id: subdivision::EntityId,
+    ) -> Result<Vec<Entity>, anyhow::Error>;
 }
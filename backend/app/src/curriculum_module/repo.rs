@@ -1,3 +1,5 @@
+use std::collections::HashSet;
+
 use crate::{curriculum, discipline};
 
 use super::{Entity, EntityId};
@@ -19,4 +21,10 @@ pub trait Repo {
         &self,
         curriculum_id: curriculum::EntityId,
     ) -> Result<Vec<Entity>, anyhow::Error>;
+
+    /// Loads modules of all the given curriculums in one round-trip
+    async fn list_by_curriculums(
+        &self,
+        curriculums_ids: HashSet<curriculum::EntityId>,
+    ) -> Result<Vec<Entity>, anyhow::Error>;
 }
@@ -0,0 +1,197 @@
+use utils::{
+    di::{Module, Provide},
+    outcome::Outcome,
+};
+
+use crate::{
+    access_policy::{AccessException, AccessPolicy, Actor},
+    attestation, class, curriculum, curriculum_module, discipline,
+    grant::Scope,
+    AdaptersModule, AppModule,
+};
+
+pub struct CurriculumModuleService {
+    repo: curriculum_module::BoxedRepo,
+    curriculum_repo: curriculum::BoxedRepo,
+    discipline_repo: discipline::BoxedRepo,
+    attestation_repo: attestation::BoxedRepo,
+    class_repo: class::BoxedRepo,
+    policy: AccessPolicy,
+}
+
+impl<A: AdaptersModule> Provide<CurriculumModuleService> for AppModule<A> {
+    fn provide(&self) -> CurriculumModuleService {
+        CurriculumModuleService {
+            repo: self.adapters.resolve(),
+            curriculum_repo: self.adapters.resolve(),
+            discipline_repo: self.adapters.resolve(),
+            attestation_repo: self.adapters.resolve(),
+            class_repo: self.adapters.resolve(),
+            policy: self.resolve(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, thiserror::Error)]
+pub enum CurriculumModuleException {
+    #[error(transparent)]
+    AccessException(#[from] AccessException),
+    #[error("curriculum module not found")]
+    NotFound,
+    #[error("curriculum not found")]
+    CurriculumNotFound,
+    #[error("discipline not found")]
+    DisciplineNotFound,
+    #[error("semester should be a positive number")]
+    InvalidSemester,
+    #[error("curriculum module has attestation or classes")]
+    InUse,
+    #[error("curriculum is not a draft and can't be changed")]
+    CurriculumNotEditable,
+}
+
+pub struct CurriculumModuleInfo {
+    pub curriculum_id: curriculum::EntityId,
+    pub discipline_id: discipline::EntityId,
+    pub semester: i32,
+}
+
+impl CurriculumModuleService {
+    pub async fn create(
+        &mut self,
+        actor: &Actor,
+        info: CurriculumModuleInfo,
+    ) -> Outcome<curriculum_module::Entity, CurriculumModuleException> {
+        let scope = self.discipline_scope(info.discipline_id).await?;
+        self.policy.authorize(actor, scope).await?;
+        self.validate(&info).await?;
+
+        let curriculum_module = curriculum_module::Entity {
+            id: Default::default(),
+            discipline_id: info.discipline_id,
+            curriculum_id: info.curriculum_id,
+            semester: info.semester,
+        };
+
+        let curriculum_module = self.repo.save(curriculum_module).await?;
+        Outcome::Ok(curriculum_module)
+    }
+
+    pub async fn update(
+        &mut self,
+        actor: &Actor,
+        id: curriculum_module::EntityId,
+        info: CurriculumModuleInfo,
+    ) -> Outcome<curriculum_module::Entity, CurriculumModuleException> {
+        let curriculum_module = self.get(id).await?;
+
+        let scope = self
+            .discipline_scope(curriculum_module.discipline_id)
+            .await?;
+        self.policy.authorize(actor, scope).await?;
+        self.ensure_editable(curriculum_module.curriculum_id)
+            .await?;
+
+        if curriculum_module.discipline_id != info.discipline_id {
+            let scope = self.discipline_scope(info.discipline_id).await?;
+            self.policy.authorize(actor, scope).await?;
+        }
+
+        self.validate(&info).await?;
+
+        let curriculum_module = curriculum_module::Entity {
+            id,
+            discipline_id: info.discipline_id,
+            curriculum_id: info.curriculum_id,
+            semester: info.semester,
+        };
+
+        let curriculum_module = self.repo.save(curriculum_module).await?;
+        Outcome::Ok(curriculum_module)
+    }
+
+    pub async fn delete(
+        &mut self,
+        actor: &Actor,
+        id: curriculum_module::EntityId,
+    ) -> Outcome<curriculum_module::Entity, CurriculumModuleException> {
+        let curriculum_module = self.get(id).await?;
+
+        let scope = self
+            .discipline_scope(curriculum_module.discipline_id)
+            .await?;
+        self.policy.authorize(actor, scope).await?;
+        self.ensure_editable(curriculum_module.curriculum_id)
+            .await?;
+
+        let is_in_use = self
+            .attestation_repo
+            .find_by_curriculum_module(id)
+            .await?
+            .is_some()
+            || !self
+                .class_repo
+                .list_by_curriculum_module(id)
+                .await?
+                .is_empty();
+
+        if is_in_use {
+            return Outcome::Ex(CurriculumModuleException::InUse);
+        }
+
+        self.repo.delete(&curriculum_module).await?;
+        Outcome::Ok(curriculum_module)
+    }
+
+    pub async fn get(
+        &self,
+        id: curriculum_module::EntityId,
+    ) -> Outcome<curriculum_module::Entity, CurriculumModuleException> {
+        let Some(curriculum_module) = self.repo.find(id).await? else {
+            return Outcome::Ex(CurriculumModuleException::NotFound);
+        };
+
+        Outcome::Ok(curriculum_module)
+    }
+
+    /// Curriculum modules are managed by the department that teaches the discipline
+    async fn discipline_scope(
+        &self,
+        discipline_id: discipline::EntityId,
+    ) -> Outcome<Scope, CurriculumModuleException> {
+        let Some(discipline) = self.discipline_repo.find(discipline_id).await? else {
+            return Outcome::Ex(CurriculumModuleException::DisciplineNotFound);
+        };
+
+        Outcome::Ok(Scope::Subdivision(discipline.department_id))
+    }
+
+    async fn validate(
+        &self,
+        info: &CurriculumModuleInfo,
+    ) -> Outcome<(), CurriculumModuleException> {
+        if info.semester <= 0 {
+            return Outcome::Ex(CurriculumModuleException::InvalidSemester);
+        }
+
+        self.ensure_editable(info.curriculum_id).await?;
+
+        Outcome::Ok(())
+    }
+
+    /// Only drafts can be changed, published versions are taught as they are
+    async fn ensure_editable(
+        &self,
+        curriculum_id: curriculum::EntityId,
+    ) -> Outcome<(), CurriculumModuleException> {
+        let Some(curriculum) = self.curriculum_repo.find(curriculum_id).await? else {
+            return Outcome::Ex(CurriculumModuleException::CurriculumNotFound);
+        };
+
+        if !curriculum.is_editable() {
+            return Outcome::Ex(CurriculumModuleException::CurriculumNotEditable);
+        }
+
+        Outcome::Ok(())
+    }
+}
@@ -0,0 +1,825 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use utils::{
+    di::{Module, Provide},
+    entity::Id,
+    outcome::Outcome,
+};
+
+use crate::{
+    access_policy::{AccessException, AccessPolicy, Actor},
+    attestation::{self, AttestationKind, Hours},
+    class, class_kind,
+    curriculum::{self, CurriculumStatus, Issue, SemesterHoursBounds},
+    curriculum_module, discipline,
+    grant::Scope,
+    study_group, subdivision, teacher,
+    validation::is_valid_name,
+    AdaptersModule, AppModule,
+};
+
+const NAME_MAX_LEN: usize = 256;
+
+pub struct CurriculumService {
+    repo: curriculum::BoxedRepo,
+    curriculum_module_repo: curriculum_module::BoxedRepo,
+    study_group_repo: study_group::BoxedRepo,
+    discipline_repo: discipline::BoxedRepo,
+    class_repo: class::BoxedRepo,
+    class_kind_repo: class_kind::BoxedRepo,
+    attestation_repo: attestation::BoxedRepo,
+    teacher_repo: teacher::BoxedRepo,
+    subdivision_repo: subdivision::BoxedRepo,
+    semester_hours_bounds: SemesterHoursBounds,
+    policy: AccessPolicy,
+}
+
+impl<A: AdaptersModule> Provide<CurriculumService> for AppModule<A> {
+    fn provide(&self) -> CurriculumService {
+        CurriculumService {
+            repo: self.adapters.resolve(),
+            curriculum_module_repo: self.adapters.resolve(),
+            study_group_repo: self.adapters.resolve(),
+            discipline_repo: self.adapters.resolve(),
+            class_repo: self.adapters.resolve(),
+            class_kind_repo: self.adapters.resolve(),
+            attestation_repo: self.adapters.resolve(),
+            teacher_repo: self.adapters.resolve(),
+            subdivision_repo: self.adapters.resolve(),
+            semester_hours_bounds: self.adapters.resolve(),
+            policy: self.resolve(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, thiserror::Error)]
+pub enum CurriculumException {
+    #[error(transparent)]
+    AccessException(#[from] AccessException),
+    #[error("curriculum not found")]
+    NotFound,
+    #[error("invalid curriculum name")]
+    InvalidName,
+    #[error("curriculum already exist")]
+    AlreadyExist,
+    #[error("curriculum has modules or study groups")]
+    InUse,
+    #[error("department not found")]
+    DepartmentNotFound,
+    #[error("discipline not found")]
+    DisciplineNotFound,
+    #[error("semester should be a positive number")]
+    InvalidSemester,
+    #[error("class kind not found")]
+    ClassKindNotFound,
+    #[error("duration should be a positive number of hours")]
+    InvalidDuration,
+    #[error("examiner not found")]
+    ExaminerNotFound,
+    #[error("curriculum is not a draft, clone it as a new version to change it")]
+    NotEditable,
+    #[error("only drafts can be published and only published curriculums can be archived")]
+    InvalidStatusChange,
+    #[error("curriculum has issues and can't be published")]
+    HasIssues,
+}
+
+pub struct CurriculumInfo {
+    pub name: String,
+    pub department_id: subdivision::EntityId,
+}
+
+/// Curriculum modules grouped by semester along with the issues of the curriculum
+pub struct Outline {
+    pub curriculum: curriculum::Entity,
+    pub semesters: Vec<OutlineSemester>,
+    pub issues: Vec<Issue>,
+}
+
+pub struct OutlineSemester {
+    pub semester: i32,
+    pub modules: Vec<OutlineModule>,
+    pub hours: i32,
+}
+
+pub struct OutlineModule {
+    pub curriculum_module: curriculum_module::Entity,
+    pub classes: Vec<class::Entity>,
+    pub attestation: Option<attestation::Entity>,
+    /// Hours of all module classes and of the attestation
+    pub hours: i32,
+}
+
+/// Discipline studied in a semester with its classes and attestation
+pub struct ModuleDraft {
+    pub discipline_id: discipline::EntityId,
+    pub semester: i32,
+    pub classes: Vec<ClassDraft>,
+    pub attestation: Option<AttestationDraft>,
+}
+
+pub struct ClassDraft {
+    pub kind_name: class_kind::EntityId,
+    pub duration: Hours,
+}
+
+pub struct AttestationDraft {
+    pub kind: AttestationKind,
+    pub duration: Hours,
+    pub examiners: HashSet<teacher::EntityId>,
+}
+
+/// Module that differs between two versions of a curriculum, modules are matched by discipline and semester
+pub struct ModuleDiff {
+    pub discipline_id: discipline::EntityId,
+    pub semester: i32,
+    /// `None` when the module was added in the other version
+    pub before: Option<ModuleSummary>,
+    /// `None` when the module was removed in the other version
+    pub after: Option<ModuleSummary>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ModuleSummary {
+    pub hours: i32,
+    pub by_class_kind: Vec<(class_kind::EntityId, i32)>,
+    pub attestation: Option<AttestationKind>,
+}
+
+impl CurriculumService {
+    pub async fn create(
+        &mut self,
+        actor: &Actor,
+        info: CurriculumInfo,
+    ) -> Outcome<curriculum::Entity, CurriculumException> {
+        self.policy
+            .authorize(actor, Scope::Subdivision(info.department_id))
+            .await?;
+        self.validate(None, &info).await?;
+
+        let curriculum = curriculum::Entity {
+            id: Default::default(),
+            name: info.name,
+            version: 1,
+            status: CurriculumStatus::Draft,
+            department_id: Some(info.department_id),
+        };
+
+        let curriculum = self.repo.save(curriculum).await?;
+        Outcome::Ok(curriculum)
+    }
+
+    pub async fn update(
+        &mut self,
+        actor: &Actor,
+        id: curriculum::EntityId,
+        info: CurriculumInfo,
+    ) -> Outcome<curriculum::Entity, CurriculumException> {
+        let curriculum = self.get_editable(id).await?;
+
+        self.policy.authorize(actor, curriculum.scope()).await?;
+
+        if curriculum.department_id != Some(info.department_id) {
+            self.policy
+                .authorize(actor, Scope::Subdivision(info.department_id))
+                .await?;
+        }
+
+        self.validate(Some(&curriculum), &info).await?;
+
+        let curriculum = self
+            .repo
+            .save(curriculum::Entity {
+                name: info.name,
+                department_id: Some(info.department_id),
+                ..curriculum
+            })
+            .await?;
+
+        Outcome::Ok(curriculum)
+    }
+
+    pub async fn delete(
+        &mut self,
+        actor: &Actor,
+        id: curriculum::EntityId,
+    ) -> Outcome<curriculum::Entity, CurriculumException> {
+        let curriculum = self.get(id).await?;
+
+        self.policy.authorize(actor, curriculum.scope()).await?;
+
+        let is_in_use = !self
+            .curriculum_module_repo
+            .list_by_curriculum_id(id)
+            .await?
+            .is_empty()
+            || !self
+                .study_group_repo
+                .list_by_curriculums(HashSet::from([id]))
+                .await?
+                .is_empty();
+
+        if is_in_use {
+            return Outcome::Ex(CurriculumException::InUse);
+        }
+
+        self.repo.delete(&curriculum).await?;
+        Outcome::Ok(curriculum)
+    }
+
+    /// Adds a module with its classes and attestation, everything is checked before saving
+    pub async fn add_module(
+        &mut self,
+        actor: &Actor,
+        id: curriculum::EntityId,
+        draft: ModuleDraft,
+    ) -> Outcome<OutlineModule, CurriculumException> {
+        self.get_editable(id).await?;
+
+        let Some(discipline) = self.discipline_repo.find(draft.discipline_id).await? else {
+            return Outcome::Ex(CurriculumException::DisciplineNotFound);
+        };
+
+        self.policy
+            .authorize(actor, Scope::Subdivision(discipline.department_id))
+            .await?;
+        self.validate_draft(&draft).await?;
+
+        let curriculum_module = self
+            .curriculum_module_repo
+            .save(curriculum_module::Entity {
+                id: Default::default(),
+                discipline_id: draft.discipline_id,
+                curriculum_id: id,
+                semester: draft.semester,
+            })
+            .await?;
+
+        let mut classes = vec![];
+        for class in draft.classes {
+            classes.push(
+                self.class_repo
+                    .save(class::Entity {
+                        id: Default::default(),
+                        curriculum_module_id: curriculum_module.id,
+                        kind_name: class.kind_name,
+                        duration: class.duration,
+                    })
+                    .await?,
+            );
+        }
+
+        let mut attestation = None;
+        if let Some(draft) = draft.attestation {
+            attestation = Some(
+                self.attestation_repo
+                    .save(attestation::Entity {
+                        id: Default::default(),
+                        curriculum_module_id: curriculum_module.id,
+                        kind: draft.kind,
+                        duration: draft.duration,
+                        examiners: draft.examiners,
+                    })
+                    .await?,
+            );
+        }
+
+        Outcome::Ok(OutlineModule::new(curriculum_module, classes, attestation))
+    }
+
+    /// Freezes a draft without issues so it can be assigned to study groups
+    pub async fn publish(
+        &mut self,
+        actor: &Actor,
+        id: curriculum::EntityId,
+    ) -> Outcome<curriculum::Entity, CurriculumException> {
+        let curriculum = self.get(id).await?;
+
+        self.policy.authorize(actor, curriculum.scope()).await?;
+
+        if curriculum.status != CurriculumStatus::Draft {
+            return Outcome::Ex(CurriculumException::InvalidStatusChange);
+        }
+
+        if !self.issues(id).await?.is_empty() {
+            return Outcome::Ex(CurriculumException::HasIssues);
+        }
+
+        let curriculum = self
+            .repo
+            .save(curriculum::Entity {
+                status: CurriculumStatus::Published,
+                ..curriculum
+            })
+            .await?;
+
+        Outcome::Ok(curriculum)
+    }
+
+    /// Archived curriculums stay with the study groups they are assigned to but can't be assigned to new ones
+    pub async fn archive(
+        &mut self,
+        actor: &Actor,
+        id: curriculum::EntityId,
+    ) -> Outcome<curriculum::Entity, CurriculumException> {
+        let curriculum = self.get(id).await?;
+
+        self.policy.authorize(actor, curriculum.scope()).await?;
+
+        if curriculum.status != CurriculumStatus::Published {
+            return Outcome::Ex(CurriculumException::InvalidStatusChange);
+        }
+
+        let curriculum = self
+            .repo
+            .save(curriculum::Entity {
+                status: CurriculumStatus::Archived,
+                ..curriculum
+            })
+            .await?;
+
+        Outcome::Ok(curriculum)
+    }
+
+    /// Copies modules, classes and attestations of the curriculum into a draft of the next version
+    pub async fn clone_version(
+        &mut self,
+        actor: &Actor,
+        id: curriculum::EntityId,
+    ) -> Outcome<curriculum::Entity, CurriculumException> {
+        let curriculum = self.get(id).await?;
+
+        self.policy.authorize(actor, curriculum.scope()).await?;
+
+        let last_version = self
+            .repo
+            .list_by_name(curriculum.name.clone())
+            .await?
+            .into_iter()
+            .map(|v| v.version)
+            .max()
+            .unwrap_or(curriculum.version);
+
+        // A concurrent clone may take the same version number first
+        let Some(copy) = self
+            .repo
+            .insert_version(curriculum::Entity {
+                id: Default::default(),
+                name: curriculum.name,
+                version: last_version + 1,
+                status: CurriculumStatus::Draft,
+                department_id: curriculum.department_id,
+            })
+            .await?
+        else {
+            return Outcome::Ex(CurriculumException::AlreadyExist);
+        };
+
+        let mut modules = self
+            .curriculum_module_repo
+            .list_by_curriculum_id(id)
+            .await?;
+        modules.sort_by_key(|v| v.id.value);
+
+        for curriculum_module in modules {
+            let module_copy = self
+                .curriculum_module_repo
+                .save(curriculum_module::Entity {
+                    id: Default::default(),
+                    curriculum_id: copy.id,
+                    ..curriculum_module.clone()
+                })
+                .await?;
+
+            let mut classes = self
+                .class_repo
+                .list_by_curriculum_module(curriculum_module.id)
+                .await?;
+            classes.sort_by_key(|v| v.id.value);
+
+            for class in classes {
+                self.class_repo
+                    .save(class::Entity {
+                        id: Default::default(),
+                        curriculum_module_id: module_copy.id,
+                        ..class
+                    })
+                    .await?;
+            }
+
+            if let Some(attestation) = self
+                .attestation_repo
+                .find_by_curriculum_module(curriculum_module.id)
+                .await?
+            {
+                self.attestation_repo
+                    .save(attestation::Entity {
+                        id: Default::default(),
+                        curriculum_module_id: module_copy.id,
+                        ..attestation
+                    })
+                    .await?;
+            }
+        }
+
+        Outcome::Ok(copy)
+    }
+
+    /// All versions of the curriculum from the first one, readable like the curriculum itself
+    pub async fn versions(
+        &self,
+        id: curriculum::EntityId,
+    ) -> Outcome<Vec<curriculum::Entity>, CurriculumException> {
+        let curriculum = self.get(id).await?;
+
+        let mut versions = self.repo.list_by_name(curriculum.name).await?;
+        versions.sort_by_key(|v| v.version);
+
+        Outcome::Ok(versions)
+    }
+
+    /// Modules whose hours or attestation kind differ between the curriculums
+    pub async fn diff(
+        &self,
+        id: curriculum::EntityId,
+        other_id: curriculum::EntityId,
+    ) -> Outcome<Vec<ModuleDiff>, CurriculumException> {
+        let mut before = summaries(self.outline(id).await?);
+        let mut after = summaries(self.outline(other_id).await?);
+
+        let keys = before
+            .keys()
+            .chain(after.keys())
+            .copied()
+            .collect::<HashSet<_>>();
+        let mut keys = keys.into_iter().collect::<Vec<_>>();
+        keys.sort();
+
+        let diff = keys
+            .into_iter()
+            .filter_map(|key| {
+                let before = before.remove(&key);
+                let after = after.remove(&key);
+                let (discipline_id, semester) = key;
+
+                (before != after).then(|| ModuleDiff {
+                    discipline_id: Id::new(discipline_id),
+                    semester,
+                    before,
+                    after,
+                })
+            })
+            .collect();
+
+        Outcome::Ok(diff)
+    }
+
+    pub async fn outline(&self, id: curriculum::EntityId) -> Outcome<Outline, CurriculumException> {
+        let curriculum = self.get(id).await?;
+
+        let mut semesters = BTreeMap::<i32, Vec<OutlineModule>>::new();
+        for curriculum_module in self
+            .curriculum_module_repo
+            .list_by_curriculum_id(id)
+            .await?
+        {
+            let mut classes = self
+                .class_repo
+                .list_by_curriculum_module(curriculum_module.id)
+                .await?;
+            classes.sort_by_key(|v| v.id.value);
+
+            let attestation = self
+                .attestation_repo
+                .find_by_curriculum_module(curriculum_module.id)
+                .await?;
+
+            semesters
+                .entry(curriculum_module.semester)
+                .or_default()
+                .push(OutlineModule::new(curriculum_module, classes, attestation));
+        }
+
+        let semesters = semesters
+            .into_iter()
+            .map(|(semester, mut modules)| {
+                modules.sort_by_key(|v| v.curriculum_module.id.value);
+
+                OutlineSemester {
+                    semester,
+                    hours: modules.iter().map(|v| v.hours).sum(),
+                    modules,
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let issues = self.issues_of(&semesters);
+        Outcome::Ok(Outline {
+            curriculum,
+            semesters,
+            issues,
+        })
+    }
+
+    /// Issues of the curriculum, only curriculums without issues can be assigned to study groups
+    pub async fn issues(
+        &self,
+        id: curriculum::EntityId,
+    ) -> Outcome<Vec<Issue>, CurriculumException> {
+        let outline = self.outline(id).await?;
+        Outcome::Ok(outline.issues)
+    }
+
+    pub async fn get(
+        &self,
+        id: curriculum::EntityId,
+    ) -> Outcome<curriculum::Entity, CurriculumException> {
+        let Some(curriculum) = self.repo.find(id).await? else {
+            return Outcome::Ex(CurriculumException::NotFound);
+        };
+
+        Outcome::Ok(curriculum)
+    }
+
+    async fn get_editable(
+        &self,
+        id: curriculum::EntityId,
+    ) -> Outcome<curriculum::Entity, CurriculumException> {
+        let curriculum = self.get(id).await?;
+
+        if !curriculum.is_editable() {
+            return Outcome::Ex(CurriculumException::NotEditable);
+        }
+
+        Outcome::Ok(curriculum)
+    }
+
+    async fn validate_draft(&self, draft: &ModuleDraft) -> Outcome<(), CurriculumException> {
+        if draft.semester <= 0 {
+            return Outcome::Ex(CurriculumException::InvalidSemester);
+        }
+
+        for class in draft.classes.iter() {
+            if class.duration.0 <= 0 {
+                return Outcome::Ex(CurriculumException::InvalidDuration);
+            }
+
+            if self
+                .class_kind_repo
+                .find(class.kind_name.clone())
+                .await?
+                .is_none()
+            {
+                return Outcome::Ex(CurriculumException::ClassKindNotFound);
+            }
+        }
+
+        if let Some(attestation) = &draft.attestation {
+            if attestation.duration.0 <= 0 {
+                return Outcome::Ex(CurriculumException::InvalidDuration);
+            }
+
+            for examiner_id in attestation.examiners.iter() {
+                if self.teacher_repo.find(*examiner_id).await?.is_none() {
+                    return Outcome::Ex(CurriculumException::ExaminerNotFound);
+                }
+            }
+        }
+
+        Outcome::Ok(())
+    }
+
+    /// Semesters should follow each other from the first one and every module needs an attestation
+    fn issues_of(&self, semesters: &[OutlineSemester]) -> Vec<Issue> {
+        if semesters.is_empty() {
+            return vec![Issue::Empty];
+        }
+
+        let mut issues = vec![];
+        let last = semesters
+            .iter()
+            .map(|v| v.semester)
+            .max()
+            .unwrap_or_default();
+        for semester in 1..=last {
+            if !semesters.iter().any(|v| v.semester == semester) {
+                issues.push(Issue::MissingSemester { semester });
+            }
+        }
+
+        let SemesterHoursBounds { min, max } = self.semester_hours_bounds;
+        for semester in semesters {
+            if semester.hours < min || semester.hours > max {
+                issues.push(Issue::SemesterHours {
+                    semester: semester.semester,
+                    hours: semester.hours,
+                });
+            }
+
+            for module in semester.modules.iter() {
+                if module.attestation.is_none() {
+                    issues.push(Issue::MissingAttestation {
+                        curriculum_module_id: module.curriculum_module.id,
+                    });
+                }
+            }
+        }
+
+        issues
+    }
+
+    /// New curriculums need an unused name, a renamed version must not clash with a version of that name
+    async fn validate(
+        &self,
+        curriculum: Option<&curriculum::Entity>,
+        info: &CurriculumInfo,
+    ) -> Outcome<(), CurriculumException> {
+        if !is_valid_name(&info.name, NAME_MAX_LEN) {
+            return Outcome::Ex(CurriculumException::InvalidName);
+        }
+
+        if self
+            .subdivision_repo
+            .find(info.department_id)
+            .await?
+            .is_none()
+        {
+            return Outcome::Ex(CurriculumException::DepartmentNotFound);
+        }
+
+        let versions = self.repo.list_by_name(info.name.clone()).await?;
+        let is_taken = match curriculum {
+            Some(curriculum) => versions
+                .iter()
+                .any(|v| v.id != curriculum.id && v.version == curriculum.version),
+            None => !versions.is_empty(),
+        };
+
+        if is_taken {
+            return Outcome::Ex(CurriculumException::AlreadyExist);
+        }
+
+        Outcome::Ok(())
+    }
+}
+
+impl OutlineModule {
+    fn new(
+        curriculum_module: curriculum_module::Entity,
+        classes: Vec<class::Entity>,
+        attestation: Option<attestation::Entity>,
+    ) -> Self {
+        let hours = classes.iter().map(|v| v.duration.0).sum::<i32>()
+            + attestation
+                .as_ref()
+                .map(|v| v.duration.0)
+                .unwrap_or_default();
+
+        Self {
+            curriculum_module,
+            classes,
+            attestation,
+            hours,
+        }
+    }
+}
+
+/// Sums up modules of the outline by discipline and semester
+fn summaries(outline: Outline) -> HashMap<(i32, i32), ModuleSummary> {
+    let mut hours = HashMap::<(i32, i32), (i32, HashMap<class_kind::EntityId, i32>)>::new();
+    let mut attestations = HashMap::new();
+    for module in outline.semesters.into_iter().flat_map(|v| v.modules) {
+        let key = (
+            module.curriculum_module.discipline_id.value,
+            module.curriculum_module.semester,
+        );
+
+        let (total, by_class_kind) = hours.entry(key).or_default();
+        *total += module.hours;
+        for class in module.classes {
+            *by_class_kind.entry(class.kind_name).or_default() += class.duration.0;
+        }
+
+        if let Some(attestation) = module.attestation {
+            attestations.insert(key, attestation.kind);
+        }
+    }
+
+    hours
+        .into_iter()
+        .map(|(key, (total, by_class_kind))| {
+            let mut by_class_kind = by_class_kind.into_iter().collect::<Vec<_>>();
+            by_class_kind.sort_by(|(a, _), (b, _)| a.value.cmp(&b.value));
+
+            let summary = ModuleSummary {
+                hours: total,
+                by_class_kind,
+                attestation: attestations.remove(&key),
+            };
+
+            (key, summary)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn class(kind: &str, hours: i32) -> class::Entity {
+        class::Entity {
+            id: Default::default(),
+            curriculum_module_id: Default::default(),
+            kind_name: Id::new(kind.to_owned()),
+            duration: Hours(hours),
+        }
+    }
+
+    fn module(
+        discipline_id: i32,
+        semester: i32,
+        classes: Vec<class::Entity>,
+        attestation: Option<AttestationKind>,
+    ) -> OutlineModule {
+        OutlineModule::new(
+            curriculum_module::Entity {
+                id: Default::default(),
+                discipline_id: Id::new(discipline_id),
+                curriculum_id: Default::default(),
+                semester,
+            },
+            classes,
+            attestation.map(|kind| attestation::Entity {
+                id: Default::default(),
+                curriculum_module_id: Default::default(),
+                kind,
+                duration: Hours(4),
+                examiners: HashSet::new(),
+            }),
+        )
+    }
+
+    fn outline(modules: Vec<OutlineModule>) -> Outline {
+        Outline {
+            curriculum: curriculum::Entity {
+                id: Default::default(),
+                name: "avt-113 2021".to_owned(),
+                version: 1,
+                status: CurriculumStatus::Draft,
+                department_id: None,
+            },
+            semesters: vec![OutlineSemester {
+                semester: 1,
+                hours: modules.iter().map(|v| v.hours).sum(),
+                modules,
+            }],
+            issues: vec![],
+        }
+    }
+
+    #[test]
+    fn module_hours_include_the_attestation() {
+        let module = module(
+            1,
+            1,
+            vec![class("lecture", 16), class("lab", 16)],
+            Some(AttestationKind::Exam),
+        );
+
+        assert_eq!(module.hours, 36);
+    }
+
+    #[test]
+    fn summaries_sum_hours_by_class_kind() {
+        let summaries = summaries(outline(vec![
+            module(
+                1,
+                1,
+                vec![class("lecture", 16), class("lab", 16), class("lecture", 8)],
+                Some(AttestationKind::Exam),
+            ),
+            module(2, 2, vec![class("practice", 32)], None),
+        ]));
+
+        assert_eq!(summaries.len(), 2);
+        assert_eq!(
+            summaries[&(1, 1)],
+            ModuleSummary {
+                hours: 44,
+                by_class_kind: vec![
+                    (Id::new("lab".to_owned()), 16),
+                    (Id::new("lecture".to_owned()), 24),
+                ],
+                attestation: Some(AttestationKind::Exam),
+            }
+        );
+        assert_eq!(
+            summaries[&(2, 2)],
+            ModuleSummary {
+                hours: 32,
+                by_class_kind: vec![(Id::new("practice".to_owned()), 32)],
+                attestation: None,
+            }
+        );
+    }
+}
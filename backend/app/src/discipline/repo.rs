@@ -1,3 +1,5 @@
+use std::collections::HashSet;
+
 use crate::subdivision;
 
 use super::{Entity, EntityId};
@@ -10,6 +12,9 @@ pub trait Repo {
 
     async fn find(&self, id: EntityId) -> Result<Option<Entity>, anyhow::Error>;
 
+    /// Loads all entities with the given ids in one round-trip, missing ids are skipped
+    async fn find_many(&self, ids: HashSet<EntityId>) -> Result<Vec<Entity>, anyhow::Error>;
+
     async fn find_by_name(&self, name: String) -> Result<Option<Entity>, anyhow::Error>;
 
     async fn list_by_department_id(
@@ -0,0 +1,157 @@
+use utils::{
+    di::{Module, Provide},
+    outcome::Outcome,
+};
+
+use crate::{
+    access_policy::{AccessException, AccessPolicy, Actor},
+    curriculum_module, discipline,
+    grant::Scope,
+    subdivision,
+    validation::is_valid_name,
+    AdaptersModule, AppModule,
+};
+
+const NAME_MAX_LEN: usize = 256;
+
+pub struct DisciplineService {
+    repo: discipline::BoxedRepo,
+    subdivision_repo: subdivision::BoxedRepo,
+    curriculum_module_repo: curriculum_module::BoxedRepo,
+    policy: AccessPolicy,
+}
+
+impl<A: AdaptersModule> Provide<DisciplineService> for AppModule<A> {
+    fn provide(&self) -> DisciplineService {
+        DisciplineService {
+            repo: self.adapters.resolve(),
+            subdivision_repo: self.adapters.resolve(),
+            curriculum_module_repo: self.adapters.resolve(),
+            policy: self.resolve(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, thiserror::Error)]
+pub enum DisciplineException {
+    #[error(transparent)]
+    AccessException(#[from] AccessException),
+    #[error("discipline not found")]
+    NotFound,
+    #[error("invalid discipline name")]
+    InvalidName,
+    #[error("department not found")]
+    DepartmentNotFound,
+    #[error("discipline is used in curriculums")]
+    InUse,
+}
+
+pub struct DisciplineInfo {
+    pub name: String,
+    pub department_id: subdivision::EntityId,
+}
+
+impl DisciplineService {
+    pub async fn create(
+        &mut self,
+        actor: &Actor,
+        info: DisciplineInfo,
+    ) -> Outcome<discipline::Entity, DisciplineException> {
+        self.policy
+            .authorize(actor, Scope::Subdivision(info.department_id))
+            .await?;
+        self.validate(&info).await?;
+
+        let discipline = discipline::Entity {
+            id: Default::default(),
+            name: info.name,
+            department_id: info.department_id,
+        };
+
+        let discipline = self.repo.save(discipline).await?;
+        Outcome::Ok(discipline)
+    }
+
+    pub async fn update(
+        &mut self,
+        actor: &Actor,
+        id: discipline::EntityId,
+        info: DisciplineInfo,
+    ) -> Outcome<discipline::Entity, DisciplineException> {
+        let discipline = self.get(id).await?;
+
+        self.policy
+            .authorize(actor, Scope::Subdivision(discipline.department_id))
+            .await?;
+
+        if discipline.department_id != info.department_id {
+            self.policy
+                .authorize(actor, Scope::Subdivision(info.department_id))
+                .await?;
+        }
+
+        self.validate(&info).await?;
+
+        let discipline = discipline::Entity {
+            id,
+            name: info.name,
+            department_id: info.department_id,
+        };
+
+        let discipline = self.repo.save(discipline).await?;
+        Outcome::Ok(discipline)
+    }
+
+    pub async fn delete(
+        &mut self,
+        actor: &Actor,
+        id: discipline::EntityId,
+    ) -> Outcome<discipline::Entity, DisciplineException> {
+        let discipline = self.get(id).await?;
+
+        self.policy
+            .authorize(actor, Scope::Subdivision(discipline.department_id))
+            .await?;
+
+        let is_in_use = !self
+            .curriculum_module_repo
+            .list_by_discipline_id(id)
+            .await?
+            .is_empty();
+
+        if is_in_use {
+            return Outcome::Ex(DisciplineException::InUse);
+        }
+
+        self.repo.delete(&discipline).await?;
+        Outcome::Ok(discipline)
+    }
+
+    pub async fn get(
+        &self,
+        id: discipline::EntityId,
+    ) -> Outcome<discipline::Entity, DisciplineException> {
+        let Some(discipline) = self.repo.find(id).await? else {
+            return Outcome::Ex(DisciplineException::NotFound);
+        };
+
+        Outcome::Ok(discipline)
+    }
+
+    async fn validate(&self, info: &DisciplineInfo) -> Outcome<(), DisciplineException> {
+        if !is_valid_name(&info.name, NAME_MAX_LEN) {
+            return Outcome::Ex(DisciplineException::InvalidName);
+        }
+
+        if self
+            .subdivision_repo
+            .find(info.department_id)
+            .await?
+            .is_none()
+        {
+            return Outcome::Ex(DisciplineException::DepartmentNotFound);
+        }
+
+        Outcome::Ok(())
+    }
+}
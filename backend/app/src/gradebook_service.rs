@@ -0,0 +1,385 @@
+use std::{
+    collections::{HashMap, HashSet},
+    ops::RangeInclusive,
+};
+
+use utils::{
+    di::{Module, Provide},
+    outcome::Outcome,
+};
+
+use crate::{
+    access_policy::{AccessException, AccessPolicy, Actor},
+    attestation, curriculum_module,
+    grading_scale::{Grade, Scale},
+    grading_scale_service::GradingScaleService,
+    grant::Scope,
+    person,
+    student::{self, StudentAttestation},
+    study_group, teacher, AdaptersModule, AppModule,
+};
+
+/// Same bounds as the `students_attestations_score_check` constraint
+const SCORE_RANGE: RangeInclusive<i32> = 0..=100;
+
+pub struct GradebookService {
+    student_repo: student::BoxedRepo,
+    attestation_repo: attestation::BoxedRepo,
+    curriculum_module_repo: curriculum_module::BoxedRepo,
+    study_group_repo: study_group::BoxedRepo,
+    person_repo: person::BoxedRepo,
+    teacher_repo: teacher::BoxedRepo,
+    grading_scale_service: GradingScaleService,
+    policy: AccessPolicy,
+}
+
+impl<A: AdaptersModule> Provide<GradebookService> for AppModule<A> {
+    fn provide(&self) -> GradebookService {
+        GradebookService {
+            student_repo: self.adapters.resolve(),
+            attestation_repo: self.adapters.resolve(),
+            curriculum_module_repo: self.adapters.resolve(),
+            study_group_repo: self.adapters.resolve(),
+            person_repo: self.adapters.resolve(),
+            teacher_repo: self.adapters.resolve(),
+            grading_scale_service: self.resolve(),
+            policy: self.resolve(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, thiserror::Error)]
+pub enum GradebookException {
+    #[error(transparent)]
+    AccessException(#[from] AccessException),
+    #[error("attestation not found")]
+    AttestationNotFound,
+    #[error("student not found")]
+    StudentNotFound,
+    #[error("study group not found")]
+    StudyGroupNotFound,
+    #[error("score should be between 0 and 100")]
+    InvalidScore,
+    #[error("semester should be a positive number")]
+    InvalidSemester,
+    #[error("attestation is not a part of the student curriculum")]
+    NotInCurriculum,
+    #[error("student already has a score for the attestation")]
+    AlreadyGraded,
+    #[error("student has no score for the attestation")]
+    NotGraded,
+    #[error("score is finalized")]
+    Finalized,
+}
+
+/// Scores of one study group for the attestations of one semester
+pub struct Gradebook {
+    pub study_group: study_group::Entity,
+    pub semester: i32,
+    /// Scale of the university the study group belongs to
+    pub scale: Scale,
+    pub attestations: Vec<attestation::Entity>,
+    pub rows: Vec<GradebookRow>,
+}
+
+pub struct GradebookRow {
+    pub student: student::Entity,
+    pub person: person::Entity,
+    /// Scores for the gradebook attestations only, ungraded ones are missing
+    pub scores: Vec<GradedScore>,
+}
+
+pub struct GradedScore {
+    pub score: StudentAttestation,
+    pub grade: Grade,
+}
+
+impl GradebookService {
+    pub async fn submit(
+        &mut self,
+        actor: &Actor,
+        attestation_id: attestation::EntityId,
+        student_id: student::EntityId,
+        score: i32,
+    ) -> Outcome<StudentAttestation, GradebookException> {
+        let (attestation, mut student) = self.load(attestation_id, student_id).await?;
+        self.authorize_examiner(actor, &attestation).await?;
+        self.validate(&attestation, &student, score).await?;
+
+        if Self::find_score(&student, attestation_id).is_some() {
+            return Outcome::Ex(GradebookException::AlreadyGraded);
+        }
+
+        let score = StudentAttestation {
+            attestation_id,
+            score,
+            is_final: false,
+        };
+
+        student.attestations.insert(score.clone());
+        self.student_repo.save(student).await?;
+
+        Outcome::Ok(score)
+    }
+
+    pub async fn amend(
+        &mut self,
+        actor: &Actor,
+        attestation_id: attestation::EntityId,
+        student_id: student::EntityId,
+        score: i32,
+    ) -> Outcome<StudentAttestation, GradebookException> {
+        let (attestation, student) = self.load(attestation_id, student_id).await?;
+        self.authorize_examiner(actor, &attestation).await?;
+        self.validate(&attestation, &student, score).await?;
+
+        let score = StudentAttestation {
+            attestation_id,
+            score,
+            is_final: false,
+        };
+
+        self.replace_score(student, score).await
+    }
+
+    /// Locks the score, it can't be amended afterwards
+    pub async fn finalize(
+        &mut self,
+        actor: &Actor,
+        attestation_id: attestation::EntityId,
+        student_id: student::EntityId,
+    ) -> Outcome<StudentAttestation, GradebookException> {
+        let (attestation, student) = self.load(attestation_id, student_id).await?;
+        self.authorize_examiner(actor, &attestation).await?;
+
+        let Some(score) = Self::find_score(&student, attestation_id) else {
+            return Outcome::Ex(GradebookException::NotGraded);
+        };
+
+        let score = StudentAttestation {
+            is_final: true,
+            ..score.clone()
+        };
+
+        self.replace_score(student, score).await
+    }
+
+    /// Visible to the study group managers and to the examiners of the semester attestations
+    pub async fn get(
+        &self,
+        actor: &Actor,
+        study_group_id: study_group::EntityId,
+        semester: i32,
+    ) -> Outcome<Gradebook, GradebookException> {
+        if semester <= 0 {
+            return Outcome::Ex(GradebookException::InvalidSemester);
+        }
+
+        let Some(study_group) = self.study_group_repo.find(study_group_id).await? else {
+            return Outcome::Ex(GradebookException::StudyGroupNotFound);
+        };
+
+        let mut modules_ids = HashSet::new();
+        for curriculum_id in study_group.curriculums.iter() {
+            let modules = self
+                .curriculum_module_repo
+                .list_by_curriculum_id(*curriculum_id)
+                .await?;
+
+            modules_ids.extend(
+                modules
+                    .into_iter()
+                    .filter(|v| v.semester == semester)
+                    .map(|v| v.id),
+            );
+        }
+
+        let attestations = self
+            .attestation_repo
+            .list_by_curriculum_modules(modules_ids)
+            .await?;
+
+        let is_examiner = match self.find_teacher(actor).await? {
+            Some(teacher) => attestations
+                .iter()
+                .any(|v| v.examiners.contains(&teacher.id)),
+            None => false,
+        };
+
+        if !is_examiner {
+            self.policy
+                .authorize(actor, Scope::StudyGroup(study_group_id))
+                .await?;
+        }
+
+        let students = self
+            .student_repo
+            .list_by_study_group(study_group_id)
+            .await?
+            .into_iter()
+            .filter(|v| v.status.is_enrolled())
+            .collect::<Vec<_>>();
+        let persons = self
+            .person_repo
+            .find_many(students.iter().map(|v| v.person_id).collect())
+            .await?
+            .into_iter()
+            .map(|v| (v.id, v))
+            .collect::<HashMap<_, _>>();
+
+        let scale = self
+            .grading_scale_service
+            .scale_of_subdivision(study_group.department_id)
+            .await?;
+
+        let kinds = attestations
+            .iter()
+            .map(|v| (v.id, &v.kind))
+            .collect::<HashMap<_, _>>();
+
+        let mut rows = students
+            .into_iter()
+            .filter_map(|student| {
+                let person = persons.get(&student.person_id)?.clone();
+                let mut scores = student
+                    .attestations
+                    .iter()
+                    .filter_map(|v| {
+                        let kind = kinds.get(&v.attestation_id)?;
+
+                        Some(GradedScore {
+                            score: v.clone(),
+                            grade: scale.grade(kind, v.score),
+                        })
+                    })
+                    .collect::<Vec<_>>();
+                scores.sort_by_key(|v| v.score.attestation_id.value);
+
+                Some(GradebookRow {
+                    student,
+                    person,
+                    scores,
+                })
+            })
+            .collect::<Vec<_>>();
+
+        rows.sort_by(|a, b| {
+            a.person
+                .full_name
+                .cmp(&b.person.full_name)
+                .then(a.student.id.value.cmp(&b.student.id.value))
+        });
+
+        Outcome::Ok(Gradebook {
+            study_group,
+            semester,
+            scale,
+            attestations,
+            rows,
+        })
+    }
+
+    async fn load(
+        &self,
+        attestation_id: attestation::EntityId,
+        student_id: student::EntityId,
+    ) -> Outcome<(attestation::Entity, student::Entity), GradebookException> {
+        let Some(attestation) = self.attestation_repo.find(attestation_id).await? else {
+            return Outcome::Ex(GradebookException::AttestationNotFound);
+        };
+
+        let Some(student) = self.student_repo.find(student_id).await? else {
+            return Outcome::Ex(GradebookException::StudentNotFound);
+        };
+
+        Outcome::Ok((attestation, student))
+    }
+
+    async fn find_teacher(&self, actor: &Actor) -> Result<Option<teacher::Entity>, anyhow::Error> {
+        let Some(person) = self.person_repo.find_by_user_id(actor.user_id).await? else {
+            return Ok(None);
+        };
+
+        self.teacher_repo.find_by_person_id(person.id).await
+    }
+
+    /// Only the teachers listed as examiners of the attestation can grade it
+    async fn authorize_examiner(
+        &self,
+        actor: &Actor,
+        attestation: &attestation::Entity,
+    ) -> Outcome<(), GradebookException> {
+        let Some(teacher) = self.find_teacher(actor).await? else {
+            return Outcome::Ex(AccessException::NoRights.into());
+        };
+
+        if !attestation.examiners.contains(&teacher.id) {
+            return Outcome::Ex(AccessException::NoRights.into());
+        }
+
+        Outcome::Ok(())
+    }
+
+    async fn validate(
+        &self,
+        attestation: &attestation::Entity,
+        student: &student::Entity,
+        score: i32,
+    ) -> Outcome<(), GradebookException> {
+        if !SCORE_RANGE.contains(&score) {
+            return Outcome::Ex(GradebookException::InvalidScore);
+        }
+
+        let Some(curriculum_module) = self
+            .curriculum_module_repo
+            .find(attestation.curriculum_module_id)
+            .await?
+        else {
+            return Outcome::Ex(GradebookException::NotInCurriculum);
+        };
+
+        let Some(study_group) = self.study_group_repo.find(student.study_group_id).await? else {
+            return Outcome::Ex(GradebookException::StudyGroupNotFound);
+        };
+
+        if !study_group
+            .curriculums
+            .contains(&curriculum_module.curriculum_id)
+        {
+            return Outcome::Ex(GradebookException::NotInCurriculum);
+        }
+
+        Outcome::Ok(())
+    }
+
+    fn find_score(
+        student: &student::Entity,
+        attestation_id: attestation::EntityId,
+    ) -> Option<&StudentAttestation> {
+        student
+            .attestations
+            .iter()
+            .find(|v| v.attestation_id == attestation_id)
+    }
+
+    /// Swaps an existing unlocked score of the student for the new one
+    async fn replace_score(
+        &mut self,
+        mut student: student::Entity,
+        score: StudentAttestation,
+    ) -> Outcome<StudentAttestation, GradebookException> {
+        let Some(current) = Self::find_score(&student, score.attestation_id).cloned() else {
+            return Outcome::Ex(GradebookException::NotGraded);
+        };
+
+        if current.is_final {
+            return Outcome::Ex(GradebookException::Finalized);
+        }
+
+        student.attestations.remove(&current);
+        student.attestations.insert(score.clone());
+        self.student_repo.save(student).await?;
+
+        Outcome::Ok(score)
+    }
+}
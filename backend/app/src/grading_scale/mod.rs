@@ -0,0 +1,177 @@
+mod repo;
+
+use utils::entity::entity;
+
+use crate::{attestation::AttestationKind, university};
+
+pub use repo::Repo;
+pub type BoxedRepo = Box<dyn Repo + Send + Sync>;
+
+/// Grading scale of a university, universities without one use `Scale::default()`
+#[entity]
+#[derive(Debug, Clone)]
+pub struct Entity {
+    #[id]
+    pub id: i32,
+    pub university_id: university::EntityId,
+    pub scale: Scale,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Scale {
+    pub thresholds: Thresholds,
+    pub notation: Notation,
+}
+
+/// Minimal scores for each grade, tests use only `pass`,
+/// differentiated tests and exams use the rest
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Thresholds {
+    pub pass: i32,
+    pub satisfactory: i32,
+    pub good: i32,
+    pub excellent: i32,
+}
+
+/// How differentiated grades are shown, `5..2` or `A..F`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Notation {
+    #[default]
+    Numeric,
+    Letter,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Grade {
+    Pass,
+    Fail,
+    Excellent,
+    Good,
+    Satisfactory,
+    Unsatisfactory,
+}
+
+impl Default for Thresholds {
+    fn default() -> Self {
+        Self {
+            pass: 50,
+            satisfactory: 50,
+            good: 70,
+            excellent: 85,
+        }
+    }
+}
+
+impl Scale {
+    pub fn grade(&self, kind: &AttestationKind, score: i32) -> Grade {
+        let Thresholds {
+            pass,
+            satisfactory,
+            good,
+            excellent,
+        } = self.thresholds;
+
+        match kind {
+            AttestationKind::Test if score >= pass => Grade::Pass,
+            AttestationKind::Test => Grade::Fail,
+            AttestationKind::DiffTest | AttestationKind::Exam => match score {
+                _ if score >= excellent => Grade::Excellent,
+                _ if score >= good => Grade::Good,
+                _ if score >= satisfactory => Grade::Satisfactory,
+                _ => Grade::Unsatisfactory,
+            },
+        }
+    }
+
+    /// Grade as it's written in the record book
+    pub fn mark(&self, grade: Grade) -> &'static str {
+        match (self.notation, grade) {
+            (_, Grade::Pass) => "pass",
+            (_, Grade::Fail) => "fail",
+            (Notation::Numeric, Grade::Excellent) => "5",
+            (Notation::Numeric, Grade::Good) => "4",
+            (Notation::Numeric, Grade::Satisfactory) => "3",
+            (Notation::Numeric, Grade::Unsatisfactory) => "2",
+            (Notation::Letter, Grade::Excellent) => "A",
+            (Notation::Letter, Grade::Good) => "B",
+            (Notation::Letter, Grade::Satisfactory) => "C",
+            (Notation::Letter, Grade::Unsatisfactory) => "F",
+        }
+    }
+}
+
+impl Grade {
+    /// Points of a differentiated grade used for averages, tests have none
+    pub fn points(&self) -> Option<u8> {
+        match self {
+            Grade::Excellent => Some(5),
+            Grade::Good => Some(4),
+            Grade::Satisfactory => Some(3),
+            Grade::Unsatisfactory => Some(2),
+            Grade::Pass | Grade::Fail => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tests_are_graded_by_the_pass_threshold_only() {
+        let scale = Scale::default();
+
+        assert_eq!(scale.grade(&AttestationKind::Test, 49), Grade::Fail);
+        assert_eq!(scale.grade(&AttestationKind::Test, 50), Grade::Pass);
+        assert_eq!(scale.grade(&AttestationKind::Test, 100), Grade::Pass);
+    }
+
+    #[test]
+    fn differentiated_grades_start_at_their_thresholds() {
+        let scale = Scale::default();
+
+        for kind in [AttestationKind::DiffTest, AttestationKind::Exam] {
+            assert_eq!(scale.grade(&kind, 0), Grade::Unsatisfactory);
+            assert_eq!(scale.grade(&kind, 49), Grade::Unsatisfactory);
+            assert_eq!(scale.grade(&kind, 50), Grade::Satisfactory);
+            assert_eq!(scale.grade(&kind, 69), Grade::Satisfactory);
+            assert_eq!(scale.grade(&kind, 70), Grade::Good);
+            assert_eq!(scale.grade(&kind, 84), Grade::Good);
+            assert_eq!(scale.grade(&kind, 85), Grade::Excellent);
+            assert_eq!(scale.grade(&kind, 100), Grade::Excellent);
+        }
+    }
+
+    #[test]
+    fn collapsed_bands_skip_the_lower_grade() {
+        let scale = Scale {
+            thresholds: Thresholds {
+                pass: 60,
+                satisfactory: 60,
+                good: 80,
+                excellent: 80,
+            },
+            notation: Notation::Numeric,
+        };
+
+        assert_eq!(scale.grade(&AttestationKind::Exam, 79), Grade::Satisfactory);
+        assert_eq!(scale.grade(&AttestationKind::Exam, 80), Grade::Excellent);
+        assert_eq!(scale.grade(&AttestationKind::Test, 59), Grade::Fail);
+    }
+
+    #[test]
+    fn marks_follow_the_notation() {
+        let numeric = Scale::default();
+        let letter = Scale {
+            notation: Notation::Letter,
+            ..Default::default()
+        };
+
+        assert_eq!(numeric.mark(Grade::Good), "4");
+        assert_eq!(letter.mark(Grade::Good), "B");
+        assert_eq!(letter.mark(Grade::Unsatisfactory), "F");
+        assert_eq!(letter.mark(Grade::Pass), "pass");
+        assert_eq!(Grade::Pass.points(), None);
+        assert_eq!(Grade::Satisfactory.points(), Some(3));
+    }
+}
@@ -0,0 +1,187 @@
+use std::ops::RangeInclusive;
+
+use utils::{
+    di::{Module, Provide},
+    outcome::Outcome,
+};
+
+use crate::{
+    access_policy::{AccessException, AccessPolicy, Actor},
+    grading_scale::{self, Scale, Thresholds},
+    grant::Scope,
+    subdivision, university, AdaptersModule, AppModule,
+};
+
+/// Thresholds are compared with scores, so they share the score bounds
+const THRESHOLD_RANGE: RangeInclusive<i32> = 0..=100;
+
+pub struct GradingScaleService {
+    repo: grading_scale::BoxedRepo,
+    university_repo: university::BoxedRepo,
+    subdivision_repo: subdivision::BoxedRepo,
+    policy: AccessPolicy,
+}
+
+impl<A: AdaptersModule> Provide<GradingScaleService> for AppModule<A> {
+    fn provide(&self) -> GradingScaleService {
+        GradingScaleService {
+            repo: self.adapters.resolve(),
+            university_repo: self.adapters.resolve(),
+            subdivision_repo: self.adapters.resolve(),
+            policy: self.resolve(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, thiserror::Error)]
+pub enum GradingScaleException {
+    #[error(transparent)]
+    AccessException(#[from] AccessException),
+    #[error("university not found")]
+    UniversityNotFound,
+    #[error("thresholds should be between 0 and 100 and grow with the grade")]
+    InvalidThresholds,
+}
+
+impl GradingScaleService {
+    pub async fn get(
+        &self,
+        university_id: university::EntityId,
+    ) -> Outcome<Scale, GradingScaleException> {
+        self.ensure_university(university_id).await?;
+
+        let scale = self.scale_of_university(university_id).await?;
+        Outcome::Ok(scale)
+    }
+
+    pub async fn set(
+        &mut self,
+        actor: &Actor,
+        university_id: university::EntityId,
+        scale: Scale,
+    ) -> Outcome<Scale, GradingScaleException> {
+        self.ensure_university(university_id).await?;
+        self.policy
+            .authorize(actor, Scope::University(university_id))
+            .await?;
+
+        if !Self::is_valid_thresholds(&scale.thresholds) {
+            return Outcome::Ex(GradingScaleException::InvalidThresholds);
+        }
+
+        let id = match self.repo.find_by_university(university_id).await? {
+            Some(entity) => entity.id,
+            None => Default::default(),
+        };
+
+        let entity = self
+            .repo
+            .save(grading_scale::Entity {
+                id,
+                university_id,
+                scale,
+            })
+            .await?;
+
+        Outcome::Ok(entity.scale)
+    }
+
+    /// Drops the university scale, the default one is used afterwards
+    pub async fn reset(
+        &mut self,
+        actor: &Actor,
+        university_id: university::EntityId,
+    ) -> Outcome<Scale, GradingScaleException> {
+        self.ensure_university(university_id).await?;
+        self.policy
+            .authorize(actor, Scope::University(university_id))
+            .await?;
+
+        if let Some(entity) = self.repo.find_by_university(university_id).await? {
+            self.repo.delete(&entity).await?;
+        }
+
+        Outcome::Ok(Scale::default())
+    }
+
+    pub async fn scale_of_university(
+        &self,
+        university_id: university::EntityId,
+    ) -> Result<Scale, anyhow::Error> {
+        let scale = self
+            .repo
+            .find_by_university(university_id)
+            .await?
+            .map(|v| v.scale)
+            .unwrap_or_default();
+
+        Ok(scale)
+    }
+
+    /// Scale of the university the subdivision belongs to
+    pub async fn scale_of_subdivision(
+        &self,
+        subdivision_id: subdivision::EntityId,
+    ) -> Result<Scale, anyhow::Error> {
+        match self.subdivision_repo.find(subdivision_id).await? {
+            Some(subdivision) => self.scale_of_university(subdivision.university_id).await,
+            None => Ok(Scale::default()),
+        }
+    }
+
+    async fn ensure_university(
+        &self,
+        university_id: university::EntityId,
+    ) -> Outcome<(), GradingScaleException> {
+        if self.university_repo.find(university_id).await?.is_none() {
+            return Outcome::Ex(GradingScaleException::UniversityNotFound);
+        }
+
+        Outcome::Ok(())
+    }
+
+    fn is_valid_thresholds(thresholds: &Thresholds) -> bool {
+        let Thresholds {
+            pass,
+            satisfactory,
+            good,
+            excellent,
+        } = *thresholds;
+
+        [pass, satisfactory, good, excellent]
+            .iter()
+            .all(|v| THRESHOLD_RANGE.contains(v))
+            && satisfactory <= good
+            && good <= excellent
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn thresholds_must_be_ordered_scores() {
+        assert!(GradingScaleService::is_valid_thresholds(
+            &Thresholds::default()
+        ));
+        assert!(GradingScaleService::is_valid_thresholds(&Thresholds {
+            pass: 0,
+            satisfactory: 100,
+            good: 100,
+            excellent: 100,
+        }));
+        assert!(!GradingScaleService::is_valid_thresholds(&Thresholds {
+            good: 90,
+            ..Default::default()
+        }));
+        assert!(!GradingScaleService::is_valid_thresholds(&Thresholds {
+            pass: -1,
+            ..Default::default()
+        }));
+        assert!(!GradingScaleService::is_valid_thresholds(&Thresholds {
+            excellent: 101,
+            ..Default::default()
+        }));
+    }
+}
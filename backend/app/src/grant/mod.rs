@@ -0,0 +1,25 @@
+mod repo;
+
+use crate::{study_group, subdivision, university, user};
+use utils::entity::entity;
+
+pub use repo::Repo;
+pub type BoxedRepo = Box<dyn Repo + Send + Sync>;
+
+#[entity]
+#[derive(Debug, Clone)]
+pub struct Entity {
+    #[id]
+    pub id: i32,
+    pub user_id: user::EntityId,
+    pub role: user::Role,
+    pub scope: Scope,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Scope {
+    Global,
+    University(university::EntityId),
+    Subdivision(subdivision::EntityId),
+    StudyGroup(study_group::EntityId),
+}
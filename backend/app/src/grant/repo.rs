@@ -0,0 +1,18 @@
+use crate::user;
+
+use super::{Entity, EntityId, Scope};
+
+#[async_trait::async_trait]
+pub trait Repo {
+    async fn save(&mut self, entity: Entity) -> Result<Entity, anyhow::Error>;
+
+    async fn delete(&mut self, entity: &Entity) -> Result<(), anyhow::Error>;
+
+    async fn delete_by_scope(&mut self, scope: Scope) -> Result<(), anyhow::Error>;
+
+    async fn find(&self, id: EntityId) -> Result<Option<Entity>, anyhow::Error>;
+
+    async fn list_by_user(&self, user_id: user::EntityId) -> Result<Vec<Entity>, anyhow::Error>;
+
+    async fn list_by_scope(&self, scope: Scope) -> Result<Vec<Entity>, anyhow::Error>;
+}
@@ -0,0 +1,98 @@
+use utils::{
+    di::{Module, Provide},
+    outcome::Outcome,
+};
+
+use crate::{
+    access_policy::{AccessException, AccessPolicy, Actor},
+    grant::{self, Scope},
+    user::{self, Role},
+    AdaptersModule, AppModule,
+};
+
+pub struct GrantService {
+    repo: grant::BoxedRepo,
+    user_repo: user::BoxedRepo,
+    policy: AccessPolicy,
+}
+
+impl<A: AdaptersModule> Provide<GrantService> for AppModule<A> {
+    fn provide(&self) -> GrantService {
+        GrantService {
+            repo: self.adapters.resolve(),
+            user_repo: self.adapters.resolve(),
+            policy: self.resolve(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, thiserror::Error)]
+pub enum GrantException {
+    #[error(transparent)]
+    AccessException(#[from] AccessException),
+    #[error("user not found")]
+    UserNotFound,
+    #[error("grant not found")]
+    GrantNotFound,
+    #[error("grant already exist")]
+    AlreadyExist,
+}
+
+impl GrantService {
+    pub async fn grant(
+        &mut self,
+        actor: &Actor,
+        user_id: user::EntityId,
+        role: Role,
+        scope: Scope,
+    ) -> Outcome<grant::Entity, GrantException> {
+        self.policy.authorize_grant(actor, role, scope).await?;
+
+        if self.user_repo.find(user_id).await?.is_none() {
+            return Outcome::Ex(GrantException::UserNotFound);
+        }
+
+        let is_already_exist = self
+            .repo
+            .list_by_user(user_id)
+            .await?
+            .into_iter()
+            .any(|grant| grant.role == role && grant.scope == scope);
+
+        if is_already_exist {
+            return Outcome::Ex(GrantException::AlreadyExist);
+        }
+
+        let grant = grant::Entity {
+            id: Default::default(),
+            user_id,
+            role,
+            scope,
+        };
+
+        let grant = self.repo.save(grant).await?;
+        Outcome::Ok(grant)
+    }
+
+    pub async fn revoke(
+        &mut self,
+        actor: &Actor,
+        grant_id: grant::EntityId,
+    ) -> Outcome<grant::Entity, GrantException> {
+        let Some(grant) = self.repo.find(grant_id).await? else {
+            return Outcome::Ex(GrantException::GrantNotFound);
+        };
+
+        self.policy
+            .authorize_grant(actor, grant.role, grant.scope)
+            .await?;
+
+        self.repo.delete(&grant).await?;
+        Outcome::Ok(grant)
+    }
+
+    pub async fn list_by_scope(&self, scope: Scope) -> Outcome<Vec<grant::Entity>, GrantException> {
+        let grants = self.repo.list_by_scope(scope).await?;
+        Outcome::Ok(grants)
+    }
+}
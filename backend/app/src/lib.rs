@@ -1,27 +1,71 @@
 use utils::di::{Module, Provide};
 
+pub mod academic_calendar_service;
+pub mod academic_year;
+pub mod access_policy;
+pub mod account_service;
+pub mod account_token;
 pub mod attestation;
+pub mod attestation_service;
+pub mod attestation_session;
+pub mod attestation_session_service;
 pub mod auth_service;
+pub mod calendar_feed;
+pub mod calendar_service;
 pub mod class;
 pub mod class_kind;
+pub mod class_kind_service;
+pub mod class_service;
 pub mod curriculum;
 pub mod curriculum_module;
+pub mod curriculum_module_service;
+pub mod curriculum_service;
 pub mod discipline;
+pub mod discipline_service;
+pub mod gradebook_service;
+pub mod grading_scale;
+pub mod grading_scale_service;
+pub mod grant;
+pub mod grant_service;
 pub mod hasher;
+pub mod load_limit;
+pub mod login_attempt;
+pub mod login_throttle_service;
+pub mod mail;
+pub mod paging;
 pub mod passport;
 pub mod person;
 pub mod person_service;
+pub mod room;
+pub mod room_service;
+pub mod schedule;
+pub mod schedule_service;
 pub mod student;
+pub mod student_import_service;
+pub mod student_order;
+pub mod student_service;
 pub mod study_group;
+pub mod study_group_service;
 pub mod subdivision;
+pub mod subdivision_service;
 pub mod tag;
+pub mod tag_service;
 pub mod teacher;
+pub mod teacher_service;
+pub mod teaching_load_service;
+pub mod timetable_draft;
+pub mod timetable_draft_service;
+pub mod timetable_generator;
 pub mod token;
+pub mod transcript_service;
 pub mod university;
+pub mod university_service;
 pub mod user;
 pub mod user_service;
 pub mod user_session;
 
+mod validation;
+
 pub trait AdaptersModule:
     Send
     + Module
@@ -30,6 +74,9 @@ pub trait AdaptersModule:
     + Provide<user_session::BoxedRepo>
     + Provide<user_session::SessionTTL>
     + Provide<user_session::SessionsMaxNumber>
+    + Provide<user_session::BoxedDeviceIdGenerator>
+    + Provide<login_attempt::BoxedStore>
+    + Provide<login_attempt::LoginThrottling>
     + Provide<university::BoxedRepo>
     + Provide<subdivision::BoxedRepo>
     + Provide<tag::BoxedRepo>
@@ -37,15 +84,34 @@ pub trait AdaptersModule:
     + Provide<person::BoxedRepo>
     + Provide<token::BoxedAccessTokenEngine>
     + Provide<token::BoxedRefreshTokenGenerator>
+    + Provide<token::BoxedRefreshTokenHasher>
     + Provide<token::AccessTokenTTL>
     + Provide<study_group::BoxedRepo>
     + Provide<student::BoxedRepo>
+    + Provide<teacher::BoxedRepo>
     + Provide<discipline::BoxedRepo>
     + Provide<curriculum::BoxedRepo>
+    + Provide<curriculum::SemesterHoursBounds>
     + Provide<curriculum_module::BoxedRepo>
     + Provide<class_kind::BoxedRepo>
     + Provide<class::BoxedRepo>
     + Provide<attestation::BoxedRepo>
+    + Provide<grant::BoxedRepo>
+    + Provide<grading_scale::BoxedRepo>
+    + Provide<room::BoxedRepo>
+    + Provide<schedule::BoxedRepo>
+    + Provide<timetable_draft::BoxedRepo>
+    + Provide<attestation_session::BoxedRepo>
+    + Provide<calendar_feed::BoxedRepo>
+    + Provide<load_limit::BoxedRepo>
+    + Provide<academic_year::BoxedRepo>
+    + Provide<student_order::BoxedRepo>
+    + Provide<account_token::BoxedRepo>
+    + Provide<account_token::EmailVerificationTTL>
+    + Provide<account_token::PasswordResetTTL>
+    + Provide<token::BoxedAccountTokenEngine>
+    + Provide<mail::BoxedMailSender>
+    + Provide<mail::LinkBaseUrl>
 {
 }
 
@@ -0,0 +1,44 @@
+mod repo;
+
+use utils::entity::entity;
+
+use crate::{attestation::Hours, teacher::TeacherKind, university};
+
+pub use repo::Repo;
+pub type BoxedRepo = Box<dyn Repo + Send + Sync>;
+
+/// Planned hours per semester a teacher of the kind is expected to have,
+/// teachers of kinds without a limit are not checked
+#[entity]
+#[derive(Debug, Clone)]
+pub struct Entity {
+    #[id]
+    pub id: i32,
+    pub university_id: university::EntityId,
+    pub teacher_kind: TeacherKind,
+    pub min_hours: Hours,
+    pub max_hours: Hours,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LoadStatus {
+    Underload,
+    Normal,
+    Overload,
+}
+
+impl Entity {
+    pub fn is_valid(&self) -> bool {
+        0 <= self.min_hours.0 && self.min_hours.0 <= self.max_hours.0
+    }
+
+    pub fn status(&self, hours: i32) -> LoadStatus {
+        if hours < self.min_hours.0 {
+            LoadStatus::Underload
+        } else if hours > self.max_hours.0 {
+            LoadStatus::Overload
+        } else {
+            LoadStatus::Normal
+        }
+    }
+}
@@ -0,0 +1,128 @@
+mod store;
+
+use crate::user_session::{Seconds, SecondsFromUnixEpoch};
+
+pub use store::Store;
+pub type BoxedStore = Box<dyn Store + Send + Sync>;
+
+/// Failed logins are counted per account and per address, an attacker either tries
+/// many passwords for one account or a few common ones for many accounts
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Key {
+    /// Normalized email, unknown emails are counted too, so a lockout does not
+    /// tell which emails are registered
+    Account(String),
+    Ip(String),
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Counter {
+    /// Attempts are counted before the password is checked,
+    /// so ones still in progress are counted as failed
+    pub failures: u32,
+    pub last_failure_at: SecondsFromUnixEpoch,
+    pub locked_until: Option<SecondsFromUnixEpoch>,
+}
+
+/// Every failure past the limit locks the key for twice as long as the previous one,
+/// the counter starts over after `max_lockout` without failures
+#[derive(Debug, Clone, Copy)]
+pub struct LoginThrottling {
+    pub account_max_failures: u32,
+    pub ip_max_failures: u32,
+    pub lockout: Seconds,
+    pub max_lockout: Seconds,
+}
+
+impl LoginThrottling {
+    pub fn account_lockout(&self) -> Lockout {
+        self.lockout_after(self.account_max_failures)
+    }
+
+    pub fn ip_lockout(&self) -> Lockout {
+        self.lockout_after(self.ip_max_failures)
+    }
+
+    fn lockout_after(&self, max_failures: u32) -> Lockout {
+        Lockout {
+            max_failures,
+            base: self.lockout,
+            max: self.max_lockout,
+        }
+    }
+}
+
+/// Lockouts of one kind of keys
+#[derive(Debug, Clone, Copy)]
+pub struct Lockout {
+    pub max_failures: u32,
+    pub base: Seconds,
+    pub max: Seconds,
+}
+
+impl Lockout {
+    /// Lockout of a key with the failures, `None` while they are under the limit
+    pub fn after(&self, failures: u32) -> Option<Seconds> {
+        if failures < self.max_failures {
+            return None;
+        }
+
+        let lockout = self
+            .base
+            .val
+            .saturating_mul(2u64.saturating_pow(failures - self.max_failures))
+            .min(self.max.val);
+
+        Some(lockout.into())
+    }
+}
+
+pub enum Increment {
+    Counted(Counter),
+    /// The attempt is not counted while the key is locked
+    Locked {
+        until: SecondsFromUnixEpoch,
+    },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn throttling() -> LoginThrottling {
+        LoginThrottling {
+            account_max_failures: 5,
+            ip_max_failures: 20,
+            lockout: 60.into(),
+            max_lockout: 3600.into(),
+        }
+    }
+
+    #[test]
+    fn keys_are_not_locked_under_the_limit() {
+        let lockout = throttling().account_lockout();
+
+        assert_eq!(lockout.after(0), None);
+        assert_eq!(lockout.after(4), None);
+        assert_eq!(lockout.after(5), Some(60.into()));
+    }
+
+    #[test]
+    fn lockouts_double_up_to_the_max() {
+        let lockout = throttling().account_lockout();
+
+        assert_eq!(lockout.after(6), Some(120.into()));
+        assert_eq!(lockout.after(7), Some(240.into()));
+        assert_eq!(lockout.after(10), Some(1920.into()));
+        assert_eq!(lockout.after(11), Some(3600.into()));
+        assert_eq!(lockout.after(u32::MAX), Some(3600.into()));
+    }
+
+    #[test]
+    fn addresses_have_their_own_limit() {
+        let lockout = throttling().ip_lockout();
+
+        assert_eq!(lockout.after(19), None);
+        assert_eq!(lockout.after(20), Some(60.into()));
+    }
+}
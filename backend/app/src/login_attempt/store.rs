@@ -0,0 +1,25 @@
+use crate::user_session::SecondsFromUnixEpoch;
+
+use super::{Increment, Key, Lockout};
+
+/// Counters only matter for a short time, they can be kept out of the database.
+/// Every method changes the counter atomically, concurrent logins never read a stale count
+#[async_trait::async_trait]
+pub trait Store {
+    /// Counts one more attempt unless the key is locked at the time,
+    /// the key is locked right away once the attempt takes it to the limit
+    async fn increment(
+        &mut self,
+        key: Key,
+        now: SecondsFromUnixEpoch,
+        lockout: Lockout,
+    ) -> Result<Increment, anyhow::Error>;
+
+    /// Takes back an attempt that turned out successful, along with the lock it set
+    async fn decrement(&mut self, key: &Key, unlock: bool) -> Result<(), anyhow::Error>;
+
+    async fn delete(&mut self, key: &Key) -> Result<(), anyhow::Error>;
+
+    /// Forgets the counters without attempts since the time
+    async fn delete_stale(&mut self, before: SecondsFromUnixEpoch) -> Result<(), anyhow::Error>;
+}
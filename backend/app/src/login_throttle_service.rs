@@ -0,0 +1,125 @@
+use utils::{di::Provide, outcome::Outcome};
+
+use crate::{
+    login_attempt::{self, Increment, Key, LoginThrottling},
+    user,
+    user_service::UserException,
+    user_session::SecondsFromUnixEpoch,
+    AdaptersModule, AppModule,
+};
+
+/// Slows down password guessing, failed logins lock the account and the address
+/// for a growing time
+pub struct LoginThrottleService {
+    user_repo: user::BoxedRepo,
+    store: login_attempt::BoxedStore,
+    throttling: LoginThrottling,
+}
+
+impl<A: AdaptersModule> Provide<LoginThrottleService> for AppModule<A> {
+    fn provide(&self) -> LoginThrottleService {
+        LoginThrottleService {
+            user_repo: self.adapters.resolve(),
+            store: self.adapters.resolve(),
+            throttling: self.adapters.resolve(),
+        }
+    }
+}
+
+/// Login counted in the throttling counters, a successful one gives its attempts back
+pub struct Attempt {
+    account: Key,
+    ip: Option<(Key, u32)>,
+}
+
+impl LoginThrottleService {
+    /// Counts the login as failed before the password is checked, so concurrent logins can't
+    /// get past the limit together: the one taking a key to the limit locks it right away.
+    /// The password is not checked while locked, guessing it is pointless until the lockout ends
+    pub async fn begin(
+        &mut self,
+        email: &str,
+        ip: Option<&str>,
+    ) -> Outcome<Attempt, UserException> {
+        let now = SecondsFromUnixEpoch::now()?;
+
+        self.store
+            .delete_stale(
+                now.seconds
+                    .val
+                    .saturating_sub(self.throttling.max_lockout.val)
+                    .into(),
+            )
+            .await?;
+
+        // the address goes first, so a rejected address does not cost the account an attempt
+        let ip = match ip {
+            Some(ip) => {
+                let key = Key::Ip(ip.to_owned());
+                match self
+                    .store
+                    .increment(key.clone(), now, self.throttling.ip_lockout())
+                    .await?
+                {
+                    Increment::Counted(counter) => Some((key, counter.failures)),
+                    Increment::Locked { until } => {
+                        return Outcome::Ex(UserException::TooManyLoginAttempts {
+                            retry_after: until.seconds.val - now.seconds.val,
+                        })
+                    }
+                }
+            }
+            None => None,
+        };
+
+        let account = account_key(email);
+        if let Increment::Locked { until } = self
+            .store
+            .increment(account.clone(), now, self.throttling.account_lockout())
+            .await?
+        {
+            if let Some((key, failures)) = ip {
+                self.give_back(&key, failures).await?;
+            }
+
+            return Outcome::Ex(UserException::AccountLocked {
+                retry_after: until.seconds.val - now.seconds.val,
+            });
+        }
+
+        Outcome::Ok(Attempt { account, ip })
+    }
+
+    /// The address only gets its attempt back, one known password must not
+    /// wipe out the failures made against other accounts
+    pub async fn record_success(&mut self, attempt: Attempt) -> Result<(), anyhow::Error> {
+        self.store.delete(&attempt.account).await?;
+        if let Some((key, failures)) = attempt.ip {
+            self.give_back(&key, failures).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Only the account counter is cleared, addresses are shared by many accounts
+    /// and keep their lockouts
+    pub async fn unlock(&mut self, user_id: user::EntityId) -> Outcome<(), UserException> {
+        let Some(user) = self.user_repo.find(user_id).await? else {
+            return Outcome::Ex(UserException::UserNotFound);
+        };
+
+        self.store.delete(&account_key(&user.email)).await?;
+        Outcome::Ok(())
+    }
+
+    /// Removes an attempt of the address that turned out not to be a failure,
+    /// the lock it set goes with it
+    async fn give_back(&mut self, key: &Key, failures: u32) -> Result<(), anyhow::Error> {
+        let unlock = self.throttling.ip_lockout().after(failures).is_some();
+        self.store.decrement(key, unlock).await
+    }
+}
+
+fn account_key(email: &str) -> Key {
+    Key::Account(email.trim().to_lowercase())
+}
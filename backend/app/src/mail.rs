@@ -0,0 +1,18 @@
+/// Plain text letter, the sender adds its own from address
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mail {
+    pub to: String,
+    pub subject: String,
+    pub body: String,
+}
+
+pub type BoxedMailSender = Box<dyn MailSender + Send + Sync>;
+
+#[async_trait::async_trait]
+pub trait MailSender {
+    async fn send(&self, mail: Mail) -> Result<(), anyhow::Error>;
+}
+
+/// Address of the frontend, links in the letters lead to its pages
+#[derive(Debug, Clone)]
+pub struct LinkBaseUrl(pub std::sync::Arc<str>);
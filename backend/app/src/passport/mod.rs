@@ -11,6 +11,7 @@ pub use number::{
 };
 
 #[utils::entity::entity]
+#[derive(Debug, Clone)]
 pub struct Entity {
     #[id]
     pub id: i32,
@@ -25,6 +26,7 @@ pub struct Entity {
     pub gender: Gender,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Gender {
     Male,
     Female,
@@ -3,12 +3,12 @@ use std::{fmt::Display, num::ParseIntError, str::FromStr};
 const PASSPORT_NUMBER_LEN: usize = 6;
 const PASSPORT_SERIES_LEN: usize = 4;
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct PassportNumber {
     value: FixedLenU32<PASSPORT_NUMBER_LEN>,
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct PassportSeries {
     value: FixedLenU32<PASSPORT_SERIES_LEN>,
 }
@@ -53,7 +53,7 @@ impl Display for PassportSeries {
     }
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 struct FixedLenU32<const LEN: usize> {
     value: u32,
 }
@@ -89,7 +89,7 @@ impl<const LEN: usize> Display for FixedLenU32<LEN> {
                 .chars()
                 .rev()
                 .chain("0".chars().cycle())
-                .take(6)
+                .take(LEN)
                 .collect::<Vec<_>>()
                 .into_iter()
                 .rev()
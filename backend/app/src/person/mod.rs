@@ -2,13 +2,36 @@ mod repo;
 
 pub use repo::Repo;
 
-use crate::user;
+use std::collections::HashSet;
+
+use crate::{study_group, subdivision, user};
 pub type BoxedRepo = Box<dyn Repo + Send + Sync>;
 
 #[utils::entity::entity]
+#[derive(Debug, Clone)]
 pub struct Entity {
     #[id]
     pub id: i32,
     pub user_id: user::EntityId,
     pub full_name: String,
 }
+
+#[derive(Debug, Clone, Default)]
+pub struct ListFilter {
+    pub name_prefix: Option<String>,
+    /// Restricts the list to persons studying or teaching there, `None` lists everyone
+    pub within: Option<Within>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Within {
+    pub study_group_ids: HashSet<study_group::EntityId>,
+    pub department_ids: HashSet<subdivision::EntityId>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SortKey {
+    #[default]
+    Id,
+    FullName,
+}
@@ -1,6 +1,11 @@
-use crate::user;
+use std::collections::HashSet;
 
-use super::{Entity, EntityId};
+use crate::{
+    paging::{Page, Paginated, Sort},
+    user,
+};
+
+use super::{Entity, EntityId, ListFilter, SortKey};
 
 #[async_trait::async_trait]
 pub trait Repo {
@@ -10,7 +15,15 @@ pub trait Repo {
 
     async fn find(&self, id: EntityId) -> Result<Option<Entity>, anyhow::Error>;
 
-    async fn list(&self) -> Result<Vec<Entity>, anyhow::Error>;
+    /// Loads all entities with the given ids in one round-trip, missing ids are skipped
+    async fn find_many(&self, ids: HashSet<EntityId>) -> Result<Vec<Entity>, anyhow::Error>;
+
+    async fn list(
+        &self,
+        filter: ListFilter,
+        sort: Sort<SortKey>,
+        page: Page,
+    ) -> Result<Paginated<Entity>, anyhow::Error>;
 
     async fn find_by_user_id(
         &self,
@@ -1,19 +1,65 @@
-use utils::{entity::Id, outcome::Outcome};
+use std::collections::HashSet;
+
+use utils::{
+    di::{Module, Provide},
+    outcome::Outcome,
+};
 
 use crate::{
+    access_policy::{AccessException, AccessPolicy, Actor},
+    grant::Scope,
+    paging::{Page, Paginated, Sort},
     passport::{self, Gender, PassportNumber, PassportSeries},
-    person, user,
+    person, student, subdivision, teacher, user,
+    validation::is_valid_name,
+    AdaptersModule, AppModule,
 };
 
+pub(crate) const FULL_NAME_MAX_LEN: usize = 1024;
+const PASSPORT_NAME_MAX_LEN: usize = 256;
+
 pub struct PersonService {
     repo: person::BoxedRepo,
     passport_repo: passport::BoxedRepo,
+    user_repo: user::BoxedRepo,
+    student_repo: student::BoxedRepo,
+    teacher_repo: teacher::BoxedRepo,
+    subdivision_repo: subdivision::BoxedRepo,
+    policy: AccessPolicy,
+}
+
+impl<A: AdaptersModule> Provide<PersonService> for AppModule<A> {
+    fn provide(&self) -> PersonService {
+        PersonService {
+            repo: self.adapters.resolve(),
+            passport_repo: self.adapters.resolve(),
+            user_repo: self.adapters.resolve(),
+            student_repo: self.adapters.resolve(),
+            teacher_repo: self.adapters.resolve(),
+            subdivision_repo: self.adapters.resolve(),
+            policy: self.resolve(),
+        }
+    }
 }
 
 #[derive(Debug, Clone, thiserror::Error)]
 pub enum PersonException {
+    #[error(transparent)]
+    AccessException(#[from] AccessException),
+    #[error("person not found")]
+    NotFound,
+    #[error("user not found")]
+    UserNotFound,
+    #[error("invalid full name")]
+    InvalidFullName,
     #[error("person already exist")]
     AlreadyExist,
+    #[error("person is student, teacher or subdivision member")]
+    InUse,
+    #[error("passport not found")]
+    PassportNotFound,
+    #[error("invalid passport")]
+    InvalidPassport,
     #[error("passport already exist")]
     PassportAlreadyExist,
 }
@@ -30,9 +76,20 @@ pub struct PassportInfo {
 }
 
 impl PassportInfo {
-    fn into_passport_entity(self, person_id: person::EntityId) -> passport::Entity {
+    pub(crate) fn is_valid(&self) -> bool {
+        is_valid_name(&self.first_name, PASSPORT_NAME_MAX_LEN)
+            && is_valid_name(&self.last_name, PASSPORT_NAME_MAX_LEN)
+            && self.patronymic.chars().count() <= PASSPORT_NAME_MAX_LEN
+            && self.date_of_birth < self.date_of_issue
+    }
+
+    fn into_passport_entity(
+        self,
+        id: passport::EntityId,
+        person_id: person::EntityId,
+    ) -> passport::Entity {
         passport::Entity {
-            id: Default::default(),
+            id,
             person_id,
             first_name: self.first_name,
             last_name: self.last_name,
@@ -47,15 +104,59 @@ impl PassportInfo {
 }
 
 impl PersonService {
+    /// Lists the persons studying and teaching inside of the scopes granted to the actor
+    pub async fn list(
+        &self,
+        actor: &Actor,
+        filter: person::ListFilter,
+        sort: Sort<person::SortKey>,
+        page: Page,
+    ) -> Outcome<Paginated<person::Entity>, PersonException> {
+        let within = self.policy.readable(actor).await?.map(|v| person::Within {
+            study_group_ids: v.study_group_ids,
+            department_ids: v.subdivision_ids,
+        });
+
+        let persons = self
+            .repo
+            .list(person::ListFilter { within, ..filter }, sort, page)
+            .await?;
+
+        Outcome::Ok(persons)
+    }
+
+    pub async fn find(
+        &self,
+        actor: &Actor,
+        id: person::EntityId,
+    ) -> Outcome<person::Entity, PersonException> {
+        let scopes = self.scopes(id).await?;
+        self.policy.authorize_read(actor, &scopes).await?;
+
+        self.get(id).await
+    }
+
     pub async fn create(
         &mut self,
+        actor: &Actor,
         user_id: user::EntityId,
         full_name: String,
     ) -> Outcome<person::Entity, PersonException> {
+        // a new person belongs to no study group or department yet
+        self.policy.authorize(actor, Scope::Global).await?;
+
+        if self.user_repo.find(user_id).await?.is_none() {
+            return Outcome::Ex(PersonException::UserNotFound);
+        }
+
         if self.repo.find_by_user_id(user_id).await?.is_some() {
             return Outcome::Ex(PersonException::AlreadyExist);
         }
 
+        if !is_valid_name(&full_name, FULL_NAME_MAX_LEN) {
+            return Outcome::Ex(PersonException::InvalidFullName);
+        }
+
         let person = person::Entity {
             id: Default::default(),
             user_id,
@@ -66,26 +167,177 @@ impl PersonService {
         Outcome::Ok(person)
     }
 
-    pub async fn add_passort(
+    pub async fn update(
         &mut self,
+        actor: &Actor,
+        id: person::EntityId,
+        full_name: String,
+    ) -> Outcome<person::Entity, PersonException> {
+        self.authorize(actor, id).await?;
+        let person = self.get(id).await?;
+
+        if !is_valid_name(&full_name, FULL_NAME_MAX_LEN) {
+            return Outcome::Ex(PersonException::InvalidFullName);
+        }
+
+        let person = self
+            .repo
+            .save(person::Entity {
+                full_name,
+                ..person
+            })
+            .await?;
+
+        Outcome::Ok(person)
+    }
+
+    pub async fn delete(
+        &mut self,
+        actor: &Actor,
+        id: person::EntityId,
+    ) -> Outcome<person::Entity, PersonException> {
+        self.authorize(actor, id).await?;
+        let person = self.get(id).await?;
+
+        let is_in_use = !self.student_repo.list_by_person(id).await?.is_empty()
+            || self.teacher_repo.find_by_person_id(id).await?.is_some()
+            || !self
+                .subdivision_repo
+                .list_by_members(HashSet::from([id]))
+                .await?
+                .is_empty();
+
+        if is_in_use {
+            return Outcome::Ex(PersonException::InUse);
+        }
+
+        for passport in self.passport_repo.list_by_person_id(id).await? {
+            self.passport_repo.delete(&passport).await?;
+        }
+
+        self.repo.delete(&person).await?;
+        Outcome::Ok(person)
+    }
+
+    pub async fn add_passport(
+        &mut self,
+        actor: &Actor,
         person_id: person::EntityId,
         passport: PassportInfo,
     ) -> Outcome<passport::Entity, PersonException> {
-        let is_already_exist = self
+        self.authorize(actor, person_id).await?;
+        self.get(person_id).await?;
+
+        self.validate_passport(None, &passport).await?;
+
+        let passport = self
+            .passport_repo
+            .save(passport.into_passport_entity(Default::default(), person_id))
+            .await?;
+
+        Outcome::Ok(passport)
+    }
+
+    pub async fn update_passport(
+        &mut self,
+        actor: &Actor,
+        id: passport::EntityId,
+        passport: PassportInfo,
+    ) -> Outcome<passport::Entity, PersonException> {
+        let current = self.get_authorized_passport(actor, id).await?;
+
+        self.validate_passport(Some(id), &passport).await?;
+
+        let passport = self
             .passport_repo
-            .find_by_number_series(passport.number, passport.series)
+            .save(passport.into_passport_entity(id, current.person_id))
+            .await?;
+
+        Outcome::Ok(passport)
+    }
+
+    pub async fn remove_passport(
+        &mut self,
+        actor: &Actor,
+        id: passport::EntityId,
+    ) -> Outcome<passport::Entity, PersonException> {
+        let passport = self.get_authorized_passport(actor, id).await?;
+
+        self.passport_repo.delete(&passport).await?;
+        Outcome::Ok(passport)
+    }
+
+    async fn get(&self, id: person::EntityId) -> Outcome<person::Entity, PersonException> {
+        let Some(person) = self.repo.find(id).await? else {
+            return Outcome::Ex(PersonException::NotFound);
+        };
+
+        Outcome::Ok(person)
+    }
+
+    /// Persons are managed within the study groups they study in and the departments they teach at,
+    /// the ones that are neither students nor teachers, missing ones included, are left to admins
+    async fn authorize(&self, actor: &Actor, id: person::EntityId) -> Outcome<(), PersonException> {
+        let scopes = self.scopes(id).await?;
+        self.policy.authorize_some(actor, &scopes).await?;
+        Outcome::Ok(())
+    }
+
+    async fn scopes(&self, id: person::EntityId) -> Outcome<Vec<Scope>, PersonException> {
+        let mut scopes = self
+            .student_repo
+            .list_by_person(id)
             .await?
-            .is_some();
+            .into_iter()
+            .map(|v| Scope::StudyGroup(v.study_group_id))
+            .collect::<Vec<_>>();
 
-        if is_already_exist {
-            return Outcome::Ex(PersonException::PassportAlreadyExist);
+        if let Some(teacher) = self.teacher_repo.find_by_person_id(id).await? {
+            scopes.push(Scope::Subdivision(teacher.department_id));
         }
 
-        let passport = self
+        if scopes.is_empty() {
+            scopes.push(Scope::Global);
+        }
+
+        Outcome::Ok(scopes)
+    }
+
+    /// Passport is looked up first to find its person, a missing one is only reported to admins
+    async fn get_authorized_passport(
+        &self,
+        actor: &Actor,
+        id: passport::EntityId,
+    ) -> Outcome<passport::Entity, PersonException> {
+        let Some(passport) = self.passport_repo.find(id).await? else {
+            self.policy.authorize(actor, Scope::Global).await?;
+            return Outcome::Ex(PersonException::PassportNotFound);
+        };
+
+        self.authorize(actor, passport.person_id).await?;
+        Outcome::Ok(passport)
+    }
+
+    async fn validate_passport(
+        &self,
+        id: Option<passport::EntityId>,
+        passport: &PassportInfo,
+    ) -> Outcome<(), PersonException> {
+        if !passport.is_valid() {
+            return Outcome::Ex(PersonException::InvalidPassport);
+        }
+
+        let existing = self
             .passport_repo
-            .save(passport.into_passport_entity(person_id))
+            .find_by_number_series(passport.number, passport.series)
             .await?;
 
-        Outcome::Ok(passport)
+        if let Some(existing) = existing {
+            if Some(existing.id) != id {
+                return Outcome::Ex(PersonException::PassportAlreadyExist);
+            }
+        }
+
+        Outcome::Ok(())
     }
 }
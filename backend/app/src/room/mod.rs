@@ -0,0 +1,18 @@
+mod repo;
+
+use utils::entity::entity;
+
+use crate::university;
+
+pub use repo::Repo;
+pub type BoxedRepo = Box<dyn Repo + Send + Sync>;
+
+#[entity]
+#[derive(Debug, Clone)]
+pub struct Entity {
+    #[id]
+    pub id: i32,
+    pub university_id: university::EntityId,
+    pub name: String,
+    pub capacity: i32,
+}
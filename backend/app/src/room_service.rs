@@ -0,0 +1,192 @@
+use utils::{
+    di::{Module, Provide},
+    outcome::Outcome,
+};
+
+use crate::{
+    access_policy::{AccessException, AccessPolicy, Actor},
+    attestation_session,
+    grant::Scope,
+    room, schedule, university,
+    validation::is_valid_name,
+    AdaptersModule, AppModule,
+};
+
+const NAME_MAX_LEN: usize = 64;
+
+pub struct RoomService {
+    repo: room::BoxedRepo,
+    university_repo: university::BoxedRepo,
+    schedule_repo: schedule::BoxedRepo,
+    attestation_session_repo: attestation_session::BoxedRepo,
+    policy: AccessPolicy,
+}
+
+impl<A: AdaptersModule> Provide<RoomService> for AppModule<A> {
+    fn provide(&self) -> RoomService {
+        RoomService {
+            repo: self.adapters.resolve(),
+            university_repo: self.adapters.resolve(),
+            schedule_repo: self.adapters.resolve(),
+            attestation_session_repo: self.adapters.resolve(),
+            policy: self.resolve(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, thiserror::Error)]
+pub enum RoomException {
+    #[error(transparent)]
+    AccessException(#[from] AccessException),
+    #[error("room not found")]
+    NotFound,
+    #[error("university not found")]
+    UniversityNotFound,
+    #[error("invalid room name")]
+    InvalidName,
+    #[error("room already exist")]
+    AlreadyExist,
+    #[error("capacity should be a positive number of seats")]
+    InvalidCapacity,
+    #[error("room is used by the timetable")]
+    InUse,
+}
+
+pub struct RoomInfo {
+    pub university_id: university::EntityId,
+    pub name: String,
+    pub capacity: i32,
+}
+
+impl RoomInfo {
+    fn into_entity(self, id: room::EntityId) -> room::Entity {
+        room::Entity {
+            id,
+            university_id: self.university_id,
+            name: self.name,
+            capacity: self.capacity,
+        }
+    }
+}
+
+impl RoomService {
+    pub async fn create(
+        &mut self,
+        actor: &Actor,
+        info: RoomInfo,
+    ) -> Outcome<room::Entity, RoomException> {
+        self.policy
+            .authorize(actor, Scope::University(info.university_id))
+            .await?;
+        self.validate(None, &info).await?;
+
+        let room = self.repo.save(info.into_entity(Default::default())).await?;
+        Outcome::Ok(room)
+    }
+
+    pub async fn update(
+        &mut self,
+        actor: &Actor,
+        id: room::EntityId,
+        info: RoomInfo,
+    ) -> Outcome<room::Entity, RoomException> {
+        let room = self.get(id).await?;
+
+        self.policy
+            .authorize(actor, Scope::University(room.university_id))
+            .await?;
+
+        if room.university_id != info.university_id {
+            self.policy
+                .authorize(actor, Scope::University(info.university_id))
+                .await?;
+        }
+
+        self.validate(Some(id), &info).await?;
+
+        let room = self.repo.save(info.into_entity(id)).await?;
+        Outcome::Ok(room)
+    }
+
+    pub async fn delete(
+        &mut self,
+        actor: &Actor,
+        id: room::EntityId,
+    ) -> Outcome<room::Entity, RoomException> {
+        let room = self.get(id).await?;
+
+        self.policy
+            .authorize(actor, Scope::University(room.university_id))
+            .await?;
+
+        if !self.schedule_repo.list_by_room(id).await?.is_empty()
+            || !self
+                .attestation_session_repo
+                .list_by_room(id)
+                .await?
+                .is_empty()
+        {
+            return Outcome::Ex(RoomException::InUse);
+        }
+
+        self.repo.delete(&room).await?;
+        Outcome::Ok(room)
+    }
+
+    pub async fn get(&self, id: room::EntityId) -> Outcome<room::Entity, RoomException> {
+        let Some(room) = self.repo.find(id).await? else {
+            return Outcome::Ex(RoomException::NotFound);
+        };
+
+        Outcome::Ok(room)
+    }
+
+    pub async fn list_by_university(
+        &self,
+        university_id: university::EntityId,
+    ) -> Outcome<Vec<room::Entity>, RoomException> {
+        if self.university_repo.find(university_id).await?.is_none() {
+            return Outcome::Ex(RoomException::UniversityNotFound);
+        }
+
+        let mut rooms = self.repo.list_by_university(university_id).await?;
+        rooms.sort_by(|a, b| a.name.cmp(&b.name));
+
+        Outcome::Ok(rooms)
+    }
+
+    async fn validate(
+        &self,
+        id: Option<room::EntityId>,
+        info: &RoomInfo,
+    ) -> Outcome<(), RoomException> {
+        if !is_valid_name(&info.name, NAME_MAX_LEN) {
+            return Outcome::Ex(RoomException::InvalidName);
+        }
+
+        if info.capacity <= 0 {
+            return Outcome::Ex(RoomException::InvalidCapacity);
+        }
+
+        if self
+            .university_repo
+            .find(info.university_id)
+            .await?
+            .is_none()
+        {
+            return Outcome::Ex(RoomException::UniversityNotFound);
+        }
+
+        if let Some(room) = self
+            .repo
+            .find_by_name(info.university_id, info.name.clone())
+            .await?
+        {
+            if Some(room.id) != id {
+                return Outcome::Ex(RoomException::AlreadyExist);
+            }
+        }
+
+        Outcome::Ok(())
+    }
+}
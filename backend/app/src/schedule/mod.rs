@@ -0,0 +1,288 @@
+use time::{Date, Duration, Time, Weekday};
+use utils::entity::entity;
+
+use crate::{class, room, study_group, teacher};
+
+mod repo;
+
+pub use repo::Repo;
+pub type BoxedRepo = Box<dyn Repo + Send + Sync>;
+
+/// Recurring class of a study group held by a teacher in a room
+#[entity]
+#[derive(Debug, Clone)]
+pub struct Entity {
+    #[id]
+    pub id: i32,
+    pub class_id: class::EntityId,
+    pub study_group_id: study_group::EntityId,
+    pub teacher_id: teacher::EntityId,
+    pub room_id: room::EntityId,
+    pub slot: TimeSlot,
+    pub recurrence: Recurrence,
+    pub valid_from: Date,
+    pub valid_until: Date,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TimeSlot {
+    pub weekday: Weekday,
+    pub starts_at: Time,
+    pub ends_at: Time,
+}
+
+impl TimeSlot {
+    pub fn is_valid(&self) -> bool {
+        self.starts_at < self.ends_at
+    }
+
+    pub fn overlaps(&self, other: &TimeSlot) -> bool {
+        self.weekday == other.weekday
+            && self.starts_at < other.ends_at
+            && other.starts_at < self.ends_at
+    }
+}
+
+/// Bi-weekly classes take place on the week of `valid_from` and every second week after it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Recurrence {
+    Weekly,
+    BiWeekly,
+}
+
+/// Timetables are made for a semester, a year is plenty even for a whole academic year
+pub const MAX_VALID_PERIOD: Duration = Duration::days(366);
+
+/// Monday of the week the date belongs to
+pub fn week_start(date: Date) -> Date {
+    date - Duration::days(date.weekday().number_days_from_monday().into())
+}
+
+/// Periods are bounded, so every date of them can be computed without overflowing
+pub fn is_valid_period(valid_from: Date, valid_until: Date) -> bool {
+    valid_from <= valid_until && valid_until - valid_from <= MAX_VALID_PERIOD
+}
+
+impl Entity {
+    pub fn is_valid_period(&self) -> bool {
+        is_valid_period(self.valid_from, self.valid_until)
+    }
+
+    /// Date of the class on the week starting with `monday`, if it takes place that week
+    pub fn occurrence_in_week(&self, monday: Date) -> Option<Date> {
+        let date = monday.checked_add(Duration::days(
+            self.slot.weekday.number_days_from_monday().into(),
+        ))?;
+        if date < self.valid_from || date > self.valid_until {
+            return None;
+        }
+
+        match self.recurrence {
+            Recurrence::Weekly => Some(date),
+            Recurrence::BiWeekly => {
+                let weeks = (monday - week_start(self.valid_from)).whole_weeks();
+                (weeks % 2 == 0).then_some(date)
+            }
+        }
+    }
+
+    pub fn first_occurrence(&self) -> Option<Date> {
+        first_shared_occurrence(&[self])
+    }
+
+    /// Whether both classes take place at the same time at least once
+    pub fn conflicts_with(&self, other: &Entity) -> bool {
+        self.slot.overlaps(&other.slot) && first_shared_occurrence(&[self, other]).is_some()
+    }
+}
+
+/// First date all the entries take place on, they are expected to share the weekday.
+/// Bi-weekly entries only allow every second week, so the answer is the first matching
+/// date of the common period or the week after it
+fn first_shared_occurrence(entries: &[&Entity]) -> Option<Date> {
+    let from = entries.iter().map(|v| v.valid_from).max()?;
+    let until = entries.iter().map(|v| v.valid_until).min()?;
+    let weekday = entries.first()?.slot.weekday;
+
+    let days_to_weekday = (i64::from(weekday.number_days_from_monday())
+        - i64::from(from.weekday().number_days_from_monday()))
+    .rem_euclid(7);
+    let first = from.checked_add(Duration::days(days_to_weekday))?;
+
+    let mut skipped_weeks = None;
+    for entry in entries
+        .iter()
+        .filter(|v| v.recurrence == Recurrence::BiWeekly)
+    {
+        let parity = (week_start(first) - week_start(entry.valid_from))
+            .whole_weeks()
+            .rem_euclid(2);
+
+        match skipped_weeks {
+            Some(v) if v != parity => return None,
+            _ => skipped_weeks = Some(parity),
+        }
+    }
+
+    let date = first.checked_add(Duration::weeks(skipped_weeks.unwrap_or(0)))?;
+    (date <= until).then_some(date)
+}
+
+#[cfg(test)]
+mod tests {
+    use time::Month;
+
+    use super::*;
+
+    fn date(year: i32, month: Month, day: u8) -> Date {
+        Date::from_calendar_date(year, month, day).unwrap()
+    }
+
+    fn time(hour: u8, minute: u8) -> Time {
+        Time::from_hms(hour, minute, 0).unwrap()
+    }
+
+    fn entry(recurrence: Recurrence, valid_from: Date, valid_until: Date) -> Entity {
+        Entity {
+            id: Default::default(),
+            class_id: Default::default(),
+            study_group_id: Default::default(),
+            teacher_id: Default::default(),
+            room_id: Default::default(),
+            slot: TimeSlot {
+                weekday: Weekday::Wednesday,
+                starts_at: time(10, 10),
+                ends_at: time(11, 40),
+            },
+            recurrence,
+            valid_from,
+            valid_until,
+        }
+    }
+
+    #[test]
+    fn weekly_entries_conflict_when_periods_overlap() {
+        let a = entry(
+            Recurrence::Weekly,
+            date(2024, Month::September, 2),
+            date(2024, Month::December, 29),
+        );
+        let b = entry(
+            Recurrence::Weekly,
+            date(2024, Month::December, 1),
+            date(2025, Month::May, 31),
+        );
+
+        assert!(a.conflicts_with(&b));
+        assert!(b.conflicts_with(&a));
+    }
+
+    #[test]
+    fn overlapping_periods_without_a_shared_weekday_do_not_conflict() {
+        // the only common days are Thursday to Sunday
+        let a = entry(
+            Recurrence::Weekly,
+            date(2024, Month::September, 2),
+            date(2024, Month::September, 8),
+        );
+        let b = entry(
+            Recurrence::Weekly,
+            date(2024, Month::September, 5),
+            date(2024, Month::September, 30),
+        );
+
+        assert!(!a.conflicts_with(&b));
+    }
+
+    #[test]
+    fn bi_weekly_entries_on_alternating_weeks_do_not_conflict() {
+        let until = date(2024, Month::December, 29);
+        let even = entry(Recurrence::BiWeekly, date(2024, Month::September, 2), until);
+        let odd = entry(Recurrence::BiWeekly, date(2024, Month::September, 9), until);
+        let even_later = entry(
+            Recurrence::BiWeekly,
+            date(2024, Month::September, 16),
+            until,
+        );
+
+        assert!(!even.conflicts_with(&odd));
+        assert!(even.conflicts_with(&even_later));
+        // 14 weeks after the start of the odd one
+        let one_week = entry(
+            Recurrence::Weekly,
+            date(2024, Month::December, 16),
+            date(2024, Month::December, 22),
+        );
+        assert!(odd.conflicts_with(&one_week));
+        assert!(!even.conflicts_with(&one_week));
+    }
+
+    #[test]
+    fn bi_weekly_entry_skipping_the_only_shared_week_does_not_conflict() {
+        let bi_weekly = entry(
+            Recurrence::BiWeekly,
+            date(2024, Month::September, 2),
+            date(2024, Month::December, 29),
+        );
+        let one_week = entry(
+            Recurrence::Weekly,
+            date(2024, Month::September, 9),
+            date(2024, Month::September, 15),
+        );
+
+        assert!(!bi_weekly.conflicts_with(&one_week));
+    }
+
+    #[test]
+    fn first_occurrence_follows_recurrence() {
+        let weekly = entry(
+            Recurrence::Weekly,
+            date(2024, Month::September, 5),
+            date(2024, Month::December, 29),
+        );
+        assert_eq!(
+            weekly.first_occurrence(),
+            Some(date(2024, Month::September, 11))
+        );
+
+        let too_short = entry(
+            Recurrence::Weekly,
+            date(2024, Month::September, 5),
+            date(2024, Month::September, 10),
+        );
+        assert_eq!(too_short.first_occurrence(), None);
+    }
+
+    #[test]
+    fn periods_ending_at_the_last_date_do_not_overflow() {
+        let a = entry(Recurrence::Weekly, date(9000, Month::January, 1), Date::MAX);
+        let b = entry(Recurrence::BiWeekly, date(9999, Month::June, 1), Date::MAX);
+
+        assert!(a.conflicts_with(&b));
+        assert!(!a.is_valid_period());
+
+        // the Sunday of the last week is past the last date
+        let sunday = Entity {
+            slot: TimeSlot {
+                weekday: Weekday::Sunday,
+                ..a.slot
+            },
+            ..a
+        };
+        assert_eq!(sunday.occurrence_in_week(week_start(Date::MAX)), None);
+        assert_eq!(
+            sunday.first_occurrence(),
+            Some(date(9000, Month::January, 5))
+        );
+    }
+
+    #[test]
+    fn periods_are_limited_to_a_year() {
+        let from = date(2024, Month::September, 1);
+
+        assert!(is_valid_period(from, from));
+        assert!(is_valid_period(from, date(2025, Month::August, 31)));
+        assert!(!is_valid_period(from, date(2025, Month::October, 1)));
+        assert!(!is_valid_period(from, date(2024, Month::August, 31)));
+    }
+}
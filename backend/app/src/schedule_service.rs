@@ -0,0 +1,345 @@
+use time::Date;
+use utils::{
+    di::{Module, Provide},
+    outcome::Outcome,
+};
+
+use crate::{
+    access_policy::{AccessException, AccessPolicy, Actor},
+    attestation_session, class,
+    grant::Scope,
+    room,
+    schedule::{self, week_start, Recurrence, TimeSlot},
+    study_group,
+    teacher::{self, TeacherClass},
+    AdaptersModule, AppModule,
+};
+
+pub struct ScheduleService {
+    repo: schedule::BoxedRepo,
+    class_repo: class::BoxedRepo,
+    study_group_repo: study_group::BoxedRepo,
+    teacher_repo: teacher::BoxedRepo,
+    room_repo: room::BoxedRepo,
+    attestation_session_repo: attestation_session::BoxedRepo,
+    policy: AccessPolicy,
+}
+
+impl<A: AdaptersModule> Provide<ScheduleService> for AppModule<A> {
+    fn provide(&self) -> ScheduleService {
+        ScheduleService {
+            repo: self.adapters.resolve(),
+            class_repo: self.adapters.resolve(),
+            study_group_repo: self.adapters.resolve(),
+            teacher_repo: self.adapters.resolve(),
+            room_repo: self.adapters.resolve(),
+            attestation_session_repo: self.adapters.resolve(),
+            policy: self.resolve(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, thiserror::Error)]
+pub enum ScheduleException {
+    #[error(transparent)]
+    AccessException(#[from] AccessException),
+    #[error("schedule entry not found")]
+    NotFound,
+    #[error("class not found")]
+    ClassNotFound,
+    #[error("study group not found")]
+    StudyGroupNotFound,
+    #[error("teacher not found")]
+    TeacherNotFound,
+    #[error("room not found")]
+    RoomNotFound,
+    #[error("teacher does not teach the class to the study group")]
+    NotAssigned,
+    #[error("time slot should end after it starts")]
+    InvalidTimeSlot,
+    #[error("validity period should end after it starts and last at most a year")]
+    InvalidPeriod,
+    #[error("teacher is already busy at this time")]
+    TeacherBusy,
+    #[error("room is already occupied at this time")]
+    RoomBusy,
+    #[error("study group already has a class or an attestation at this time")]
+    StudyGroupBusy,
+}
+
+pub struct ScheduleInfo {
+    pub class_id: class::EntityId,
+    pub study_group_id: study_group::EntityId,
+    pub teacher_id: teacher::EntityId,
+    pub room_id: room::EntityId,
+    pub slot: TimeSlot,
+    pub recurrence: Recurrence,
+    pub valid_from: Date,
+    pub valid_until: Date,
+}
+
+impl ScheduleInfo {
+    fn into_entity(self, id: schedule::EntityId) -> schedule::Entity {
+        schedule::Entity {
+            id,
+            class_id: self.class_id,
+            study_group_id: self.study_group_id,
+            teacher_id: self.teacher_id,
+            room_id: self.room_id,
+            slot: self.slot,
+            recurrence: self.recurrence,
+            valid_from: self.valid_from,
+            valid_until: self.valid_until,
+        }
+    }
+}
+
+/// Single class of a week timetable
+pub struct Occurrence {
+    pub date: Date,
+    pub entry: schedule::Entity,
+}
+
+impl ScheduleService {
+    pub async fn create(
+        &mut self,
+        actor: &Actor,
+        info: ScheduleInfo,
+    ) -> Outcome<schedule::Entity, ScheduleException> {
+        self.policy
+            .authorize(actor, Scope::StudyGroup(info.study_group_id))
+            .await?;
+
+        let entry = info.into_entity(Default::default());
+        self.validate(&entry).await?;
+
+        let entry = self.repo.save(entry).await?;
+        Outcome::Ok(entry)
+    }
+
+    /// Validates every entry before saving any, so a failure leaves the timetable untouched
+    pub async fn create_many(
+        &mut self,
+        actor: &Actor,
+        infos: Vec<ScheduleInfo>,
+    ) -> Outcome<Vec<schedule::Entity>, ScheduleException> {
+        let entries = infos
+            .into_iter()
+            .map(|v| v.into_entity(Default::default()))
+            .collect::<Vec<_>>();
+
+        for (i, entry) in entries.iter().enumerate() {
+            self.policy
+                .authorize(actor, Scope::StudyGroup(entry.study_group_id))
+                .await?;
+            self.validate(entry).await?;
+
+            for other in entries[..i].iter().filter(|v| v.conflicts_with(entry)) {
+                if other.teacher_id == entry.teacher_id {
+                    return Outcome::Ex(ScheduleException::TeacherBusy);
+                }
+
+                if other.room_id == entry.room_id {
+                    return Outcome::Ex(ScheduleException::RoomBusy);
+                }
+
+                if other.study_group_id == entry.study_group_id {
+                    return Outcome::Ex(ScheduleException::StudyGroupBusy);
+                }
+            }
+        }
+
+        let mut saved = vec![];
+        for entry in entries {
+            saved.push(self.repo.save(entry).await?);
+        }
+
+        Outcome::Ok(saved)
+    }
+
+    pub async fn update(
+        &mut self,
+        actor: &Actor,
+        id: schedule::EntityId,
+        info: ScheduleInfo,
+    ) -> Outcome<schedule::Entity, ScheduleException> {
+        let entry = self.get(id).await?;
+
+        self.policy
+            .authorize(actor, Scope::StudyGroup(entry.study_group_id))
+            .await?;
+
+        if entry.study_group_id != info.study_group_id {
+            self.policy
+                .authorize(actor, Scope::StudyGroup(info.study_group_id))
+                .await?;
+        }
+
+        let entry = info.into_entity(id);
+        self.validate(&entry).await?;
+
+        let entry = self.repo.save(entry).await?;
+        Outcome::Ok(entry)
+    }
+
+    pub async fn delete(
+        &mut self,
+        actor: &Actor,
+        id: schedule::EntityId,
+    ) -> Outcome<schedule::Entity, ScheduleException> {
+        let entry = self.get(id).await?;
+
+        self.policy
+            .authorize(actor, Scope::StudyGroup(entry.study_group_id))
+            .await?;
+
+        self.repo.delete(&entry).await?;
+        Outcome::Ok(entry)
+    }
+
+    pub async fn get(
+        &self,
+        id: schedule::EntityId,
+    ) -> Outcome<schedule::Entity, ScheduleException> {
+        let Some(entry) = self.repo.find(id).await? else {
+            return Outcome::Ex(ScheduleException::NotFound);
+        };
+
+        Outcome::Ok(entry)
+    }
+
+    /// Classes of the study group on the week containing the date
+    pub async fn week_of_study_group(
+        &self,
+        study_group_id: study_group::EntityId,
+        date: Date,
+    ) -> Outcome<Vec<Occurrence>, ScheduleException> {
+        if self.study_group_repo.find(study_group_id).await?.is_none() {
+            return Outcome::Ex(ScheduleException::StudyGroupNotFound);
+        }
+
+        let entries = self.repo.list_by_study_group(study_group_id).await?;
+        Outcome::Ok(Self::week(entries, date))
+    }
+
+    /// Classes of the teacher on the week containing the date
+    pub async fn week_of_teacher(
+        &self,
+        teacher_id: teacher::EntityId,
+        date: Date,
+    ) -> Outcome<Vec<Occurrence>, ScheduleException> {
+        if self.teacher_repo.find(teacher_id).await?.is_none() {
+            return Outcome::Ex(ScheduleException::TeacherNotFound);
+        }
+
+        let entries = self.repo.list_by_teacher(teacher_id).await?;
+        Outcome::Ok(Self::week(entries, date))
+    }
+
+    /// Classes held in the room on the week containing the date
+    pub async fn week_of_room(
+        &self,
+        room_id: room::EntityId,
+        date: Date,
+    ) -> Outcome<Vec<Occurrence>, ScheduleException> {
+        if self.room_repo.find(room_id).await?.is_none() {
+            return Outcome::Ex(ScheduleException::RoomNotFound);
+        }
+
+        let entries = self.repo.list_by_room(room_id).await?;
+        Outcome::Ok(Self::week(entries, date))
+    }
+
+    fn week(entries: Vec<schedule::Entity>, date: Date) -> Vec<Occurrence> {
+        let monday = week_start(date);
+
+        let mut occurrences = entries
+            .into_iter()
+            .filter_map(|entry| {
+                Some(Occurrence {
+                    date: entry.occurrence_in_week(monday)?,
+                    entry,
+                })
+            })
+            .collect::<Vec<_>>();
+
+        occurrences.sort_by_key(|v| (v.date, v.entry.slot.starts_at, v.entry.id.value));
+        occurrences
+    }
+
+    async fn validate(&self, entry: &schedule::Entity) -> Outcome<(), ScheduleException> {
+        if !entry.slot.is_valid() {
+            return Outcome::Ex(ScheduleException::InvalidTimeSlot);
+        }
+
+        if !entry.is_valid_period() {
+            return Outcome::Ex(ScheduleException::InvalidPeriod);
+        }
+
+        if self.class_repo.find(entry.class_id).await?.is_none() {
+            return Outcome::Ex(ScheduleException::ClassNotFound);
+        }
+
+        if self
+            .study_group_repo
+            .find(entry.study_group_id)
+            .await?
+            .is_none()
+        {
+            return Outcome::Ex(ScheduleException::StudyGroupNotFound);
+        }
+
+        if self.room_repo.find(entry.room_id).await?.is_none() {
+            return Outcome::Ex(ScheduleException::RoomNotFound);
+        }
+
+        let Some(teacher) = self.teacher_repo.find(entry.teacher_id).await? else {
+            return Outcome::Ex(ScheduleException::TeacherNotFound);
+        };
+
+        let assignment = TeacherClass {
+            study_group_id: entry.study_group_id,
+            class_id: entry.class_id,
+        };
+
+        if !teacher.classes.contains(&assignment) {
+            return Outcome::Ex(ScheduleException::NotAssigned);
+        }
+
+        let conflicts = |entries: Vec<schedule::Entity>| {
+            entries
+                .iter()
+                .any(|v| v.id != entry.id && v.conflicts_with(entry))
+        };
+
+        let conflicts_with_sessions = |sessions: Vec<attestation_session::Entity>| {
+            sessions.iter().any(|v| v.conflicts_with(entry))
+        };
+
+        if conflicts(self.repo.list_by_teacher(entry.teacher_id).await?) {
+            return Outcome::Ex(ScheduleException::TeacherBusy);
+        }
+
+        if conflicts(self.repo.list_by_room(entry.room_id).await?)
+            || conflicts_with_sessions(
+                self.attestation_session_repo
+                    .list_by_room(entry.room_id)
+                    .await?,
+            )
+        {
+            return Outcome::Ex(ScheduleException::RoomBusy);
+        }
+
+        if conflicts(self.repo.list_by_study_group(entry.study_group_id).await?)
+            || conflicts_with_sessions(
+                self.attestation_session_repo
+                    .list_by_study_group(entry.study_group_id)
+                    .await?,
+            )
+        {
+            return Outcome::Ex(ScheduleException::StudyGroupBusy);
+        }
+
+        Outcome::Ok(())
+    }
+}
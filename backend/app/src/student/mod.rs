@@ -9,18 +9,37 @@ mod repo;
 pub use repo::Repo;
 pub type BoxedRepo = Box<dyn Repo + Send + Sync>;
 
-#[derive(Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 #[entity]
 pub struct Entity {
     #[id]
     pub id: i32,
     pub person_id: person::EntityId,
+    /// Current study group, earlier ones are kept in the student orders
     pub study_group_id: study_group::EntityId,
+    pub status: StudentStatus,
     pub attestations: HashSet<StudentAttestation>,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StudentStatus {
+    Active,
+    OnLeave,
+    Expelled,
+    Graduated,
+}
+
+impl StudentStatus {
+    /// Expelled and graduated students are no longer members of their study group
+    pub fn is_enrolled(&self) -> bool {
+        matches!(self, StudentStatus::Active | StudentStatus::OnLeave)
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct StudentAttestation {
     pub attestation_id: attestation::EntityId,
     pub score: i32,
+    /// Finalized scores are locked and can't be amended
+    pub is_final: bool,
 }
@@ -0,0 +1,624 @@
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::Display,
+};
+
+use time::{Date, Month};
+use utils::{
+    di::{Module, Provide},
+    outcome::Outcome,
+};
+
+use crate::{
+    access_policy::{AccessException, AccessPolicy, Actor},
+    grant::Scope,
+    passport::{self, Gender, PassportNumber, PassportSeries},
+    person,
+    person_service::{PassportInfo, PersonException, PersonService, FULL_NAME_MAX_LEN},
+    student,
+    student_service::{
+        is_valid_reason, EnrollmentInfo, StudentException, StudentInfo, StudentService,
+    },
+    study_group,
+    token::BoxedRefreshTokenGenerator,
+    user::{self, Role},
+    user_service::{UserException, UserService},
+    validation::is_valid_name,
+    AdaptersModule, AppModule,
+};
+
+const EMAIL_MAX_LEN: usize = 256;
+const DEFAULT_REASON: &str = "enrolled by admissions import";
+
+pub struct StudentImportService {
+    user_service: UserService,
+    person_service: PersonService,
+    student_service: StudentService,
+    user_repo: user::BoxedRepo,
+    passport_repo: passport::BoxedRepo,
+    study_group_repo: study_group::BoxedRepo,
+    /// Initial passwords of rows without one, students are expected to change them
+    password_generator: BoxedRefreshTokenGenerator,
+    policy: AccessPolicy,
+}
+
+impl<A: AdaptersModule> Provide<StudentImportService> for AppModule<A> {
+    fn provide(&self) -> StudentImportService {
+        StudentImportService {
+            user_service: self.resolve(),
+            person_service: self.resolve(),
+            student_service: self.resolve(),
+            user_repo: self.adapters.resolve(),
+            passport_repo: self.adapters.resolve(),
+            study_group_repo: self.adapters.resolve(),
+            password_generator: self.adapters.resolve(),
+            policy: self.resolve(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, thiserror::Error)]
+pub enum StudentImportException {
+    #[error(transparent)]
+    AccessException(#[from] AccessException),
+    #[error("spreadsheet misses required columns: {}", column_list(.0))]
+    MissingColumns(Vec<Column>),
+    #[error(transparent)]
+    UserException(#[from] UserException),
+    #[error(transparent)]
+    PersonException(#[from] PersonException),
+    #[error(transparent)]
+    StudentException(#[from] StudentException),
+}
+
+/// Problem with a single row, rows with problems are skipped
+#[derive(Debug, Clone, thiserror::Error)]
+pub enum RowException {
+    #[error("{0} is required")]
+    Missing(Column),
+    #[error("{0} should be a date like 2005-08-31 or 31.08.2005")]
+    InvalidDate(Column),
+    #[error("gender should be male or female")]
+    InvalidGender,
+    #[error("invalid email")]
+    InvalidEmail,
+    #[error("passport series should consist of 4 digits")]
+    InvalidPassportSeries,
+    #[error("passport number should consist of 6 digits")]
+    InvalidPassportNumber,
+    #[error("invalid passport, names should be filled in and the date of birth should precede the date of issue")]
+    InvalidPassport,
+    #[error("invalid full name")]
+    InvalidFullName,
+    #[error("reason should be at most 1024 characters long")]
+    InvalidReason,
+    #[error("study group {0} not found")]
+    StudyGroupNotFound(String),
+    #[error("email already in use")]
+    EmailAlreadyInUse,
+    #[error("passport already exist")]
+    PassportAlreadyExist,
+    #[error("email repeats the one of row {0}")]
+    DuplicateEmail(usize),
+    #[error("passport repeats the one of row {0}")]
+    DuplicatePassport(usize),
+}
+
+/// Columns of an admissions spreadsheet, recognized by their names or russian headers
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Column {
+    Email,
+    Password,
+    FullName,
+    LastName,
+    FirstName,
+    Patronymic,
+    Gender,
+    DateOfBirth,
+    PassportSeries,
+    PassportNumber,
+    DateOfIssue,
+    StudyGroup,
+    EnrollmentDate,
+    Reason,
+}
+
+impl Column {
+    pub const ALL: [Column; 14] = [
+        Column::Email,
+        Column::Password,
+        Column::FullName,
+        Column::LastName,
+        Column::FirstName,
+        Column::Patronymic,
+        Column::Gender,
+        Column::DateOfBirth,
+        Column::PassportSeries,
+        Column::PassportNumber,
+        Column::DateOfIssue,
+        Column::StudyGroup,
+        Column::EnrollmentDate,
+        Column::Reason,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Column::Email => "email",
+            Column::Password => "password",
+            Column::FullName => "full_name",
+            Column::LastName => "last_name",
+            Column::FirstName => "first_name",
+            Column::Patronymic => "patronymic",
+            Column::Gender => "gender",
+            Column::DateOfBirth => "date_of_birth",
+            Column::PassportSeries => "passport_series",
+            Column::PassportNumber => "passport_number",
+            Column::DateOfIssue => "date_of_issue",
+            Column::StudyGroup => "study_group",
+            Column::EnrollmentDate => "enrollment_date",
+            Column::Reason => "reason",
+        }
+    }
+
+    fn aliases(&self) -> &'static [&'static str] {
+        match self {
+            Column::Email => &["электронная_почта", "e-mail", "почта"],
+            Column::Password => &["пароль"],
+            Column::FullName => &["фио"],
+            Column::LastName => &["фамилия"],
+            Column::FirstName => &["имя"],
+            Column::Patronymic => &["отчество"],
+            Column::Gender => &["пол"],
+            Column::DateOfBirth => &["дата_рождения"],
+            Column::PassportSeries => &["серия_паспорта", "серия"],
+            Column::PassportNumber => &["номер_паспорта", "номер"],
+            Column::DateOfIssue => &["дата_выдачи"],
+            Column::StudyGroup => &["группа", "учебная_группа"],
+            Column::EnrollmentDate => &["дата_зачисления"],
+            Column::Reason => &["основание"],
+        }
+    }
+
+    /// Optional columns fall back to generated passwords, names joined from the passport,
+    /// the import date and a default reason
+    pub fn is_required(&self) -> bool {
+        !matches!(
+            self,
+            Column::Password
+                | Column::FullName
+                | Column::Patronymic
+                | Column::EnrollmentDate
+                | Column::Reason
+        )
+    }
+
+    /// Headers are matched ignoring case, surrounding whitespace and spaces in place of underscores
+    pub fn from_header(header: &str) -> Option<Column> {
+        let header = header.trim().to_lowercase().replace(' ', "_");
+
+        Column::ALL
+            .into_iter()
+            .find(|v| v.name() == header || v.aliases().contains(&header.as_str()))
+    }
+}
+
+impl Display for Column {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+fn column_list(columns: &[Column]) -> String {
+    columns
+        .iter()
+        .map(Column::name)
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Cells of a spreadsheet row keyed by the headers of the first row
+pub struct ImportRow {
+    /// Number of the row in the spreadsheet, the header row is the first one
+    pub number: usize,
+    pub cells: HashMap<String, String>,
+}
+
+pub struct RowError {
+    pub row: usize,
+    pub error: RowException,
+}
+
+pub struct ImportedStudent {
+    pub row: usize,
+    pub user: user::Entity,
+    pub person: person::Entity,
+    pub student: student::Entity,
+    /// Generated password for rows without one
+    pub initial_password: Option<String>,
+}
+
+pub struct ImportReport {
+    pub rows: usize,
+    /// Rows with errors are skipped, the rest are imported
+    pub errors: Vec<RowError>,
+    /// Empty on dry runs
+    pub students: Vec<ImportedStudent>,
+}
+
+/// Row that passed validation
+struct ParsedRow {
+    number: usize,
+    email: String,
+    password: Option<String>,
+    full_name: String,
+    passport: PassportInfo,
+    study_group_id: study_group::EntityId,
+    enrollment: EnrollmentInfo,
+}
+
+impl StudentImportService {
+    /// Creates users, persons, passports and enrolled students from admissions rows.
+    /// All rows are validated before anything is written, invalid rows are reported and skipped.
+    /// The valid ones are written in the transaction of the caller, which should roll it back
+    /// when the import fails half way, or the students written before the failure would remain
+    pub async fn import(
+        &mut self,
+        actor: &Actor,
+        rows: Vec<ImportRow>,
+        today: Date,
+        dry_run: bool,
+    ) -> Outcome<ImportReport, StudentImportException> {
+        let columns = rows
+            .iter()
+            .flat_map(|v| v.cells.keys())
+            .filter_map(|v| Column::from_header(v))
+            .collect::<HashSet<_>>();
+        let missing = Column::ALL
+            .into_iter()
+            .filter(|v| v.is_required() && !columns.contains(v))
+            .collect::<Vec<_>>();
+
+        if !rows.is_empty() && !missing.is_empty() {
+            return Outcome::Ex(StudentImportException::MissingColumns(missing));
+        }
+
+        let mut report = ImportReport {
+            rows: rows.len(),
+            errors: vec![],
+            students: vec![],
+        };
+
+        let mut study_groups = HashMap::new();
+        let mut emails = HashMap::new();
+        let mut passports = HashMap::new();
+        let mut parsed = vec![];
+
+        for row in rows {
+            let mut errors = vec![];
+            let cells = row
+                .cells
+                .into_iter()
+                .filter_map(|(k, v)| Some((Column::from_header(&k)?, v.trim().to_owned())))
+                .filter(|(_, v)| !v.is_empty())
+                .collect::<HashMap<_, _>>();
+
+            for column in Column::ALL {
+                if column.is_required() && !cells.contains_key(&column) {
+                    errors.push(RowException::Missing(column));
+                }
+            }
+
+            let cell = |column| cells.get(&column).cloned().unwrap_or_default();
+            let date = |column, errors: &mut Vec<_>| {
+                let value = cells.get(&column)?;
+                let date = parse_date(value);
+                if date.is_none() {
+                    errors.push(RowException::InvalidDate(column));
+                }
+                date
+            };
+
+            let date_of_birth = date(Column::DateOfBirth, &mut errors);
+            let date_of_issue = date(Column::DateOfIssue, &mut errors);
+            let enrollment_date = match cells.contains_key(&Column::EnrollmentDate) {
+                true => date(Column::EnrollmentDate, &mut errors),
+                false => Some(today),
+            };
+
+            let gender = match cells.get(&Column::Gender) {
+                Some(v) => {
+                    let gender = parse_gender(v);
+                    if gender.is_none() {
+                        errors.push(RowException::InvalidGender);
+                    }
+                    gender
+                }
+                None => None,
+            };
+
+            let series = match cells.get(&Column::PassportSeries) {
+                Some(v) => {
+                    let series = v.parse::<PassportSeries>().ok();
+                    if series.is_none() {
+                        errors.push(RowException::InvalidPassportSeries);
+                    }
+                    series
+                }
+                None => None,
+            };
+
+            let number = match cells.get(&Column::PassportNumber) {
+                Some(v) => {
+                    let number = v.parse::<PassportNumber>().ok();
+                    if number.is_none() {
+                        errors.push(RowException::InvalidPassportNumber);
+                    }
+                    number
+                }
+                None => None,
+            };
+
+            // existing users and passports are only looked up for the study groups the actor manages
+            let mut study_group_id = None;
+            if let Some(name) = cells.get(&Column::StudyGroup) {
+                if !study_groups.contains_key(name) {
+                    let study_group = self.study_group_repo.find_by_name(name.clone()).await?;
+                    if let Some(study_group) = &study_group {
+                        self.policy
+                            .authorize(actor, Scope::StudyGroup(study_group.id))
+                            .await?;
+                    }
+                    study_groups.insert(name.clone(), study_group.map(|v| v.id));
+                }
+
+                study_group_id = study_groups[name];
+                if study_group_id.is_none() {
+                    errors.push(RowException::StudyGroupNotFound(name.clone()));
+                }
+            }
+
+            let email = cell(Column::Email).to_lowercase();
+            if !email.is_empty() {
+                if !is_valid_email(&email) {
+                    errors.push(RowException::InvalidEmail);
+                } else if let Some(other) = emails.get(&email) {
+                    errors.push(RowException::DuplicateEmail(*other));
+                } else {
+                    emails.insert(email.clone(), row.number);
+
+                    if study_group_id.is_some()
+                        && self.user_repo.find_by_email(email.clone()).await?.is_some()
+                    {
+                        errors.push(RowException::EmailAlreadyInUse);
+                    }
+                }
+            }
+
+            let full_name = match cells.get(&Column::FullName) {
+                Some(v) => v.clone(),
+                None => [
+                    cell(Column::LastName),
+                    cell(Column::FirstName),
+                    cell(Column::Patronymic),
+                ]
+                .into_iter()
+                .filter(|v| !v.is_empty())
+                .collect::<Vec<_>>()
+                .join(" "),
+            };
+            if !full_name.is_empty() && !is_valid_name(&full_name, FULL_NAME_MAX_LEN) {
+                errors.push(RowException::InvalidFullName);
+            }
+
+            let reason = cells
+                .get(&Column::Reason)
+                .cloned()
+                .unwrap_or_else(|| DEFAULT_REASON.to_owned());
+            if !is_valid_reason(&reason) {
+                errors.push(RowException::InvalidReason);
+            }
+
+            let passport = match (date_of_birth, date_of_issue, gender, series, number) {
+                (
+                    Some(date_of_birth),
+                    Some(date_of_issue),
+                    Some(gender),
+                    Some(series),
+                    Some(number),
+                ) => Some(PassportInfo {
+                    first_name: cell(Column::FirstName),
+                    last_name: cell(Column::LastName),
+                    patronymic: cell(Column::Patronymic),
+                    date_of_birth,
+                    date_of_issue,
+                    number,
+                    series,
+                    gender,
+                }),
+                _ => None,
+            };
+
+            if let Some(passport) = &passport {
+                if !passport.is_valid() {
+                    errors.push(RowException::InvalidPassport);
+                }
+
+                let key = (passport.series, passport.number);
+                if let Some(other) = passports.get(&key) {
+                    errors.push(RowException::DuplicatePassport(*other));
+                } else {
+                    passports.insert(key, row.number);
+
+                    if study_group_id.is_some()
+                        && self
+                            .passport_repo
+                            .find_by_number_series(passport.number, passport.series)
+                            .await?
+                            .is_some()
+                    {
+                        errors.push(RowException::PassportAlreadyExist);
+                    }
+                }
+            }
+
+            match (passport, study_group_id, enrollment_date) {
+                (Some(passport), Some(study_group_id), Some(date)) if errors.is_empty() => parsed
+                    .push(ParsedRow {
+                        number: row.number,
+                        email,
+                        password: cells.get(&Column::Password).cloned(),
+                        full_name,
+                        passport,
+                        study_group_id,
+                        enrollment: EnrollmentInfo { date, reason },
+                    }),
+                _ => report
+                    .errors
+                    .extend(errors.into_iter().map(|error| RowError {
+                        row: row.number,
+                        error,
+                    })),
+            }
+        }
+
+        if dry_run {
+            return Outcome::Ok(report);
+        }
+
+        for row in parsed {
+            let (password, initial_password) = match row.password {
+                Some(password) => (password, None),
+                None => {
+                    let password = self.password_generator.generate().await?;
+                    (password.clone(), Some(password))
+                }
+            };
+
+            let user = self
+                .user_service
+                .create(row.email, password, Role::Student)
+                .await?;
+
+            let person = self
+                .person_service
+                .create(actor, user.id, row.full_name)
+                .await?;
+
+            self.person_service
+                .add_passport(actor, person.id, row.passport)
+                .await?;
+
+            let student = self
+                .student_service
+                .create(
+                    actor,
+                    StudentInfo {
+                        person_id: person.id,
+                        study_group_id: row.study_group_id,
+                    },
+                    row.enrollment,
+                )
+                .await?;
+
+            report.students.push(ImportedStudent {
+                row: row.number,
+                user,
+                person,
+                student,
+                initial_password,
+            });
+        }
+
+        Outcome::Ok(report)
+    }
+}
+
+fn is_valid_email(email: &str) -> bool {
+    let Some((name, domain)) = email.split_once('@') else {
+        return false;
+    };
+
+    !name.is_empty()
+        && domain.contains('.')
+        && !domain.starts_with('.')
+        && !domain.ends_with('.')
+        && !email.chars().any(char::is_whitespace)
+        && email.chars().count() <= EMAIL_MAX_LEN
+}
+
+/// Parses `2005-08-31` and `31.08.2005` dates
+fn parse_date(value: &str) -> Option<Date> {
+    let (year, month, day) = if let [year, month, day] = value.split('-').collect::<Vec<_>>()[..] {
+        (year, month, day)
+    } else if let [day, month, year] = value.split('.').collect::<Vec<_>>()[..] {
+        (year, month, day)
+    } else {
+        return None;
+    };
+
+    if year.len() != 4 {
+        return None;
+    }
+
+    let month = Month::try_from(month.parse::<u8>().ok()?).ok()?;
+    Date::from_calendar_date(year.parse().ok()?, month, day.parse().ok()?).ok()
+}
+
+fn parse_gender(value: &str) -> Option<Gender> {
+    match value.to_lowercase().as_str() {
+        "male" | "m" | "м" | "муж" | "мужской" => Some(Gender::Male),
+        "female" | "f" | "ж" | "жен" | "женский" => Some(Gender::Female),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn headers_are_matched_by_names_and_aliases() {
+        assert_eq!(Column::from_header("email"), Some(Column::Email));
+        assert_eq!(
+            Column::from_header(" Date of Birth "),
+            Some(Column::DateOfBirth)
+        );
+        assert_eq!(
+            Column::from_header("Дата выдачи"),
+            Some(Column::DateOfIssue)
+        );
+        assert_eq!(Column::from_header("ФИО"), Some(Column::FullName));
+        assert_eq!(Column::from_header("комментарий"), None);
+    }
+
+    #[test]
+    fn dates_are_parsed_in_both_notations() {
+        let date = Date::from_calendar_date(2005, Month::August, 31).unwrap();
+
+        assert_eq!(parse_date("2005-08-31"), Some(date));
+        assert_eq!(parse_date("31.08.2005"), Some(date));
+        assert_eq!(parse_date("31.8.2005"), Some(date));
+        assert_eq!(parse_date("31.08.05"), None);
+        assert_eq!(parse_date("2005-02-30"), None);
+        assert_eq!(parse_date("2005/08/31"), None);
+        assert_eq!(parse_date(""), None);
+    }
+
+    #[test]
+    fn genders_are_parsed_ignoring_case() {
+        assert_eq!(parse_gender("М"), Some(Gender::Male));
+        assert_eq!(parse_gender("Female"), Some(Gender::Female));
+        assert_eq!(parse_gender("женский"), Some(Gender::Female));
+        assert_eq!(parse_gender("x"), None);
+    }
+
+    #[test]
+    fn emails_need_a_name_and_a_dotted_domain() {
+        assert!(is_valid_email("d.churikov@stud.nstu.ru"));
+        assert!(!is_valid_email("d.churikov"));
+        assert!(!is_valid_email("@stud.nstu.ru"));
+        assert!(!is_valid_email("d.churikov@localhost"));
+        assert!(!is_valid_email("d.churikov@.ru"));
+        assert!(!is_valid_email("d churikov@stud.nstu.ru"));
+    }
+}
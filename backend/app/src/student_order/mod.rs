@@ -0,0 +1,74 @@
+use time::Date;
+use utils::entity::entity;
+
+use crate::{
+    student::{self, StudentStatus},
+    study_group, user,
+};
+
+mod repo;
+
+pub use repo::Repo;
+pub type BoxedRepo = Box<dyn Repo + Send + Sync>;
+
+/// Order of the university that changes the status or the study group of a student
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[entity]
+pub struct Entity {
+    #[id]
+    pub id: i32,
+    pub student_id: student::EntityId,
+    pub kind: OrderKind,
+    /// Study group of the student after the order
+    pub study_group_id: study_group::EntityId,
+    pub date: Date,
+    pub reason: String,
+    /// `None` for orders recorded before authors were tracked or whose author was removed
+    pub author_id: Option<user::EntityId>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OrderKind {
+    Enrollment,
+    Transfer,
+    AcademicLeave,
+    ReturnFromLeave,
+    Expulsion,
+    Graduation,
+}
+
+impl OrderKind {
+    /// Status of the student after the order
+    pub fn status(&self) -> StudentStatus {
+        match self {
+            OrderKind::Enrollment | OrderKind::Transfer | OrderKind::ReturnFromLeave => {
+                StudentStatus::Active
+            }
+            OrderKind::AcademicLeave => StudentStatus::OnLeave,
+            OrderKind::Expulsion => StudentStatus::Expelled,
+            OrderKind::Graduation => StudentStatus::Graduated,
+        }
+    }
+
+    /// Whether the order can be issued for a student with the status, enrollment creates students
+    pub fn applies_to(&self, status: StudentStatus) -> bool {
+        match self {
+            OrderKind::Enrollment => false,
+            OrderKind::Transfer | OrderKind::AcademicLeave | OrderKind::Graduation => {
+                status == StudentStatus::Active
+            }
+            OrderKind::ReturnFromLeave => status == StudentStatus::OnLeave,
+            OrderKind::Expulsion => {
+                status == StudentStatus::Active || status == StudentStatus::OnLeave
+            }
+        }
+    }
+}
+
+/// Last of the orders issued on or before the date, it defines the study group and the status of the student then
+pub fn effective_on(orders: &[Entity], date: Date) -> Option<&Entity> {
+    orders
+        .iter()
+        .filter(|v| v.date <= date)
+        .max_by_key(|v| (v.date, v.id.value))
+}
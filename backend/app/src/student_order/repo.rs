@@ -0,0 +1,34 @@
+use std::collections::HashSet;
+
+use crate::{student, study_group};
+
+use super::{Entity, EntityId};
+
+#[async_trait::async_trait]
+pub trait Repo {
+    async fn save(&mut self, entity: Entity) -> Result<Entity, anyhow::Error>;
+
+    async fn find(&self, id: EntityId) -> Result<Option<Entity>, anyhow::Error>;
+
+    async fn list_by_student(
+        &self,
+        student_id: student::EntityId,
+    ) -> Result<Vec<Entity>, anyhow::Error>;
+
+    /// Loads the orders of all the given students in one round-trip
+    async fn list_by_students(
+        &self,
+        students_ids: HashSet<student::EntityId>,
+    ) -> Result<Vec<Entity>, anyhow::Error>;
+
+    /// Orders that moved students into the study group or kept them there
+    async fn list_by_study_group(
+        &self,
+        study_group_id: study_group::EntityId,
+    ) -> Result<Vec<Entity>, anyhow::Error>;
+
+    async fn delete_by_student(
+        &mut self,
+        student_id: student::EntityId,
+    ) -> Result<(), anyhow::Error>;
+}
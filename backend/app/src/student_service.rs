@@ -0,0 +1,366 @@
+use std::collections::{HashMap, HashSet};
+
+use time::Date;
+use utils::{
+    di::{Module, Provide},
+    outcome::Outcome,
+};
+
+use crate::{
+    access_policy::{AccessException, AccessPolicy, Actor},
+    grant::Scope,
+    person,
+    student::{self, StudentStatus},
+    student_order::{self, OrderKind},
+    study_group, AdaptersModule, AppModule,
+};
+
+const MAX_REASON_LEN: usize = 1024;
+
+pub struct StudentService {
+    repo: student::BoxedRepo,
+    person_repo: person::BoxedRepo,
+    study_group_repo: study_group::BoxedRepo,
+    order_repo: student_order::BoxedRepo,
+    policy: AccessPolicy,
+}
+
+impl<A: AdaptersModule> Provide<StudentService> for AppModule<A> {
+    fn provide(&self) -> StudentService {
+        StudentService {
+            repo: self.adapters.resolve(),
+            person_repo: self.adapters.resolve(),
+            study_group_repo: self.adapters.resolve(),
+            order_repo: self.adapters.resolve(),
+            policy: self.resolve(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, thiserror::Error)]
+pub enum StudentException {
+    #[error(transparent)]
+    AccessException(#[from] AccessException),
+    #[error("student not found")]
+    NotFound,
+    #[error("person is already a student of the study group")]
+    AlreadyExist,
+    #[error("person not found")]
+    PersonNotFound,
+    #[error("study group not found")]
+    StudyGroupNotFound,
+    #[error("study group of a student can only be changed by a transfer order")]
+    TransferRequired,
+    #[error("{kind:?} order can't be issued for a student in {status:?} status")]
+    InvalidTransition {
+        kind: OrderKind,
+        status: StudentStatus,
+    },
+    #[error("transfer order requires another study group")]
+    InvalidStudyGroup,
+    #[error("order date can't be earlier than the date of the previous order")]
+    InvalidDate,
+    #[error("order reason should be non empty and at most 1024 characters long")]
+    InvalidReason,
+}
+
+pub struct StudentInfo {
+    pub person_id: person::EntityId,
+    pub study_group_id: study_group::EntityId,
+}
+
+/// Enrollment order that creates the student
+pub struct EnrollmentInfo {
+    pub date: Date,
+    pub reason: String,
+}
+
+pub struct OrderInfo {
+    pub kind: OrderKind,
+    /// Study group to transfer to or to return from the academic leave to, the current one when omitted
+    pub study_group_id: Option<study_group::EntityId>,
+    pub date: Date,
+    pub reason: String,
+}
+
+/// Student of a study group on a date
+pub struct RosterEntry {
+    pub student: student::Entity,
+    pub status: StudentStatus,
+    /// Date of the order that put the student in the study group with the status
+    pub since: Date,
+}
+
+impl StudentService {
+    pub async fn create(
+        &mut self,
+        actor: &Actor,
+        info: StudentInfo,
+        enrollment: EnrollmentInfo,
+    ) -> Outcome<student::Entity, StudentException> {
+        self.policy
+            .authorize(actor, Scope::StudyGroup(info.study_group_id))
+            .await?;
+        self.validate(None, &info).await?;
+        validate_reason(&enrollment.reason)?;
+
+        let student = student::Entity {
+            id: Default::default(),
+            person_id: info.person_id,
+            study_group_id: info.study_group_id,
+            status: StudentStatus::Active,
+            attestations: Default::default(),
+        };
+
+        let student = self.repo.save(student).await?;
+
+        self.order_repo
+            .save(student_order::Entity {
+                id: Default::default(),
+                student_id: student.id,
+                kind: OrderKind::Enrollment,
+                study_group_id: student.study_group_id,
+                date: enrollment.date,
+                reason: enrollment.reason,
+                author_id: Some(actor.user_id),
+            })
+            .await?;
+
+        Outcome::Ok(student)
+    }
+
+    /// Corrects the person of the student, keeping the scores.
+    /// Study group changes are recorded with transfer orders
+    pub async fn update(
+        &mut self,
+        actor: &Actor,
+        id: student::EntityId,
+        info: StudentInfo,
+    ) -> Outcome<student::Entity, StudentException> {
+        let student = self.get(id).await?;
+
+        self.policy
+            .authorize(actor, Scope::StudyGroup(student.study_group_id))
+            .await?;
+
+        if student.study_group_id != info.study_group_id {
+            return Outcome::Ex(StudentException::TransferRequired);
+        }
+
+        self.validate(Some(id), &info).await?;
+
+        let student = student::Entity {
+            person_id: info.person_id,
+            study_group_id: info.study_group_id,
+            ..student
+        };
+
+        let student = self.repo.save(student).await?;
+        Outcome::Ok(student)
+    }
+
+    pub async fn delete(
+        &mut self,
+        actor: &Actor,
+        id: student::EntityId,
+    ) -> Outcome<student::Entity, StudentException> {
+        let student = self.get(id).await?;
+
+        self.policy
+            .authorize(actor, Scope::StudyGroup(student.study_group_id))
+            .await?;
+
+        self.order_repo.delete_by_student(student.id).await?;
+        self.repo.delete(&student).await?;
+        Outcome::Ok(student)
+    }
+
+    /// Records the order and applies it to the student
+    pub async fn issue_order(
+        &mut self,
+        actor: &Actor,
+        id: student::EntityId,
+        info: OrderInfo,
+    ) -> Outcome<(student::Entity, student_order::Entity), StudentException> {
+        let student = self.get(id).await?;
+
+        self.policy
+            .authorize(actor, Scope::StudyGroup(student.study_group_id))
+            .await?;
+
+        if !info.kind.applies_to(student.status) {
+            return Outcome::Ex(StudentException::InvalidTransition {
+                kind: info.kind,
+                status: student.status,
+            });
+        }
+
+        let study_group_id = match (info.kind, info.study_group_id) {
+            (OrderKind::Transfer, Some(v)) if v != student.study_group_id => v,
+            (OrderKind::Transfer, _) => return Outcome::Ex(StudentException::InvalidStudyGroup),
+            (OrderKind::ReturnFromLeave, Some(v)) => v,
+            (_, Some(v)) if v != student.study_group_id => {
+                return Outcome::Ex(StudentException::InvalidStudyGroup)
+            }
+            _ => student.study_group_id,
+        };
+
+        if study_group_id != student.study_group_id {
+            self.policy
+                .authorize(actor, Scope::StudyGroup(study_group_id))
+                .await?;
+            self.validate(
+                Some(id),
+                &StudentInfo {
+                    person_id: student.person_id,
+                    study_group_id,
+                },
+            )
+            .await?;
+        }
+
+        validate_reason(&info.reason)?;
+
+        let orders = self.order_repo.list_by_student(id).await?;
+        if orders.iter().any(|v| v.date > info.date) {
+            return Outcome::Ex(StudentException::InvalidDate);
+        }
+
+        let order = self
+            .order_repo
+            .save(student_order::Entity {
+                id: Default::default(),
+                student_id: id,
+                kind: info.kind,
+                study_group_id,
+                date: info.date,
+                reason: info.reason,
+                author_id: Some(actor.user_id),
+            })
+            .await?;
+
+        let student = self
+            .repo
+            .save(student::Entity {
+                study_group_id,
+                status: info.kind.status(),
+                ..student
+            })
+            .await?;
+
+        Outcome::Ok((student, order))
+    }
+
+    /// Orders of the student in the order they were issued
+    pub async fn orders(
+        &self,
+        id: student::EntityId,
+    ) -> Outcome<Vec<student_order::Entity>, StudentException> {
+        let student = self.get(id).await?;
+
+        let mut orders = self.order_repo.list_by_student(student.id).await?;
+        orders.sort_by_key(|v| (v.date, v.id.value));
+
+        Outcome::Ok(orders)
+    }
+
+    /// Students studying in the study group or on academic leave from it on the date
+    pub async fn roster(
+        &self,
+        study_group_id: study_group::EntityId,
+        date: Date,
+    ) -> Outcome<Vec<RosterEntry>, StudentException> {
+        if self.study_group_repo.find(study_group_id).await?.is_none() {
+            return Outcome::Ex(StudentException::StudyGroupNotFound);
+        }
+
+        let candidates = self
+            .order_repo
+            .list_by_study_group(study_group_id)
+            .await?
+            .into_iter()
+            .map(|v| v.student_id)
+            .collect::<HashSet<_>>();
+
+        let mut orders = HashMap::<_, Vec<_>>::new();
+        for order in self.order_repo.list_by_students(candidates.clone()).await? {
+            orders.entry(order.student_id).or_default().push(order);
+        }
+
+        let mut roster = vec![];
+        for student in self.repo.find_many(candidates).await? {
+            let Some(order) = orders
+                .get(&student.id)
+                .and_then(|v| student_order::effective_on(v, date))
+            else {
+                continue;
+            };
+
+            let status = order.kind.status();
+            if order.study_group_id != study_group_id || !status.is_enrolled() {
+                continue;
+            }
+
+            roster.push(RosterEntry {
+                since: order.date,
+                status,
+                student,
+            });
+        }
+        roster.sort_by_key(|v| v.student.id.value);
+
+        Outcome::Ok(roster)
+    }
+
+    pub async fn get(&self, id: student::EntityId) -> Outcome<student::Entity, StudentException> {
+        let Some(student) = self.repo.find(id).await? else {
+            return Outcome::Ex(StudentException::NotFound);
+        };
+
+        Outcome::Ok(student)
+    }
+
+    async fn validate(
+        &self,
+        id: Option<student::EntityId>,
+        info: &StudentInfo,
+    ) -> Outcome<(), StudentException> {
+        if self.person_repo.find(info.person_id).await?.is_none() {
+            return Outcome::Ex(StudentException::PersonNotFound);
+        }
+
+        if self
+            .study_group_repo
+            .find(info.study_group_id)
+            .await?
+            .is_none()
+        {
+            return Outcome::Ex(StudentException::StudyGroupNotFound);
+        }
+
+        let is_already_exist = self
+            .repo
+            .list_by_person(info.person_id)
+            .await?
+            .into_iter()
+            .any(|v| v.study_group_id == info.study_group_id && Some(v.id) != id);
+
+        if is_already_exist {
+            return Outcome::Ex(StudentException::AlreadyExist);
+        }
+
+        Outcome::Ok(())
+    }
+}
+
+pub(crate) fn is_valid_reason(reason: &str) -> bool {
+    !reason.trim().is_empty() && reason.chars().count() <= MAX_REASON_LEN
+}
+
+fn validate_reason(reason: &str) -> Outcome<(), StudentException> {
+    if !is_valid_reason(reason) {
+        return Outcome::Ex(StudentException::InvalidReason);
+    }
+
+    Outcome::Ok(())
+}
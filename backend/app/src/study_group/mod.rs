@@ -19,9 +19,26 @@ pub struct Entity {
     pub training_kind: TrainingKind,
     pub department_id: subdivision::EntityId,
     pub curriculums: HashSet<curriculum::EntityId>,
+    /// Calendar year the group started studying in, the first semester is in its academic year
+    pub enrollment_year: Option<i32>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
+pub struct ListFilter {
+    pub name_prefix: Option<String>,
+    pub qualification: Option<Qualification>,
+    pub training_kind: Option<TrainingKind>,
+    pub department_id: Option<subdivision::EntityId>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SortKey {
+    #[default]
+    Id,
+    Name,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Qualification {
     Bachelor,
     Master,
@@ -29,7 +46,7 @@ pub enum Qualification {
     Doctorate,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum TrainingKind {
     FullTime,
     Correspondence,
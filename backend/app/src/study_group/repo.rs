@@ -1,8 +1,12 @@
 use std::collections::HashSet;
 
-use crate::curriculum;
+use crate::{
+    curriculum,
+    paging::{Page, Paginated, Sort},
+    subdivision,
+};
 
-use super::{Entity, EntityId};
+use super::{Entity, EntityId, ListFilter, SortKey};
 
 #[async_trait::async_trait]
 pub trait Repo {
@@ -12,9 +16,22 @@ pub trait Repo {
 
     async fn find(&self, id: EntityId) -> Result<Option<Entity>, anyhow::Error>;
 
+    /// Loads all entities with the given ids in one round-trip, missing ids are skipped
+    async fn find_many(&self, ids: HashSet<EntityId>) -> Result<Vec<Entity>, anyhow::Error>;
+
     async fn find_by_name(&self, name: String) -> Result<Option<Entity>, anyhow::Error>;
 
-    async fn list(&self) -> Result<Vec<Entity>, anyhow::Error>;
+    async fn list(
+        &self,
+        filter: ListFilter,
+        sort: Sort<SortKey>,
+        page: Page,
+    ) -> Result<Paginated<Entity>, anyhow::Error>;
+
+    async fn list_by_department(
+        &self,
+        department_id: subdivision::EntityId,
+    ) -> Result<Vec<Entity>, anyhow::Error>;
 
     async fn list_by_curriculums(
         &self,
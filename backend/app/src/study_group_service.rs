@@ -0,0 +1,232 @@
+use std::collections::HashSet;
+
+use utils::{
+    di::{Module, Provide},
+    outcome::Outcome,
+};
+
+use crate::{
+    access_policy::{AccessException, AccessPolicy, Actor},
+    attestation_session,
+    curriculum::{self, CurriculumStatus},
+    grant::{self, Scope},
+    schedule, student, student_order,
+    study_group::{self, Qualification, TrainingKind},
+    subdivision,
+    validation::is_valid_name,
+    AdaptersModule, AppModule,
+};
+
+const NAME_MAX_LEN: usize = 256;
+
+pub struct StudyGroupService {
+    repo: study_group::BoxedRepo,
+    subdivision_repo: subdivision::BoxedRepo,
+    curriculum_repo: curriculum::BoxedRepo,
+    student_repo: student::BoxedRepo,
+    student_order_repo: student_order::BoxedRepo,
+    schedule_repo: schedule::BoxedRepo,
+    attestation_session_repo: attestation_session::BoxedRepo,
+    grant_repo: grant::BoxedRepo,
+    policy: AccessPolicy,
+}
+
+impl<A: AdaptersModule> Provide<StudyGroupService> for AppModule<A> {
+    fn provide(&self) -> StudyGroupService {
+        StudyGroupService {
+            repo: self.adapters.resolve(),
+            subdivision_repo: self.adapters.resolve(),
+            curriculum_repo: self.adapters.resolve(),
+            student_repo: self.adapters.resolve(),
+            student_order_repo: self.adapters.resolve(),
+            schedule_repo: self.adapters.resolve(),
+            attestation_session_repo: self.adapters.resolve(),
+            grant_repo: self.adapters.resolve(),
+            policy: self.resolve(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, thiserror::Error)]
+pub enum StudyGroupException {
+    #[error(transparent)]
+    AccessException(#[from] AccessException),
+    #[error("study group not found")]
+    NotFound,
+    #[error("invalid study group name")]
+    InvalidName,
+    #[error("study group already exist")]
+    AlreadyExist,
+    #[error("enrollment year should be a positive number")]
+    InvalidEnrollmentYear,
+    #[error("department not found")]
+    DepartmentNotFound,
+    #[error("curriculum not found")]
+    CurriculumNotFound,
+    #[error("only published curriculums can be assigned to study groups")]
+    InvalidCurriculum,
+    #[error("study group has students")]
+    InUse,
+    #[error("study group has classes or attestations in the timetable")]
+    Scheduled,
+}
+
+pub struct StudyGroupInfo {
+    pub name: String,
+    pub studying_qualification: Qualification,
+    pub training_kind: TrainingKind,
+    pub department_id: subdivision::EntityId,
+    pub curriculums: HashSet<curriculum::EntityId>,
+    pub enrollment_year: Option<i32>,
+}
+
+impl StudyGroupInfo {
+    fn into_entity(self, id: study_group::EntityId) -> study_group::Entity {
+        study_group::Entity {
+            id,
+            name: self.name,
+            studying_qualification: self.studying_qualification,
+            training_kind: self.training_kind,
+            department_id: self.department_id,
+            curriculums: self.curriculums,
+            enrollment_year: self.enrollment_year,
+        }
+    }
+}
+
+impl StudyGroupService {
+    pub async fn create(
+        &mut self,
+        actor: &Actor,
+        info: StudyGroupInfo,
+    ) -> Outcome<study_group::Entity, StudyGroupException> {
+        self.policy
+            .authorize(actor, Scope::Subdivision(info.department_id))
+            .await?;
+        self.validate(None, &info).await?;
+
+        let study_group = self.repo.save(info.into_entity(Default::default())).await?;
+        Outcome::Ok(study_group)
+    }
+
+    pub async fn update(
+        &mut self,
+        actor: &Actor,
+        id: study_group::EntityId,
+        info: StudyGroupInfo,
+    ) -> Outcome<study_group::Entity, StudyGroupException> {
+        let study_group = self.get(id).await?;
+
+        self.policy.authorize(actor, Scope::StudyGroup(id)).await?;
+
+        if study_group.department_id != info.department_id {
+            self.policy
+                .authorize(actor, Scope::Subdivision(info.department_id))
+                .await?;
+        }
+
+        self.validate(Some(id), &info).await?;
+
+        let study_group = self.repo.save(info.into_entity(id)).await?;
+        Outcome::Ok(study_group)
+    }
+
+    pub async fn delete(
+        &mut self,
+        actor: &Actor,
+        id: study_group::EntityId,
+    ) -> Outcome<study_group::Entity, StudyGroupException> {
+        let study_group = self.get(id).await?;
+
+        self.policy.authorize(actor, Scope::StudyGroup(id)).await?;
+
+        if !self.student_repo.list_by_study_group(id).await?.is_empty()
+            || !self
+                .student_order_repo
+                .list_by_study_group(id)
+                .await?
+                .is_empty()
+        {
+            return Outcome::Ex(StudyGroupException::InUse);
+        }
+
+        if !self.schedule_repo.list_by_study_group(id).await?.is_empty()
+            || !self
+                .attestation_session_repo
+                .list_by_study_group(id)
+                .await?
+                .is_empty()
+        {
+            return Outcome::Ex(StudyGroupException::Scheduled);
+        }
+
+        self.grant_repo
+            .delete_by_scope(Scope::StudyGroup(id))
+            .await?;
+        self.repo.delete(&study_group).await?;
+
+        Outcome::Ok(study_group)
+    }
+
+    async fn get(
+        &self,
+        id: study_group::EntityId,
+    ) -> Outcome<study_group::Entity, StudyGroupException> {
+        let Some(study_group) = self.repo.find(id).await? else {
+            return Outcome::Ex(StudyGroupException::NotFound);
+        };
+
+        Outcome::Ok(study_group)
+    }
+
+    async fn validate(
+        &self,
+        id: Option<study_group::EntityId>,
+        info: &StudyGroupInfo,
+    ) -> Outcome<(), StudyGroupException> {
+        if !is_valid_name(&info.name, NAME_MAX_LEN) {
+            return Outcome::Ex(StudyGroupException::InvalidName);
+        }
+
+        if let Some(study_group) = self.repo.find_by_name(info.name.clone()).await? {
+            if Some(study_group.id) != id {
+                return Outcome::Ex(StudyGroupException::AlreadyExist);
+            }
+        }
+
+        if info.enrollment_year.is_some_and(|v| v <= 0) {
+            return Outcome::Ex(StudyGroupException::InvalidEnrollmentYear);
+        }
+
+        if self
+            .subdivision_repo
+            .find(info.department_id)
+            .await?
+            .is_none()
+        {
+            return Outcome::Ex(StudyGroupException::DepartmentNotFound);
+        }
+
+        let assigned = match id {
+            Some(id) => self.get(id).await?.curriculums,
+            None => HashSet::new(),
+        };
+
+        for curriculum_id in info.curriculums.iter() {
+            let Some(curriculum) = self.curriculum_repo.find(*curriculum_id).await? else {
+                return Outcome::Ex(StudyGroupException::CurriculumNotFound);
+            };
+
+            // groups keep curriculums assigned before they were published or archived
+            if assigned.contains(curriculum_id) {
+                continue;
+            }
+
+            if curriculum.status != CurriculumStatus::Published {
+                return Outcome::Ex(StudyGroupException::InvalidCurriculum);
+            }
+        }
+
+        Outcome::Ok(())
+    }
+}
@@ -15,10 +15,26 @@ pub struct Entity {
     pub id: i32,
     pub name: String,
     pub university_id: university::EntityId,
+    pub parent_id: Option<EntityId>,
     pub tags: HashSet<tag::EntityId>,
     pub members: HashSet<Member>,
 }
 
+#[derive(Debug, Clone, Default)]
+pub struct ListFilter {
+    pub name_prefix: Option<String>,
+    pub university_id: Option<university::EntityId>,
+    pub parent_id: Option<EntityId>,
+    pub tag: Option<tag::EntityId>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SortKey {
+    #[default]
+    Id,
+    Name,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Member {
     pub person_id: person::EntityId,
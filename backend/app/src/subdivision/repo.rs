@@ -1,8 +1,11 @@
 use std::collections::HashSet;
 
-use crate::{person, tag, university};
+use crate::{
+    paging::{Page, Paginated, Sort},
+    person, tag, university,
+};
 
-use super::{Entity, EntityId};
+use super::{Entity, EntityId, ListFilter, SortKey};
 
 #[async_trait::async_trait]
 pub trait Repo {
@@ -14,7 +17,12 @@ pub trait Repo {
 
     async fn find_by_name(&self, name: String) -> Result<Option<Entity>, anyhow::Error>;
 
-    async fn list(&self) -> Result<Vec<Entity>, anyhow::Error>;
+    async fn list(
+        &self,
+        filter: ListFilter,
+        sort: Sort<SortKey>,
+        page: Page,
+    ) -> Result<Paginated<Entity>, anyhow::Error>;
 
     async fn list_by_university(
         &self,
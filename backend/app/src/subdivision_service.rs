@@ -0,0 +1,286 @@
+use std::collections::HashSet;
+
+use utils::{
+    di::{Module, Provide},
+    outcome::Outcome,
+};
+
+use crate::{
+    access_policy::{AccessException, AccessPolicy, Actor},
+    curriculum, discipline,
+    grant::{self, Scope},
+    person, study_group, subdivision, tag, teacher, university,
+    validation::is_valid_name,
+    AdaptersModule, AppModule,
+};
+
+const NAME_MAX_LEN: usize = 256;
+const MEMBER_ROLE_MAX_LEN: usize = 512;
+
+pub struct SubdivisionService {
+    repo: subdivision::BoxedRepo,
+    university_repo: university::BoxedRepo,
+    tag_repo: tag::BoxedRepo,
+    person_repo: person::BoxedRepo,
+    study_group_repo: study_group::BoxedRepo,
+    teacher_repo: teacher::BoxedRepo,
+    discipline_repo: discipline::BoxedRepo,
+    curriculum_repo: curriculum::BoxedRepo,
+    grant_repo: grant::BoxedRepo,
+    policy: AccessPolicy,
+}
+
+impl<A: AdaptersModule> Provide<SubdivisionService> for AppModule<A> {
+    fn provide(&self) -> SubdivisionService {
+        SubdivisionService {
+            repo: self.adapters.resolve(),
+            university_repo: self.adapters.resolve(),
+            tag_repo: self.adapters.resolve(),
+            person_repo: self.adapters.resolve(),
+            study_group_repo: self.adapters.resolve(),
+            teacher_repo: self.adapters.resolve(),
+            discipline_repo: self.adapters.resolve(),
+            curriculum_repo: self.adapters.resolve(),
+            grant_repo: self.adapters.resolve(),
+            policy: self.resolve(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, thiserror::Error)]
+pub enum SubdivisionException {
+    #[error(transparent)]
+    AccessException(#[from] AccessException),
+    #[error("subdivision not found")]
+    NotFound,
+    #[error("invalid subdivision name")]
+    InvalidName,
+    #[error("subdivision already exist")]
+    AlreadyExist,
+    #[error("university not found")]
+    UniversityNotFound,
+    #[error("parent subdivision not found")]
+    ParentNotFound,
+    #[error("parent subdivision should belong to the same university and not be a descendant")]
+    InvalidParent,
+    #[error("tag not found")]
+    TagNotFound,
+    #[error("person not found")]
+    PersonNotFound,
+    #[error("invalid member role")]
+    InvalidMemberRole,
+    #[error(
+        "subdivision has child subdivisions, study groups, teachers, disciplines or curriculums"
+    )]
+    InUse,
+}
+
+pub struct SubdivisionInfo {
+    pub name: String,
+    pub university_id: university::EntityId,
+    pub parent_id: Option<subdivision::EntityId>,
+    pub tags: HashSet<tag::EntityId>,
+    pub members: HashSet<subdivision::Member>,
+}
+
+impl SubdivisionInfo {
+    fn location_scope(&self) -> Scope {
+        match self.parent_id {
+            Some(parent_id) => Scope::Subdivision(parent_id),
+            None => Scope::University(self.university_id),
+        }
+    }
+
+    fn into_entity(self, id: subdivision::EntityId) -> subdivision::Entity {
+        subdivision::Entity {
+            id,
+            name: self.name,
+            university_id: self.university_id,
+            parent_id: self.parent_id,
+            tags: self.tags,
+            members: self.members,
+        }
+    }
+}
+
+impl SubdivisionService {
+    pub async fn create(
+        &mut self,
+        actor: &Actor,
+        info: SubdivisionInfo,
+    ) -> Outcome<subdivision::Entity, SubdivisionException> {
+        self.policy.authorize(actor, info.location_scope()).await?;
+        self.validate(None, &info).await?;
+
+        let subdivision = self.repo.save(info.into_entity(Default::default())).await?;
+        Outcome::Ok(subdivision)
+    }
+
+    pub async fn update(
+        &mut self,
+        actor: &Actor,
+        id: subdivision::EntityId,
+        info: SubdivisionInfo,
+    ) -> Outcome<subdivision::Entity, SubdivisionException> {
+        let subdivision = self.get(id).await?;
+
+        self.policy.authorize(actor, Scope::Subdivision(id)).await?;
+
+        let is_moved = subdivision.parent_id != info.parent_id
+            || subdivision.university_id != info.university_id;
+        if is_moved {
+            self.policy.authorize(actor, info.location_scope()).await?;
+        }
+
+        self.validate(Some(id), &info).await?;
+
+        let subdivision = self.repo.save(info.into_entity(id)).await?;
+        Outcome::Ok(subdivision)
+    }
+
+    pub async fn delete(
+        &mut self,
+        actor: &Actor,
+        id: subdivision::EntityId,
+    ) -> Outcome<subdivision::Entity, SubdivisionException> {
+        let subdivision = self.get(id).await?;
+
+        self.policy.authorize(actor, Scope::Subdivision(id)).await?;
+
+        let has_children = self
+            .repo
+            .list_by_university(subdivision.university_id)
+            .await?
+            .into_iter()
+            .any(|v| v.parent_id == Some(id));
+
+        let is_in_use = has_children
+            || !self
+                .study_group_repo
+                .list_by_department(id)
+                .await?
+                .is_empty()
+            || !self
+                .teacher_repo
+                .list_by_department_id(id)
+                .await?
+                .is_empty()
+            || !self
+                .discipline_repo
+                .list_by_department_id(id)
+                .await?
+                .is_empty()
+            || !self
+                .curriculum_repo
+                .list_by_department_id(id)
+                .await?
+                .is_empty();
+
+        if is_in_use {
+            return Outcome::Ex(SubdivisionException::InUse);
+        }
+
+        self.grant_repo
+            .delete_by_scope(Scope::Subdivision(id))
+            .await?;
+        self.repo.delete(&subdivision).await?;
+
+        Outcome::Ok(subdivision)
+    }
+
+    async fn get(
+        &self,
+        id: subdivision::EntityId,
+    ) -> Outcome<subdivision::Entity, SubdivisionException> {
+        let Some(subdivision) = self.repo.find(id).await? else {
+            return Outcome::Ex(SubdivisionException::NotFound);
+        };
+
+        Outcome::Ok(subdivision)
+    }
+
+    async fn validate(
+        &self,
+        id: Option<subdivision::EntityId>,
+        info: &SubdivisionInfo,
+    ) -> Outcome<(), SubdivisionException> {
+        if !is_valid_name(&info.name, NAME_MAX_LEN) {
+            return Outcome::Ex(SubdivisionException::InvalidName);
+        }
+
+        if self
+            .university_repo
+            .find(info.university_id)
+            .await?
+            .is_none()
+        {
+            return Outcome::Ex(SubdivisionException::UniversityNotFound);
+        }
+
+        let is_name_taken = self
+            .repo
+            .list_by_university(info.university_id)
+            .await?
+            .into_iter()
+            .any(|v| v.name == info.name && Some(v.id) != id);
+
+        if is_name_taken {
+            return Outcome::Ex(SubdivisionException::AlreadyExist);
+        }
+
+        self.validate_parent(id, info).await?;
+
+        for tag_id in info.tags.iter() {
+            if self.tag_repo.find(tag_id.clone()).await?.is_none() {
+                return Outcome::Ex(SubdivisionException::TagNotFound);
+            }
+        }
+
+        for member in info.members.iter() {
+            if !is_valid_name(&member.role, MEMBER_ROLE_MAX_LEN) {
+                return Outcome::Ex(SubdivisionException::InvalidMemberRole);
+            }
+
+            if self.person_repo.find(member.person_id).await?.is_none() {
+                return Outcome::Ex(SubdivisionException::PersonNotFound);
+            }
+        }
+
+        Outcome::Ok(())
+    }
+
+    /// Walks up from the new parent to the root to make sure the tree stays acyclic
+    async fn validate_parent(
+        &self,
+        id: Option<subdivision::EntityId>,
+        info: &SubdivisionInfo,
+    ) -> Outcome<(), SubdivisionException> {
+        let Some(parent_id) = info.parent_id else {
+            return Outcome::Ok(());
+        };
+
+        let Some(parent) = self.repo.find(parent_id).await? else {
+            return Outcome::Ex(SubdivisionException::ParentNotFound);
+        };
+
+        if parent.university_id != info.university_id {
+            return Outcome::Ex(SubdivisionException::InvalidParent);
+        }
+
+        let mut visited = HashSet::new();
+        let mut next_id = Some(parent_id);
+
+        while let Some(ancestor_id) = next_id {
+            if Some(ancestor_id) == id || !visited.insert(ancestor_id) {
+                return Outcome::Ex(SubdivisionException::InvalidParent);
+            }
+
+            next_id = match self.repo.find(ancestor_id).await? {
+                Some(ancestor) => ancestor.parent_id,
+                None => None,
+            };
+        }
+
+        Outcome::Ok(())
+    }
+}
@@ -0,0 +1,99 @@
+use std::collections::HashSet;
+
+use utils::{
+    di::{Module, Provide},
+    entity::Id,
+    outcome::Outcome,
+};
+
+use crate::{
+    access_policy::{AccessException, AccessPolicy, Actor},
+    grant::Scope,
+    subdivision, tag,
+    validation::is_valid_name,
+    AdaptersModule, AppModule,
+};
+
+const NAME_MAX_LEN: usize = 128;
+
+pub struct TagService {
+    repo: tag::BoxedRepo,
+    subdivision_repo: subdivision::BoxedRepo,
+    policy: AccessPolicy,
+}
+
+impl<A: AdaptersModule> Provide<TagService> for AppModule<A> {
+    fn provide(&self) -> TagService {
+        TagService {
+            repo: self.adapters.resolve(),
+            subdivision_repo: self.adapters.resolve(),
+            policy: self.resolve(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, thiserror::Error)]
+pub enum TagException {
+    #[error(transparent)]
+    AccessException(#[from] AccessException),
+    #[error("tag not found")]
+    NotFound,
+    #[error("invalid tag name")]
+    InvalidName,
+    #[error("tag already exist")]
+    AlreadyExist,
+    #[error("tag is used by subdivisions")]
+    InUse,
+}
+
+/// Tags are a dictionary shared by every university, so only global managers can edit it
+impl TagService {
+    pub async fn create(
+        &mut self,
+        actor: &Actor,
+        name: String,
+    ) -> Outcome<tag::Entity, TagException> {
+        self.policy.authorize(actor, Scope::Global).await?;
+
+        if !is_valid_name(&name, NAME_MAX_LEN) {
+            return Outcome::Ex(TagException::InvalidName);
+        }
+
+        if self.repo.find(Id::new(name.clone())).await?.is_some() {
+            return Outcome::Ex(TagException::AlreadyExist);
+        }
+
+        let tag = self
+            .repo
+            .save(tag::Entity {
+                name: Id::new(name),
+            })
+            .await?;
+        Outcome::Ok(tag)
+    }
+
+    pub async fn delete(
+        &mut self,
+        actor: &Actor,
+        name: tag::EntityId,
+    ) -> Outcome<tag::Entity, TagException> {
+        let Some(tag) = self.repo.find(name.clone()).await? else {
+            return Outcome::Ex(TagException::NotFound);
+        };
+
+        self.policy.authorize(actor, Scope::Global).await?;
+
+        let is_in_use = !self
+            .subdivision_repo
+            .list_by_tags(HashSet::from([name]))
+            .await?
+            .is_empty();
+
+        if is_in_use {
+            return Outcome::Ex(TagException::InUse);
+        }
+
+        self.repo.delete(&tag).await?;
+        Outcome::Ok(tag)
+    }
+}
@@ -26,7 +26,7 @@ pub struct TeacherClass {
     pub class_id: class::EntityId,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum TeacherKind {
     Assistant,
     RegularTeacher,
@@ -1,4 +1,4 @@
-use crate::{person, subdivision};
+use crate::{class, person, schedule::TimeSlot, subdivision};
 
 use super::{Entity, EntityId};
 
@@ -19,4 +19,15 @@ pub trait Repo {
         &self,
         department_id: subdivision::EntityId,
     ) -> Result<Vec<Entity>, anyhow::Error>;
+
+    async fn list_by_class(&self, class_id: class::EntityId) -> Result<Vec<Entity>, anyhow::Error>;
+
+    /// Windows of the week the teacher prefers to teach in, empty when there is no preference
+    async fn availability(&self, id: EntityId) -> Result<Vec<TimeSlot>, anyhow::Error>;
+
+    async fn set_availability(
+        &mut self,
+        id: EntityId,
+        windows: Vec<TimeSlot>,
+    ) -> Result<(), anyhow::Error>;
 }
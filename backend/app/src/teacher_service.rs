@@ -0,0 +1,252 @@
+use std::collections::HashSet;
+
+use utils::{
+    di::{Module, Provide},
+    outcome::Outcome,
+};
+
+use crate::{
+    access_policy::{AccessException, AccessPolicy, Actor},
+    attestation, class,
+    grant::Scope,
+    person,
+    schedule::{self, TimeSlot},
+    study_group, subdivision,
+    teacher::{self, TeacherClass, TeacherKind},
+    AdaptersModule, AppModule,
+};
+
+pub struct TeacherService {
+    repo: teacher::BoxedRepo,
+    person_repo: person::BoxedRepo,
+    subdivision_repo: subdivision::BoxedRepo,
+    class_repo: class::BoxedRepo,
+    study_group_repo: study_group::BoxedRepo,
+    attestation_repo: attestation::BoxedRepo,
+    schedule_repo: schedule::BoxedRepo,
+    policy: AccessPolicy,
+}
+
+impl<A: AdaptersModule> Provide<TeacherService> for AppModule<A> {
+    fn provide(&self) -> TeacherService {
+        TeacherService {
+            repo: self.adapters.resolve(),
+            person_repo: self.adapters.resolve(),
+            subdivision_repo: self.adapters.resolve(),
+            class_repo: self.adapters.resolve(),
+            study_group_repo: self.adapters.resolve(),
+            attestation_repo: self.adapters.resolve(),
+            schedule_repo: self.adapters.resolve(),
+            policy: self.resolve(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, thiserror::Error)]
+pub enum TeacherException {
+    #[error(transparent)]
+    AccessException(#[from] AccessException),
+    #[error("teacher not found")]
+    NotFound,
+    #[error("person is already a teacher")]
+    AlreadyExist,
+    #[error("person not found")]
+    PersonNotFound,
+    #[error("department not found")]
+    DepartmentNotFound,
+    #[error("class not found")]
+    ClassNotFound,
+    #[error("study group not found")]
+    StudyGroupNotFound,
+    #[error("teacher is an examiner of attestations")]
+    InUse,
+    #[error("teacher has classes in the timetable")]
+    Scheduled,
+    #[error("availability windows should end after they start")]
+    InvalidAvailability,
+}
+
+pub struct TeacherInfo {
+    pub person_id: person::EntityId,
+    pub kind: TeacherKind,
+    pub department_id: subdivision::EntityId,
+    pub classes: HashSet<TeacherClass>,
+}
+
+impl TeacherInfo {
+    fn into_entity(self, id: teacher::EntityId) -> teacher::Entity {
+        teacher::Entity {
+            id,
+            person_id: self.person_id,
+            kind: self.kind,
+            department_id: self.department_id,
+            classes: self.classes,
+        }
+    }
+}
+
+impl TeacherService {
+    pub async fn create(
+        &mut self,
+        actor: &Actor,
+        info: TeacherInfo,
+    ) -> Outcome<teacher::Entity, TeacherException> {
+        self.policy
+            .authorize(actor, Scope::Subdivision(info.department_id))
+            .await?;
+        self.validate(None, &info).await?;
+
+        let teacher = self.repo.save(info.into_entity(Default::default())).await?;
+        Outcome::Ok(teacher)
+    }
+
+    pub async fn update(
+        &mut self,
+        actor: &Actor,
+        id: teacher::EntityId,
+        info: TeacherInfo,
+    ) -> Outcome<teacher::Entity, TeacherException> {
+        let teacher = self.get(id).await?;
+
+        self.policy
+            .authorize(actor, Scope::Subdivision(teacher.department_id))
+            .await?;
+
+        if teacher.department_id != info.department_id {
+            self.policy
+                .authorize(actor, Scope::Subdivision(info.department_id))
+                .await?;
+        }
+
+        self.validate(Some(id), &info).await?;
+
+        let is_unassigned_scheduled =
+            self.schedule_repo
+                .list_by_teacher(id)
+                .await?
+                .iter()
+                .any(|v| {
+                    !info.classes.contains(&TeacherClass {
+                        study_group_id: v.study_group_id,
+                        class_id: v.class_id,
+                    })
+                });
+
+        if is_unassigned_scheduled {
+            return Outcome::Ex(TeacherException::Scheduled);
+        }
+
+        let teacher = self.repo.save(info.into_entity(id)).await?;
+        Outcome::Ok(teacher)
+    }
+
+    pub async fn delete(
+        &mut self,
+        actor: &Actor,
+        id: teacher::EntityId,
+    ) -> Outcome<teacher::Entity, TeacherException> {
+        let teacher = self.get(id).await?;
+
+        self.policy
+            .authorize(actor, Scope::Subdivision(teacher.department_id))
+            .await?;
+
+        let is_in_use = !self
+            .attestation_repo
+            .list_by_examiners(HashSet::from([id]))
+            .await?
+            .is_empty();
+
+        if is_in_use {
+            return Outcome::Ex(TeacherException::InUse);
+        }
+
+        if !self.schedule_repo.list_by_teacher(id).await?.is_empty() {
+            return Outcome::Ex(TeacherException::Scheduled);
+        }
+
+        self.repo.delete(&teacher).await?;
+        Outcome::Ok(teacher)
+    }
+
+    pub async fn get(&self, id: teacher::EntityId) -> Outcome<teacher::Entity, TeacherException> {
+        let Some(teacher) = self.repo.find(id).await? else {
+            return Outcome::Ex(TeacherException::NotFound);
+        };
+
+        Outcome::Ok(teacher)
+    }
+
+    pub async fn availability(
+        &self,
+        id: teacher::EntityId,
+    ) -> Outcome<Vec<TimeSlot>, TeacherException> {
+        self.get(id).await?;
+
+        let windows = self.repo.availability(id).await?;
+        Outcome::Ok(windows)
+    }
+
+    /// Replaces the availability windows, the timetable generator tries to keep classes inside them
+    pub async fn set_availability(
+        &mut self,
+        actor: &Actor,
+        id: teacher::EntityId,
+        windows: Vec<TimeSlot>,
+    ) -> Outcome<Vec<TimeSlot>, TeacherException> {
+        let teacher = self.get(id).await?;
+
+        self.policy
+            .authorize(actor, Scope::Subdivision(teacher.department_id))
+            .await?;
+
+        if !windows.iter().all(TimeSlot::is_valid) {
+            return Outcome::Ex(TeacherException::InvalidAvailability);
+        }
+
+        self.repo.set_availability(id, windows.clone()).await?;
+        Outcome::Ok(windows)
+    }
+
+    async fn validate(
+        &self,
+        id: Option<teacher::EntityId>,
+        info: &TeacherInfo,
+    ) -> Outcome<(), TeacherException> {
+        if self.person_repo.find(info.person_id).await?.is_none() {
+            return Outcome::Ex(TeacherException::PersonNotFound);
+        }
+
+        if let Some(teacher) = self.repo.find_by_person_id(info.person_id).await? {
+            if Some(teacher.id) != id {
+                return Outcome::Ex(TeacherException::AlreadyExist);
+            }
+        }
+
+        if self
+            .subdivision_repo
+            .find(info.department_id)
+            .await?
+            .is_none()
+        {
+            return Outcome::Ex(TeacherException::DepartmentNotFound);
+        }
+
+        for class in info.classes.iter() {
+            if self.class_repo.find(class.class_id).await?.is_none() {
+                return Outcome::Ex(TeacherException::ClassNotFound);
+            }
+
+            if self
+                .study_group_repo
+                .find(class.study_group_id)
+                .await?
+                .is_none()
+            {
+                return Outcome::Ex(TeacherException::StudyGroupNotFound);
+            }
+        }
+
+        Outcome::Ok(())
+    }
+}
@@ -0,0 +1,387 @@
+use std::collections::{BTreeMap, HashMap};
+
+use utils::{
+    di::{Module, Provide},
+    outcome::Outcome,
+};
+
+use crate::{
+    access_policy::{AccessException, AccessPolicy, Actor},
+    attestation::Hours,
+    class, class_kind, curriculum_module,
+    grant::Scope,
+    load_limit::{self, LoadStatus},
+    person, study_group, subdivision,
+    teacher::{self, TeacherKind},
+    university, AdaptersModule, AppModule,
+};
+
+pub struct TeachingLoadService {
+    load_limit_repo: load_limit::BoxedRepo,
+    teacher_repo: teacher::BoxedRepo,
+    person_repo: person::BoxedRepo,
+    class_repo: class::BoxedRepo,
+    curriculum_module_repo: curriculum_module::BoxedRepo,
+    subdivision_repo: subdivision::BoxedRepo,
+    university_repo: university::BoxedRepo,
+    policy: AccessPolicy,
+}
+
+impl<A: AdaptersModule> Provide<TeachingLoadService> for AppModule<A> {
+    fn provide(&self) -> TeachingLoadService {
+        TeachingLoadService {
+            load_limit_repo: self.adapters.resolve(),
+            teacher_repo: self.adapters.resolve(),
+            person_repo: self.adapters.resolve(),
+            class_repo: self.adapters.resolve(),
+            curriculum_module_repo: self.adapters.resolve(),
+            subdivision_repo: self.adapters.resolve(),
+            university_repo: self.adapters.resolve(),
+            policy: self.resolve(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, thiserror::Error)]
+pub enum TeachingLoadException {
+    #[error(transparent)]
+    AccessException(#[from] AccessException),
+    #[error("university not found")]
+    UniversityNotFound,
+    #[error("department not found")]
+    DepartmentNotFound,
+    #[error("teacher not found")]
+    TeacherNotFound,
+    #[error("load limit not found")]
+    LimitNotFound,
+    #[error("load limit hours should not be negative and min hours should not exceed max hours")]
+    InvalidLimit,
+}
+
+/// Planned hours of a teacher in every semester the teacher has classes in
+pub struct TeacherLoad {
+    pub teacher_id: teacher::EntityId,
+    pub kind: TeacherKind,
+    pub semesters: Vec<SemesterLoad>,
+}
+
+pub struct SemesterLoad {
+    pub semester: i32,
+    pub hours: i32,
+    pub by_class_kind: Vec<(class_kind::EntityId, i32)>,
+    pub by_study_group: Vec<(study_group::EntityId, i32)>,
+    pub limit: Option<load_limit::Entity>,
+    /// `None` when the university has no limit for the kind of the teacher
+    pub status: Option<LoadStatus>,
+}
+
+pub struct DepartmentLoad {
+    pub department_id: subdivision::EntityId,
+    pub teachers: Vec<TeacherLoad>,
+    pub semesters: Vec<DepartmentSemesterLoad>,
+}
+
+/// Hours of all teachers of the department in a semester
+pub struct DepartmentSemesterLoad {
+    pub semester: i32,
+    pub hours: i32,
+    pub by_class_kind: Vec<(class_kind::EntityId, i32)>,
+    pub underloaded: usize,
+    pub overloaded: usize,
+}
+
+pub struct LoadLimitInfo {
+    pub min_hours: i32,
+    pub max_hours: i32,
+}
+
+impl TeachingLoadService {
+    pub async fn limits(
+        &self,
+        university_id: university::EntityId,
+    ) -> Outcome<Vec<load_limit::Entity>, TeachingLoadException> {
+        self.ensure_university(university_id).await?;
+
+        let mut limits = self
+            .load_limit_repo
+            .list_by_university(university_id)
+            .await?;
+        limits.sort_by_key(|v| v.id.value);
+
+        Outcome::Ok(limits)
+    }
+
+    pub async fn set_limit(
+        &mut self,
+        actor: &Actor,
+        university_id: university::EntityId,
+        teacher_kind: TeacherKind,
+        info: LoadLimitInfo,
+    ) -> Outcome<load_limit::Entity, TeachingLoadException> {
+        self.ensure_university(university_id).await?;
+        self.policy
+            .authorize(actor, Scope::University(university_id))
+            .await?;
+
+        let id = match self.find_limit(university_id, teacher_kind).await? {
+            Some(limit) => limit.id,
+            None => Default::default(),
+        };
+
+        let limit = load_limit::Entity {
+            id,
+            university_id,
+            teacher_kind,
+            min_hours: Hours(info.min_hours),
+            max_hours: Hours(info.max_hours),
+        };
+
+        if !limit.is_valid() {
+            return Outcome::Ex(TeachingLoadException::InvalidLimit);
+        }
+
+        let limit = self.load_limit_repo.save(limit).await?;
+        Outcome::Ok(limit)
+    }
+
+    pub async fn remove_limit(
+        &mut self,
+        actor: &Actor,
+        university_id: university::EntityId,
+        teacher_kind: TeacherKind,
+    ) -> Outcome<load_limit::Entity, TeachingLoadException> {
+        self.ensure_university(university_id).await?;
+        self.policy
+            .authorize(actor, Scope::University(university_id))
+            .await?;
+
+        let Some(limit) = self.find_limit(university_id, teacher_kind).await? else {
+            return Outcome::Ex(TeachingLoadException::LimitNotFound);
+        };
+
+        self.load_limit_repo.delete(&limit).await?;
+        Outcome::Ok(limit)
+    }
+
+    /// Visible to the teacher and to the department managers
+    pub async fn teacher_load(
+        &self,
+        actor: &Actor,
+        teacher_id: teacher::EntityId,
+    ) -> Outcome<TeacherLoad, TeachingLoadException> {
+        let Some(teacher) = self.teacher_repo.find(teacher_id).await? else {
+            return Outcome::Ex(TeachingLoadException::TeacherNotFound);
+        };
+
+        let is_own = self
+            .person_repo
+            .find(teacher.person_id)
+            .await?
+            .is_some_and(|v| v.user_id == actor.user_id);
+
+        if !is_own {
+            self.policy
+                .authorize(actor, Scope::Subdivision(teacher.department_id))
+                .await?;
+        }
+
+        let limits = match self.subdivision_repo.find(teacher.department_id).await? {
+            Some(department) => self.limits_by_kind(department.university_id).await?,
+            None => HashMap::new(),
+        };
+
+        let load = self.load(&teacher, &limits, &mut Cache::default()).await?;
+        Outcome::Ok(load)
+    }
+
+    pub async fn department_load(
+        &self,
+        actor: &Actor,
+        department_id: subdivision::EntityId,
+    ) -> Outcome<DepartmentLoad, TeachingLoadException> {
+        let Some(department) = self.subdivision_repo.find(department_id).await? else {
+            return Outcome::Ex(TeachingLoadException::DepartmentNotFound);
+        };
+
+        self.policy
+            .authorize(actor, Scope::Subdivision(department_id))
+            .await?;
+
+        let limits = self.limits_by_kind(department.university_id).await?;
+
+        let mut teachers = self
+            .teacher_repo
+            .list_by_department_id(department_id)
+            .await?;
+        teachers.sort_by_key(|v| v.id.value);
+
+        let mut cache = Cache::default();
+        let mut loads = vec![];
+        for teacher in teachers {
+            loads.push(self.load(&teacher, &limits, &mut cache).await?);
+        }
+
+        let mut semesters = BTreeMap::<i32, (i32, HashMap<class_kind::EntityId, i32>)>::new();
+        let mut statuses = HashMap::<(i32, LoadStatus), usize>::new();
+        for semester in loads.iter().flat_map(|v| v.semesters.iter()) {
+            let (hours, by_class_kind) = semesters.entry(semester.semester).or_default();
+            *hours += semester.hours;
+            for (kind, kind_hours) in semester.by_class_kind.iter() {
+                *by_class_kind.entry(kind.clone()).or_default() += kind_hours;
+            }
+
+            if let Some(status) = semester.status {
+                *statuses.entry((semester.semester, status)).or_default() += 1;
+            }
+        }
+
+        let semesters = semesters
+            .into_iter()
+            .map(
+                |(semester, (hours, by_class_kind))| DepartmentSemesterLoad {
+                    semester,
+                    hours,
+                    by_class_kind: sorted(by_class_kind, |v| v.value.clone()),
+                    underloaded: statuses
+                        .get(&(semester, LoadStatus::Underload))
+                        .copied()
+                        .unwrap_or_default(),
+                    overloaded: statuses
+                        .get(&(semester, LoadStatus::Overload))
+                        .copied()
+                        .unwrap_or_default(),
+                },
+            )
+            .collect();
+
+        Outcome::Ok(DepartmentLoad {
+            department_id,
+            teachers: loads,
+            semesters,
+        })
+    }
+
+    async fn load(
+        &self,
+        teacher: &teacher::Entity,
+        limits: &HashMap<TeacherKind, load_limit::Entity>,
+        cache: &mut Cache,
+    ) -> Result<TeacherLoad, anyhow::Error> {
+        let mut semesters = BTreeMap::<i32, SemesterHours>::new();
+        for teacher_class in teacher.classes.iter() {
+            let Some((semester, class)) =
+                self.class_semester(cache, teacher_class.class_id).await?
+            else {
+                continue;
+            };
+
+            let hours = semesters.entry(semester).or_default();
+            hours.total += class.duration.0;
+            *hours.by_class_kind.entry(class.kind_name).or_default() += class.duration.0;
+            *hours
+                .by_study_group
+                .entry(teacher_class.study_group_id)
+                .or_default() += class.duration.0;
+        }
+
+        let limit = limits.get(&teacher.kind);
+        let semesters = semesters
+            .into_iter()
+            .map(|(semester, hours)| SemesterLoad {
+                semester,
+                hours: hours.total,
+                by_class_kind: sorted(hours.by_class_kind, |v| v.value.clone()),
+                by_study_group: sorted(hours.by_study_group, |v| v.value),
+                limit: limit.cloned(),
+                status: limit.map(|v| v.status(hours.total)),
+            })
+            .collect();
+
+        Ok(TeacherLoad {
+            teacher_id: teacher.id,
+            kind: teacher.kind,
+            semesters,
+        })
+    }
+
+    /// Class with the curriculum semester it's taught in
+    async fn class_semester(
+        &self,
+        cache: &mut Cache,
+        class_id: class::EntityId,
+    ) -> Result<Option<(i32, class::Entity)>, anyhow::Error> {
+        if let Some(found) = cache.classes.get(&class_id) {
+            return Ok(found.clone());
+        }
+
+        let mut found = None;
+        if let Some(class) = self.class_repo.find(class_id).await? {
+            if let Some(curriculum_module) = self
+                .curriculum_module_repo
+                .find(class.curriculum_module_id)
+                .await?
+            {
+                found = Some((curriculum_module.semester, class));
+            }
+        }
+
+        cache.classes.insert(class_id, found.clone());
+        Ok(found)
+    }
+
+    async fn limits_by_kind(
+        &self,
+        university_id: university::EntityId,
+    ) -> Result<HashMap<TeacherKind, load_limit::Entity>, anyhow::Error> {
+        let limits = self
+            .load_limit_repo
+            .list_by_university(university_id)
+            .await?
+            .into_iter()
+            .map(|v| (v.teacher_kind, v))
+            .collect();
+
+        Ok(limits)
+    }
+
+    async fn find_limit(
+        &self,
+        university_id: university::EntityId,
+        teacher_kind: TeacherKind,
+    ) -> Result<Option<load_limit::Entity>, anyhow::Error> {
+        Ok(self
+            .limits_by_kind(university_id)
+            .await?
+            .remove(&teacher_kind))
+    }
+
+    async fn ensure_university(
+        &self,
+        university_id: university::EntityId,
+    ) -> Outcome<(), TeachingLoadException> {
+        if self.university_repo.find(university_id).await?.is_none() {
+            return Outcome::Ex(TeachingLoadException::UniversityNotFound);
+        }
+
+        Outcome::Ok(())
+    }
+}
+
+/// Classes already loaded for a report, teachers of a department share most of them
+#[derive(Default)]
+struct Cache {
+    classes: HashMap<class::EntityId, Option<(i32, class::Entity)>>,
+}
+
+#[derive(Default)]
+struct SemesterHours {
+    total: i32,
+    by_class_kind: HashMap<class_kind::EntityId, i32>,
+    by_study_group: HashMap<study_group::EntityId, i32>,
+}
+
+fn sorted<K, O: Ord>(hours: HashMap<K, i32>, key: impl Fn(&K) -> O) -> Vec<(K, i32)> {
+    let mut hours = hours.into_iter().collect::<Vec<_>>();
+    hours.sort_by_key(|(k, _)| key(k));
+    hours
+}
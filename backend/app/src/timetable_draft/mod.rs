@@ -0,0 +1,70 @@
+use std::collections::HashSet;
+
+use time::Date;
+use utils::entity::entity;
+
+use crate::{
+    class, room,
+    schedule::{Recurrence, TimeSlot},
+    study_group, teacher, user,
+};
+
+mod repo;
+
+pub use repo::Repo;
+pub type BoxedRepo = Box<dyn Repo + Send + Sync>;
+
+/// Timetable proposed by the generator, nothing is scheduled until it is applied
+#[entity]
+#[derive(Debug, Clone)]
+pub struct Entity {
+    #[id]
+    pub id: i32,
+    pub author_id: user::EntityId,
+    pub study_groups: HashSet<study_group::EntityId>,
+    pub semester: i32,
+    pub valid_from: Date,
+    pub valid_until: Date,
+    pub status: DraftStatus,
+    /// Penalty of the soft constraints, lower is better
+    pub score: Option<i32>,
+    pub entries: Vec<DraftEntry>,
+    pub unplaced: Vec<UnplacedClass>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DraftStatus {
+    Pending,
+    Ready,
+    Failed,
+    Applied,
+}
+
+#[derive(Debug, Clone)]
+pub struct DraftEntry {
+    pub class_id: class::EntityId,
+    pub study_group_id: study_group::EntityId,
+    pub teacher_id: teacher::EntityId,
+    pub room_id: room::EntityId,
+    pub slot: TimeSlot,
+    pub recurrence: Recurrence,
+    /// Bi-weekly entries of the other parity start a week later than the draft
+    pub valid_from: Date,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnplacedClass {
+    pub class_id: class::EntityId,
+    pub study_group_id: study_group::EntityId,
+    pub reason: UnplacedReason,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnplacedReason {
+    /// Nobody teaches the class to the study group
+    Unassigned,
+    /// No room of the university seats the whole study group
+    NoRoom,
+    /// Every slot clashes with the teacher, the room or the study group
+    NoSlot,
+}
@@ -0,0 +1,398 @@
+use std::collections::{HashMap, HashSet};
+
+use time::Date;
+use utils::{
+    di::{Module, Provide},
+    outcome::Outcome,
+};
+
+use crate::{
+    access_policy::{AccessException, AccessPolicy, Actor},
+    class, curriculum_module,
+    grant::Scope,
+    room, schedule,
+    schedule_service::{ScheduleException, ScheduleInfo, ScheduleService},
+    student, study_group, subdivision,
+    teacher::{self, TeacherClass},
+    timetable_draft::{self, DraftStatus},
+    timetable_generator::{Demand, Input, Solution},
+    AdaptersModule, AppModule,
+};
+
+pub struct TimetableDraftService {
+    repo: timetable_draft::BoxedRepo,
+    study_group_repo: study_group::BoxedRepo,
+    subdivision_repo: subdivision::BoxedRepo,
+    student_repo: student::BoxedRepo,
+    curriculum_module_repo: curriculum_module::BoxedRepo,
+    class_repo: class::BoxedRepo,
+    teacher_repo: teacher::BoxedRepo,
+    room_repo: room::BoxedRepo,
+    schedule_repo: schedule::BoxedRepo,
+    schedule_service: ScheduleService,
+    policy: AccessPolicy,
+}
+
+impl<A: AdaptersModule> Provide<TimetableDraftService> for AppModule<A> {
+    fn provide(&self) -> TimetableDraftService {
+        TimetableDraftService {
+            repo: self.adapters.resolve(),
+            study_group_repo: self.adapters.resolve(),
+            subdivision_repo: self.adapters.resolve(),
+            student_repo: self.adapters.resolve(),
+            curriculum_module_repo: self.adapters.resolve(),
+            class_repo: self.adapters.resolve(),
+            teacher_repo: self.adapters.resolve(),
+            room_repo: self.adapters.resolve(),
+            schedule_repo: self.adapters.resolve(),
+            schedule_service: self.resolve(),
+            policy: self.resolve(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, thiserror::Error)]
+pub enum TimetableDraftException {
+    #[error(transparent)]
+    AccessException(#[from] AccessException),
+    #[error(transparent)]
+    ScheduleException(#[from] ScheduleException),
+    #[error("timetable draft not found")]
+    NotFound,
+    #[error("study group not found")]
+    StudyGroupNotFound,
+    #[error("at least one study group is required")]
+    NoStudyGroups,
+    #[error("semester should be a positive number")]
+    InvalidSemester,
+    #[error("validity period should end after it starts and last at most a year")]
+    InvalidPeriod,
+    #[error("timetable draft is not ready yet")]
+    NotReady,
+    #[error("timetable draft is already applied")]
+    AlreadyApplied,
+}
+
+pub struct DraftRequest {
+    pub study_groups: HashSet<study_group::EntityId>,
+    pub semester: i32,
+    pub valid_from: Date,
+    pub valid_until: Date,
+}
+
+impl TimetableDraftService {
+    /// Saves a pending draft, it is filled in later by [`Self::generate`]
+    pub async fn request(
+        &mut self,
+        actor: &Actor,
+        request: DraftRequest,
+    ) -> Outcome<timetable_draft::Entity, TimetableDraftException> {
+        if request.study_groups.is_empty() {
+            return Outcome::Ex(TimetableDraftException::NoStudyGroups);
+        }
+
+        for study_group_id in request.study_groups.iter() {
+            if self.study_group_repo.find(*study_group_id).await?.is_none() {
+                return Outcome::Ex(TimetableDraftException::StudyGroupNotFound);
+            }
+        }
+
+        self.authorize(actor, &request.study_groups).await?;
+
+        if request.semester <= 0 {
+            return Outcome::Ex(TimetableDraftException::InvalidSemester);
+        }
+
+        if !schedule::is_valid_period(request.valid_from, request.valid_until) {
+            return Outcome::Ex(TimetableDraftException::InvalidPeriod);
+        }
+
+        let draft = self
+            .repo
+            .save(timetable_draft::Entity {
+                id: Default::default(),
+                author_id: actor.user_id,
+                study_groups: request.study_groups,
+                semester: request.semester,
+                valid_from: request.valid_from,
+                valid_until: request.valid_until,
+                status: DraftStatus::Pending,
+                score: None,
+                entries: vec![],
+                unplaced: vec![],
+            })
+            .await?;
+
+        Outcome::Ok(draft)
+    }
+
+    /// Solver input of a pending draft, `None` once the draft is generated or removed.
+    /// The solver itself runs outside of any transaction, on a blocking thread
+    pub async fn prepare(
+        &mut self,
+        id: timetable_draft::EntityId,
+    ) -> Result<Option<Input>, anyhow::Error> {
+        let Some(draft) = self.repo.find(id).await? else {
+            return Ok(None);
+        };
+
+        if draft.status != DraftStatus::Pending {
+            return Ok(None);
+        }
+
+        Ok(Some(self.collect_input(&draft).await?))
+    }
+
+    /// Stores the solution of a draft that is still pending
+    pub async fn complete(
+        &mut self,
+        id: timetable_draft::EntityId,
+        solution: Solution,
+    ) -> Result<(), anyhow::Error> {
+        let Some(draft) = self.repo.find(id).await? else {
+            return Ok(());
+        };
+
+        if draft.status != DraftStatus::Pending {
+            return Ok(());
+        }
+
+        self.repo
+            .save(timetable_draft::Entity {
+                status: DraftStatus::Ready,
+                score: Some(solution.score),
+                entries: solution.entries,
+                unplaced: solution.unplaced,
+                ..draft
+            })
+            .await?;
+
+        Ok(())
+    }
+
+    /// Marks a draft whose generation could not complete
+    pub async fn fail(&mut self, id: timetable_draft::EntityId) -> Result<(), anyhow::Error> {
+        let Some(draft) = self.repo.find(id).await? else {
+            return Ok(());
+        };
+
+        self.repo
+            .save(timetable_draft::Entity {
+                status: DraftStatus::Failed,
+                ..draft
+            })
+            .await?;
+
+        Ok(())
+    }
+
+    pub async fn get(
+        &self,
+        actor: &Actor,
+        id: timetable_draft::EntityId,
+    ) -> Outcome<timetable_draft::Entity, TimetableDraftException> {
+        let Some(draft) = self.repo.find(id).await? else {
+            return Outcome::Ex(TimetableDraftException::NotFound);
+        };
+
+        self.authorize(actor, &draft.study_groups).await?;
+
+        Outcome::Ok(draft)
+    }
+
+    /// Writes the draft entries into the timetable, they are validated again since the timetable may have changed
+    pub async fn apply(
+        &mut self,
+        actor: &Actor,
+        id: timetable_draft::EntityId,
+    ) -> Outcome<Vec<schedule::Entity>, TimetableDraftException> {
+        let draft = self.get(actor, id).await?;
+
+        match draft.status {
+            DraftStatus::Ready => {}
+            DraftStatus::Applied => return Outcome::Ex(TimetableDraftException::AlreadyApplied),
+            DraftStatus::Pending | DraftStatus::Failed => {
+                return Outcome::Ex(TimetableDraftException::NotReady)
+            }
+        }
+
+        let infos = draft
+            .entries
+            .iter()
+            .map(|v| ScheduleInfo {
+                class_id: v.class_id,
+                study_group_id: v.study_group_id,
+                teacher_id: v.teacher_id,
+                room_id: v.room_id,
+                slot: v.slot,
+                recurrence: v.recurrence,
+                valid_from: v.valid_from,
+                valid_until: draft.valid_until,
+            })
+            .collect();
+
+        let entries = self.schedule_service.create_many(actor, infos).await?;
+
+        self.repo
+            .save(timetable_draft::Entity {
+                status: DraftStatus::Applied,
+                ..draft
+            })
+            .await?;
+
+        Outcome::Ok(entries)
+    }
+
+    pub async fn delete(
+        &mut self,
+        actor: &Actor,
+        id: timetable_draft::EntityId,
+    ) -> Outcome<timetable_draft::Entity, TimetableDraftException> {
+        let draft = self.get(actor, id).await?;
+
+        self.repo.delete(&draft).await?;
+        Outcome::Ok(draft)
+    }
+
+    async fn authorize(
+        &self,
+        actor: &Actor,
+        study_groups: &HashSet<study_group::EntityId>,
+    ) -> Outcome<(), TimetableDraftException> {
+        for study_group_id in study_groups.iter() {
+            self.policy
+                .authorize(actor, Scope::StudyGroup(*study_group_id))
+                .await?;
+        }
+
+        Outcome::Ok(())
+    }
+
+    /// Classes of the semester of every group with their teachers, rooms and the current timetable,
+    /// classes the group already has in the timetable are left out
+    async fn collect_input(&self, draft: &timetable_draft::Entity) -> Result<Input, anyhow::Error> {
+        let mut demands = vec![];
+        let mut rooms = HashMap::new();
+        let mut scheduled = HashMap::new();
+
+        for study_group_id in draft.study_groups.iter() {
+            let Some(study_group) = self.study_group_repo.find(*study_group_id).await? else {
+                continue;
+            };
+
+            let Some(department) = self
+                .subdivision_repo
+                .find(study_group.department_id)
+                .await?
+            else {
+                continue;
+            };
+
+            for room in self
+                .room_repo
+                .list_by_university(department.university_id)
+                .await?
+            {
+                rooms.insert(room.id, room);
+            }
+
+            for entry in self
+                .schedule_repo
+                .list_by_study_group(study_group.id)
+                .await?
+            {
+                scheduled.insert(entry.id.value, entry);
+            }
+
+            let students = self
+                .student_repo
+                .list_by_study_group(study_group.id)
+                .await?
+                .into_iter()
+                .filter(|v| v.status.is_enrolled())
+                .count();
+
+            for curriculum_id in study_group.curriculums.iter() {
+                let modules = self
+                    .curriculum_module_repo
+                    .list_by_curriculum_id(*curriculum_id)
+                    .await?
+                    .into_iter()
+                    .filter(|v| v.semester == draft.semester);
+
+                for curriculum_module in modules {
+                    for class in self
+                        .class_repo
+                        .list_by_curriculum_module(curriculum_module.id)
+                        .await?
+                    {
+                        // the group already has the class in the timetable for this period
+                        if scheduled.values().any(|v: &schedule::Entity| {
+                            v.study_group_id == study_group.id
+                                && v.class_id == class.id
+                                && v.valid_from <= draft.valid_until
+                                && draft.valid_from <= v.valid_until
+                        }) {
+                            continue;
+                        }
+
+                        let assignment = TeacherClass {
+                            study_group_id: study_group.id,
+                            class_id: class.id,
+                        };
+
+                        let teacher_id = self
+                            .teacher_repo
+                            .list_by_class(class.id)
+                            .await?
+                            .into_iter()
+                            .filter(|v| v.classes.contains(&assignment))
+                            .map(|v| v.id)
+                            .min_by_key(|v| v.value);
+
+                        demands.push(Demand {
+                            class_id: class.id,
+                            study_group_id: study_group.id,
+                            teacher_id,
+                            university_id: department.university_id,
+                            students,
+                            hours: class.duration.0,
+                        });
+                    }
+                }
+            }
+        }
+
+        let mut availability = HashMap::new();
+        let teachers = demands
+            .iter()
+            .filter_map(|v| v.teacher_id)
+            .collect::<HashSet<_>>();
+
+        for teacher_id in teachers {
+            for entry in self.schedule_repo.list_by_teacher(teacher_id).await? {
+                scheduled.insert(entry.id.value, entry);
+            }
+
+            let windows = self.teacher_repo.availability(teacher_id).await?;
+            if !windows.is_empty() {
+                availability.insert(teacher_id, windows);
+            }
+        }
+
+        for room_id in rooms.keys() {
+            for entry in self.schedule_repo.list_by_room(*room_id).await? {
+                scheduled.insert(entry.id.value, entry);
+            }
+        }
+
+        Ok(Input {
+            valid_from: draft.valid_from,
+            valid_until: draft.valid_until,
+            demands,
+            rooms: rooms.into_values().collect(),
+            scheduled: scheduled.into_values().collect(),
+            availability,
+        })
+    }
+}
@@ -0,0 +1,616 @@
+//! Greedy solver proposing a weekly timetable for the classes of a semester.
+//!
+//! Sessions are placed one by one, the hardest to place first. A placement never
+//! double-books a teacher, a room or a study group and never puts a group into a
+//! room too small for it. Among the valid placements the one with the lowest
+//! penalty wins, penalties come from gaps in the days of groups, overloaded days
+//! and classes outside the availability windows of teachers.
+
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+use time::{Date, Duration, Time, Weekday};
+
+use crate::{
+    class, room,
+    schedule::{self, week_start, Recurrence, TimeSlot},
+    study_group, teacher,
+    timetable_draft::{DraftEntry, UnplacedClass, UnplacedReason},
+    university,
+};
+
+/// Start and end of the periods of a day, a period is two academic hours
+const BELLS: [((u8, u8), (u8, u8)); 7] = [
+    ((8, 30), (10, 0)),
+    ((10, 10), (11, 40)),
+    ((12, 0), (13, 30)),
+    ((14, 0), (15, 30)),
+    ((15, 40), (17, 10)),
+    ((17, 20), (18, 50)),
+    ((19, 0), (20, 30)),
+];
+
+const WORKING_DAYS: [Weekday; 6] = [
+    Weekday::Monday,
+    Weekday::Tuesday,
+    Weekday::Wednesday,
+    Weekday::Thursday,
+    Weekday::Friday,
+    Weekday::Saturday,
+];
+
+/// Academic hours of a class held once in one period
+const HOURS_PER_PERIOD: i32 = 2;
+
+/// Periods of a day a group attends without the day counting as overloaded
+const MAX_PERIODS_PER_DAY: usize = 4;
+
+const GAP_PENALTY: i32 = 3;
+const OVERLOAD_PENALTY: i32 = 5;
+const UNAVAILABLE_TEACHER_PENALTY: i32 = 10;
+
+/// Class of a study group that needs places in the timetable
+pub struct Demand {
+    pub class_id: class::EntityId,
+    pub study_group_id: study_group::EntityId,
+    /// `None` when nobody teaches the class to the group
+    pub teacher_id: Option<teacher::EntityId>,
+    /// Groups only use rooms of their own university
+    pub university_id: university::EntityId,
+    pub students: usize,
+    pub hours: i32,
+}
+
+pub struct Input {
+    pub valid_from: Date,
+    pub valid_until: Date,
+    pub demands: Vec<Demand>,
+    pub rooms: Vec<room::Entity>,
+    /// Entries already in the timetable, they are never moved
+    pub scheduled: Vec<schedule::Entity>,
+    /// Teachers without windows are available at any time
+    pub availability: HashMap<teacher::EntityId, Vec<TimeSlot>>,
+}
+
+pub struct Solution {
+    pub entries: Vec<DraftEntry>,
+    pub unplaced: Vec<UnplacedClass>,
+    /// Penalty of the soft constraints, lower is better
+    pub score: i32,
+}
+
+struct Session<'a> {
+    demand: &'a Demand,
+    teacher_id: teacher::EntityId,
+    recurrence: Recurrence,
+}
+
+/// Candidate time of a session before a room is picked
+struct Placement {
+    penalty: i32,
+    day: usize,
+    period: usize,
+    valid_from: Date,
+}
+
+#[derive(Default)]
+struct Occupancy {
+    by_teacher: HashMap<teacher::EntityId, Vec<schedule::Entity>>,
+    by_room: HashMap<room::EntityId, Vec<schedule::Entity>>,
+    by_study_group: HashMap<study_group::EntityId, Vec<schedule::Entity>>,
+}
+
+impl Occupancy {
+    fn add(&mut self, entry: schedule::Entity) {
+        self.by_teacher
+            .entry(entry.teacher_id)
+            .or_default()
+            .push(entry.clone());
+        self.by_room
+            .entry(entry.room_id)
+            .or_default()
+            .push(entry.clone());
+        self.by_study_group
+            .entry(entry.study_group_id)
+            .or_default()
+            .push(entry);
+    }
+
+    fn is_free<K: std::hash::Hash + Eq>(
+        entries: &HashMap<K, Vec<schedule::Entity>>,
+        key: &K,
+        candidate: &schedule::Entity,
+    ) -> bool {
+        entries
+            .get(key)
+            .map_or(true, |v| !v.iter().any(|v| v.conflicts_with(candidate)))
+    }
+
+    /// Periods of the day the group attends in any week
+    fn group_periods(
+        &self,
+        study_group_id: study_group::EntityId,
+        weekday: Weekday,
+    ) -> BTreeSet<usize> {
+        let Some(entries) = self.by_study_group.get(&study_group_id) else {
+            return BTreeSet::new();
+        };
+
+        (0..BELLS.len())
+            .filter(|period| {
+                let slot = bell(weekday, *period);
+                entries.iter().any(|v| v.slot.overlaps(&slot))
+            })
+            .collect()
+    }
+}
+
+pub fn generate(input: &Input) -> Solution {
+    let weeks = (week_start(input.valid_until) - week_start(input.valid_from)).whole_weeks() + 1;
+
+    let mut occupancy = Occupancy::default();
+    for entry in input.scheduled.iter() {
+        occupancy.add(entry.clone());
+    }
+
+    let mut rooms = input.rooms.iter().collect::<Vec<_>>();
+    rooms.sort_by_key(|v| (v.capacity, v.id.value));
+
+    let mut unplaced = vec![];
+    let mut sessions = vec![];
+    for demand in input.demands.iter() {
+        let Some(teacher_id) = demand.teacher_id else {
+            unplaced.push(unplaced_class(demand, UnplacedReason::Unassigned));
+            continue;
+        };
+
+        if !rooms.iter().any(|v| fits(v, demand)) {
+            unplaced.push(unplaced_class(demand, UnplacedReason::NoRoom));
+            continue;
+        }
+
+        for recurrence in session_recurrences(demand.hours, weeks as i32) {
+            sessions.push(Session {
+                demand,
+                teacher_id,
+                recurrence,
+            });
+        }
+    }
+
+    // big groups and weekly classes have the fewest options, so they go first
+    sessions.sort_by_key(|v| {
+        (
+            std::cmp::Reverse(v.demand.students),
+            v.recurrence != Recurrence::Weekly,
+            v.demand.study_group_id.value,
+            v.demand.class_id.value,
+        )
+    });
+
+    let mut entries = vec![];
+    for session in sessions {
+        match place(input, &rooms, &occupancy, &session) {
+            Some(entry) => {
+                occupancy.add(entry.clone());
+                entries.push(entry);
+            }
+            None => {
+                let class = unplaced_class(session.demand, UnplacedReason::NoSlot);
+                if !unplaced.contains(&class) {
+                    unplaced.push(class);
+                }
+            }
+        }
+    }
+
+    let score = score(input, &occupancy, &entries);
+
+    let entries = entries
+        .into_iter()
+        .map(|v| DraftEntry {
+            class_id: v.class_id,
+            study_group_id: v.study_group_id,
+            teacher_id: v.teacher_id,
+            room_id: v.room_id,
+            slot: v.slot,
+            recurrence: v.recurrence,
+            valid_from: v.valid_from,
+        })
+        .collect();
+
+    Solution {
+        entries,
+        unplaced,
+        score,
+    }
+}
+
+fn place(
+    input: &Input,
+    rooms: &[&room::Entity],
+    occupancy: &Occupancy,
+    session: &Session,
+) -> Option<schedule::Entity> {
+    let demand = session.demand;
+
+    let mut starts = vec![input.valid_from];
+    if session.recurrence == Recurrence::BiWeekly {
+        if let Some(next_week) = week_start(input.valid_from).checked_add(Duration::weeks(1)) {
+            if next_week <= input.valid_until {
+                starts.push(next_week);
+            }
+        }
+    }
+
+    let mut placements = vec![];
+    for (day, weekday) in WORKING_DAYS.iter().enumerate() {
+        let periods = occupancy.group_periods(demand.study_group_id, *weekday);
+
+        for period in 0..BELLS.len() {
+            let slot = bell(*weekday, period);
+            let penalty = day_penalty(&periods, Some(period)) - day_penalty(&periods, None)
+                + availability_penalty(input, session.teacher_id, &slot);
+
+            for valid_from in starts.iter() {
+                placements.push(Placement {
+                    penalty,
+                    day,
+                    period,
+                    valid_from: *valid_from,
+                });
+            }
+        }
+    }
+
+    placements.sort_by_key(|v| (v.penalty, v.day, v.period, v.valid_from));
+
+    for placement in placements {
+        let mut candidate = schedule::Entity {
+            id: Default::default(),
+            class_id: demand.class_id,
+            study_group_id: demand.study_group_id,
+            teacher_id: session.teacher_id,
+            room_id: Default::default(),
+            slot: bell(WORKING_DAYS[placement.day], placement.period),
+            recurrence: session.recurrence,
+            valid_from: placement.valid_from,
+            valid_until: input.valid_until,
+        };
+
+        if !Occupancy::is_free(&occupancy.by_teacher, &session.teacher_id, &candidate)
+            || !Occupancy::is_free(
+                &occupancy.by_study_group,
+                &demand.study_group_id,
+                &candidate,
+            )
+        {
+            continue;
+        }
+
+        // rooms are sorted by capacity, so the first free one wastes the fewest seats
+        for room in rooms.iter().filter(|v| fits(v, demand)) {
+            candidate.room_id = room.id;
+            if Occupancy::is_free(&occupancy.by_room, &room.id, &candidate) {
+                return Some(candidate);
+            }
+        }
+    }
+
+    None
+}
+
+fn score(input: &Input, occupancy: &Occupancy, entries: &[schedule::Entity]) -> i32 {
+    let study_groups = input
+        .demands
+        .iter()
+        .map(|v| v.study_group_id)
+        .collect::<HashSet<_>>();
+
+    let days = study_groups
+        .iter()
+        .flat_map(|study_group_id| {
+            WORKING_DAYS
+                .iter()
+                .map(|weekday| occupancy.group_periods(*study_group_id, *weekday))
+        })
+        .map(|periods| day_penalty(&periods, None))
+        .sum::<i32>();
+
+    let availability = entries
+        .iter()
+        .map(|v| availability_penalty(input, v.teacher_id, &v.slot))
+        .sum::<i32>();
+
+    days + availability
+}
+
+/// Number of whole-semester sessions, the leftover hours get a bi-weekly one when it is enough
+fn session_recurrences(hours: i32, weeks: i32) -> Vec<Recurrence> {
+    let weekly_hours = weeks * HOURS_PER_PERIOD;
+    let bi_weekly_hours = (weeks + 1) / 2 * HOURS_PER_PERIOD;
+
+    let mut recurrences = vec![Recurrence::Weekly; (hours / weekly_hours) as usize];
+
+    let leftover = hours % weekly_hours;
+    if leftover > 0 {
+        recurrences.push(if leftover <= bi_weekly_hours {
+            Recurrence::BiWeekly
+        } else {
+            Recurrence::Weekly
+        });
+    }
+
+    recurrences
+}
+
+/// Penalty of gaps and overload of a group day, optionally with one more period
+fn day_penalty(periods: &BTreeSet<usize>, extra: Option<usize>) -> i32 {
+    let mut periods = periods.clone();
+    periods.extend(extra);
+
+    let (Some(first), Some(last)) = (periods.first(), periods.last()) else {
+        return 0;
+    };
+
+    let gaps = last - first + 1 - periods.len();
+    let overload = periods.len().saturating_sub(MAX_PERIODS_PER_DAY);
+
+    gaps as i32 * GAP_PENALTY + overload as i32 * OVERLOAD_PENALTY
+}
+
+fn availability_penalty(input: &Input, teacher_id: teacher::EntityId, slot: &TimeSlot) -> i32 {
+    let Some(windows) = input.availability.get(&teacher_id) else {
+        return 0;
+    };
+
+    let is_available = windows.is_empty()
+        || windows.iter().any(|v| {
+            v.weekday == slot.weekday && v.starts_at <= slot.starts_at && slot.ends_at <= v.ends_at
+        });
+
+    if is_available {
+        0
+    } else {
+        UNAVAILABLE_TEACHER_PENALTY
+    }
+}
+
+fn bell(weekday: Weekday, period: usize) -> TimeSlot {
+    let ((start_hour, start_minute), (end_hour, end_minute)) = BELLS[period];
+
+    TimeSlot {
+        weekday,
+        starts_at: Time::from_hms(start_hour, start_minute, 0).unwrap(),
+        ends_at: Time::from_hms(end_hour, end_minute, 0).unwrap(),
+    }
+}
+
+fn fits(room: &room::Entity, demand: &Demand) -> bool {
+    room.university_id == demand.university_id && room.capacity as usize >= demand.students
+}
+
+fn unplaced_class(demand: &Demand, reason: UnplacedReason) -> UnplacedClass {
+    UnplacedClass {
+        class_id: demand.class_id,
+        study_group_id: demand.study_group_id,
+        reason,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use time::Month;
+    use utils::entity::Id;
+
+    use super::*;
+
+    fn date(year: i32, month: Month, day: u8) -> Date {
+        Date::from_calendar_date(year, month, day).unwrap()
+    }
+
+    fn room(id: i32, capacity: i32) -> room::Entity {
+        room::Entity {
+            id: Id::new(id),
+            university_id: Id::new(1),
+            name: format!("room {id}"),
+            capacity,
+        }
+    }
+
+    fn demand(class_id: i32, study_group_id: i32, teacher_id: Option<i32>, hours: i32) -> Demand {
+        Demand {
+            class_id: Id::new(class_id),
+            study_group_id: Id::new(study_group_id),
+            teacher_id: teacher_id.map(Id::new),
+            university_id: Id::new(1),
+            students: 25,
+            hours,
+        }
+    }
+
+    /// Autumn semester of 16 weeks, 32 hours make one weekly session
+    fn input(demands: Vec<Demand>, rooms: Vec<room::Entity>) -> Input {
+        Input {
+            valid_from: date(2024, Month::September, 2),
+            valid_until: date(2024, Month::December, 22),
+            demands,
+            rooms,
+            scheduled: vec![],
+            availability: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn leftover_hours_get_a_bi_weekly_session_when_it_is_enough() {
+        assert_eq!(session_recurrences(32, 16), vec![Recurrence::Weekly]);
+        assert_eq!(
+            session_recurrences(48, 16),
+            vec![Recurrence::Weekly, Recurrence::BiWeekly]
+        );
+        assert_eq!(
+            session_recurrences(50, 16),
+            vec![Recurrence::Weekly, Recurrence::Weekly]
+        );
+        // odd number of weeks gives the bi-weekly session the extra week
+        assert_eq!(session_recurrences(18, 17), vec![Recurrence::BiWeekly]);
+    }
+
+    #[test]
+    fn gaps_and_overloaded_days_are_penalized() {
+        let periods = BTreeSet::from([0, 1]);
+
+        assert_eq!(day_penalty(&BTreeSet::new(), None), 0);
+        assert_eq!(day_penalty(&periods, Some(2)), 0);
+        assert_eq!(day_penalty(&periods, Some(4)), 2 * GAP_PENALTY);
+        assert_eq!(
+            day_penalty(&BTreeSet::from([0, 1, 2, 3]), Some(4)),
+            OVERLOAD_PENALTY
+        );
+    }
+
+    #[test]
+    fn classes_without_teacher_or_room_are_reported() {
+        let mut big_group = demand(2, 2, Some(1), 32);
+        big_group.students = 40;
+        let mut other_university = demand(3, 3, Some(1), 32);
+        other_university.university_id = Id::new(2);
+
+        let solution = generate(&input(
+            vec![demand(1, 1, None, 32), big_group, other_university],
+            vec![room(1, 30)],
+        ));
+
+        assert!(solution.entries.is_empty());
+        assert_eq!(
+            solution
+                .unplaced
+                .iter()
+                .map(|v| (v.class_id.value, v.reason))
+                .collect::<Vec<_>>(),
+            vec![
+                (1, UnplacedReason::Unassigned),
+                (2, UnplacedReason::NoRoom),
+                (3, UnplacedReason::NoRoom),
+            ]
+        );
+    }
+
+    #[test]
+    fn sessions_of_a_teacher_fill_compact_days_without_overlaps() {
+        let solution = generate(&input(
+            vec![demand(1, 1, Some(1), 64), demand(2, 2, Some(1), 64)],
+            vec![room(1, 30)],
+        ));
+
+        assert!(solution.unplaced.is_empty());
+        assert_eq!(solution.score, 0);
+
+        let slots = solution
+            .entries
+            .iter()
+            .map(|v| (v.slot.weekday, v.slot.starts_at))
+            .collect::<HashSet<_>>();
+        assert_eq!(solution.entries.len(), 4);
+        assert_eq!(slots.len(), 4);
+        assert!(solution
+            .entries
+            .iter()
+            .all(|v| v.slot.weekday == Weekday::Monday));
+    }
+
+    #[test]
+    fn smallest_fitting_room_is_picked() {
+        let solution = generate(&input(
+            vec![demand(1, 1, Some(1), 32)],
+            vec![room(1, 100), room(2, 30), room(3, 20)],
+        ));
+
+        assert_eq!(solution.entries.len(), 1);
+        assert_eq!(solution.entries[0].room_id, Id::new(2));
+    }
+
+    #[test]
+    fn scheduled_entries_are_not_double_booked() {
+        let mut input = input(vec![demand(1, 1, Some(1), 32)], vec![room(1, 30)]);
+        input.scheduled.push(schedule::Entity {
+            id: Id::new(1),
+            class_id: Id::new(9),
+            study_group_id: Id::new(9),
+            teacher_id: Id::new(1),
+            room_id: Id::new(2),
+            slot: bell(Weekday::Monday, 0),
+            recurrence: Recurrence::Weekly,
+            valid_from: input.valid_from,
+            valid_until: input.valid_until,
+        });
+
+        let solution = generate(&input);
+
+        assert_eq!(solution.entries.len(), 1);
+        assert_eq!(solution.entries[0].slot, bell(Weekday::Monday, 1));
+    }
+
+    #[test]
+    fn availability_windows_of_teachers_are_preferred() {
+        let mut input = input(vec![demand(1, 1, Some(1), 32)], vec![room(1, 30)]);
+        input.availability.insert(
+            Id::new(1),
+            vec![TimeSlot {
+                weekday: Weekday::Tuesday,
+                starts_at: Time::from_hms(14, 0, 0).unwrap(),
+                ends_at: Time::from_hms(17, 10, 0).unwrap(),
+            }],
+        );
+
+        let solution = generate(&input);
+
+        assert_eq!(solution.score, 0);
+        assert_eq!(solution.entries[0].slot, bell(Weekday::Tuesday, 3));
+    }
+
+    #[test]
+    fn bi_weekly_sessions_of_a_group_share_a_slot_on_alternating_weeks() {
+        let solution = generate(&input(
+            vec![demand(1, 1, Some(1), 16), demand(2, 1, Some(2), 16)],
+            vec![room(1, 30)],
+        ));
+
+        assert_eq!(solution.entries.len(), 2);
+        assert!(solution
+            .entries
+            .iter()
+            .all(|v| v.recurrence == Recurrence::BiWeekly && v.slot == bell(Weekday::Monday, 0)));
+        assert_eq!(
+            solution
+                .entries
+                .iter()
+                .map(|v| v.valid_from)
+                .collect::<HashSet<_>>(),
+            HashSet::from([
+                date(2024, Month::September, 2),
+                date(2024, Month::September, 9)
+            ])
+        );
+    }
+
+    #[test]
+    fn class_without_a_free_slot_is_reported_once() {
+        let slots = WORKING_DAYS.len() * BELLS.len();
+        let hours = (slots as i32 + 2) * 32;
+
+        let solution = generate(&input(
+            vec![demand(1, 1, Some(1), hours)],
+            vec![room(1, 30)],
+        ));
+
+        assert_eq!(solution.entries.len(), slots);
+        assert_eq!(
+            solution.unplaced,
+            vec![UnplacedClass {
+                class_id: Id::new(1),
+                study_group_id: Id::new(1),
+                reason: UnplacedReason::NoSlot,
+            }]
+        );
+    }
+}
@@ -0,0 +1,10 @@
+use super::AccountTokenClaims;
+
+#[async_trait::async_trait]
+pub trait AccountTokenEngine {
+    async fn encode(&self, claims: AccountTokenClaims) -> Result<String, anyhow::Error>;
+
+    /// `None` when the token is malformed or its signature does not match,
+    /// expiration is left to the caller
+    async fn decode(&self, token: &str) -> Result<Option<AccountTokenClaims>, anyhow::Error>;
+}
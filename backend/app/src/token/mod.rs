@@ -1,20 +1,37 @@
 mod access_token_engine;
+mod account_token_engine;
 mod refresh_token_generator;
 
 pub use access_token_engine::AccessTokenEngine;
-pub use refresh_token_generator::RefreshTokenGenerator;
+pub use account_token_engine::AccountTokenEngine;
+pub use refresh_token_generator::{RefreshTokenGenerator, RefreshTokenHasher};
 
-use crate::user_session::{Seconds, SecondsFromUnixEpoch};
+use crate::{
+    account_token::Purpose,
+    user::Role,
+    user_session::{Seconds, SecondsFromUnixEpoch},
+};
 
 pub type BoxedAccessTokenEngine = Box<dyn AccessTokenEngine + Send + Sync>;
+pub type BoxedAccountTokenEngine = Box<dyn AccountTokenEngine + Send + Sync>;
 pub type BoxedRefreshTokenGenerator = Box<dyn RefreshTokenGenerator + Send + Sync>;
+pub type BoxedRefreshTokenHasher = Box<dyn RefreshTokenHasher + Send + Sync>;
 
 #[derive(Debug, Clone, Hash, PartialEq, Eq)]
 pub struct Claims {
     pub user_id: i32,
     pub email: String,
     pub expires_at: SecondsFromUnixEpoch,
-    // pub role: Role,
+    pub role: Role,
+}
+
+/// Signed reference to an account token row, the row makes the token single use
+#[derive(Debug, Clone, Hash, PartialEq, Eq)]
+pub struct AccountTokenClaims {
+    pub token_id: i32,
+    pub user_id: i32,
+    pub purpose: Purpose,
+    pub expires_at: SecondsFromUnixEpoch,
 }
 
 #[derive(Debug, Clone, Hash, PartialEq, Eq)]
@@ -2,3 +2,8 @@
 pub trait RefreshTokenGenerator {
     async fn generate(&self) -> Result<String, anyhow::Error>;
 }
+
+/// Refresh tokens are random and long, a fast unsalted hash is enough to store them
+pub trait RefreshTokenHasher {
+    fn hash(&self, token: &str) -> String;
+}
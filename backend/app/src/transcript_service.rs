@@ -0,0 +1,242 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use utils::{
+    di::{Module, Provide},
+    outcome::Outcome,
+};
+
+use crate::{
+    access_policy::{AccessException, AccessPolicy, Actor},
+    attestation, class, curriculum_module, discipline,
+    gradebook_service::GradedScore,
+    grading_scale::Scale,
+    grading_scale_service::GradingScaleService,
+    grant::Scope,
+    person, student, student_order, study_group, AdaptersModule, AppModule,
+};
+
+pub struct TranscriptService {
+    student_repo: student::BoxedRepo,
+    order_repo: student_order::BoxedRepo,
+    person_repo: person::BoxedRepo,
+    study_group_repo: study_group::BoxedRepo,
+    curriculum_module_repo: curriculum_module::BoxedRepo,
+    discipline_repo: discipline::BoxedRepo,
+    attestation_repo: attestation::BoxedRepo,
+    class_repo: class::BoxedRepo,
+    grading_scale_service: GradingScaleService,
+    policy: AccessPolicy,
+}
+
+impl<A: AdaptersModule> Provide<TranscriptService> for AppModule<A> {
+    fn provide(&self) -> TranscriptService {
+        TranscriptService {
+            student_repo: self.adapters.resolve(),
+            order_repo: self.adapters.resolve(),
+            person_repo: self.adapters.resolve(),
+            study_group_repo: self.adapters.resolve(),
+            curriculum_module_repo: self.adapters.resolve(),
+            discipline_repo: self.adapters.resolve(),
+            attestation_repo: self.adapters.resolve(),
+            class_repo: self.adapters.resolve(),
+            grading_scale_service: self.resolve(),
+            policy: self.resolve(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, thiserror::Error)]
+pub enum TranscriptException {
+    #[error(transparent)]
+    AccessException(#[from] AccessException),
+    #[error("student not found")]
+    StudentNotFound,
+    #[error("study group not found")]
+    StudyGroupNotFound,
+}
+
+/// Academic record of a student over all curriculums of the study groups the student
+/// was a member of, transfers keep the record of the previous groups
+pub struct Transcript {
+    pub student: student::Entity,
+    pub person: person::Entity,
+    pub scale: Scale,
+    pub semesters: Vec<TranscriptSemester>,
+    pub credit_hours: i32,
+    /// Mean points of all differentiated grades, `None` until there is one
+    pub average: Option<f64>,
+}
+
+pub struct TranscriptSemester {
+    pub semester: i32,
+    pub entries: Vec<TranscriptEntry>,
+    pub credit_hours: i32,
+    pub average: Option<f64>,
+}
+
+pub struct TranscriptEntry {
+    pub curriculum_module: curriculum_module::Entity,
+    pub discipline: discipline::Entity,
+    pub attestation: Option<attestation::Entity>,
+    pub score: Option<GradedScore>,
+    /// Hours of all module classes and of the attestation
+    pub credit_hours: i32,
+}
+
+impl TranscriptService {
+    /// Visible to the student and to the study group managers
+    pub async fn get(
+        &self,
+        actor: &Actor,
+        student_id: student::EntityId,
+    ) -> Outcome<Transcript, TranscriptException> {
+        let Some(student) = self.student_repo.find(student_id).await? else {
+            return Outcome::Ex(TranscriptException::StudentNotFound);
+        };
+
+        let Some(person) = self.person_repo.find(student.person_id).await? else {
+            return Outcome::Ex(TranscriptException::StudentNotFound);
+        };
+
+        if person.user_id != actor.user_id {
+            self.policy
+                .authorize(actor, Scope::StudyGroup(student.study_group_id))
+                .await?;
+        }
+
+        let mut study_groups_ids = self
+            .order_repo
+            .list_by_student(student.id)
+            .await?
+            .into_iter()
+            .map(|v| v.study_group_id)
+            .collect::<HashSet<_>>();
+        study_groups_ids.insert(student.study_group_id);
+
+        let study_groups = self.study_group_repo.find_many(study_groups_ids).await?;
+        let Some(study_group) = study_groups.iter().find(|v| v.id == student.study_group_id) else {
+            return Outcome::Ex(TranscriptException::StudyGroupNotFound);
+        };
+
+        let scale = self
+            .grading_scale_service
+            .scale_of_subdivision(study_group.department_id)
+            .await?;
+
+        let curriculums_ids = study_groups
+            .iter()
+            .flat_map(|v| v.curriculums.iter().copied())
+            .collect::<HashSet<_>>();
+        let modules = self
+            .curriculum_module_repo
+            .list_by_curriculums(curriculums_ids)
+            .await?;
+        let modules_ids = modules.iter().map(|v| v.id).collect::<HashSet<_>>();
+
+        let disciplines = self
+            .discipline_repo
+            .find_many(modules.iter().map(|v| v.discipline_id).collect())
+            .await?
+            .into_iter()
+            .map(|v| (v.id, v))
+            .collect::<HashMap<_, _>>();
+
+        let mut attestations = self
+            .attestation_repo
+            .list_by_curriculum_modules(modules_ids.clone())
+            .await?
+            .into_iter()
+            .map(|v| (v.curriculum_module_id, v))
+            .collect::<HashMap<_, _>>();
+
+        let mut classes_hours = HashMap::<_, i32>::new();
+        for class in self
+            .class_repo
+            .list_by_curriculum_modules(modules_ids)
+            .await?
+        {
+            *classes_hours.entry(class.curriculum_module_id).or_default() += class.duration.0;
+        }
+
+        let mut semesters = BTreeMap::<i32, Vec<TranscriptEntry>>::new();
+        for curriculum_module in modules {
+            let Some(discipline) = disciplines.get(&curriculum_module.discipline_id) else {
+                continue;
+            };
+
+            let attestation = attestations.remove(&curriculum_module.id);
+            let attestation_hours = attestation.as_ref().map_or(0, |v| v.duration.0);
+
+            let score = attestation.as_ref().and_then(|attestation| {
+                let score = student
+                    .attestations
+                    .iter()
+                    .find(|v| v.attestation_id == attestation.id)?;
+
+                Some(GradedScore {
+                    score: score.clone(),
+                    grade: scale.grade(&attestation.kind, score.score),
+                })
+            });
+
+            semesters
+                .entry(curriculum_module.semester)
+                .or_default()
+                .push(TranscriptEntry {
+                    discipline: discipline.clone(),
+                    credit_hours: classes_hours
+                        .get(&curriculum_module.id)
+                        .copied()
+                        .unwrap_or_default()
+                        + attestation_hours,
+                    curriculum_module,
+                    attestation,
+                    score,
+                });
+        }
+
+        let semesters = semesters
+            .into_iter()
+            .map(|(semester, mut entries)| {
+                entries.sort_by(|a, b| {
+                    a.discipline.name.cmp(&b.discipline.name).then(
+                        a.curriculum_module
+                            .id
+                            .value
+                            .cmp(&b.curriculum_module.id.value),
+                    )
+                });
+
+                TranscriptSemester {
+                    semester,
+                    credit_hours: entries.iter().map(|v| v.credit_hours).sum(),
+                    average: Self::average(&entries),
+                    entries,
+                }
+            })
+            .collect::<Vec<_>>();
+
+        Outcome::Ok(Transcript {
+            credit_hours: semesters.iter().map(|v| v.credit_hours).sum(),
+            average: Self::average(semesters.iter().flat_map(|v| v.entries.iter())),
+            student,
+            person,
+            scale,
+            semesters,
+        })
+    }
+
+    fn average<'a>(entries: impl IntoIterator<Item = &'a TranscriptEntry>) -> Option<f64> {
+        let points = entries
+            .into_iter()
+            .filter_map(|v| v.score.as_ref()?.grade.points())
+            .map(f64::from)
+            .collect::<Vec<_>>();
+
+        if points.is_empty() {
+            return None;
+        }
+
+        Some(points.iter().sum::<f64>() / points.len() as f64)
+    }
+}
@@ -1,14 +1,17 @@
 mod repo;
 
+use time::UtcOffset;
 use utils::entity::entity;
 
 pub use repo::Repo;
 pub type BoxedRepo = Box<dyn Repo + Send + Sync>;
 
 #[entity]
-#[derive(Clone)]
+#[derive(Debug, Clone)]
 pub struct Entity {
     #[id]
     pub id: i32,
     pub name: String,
+    /// Offset of the local time the timetable of the university is kept in
+    pub utc_offset: UtcOffset,
 }
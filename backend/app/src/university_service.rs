@@ -0,0 +1,188 @@
+use time::UtcOffset;
+use utils::{
+    di::{Module, Provide},
+    outcome::Outcome,
+};
+
+use crate::{
+    academic_year,
+    access_policy::{AccessException, AccessPolicy, Actor},
+    grading_scale,
+    grant::{self, Scope},
+    load_limit, room, subdivision, university,
+    validation::is_valid_name,
+    AdaptersModule, AppModule,
+};
+
+const NAME_MAX_LEN: usize = 256;
+
+/// Offsets of the time zones in use, from -12:00 to +14:00
+const UTC_OFFSET_MINUTES: std::ops::RangeInclusive<i32> = -12 * 60..=14 * 60;
+
+pub struct UniversityService {
+    repo: university::BoxedRepo,
+    subdivision_repo: subdivision::BoxedRepo,
+    room_repo: room::BoxedRepo,
+    grant_repo: grant::BoxedRepo,
+    grading_scale_repo: grading_scale::BoxedRepo,
+    load_limit_repo: load_limit::BoxedRepo,
+    academic_year_repo: academic_year::BoxedRepo,
+    policy: AccessPolicy,
+}
+
+impl<A: AdaptersModule> Provide<UniversityService> for AppModule<A> {
+    fn provide(&self) -> UniversityService {
+        UniversityService {
+            repo: self.adapters.resolve(),
+            subdivision_repo: self.adapters.resolve(),
+            room_repo: self.adapters.resolve(),
+            grant_repo: self.adapters.resolve(),
+            grading_scale_repo: self.adapters.resolve(),
+            load_limit_repo: self.adapters.resolve(),
+            academic_year_repo: self.adapters.resolve(),
+            policy: self.resolve(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, thiserror::Error)]
+pub enum UniversityException {
+    #[error(transparent)]
+    AccessException(#[from] AccessException),
+    #[error("university not found")]
+    NotFound,
+    #[error("invalid university name")]
+    InvalidName,
+    #[error("utc offset should be within -12:00 and +14:00")]
+    InvalidUtcOffset,
+    #[error("university already exist")]
+    AlreadyExist,
+    #[error("university has subdivisions or rooms")]
+    InUse,
+}
+
+pub struct UniversityInfo {
+    pub name: String,
+    pub utc_offset_minutes: i32,
+}
+
+impl UniversityService {
+    pub async fn create(
+        &mut self,
+        actor: &Actor,
+        info: UniversityInfo,
+    ) -> Outcome<university::Entity, UniversityException> {
+        self.policy.authorize(actor, Scope::Global).await?;
+        let utc_offset = self.validate(None, &info).await?;
+
+        let university = university::Entity {
+            id: Default::default(),
+            name: info.name,
+            utc_offset,
+        };
+
+        let university = self.repo.save(university).await?;
+        Outcome::Ok(university)
+    }
+
+    pub async fn update(
+        &mut self,
+        actor: &Actor,
+        id: university::EntityId,
+        info: UniversityInfo,
+    ) -> Outcome<university::Entity, UniversityException> {
+        // authorized before the lookup so missing universities don't stand out
+        self.policy.authorize(actor, Scope::University(id)).await?;
+
+        let university = self.get(id).await?;
+        let utc_offset = self.validate(Some(id), &info).await?;
+
+        let university = self
+            .repo
+            .save(university::Entity {
+                name: info.name,
+                utc_offset,
+                ..university
+            })
+            .await?;
+
+        Outcome::Ok(university)
+    }
+
+    pub async fn delete(
+        &mut self,
+        actor: &Actor,
+        id: university::EntityId,
+    ) -> Outcome<university::Entity, UniversityException> {
+        self.policy.authorize(actor, Scope::University(id)).await?;
+
+        let university = self.get(id).await?;
+
+        if !self
+            .subdivision_repo
+            .list_by_university(id)
+            .await?
+            .is_empty()
+            || !self.room_repo.list_by_university(id).await?.is_empty()
+        {
+            return Outcome::Ex(UniversityException::InUse);
+        }
+
+        self.grant_repo
+            .delete_by_scope(Scope::University(id))
+            .await?;
+
+        if let Some(grading_scale) = self.grading_scale_repo.find_by_university(id).await? {
+            self.grading_scale_repo.delete(&grading_scale).await?;
+        }
+
+        for load_limit in self.load_limit_repo.list_by_university(id).await? {
+            self.load_limit_repo.delete(&load_limit).await?;
+        }
+
+        for academic_year in self.academic_year_repo.list_by_university(id).await? {
+            self.academic_year_repo.delete(&academic_year).await?;
+        }
+
+        self.repo.delete(&university).await?;
+
+        Outcome::Ok(university)
+    }
+
+    async fn get(
+        &self,
+        id: university::EntityId,
+    ) -> Outcome<university::Entity, UniversityException> {
+        let Some(university) = self.repo.find(id).await? else {
+            return Outcome::Ex(UniversityException::NotFound);
+        };
+
+        Outcome::Ok(university)
+    }
+
+    async fn validate(
+        &self,
+        id: Option<university::EntityId>,
+        info: &UniversityInfo,
+    ) -> Outcome<UtcOffset, UniversityException> {
+        if !is_valid_name(&info.name, NAME_MAX_LEN) {
+            return Outcome::Ex(UniversityException::InvalidName);
+        }
+
+        if !UTC_OFFSET_MINUTES.contains(&info.utc_offset_minutes) {
+            return Outcome::Ex(UniversityException::InvalidUtcOffset);
+        }
+
+        let Ok(utc_offset) = UtcOffset::from_whole_seconds(info.utc_offset_minutes * 60) else {
+            return Outcome::Ex(UniversityException::InvalidUtcOffset);
+        };
+
+        if let Some(university) = self.repo.find_by_name(info.name.clone()).await? {
+            if Some(university.id) != id {
+                return Outcome::Ex(UniversityException::AlreadyExist);
+            }
+        }
+
+        Outcome::Ok(utc_offset)
+    }
+}
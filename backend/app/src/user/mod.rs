@@ -12,6 +12,19 @@ pub struct Entity {
     pub id: i32,
     pub email: String,
     pub password: HashedPassword,
+    pub role: Role,
+    /// Set once the user follows the link sent to the email. Informational only, login
+    /// doesn't require it since accounts that predate verification are unverified too
+    pub email_verified: bool,
+}
+
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub enum Role {
+    Admin,
+    UniversityStaff,
+    DepartmentHead,
+    Teacher,
+    Student,
 }
 
 pub use crate::hasher::HashedPassword;
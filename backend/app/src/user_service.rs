@@ -1,7 +1,11 @@
 use utils::{di::Provide, entity::Id, outcome::Outcome};
 
 use crate::{
-    hasher, token::BoxedRefreshTokenGenerator, user, user_session, AdaptersModule, AppModule,
+    hasher,
+    token::{BoxedRefreshTokenGenerator, BoxedRefreshTokenHasher},
+    user, user_session,
+    validation::is_valid_password,
+    AdaptersModule, AppModule,
 };
 
 pub struct UserService {
@@ -9,6 +13,8 @@ pub struct UserService {
     session_repo: user_session::BoxedRepo,
     hasher: hasher::BoxedPasswordHasher,
     refresh_token_generator: BoxedRefreshTokenGenerator,
+    refresh_token_hasher: BoxedRefreshTokenHasher,
+    device_id_generator: user_session::BoxedDeviceIdGenerator,
     session_ttl: user_session::SessionTTL,
     sessions_max_number: user_session::SessionsMaxNumber,
 }
@@ -20,12 +26,21 @@ impl<A: AdaptersModule> Provide<UserService> for AppModule<A> {
             session_repo: self.adapters.resolve(),
             hasher: self.adapters.resolve(),
             refresh_token_generator: self.adapters.resolve(),
+            refresh_token_hasher: self.adapters.resolve(),
+            device_id_generator: self.adapters.resolve(),
             session_ttl: self.adapters.resolve(),
             sessions_max_number: self.adapters.resolve(),
         }
     }
 }
 
+/// The refresh token is only known when the session is created or rotated,
+/// the session keeps its hash
+pub struct IssuedSession {
+    pub session: user_session::Entity,
+    pub refresh_token: String,
+}
+
 #[derive(Debug, Clone, thiserror::Error)]
 pub enum UserException {
     #[error("email already in use")]
@@ -34,16 +49,26 @@ pub enum UserException {
     InvalidEmailOrPassword,
     #[error("user not found")]
     UserNotFound,
-    // #[error("invalid password")]
-    // InvalidPassword,
+    #[error("invalid password")]
+    InvalidPassword,
+    #[error("new password must be from 8 to 256 characters long")]
+    InvalidNewPassword,
+    #[error("device id must be from 1 to 64 letters, digits, '-' or '_'")]
+    InvalidDeviceId,
     #[error("session not found")]
     SessionNotFound,
     #[error("invalid refresh token")]
     InvalidRefreshToken,
+    #[error("refresh token reused, session revoked")]
+    RefreshTokenReused,
     #[error("session expired")]
     SessionExpired,
     #[error("sessions limit reached")]
     SessionsLimitReached,
+    #[error("account is locked after too many failed logins, try again in {retry_after} seconds")]
+    AccountLocked { retry_after: u64 },
+    #[error("too many failed logins from the address, try again in {retry_after} seconds")]
+    TooManyLoginAttempts { retry_after: u64 },
 }
 
 impl UserService {
@@ -51,6 +76,7 @@ impl UserService {
         &mut self,
         email: String,
         password: String,
+        role: user::Role,
     ) -> Outcome<user::Entity, UserException> {
         if self.repo.find_by_email(email.clone()).await?.is_some() {
             return Outcome::Ex(UserException::EmailAlreadyInUse);
@@ -60,6 +86,8 @@ impl UserService {
             id: Default::default(),
             email,
             password: self.hasher.hash(password).await?,
+            role,
+            email_verified: false,
         };
 
         let user = self.repo.save(user).await?;
@@ -90,69 +118,201 @@ impl UserService {
         Outcome::Ok(user)
     }
 
+    pub async fn get_by_email(&self, email: String) -> Outcome<user::Entity, UserException> {
+        let Some(user) = self.repo.find_by_email(email).await? else {
+            return Outcome::Ex(UserException::UserNotFound);
+        };
+
+        Outcome::Ok(user)
+    }
+
+    /// Starts a new token family, a session logged in again from the same device
+    /// is replaced along with the tokens rotated in it. A device id is issued
+    /// when the client has none yet
     pub async fn create_session(
         &mut self,
         user_id: user::EntityId,
-        metadata: String,
-    ) -> Outcome<user_session::Entity, UserException> {
+        device_id: Option<String>,
+        client: user_session::ClientInfo,
+    ) -> Outcome<IssuedSession, UserException> {
         let user_session::SessionTTL(ttl) = self.session_ttl;
 
-        let id = Id::new(user_session::Id { user_id, metadata });
+        let device_id = match device_id {
+            Some(device_id) if !user_session::is_valid_device_id(&device_id) => {
+                return Outcome::Ex(UserException::InvalidDeviceId);
+            }
+            Some(device_id) => device_id,
+            None => self.device_id_generator.generate(),
+        };
+
+        let id = Id::new(user_session::Id { user_id, device_id });
         let refresh_token = self.refresh_token_generator.generate().await?;
+        let refresh_token_hash = self.refresh_token_hasher.hash(&refresh_token);
         let expires_at = user_session::SecondsFromUnixEpoch::expired_at_from_ttl(ttl)?;
 
         let session = user_session::Entity {
             id: id.clone(),
-            refresh_token,
+            family_id: refresh_token_hash.clone(),
+            refresh_token_hash,
             expires_at,
+            client,
+            last_seen_at: user_session::SecondsFromUnixEpoch::now()?,
         };
 
-        if self.session_repo.find(id).await?.is_none() {
-            self.check_limit(user_id).await?;
+        match self.session_repo.find(id).await? {
+            Some(old_session) => self.session_repo.delete(&old_session).await?,
+            None => self.check_limit(user_id).await?,
         }
 
         let session = self.session_repo.save(session).await?;
-        Outcome::Ok(session)
+        Outcome::Ok(IssuedSession {
+            session,
+            refresh_token,
+        })
     }
 
+    /// Rotates the refresh token, the replaced one is remembered to detect its reuse
     pub async fn update_session(
         &mut self,
         user_id: user::EntityId,
-        metadata: String,
+        device_id: String,
+        client: user_session::ClientInfo,
         refresh_token_to_validate: String,
-    ) -> Outcome<user_session::Entity, UserException> {
+    ) -> Outcome<IssuedSession, UserException> {
         let old_session = self
-            .get_validated_session(user_id, metadata, refresh_token_to_validate)
+            .get_validated_session(user_id, device_id, refresh_token_to_validate)
             .await?;
 
-        let new_refresh_token = self.refresh_token_generator.generate().await?;
+        let refresh_token = self.refresh_token_generator.generate().await?;
 
         let session = user_session::Entity {
-            refresh_token: new_refresh_token,
-            ..old_session
+            refresh_token_hash: self.refresh_token_hasher.hash(&refresh_token),
+            client,
+            last_seen_at: user_session::SecondsFromUnixEpoch::now()?,
+            ..old_session.clone()
         };
 
         let session = self.session_repo.save(session).await?;
-        Outcome::Ok(session)
+        self.session_repo
+            .save_rotated_token(user_session::RotatedToken {
+                token_hash: old_session.refresh_token_hash,
+                session_id: old_session.id,
+                family_id: old_session.family_id,
+            })
+            .await?;
+
+        Outcome::Ok(IssuedSession {
+            session,
+            refresh_token,
+        })
     }
 
     pub async fn remove_session(
         &mut self,
         user_id: user::EntityId,
-        metadata: String,
+        device_id: String,
         refresh_token_to_validate: String,
     ) -> Outcome<user_session::Entity, UserException> {
         let session = self
-            .get_validated_session(user_id, metadata, refresh_token_to_validate)
+            .get_validated_session(user_id, device_id, refresh_token_to_validate)
             .await?;
 
         self.session_repo.delete(&session).await?;
         Outcome::Ok(session)
     }
 
-    async fn check_limit(&self, user_id: user::EntityId) -> Outcome<(), UserException> {
+    /// Other sessions are revoked, the one the password is changed from stays logged in
+    pub async fn change_password(
+        &mut self,
+        user_id: user::EntityId,
+        current_device_id: String,
+        old_password: String,
+        new_password: String,
+    ) -> Outcome<(), UserException> {
+        let user = self.get(user_id).await?;
+
+        if !self
+            .hasher
+            .is_matches(&old_password, &user.password)
+            .await?
+        {
+            return Outcome::Ex(UserException::InvalidPassword);
+        }
+
+        if !is_valid_password(&new_password) {
+            return Outcome::Ex(UserException::InvalidNewPassword);
+        }
+
+        let user = user::Entity {
+            password: self.hasher.hash(new_password).await?,
+            ..user
+        };
+        self.repo.save(user).await?;
+
+        self.revoke_other_sessions(user_id, current_device_id)
+            .await?;
+        Outcome::Ok(())
+    }
+
+    /// Sessions that can still be refreshed, expired ones are left out
+    pub async fn list_sessions(
+        &self,
+        user_id: user::EntityId,
+    ) -> Outcome<Vec<user_session::Entity>, UserException> {
+        let mut sessions = vec![];
+        for session in self.session_repo.list_by_user_id(user_id).await? {
+            if !session.expires_at.is_expired()? {
+                sessions.push(session);
+            }
+        }
+
+        Outcome::Ok(sessions)
+    }
+
+    /// Access tokens issued for the session stay valid until they expire,
+    /// only refreshing them is no longer possible
+    pub async fn revoke_session(
+        &mut self,
+        user_id: user::EntityId,
+        device_id: String,
+    ) -> Outcome<(), UserException> {
+        let id = Id::new(user_session::Id { user_id, device_id });
+
+        let Some(session) = self.session_repo.find(id).await? else {
+            return Outcome::Ex(UserException::SessionNotFound);
+        };
+
+        self.session_repo.delete(&session).await?;
+        Outcome::Ok(())
+    }
+
+    /// Returns the number of revoked sessions
+    pub async fn revoke_other_sessions(
+        &mut self,
+        user_id: user::EntityId,
+        current_device_id: String,
+    ) -> Outcome<usize, UserException> {
+        let mut revoked = 0;
+        for session in self.session_repo.list_by_user_id(user_id).await? {
+            if session.id.value.device_id != current_device_id {
+                self.session_repo.delete(&session).await?;
+                revoked += 1;
+            }
+        }
+
+        Outcome::Ok(revoked)
+    }
+
+    /// Expired sessions are dropped first, they are hidden from the user and can't be revoked
+    async fn check_limit(&mut self, user_id: user::EntityId) -> Outcome<(), UserException> {
         let user_session::SessionsMaxNumber(max_number) = self.sessions_max_number;
 
+        for session in self.session_repo.list_by_user_id(user_id).await? {
+            if session.expires_at.is_expired()? {
+                self.session_repo.delete(&session).await?;
+            }
+        }
+
         let current_number = self.session_repo.count_not_expired(user_id).await?;
         if current_number + 1 > max_number {
             return Outcome::Ex(UserException::SessionsLimitReached);
@@ -161,19 +321,29 @@ impl UserService {
         Outcome::Ok(())
     }
 
+    /// A token rotated out of the session family revokes the whole family,
+    /// either the thief or the user is holding the current token and there is no telling which
     async fn get_validated_session(
-        &self,
+        &mut self,
         user_id: user::EntityId,
-        metadata: String,
+        device_id: String,
         refresh_token_to_validate: String,
     ) -> Outcome<user_session::Entity, UserException> {
-        let id = Id::new(user_session::Id { user_id, metadata });
+        let id = Id::new(user_session::Id { user_id, device_id });
 
         let Some(session) = self.session_repo.find(id).await? else {
             return Outcome::Ex(UserException::SessionNotFound);
         };
 
-        if session.refresh_token != refresh_token_to_validate {
+        let token_hash = self.refresh_token_hasher.hash(&refresh_token_to_validate);
+        if session.refresh_token_hash != token_hash {
+            let rotated = self.session_repo.find_rotated_token(token_hash).await?;
+
+            if rotated.is_some_and(|v| v.family_id == session.family_id) {
+                self.session_repo.delete(&session).await?;
+                return Outcome::Ex(UserException::RefreshTokenReused);
+            }
+
             return Outcome::Ex(UserException::InvalidRefreshToken);
         }
 
@@ -0,0 +1,15 @@
+/// Issues device ids to clients that log in without one
+pub trait DeviceIdGenerator {
+    fn generate(&self) -> String;
+}
+
+pub const DEVICE_ID_MAX_LEN: usize = 64;
+
+/// Device ids are sent in a header and stored in a `varchar(64)` column,
+/// only url safe characters are allowed
+pub fn is_valid_device_id(device_id: &str) -> bool {
+    (1..=DEVICE_ID_MAX_LEN).contains(&device_id.len())
+        && device_id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
@@ -1,3 +1,4 @@
+mod device;
 mod repo;
 mod seconds;
 
@@ -6,23 +7,51 @@ use utils::entity::entity;
 use crate::user;
 
 // pub use ex::*;
+pub use device::*;
 pub use repo::Repo;
 pub use seconds::*;
 pub type BoxedRepo = Box<dyn Repo + Send + Sync>;
+pub type BoxedDeviceIdGenerator = Box<dyn DeviceIdGenerator + Send + Sync>;
 
 #[derive(Debug, Clone)]
 #[entity]
 pub struct Entity {
     #[id]
     pub id: Id,
-    pub refresh_token: String,
+    /// Only the hash is stored, a leaked table does not let anyone refresh tokens
+    pub refresh_token_hash: String,
+    /// Hash of the refresh token issued at login, every token rotated from it
+    /// belongs to the same family
+    pub family_id: String,
     pub expires_at: SecondsFromUnixEpoch,
+    pub client: ClientInfo,
+    /// Last login or refresh of the session
+    pub last_seen_at: SecondsFromUnixEpoch,
+}
+
+/// Describes where the session is used from, shown in the session list
+/// and refreshed with the session, it is never used to tell sessions apart
+#[derive(Debug, Clone, Default)]
+pub struct ClientInfo {
+    pub user_agent: Option<String>,
+    pub ip: Option<String>,
+}
+
+/// Refresh token that was replaced by rotation, presenting it again means
+/// the token was copied and the family is revoked
+#[derive(Debug, Clone)]
+pub struct RotatedToken {
+    pub token_hash: String,
+    pub session_id: EntityId,
+    pub family_id: String,
 }
 
 #[derive(Debug, Clone)]
 pub struct Id {
     pub user_id: user::EntityId,
-    pub metadata: String,
+    /// Chosen by the client or issued at login, it stays the same
+    /// when the browser is updated
+    pub device_id: String,
 }
 
 #[derive(Debug, Clone, Copy)]
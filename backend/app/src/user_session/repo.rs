@@ -1,6 +1,6 @@
 use crate::user;
 
-use super::{Entity, EntityId};
+use super::{Entity, EntityId, RotatedToken};
 
 #[async_trait::async_trait]
 pub trait Repo {
@@ -12,5 +12,13 @@ pub trait Repo {
 
     async fn list_by_user_id(&self, user_id: user::EntityId) -> Result<Vec<Entity>, anyhow::Error>;
 
+    /// Rotated tokens are kept until their session is deleted
+    async fn save_rotated_token(&mut self, token: RotatedToken) -> Result<(), anyhow::Error>;
+
+    async fn find_rotated_token(
+        &self,
+        token_hash: String,
+    ) -> Result<Option<RotatedToken>, anyhow::Error>;
+
     async fn count_not_expired(&self, user_id: user::EntityId) -> Result<i64, anyhow::Error>;
 }
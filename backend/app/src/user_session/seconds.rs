@@ -25,6 +25,10 @@ impl SecondsFromUnixEpoch {
         Ok(duration.into())
     }
 
+    pub fn now() -> Result<SecondsFromUnixEpoch, anyhow::Error> {
+        Self::expired_at_from_ttl(Seconds { val: 0 })
+    }
+
     pub fn is_expired(&self) -> Result<bool, anyhow::Error> {
         let now = SystemTime::now()
             .duration_since(SystemTime::UNIX_EPOCH)
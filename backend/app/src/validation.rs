@@ -0,0 +1,9 @@
+/// Checks that the name is not blank and fits into a `varchar(max_len)` column
+pub fn is_valid_name(name: &str, max_len: usize) -> bool {
+    !name.trim().is_empty() && name.chars().count() <= max_len
+}
+
+/// Passwords are only limited in length, the upper bound keeps hashing cheap
+pub fn is_valid_password(password: &str) -> bool {
+    (8..=256).contains(&password.chars().count())
+}
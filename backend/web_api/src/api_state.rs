@@ -1,20 +1,53 @@
+use std::future::Future;
+
 use adapters::{AdaptersModule, TransactionModule};
+use app::AppModule;
+use utils::outcome::Outcome;
+
+use crate::config::TrustedProxies;
 
 #[derive(Debug, Clone)]
 pub struct ApiState {
     adapters_module: AdaptersModule<crate::config::ConfigModule>,
+    trusted_proxies: TrustedProxies,
 }
 
 impl ApiState {
     pub async fn new(config: crate::config::ConfigModule) -> Result<Self, anyhow::Error> {
+        let trusted_proxies = config.trusted_proxies.clone();
+        let adapters_module = adapters::AdaptersModule::new(config).await?;
+        adapters_module.migrate().await?;
+
         Ok(Self {
-            adapters_module: adapters::AdaptersModule::new(config).await?,
+            adapters_module,
+            trusted_proxies,
         })
     }
 
+    pub fn trusted_proxies(&self) -> TrustedProxies {
+        self.trusted_proxies.clone()
+    }
+
     pub async fn begin_request_scope(
         &self,
     ) -> Result<TransactionModule<crate::config::ConfigModule>, anyhow::Error> {
         self.adapters_module.begin_transaction_scope().await
     }
+
+    /// Runs the work in a transaction of its own, committed only when the work succeeds.
+    /// Meant for background jobs, which have no request scope
+    pub async fn run_in_scope<T, Ex, F, Fut>(&self, work: F) -> Outcome<T, Ex>
+    where
+        F: FnOnce(AppModule<TransactionModule<crate::config::ConfigModule>>) -> Fut,
+        Fut: Future<Output = Outcome<T, Ex>>,
+    {
+        let adapters = self.begin_request_scope().await?;
+
+        let outcome = work(AppModule::new(adapters.clone())).await;
+        if outcome.is_ok() {
+            adapters.commit().await?;
+        }
+
+        outcome
+    }
 }
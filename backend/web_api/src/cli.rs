@@ -0,0 +1,65 @@
+use adapters::spreadsheet::{self, SpreadsheetFormat};
+use anyhow::{bail, Context};
+use app::{
+    access_policy::Actor, student_import_service::StudentImportService, user_service::UserService,
+};
+use time::OffsetDateTime;
+use utils::{di::Module, outcome::Outcome};
+
+use crate::{api_state::ApiState, handlers::import_report_to_json};
+
+const DRY_RUN_FLAG: &str = "--dry-run";
+
+/// Imports the spreadsheet on behalf of the user and prints the report,
+/// invalid rows are skipped and make the command fail after the rest are committed
+pub async fn import_students(api_state: &ApiState, args: &[String]) -> Result<(), anyhow::Error> {
+    let dry_run = args.iter().any(|v| v == DRY_RUN_FLAG);
+    let args = args
+        .iter()
+        .filter(|v| *v != DRY_RUN_FLAG)
+        .collect::<Vec<_>>();
+
+    let [path, email] = args[..] else {
+        bail!("usage: import-students <file> <email of the acting user> [{DRY_RUN_FLAG}]");
+    };
+
+    let format = SpreadsheetFormat::detect(path).context("file should be a csv or xlsx file")?;
+    let bytes = tokio::fs::read(path)
+        .await
+        .with_context(|| format!("failed to read {path}"))?;
+    let rows = spreadsheet::read_rows(format, &bytes)?;
+
+    let outcome = api_state
+        .run_in_scope(|module| async move {
+            let user = module
+                .resolve::<UserService>()
+                .get_by_email(email.clone())
+                .await?;
+
+            let actor = Actor {
+                user_id: user.id,
+                role: user.role,
+            };
+
+            module
+                .resolve::<StudentImportService>()
+                .import(&actor, rows, OffsetDateTime::now_utc().date(), dry_run)
+                .await
+        })
+        .await;
+
+    let report = match outcome {
+        Outcome::Ok(report) => report,
+        Outcome::Ex(ex) => bail!(ex),
+        Outcome::Error(err) => return Err(err),
+    };
+
+    let has_errors = !report.errors.is_empty();
+    println!("{:#}", import_report_to_json(report));
+
+    if has_errors {
+        bail!("spreadsheet has invalid rows, they were skipped");
+    }
+
+    Ok(())
+}
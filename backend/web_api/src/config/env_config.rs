@@ -1,4 +1,4 @@
-use std::{str::FromStr, sync::Arc};
+use std::{net::IpAddr, str::FromStr, sync::Arc};
 
 use serde::{de::Error, de::Unexpected, Deserialize, Deserializer};
 
@@ -21,6 +21,47 @@ pub struct EnvConfig {
     pub sessions_max_number_per_user: i64,
     pub session_ttl_in_seconds: u64,
 
+    #[serde(default = "get_default_login_account_max_failures")]
+    pub login_account_max_failures: u32,
+    #[serde(default = "get_default_login_ip_max_failures")]
+    pub login_ip_max_failures: u32,
+    #[serde(default = "get_default_login_lockout_in_seconds")]
+    pub login_lockout_in_seconds: u64,
+    #[serde(default = "get_default_login_max_lockout_in_seconds")]
+    pub login_max_lockout_in_seconds: u64,
+
+    /// Comma separated addresses of the proxies allowed to set `x-forwarded-for`
+    #[serde(default = "get_default_trusted_proxies")]
+    #[serde(deserialize_with = "deserialize_trusted_proxies")]
+    pub trusted_proxies: Vec<IpAddr>,
+
+    #[serde(default)]
+    pub semester_min_hours: i32,
+    #[serde(default = "get_default_semester_max_hours")]
+    pub semester_max_hours: i32,
+
+    #[serde(default = "get_default_pdf_font_path")]
+    pub pdf_font_path: String,
+
+    #[serde(default = "get_default_email_verification_ttl_in_seconds")]
+    pub email_verification_ttl_in_seconds: u64,
+    #[serde(default = "get_default_password_reset_ttl_in_seconds")]
+    pub password_reset_ttl_in_seconds: u64,
+    #[serde(default = "get_default_mail_link_base_url")]
+    pub mail_link_base_url: String,
+    #[serde(default = "get_default_mail_from")]
+    pub mail_from: String,
+    /// Letters are written here when no smtp host is set
+    #[serde(default = "get_default_mail_dir")]
+    pub mail_dir: String,
+    pub smtp_host: Option<String>,
+    pub smtp_port: Option<u16>,
+    #[serde(default = "get_default_smtp_security")]
+    #[serde(deserialize_with = "deserialize_smtp_security")]
+    pub smtp_security: adapters::config::SmtpSecurity,
+    pub smtp_username: Option<String>,
+    pub smtp_password: Option<String>,
+
     #[serde(default = "get_default_workers_count")]
     pub argon2_parallelism_degree: u32,
     #[serde(deserialize_with = "deserialize_argon2_algorithm")]
@@ -48,6 +89,101 @@ fn get_default_workers_count() -> u32 {
     std::thread::available_parallelism().unwrap().get() as u32
 }
 
+fn get_default_login_account_max_failures() -> u32 {
+    5
+}
+
+/// Higher than for an account, users behind the same NAT share the address
+fn get_default_login_ip_max_failures() -> u32 {
+    20
+}
+
+fn get_default_login_lockout_in_seconds() -> u64 {
+    30
+}
+
+fn get_default_login_max_lockout_in_seconds() -> u64 {
+    60 * 60
+}
+
+/// The server listens on localhost, so only a proxy on the same host can reach it
+fn get_default_trusted_proxies() -> Vec<IpAddr> {
+    vec![
+        IpAddr::from([127, 0, 0, 1]),
+        IpAddr::from([0, 0, 0, 0, 0, 0, 0, 1]),
+    ]
+}
+
+/// 30 credit units of 36 hours
+fn get_default_semester_max_hours() -> i32 {
+    30 * 36
+}
+
+/// Any ttf font with cyrillic glyphs will do
+fn get_default_pdf_font_path() -> String {
+    "/usr/share/fonts/truetype/dejavu/DejaVuSans.ttf".to_owned()
+}
+
+fn get_default_email_verification_ttl_in_seconds() -> u64 {
+    24 * 60 * 60
+}
+
+fn get_default_password_reset_ttl_in_seconds() -> u64 {
+    60 * 60
+}
+
+/// Frontend dev server
+fn get_default_mail_link_base_url() -> String {
+    "http://localhost:3000".to_owned()
+}
+
+fn get_default_mail_from() -> String {
+    "noreply@localhost".to_owned()
+}
+
+fn get_default_mail_dir() -> String {
+    "mail".to_owned()
+}
+
+fn get_default_smtp_security() -> adapters::config::SmtpSecurity {
+    adapters::config::SmtpSecurity::StartTls
+}
+
+fn deserialize_smtp_security<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<adapters::config::SmtpSecurity, D::Error> {
+    use adapters::config::SmtpSecurity;
+
+    let value = String::deserialize(deserializer)?;
+
+    match value.to_ascii_lowercase().as_str() {
+        "none" => Ok(SmtpSecurity::None),
+        "starttls" => Ok(SmtpSecurity::StartTls),
+        "tls" => Ok(SmtpSecurity::Tls),
+        _ => Err(D::Error::invalid_value(
+            Unexpected::Str(&value),
+            &"none, starttls or tls",
+        )),
+    }
+}
+
+fn deserialize_trusted_proxies<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Vec<IpAddr>, D::Error> {
+    let value = String::deserialize(deserializer)?;
+
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|v| !v.is_empty())
+        .map(|v| {
+            v.parse::<IpAddr>().map_err(|_| {
+                D::Error::invalid_value(Unexpected::Str(v), &"comma separated ip addresses")
+            })
+        })
+        .collect()
+}
+
 fn deserialize_argon2_version<'de, D: Deserializer<'de>>(
     deserializer: D,
 ) -> Result<argon2::Version, D::Error> {
@@ -92,9 +228,31 @@ impl Into<super::ConfigModule> for EnvConfig {
         let decoding_key = jsonwebtoken::DecodingKey::from_secret(self.jwt_secret.as_bytes());
         let jwt_keys = Arc::new(JwtKeys(encoding_key, decoding_key));
 
+        let mail_transport = match self.smtp_host {
+            Some(host) => MailTransport::Smtp(SmtpSettings {
+                host: Arc::from(host),
+                port: self.smtp_port,
+                security: self.smtp_security,
+                username: self.smtp_username.map(Arc::from),
+                password: self.smtp_password.map(Arc::from),
+            }),
+            None => MailTransport::Directory(Arc::from(self.mail_dir)),
+        };
+
         super::ConfigModule {
             sessions_max_number: SessionsMaxNumber(self.sessions_max_number_per_user),
             session_ttl: SessionTTL(Seconds::from(self.session_ttl_in_seconds)),
+            login_throttling: LoginThrottling {
+                account_max_failures: self.login_account_max_failures,
+                ip_max_failures: self.login_ip_max_failures,
+                lockout: Seconds::from(self.login_lockout_in_seconds),
+                max_lockout: Seconds::from(self.login_max_lockout_in_seconds),
+            },
+            trusted_proxies: super::TrustedProxies(Arc::from(self.trusted_proxies)),
+            semester_hours_bounds: SemesterHoursBounds {
+                min: self.semester_min_hours,
+                max: self.semester_max_hours,
+            },
             access_token_ttl: AccessTokenTTL(Seconds::from(self.jwt_token_ttl_in_seconds)),
             refresh_token_length: RefreshTokenLength(self.refresh_token_length),
             argon2_params: Argon2Params {
@@ -111,6 +269,16 @@ impl Into<super::ConfigModule> for EnvConfig {
             pg_user_name: Arc::from(self.pg_username),
             sqlx_log_level_filter: self.sqlx_log,
             sqlx_max_connections: self.pg_max_connections,
+            pdf_font_path: Arc::from(self.pdf_font_path),
+            email_verification_ttl: EmailVerificationTTL(Seconds::from(
+                self.email_verification_ttl_in_seconds,
+            )),
+            password_reset_ttl: PasswordResetTTL(Seconds::from(self.password_reset_ttl_in_seconds)),
+            link_base_url: LinkBaseUrl(Arc::from(self.mail_link_base_url)),
+            mail_settings: Arc::new(MailSettings {
+                from: Arc::from(self.mail_from),
+                transport: mail_transport,
+            }),
         }
     }
 }
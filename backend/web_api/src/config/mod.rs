@@ -2,17 +2,21 @@ pub mod env_config;
 
 use adapters::config::*;
 use app::{
+    curriculum::SemesterHoursBounds,
     token::AccessTokenTTL,
     user_session::{SessionTTL, SessionsMaxNumber},
 };
 use log::LevelFilter;
-use std::sync::Arc;
+use std::{net::IpAddr, sync::Arc};
 use utils::di::{Module, Provide};
 
 #[derive(Debug, Clone)]
 pub struct ConfigModule {
     pub sessions_max_number: SessionsMaxNumber,
     pub session_ttl: SessionTTL,
+    pub login_throttling: LoginThrottling,
+    pub trusted_proxies: TrustedProxies,
+    pub semester_hours_bounds: SemesterHoursBounds,
     pub access_token_ttl: AccessTokenTTL,
     pub refresh_token_length: RefreshTokenLength,
     pub argon2_params: Argon2Params,
@@ -25,6 +29,21 @@ pub struct ConfigModule {
     pub application_name: Arc<str>,
     pub sqlx_log_level_filter: Option<LevelFilter>,
     pub sqlx_max_connections: u32,
+    pub pdf_font_path: Arc<str>,
+    pub email_verification_ttl: EmailVerificationTTL,
+    pub password_reset_ttl: PasswordResetTTL,
+    pub link_base_url: LinkBaseUrl,
+    pub mail_settings: Arc<MailSettings>,
+}
+
+/// Proxies in front of the server, only they are believed about the client address
+#[derive(Debug, Clone)]
+pub struct TrustedProxies(pub Arc<[IpAddr]>);
+
+impl TrustedProxies {
+    pub fn contains(&self, ip: &IpAddr) -> bool {
+        self.0.contains(ip)
+    }
 }
 
 impl Module for ConfigModule {}
@@ -43,6 +62,42 @@ impl Provide<SessionTTL> for ConfigModule {
     }
 }
 
+impl Provide<LoginThrottling> for ConfigModule {
+    fn provide(&self) -> LoginThrottling {
+        self.login_throttling
+    }
+}
+
+impl Provide<SemesterHoursBounds> for ConfigModule {
+    fn provide(&self) -> SemesterHoursBounds {
+        self.semester_hours_bounds
+    }
+}
+
+impl Provide<EmailVerificationTTL> for ConfigModule {
+    fn provide(&self) -> EmailVerificationTTL {
+        self.email_verification_ttl
+    }
+}
+
+impl Provide<PasswordResetTTL> for ConfigModule {
+    fn provide(&self) -> PasswordResetTTL {
+        self.password_reset_ttl
+    }
+}
+
+impl Provide<LinkBaseUrl> for ConfigModule {
+    fn provide(&self) -> LinkBaseUrl {
+        self.link_base_url.clone()
+    }
+}
+
+impl Provide<Arc<MailSettings>> for ConfigModule {
+    fn provide(&self) -> Arc<MailSettings> {
+        Arc::clone(&self.mail_settings)
+    }
+}
+
 impl Provide<Arc<JwtKeys>> for ConfigModule {
     fn provide(&self) -> Arc<JwtKeys> {
         Arc::clone(&self.jwt_keys)
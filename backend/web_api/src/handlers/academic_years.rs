@@ -0,0 +1,287 @@
+use app::{
+    academic_calendar_service::{
+        AcademicCalendarException, AcademicCalendarService, AcademicYearInfo,
+    },
+    academic_year::{self, SemesterRange},
+    access_policy::Actor,
+};
+use axum::{
+    debug_handler,
+    extract::{Path, Query},
+    response::IntoResponse,
+    routing::{get, post},
+    Json, Router,
+};
+use http::StatusCode;
+use serde::Deserialize;
+use serde_json::json;
+use time::{Date, OffsetDateTime};
+use utils::{di::Module, entity::Id};
+
+use super::curriculum_modules::curriculum_module_to_json;
+use crate::utils::{
+    extractors::{AccessRejection, Auth, ReqScopeModule},
+    ApiResult, Authenticated, CommonState, Reply,
+};
+
+pub fn router<S: CommonState>() -> Router<S> {
+    Router::new()
+        .route("/", post(create))
+        .route("/:id", get(get_info).put(update).delete(remove))
+        .route("/universities/:id", get(list_by_university))
+        .route("/study_groups/:id/current", get(studying_now))
+}
+
+#[derive(Debug, Deserialize)]
+struct SemesterPayload {
+    starts_on: Date,
+    ends_on: Date,
+}
+
+#[derive(Debug, Deserialize)]
+struct AcademicYearPayload {
+    university_id: i32,
+    start_year: i32,
+    semesters: Vec<SemesterPayload>,
+}
+
+impl From<AcademicYearPayload> for AcademicYearInfo {
+    fn from(value: AcademicYearPayload) -> Self {
+        Self {
+            university_id: Id::new(value.university_id),
+            start_year: value.start_year,
+            semesters: value
+                .semesters
+                .into_iter()
+                .map(|v| SemesterRange {
+                    starts_on: v.starts_on,
+                    ends_on: v.ends_on,
+                })
+                .collect(),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct DateQuery {
+    /// Today if omitted
+    date: Option<Date>,
+}
+
+struct Exception(AcademicCalendarException);
+
+impl IntoResponse for Exception {
+    fn into_response(self) -> axum::response::Response {
+        let Self(ex) = self;
+        let code = match ex {
+            AcademicCalendarException::AccessException(ex) => {
+                return AccessRejection(ex).into_response()
+            }
+            AcademicCalendarException::NotFound => StatusCode::NOT_FOUND,
+            AcademicCalendarException::UniversityNotFound
+            | AcademicCalendarException::StudyGroupNotFound
+            | AcademicCalendarException::InvalidSemesters => StatusCode::BAD_REQUEST,
+            AcademicCalendarException::AlreadyExist
+            | AcademicCalendarException::Overlaps
+            | AcademicCalendarException::NoEnrollmentYear => StatusCode::CONFLICT,
+        };
+
+        (code, Reply::from(ex)).into_response()
+    }
+}
+
+/// Asking about a missing university or study group answers 404
+struct ListException(AcademicCalendarException);
+
+impl IntoResponse for ListException {
+    fn into_response(self) -> axum::response::Response {
+        let Self(ex) = self;
+        match ex {
+            AcademicCalendarException::UniversityNotFound
+            | AcademicCalendarException::StudyGroupNotFound => {
+                (StatusCode::NOT_FOUND, Reply::from(ex)).into_response()
+            }
+            ex => Exception(ex).into_response(),
+        }
+    }
+}
+
+fn semester_to_json(number: i32, semester: SemesterRange) -> serde_json::Value {
+    json!({
+        "number": number,
+        "startsOn": semester.starts_on,
+        "endsOn": semester.ends_on,
+    })
+}
+
+fn academic_year_to_json(year: academic_year::Entity) -> serde_json::Value {
+    json!({
+        "id": year.id.value,
+        "universityId": year.university_id.value,
+        "startYear": year.start_year,
+        "semesters": (1..)
+            .zip(year.semesters)
+            .map(|(number, semester)| semester_to_json(number, semester))
+            .collect::<Vec<_>>(),
+    })
+}
+
+#[debug_handler]
+async fn get_info(
+    ReqScopeModule(module): ReqScopeModule,
+    _: Auth<Authenticated>,
+    Path(id): Path<i32>,
+) -> ApiResult {
+    let year = module
+        .resolve::<AcademicCalendarService>()
+        .get(Id::new(id))
+        .await
+        .map_ex(Exception)?;
+
+    ApiResult::new((
+        StatusCode::OK,
+        Reply {
+            message: "academic year found successfully",
+            data: academic_year_to_json(year),
+        },
+    ))
+}
+
+#[debug_handler]
+async fn list_by_university(
+    ReqScopeModule(module): ReqScopeModule,
+    _: Auth<Authenticated>,
+    Path(id): Path<i32>,
+) -> ApiResult {
+    let years = module
+        .resolve::<AcademicCalendarService>()
+        .years(Id::new(id))
+        .await
+        .map_ex(ListException)?;
+
+    ApiResult::new((
+        StatusCode::OK,
+        Reply {
+            message: "academic years found successfully",
+            data: years
+                .into_iter()
+                .map(academic_year_to_json)
+                .collect::<Vec<_>>(),
+        },
+    ))
+}
+
+#[debug_handler]
+async fn create(
+    ReqScopeModule(module): ReqScopeModule,
+    Auth(claims, _): Auth<Authenticated>,
+    Json(payload): Json<AcademicYearPayload>,
+) -> ApiResult {
+    let year = module
+        .resolve::<AcademicCalendarService>()
+        .create(&Actor::from(&claims), payload.into())
+        .await
+        .map_ex(Exception)?;
+
+    ApiResult::new((
+        StatusCode::OK,
+        Reply {
+            message: "academic year created successfully",
+            data: academic_year_to_json(year),
+        },
+    ))
+}
+
+#[debug_handler]
+async fn update(
+    ReqScopeModule(module): ReqScopeModule,
+    Auth(claims, _): Auth<Authenticated>,
+    Path(id): Path<i32>,
+    Json(payload): Json<AcademicYearPayload>,
+) -> ApiResult {
+    let year = module
+        .resolve::<AcademicCalendarService>()
+        .update(&Actor::from(&claims), Id::new(id), payload.into())
+        .await
+        .map_ex(Exception)?;
+
+    ApiResult::new((
+        StatusCode::OK,
+        Reply {
+            message: "academic year updated successfully",
+            data: academic_year_to_json(year),
+        },
+    ))
+}
+
+#[debug_handler]
+async fn remove(
+    ReqScopeModule(module): ReqScopeModule,
+    Auth(claims, _): Auth<Authenticated>,
+    Path(id): Path<i32>,
+) -> ApiResult {
+    let year = module
+        .resolve::<AcademicCalendarService>()
+        .delete(&Actor::from(&claims), Id::new(id))
+        .await
+        .map_ex(Exception)?;
+
+    ApiResult::new((
+        StatusCode::OK,
+        Reply {
+            message: "academic year deleted successfully",
+            data: academic_year_to_json(year),
+        },
+    ))
+}
+
+#[debug_handler]
+async fn studying_now(
+    ReqScopeModule(module): ReqScopeModule,
+    _: Auth<Authenticated>,
+    Path(id): Path<i32>,
+    Query(query): Query<DateQuery>,
+) -> ApiResult {
+    let date = query
+        .date
+        .unwrap_or_else(|| OffsetDateTime::now_utc().date());
+
+    let studying = module
+        .resolve::<AcademicCalendarService>()
+        .studying_now(Id::new(id), date)
+        .await
+        .map_ex(ListException)?;
+
+    let current = studying.current.map(|v| {
+        json!({
+            "academicYearId": v.academic_year.id.value,
+            "startYear": v.academic_year.start_year,
+            "semester": v.semester,
+            "semesterOfYear": v.semester_of_year,
+            "startsOn": v.range.starts_on,
+            "endsOn": v.range.ends_on,
+        })
+    });
+
+    ApiResult::new((
+        StatusCode::OK,
+        Reply {
+            message: "current semester found successfully",
+            data: json!({
+                "studyGroupId": studying.study_group.id.value,
+                "enrollmentYear": studying.study_group.enrollment_year,
+                "date": studying.date,
+                "current": current,
+                "modules": studying
+                    .modules
+                    .into_iter()
+                    .map(|(curriculum_module, discipline)| {
+                        let mut json = curriculum_module_to_json(curriculum_module);
+                        json["disciplineName"] = discipline.name.into();
+                        json
+                    })
+                    .collect::<Vec<_>>(),
+            }),
+        },
+    ))
+}
@@ -0,0 +1,132 @@
+use app::account_service::{AccountException, AccountService};
+use axum::{response::IntoResponse, routing::post, Json, Router};
+use http::StatusCode;
+use serde::Deserialize;
+use utils::{di::Module, entity::Id};
+
+use crate::utils::{
+    extractors::{Auth, ReqScopeModule},
+    ApiResult, Authenticated, CommonState, EmptyData, Reply,
+};
+
+/// Nested under `/auth` next to the login routes
+pub fn router<S: CommonState>() -> Router<S> {
+    Router::new()
+        .route("/verify-email/request", post(request_email_verification))
+        .route("/verify-email", post(verify_email))
+        .route("/password-reset/request", post(request_password_reset))
+        .route("/password-reset", post(reset_password))
+}
+
+#[derive(Debug, Deserialize)]
+struct TokenPayload {
+    token: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct PasswordResetRequestPayload {
+    email: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct PasswordResetPayload {
+    token: String,
+    password: String,
+}
+
+pub struct Exception(pub AccountException);
+
+impl IntoResponse for Exception {
+    fn into_response(self) -> axum::response::Response {
+        let Self(ex) = self;
+        let code = match ex {
+            AccountException::UserNotFound => StatusCode::NOT_FOUND,
+            AccountException::EmailAlreadyVerified => StatusCode::CONFLICT,
+            AccountException::InvalidToken => StatusCode::BAD_REQUEST,
+            AccountException::TokenExpired => StatusCode::BAD_REQUEST,
+            AccountException::InvalidPassword => StatusCode::BAD_REQUEST,
+        };
+
+        (code, Reply::from(ex)).into_response()
+    }
+}
+
+#[axum::debug_handler]
+async fn request_email_verification(
+    ReqScopeModule(module): ReqScopeModule,
+    Auth(claims, _): Auth<Authenticated>,
+) -> ApiResult {
+    module
+        .resolve::<AccountService>()
+        .request_email_verification(Id::new(claims.user_id))
+        .await
+        .map_ex(Exception)?;
+
+    ApiResult::new((
+        StatusCode::OK,
+        Reply {
+            message: "verification mail sent",
+            data: EmptyData,
+        },
+    ))
+}
+
+#[axum::debug_handler]
+async fn verify_email(
+    ReqScopeModule(module): ReqScopeModule,
+    Json(payload): Json<TokenPayload>,
+) -> ApiResult {
+    module
+        .resolve::<AccountService>()
+        .verify_email(payload.token)
+        .await
+        .map_ex(Exception)?;
+
+    ApiResult::new((
+        StatusCode::OK,
+        Reply {
+            message: "email verified successfully",
+            data: EmptyData,
+        },
+    ))
+}
+
+#[axum::debug_handler]
+async fn request_password_reset(
+    ReqScopeModule(module): ReqScopeModule,
+    Json(payload): Json<PasswordResetRequestPayload>,
+) -> ApiResult {
+    module
+        .resolve::<AccountService>()
+        .request_password_reset(payload.email)
+        .await
+        .map_ex(Exception)?;
+
+    ApiResult::new((
+        StatusCode::OK,
+        Reply {
+            message: "password reset mail sent if the email is registered",
+            data: EmptyData,
+        },
+    ))
+}
+
+#[axum::debug_handler]
+async fn reset_password(
+    ReqScopeModule(module): ReqScopeModule,
+    Json(payload): Json<PasswordResetPayload>,
+) -> ApiResult {
+    module
+        .resolve::<AccountService>()
+        .reset_password(payload.token, payload.password)
+        .await
+        .map_ex(Exception)?;
+
+    ApiResult::new((
+        StatusCode::OK,
+        Reply {
+            message: "password reset successfully",
+            data: EmptyData,
+        },
+    ))
+}
@@ -0,0 +1,217 @@
+use app::{
+    access_policy::Actor,
+    attestation_session,
+    attestation_session_service::{
+        AttestationSessionException, AttestationSessionInfo, AttestationSessionService,
+    },
+};
+use axum::{
+    debug_handler,
+    extract::Path,
+    response::IntoResponse,
+    routing::{get, post},
+    Json, Router,
+};
+use http::StatusCode;
+use serde::Deserialize;
+use serde_json::json;
+use time::{Date, Time};
+use utils::{di::Module, entity::Id};
+
+use super::timetable::{clock_time_to_json, deserialize_clock_time};
+use crate::utils::{
+    extractors::{AccessRejection, Auth, ReqScopeModule},
+    ApiResult, Authenticated, CommonState, Reply,
+};
+
+pub fn router<S: CommonState>() -> Router<S> {
+    Router::new()
+        .route("/", post(create))
+        .route("/:id", get(get_info).put(update).delete(remove))
+        .route("/study_groups/:id", get(list_by_study_group))
+}
+
+#[derive(Debug, Deserialize)]
+struct SessionPayload {
+    attestation_id: i32,
+    study_group_id: i32,
+    room_id: Option<i32>,
+    date: Date,
+    #[serde(deserialize_with = "deserialize_clock_time")]
+    starts_at: Time,
+    #[serde(deserialize_with = "deserialize_clock_time")]
+    ends_at: Time,
+}
+
+impl From<SessionPayload> for AttestationSessionInfo {
+    fn from(value: SessionPayload) -> Self {
+        Self {
+            attestation_id: Id::new(value.attestation_id),
+            study_group_id: Id::new(value.study_group_id),
+            room_id: value.room_id.map(Id::new),
+            date: value.date,
+            starts_at: value.starts_at,
+            ends_at: value.ends_at,
+        }
+    }
+}
+
+struct Exception(AttestationSessionException);
+
+impl IntoResponse for Exception {
+    fn into_response(self) -> axum::response::Response {
+        let Self(ex) = self;
+        let code = match ex {
+            AttestationSessionException::AccessException(ex) => {
+                return AccessRejection(ex).into_response()
+            }
+            AttestationSessionException::NotFound => StatusCode::NOT_FOUND,
+            AttestationSessionException::AttestationNotFound
+            | AttestationSessionException::StudyGroupNotFound
+            | AttestationSessionException::RoomNotFound
+            | AttestationSessionException::NotInCurriculum
+            | AttestationSessionException::InvalidTime => StatusCode::BAD_REQUEST,
+            AttestationSessionException::RoomBusy | AttestationSessionException::StudyGroupBusy => {
+                StatusCode::CONFLICT
+            }
+        };
+
+        (code, Reply::from(ex)).into_response()
+    }
+}
+
+/// Listing sessions of a missing study group answers 404
+struct ListException(AttestationSessionException);
+
+impl IntoResponse for ListException {
+    fn into_response(self) -> axum::response::Response {
+        let Self(ex) = self;
+        match ex {
+            AttestationSessionException::StudyGroupNotFound => {
+                (StatusCode::NOT_FOUND, Reply::from(ex)).into_response()
+            }
+            ex => Exception(ex).into_response(),
+        }
+    }
+}
+
+fn session_to_json(session: attestation_session::Entity) -> serde_json::Value {
+    json!({
+        "id": session.id.value,
+        "attestationId": session.attestation_id.value,
+        "studyGroupId": session.study_group_id.value,
+        "roomId": session.room_id.map(|v| v.value),
+        "date": session.date,
+        "startsAt": clock_time_to_json(session.starts_at),
+        "endsAt": clock_time_to_json(session.ends_at),
+    })
+}
+
+#[debug_handler]
+async fn get_info(
+    ReqScopeModule(module): ReqScopeModule,
+    _: Auth<Authenticated>,
+    Path(id): Path<i32>,
+) -> ApiResult {
+    let session = module
+        .resolve::<AttestationSessionService>()
+        .get(Id::new(id))
+        .await
+        .map_ex(Exception)?;
+
+    ApiResult::new((
+        StatusCode::OK,
+        Reply {
+            message: "attestation session found successfully",
+            data: session_to_json(session),
+        },
+    ))
+}
+
+#[debug_handler]
+async fn list_by_study_group(
+    ReqScopeModule(module): ReqScopeModule,
+    _: Auth<Authenticated>,
+    Path(id): Path<i32>,
+) -> ApiResult {
+    let sessions = module
+        .resolve::<AttestationSessionService>()
+        .list_by_study_group(Id::new(id))
+        .await
+        .map_ex(ListException)?;
+
+    ApiResult::new((
+        StatusCode::OK,
+        Reply {
+            message: "attestation sessions found successfully",
+            data: sessions
+                .into_iter()
+                .map(session_to_json)
+                .collect::<Vec<_>>(),
+        },
+    ))
+}
+
+#[debug_handler]
+async fn create(
+    ReqScopeModule(module): ReqScopeModule,
+    Auth(claims, _): Auth<Authenticated>,
+    Json(payload): Json<SessionPayload>,
+) -> ApiResult {
+    let session = module
+        .resolve::<AttestationSessionService>()
+        .create(&Actor::from(&claims), payload.into())
+        .await
+        .map_ex(Exception)?;
+
+    ApiResult::new((
+        StatusCode::OK,
+        Reply {
+            message: "attestation session created successfully",
+            data: session_to_json(session),
+        },
+    ))
+}
+
+#[debug_handler]
+async fn update(
+    ReqScopeModule(module): ReqScopeModule,
+    Auth(claims, _): Auth<Authenticated>,
+    Path(id): Path<i32>,
+    Json(payload): Json<SessionPayload>,
+) -> ApiResult {
+    let session = module
+        .resolve::<AttestationSessionService>()
+        .update(&Actor::from(&claims), Id::new(id), payload.into())
+        .await
+        .map_ex(Exception)?;
+
+    ApiResult::new((
+        StatusCode::OK,
+        Reply {
+            message: "attestation session updated successfully",
+            data: session_to_json(session),
+        },
+    ))
+}
+
+#[debug_handler]
+async fn remove(
+    ReqScopeModule(module): ReqScopeModule,
+    Auth(claims, _): Auth<Authenticated>,
+    Path(id): Path<i32>,
+) -> ApiResult {
+    let session = module
+        .resolve::<AttestationSessionService>()
+        .delete(&Actor::from(&claims), Id::new(id))
+        .await
+        .map_ex(Exception)?;
+
+    ApiResult::new((
+        StatusCode::OK,
+        Reply {
+            message: "attestation session deleted successfully",
+            data: session_to_json(session),
+        },
+    ))
+}
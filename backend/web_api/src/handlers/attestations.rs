@@ -0,0 +1,196 @@
+use app::{
+    access_policy::Actor,
+    attestation::{self, AttestationKind, Hours},
+    attestation_service::{AttestationException, AttestationInfo, AttestationService},
+};
+use axum::{
+    debug_handler,
+    extract::Path,
+    response::IntoResponse,
+    routing::{get, post},
+    Json, Router,
+};
+use http::StatusCode;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use utils::{di::Module, entity::Id};
+
+use crate::utils::{
+    extractors::{AccessRejection, Auth, ReqScopeModule},
+    ApiResult, Authenticated, CommonState, Reply,
+};
+
+pub fn router<S: CommonState>() -> Router<S> {
+    Router::new()
+        .route("/", post(create))
+        .route("/:id", get(get_info).put(update).delete(remove))
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(super) enum AttestationKindPayload {
+    Test,
+    DiffTest,
+    Exam,
+}
+
+impl From<AttestationKindPayload> for AttestationKind {
+    fn from(value: AttestationKindPayload) -> Self {
+        match value {
+            AttestationKindPayload::Test => AttestationKind::Test,
+            AttestationKindPayload::DiffTest => AttestationKind::DiffTest,
+            AttestationKindPayload::Exam => AttestationKind::Exam,
+        }
+    }
+}
+
+impl From<AttestationKind> for AttestationKindPayload {
+    fn from(value: AttestationKind) -> Self {
+        match value {
+            AttestationKind::Test => AttestationKindPayload::Test,
+            AttestationKind::DiffTest => AttestationKindPayload::DiffTest,
+            AttestationKind::Exam => AttestationKindPayload::Exam,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct AttestationPayload {
+    curriculum_module_id: i32,
+    kind: AttestationKindPayload,
+    duration_in_hours: i32,
+    examiners: Vec<i32>,
+}
+
+impl From<AttestationPayload> for AttestationInfo {
+    fn from(value: AttestationPayload) -> Self {
+        Self {
+            curriculum_module_id: Id::new(value.curriculum_module_id),
+            kind: value.kind.into(),
+            duration: Hours(value.duration_in_hours),
+            examiners: value.examiners.into_iter().map(Id::new).collect(),
+        }
+    }
+}
+
+struct Exception(AttestationException);
+
+impl IntoResponse for Exception {
+    fn into_response(self) -> axum::response::Response {
+        let Self(ex) = self;
+        let code = match ex {
+            AttestationException::AccessException(ex) => {
+                return AccessRejection(ex).into_response()
+            }
+            AttestationException::NotFound => StatusCode::NOT_FOUND,
+            AttestationException::CurriculumModuleNotFound
+            | AttestationException::ExaminerNotFound
+            | AttestationException::InvalidDuration => StatusCode::BAD_REQUEST,
+            AttestationException::AlreadyExist
+            | AttestationException::InUse
+            | AttestationException::Scheduled
+            | AttestationException::CurriculumNotEditable => StatusCode::CONFLICT,
+        };
+
+        (code, Reply::from(ex)).into_response()
+    }
+}
+
+pub(super) fn attestation_to_json(attestation: attestation::Entity) -> serde_json::Value {
+    json!({
+        "id": attestation.id.value,
+        "curriculumModuleId": attestation.curriculum_module_id.value,
+        "kind": AttestationKindPayload::from(attestation.kind),
+        "durationInHours": attestation.duration.0,
+        "examiners": attestation
+            .examiners
+            .into_iter()
+            .map(|v| v.value)
+            .collect::<Vec<_>>(),
+    })
+}
+
+#[debug_handler]
+async fn get_info(
+    ReqScopeModule(module): ReqScopeModule,
+    _: Auth<Authenticated>,
+    Path(id): Path<i32>,
+) -> ApiResult {
+    let entity = module
+        .resolve::<AttestationService>()
+        .get(Id::new(id))
+        .await
+        .map_ex(Exception)?;
+
+    ApiResult::new((
+        StatusCode::OK,
+        Reply {
+            message: "attestation found successfully",
+            data: attestation_to_json(entity),
+        },
+    ))
+}
+
+#[debug_handler]
+async fn create(
+    ReqScopeModule(module): ReqScopeModule,
+    Auth(claims, _): Auth<Authenticated>,
+    Json(payload): Json<AttestationPayload>,
+) -> ApiResult {
+    let entity = module
+        .resolve::<AttestationService>()
+        .create(&Actor::from(&claims), payload.into())
+        .await
+        .map_ex(Exception)?;
+
+    ApiResult::new((
+        StatusCode::OK,
+        Reply {
+            message: "attestation created successfully",
+            data: attestation_to_json(entity),
+        },
+    ))
+}
+
+#[debug_handler]
+async fn update(
+    ReqScopeModule(module): ReqScopeModule,
+    Auth(claims, _): Auth<Authenticated>,
+    Path(id): Path<i32>,
+    Json(payload): Json<AttestationPayload>,
+) -> ApiResult {
+    let entity = module
+        .resolve::<AttestationService>()
+        .update(&Actor::from(&claims), Id::new(id), payload.into())
+        .await
+        .map_ex(Exception)?;
+
+    ApiResult::new((
+        StatusCode::OK,
+        Reply {
+            message: "attestation updated successfully",
+            data: attestation_to_json(entity),
+        },
+    ))
+}
+
+#[debug_handler]
+async fn remove(
+    ReqScopeModule(module): ReqScopeModule,
+    Auth(claims, _): Auth<Authenticated>,
+    Path(id): Path<i32>,
+) -> ApiResult {
+    let entity = module
+        .resolve::<AttestationService>()
+        .delete(&Actor::from(&claims), Id::new(id))
+        .await
+        .map_ex(Exception)?;
+
+    ApiResult::new((
+        StatusCode::OK,
+        Reply {
+            message: "attestation deleted successfully",
+            data: attestation_to_json(entity),
+        },
+    ))
+}
@@ -6,7 +6,7 @@ use serde_json::json;
 use utils::di::Module;
 
 use crate::utils::{
-    extractors::{ReqScopeModule, SessionMetadata},
+    extractors::{DeviceId, ReqScopeModule, SessionMetadata},
     ApiResult, EmptyData, Reply,
 };
 
@@ -44,12 +44,18 @@ impl IntoResponse for Exception {
 #[axum::debug_handler]
 async fn login(
     ReqScopeModule(module): ReqScopeModule,
-    SessionMetadata(metadata): SessionMetadata,
+    device_id: Option<DeviceId>,
+    SessionMetadata(client): SessionMetadata,
     Json(payload): Json<LoginPayload>,
 ) -> ApiResult {
     let tokens = module
         .resolve::<AuthService>()
-        .login(payload.email, payload.password, metadata)
+        .login(
+            payload.email,
+            payload.password,
+            device_id.map(|DeviceId(v)| v),
+            client,
+        )
         .await
         .map_ex(Exception)?;
 
@@ -60,6 +66,7 @@ async fn login(
             data: json!({
                 "access_token": tokens.access_token,
                 "refresh_token": tokens.refresh_token,
+                "device_id": tokens.device_id,
             }),
         },
     ))
@@ -74,12 +81,13 @@ struct RefreshTokenPayload {
 #[axum::debug_handler]
 async fn refresh_token(
     ReqScopeModule(module): ReqScopeModule,
-    SessionMetadata(metadata): SessionMetadata,
+    DeviceId(device_id): DeviceId,
+    SessionMetadata(client): SessionMetadata,
     Json(payload): Json<RefreshTokenPayload>,
 ) -> ApiResult {
     let tokens = module
         .resolve::<AuthService>()
-        .refresh_token(payload.user_id, payload.refresh_token, metadata)
+        .refresh_token(payload.user_id, payload.refresh_token, device_id, client)
         .await
         .map_ex(Exception)?;
 
@@ -98,12 +106,12 @@ async fn refresh_token(
 #[axum::debug_handler]
 async fn logout(
     ReqScopeModule(module): ReqScopeModule,
-    SessionMetadata(metadata): SessionMetadata,
+    DeviceId(device_id): DeviceId,
     Json(payload): Json<RefreshTokenPayload>,
 ) -> ApiResult {
     module
         .resolve::<AuthService>()
-        .logout(payload.user_id, payload.refresh_token, metadata)
+        .logout(payload.user_id, device_id, payload.refresh_token)
         .await
         .map_ex(Exception)?;
 
@@ -0,0 +1,363 @@
+use app::{
+    access_policy::Actor,
+    attestation::AttestationKind,
+    calendar_feed,
+    calendar_service::{Calendar, CalendarException, CalendarService, EventKind, EventSource},
+};
+use axum::{debug_handler, extract::Path, response::IntoResponse, routing::get, Router};
+use http::{header, StatusCode};
+use serde_json::json;
+use time::{OffsetDateTime, UtcOffset};
+use utils::{di::Module, entity::Id};
+
+use crate::utils::{
+    extractors::{Auth, ReqScopeModule},
+    ApiResult, Authenticated, CommonState, Reply,
+};
+
+pub fn router<S: CommonState>() -> Router<S> {
+    Router::new()
+        .route("/feed", get(get_feed).post(issue_feed).delete(revoke_feed))
+        .route("/:token/person", get(person_calendar))
+        .route("/:token/study_groups/:id", get(study_group_calendar))
+        .route("/:token/teachers/:id", get(teacher_calendar))
+}
+
+/// Longest line of an iCalendar file in octets, longer ones are folded
+const LINE_LIMIT: usize = 75;
+
+struct Exception(CalendarException);
+
+impl IntoResponse for Exception {
+    fn into_response(self) -> axum::response::Response {
+        let Self(ex) = self;
+        let code = match ex {
+            CalendarException::FeedNotFound
+            | CalendarException::PersonNotFound
+            | CalendarException::StudyGroupNotFound
+            | CalendarException::TeacherNotFound => StatusCode::NOT_FOUND,
+        };
+
+        (code, Reply::from(ex)).into_response()
+    }
+}
+
+fn feed_to_json(feed: calendar_feed::Entity) -> serde_json::Value {
+    json!({
+        "id": feed.id.value,
+        "personId": feed.person_id.value,
+    })
+}
+
+fn attestation_kind_label(kind: &AttestationKind) -> &'static str {
+    match kind {
+        AttestationKind::Test => "зачёт",
+        AttestationKind::DiffTest => "дифференцированный зачёт",
+        AttestationKind::Exam => "экзамен",
+    }
+}
+
+/// Escapes a text value as RFC 5545 requires
+fn escape_text(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for char in value.chars() {
+        match char {
+            '\\' | ';' | ',' => {
+                escaped.push('\\');
+                escaped.push(char);
+            }
+            '\n' => escaped.push_str("\\n"),
+            '\r' => {}
+            char => escaped.push(char),
+        }
+    }
+
+    escaped
+}
+
+fn date_time_utc(value: OffsetDateTime) -> String {
+    let value = value.to_offset(UtcOffset::UTC);
+    format!(
+        "{:04}{:02}{:02}T{:02}{:02}{:02}Z",
+        value.year(),
+        u8::from(value.month()),
+        value.day(),
+        value.hour(),
+        value.minute(),
+        value.second(),
+    )
+}
+
+/// Appends a content line, folding it on char boundaries and ending it with CRLF
+fn push_line(ics: &mut String, line: &str) {
+    let mut length = 0;
+    for char in line.chars() {
+        if length + char.len_utf8() > LINE_LIMIT {
+            ics.push_str("\r\n ");
+            length = 1;
+        }
+
+        ics.push(char);
+        length += char.len_utf8();
+    }
+
+    ics.push_str("\r\n");
+}
+
+fn calendar_to_ics(calendar: Calendar) -> String {
+    let stamp = date_time_utc(OffsetDateTime::now_utc());
+    let mut ics = String::new();
+
+    push_line(&mut ics, "BEGIN:VCALENDAR");
+    push_line(&mut ics, "VERSION:2.0");
+    push_line(&mut ics, "PRODID:-//uis//timetable//RU");
+    push_line(&mut ics, "CALSCALE:GREGORIAN");
+    push_line(&mut ics, "METHOD:PUBLISH");
+    push_line(
+        &mut ics,
+        &format!("X-WR-CALNAME:{}", escape_text(&calendar.name)),
+    );
+
+    for event in calendar.events {
+        let uid = match event.source {
+            EventSource::Class(id) => format!("class-{}@uis", id.value),
+            EventSource::AttestationSession(id) => format!("attestation-{}@uis", id.value),
+        };
+        let kind = match &event.kind {
+            EventKind::Class(kind) => kind.value.clone(),
+            EventKind::Attestation(kind) => attestation_kind_label(kind).to_owned(),
+        };
+
+        let mut description = vec![format!("Группа: {}", event.study_group)];
+        if !event.teachers.is_empty() {
+            description.push(format!("Преподаватель: {}", event.teachers.join(", ")));
+        }
+
+        push_line(&mut ics, "BEGIN:VEVENT");
+        push_line(&mut ics, &format!("UID:{uid}"));
+        push_line(&mut ics, &format!("DTSTAMP:{stamp}"));
+        push_line(
+            &mut ics,
+            &format!("DTSTART:{}", date_time_utc(event.starts_at)),
+        );
+        push_line(&mut ics, &format!("DTEND:{}", date_time_utc(event.ends_at)));
+        if let Some(repeat) = event.repeat {
+            push_line(
+                &mut ics,
+                &format!(
+                    "RRULE:FREQ=WEEKLY;INTERVAL={};UNTIL={}",
+                    repeat.interval_weeks,
+                    date_time_utc(repeat.until)
+                ),
+            );
+        }
+        push_line(
+            &mut ics,
+            &format!(
+                "SUMMARY:{}",
+                escape_text(&format!("{} ({kind})", event.discipline))
+            ),
+        );
+        if let Some(room) = event.room {
+            push_line(&mut ics, &format!("LOCATION:{}", escape_text(&room)));
+        }
+        push_line(
+            &mut ics,
+            &format!("DESCRIPTION:{}", escape_text(&description.join("\n"))),
+        );
+        push_line(&mut ics, "END:VEVENT");
+    }
+
+    push_line(&mut ics, "END:VCALENDAR");
+    ics
+}
+
+fn calendar_response(calendar: Calendar) -> ApiResult {
+    ApiResult::new((
+        StatusCode::OK,
+        [
+            (header::CONTENT_TYPE, "text/calendar; charset=utf-8"),
+            (
+                header::CONTENT_DISPOSITION,
+                "inline; filename=\"timetable.ics\"",
+            ),
+        ],
+        calendar_to_ics(calendar),
+    ))
+}
+
+#[debug_handler]
+async fn get_feed(
+    ReqScopeModule(module): ReqScopeModule,
+    Auth(claims, _): Auth<Authenticated>,
+) -> ApiResult {
+    let feed = module
+        .resolve::<CalendarService>()
+        .get_feed(&Actor::from(&claims))
+        .await
+        .map_ex(Exception)?;
+
+    ApiResult::new((
+        StatusCode::OK,
+        Reply {
+            message: "calendar feed found successfully",
+            data: feed_to_json(feed),
+        },
+    ))
+}
+
+#[debug_handler]
+async fn issue_feed(
+    ReqScopeModule(module): ReqScopeModule,
+    Auth(claims, _): Auth<Authenticated>,
+) -> ApiResult {
+    let issued = module
+        .resolve::<CalendarService>()
+        .issue_feed(&Actor::from(&claims))
+        .await
+        .map_ex(Exception)?;
+
+    let mut data = feed_to_json(issued.feed);
+    data["token"] = json!(issued.token);
+
+    ApiResult::new((
+        StatusCode::OK,
+        Reply {
+            message: "calendar feed issued successfully",
+            data,
+        },
+    ))
+}
+
+#[debug_handler]
+async fn revoke_feed(
+    ReqScopeModule(module): ReqScopeModule,
+    Auth(claims, _): Auth<Authenticated>,
+) -> ApiResult {
+    let feed = module
+        .resolve::<CalendarService>()
+        .revoke_feed(&Actor::from(&claims))
+        .await
+        .map_ex(Exception)?;
+
+    ApiResult::new((
+        StatusCode::OK,
+        Reply {
+            message: "calendar feed revoked successfully",
+            data: feed_to_json(feed),
+        },
+    ))
+}
+
+/// Feeds are read by calendar apps, so the token in the path stands in for the bearer token
+#[debug_handler]
+async fn person_calendar(
+    ReqScopeModule(module): ReqScopeModule,
+    Path(token): Path<String>,
+) -> ApiResult {
+    let calendar = module
+        .resolve::<CalendarService>()
+        .person_calendar(token)
+        .await
+        .map_ex(Exception)?;
+
+    calendar_response(calendar)
+}
+
+#[debug_handler]
+async fn study_group_calendar(
+    ReqScopeModule(module): ReqScopeModule,
+    Path((token, id)): Path<(String, i32)>,
+) -> ApiResult {
+    let calendar = module
+        .resolve::<CalendarService>()
+        .study_group_calendar(token, Id::new(id))
+        .await
+        .map_ex(Exception)?;
+
+    calendar_response(calendar)
+}
+
+#[debug_handler]
+async fn teacher_calendar(
+    ReqScopeModule(module): ReqScopeModule,
+    Path((token, id)): Path<(String, i32)>,
+) -> ApiResult {
+    let calendar = module
+        .resolve::<CalendarService>()
+        .teacher_calendar(token, Id::new(id))
+        .await
+        .map_ex(Exception)?;
+
+    calendar_response(calendar)
+}
+
+#[cfg(test)]
+mod tests {
+    use time::{Date, Month};
+
+    use super::*;
+
+    #[test]
+    fn text_values_are_escaped() {
+        assert_eq!(
+            escape_text("Иванов, Петров; C:\\lab\r\nroom"),
+            r"Иванов\, Петров\; C:\\lab\nroom"
+        );
+    }
+
+    #[test]
+    fn short_lines_are_not_folded() {
+        let mut ics = String::new();
+        push_line(&mut ics, &"x".repeat(LINE_LIMIT));
+
+        assert_eq!(ics, format!("{}\r\n", "x".repeat(LINE_LIMIT)));
+    }
+
+    #[test]
+    fn long_lines_are_folded_at_the_octet_limit() {
+        let mut ics = String::new();
+        push_line(&mut ics, &"x".repeat(2 * LINE_LIMIT));
+
+        let lines = ics
+            .trim_end_matches("\r\n")
+            .split("\r\n")
+            .collect::<Vec<_>>();
+        assert_eq!(lines.len(), 3);
+        assert!(lines.iter().all(|v| v.len() <= LINE_LIMIT));
+        assert_eq!(lines[0].len(), LINE_LIMIT);
+        assert!(lines[1..].iter().all(|v| v.starts_with(' ')));
+        assert_eq!(lines.concat().replace(' ', ""), "x".repeat(2 * LINE_LIMIT));
+    }
+
+    #[test]
+    fn folding_keeps_multibyte_chars_whole() {
+        let line = "Ж".repeat(LINE_LIMIT);
+        let mut ics = String::new();
+        push_line(&mut ics, &line);
+
+        let lines = ics
+            .trim_end_matches("\r\n")
+            .split("\r\n")
+            .collect::<Vec<_>>();
+        assert!(lines.iter().all(|v| v.len() <= LINE_LIMIT));
+        // 37 two-octet chars fit the first line, 37 more after the leading space
+        assert_eq!(lines[0].chars().count(), 37);
+        assert_eq!(lines[1].chars().count(), 38);
+        assert_eq!(lines.concat().replace(' ', ""), line);
+    }
+
+    #[test]
+    fn date_times_are_written_in_utc() {
+        assert_eq!(
+            date_time_utc(
+                Date::from_calendar_date(2024, Month::September, 2)
+                    .unwrap()
+                    .with_hms(8, 30, 0)
+                    .unwrap()
+                    .assume_offset(UtcOffset::from_hms(7, 0, 0).unwrap())
+            ),
+            "20240902T013000Z"
+        );
+    }
+}
@@ -0,0 +1,96 @@
+use app::{
+    access_policy::Actor,
+    class_kind,
+    class_kind_service::{ClassKindException, ClassKindService},
+};
+use axum::{
+    debug_handler,
+    extract::Path,
+    response::IntoResponse,
+    routing::{delete, post},
+    Json, Router,
+};
+use http::StatusCode;
+use serde::Deserialize;
+use serde_json::json;
+use utils::{di::Module, entity::Id};
+
+use crate::utils::{
+    extractors::{AccessRejection, Auth, ReqScopeModule},
+    ApiResult, Authenticated, CommonState, Reply,
+};
+
+pub fn router<S: CommonState>() -> Router<S> {
+    Router::new()
+        .route("/", post(create))
+        .route("/:name", delete(remove))
+}
+
+#[derive(Debug, Deserialize)]
+struct ClassKindPayload {
+    name: String,
+}
+
+struct Exception(ClassKindException);
+
+impl IntoResponse for Exception {
+    fn into_response(self) -> axum::response::Response {
+        let Self(ex) = self;
+        let code = match ex {
+            ClassKindException::AccessException(ex) => return AccessRejection(ex).into_response(),
+            ClassKindException::NotFound => StatusCode::NOT_FOUND,
+            ClassKindException::InvalidName => StatusCode::BAD_REQUEST,
+            ClassKindException::AlreadyExist | ClassKindException::InUse => StatusCode::CONFLICT,
+        };
+
+        (code, Reply::from(ex)).into_response()
+    }
+}
+
+fn class_kind_to_json(class_kind: class_kind::Entity) -> serde_json::Value {
+    json!({
+        "name": class_kind.name.value,
+    })
+}
+
+#[debug_handler]
+async fn create(
+    ReqScopeModule(module): ReqScopeModule,
+    Auth(claims, _): Auth<Authenticated>,
+    Json(payload): Json<ClassKindPayload>,
+) -> ApiResult {
+    let entity = module
+        .resolve::<ClassKindService>()
+        .create(&Actor::from(&claims), payload.name)
+        .await
+        .map_ex(Exception)?;
+
+    ApiResult::new((
+        StatusCode::OK,
+        Reply {
+            message: "class kind created successfully",
+            data: class_kind_to_json(entity),
+        },
+    ))
+}
+
+#[debug_handler]
+async fn remove(
+    ReqScopeModule(module): ReqScopeModule,
+    Auth(claims, _): Auth<Authenticated>,
+    Path(name): Path<String>,
+) -> ApiResult {
+    let entity = module
+        .resolve::<ClassKindService>()
+        .delete(&Actor::from(&claims), Id::new(name))
+        .await
+        .map_ex(Exception)?;
+
+    ApiResult::new((
+        StatusCode::OK,
+        Reply {
+            message: "class kind deleted successfully",
+            data: class_kind_to_json(entity),
+        },
+    ))
+}
@@ -0,0 +1,159 @@
+use app::{
+    access_policy::Actor,
+    attestation::Hours,
+    class,
+    class_service::{ClassException, ClassInfo, ClassService},
+};
+use axum::{
+    debug_handler,
+    extract::Path,
+    response::IntoResponse,
+    routing::{get, post},
+    Json, Router,
+};
+use http::StatusCode;
+use serde::Deserialize;
+use serde_json::json;
+use utils::{di::Module, entity::Id};
+
+use crate::utils::{
+    extractors::{AccessRejection, Auth, ReqScopeModule},
+    ApiResult, Authenticated, CommonState, Reply,
+};
+
+pub fn router<S: CommonState>() -> Router<S> {
+    Router::new()
+        .route("/", post(create))
+        .route("/:id", get(get_info).put(update).delete(remove))
+}
+
+#[derive(Debug, Deserialize)]
+struct ClassPayload {
+    curriculum_module_id: i32,
+    kind_name: String,
+    duration_in_hours: i32,
+}
+
+impl From<ClassPayload> for ClassInfo {
+    fn from(value: ClassPayload) -> Self {
+        Self {
+            curriculum_module_id: Id::new(value.curriculum_module_id),
+            kind_name: Id::new(value.kind_name),
+            duration: Hours(value.duration_in_hours),
+        }
+    }
+}
+
+struct Exception(ClassException);
+
+impl IntoResponse for Exception {
+    fn into_response(self) -> axum::response::Response {
+        let Self(ex) = self;
+        let code = match ex {
+            ClassException::AccessException(ex) => return AccessRejection(ex).into_response(),
+            ClassException::NotFound => StatusCode::NOT_FOUND,
+            ClassException::CurriculumModuleNotFound
+            | ClassException::ClassKindNotFound
+            | ClassException::InvalidDuration => StatusCode::BAD_REQUEST,
+            ClassException::InUse
+            | ClassException::Scheduled
+            | ClassException::CurriculumNotEditable => StatusCode::CONFLICT,
+        };
+
+        (code, Reply::from(ex)).into_response()
+    }
+}
+
+pub(super) fn class_to_json(class: class::Entity) -> serde_json::Value {
+    json!({
+        "id": class.id.value,
+        "curriculumModuleId": class.curriculum_module_id.value,
+        "kindName": class.kind_name.value,
+        "durationInHours": class.duration.0,
+    })
+}
+
+#[debug_handler]
+async fn get_info(
+    ReqScopeModule(module): ReqScopeModule,
+    _: Auth<Authenticated>,
+    Path(id): Path<i32>,
+) -> ApiResult {
+    let entity = module
+        .resolve::<ClassService>()
+        .get(Id::new(id))
+        .await
+        .map_ex(Exception)?;
+
+    ApiResult::new((
+        StatusCode::OK,
+        Reply {
+            message: "class found successfully",
+            data: class_to_json(entity),
+        },
+    ))
+}
+
+#[debug_handler]
+async fn create(
+    ReqScopeModule(module): ReqScopeModule,
+    Auth(claims, _): Auth<Authenticated>,
+    Json(payload): Json<ClassPayload>,
+) -> ApiResult {
+    let entity = module
+        .resolve::<ClassService>()
+        .create(&Actor::from(&claims), payload.into())
+        .await
+        .map_ex(Exception)?;
+
+    ApiResult::new((
+        StatusCode::OK,
+        Reply {
+            message: "class created successfully",
+            data: class_to_json(entity),
+        },
+    ))
+}
+
+#[debug_handler]
+async fn update(
+    ReqScopeModule(module): ReqScopeModule,
+    Auth(claims, _): Auth<Authenticated>,
+    Path(id): Path<i32>,
+    Json(payload): Json<ClassPayload>,
+) -> ApiResult {
+    let entity = module
+        .resolve::<ClassService>()
+        .update(&Actor::from(&claims), Id::new(id), payload.into())
+        .await
+        .map_ex(Exception)?;
+
+    ApiResult::new((
+        StatusCode::OK,
+        Reply {
+            message: "class updated successfully",
+            data: class_to_json(entity),
+        },
+    ))
+}
+
+#[debug_handler]
+async fn remove(
+    ReqScopeModule(module): ReqScopeModule,
+    Auth(claims, _): Auth<Authenticated>,
+    Path(id): Path<i32>,
+) -> ApiResult {
+    let entity = module
+        .resolve::<ClassService>()
+        .delete(&Actor::from(&claims), Id::new(id))
+        .await
+        .map_ex(Exception)?;
+
+    ApiResult::new((
+        StatusCode::OK,
+        Reply {
+            message: "class deleted successfully",
+            data: class_to_json(entity),
+        },
+    ))
+}
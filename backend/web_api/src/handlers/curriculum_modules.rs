@@ -0,0 +1,162 @@
+use app::{
+    access_policy::Actor,
+    curriculum_module,
+    curriculum_module_service::{
+        CurriculumModuleException, CurriculumModuleInfo, CurriculumModuleService,
+    },
+};
+use axum::{
+    debug_handler,
+    extract::Path,
+    response::IntoResponse,
+    routing::{get, post},
+    Json, Router,
+};
+use http::StatusCode;
+use serde::Deserialize;
+use serde_json::json;
+use utils::{di::Module, entity::Id};
+
+use crate::utils::{
+    extractors::{AccessRejection, Auth, ReqScopeModule},
+    ApiResult, Authenticated, CommonState, Reply,
+};
+
+pub fn router<S: CommonState>() -> Router<S> {
+    Router::new()
+        .route("/", post(create))
+        .route("/:id", get(get_info).put(update).delete(remove))
+}
+
+#[derive(Debug, Deserialize)]
+struct CurriculumModulePayload {
+    curriculum_id: i32,
+    discipline_id: i32,
+    semester: i32,
+}
+
+impl From<CurriculumModulePayload> for CurriculumModuleInfo {
+    fn from(value: CurriculumModulePayload) -> Self {
+        Self {
+            curriculum_id: Id::new(value.curriculum_id),
+            discipline_id: Id::new(value.discipline_id),
+            semester: value.semester,
+        }
+    }
+}
+
+struct Exception(CurriculumModuleException);
+
+impl IntoResponse for Exception {
+    fn into_response(self) -> axum::response::Response {
+        let Self(ex) = self;
+        let code = match ex {
+            CurriculumModuleException::AccessException(ex) => {
+                return AccessRejection(ex).into_response()
+            }
+            CurriculumModuleException::NotFound => StatusCode::NOT_FOUND,
+            CurriculumModuleException::CurriculumNotFound
+            | CurriculumModuleException::DisciplineNotFound
+            | CurriculumModuleException::InvalidSemester => StatusCode::BAD_REQUEST,
+            CurriculumModuleException::InUse | CurriculumModuleException::CurriculumNotEditable => {
+                StatusCode::CONFLICT
+            }
+        };
+
+        (code, Reply::from(ex)).into_response()
+    }
+}
+
+pub(super) fn curriculum_module_to_json(module: curriculum_module::Entity) -> serde_json::Value {
+    json!({
+        "id": module.id.value,
+        "curriculumId": module.curriculum_id.value,
+        "disciplineId": module.discipline_id.value,
+        "semester": module.semester,
+    })
+}
+
+#[debug_handler]
+async fn get_info(
+    ReqScopeModule(module): ReqScopeModule,
+    _: Auth<Authenticated>,
+    Path(id): Path<i32>,
+) -> ApiResult {
+    let entity = module
+        .resolve::<CurriculumModuleService>()
+        .get(Id::new(id))
+        .await
+        .map_ex(Exception)?;
+
+    ApiResult::new((
+        StatusCode::OK,
+        Reply {
+            message: "curriculum module found successfully",
+            data: curriculum_module_to_json(entity),
+        },
+    ))
+}
+
+#[debug_handler]
+async fn create(
+    ReqScopeModule(module): ReqScopeModule,
+    Auth(claims, _): Auth<Authenticated>,
+    Json(payload): Json<CurriculumModulePayload>,
+) -> ApiResult {
+    let entity = module
+        .resolve::<CurriculumModuleService>()
+        .create(&Actor::from(&claims), payload.into())
+        .await
+        .map_ex(Exception)?;
+
+    ApiResult::new((
+        StatusCode::OK,
+        Reply {
+            message: "curriculum module created successfully",
+            data: curriculum_module_to_json(entity),
+        },
+    ))
+}
+
+#[debug_handler]
+async fn update(
+    ReqScopeModule(module): ReqScopeModule,
+    Auth(claims, _): Auth<Authenticated>,
+    Path(id): Path<i32>,
+    Json(payload): Json<CurriculumModulePayload>,
+) -> ApiResult {
+    let entity = module
+        .resolve::<CurriculumModuleService>()
+        .update(&Actor::from(&claims), Id::new(id), payload.into())
+        .await
+        .map_ex(Exception)?;
+
+    ApiResult::new((
+        StatusCode::OK,
+        Reply {
+            message: "curriculum module updated successfully",
+            data: curriculum_module_to_json(entity),
+        },
+    ))
+}
+
+#[debug_handler]
+async fn remove(
+    ReqScopeModule(module): ReqScopeModule,
+    Auth(claims, _): Auth<Authenticated>,
+    Path(id): Path<i32>,
+) -> ApiResult {
+    let entity = module
+        .resolve::<CurriculumModuleService>()
+        .delete(&Actor::from(&claims), Id::new(id))
+        .await
+        .map_ex(Exception)?;
+
+    ApiResult::new((
+        StatusCode::OK,
+        Reply {
+            message: "curriculum module deleted successfully",
+            data: curriculum_module_to_json(entity),
+        },
+    ))
+}
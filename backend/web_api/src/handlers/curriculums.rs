@@ -1,22 +1,238 @@
 use std::collections::{HashMap, HashSet};
 
 use anyhow::Context;
-use app::{curriculum, curriculum_module, discipline, study_group, subdivision};
-use axum::{debug_handler, extract::Path, response::IntoResponse, routing::post, Json, Router};
+use app::{
+    access_policy::Actor,
+    attestation::Hours,
+    curriculum::{self, CurriculumStatus, Issue},
+    curriculum_module,
+    curriculum_service::{
+        AttestationDraft, ClassDraft, CurriculumException, CurriculumInfo, CurriculumService,
+        ModuleDiff, ModuleDraft, ModuleSummary, OutlineModule,
+    },
+    discipline, study_group, subdivision,
+};
+use axum::{
+    debug_handler,
+    extract::Path,
+    response::IntoResponse,
+    routing::{get, post},
+    Json, Router,
+};
 use http::StatusCode;
+use serde::{Deserialize, Serialize};
 use serde_json::json;
 use utils::{di::Module, entity::Id};
 
-use crate::utils::{extractors::ReqScopeModule, ApiResult, CommonState};
+use super::{
+    attestations::{attestation_to_json, AttestationKindPayload},
+    classes::class_to_json,
+    curriculum_modules::curriculum_module_to_json,
+};
+use crate::utils::{
+    extractors::{AccessRejection, Auth, ReqScopeModule},
+    ApiResult, Authenticated, CommonState, Reply,
+};
 
 pub fn router<S: CommonState>() -> Router<S> {
     Router::new()
-        .route("/", axum::routing::get(get_infos))
-        .route("/:id", axum::routing::get(get_info))
+        .route("/", get(get_infos).post(create))
+        .route("/:id", get(get_info).put(update).delete(remove))
+        .route("/:id/outline", get(get_outline))
+        .route("/:id/issues", get(get_issues))
+        .route("/:id/modules", post(add_module))
+        .route("/:id/publish", post(publish))
+        .route("/:id/archive", post(archive))
+        .route("/:id/versions", get(get_versions).post(clone_version))
+        .route("/:id/diff/:other_id", get(get_diff))
+}
+
+#[derive(Debug, Deserialize)]
+struct CurriculumPayload {
+    name: String,
+    department_id: i32,
+}
+
+impl From<CurriculumPayload> for CurriculumInfo {
+    fn from(value: CurriculumPayload) -> Self {
+        Self {
+            name: value.name,
+            department_id: Id::new(value.department_id),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct ClassDraftPayload {
+    kind_name: String,
+    duration_in_hours: i32,
+}
+
+#[derive(Debug, Deserialize)]
+struct AttestationDraftPayload {
+    kind: AttestationKindPayload,
+    duration_in_hours: i32,
+    examiners: Vec<i32>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ModuleDraftPayload {
+    discipline_id: i32,
+    semester: i32,
+    classes: Vec<ClassDraftPayload>,
+    attestation: Option<AttestationDraftPayload>,
+}
+
+impl From<ModuleDraftPayload> for ModuleDraft {
+    fn from(value: ModuleDraftPayload) -> Self {
+        Self {
+            discipline_id: Id::new(value.discipline_id),
+            semester: value.semester,
+            classes: value
+                .classes
+                .into_iter()
+                .map(|v| ClassDraft {
+                    kind_name: Id::new(v.kind_name),
+                    duration: Hours(v.duration_in_hours),
+                })
+                .collect(),
+            attestation: value.attestation.map(|v| AttestationDraft {
+                kind: v.kind.into(),
+                duration: Hours(v.duration_in_hours),
+                examiners: v.examiners.into_iter().map(Id::new).collect(),
+            }),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize)]
+#[serde(rename_all = "snake_case")]
+enum CurriculumStatusPayload {
+    Draft,
+    Published,
+    Archived,
+}
+
+impl From<CurriculumStatus> for CurriculumStatusPayload {
+    fn from(value: CurriculumStatus) -> Self {
+        match value {
+            CurriculumStatus::Draft => CurriculumStatusPayload::Draft,
+            CurriculumStatus::Published => CurriculumStatusPayload::Published,
+            CurriculumStatus::Archived => CurriculumStatusPayload::Archived,
+        }
+    }
+}
+
+struct Exception(CurriculumException);
+
+impl IntoResponse for Exception {
+    fn into_response(self) -> axum::response::Response {
+        let Self(ex) = self;
+        let code = match ex {
+            CurriculumException::AccessException(ex) => return AccessRejection(ex).into_response(),
+            CurriculumException::NotFound => StatusCode::NOT_FOUND,
+            CurriculumException::InvalidName
+            | CurriculumException::DepartmentNotFound
+            | CurriculumException::DisciplineNotFound
+            | CurriculumException::InvalidSemester
+            | CurriculumException::ClassKindNotFound
+            | CurriculumException::InvalidDuration
+            | CurriculumException::ExaminerNotFound => StatusCode::BAD_REQUEST,
+            CurriculumException::AlreadyExist
+            | CurriculumException::InUse
+            | CurriculumException::NotEditable
+            | CurriculumException::InvalidStatusChange
+            | CurriculumException::HasIssues => StatusCode::CONFLICT,
+        };
+
+        (code, Reply::from(ex)).into_response()
+    }
+}
+
+fn curriculum_to_json(curriculum: curriculum::Entity) -> serde_json::Value {
+    json!({
+        "id": curriculum.id.value,
+        "name": curriculum.name,
+        "version": curriculum.version,
+        "status": CurriculumStatusPayload::from(curriculum.status),
+        "departmentId": curriculum.department_id.map(|v| v.value),
+    })
+}
+
+fn module_summary_to_json(summary: ModuleSummary) -> serde_json::Value {
+    json!({
+        "hours": summary.hours,
+        "byClassKind": summary
+            .by_class_kind
+            .into_iter()
+            .map(|(kind, hours)| json!({ "classKind": kind.value, "hours": hours }))
+            .collect::<Vec<_>>(),
+        "attestation": summary.attestation.map(AttestationKindPayload::from),
+    })
+}
+
+fn module_diff_to_json(diff: ModuleDiff) -> serde_json::Value {
+    let change = match (&diff.before, &diff.after) {
+        (None, _) => "added",
+        (_, None) => "removed",
+        _ => "changed",
+    };
+
+    json!({
+        "disciplineId": diff.discipline_id.value,
+        "semester": diff.semester,
+        "change": change,
+        "before": diff.before.map(module_summary_to_json),
+        "after": diff.after.map(module_summary_to_json),
+    })
+}
+
+fn issue_to_json(issue: Issue) -> serde_json::Value {
+    match issue {
+        Issue::Empty => json!({ "kind": "empty" }),
+        Issue::MissingAttestation {
+            curriculum_module_id,
+        } => json!({
+            "kind": "missing_attestation",
+            "curriculumModuleId": curriculum_module_id.value,
+        }),
+        Issue::MissingSemester { semester } => json!({
+            "kind": "missing_semester",
+            "semester": semester,
+        }),
+        Issue::SemesterHours { semester, hours } => json!({
+            "kind": "semester_hours",
+            "semester": semester,
+            "hours": hours,
+        }),
+    }
+}
+
+fn issues_to_json(issues: Vec<Issue>) -> serde_json::Value {
+    json!({
+        "isValid": issues.is_empty(),
+        "issues": issues.into_iter().map(issue_to_json).collect::<Vec<_>>(),
+    })
+}
+
+fn outline_module_to_json(module: OutlineModule) -> serde_json::Value {
+    let mut json = curriculum_module_to_json(module.curriculum_module);
+    json["classes"] = module
+        .classes
+        .into_iter()
+        .map(class_to_json)
+        .collect::<Vec<_>>()
+        .into();
+    json["attestation"] = module
+        .attestation
+        .map(attestation_to_json)
+        .unwrap_or_default();
+    json["hours"] = module.hours.into();
+    json
 }
 
 #[debug_handler]
-async fn get_infos(ReqScopeModule(module): ReqScopeModule) -> ApiResult {
+async fn get_infos(ReqScopeModule(module): ReqScopeModule, _: Auth<Authenticated>) -> ApiResult {
     let repo = module.adapters.resolve::<curriculum::BoxedRepo>();
 
     let entities = match repo.list().await {
@@ -36,11 +252,15 @@ async fn get_infos(ReqScopeModule(module): ReqScopeModule) -> ApiResult {
 }
 
 #[debug_handler]
-async fn get_info(module: ReqScopeModule, Path(id): Path<i32>) -> ApiResult {
+async fn get_info(
+    module: ReqScopeModule,
+    _: Auth<Authenticated>,
+    Path(id): Path<i32>,
+) -> ApiResult {
     let val = match load_info(module, id).await {
         Ok(val) => Json(val),
         Err(err) => {
-            dbg!(err);
+            tracing::error!(?err, curriculum_id = id, "failed to load curriculum");
             let msg = Json(json!({
                 "message": "curriculum not found",
             }));
@@ -110,7 +330,268 @@ async fn load_info(
 
     Ok(json!({
         "name": curriculum.name,
+        "version": curriculum.version,
+        "status": CurriculumStatusPayload::from(curriculum.status),
+        "departmentId": curriculum.department_id.map(|v| v.value),
         "studyGroups": study_groups,
         "semesters": semesters,
     }))
 }
+
+#[debug_handler]
+async fn create(
+    ReqScopeModule(module): ReqScopeModule,
+    Auth(claims, _): Auth<Authenticated>,
+    Json(payload): Json<CurriculumPayload>,
+) -> ApiResult {
+    let entity = module
+        .resolve::<CurriculumService>()
+        .create(&Actor::from(&claims), payload.into())
+        .await
+        .map_ex(Exception)?;
+
+    ApiResult::new((
+        StatusCode::OK,
+        Reply {
+            message: "curriculum created successfully",
+            data: curriculum_to_json(entity),
+        },
+    ))
+}
+
+#[debug_handler]
+async fn update(
+    ReqScopeModule(module): ReqScopeModule,
+    Auth(claims, _): Auth<Authenticated>,
+    Path(id): Path<i32>,
+    Json(payload): Json<CurriculumPayload>,
+) -> ApiResult {
+    let entity = module
+        .resolve::<CurriculumService>()
+        .update(&Actor::from(&claims), Id::new(id), payload.into())
+        .await
+        .map_ex(Exception)?;
+
+    ApiResult::new((
+        StatusCode::OK,
+        Reply {
+            message: "curriculum updated successfully",
+            data: curriculum_to_json(entity),
+        },
+    ))
+}
+
+#[debug_handler]
+async fn remove(
+    ReqScopeModule(module): ReqScopeModule,
+    Auth(claims, _): Auth<Authenticated>,
+    Path(id): Path<i32>,
+) -> ApiResult {
+    let entity = module
+        .resolve::<CurriculumService>()
+        .delete(&Actor::from(&claims), Id::new(id))
+        .await
+        .map_ex(Exception)?;
+
+    ApiResult::new((
+        StatusCode::OK,
+        Reply {
+            message: "curriculum deleted successfully",
+            data: curriculum_to_json(entity),
+        },
+    ))
+}
+
+#[debug_handler]
+async fn get_outline(
+    ReqScopeModule(module): ReqScopeModule,
+    _: Auth<Authenticated>,
+    Path(id): Path<i32>,
+) -> ApiResult {
+    let outline = module
+        .resolve::<CurriculumService>()
+        .outline(Id::new(id))
+        .await
+        .map_ex(Exception)?;
+
+    let mut json = curriculum_to_json(outline.curriculum);
+    json["semesters"] = outline
+        .semesters
+        .into_iter()
+        .map(|v| {
+            json!({
+                "semester": v.semester,
+                "hours": v.hours,
+                "modules": v
+                    .modules
+                    .into_iter()
+                    .map(outline_module_to_json)
+                    .collect::<Vec<_>>(),
+            })
+        })
+        .collect::<Vec<_>>()
+        .into();
+    json["validation"] = issues_to_json(outline.issues);
+
+    ApiResult::new((
+        StatusCode::OK,
+        Reply {
+            message: "curriculum outline found successfully",
+            data: json,
+        },
+    ))
+}
+
+#[debug_handler]
+async fn get_issues(
+    ReqScopeModule(module): ReqScopeModule,
+    _: Auth<Authenticated>,
+    Path(id): Path<i32>,
+) -> ApiResult {
+    let issues = module
+        .resolve::<CurriculumService>()
+        .issues(Id::new(id))
+        .await
+        .map_ex(Exception)?;
+
+    ApiResult::new((
+        StatusCode::OK,
+        Reply {
+            message: "curriculum validated successfully",
+            data: issues_to_json(issues),
+        },
+    ))
+}
+
+#[debug_handler]
+async fn add_module(
+    ReqScopeModule(module): ReqScopeModule,
+    Auth(claims, _): Auth<Authenticated>,
+    Path(id): Path<i32>,
+    Json(payload): Json<ModuleDraftPayload>,
+) -> ApiResult {
+    let curriculum_module = module
+        .resolve::<CurriculumService>()
+        .add_module(&Actor::from(&claims), Id::new(id), payload.into())
+        .await
+        .map_ex(Exception)?;
+
+    ApiResult::new((
+        StatusCode::OK,
+        Reply {
+            message: "curriculum module added successfully",
+            data: outline_module_to_json(curriculum_module),
+        },
+    ))
+}
+
+#[debug_handler]
+async fn publish(
+    ReqScopeModule(module): ReqScopeModule,
+    Auth(claims, _): Auth<Authenticated>,
+    Path(id): Path<i32>,
+) -> ApiResult {
+    let curriculum = module
+        .resolve::<CurriculumService>()
+        .publish(&Actor::from(&claims), Id::new(id))
+        .await
+        .map_ex(Exception)?;
+
+    ApiResult::new((
+        StatusCode::OK,
+        Reply {
+            message: "curriculum published successfully",
+            data: curriculum_to_json(curriculum),
+        },
+    ))
+}
+
+#[debug_handler]
+async fn archive(
+    ReqScopeModule(module): ReqScopeModule,
+    Auth(claims, _): Auth<Authenticated>,
+    Path(id): Path<i32>,
+) -> ApiResult {
+    let curriculum = module
+        .resolve::<CurriculumService>()
+        .archive(&Actor::from(&claims), Id::new(id))
+        .await
+        .map_ex(Exception)?;
+
+    ApiResult::new((
+        StatusCode::OK,
+        Reply {
+            message: "curriculum archived successfully",
+            data: curriculum_to_json(curriculum),
+        },
+    ))
+}
+
+#[debug_handler]
+async fn clone_version(
+    ReqScopeModule(module): ReqScopeModule,
+    Auth(claims, _): Auth<Authenticated>,
+    Path(id): Path<i32>,
+) -> ApiResult {
+    let curriculum = module
+        .resolve::<CurriculumService>()
+        .clone_version(&Actor::from(&claims), Id::new(id))
+        .await
+        .map_ex(Exception)?;
+
+    ApiResult::new((
+        StatusCode::OK,
+        Reply {
+            message: "curriculum version created successfully",
+            data: curriculum_to_json(curriculum),
+        },
+    ))
+}
+
+#[debug_handler]
+async fn get_versions(
+    ReqScopeModule(module): ReqScopeModule,
+    _: Auth<Authenticated>,
+    Path(id): Path<i32>,
+) -> ApiResult {
+    let versions = module
+        .resolve::<CurriculumService>()
+        .versions(Id::new(id))
+        .await
+        .map_ex(Exception)?;
+
+    ApiResult::new((
+        StatusCode::OK,
+        Reply {
+            message: "curriculum versions found successfully",
+            data: versions
+                .into_iter()
+                .map(curriculum_to_json)
+                .collect::<Vec<_>>(),
+        },
+    ))
+}
+
+#[debug_handler]
+async fn get_diff(
+    ReqScopeModule(module): ReqScopeModule,
+    _: Auth<Authenticated>,
+    Path((id, other_id)): Path<(i32, i32)>,
+) -> ApiResult {
+    let diff = module
+        .resolve::<CurriculumService>()
+        .diff(Id::new(id), Id::new(other_id))
+        .await
+        .map_ex(Exception)?;
+
+    ApiResult::new((
+        StatusCode::OK,
+        Reply {
+            message: "curriculum versions compared successfully",
+            data: diff
+                .into_iter()
+                .map(module_diff_to_json)
+                .collect::<Vec<_>>(),
+        },
+    ))
+}
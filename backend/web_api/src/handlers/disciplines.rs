@@ -0,0 +1,153 @@
+use app::{
+    access_policy::Actor,
+    discipline,
+    discipline_service::{DisciplineException, DisciplineInfo, DisciplineService},
+};
+use axum::{
+    debug_handler,
+    extract::Path,
+    response::IntoResponse,
+    routing::{get, post},
+    Json, Router,
+};
+use http::StatusCode;
+use serde::Deserialize;
+use serde_json::json;
+use utils::{di::Module, entity::Id};
+
+use crate::utils::{
+    extractors::{AccessRejection, Auth, ReqScopeModule},
+    ApiResult, Authenticated, CommonState, Reply,
+};
+
+pub fn router<S: CommonState>() -> Router<S> {
+    Router::new()
+        .route("/", post(create))
+        .route("/:id", get(get_info).put(update).delete(remove))
+}
+
+#[derive(Debug, Deserialize)]
+struct DisciplinePayload {
+    name: String,
+    department_id: i32,
+}
+
+impl From<DisciplinePayload> for DisciplineInfo {
+    fn from(value: DisciplinePayload) -> Self {
+        Self {
+            name: value.name,
+            department_id: Id::new(value.department_id),
+        }
+    }
+}
+
+struct Exception(DisciplineException);
+
+impl IntoResponse for Exception {
+    fn into_response(self) -> axum::response::Response {
+        let Self(ex) = self;
+        let code = match ex {
+            DisciplineException::AccessException(ex) => return AccessRejection(ex).into_response(),
+            DisciplineException::NotFound => StatusCode::NOT_FOUND,
+            DisciplineException::InvalidName | DisciplineException::DepartmentNotFound => {
+                StatusCode::BAD_REQUEST
+            }
+            DisciplineException::InUse => StatusCode::CONFLICT,
+        };
+
+        (code, Reply::from(ex)).into_response()
+    }
+}
+
+fn discipline_to_json(discipline: discipline::Entity) -> serde_json::Value {
+    json!({
+        "id": discipline.id.value,
+        "name": discipline.name,
+        "departmentId": discipline.department_id.value,
+    })
+}
+
+#[debug_handler]
+async fn get_info(
+    ReqScopeModule(module): ReqScopeModule,
+    _: Auth<Authenticated>,
+    Path(id): Path<i32>,
+) -> ApiResult {
+    let entity = module
+        .resolve::<DisciplineService>()
+        .get(Id::new(id))
+        .await
+        .map_ex(Exception)?;
+
+    ApiResult::new((
+        StatusCode::OK,
+        Reply {
+            message: "discipline found successfully",
+            data: discipline_to_json(entity),
+        },
+    ))
+}
+
+#[debug_handler]
+async fn create(
+    ReqScopeModule(module): ReqScopeModule,
+    Auth(claims, _): Auth<Authenticated>,
+    Json(payload): Json<DisciplinePayload>,
+) -> ApiResult {
+    let entity = module
+        .resolve::<DisciplineService>()
+        .create(&Actor::from(&claims), payload.into())
+        .await
+        .map_ex(Exception)?;
+
+    ApiResult::new((
+        StatusCode::OK,
+        Reply {
+            message: "discipline created successfully",
+            data: discipline_to_json(entity),
+        },
+    ))
+}
+
+#[debug_handler]
+async fn update(
+    ReqScopeModule(module): ReqScopeModule,
+    Auth(claims, _): Auth<Authenticated>,
+    Path(id): Path<i32>,
+    Json(payload): Json<DisciplinePayload>,
+) -> ApiResult {
+    let entity = module
+        .resolve::<DisciplineService>()
+        .update(&Actor::from(&claims), Id::new(id), payload.into())
+        .await
+        .map_ex(Exception)?;
+
+    ApiResult::new((
+        StatusCode::OK,
+        Reply {
+            message: "discipline updated successfully",
+            data: discipline_to_json(entity),
+        },
+    ))
+}
+
+#[debug_handler]
+async fn remove(
+    ReqScopeModule(module): ReqScopeModule,
+    Auth(claims, _): Auth<Authenticated>,
+    Path(id): Path<i32>,
+) -> ApiResult {
+    let entity = module
+        .resolve::<DisciplineService>()
+        .delete(&Actor::from(&claims), Id::new(id))
+        .await
+        .map_ex(Exception)?;
+
+    ApiResult::new((
+        StatusCode::OK,
+        Reply {
+            message: "discipline deleted successfully",
+            data: discipline_to_json(entity),
+        },
+    ))
+}
@@ -0,0 +1,281 @@
+use std::collections::HashMap;
+
+use app::{
+    access_policy::Actor,
+    curriculum_module, discipline,
+    gradebook_service::{Gradebook, GradebookException, GradebookService, GradedScore},
+    grading_scale::Scale,
+    student::StudentAttestation,
+};
+use axum::{
+    debug_handler,
+    extract::{Path, Query},
+    response::IntoResponse,
+    routing::{get, post},
+    Json, Router,
+};
+use http::StatusCode;
+use serde::Deserialize;
+use serde_json::json;
+use utils::{di::Module, entity::Id, outcome::Outcome};
+
+use super::grading_scales::{grade_to_cell, grade_to_json};
+use crate::utils::{
+    export::{columns, Cell, Label, Table},
+    extractors::{AccessRejection, Auth, Export, ReqScopeModule},
+    ApiResult, Authenticated, CommonState, Reply,
+};
+
+pub fn router<S: CommonState>() -> Router<S> {
+    Router::new()
+        .route("/study_groups/:id", get(get_gradebook))
+        .route(
+            "/attestations/:attestation_id/students/:student_id",
+            post(submit).put(amend),
+        )
+        .route(
+            "/attestations/:attestation_id/students/:student_id/finalize",
+            post(finalize),
+        )
+}
+
+#[derive(Debug, Deserialize)]
+struct GradebookQuery {
+    semester: i32,
+}
+
+#[derive(Debug, Deserialize)]
+struct ScorePayload {
+    score: i32,
+}
+
+struct Exception(GradebookException);
+
+impl IntoResponse for Exception {
+    fn into_response(self) -> axum::response::Response {
+        let Self(ex) = self;
+        let code = match ex {
+            GradebookException::AccessException(ex) => return AccessRejection(ex).into_response(),
+            GradebookException::AttestationNotFound
+            | GradebookException::StudentNotFound
+            | GradebookException::StudyGroupNotFound => StatusCode::NOT_FOUND,
+            GradebookException::InvalidScore
+            | GradebookException::InvalidSemester
+            | GradebookException::NotInCurriculum => StatusCode::BAD_REQUEST,
+            GradebookException::AlreadyGraded
+            | GradebookException::NotGraded
+            | GradebookException::Finalized => StatusCode::CONFLICT,
+        };
+
+        (code, Reply::from(ex)).into_response()
+    }
+}
+
+fn score_to_json(score: StudentAttestation) -> serde_json::Value {
+    json!({
+        "attestationId": score.attestation_id.value,
+        "score": score.score,
+        "isFinal": score.is_final,
+    })
+}
+
+fn graded_score_to_json(scale: &Scale, graded: GradedScore) -> serde_json::Value {
+    let mut json = score_to_json(graded.score);
+    json["grade"] = grade_to_json(scale, graded.grade);
+    json
+}
+
+fn gradebook_to_json(gradebook: Gradebook) -> serde_json::Value {
+    let scale = gradebook.scale;
+
+    json!({
+        "studyGroupId": gradebook.study_group.id.value,
+        "studyGroupName": gradebook.study_group.name,
+        "semester": gradebook.semester,
+        "attestations": gradebook
+            .attestations
+            .into_iter()
+            .map(|v| json!({
+                "id": v.id.value,
+                "curriculumModuleId": v.curriculum_module_id.value,
+            }))
+            .collect::<Vec<_>>(),
+        "students": gradebook
+            .rows
+            .into_iter()
+            .map(|v| json!({
+                "id": v.student.id.value,
+                "personId": v.person.id.value,
+                "fullName": v.person.full_name,
+                "scores": v
+                    .scores
+                    .into_iter()
+                    .map(|v| graded_score_to_json(&scale, v))
+                    .collect::<Vec<_>>(),
+            }))
+            .collect::<Vec<_>>(),
+    })
+}
+
+/// A column of grades for every attestation, titled by the discipline and the attestation kind
+async fn gradebook_table(
+    ReqScopeModule(module): ReqScopeModule,
+    gradebook: Gradebook,
+) -> Outcome<Table, Exception> {
+    let curriculum_module_repo = module.adapters.resolve::<curriculum_module::BoxedRepo>();
+    let discipline_repo = module.adapters.resolve::<discipline::BoxedRepo>();
+
+    let mut attestation_columns = vec![];
+    for attestation in &gradebook.attestations {
+        let discipline = match curriculum_module_repo
+            .find(attestation.curriculum_module_id)
+            .await?
+        {
+            Some(module) => discipline_repo.find(module.discipline_id).await?,
+            None => None,
+        };
+
+        let name = discipline.map_or_else(|| attestation.id.value.to_string(), |v| v.name);
+        attestation_columns.push(format!("{name} ({})", attestation.kind.label()));
+    }
+
+    let mut table = Table::new(
+        "gradebook",
+        format!(
+            "Ведомость группы {}, {} семестр",
+            gradebook.study_group.name, gradebook.semester
+        ),
+        [columns::NUMBER.to_owned(), columns::FULL_NAME.to_owned()]
+            .into_iter()
+            .chain(attestation_columns),
+    );
+
+    let scale = gradebook.scale;
+    let mut rows = gradebook.rows;
+    rows.sort_by(|a, b| a.person.full_name.cmp(&b.person.full_name));
+    for (number, row) in (1..).zip(rows) {
+        let mut grades = row
+            .scores
+            .into_iter()
+            .map(|v| (v.score.attestation_id, v.grade))
+            .collect::<HashMap<_, _>>();
+
+        let mut cells = vec![Cell::from(number), Cell::from(row.person.full_name)];
+        cells.extend(gradebook.attestations.iter().map(|attestation| {
+            grades
+                .remove(&attestation.id)
+                .map_or(Cell::Empty, |grade| grade_to_cell(&scale, grade))
+        }));
+        table.push(cells);
+    }
+
+    Outcome::Ok(table)
+}
+
+#[debug_handler]
+async fn get_gradebook(
+    module: ReqScopeModule,
+    Auth(claims, _): Auth<Authenticated>,
+    Export(export): Export,
+    Path(id): Path<i32>,
+    Query(query): Query<GradebookQuery>,
+) -> ApiResult {
+    let gradebook = module
+        .0
+        .resolve::<GradebookService>()
+        .get(&Actor::from(&claims), Id::new(id), query.semester)
+        .await
+        .map_ex(Exception)?;
+
+    if let Some(exporter) = export {
+        let table = gradebook_table(module, gradebook).await?;
+        return ApiResult::new(exporter.render(table));
+    }
+
+    ApiResult::new((
+        StatusCode::OK,
+        Reply {
+            message: "gradebook found successfully",
+            data: gradebook_to_json(gradebook),
+        },
+    ))
+}
+
+#[debug_handler]
+async fn submit(
+    ReqScopeModule(module): ReqScopeModule,
+    Auth(claims, _): Auth<Authenticated>,
+    Path((attestation_id, student_id)): Path<(i32, i32)>,
+    Json(payload): Json<ScorePayload>,
+) -> ApiResult {
+    let score = module
+        .resolve::<GradebookService>()
+        .submit(
+            &Actor::from(&claims),
+            Id::new(attestation_id),
+            Id::new(student_id),
+            payload.score,
+        )
+        .await
+        .map_ex(Exception)?;
+
+    ApiResult::new((
+        StatusCode::OK,
+        Reply {
+            message: "score submitted successfully",
+            data: score_to_json(score),
+        },
+    ))
+}
+
+#[debug_handler]
+async fn amend(
+    ReqScopeModule(module): ReqScopeModule,
+    Auth(claims, _): Auth<Authenticated>,
+    Path((attestation_id, student_id)): Path<(i32, i32)>,
+    Json(payload): Json<ScorePayload>,
+) -> ApiResult {
+    let score = module
+        .resolve::<GradebookService>()
+        .amend(
+            &Actor::from(&claims),
+            Id::new(attestation_id),
+            Id::new(student_id),
+            payload.score,
+        )
+        .await
+        .map_ex(Exception)?;
+
+    ApiResult::new((
+        StatusCode::OK,
+        Reply {
+            message: "score amended successfully",
+            data: score_to_json(score),
+        },
+    ))
+}
+
+#[debug_handler]
+async fn finalize(
+    ReqScopeModule(module): ReqScopeModule,
+    Auth(claims, _): Auth<Authenticated>,
+    Path((attestation_id, student_id)): Path<(i32, i32)>,
+) -> ApiResult {
+    let score = module
+        .resolve::<GradebookService>()
+        .finalize(
+            &Actor::from(&claims),
+            Id::new(attestation_id),
+            Id::new(student_id),
+        )
+        .await
+        .map_ex(Exception)?;
+
+    ApiResult::new((
+        StatusCode::OK,
+        Reply {
+            message: "score finalized successfully",
+            data: score_to_json(score),
+        },
+    ))
+}
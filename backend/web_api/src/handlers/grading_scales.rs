@@ -0,0 +1,205 @@
+use app::{
+    access_policy::Actor,
+    grading_scale::{Grade, Notation, Scale, Thresholds},
+    grading_scale_service::{GradingScaleException, GradingScaleService},
+};
+use axum::{debug_handler, extract::Path, response::IntoResponse, routing::get, Json, Router};
+use http::StatusCode;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use utils::{di::Module, entity::Id};
+
+use crate::utils::{
+    export::{Cell, Label},
+    extractors::{AccessRejection, Auth, ReqScopeModule},
+    ApiResult, Authenticated, CommonState, Reply,
+};
+
+pub fn router<S: CommonState>() -> Router<S> {
+    Router::new().route("/:university_id", get(get_info).put(update).delete(remove))
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum NotationPayload {
+    #[default]
+    Numeric,
+    Letter,
+}
+
+impl From<NotationPayload> for Notation {
+    fn from(value: NotationPayload) -> Self {
+        match value {
+            NotationPayload::Numeric => Notation::Numeric,
+            NotationPayload::Letter => Notation::Letter,
+        }
+    }
+}
+
+impl From<Notation> for NotationPayload {
+    fn from(value: Notation) -> Self {
+        match value {
+            Notation::Numeric => NotationPayload::Numeric,
+            Notation::Letter => NotationPayload::Letter,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize)]
+#[serde(rename_all = "snake_case")]
+enum GradePayload {
+    Pass,
+    Fail,
+    Excellent,
+    Good,
+    Satisfactory,
+    Unsatisfactory,
+}
+
+impl From<Grade> for GradePayload {
+    fn from(value: Grade) -> Self {
+        match value {
+            Grade::Pass => GradePayload::Pass,
+            Grade::Fail => GradePayload::Fail,
+            Grade::Excellent => GradePayload::Excellent,
+            Grade::Good => GradePayload::Good,
+            Grade::Satisfactory => GradePayload::Satisfactory,
+            Grade::Unsatisfactory => GradePayload::Unsatisfactory,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct ScalePayload {
+    pass_score: i32,
+    satisfactory_score: i32,
+    good_score: i32,
+    excellent_score: i32,
+    #[serde(default)]
+    notation: NotationPayload,
+}
+
+impl From<ScalePayload> for Scale {
+    fn from(value: ScalePayload) -> Self {
+        Self {
+            thresholds: Thresholds {
+                pass: value.pass_score,
+                satisfactory: value.satisfactory_score,
+                good: value.good_score,
+                excellent: value.excellent_score,
+            },
+            notation: value.notation.into(),
+        }
+    }
+}
+
+struct Exception(GradingScaleException);
+
+impl IntoResponse for Exception {
+    fn into_response(self) -> axum::response::Response {
+        let Self(ex) = self;
+        let code = match ex {
+            GradingScaleException::AccessException(ex) => {
+                return AccessRejection(ex).into_response()
+            }
+            GradingScaleException::UniversityNotFound => StatusCode::NOT_FOUND,
+            GradingScaleException::InvalidThresholds => StatusCode::BAD_REQUEST,
+        };
+
+        (code, Reply::from(ex)).into_response()
+    }
+}
+
+fn scale_to_json(scale: Scale) -> serde_json::Value {
+    json!({
+        "passScore": scale.thresholds.pass,
+        "satisfactoryScore": scale.thresholds.satisfactory,
+        "goodScore": scale.thresholds.good,
+        "excellentScore": scale.thresholds.excellent,
+        "notation": NotationPayload::from(scale.notation),
+    })
+}
+
+/// Grade of a score together with the mark in the notation of the scale
+pub(super) fn grade_to_json(scale: &Scale, grade: Grade) -> serde_json::Value {
+    json!({
+        "grade": GradePayload::from(grade),
+        "mark": scale.mark(grade),
+    })
+}
+
+/// Exported grades are marks of the scale, pass and fail are spelled out
+pub(super) fn grade_to_cell(scale: &Scale, grade: Grade) -> Cell {
+    match grade {
+        Grade::Pass | Grade::Fail => Cell::from(grade.label()),
+        grade => Cell::from(scale.mark(grade)),
+    }
+}
+
+#[debug_handler]
+async fn get_info(
+    ReqScopeModule(module): ReqScopeModule,
+    _: Auth<Authenticated>,
+    Path(university_id): Path<i32>,
+) -> ApiResult {
+    let scale = module
+        .resolve::<GradingScaleService>()
+        .get(Id::new(university_id))
+        .await
+        .map_ex(Exception)?;
+
+    ApiResult::new((
+        StatusCode::OK,
+        Reply {
+            message: "grading scale found successfully",
+            data: scale_to_json(scale),
+        },
+    ))
+}
+
+#[debug_handler]
+async fn update(
+    ReqScopeModule(module): ReqScopeModule,
+    Auth(claims, _): Auth<Authenticated>,
+    Path(university_id): Path<i32>,
+    Json(payload): Json<ScalePayload>,
+) -> ApiResult {
+    let scale = module
+        .resolve::<GradingScaleService>()
+        .set(
+            &Actor::from(&claims),
+            Id::new(university_id),
+            payload.into(),
+        )
+        .await
+        .map_ex(Exception)?;
+
+    ApiResult::new((
+        StatusCode::OK,
+        Reply {
+            message: "grading scale updated successfully",
+            data: scale_to_json(scale),
+        },
+    ))
+}
+
+#[debug_handler]
+async fn remove(
+    ReqScopeModule(module): ReqScopeModule,
+    Auth(claims, _): Auth<Authenticated>,
+    Path(university_id): Path<i32>,
+) -> ApiResult {
+    let scale = module
+        .resolve::<GradingScaleService>()
+        .reset(&Actor::from(&claims), Id::new(university_id))
+        .await
+        .map_ex(Exception)?;
+
+    ApiResult::new((
+        StatusCode::OK,
+        Reply {
+            message: "grading scale reset to default successfully",
+            data: scale_to_json(scale),
+        },
+    ))
+}
@@ -0,0 +1,184 @@
+use app::{
+    access_policy::Actor,
+    grant::{self, Scope},
+    grant_service::{GrantException, GrantService},
+};
+use axum::{
+    extract::Path,
+    response::IntoResponse,
+    routing::{delete, get, post},
+    Json, Router,
+};
+use http::StatusCode;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use utils::{di::Module, entity::Id};
+
+use crate::utils::{
+    extractors::{
+        AccessRejection, Auth, ReqScopeModule, Scoped, StudyGroupScope, SubdivisionScope,
+        UniversityScope,
+    },
+    ApiResult, Authenticated, CommonState, Reply,
+};
+
+use super::user::RolePayload;
+
+pub fn router<S: CommonState>() -> Router<S> {
+    Router::new()
+        .route("/", post(create))
+        .route("/:id", delete(remove))
+        .route("/universities/:id", get(list_by_university))
+        .route("/subdivisions/:id", get(list_by_subdivision))
+        .route("/study_groups/:id", get(list_by_study_group))
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(tag = "kind", content = "id", rename_all = "snake_case")]
+enum ScopePayload {
+    Global,
+    University(i32),
+    Subdivision(i32),
+    StudyGroup(i32),
+}
+
+impl From<ScopePayload> for Scope {
+    fn from(value: ScopePayload) -> Self {
+        match value {
+            ScopePayload::Global => Scope::Global,
+            ScopePayload::University(id) => Scope::University(Id::new(id)),
+            ScopePayload::Subdivision(id) => Scope::Subdivision(Id::new(id)),
+            ScopePayload::StudyGroup(id) => Scope::StudyGroup(Id::new(id)),
+        }
+    }
+}
+
+impl From<Scope> for ScopePayload {
+    fn from(value: Scope) -> Self {
+        match value {
+            Scope::Global => ScopePayload::Global,
+            Scope::University(id) => ScopePayload::University(id.value),
+            Scope::Subdivision(id) => ScopePayload::Subdivision(id.value),
+            Scope::StudyGroup(id) => ScopePayload::StudyGroup(id.value),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct CreatePayload {
+    user_id: i32,
+    role: RolePayload,
+    scope: ScopePayload,
+}
+
+struct Exception(GrantException);
+
+impl IntoResponse for Exception {
+    fn into_response(self) -> axum::response::Response {
+        let Self(ex) = self;
+        let code = match ex {
+            GrantException::AccessException(ex) => return AccessRejection(ex).into_response(),
+            GrantException::UserNotFound => StatusCode::BAD_REQUEST,
+            GrantException::GrantNotFound => StatusCode::NOT_FOUND,
+            GrantException::AlreadyExist => StatusCode::CONFLICT,
+        };
+
+        (code, Reply::from(ex)).into_response()
+    }
+}
+
+fn grant_to_json(grant: grant::Entity) -> serde_json::Value {
+    json!({
+        "id": grant.id.value,
+        "userId": grant.user_id.value,
+        "role": RolePayload::from(grant.role),
+        "scope": ScopePayload::from(grant.scope),
+    })
+}
+
+#[axum::debug_handler]
+async fn create(
+    ReqScopeModule(module): ReqScopeModule,
+    Auth(claims, _): Auth<Authenticated>,
+    Json(payload): Json<CreatePayload>,
+) -> ApiResult {
+    let grant = module
+        .resolve::<GrantService>()
+        .grant(
+            &Actor::from(&claims),
+            Id::new(payload.user_id),
+            payload.role.into(),
+            payload.scope.into(),
+        )
+        .await
+        .map_ex(Exception)?;
+
+    ApiResult::new((
+        StatusCode::OK,
+        Reply {
+            message: "role granted successfully",
+            data: grant_to_json(grant),
+        },
+    ))
+}
+
+#[axum::debug_handler]
+async fn remove(
+    ReqScopeModule(module): ReqScopeModule,
+    Auth(claims, _): Auth<Authenticated>,
+    Path(id): Path<i32>,
+) -> ApiResult {
+    let grant = module
+        .resolve::<GrantService>()
+        .revoke(&Actor::from(&claims), Id::new(id))
+        .await
+        .map_ex(Exception)?;
+
+    ApiResult::new((
+        StatusCode::OK,
+        Reply {
+            message: "role revoked successfully",
+            data: grant_to_json(grant),
+        },
+    ))
+}
+
+async fn list(ReqScopeModule(module): ReqScopeModule, scope: Scope) -> ApiResult {
+    let grants = module
+        .resolve::<GrantService>()
+        .list_by_scope(scope)
+        .await
+        .map_ex(Exception)?;
+
+    ApiResult::new((
+        StatusCode::OK,
+        Reply {
+            message: "grants listed successfully",
+            data: grants.into_iter().map(grant_to_json).collect::<Vec<_>>(),
+        },
+    ))
+}
+
+#[axum::debug_handler]
+async fn list_by_university(
+    module: ReqScopeModule,
+    Scoped(_, scope, _): Scoped<UniversityScope>,
+) -> ApiResult {
+    list(module, scope).await
+}
+
+#[axum::debug_handler]
+async fn list_by_subdivision(
+    module: ReqScopeModule,
+    Scoped(_, scope, _): Scoped<SubdivisionScope>,
+) -> ApiResult {
+    list(module, scope).await
+}
+
+#[axum::debug_handler]
+async fn list_by_study_group(
+    module: ReqScopeModule,
+    Scoped(_, scope, _): Scoped<StudyGroupScope>,
+) -> ApiResult {
+    list(module, scope).await
+}
@@ -1,26 +1,66 @@
+mod academic_years;
+mod account;
+mod attestation_sessions;
+mod attestations;
 mod auth;
+mod calendar;
+mod class_kinds;
+mod classes;
+mod curriculum_modules;
 mod curriculums;
+mod disciplines;
+mod gradebook;
+mod grading_scales;
+mod grants;
 mod persons;
+mod rooms;
+mod students;
 mod study_groups;
 mod subdivisions;
+mod tags;
+mod teachers;
+mod teaching_load;
+mod timetable;
+mod timetable_drafts;
 mod universities;
 mod user;
 
+pub(crate) use students::import_report_to_json;
+
 use crate::{api_state::ApiState, utils::provide_req_scope_module};
-use axum::{middleware, Router};
+use axum::{middleware, Extension, Router};
 
 pub fn router(state: ApiState) -> Router {
     Router::new()
-        .nest("/auth", auth::router())
+        .nest("/auth", auth::router().merge(account::router()))
         .nest("/user", user::router())
+        .nest("/grants", grants::router())
         .nest("/universities", universities::router())
         .nest("/curriculums", curriculums::router())
         .nest("/persons", persons::router())
         .nest("/study_groups", study_groups::router())
         .nest("/subdivisions", subdivisions::router())
+        .nest("/disciplines", disciplines::router())
+        .nest("/curriculum_modules", curriculum_modules::router())
+        .nest("/attestations", attestations::router())
+        .nest("/class_kinds", class_kinds::router())
+        .nest("/classes", classes::router())
+        .nest("/tags", tags::router())
+        .nest("/teachers", teachers::router())
+        .nest("/students", students::router())
+        .nest("/gradebook", gradebook::router())
+        .nest("/grading_scales", grading_scales::router())
+        .nest("/rooms", rooms::router())
+        .nest("/timetable", timetable::router())
+        .nest("/timetable_drafts", timetable_drafts::router())
+        .nest("/attestation_sessions", attestation_sessions::router())
+        .nest("/calendar", calendar::router())
+        .nest("/teaching_load", teaching_load::router())
+        .nest("/academic_years", academic_years::router())
         .layer(middleware::from_fn_with_state(
             state.clone(),
             provide_req_scope_module,
         ))
+        .layer(Extension(state.trusted_proxies()))
         .with_state(state)
 }
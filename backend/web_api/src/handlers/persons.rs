@@ -1,49 +1,243 @@
-use std::collections::{HashMap, HashSet};
+use std::collections::HashMap;
 
 use anyhow::Context;
 use app::{
-    curriculum, curriculum_module, discipline, person, student, study_group, subdivision, teacher,
+    access_policy::Actor,
+    curriculum, curriculum_module, discipline,
+    paging::{Page, Paginated, Sort},
+    passport::{self, Gender},
+    person,
+    person_service::{PassportInfo, PersonException, PersonService},
+    student, study_group, subdivision, teacher,
+};
+use axum::{
+    debug_handler,
+    extract::{Path, Query},
+    response::IntoResponse,
+    routing::{get, post, put},
+    Json, Router,
 };
-use axum::{debug_handler, extract::Path, response::IntoResponse, routing::post, Json, Router};
 use http::StatusCode;
+use serde::{Deserialize, Serialize};
 use serde_json::json;
 use utils::{di::Module, entity::Id};
 
-use crate::utils::{extractors::ReqScopeModule, ApiResult, CommonState};
+use crate::utils::{
+    export::{columns, Cell, Table},
+    extractors::{AccessRejection, Auth, Export, ReqScopeModule},
+    Admin, ApiResult, Authenticated, CommonState, DepartmentHead, PageReply, Reply,
+    SortOrderPayload, Teacher, UniversityStaff,
+};
+
+type Staff = (Admin, UniversityStaff, DepartmentHead, Teacher);
 
 pub fn router<S: CommonState>() -> Router<S> {
     Router::new()
-        .route("/", axum::routing::get(get_infos))
-        .route("/:id", axum::routing::get(get_info))
+        .route("/", get(get_infos).post(create))
+        .route("/:id", get(get_info).put(update).delete(remove))
+        .route("/:id/passports", post(add_passport))
+        .route(
+            "/passports/:id",
+            put(update_passport).delete(remove_passport),
+        )
+}
+
+#[derive(Debug, Clone, Copy, Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum SortKeyPayload {
+    #[default]
+    Id,
+    FullName,
+}
+
+impl From<SortKeyPayload> for person::SortKey {
+    fn from(value: SortKeyPayload) -> Self {
+        match value {
+            SortKeyPayload::Id => person::SortKey::Id,
+            SortKeyPayload::FullName => person::SortKey::FullName,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct ListQuery {
+    limit: Option<u64>,
+    offset: Option<u64>,
+    #[serde(default)]
+    sort: SortKeyPayload,
+    #[serde(default)]
+    order: SortOrderPayload,
+    name_prefix: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CreatePayload {
+    user_id: i32,
+    full_name: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct UpdatePayload {
+    full_name: String,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum GenderPayload {
+    Male,
+    Female,
+}
+
+impl From<GenderPayload> for Gender {
+    fn from(value: GenderPayload) -> Self {
+        match value {
+            GenderPayload::Male => Gender::Male,
+            GenderPayload::Female => Gender::Female,
+        }
+    }
+}
+
+impl From<Gender> for GenderPayload {
+    fn from(value: Gender) -> Self {
+        match value {
+            Gender::Male => GenderPayload::Male,
+            Gender::Female => GenderPayload::Female,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct PassportPayload {
+    first_name: String,
+    last_name: String,
+    patronymic: String,
+    date_of_birth: time::Date,
+    date_of_issue: time::Date,
+    number: String,
+    series: String,
+    gender: GenderPayload,
+}
+
+impl PassportPayload {
+    fn into_info(self) -> Result<PassportInfo, Exception> {
+        let (Ok(number), Ok(series)) = (self.number.parse(), self.series.parse()) else {
+            return Err(Exception(PersonException::InvalidPassport));
+        };
+
+        Ok(PassportInfo {
+            first_name: self.first_name,
+            last_name: self.last_name,
+            patronymic: self.patronymic,
+            date_of_birth: self.date_of_birth,
+            date_of_issue: self.date_of_issue,
+            number,
+            series,
+            gender: self.gender.into(),
+        })
+    }
+}
+
+struct Exception(PersonException);
+
+impl IntoResponse for Exception {
+    fn into_response(self) -> axum::response::Response {
+        let Self(ex) = self;
+        let code = match ex {
+            PersonException::AccessException(ex) => return AccessRejection(ex).into_response(),
+            PersonException::NotFound | PersonException::PassportNotFound => StatusCode::NOT_FOUND,
+            PersonException::UserNotFound
+            | PersonException::InvalidFullName
+            | PersonException::InvalidPassport => StatusCode::BAD_REQUEST,
+            PersonException::AlreadyExist
+            | PersonException::InUse
+            | PersonException::PassportAlreadyExist => StatusCode::CONFLICT,
+        };
+
+        (code, Reply::from(ex)).into_response()
+    }
+}
+
+fn person_to_json(person: person::Entity) -> serde_json::Value {
+    json!({
+        "id": person.id.value,
+        "userId": person.user_id.value,
+        "fullName": person.full_name,
+    })
+}
+
+fn passport_to_json(passport: passport::Entity) -> serde_json::Value {
+    json!({
+        "id": passport.id.value,
+        "personId": passport.person_id.value,
+        "firstName": passport.first_name,
+        "lastName": passport.last_name,
+        "patronymic": passport.patronymic,
+        "dateOfBirth": passport.date_of_birth,
+        "dateOfIssue": passport.date_of_issue,
+        "number": passport.number.to_string(),
+        "series": passport.series.to_string(),
+        "gender": GenderPayload::from(passport.gender),
+    })
 }
 
 #[debug_handler]
-async fn get_infos(ReqScopeModule(module): ReqScopeModule) -> ApiResult {
-    let repo = module.adapters.resolve::<person::BoxedRepo>();
+async fn get_infos(
+    ReqScopeModule(module): ReqScopeModule,
+    Auth(claims, _): Auth<Staff>,
+    Export(export): Export,
+    Query(query): Query<ListQuery>,
+) -> ApiResult {
+    let filter = person::ListFilter {
+        name_prefix: query.name_prefix,
+        within: None,
+    };
+    let sort = Sort {
+        key: query.sort.into(),
+        order: query.order.into(),
+    };
+    let page = Page::new(query.limit, query.offset);
+
+    let Paginated { items, total } = module
+        .resolve::<PersonService>()
+        .list(&Actor::from(&claims), filter, sort, page)
+        .await
+        .map_ex(Exception)?;
+
+    if let Some(exporter) = export {
+        let mut table = Table::new("persons", "Персоны", [columns::FULL_NAME]);
+        for person in items {
+            table.push(vec![Cell::from(person.full_name)]);
+        }
+
+        return ApiResult::new(exporter.render(table));
+    }
 
-    let entities = match repo.list().await {
-        Ok(entities) => entities
+    ApiResult::new(PageReply(Paginated {
+        items: items
             .into_iter()
             .map(|e| json!({ "id": e.id.value.to_string(), "name": e.full_name.to_string() }))
             .collect::<Vec<_>>(),
-        Err(err) => {
-            dbg!(err);
-            let msg = Json(json!({
-                "message": "persons not found",
-            }));
-            return ApiResult::new((StatusCode::BAD_REQUEST, msg).into_response());
-        }
-    };
-
-    ApiResult::new((StatusCode::OK, Json(entities)))
+        total,
+    }))
 }
 
 #[debug_handler]
-async fn get_info(module: ReqScopeModule, Path(id): Path<i32>) -> ApiResult {
-    let val = match load_info(module, id).await {
+async fn get_info(
+    module: ReqScopeModule,
+    Auth(claims, _): Auth<Staff>,
+    Path(id): Path<i32>,
+) -> ApiResult {
+    let ReqScopeModule(app) = &module;
+    let person = app
+        .resolve::<PersonService>()
+        .find(&Actor::from(&claims), Id::new(id))
+        .await
+        .map_ex(Exception)?;
+
+    let val = match load_info(module, person).await {
         Ok(val) => Json(val),
         Err(err) => {
-            dbg!(err);
+            tracing::error!(?err, person_id = id, "failed to load person");
             let msg = Json(json!({
                 "message": "person not found",
             }));
@@ -56,24 +250,25 @@ async fn get_info(module: ReqScopeModule, Path(id): Path<i32>) -> ApiResult {
 
 async fn load_info(
     ReqScopeModule(module): ReqScopeModule,
-    id: i32,
+    person: person::Entity,
 ) -> Result<serde_json::Value, anyhow::Error> {
-    let repo = module.adapters.resolve::<person::BoxedRepo>();
     let student_repo = module.adapters.resolve::<student::BoxedRepo>();
     let teacher_reop = module.adapters.resolve::<teacher::BoxedRepo>();
     let study_group_repo = module.adapters.resolve::<study_group::BoxedRepo>();
     let subdivision_repo = module.adapters.resolve::<subdivision::BoxedRepo>();
 
-    let person = repo.find(Id::new(id)).await?.context("")?;
-
     let mut roles = Vec::new();
 
     let students = student_repo.list_by_person(person.id).await?;
+    let study_groups = study_group_repo
+        .find_many(students.iter().map(|v| v.study_group_id).collect())
+        .await?
+        .into_iter()
+        .map(|v| (v.id, v))
+        .collect::<HashMap<_, _>>();
+
     for student in students {
-        let study_group = study_group_repo
-            .find(student.study_group_id)
-            .await?
-            .context("")?;
+        let study_group = study_groups.get(&student.study_group_id).context("")?;
 
         roles.push(json!({
             "role": "student",
@@ -82,7 +277,7 @@ async fn load_info(
         }));
     }
 
-    if let Some(teacher) = dbg!(teacher_reop.find_by_person_id(person.id).await)? {
+    if let Some(teacher) = teacher_reop.find_by_person_id(person.id).await? {
         let department = subdivision_repo
             .find(teacher.department_id)
             .await?
@@ -115,3 +310,136 @@ async fn load_info(
         "roles": roles,
     }))
 }
+
+#[debug_handler]
+async fn create(
+    ReqScopeModule(module): ReqScopeModule,
+    Auth(claims, _): Auth<Authenticated>,
+    Json(payload): Json<CreatePayload>,
+) -> ApiResult {
+    let entity = module
+        .resolve::<PersonService>()
+        .create(
+            &Actor::from(&claims),
+            Id::new(payload.user_id),
+            payload.full_name,
+        )
+        .await
+        .map_ex(Exception)?;
+
+    ApiResult::new((
+        StatusCode::OK,
+        Reply {
+            message: "person created successfully",
+            data: person_to_json(entity),
+        },
+    ))
+}
+
+#[debug_handler]
+async fn remove(
+    ReqScopeModule(module): ReqScopeModule,
+    Auth(claims, _): Auth<Authenticated>,
+    Path(id): Path<i32>,
+) -> ApiResult {
+    let entity = module
+        .resolve::<PersonService>()
+        .delete(&Actor::from(&claims), Id::new(id))
+        .await
+        .map_ex(Exception)?;
+
+    ApiResult::new((
+        StatusCode::OK,
+        Reply {
+            message: "person deleted successfully",
+            data: person_to_json(entity),
+        },
+    ))
+}
+
+#[debug_handler]
+async fn update(
+    ReqScopeModule(module): ReqScopeModule,
+    Auth(claims, _): Auth<Authenticated>,
+    Path(id): Path<i32>,
+    Json(payload): Json<UpdatePayload>,
+) -> ApiResult {
+    let entity = module
+        .resolve::<PersonService>()
+        .update(&Actor::from(&claims), Id::new(id), payload.full_name)
+        .await
+        .map_ex(Exception)?;
+
+    ApiResult::new((
+        StatusCode::OK,
+        Reply {
+            message: "person updated successfully",
+            data: person_to_json(entity),
+        },
+    ))
+}
+
+#[debug_handler]
+async fn add_passport(
+    ReqScopeModule(module): ReqScopeModule,
+    Auth(claims, _): Auth<Authenticated>,
+    Path(id): Path<i32>,
+    Json(payload): Json<PassportPayload>,
+) -> ApiResult {
+    let passport = module
+        .resolve::<PersonService>()
+        .add_passport(&Actor::from(&claims), Id::new(id), payload.into_info()?)
+        .await
+        .map_ex(Exception)?;
+
+    ApiResult::new((
+        StatusCode::OK,
+        Reply {
+            message: "passport added successfully",
+            data: passport_to_json(passport),
+        },
+    ))
+}
+
+#[debug_handler]
+async fn update_passport(
+    ReqScopeModule(module): ReqScopeModule,
+    Auth(claims, _): Auth<Authenticated>,
+    Path(id): Path<i32>,
+    Json(payload): Json<PassportPayload>,
+) -> ApiResult {
+    let passport = module
+        .resolve::<PersonService>()
+        .update_passport(&Actor::from(&claims), Id::new(id), payload.into_info()?)
+        .await
+        .map_ex(Exception)?;
+
+    ApiResult::new((
+        StatusCode::OK,
+        Reply {
+            message: "passport updated successfully",
+            data: passport_to_json(passport),
+        },
+    ))
+}
+
+#[debug_handler]
+async fn remove_passport(
+    ReqScopeModule(module): ReqScopeModule,
+    Auth(claims, _): Auth<Authenticated>,
+    Path(id): Path<i32>,
+) -> ApiResult {
+    let passport = module
+        .resolve::<PersonService>()
+        .remove_passport(&Actor::from(&claims), Id::new(id))
+        .await
+        .map_ex(Exception)?;
+
+    ApiResult::new((
+        StatusCode::OK,
+        Reply {
+            message: "passport removed successfully",
+            data: passport_to_json(passport),
+        },
+    ))
+}
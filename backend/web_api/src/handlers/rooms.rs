@@ -0,0 +1,178 @@
+use app::{
+    access_policy::Actor,
+    room,
+    room_service::{RoomException, RoomInfo, RoomService},
+};
+use axum::{
+    debug_handler,
+    extract::Path,
+    response::IntoResponse,
+    routing::{get, post},
+    Json, Router,
+};
+use http::StatusCode;
+use serde::Deserialize;
+use serde_json::json;
+use utils::{di::Module, entity::Id};
+
+use crate::utils::{
+    extractors::{AccessRejection, Auth, ReqScopeModule},
+    ApiResult, Authenticated, CommonState, Reply,
+};
+
+pub fn router<S: CommonState>() -> Router<S> {
+    Router::new()
+        .route("/", post(create))
+        .route("/:id", get(get_info).put(update).delete(remove))
+        .route("/universities/:id", get(list_by_university))
+}
+
+#[derive(Debug, Deserialize)]
+struct RoomPayload {
+    university_id: i32,
+    name: String,
+    capacity: i32,
+}
+
+impl From<RoomPayload> for RoomInfo {
+    fn from(value: RoomPayload) -> Self {
+        Self {
+            university_id: Id::new(value.university_id),
+            name: value.name,
+            capacity: value.capacity,
+        }
+    }
+}
+
+struct Exception(RoomException);
+
+impl IntoResponse for Exception {
+    fn into_response(self) -> axum::response::Response {
+        let Self(ex) = self;
+        let code = match ex {
+            RoomException::AccessException(ex) => return AccessRejection(ex).into_response(),
+            RoomException::NotFound => StatusCode::NOT_FOUND,
+            RoomException::UniversityNotFound
+            | RoomException::InvalidName
+            | RoomException::InvalidCapacity => StatusCode::BAD_REQUEST,
+            RoomException::AlreadyExist | RoomException::InUse => StatusCode::CONFLICT,
+        };
+
+        (code, Reply::from(ex)).into_response()
+    }
+}
+
+fn room_to_json(room: room::Entity) -> serde_json::Value {
+    json!({
+        "id": room.id.value,
+        "universityId": room.university_id.value,
+        "name": room.name,
+        "capacity": room.capacity,
+    })
+}
+
+#[debug_handler]
+async fn get_info(
+    ReqScopeModule(module): ReqScopeModule,
+    _: Auth<Authenticated>,
+    Path(id): Path<i32>,
+) -> ApiResult {
+    let entity = module
+        .resolve::<RoomService>()
+        .get(Id::new(id))
+        .await
+        .map_ex(Exception)?;
+
+    ApiResult::new((
+        StatusCode::OK,
+        Reply {
+            message: "room found successfully",
+            data: room_to_json(entity),
+        },
+    ))
+}
+
+#[debug_handler]
+async fn list_by_university(
+    ReqScopeModule(module): ReqScopeModule,
+    _: Auth<Authenticated>,
+    Path(id): Path<i32>,
+) -> ApiResult {
+    let entities = module
+        .resolve::<RoomService>()
+        .list_by_university(Id::new(id))
+        .await
+        .map_ex(Exception)?;
+
+    ApiResult::new((
+        StatusCode::OK,
+        Reply {
+            message: "rooms found successfully",
+            data: entities.into_iter().map(room_to_json).collect::<Vec<_>>(),
+        },
+    ))
+}
+
+#[debug_handler]
+async fn create(
+    ReqScopeModule(module): ReqScopeModule,
+    Auth(claims, _): Auth<Authenticated>,
+    Json(payload): Json<RoomPayload>,
+) -> ApiResult {
+    let entity = module
+        .resolve::<RoomService>()
+        .create(&Actor::from(&claims), payload.into())
+        .await
+        .map_ex(Exception)?;
+
+    ApiResult::new((
+        StatusCode::OK,
+        Reply {
+            message: "room created successfully",
+            data: room_to_json(entity),
+        },
+    ))
+}
+
+#[debug_handler]
+async fn update(
+    ReqScopeModule(module): ReqScopeModule,
+    Auth(claims, _): Auth<Authenticated>,
+    Path(id): Path<i32>,
+    Json(payload): Json<RoomPayload>,
+) -> ApiResult {
+    let entity = module
+        .resolve::<RoomService>()
+        .update(&Actor::from(&claims), Id::new(id), payload.into())
+        .await
+        .map_ex(Exception)?;
+
+    ApiResult::new((
+        StatusCode::OK,
+        Reply {
+            message: "room updated successfully",
+            data: room_to_json(entity),
+        },
+    ))
+}
+
+#[debug_handler]
+async fn remove(
+    ReqScopeModule(module): ReqScopeModule,
+    Auth(claims, _): Auth<Authenticated>,
+    Path(id): Path<i32>,
+) -> ApiResult {
+    let entity = module
+        .resolve::<RoomService>()
+        .delete(&Actor::from(&claims), Id::new(id))
+        .await
+        .map_ex(Exception)?;
+
+    ApiResult::new((
+        StatusCode::OK,
+        Reply {
+            message: "room deleted successfully",
+            data: room_to_json(entity),
+        },
+    ))
+}
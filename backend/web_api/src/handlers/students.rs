@@ -0,0 +1,703 @@
+use adapters::spreadsheet::{self, SpreadsheetFormat};
+use std::collections::HashMap;
+
+use app::{
+    access_policy::Actor,
+    person,
+    student::{self, StudentStatus},
+    student_import_service::{ImportReport, StudentImportException, StudentImportService},
+    student_order::{self, OrderKind},
+    student_service::{
+        EnrollmentInfo, OrderInfo, RosterEntry, StudentException, StudentInfo, StudentService,
+    },
+    study_group,
+    transcript_service::{Transcript, TranscriptException, TranscriptService},
+};
+use axum::{
+    debug_handler,
+    extract::{Path, Query},
+    middleware,
+    response::IntoResponse,
+    routing::{get, post},
+    Json, Router,
+};
+use http::{header::CONTENT_TYPE, HeaderMap, StatusCode};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use time::{Date, OffsetDateTime};
+use utils::{di::Module, entity::Id, outcome::Outcome};
+
+use super::{
+    attestations::AttestationKindPayload,
+    grading_scales::{grade_to_cell, grade_to_json},
+};
+use crate::utils::{
+    export::{columns, Cell, Label, Table},
+    extractors::{AccessRejection, Auth, Export, ReqScopeModule},
+    rollback_on_failure, ApiResult, Authenticated, CommonState, EmptyData, Reply,
+};
+
+pub fn router<S: CommonState>() -> Router<S> {
+    Router::new()
+        .route("/", post(create))
+        .route(
+            "/import",
+            post(import).layer(middleware::map_response(rollback_on_failure)),
+        )
+        .route("/:id", get(get_info).put(update).delete(remove))
+        .route("/:id/transcript", get(get_transcript))
+        .route("/:id/orders", get(list_orders).post(issue_order))
+        .route("/study_groups/:id/roster", get(roster))
+}
+
+#[derive(Debug, Deserialize)]
+struct StudentPayload {
+    person_id: i32,
+    study_group_id: i32,
+}
+
+#[derive(Debug, Deserialize)]
+struct EnrollmentPayload {
+    #[serde(flatten)]
+    student: StudentPayload,
+    /// Today if omitted
+    date: Option<Date>,
+    reason: String,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum OrderKindPayload {
+    Enrollment,
+    Transfer,
+    AcademicLeave,
+    ReturnFromLeave,
+    Expulsion,
+    Graduation,
+}
+
+impl From<OrderKindPayload> for OrderKind {
+    fn from(value: OrderKindPayload) -> Self {
+        match value {
+            OrderKindPayload::Enrollment => OrderKind::Enrollment,
+            OrderKindPayload::Transfer => OrderKind::Transfer,
+            OrderKindPayload::AcademicLeave => OrderKind::AcademicLeave,
+            OrderKindPayload::ReturnFromLeave => OrderKind::ReturnFromLeave,
+            OrderKindPayload::Expulsion => OrderKind::Expulsion,
+            OrderKindPayload::Graduation => OrderKind::Graduation,
+        }
+    }
+}
+
+impl From<OrderKind> for OrderKindPayload {
+    fn from(value: OrderKind) -> Self {
+        match value {
+            OrderKind::Enrollment => OrderKindPayload::Enrollment,
+            OrderKind::Transfer => OrderKindPayload::Transfer,
+            OrderKind::AcademicLeave => OrderKindPayload::AcademicLeave,
+            OrderKind::ReturnFromLeave => OrderKindPayload::ReturnFromLeave,
+            OrderKind::Expulsion => OrderKindPayload::Expulsion,
+            OrderKind::Graduation => OrderKindPayload::Graduation,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize)]
+#[serde(rename_all = "snake_case")]
+enum StudentStatusPayload {
+    Active,
+    OnLeave,
+    Expelled,
+    Graduated,
+}
+
+impl From<StudentStatus> for StudentStatusPayload {
+    fn from(value: StudentStatus) -> Self {
+        match value {
+            StudentStatus::Active => StudentStatusPayload::Active,
+            StudentStatus::OnLeave => StudentStatusPayload::OnLeave,
+            StudentStatus::Expelled => StudentStatusPayload::Expelled,
+            StudentStatus::Graduated => StudentStatusPayload::Graduated,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct OrderPayload {
+    kind: OrderKindPayload,
+    study_group_id: Option<i32>,
+    /// Today if omitted
+    date: Option<Date>,
+    reason: String,
+}
+
+impl From<OrderPayload> for OrderInfo {
+    fn from(value: OrderPayload) -> Self {
+        Self {
+            kind: value.kind.into(),
+            study_group_id: value.study_group_id.map(Id::new),
+            date: value.date.unwrap_or_else(today),
+            reason: value.reason,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct ImportQuery {
+    /// File name or extension, the content type is used if omitted
+    format: Option<String>,
+    #[serde(default)]
+    dry_run: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct DateQuery {
+    /// Today if omitted
+    date: Option<Date>,
+}
+
+fn today() -> Date {
+    OffsetDateTime::now_utc().date()
+}
+
+impl From<StudentPayload> for StudentInfo {
+    fn from(value: StudentPayload) -> Self {
+        Self {
+            person_id: Id::new(value.person_id),
+            study_group_id: Id::new(value.study_group_id),
+        }
+    }
+}
+
+struct Exception(StudentException);
+
+impl IntoResponse for Exception {
+    fn into_response(self) -> axum::response::Response {
+        let Self(ex) = self;
+        let code = match ex {
+            StudentException::AccessException(ex) => return AccessRejection(ex).into_response(),
+            StudentException::NotFound => StatusCode::NOT_FOUND,
+            StudentException::PersonNotFound
+            | StudentException::StudyGroupNotFound
+            | StudentException::InvalidStudyGroup
+            | StudentException::InvalidDate
+            | StudentException::InvalidReason => StatusCode::BAD_REQUEST,
+            StudentException::AlreadyExist
+            | StudentException::TransferRequired
+            | StudentException::InvalidTransition { .. } => StatusCode::CONFLICT,
+        };
+
+        (code, Reply::from(ex)).into_response()
+    }
+}
+
+struct ListException(StudentException);
+
+impl IntoResponse for ListException {
+    fn into_response(self) -> axum::response::Response {
+        let Self(ex) = self;
+        match ex {
+            StudentException::StudyGroupNotFound => {
+                (StatusCode::NOT_FOUND, Reply::from(ex)).into_response()
+            }
+            ex => Exception(ex).into_response(),
+        }
+    }
+}
+
+struct ImportExceptionReply(StudentImportException);
+
+impl IntoResponse for ImportExceptionReply {
+    fn into_response(self) -> axum::response::Response {
+        let Self(ex) = self;
+        let code = match ex {
+            StudentImportException::AccessException(ex) => {
+                return AccessRejection(ex).into_response()
+            }
+            StudentImportException::MissingColumns(_) => StatusCode::BAD_REQUEST,
+            StudentImportException::UserException(_)
+            | StudentImportException::PersonException(_)
+            | StudentImportException::StudentException(_) => StatusCode::CONFLICT,
+        };
+
+        (code, Reply::from(ex)).into_response()
+    }
+}
+
+struct TranscriptExceptionReply(TranscriptException);
+
+impl IntoResponse for TranscriptExceptionReply {
+    fn into_response(self) -> axum::response::Response {
+        let Self(ex) = self;
+        let code = match ex {
+            TranscriptException::AccessException(ex) => return AccessRejection(ex).into_response(),
+            TranscriptException::StudentNotFound | TranscriptException::StudyGroupNotFound => {
+                StatusCode::NOT_FOUND
+            }
+        };
+
+        (code, Reply::from(ex)).into_response()
+    }
+}
+
+/// Averages are shown with two decimal places
+fn round_average(average: Option<f64>) -> Option<f64> {
+    average.map(|v| (v * 100.0).round() / 100.0)
+}
+
+fn transcript_to_json(transcript: Transcript) -> serde_json::Value {
+    let scale = transcript.scale;
+
+    json!({
+        "studentId": transcript.student.id.value,
+        "personId": transcript.person.id.value,
+        "fullName": transcript.person.full_name,
+        "studyGroupId": transcript.student.study_group_id.value,
+        "creditHours": transcript.credit_hours,
+        "average": round_average(transcript.average),
+        "semesters": transcript
+            .semesters
+            .into_iter()
+            .map(|semester| json!({
+                "semester": semester.semester,
+                "creditHours": semester.credit_hours,
+                "average": round_average(semester.average),
+                "modules": semester
+                    .entries
+                    .into_iter()
+                    .map(|v| json!({
+                        "curriculumModuleId": v.curriculum_module.id.value,
+                        "curriculumId": v.curriculum_module.curriculum_id.value,
+                        "disciplineId": v.discipline.id.value,
+                        "disciplineName": v.discipline.name,
+                        "creditHours": v.credit_hours,
+                        "attestationId": v.attestation.as_ref().map(|a| a.id.value),
+                        "attestationKind": v
+                            .attestation
+                            .map(|a| AttestationKindPayload::from(a.kind)),
+                        "score": v.score.as_ref().map(|s| s.score.score),
+                        "isFinal": v.score.as_ref().map(|s| s.score.is_final),
+                        "grade": v.score.map(|s| grade_to_json(&scale, s.grade)),
+                    }))
+                    .collect::<Vec<_>>(),
+            }))
+            .collect::<Vec<_>>(),
+    })
+}
+
+fn transcript_to_table(transcript: Transcript) -> Table {
+    let scale = transcript.scale;
+
+    let mut table = Table::new(
+        "transcript",
+        format!("Успеваемость: {}", transcript.person.full_name),
+        [
+            columns::SEMESTER,
+            columns::DISCIPLINE,
+            columns::ATTESTATION_KIND,
+            columns::HOURS,
+            columns::SCORE,
+            columns::GRADE,
+        ],
+    );
+    for semester in transcript.semesters {
+        for entry in semester.entries {
+            table.push(vec![
+                Cell::from(semester.semester),
+                Cell::from(entry.discipline.name),
+                Cell::from(entry.attestation.map(|v| v.kind.label())),
+                Cell::from(entry.credit_hours),
+                Cell::from(entry.score.as_ref().map(|v| v.score.score)),
+                entry
+                    .score
+                    .map_or(Cell::Empty, |v| grade_to_cell(&scale, v.grade)),
+            ]);
+        }
+    }
+
+    table
+}
+
+fn student_to_json(student: student::Entity) -> serde_json::Value {
+    json!({
+        "id": student.id.value,
+        "personId": student.person_id.value,
+        "studyGroupId": student.study_group_id.value,
+        "status": StudentStatusPayload::from(student.status),
+        "attestations": student
+            .attestations
+            .into_iter()
+            .map(|v| {
+                json!({
+                    "attestationId": v.attestation_id.value,
+                    "score": v.score,
+                    "isFinal": v.is_final,
+                })
+            })
+            .collect::<Vec<_>>(),
+    })
+}
+
+fn order_to_json(order: student_order::Entity) -> serde_json::Value {
+    json!({
+        "id": order.id.value,
+        "studentId": order.student_id.value,
+        "kind": OrderKindPayload::from(order.kind),
+        "studyGroupId": order.study_group_id.value,
+        "date": order.date,
+        "reason": order.reason,
+        "authorId": order.author_id.map(|v| v.value),
+    })
+}
+
+pub(crate) fn import_report_to_json(report: ImportReport) -> serde_json::Value {
+    json!({
+        "rows": report.rows,
+        "errors": report
+            .errors
+            .into_iter()
+            .map(|v| json!({
+                "row": v.row,
+                "message": v.error.to_string(),
+            }))
+            .collect::<Vec<_>>(),
+        "students": report
+            .students
+            .into_iter()
+            .map(|v| json!({
+                "row": v.row,
+                "userId": v.user.id.value,
+                "email": v.user.email,
+                "initialPassword": v.initial_password,
+                "personId": v.person.id.value,
+                "fullName": v.person.full_name,
+                "student": student_to_json(v.student),
+            }))
+            .collect::<Vec<_>>(),
+    })
+}
+
+fn roster_entry_to_json(entry: RosterEntry) -> serde_json::Value {
+    json!({
+        "studentId": entry.student.id.value,
+        "personId": entry.student.person_id.value,
+        "status": StudentStatusPayload::from(entry.status),
+        "since": entry.since,
+    })
+}
+
+#[debug_handler]
+async fn get_info(
+    ReqScopeModule(module): ReqScopeModule,
+    _: Auth<Authenticated>,
+    Path(id): Path<i32>,
+) -> ApiResult {
+    let entity = module
+        .resolve::<StudentService>()
+        .get(Id::new(id))
+        .await
+        .map_ex(Exception)?;
+
+    ApiResult::new((
+        StatusCode::OK,
+        Reply {
+            message: "student found successfully",
+            data: student_to_json(entity),
+        },
+    ))
+}
+
+#[debug_handler]
+async fn create(
+    ReqScopeModule(module): ReqScopeModule,
+    Auth(claims, _): Auth<Authenticated>,
+    Json(payload): Json<EnrollmentPayload>,
+) -> ApiResult {
+    let enrollment = EnrollmentInfo {
+        date: payload.date.unwrap_or_else(today),
+        reason: payload.reason,
+    };
+
+    let entity = module
+        .resolve::<StudentService>()
+        .create(&Actor::from(&claims), payload.student.into(), enrollment)
+        .await
+        .map_ex(Exception)?;
+
+    ApiResult::new((
+        StatusCode::OK,
+        Reply {
+            message: "student created successfully",
+            data: student_to_json(entity),
+        },
+    ))
+}
+
+#[debug_handler]
+async fn update(
+    ReqScopeModule(module): ReqScopeModule,
+    Auth(claims, _): Auth<Authenticated>,
+    Path(id): Path<i32>,
+    Json(payload): Json<StudentPayload>,
+) -> ApiResult {
+    let entity = module
+        .resolve::<StudentService>()
+        .update(&Actor::from(&claims), Id::new(id), payload.into())
+        .await
+        .map_ex(Exception)?;
+
+    ApiResult::new((
+        StatusCode::OK,
+        Reply {
+            message: "student updated successfully",
+            data: student_to_json(entity),
+        },
+    ))
+}
+
+#[debug_handler]
+async fn remove(
+    ReqScopeModule(module): ReqScopeModule,
+    Auth(claims, _): Auth<Authenticated>,
+    Path(id): Path<i32>,
+) -> ApiResult {
+    let entity = module
+        .resolve::<StudentService>()
+        .delete(&Actor::from(&claims), Id::new(id))
+        .await
+        .map_ex(Exception)?;
+
+    ApiResult::new((
+        StatusCode::OK,
+        Reply {
+            message: "student deleted successfully",
+            data: student_to_json(entity),
+        },
+    ))
+}
+
+#[debug_handler]
+async fn get_transcript(
+    ReqScopeModule(module): ReqScopeModule,
+    Auth(claims, _): Auth<Authenticated>,
+    Export(export): Export,
+    Path(id): Path<i32>,
+) -> ApiResult {
+    let transcript = module
+        .resolve::<TranscriptService>()
+        .get(&Actor::from(&claims), Id::new(id))
+        .await
+        .map_ex(TranscriptExceptionReply)?;
+
+    if let Some(exporter) = export {
+        return ApiResult::new(exporter.render(transcript_to_table(transcript)));
+    }
+
+    ApiResult::new((
+        StatusCode::OK,
+        Reply {
+            message: "transcript built successfully",
+            data: transcript_to_json(transcript),
+        },
+    ))
+}
+
+#[debug_handler]
+async fn list_orders(
+    ReqScopeModule(module): ReqScopeModule,
+    _: Auth<Authenticated>,
+    Path(id): Path<i32>,
+) -> ApiResult {
+    let orders = module
+        .resolve::<StudentService>()
+        .orders(Id::new(id))
+        .await
+        .map_ex(Exception)?;
+
+    ApiResult::new((
+        StatusCode::OK,
+        Reply {
+            message: "student orders found successfully",
+            data: orders.into_iter().map(order_to_json).collect::<Vec<_>>(),
+        },
+    ))
+}
+
+#[debug_handler]
+async fn issue_order(
+    ReqScopeModule(module): ReqScopeModule,
+    Auth(claims, _): Auth<Authenticated>,
+    Path(id): Path<i32>,
+    Json(payload): Json<OrderPayload>,
+) -> ApiResult {
+    let (student, order) = module
+        .resolve::<StudentService>()
+        .issue_order(&Actor::from(&claims), Id::new(id), payload.into())
+        .await
+        .map_ex(Exception)?;
+
+    ApiResult::new((
+        StatusCode::OK,
+        Reply {
+            message: "student order issued successfully",
+            data: json!({
+                "student": student_to_json(student),
+                "order": order_to_json(order),
+            }),
+        },
+    ))
+}
+
+/// Printed rosters are sorted by the full names
+async fn roster_table(
+    ReqScopeModule(module): ReqScopeModule,
+    study_group_id: study_group::EntityId,
+    date: Date,
+    roster: Vec<RosterEntry>,
+) -> Outcome<Table, ListException> {
+    let study_group_repo = module.adapters.resolve::<study_group::BoxedRepo>();
+    let person_repo = module.adapters.resolve::<person::BoxedRepo>();
+
+    let Some(study_group) = study_group_repo.find(study_group_id).await? else {
+        return Outcome::Ex(ListException(StudentException::StudyGroupNotFound));
+    };
+
+    let persons = person_repo
+        .find_many(roster.iter().map(|v| v.student.person_id).collect())
+        .await?
+        .into_iter()
+        .map(|v| (v.id, v.full_name))
+        .collect::<HashMap<_, _>>();
+
+    let mut rows = roster
+        .into_iter()
+        .map(|v| {
+            let full_name = persons
+                .get(&v.student.person_id)
+                .cloned()
+                .unwrap_or_default();
+            (full_name, v)
+        })
+        .collect::<Vec<_>>();
+    rows.sort_by(|a, b| a.0.cmp(&b.0));
+
+    let mut table = Table::new(
+        "roster",
+        format!("Список группы {} на {}", study_group.name, Cell::from(date)),
+        [
+            columns::NUMBER,
+            columns::FULL_NAME,
+            columns::STATUS,
+            columns::SINCE,
+        ],
+    );
+    for (number, (full_name, entry)) in (1..).zip(rows) {
+        table.push(vec![
+            Cell::from(number),
+            Cell::from(full_name),
+            Cell::from(entry.status.label()),
+            Cell::from(entry.since),
+        ]);
+    }
+
+    Outcome::Ok(table)
+}
+
+#[debug_handler]
+async fn roster(
+    module: ReqScopeModule,
+    _: Auth<Authenticated>,
+    Export(export): Export,
+    Path(id): Path<i32>,
+    Query(query): Query<DateQuery>,
+) -> ApiResult {
+    let date = query.date.unwrap_or_else(today);
+
+    let roster = module
+        .0
+        .resolve::<StudentService>()
+        .roster(Id::new(id), date)
+        .await
+        .map_ex(ListException)?;
+
+    if let Some(exporter) = export {
+        let table = roster_table(module, Id::new(id), date, roster).await?;
+        return ApiResult::new(exporter.render(table));
+    }
+
+    ApiResult::new((
+        StatusCode::OK,
+        Reply {
+            message: "study group roster built successfully",
+            data: json!({
+                "studyGroupId": id,
+                "date": date,
+                "students": roster
+                    .into_iter()
+                    .map(roster_entry_to_json)
+                    .collect::<Vec<_>>(),
+            }),
+        },
+    ))
+}
+
+/// Takes the spreadsheet as the request body, invalid rows are reported and skipped.
+/// The valid ones are imported together, a failure on any of them rolls the whole import back
+#[debug_handler]
+async fn import(
+    ReqScopeModule(module): ReqScopeModule,
+    Auth(claims, _): Auth<Authenticated>,
+    Query(query): Query<ImportQuery>,
+    headers: HeaderMap,
+    body: axum::body::Bytes,
+) -> ApiResult {
+    let format = query
+        .format
+        .as_deref()
+        .or_else(|| headers.get(CONTENT_TYPE)?.to_str().ok())
+        .and_then(SpreadsheetFormat::detect);
+
+    let Some(format) = format else {
+        return ApiResult::new((
+            StatusCode::UNSUPPORTED_MEDIA_TYPE,
+            Reply {
+                message: "spreadsheet should be a csv or xlsx file",
+                data: EmptyData,
+            },
+        ));
+    };
+
+    let rows = match spreadsheet::read_rows(format, &body) {
+        Ok(rows) => rows,
+        Err(err) => {
+            return ApiResult::new((
+                StatusCode::BAD_REQUEST,
+                Reply {
+                    message: format!("{err:#}"),
+                    data: EmptyData,
+                },
+            ))
+        }
+    };
+
+    let report = module
+        .resolve::<StudentImportService>()
+        .import(&Actor::from(&claims), rows, today(), query.dry_run)
+        .await
+        .map_ex(ImportExceptionReply)?;
+
+    let message = match (query.dry_run, report.errors.is_empty()) {
+        (true, true) => "spreadsheet checked successfully",
+        (true, false) => "spreadsheet checked, invalid rows would be skipped",
+        (false, true) => "students imported successfully",
+        (false, false) => "students imported, invalid rows were skipped",
+    };
+
+    ApiResult::new((
+        StatusCode::OK,
+        Reply {
+            message,
+            data: import_report_to_json(report),
+        },
+    ))
+}
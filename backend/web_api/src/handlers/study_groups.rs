@@ -1,29 +1,251 @@
 use std::collections::{HashMap, HashSet};
 
 use anyhow::Context;
-use app::{curriculum, curriculum_module, discipline, person, student, study_group, subdivision};
-use axum::{debug_handler, extract::Path, response::IntoResponse, routing::post, Json, Router};
+use app::{
+    access_policy::Actor,
+    curriculum, curriculum_module, discipline,
+    paging::{Page, Paginated, Sort},
+    person, student,
+    study_group::{self, Qualification, TrainingKind},
+    study_group_service::{StudyGroupException, StudyGroupInfo, StudyGroupService},
+    subdivision,
+};
+use axum::{
+    debug_handler,
+    extract::{Path, Query},
+    response::IntoResponse,
+    routing::get,
+    Json, Router,
+};
 use http::StatusCode;
+use serde::{Deserialize, Serialize};
 use serde_json::json;
-use utils::{di::Module, entity::Id};
+use utils::{di::Module, entity::Id, outcome::Outcome};
 
-use crate::utils::{extractors::ReqScopeModule, ApiResult, CommonState};
+use crate::utils::{
+    export::{columns, Cell, Label, Table},
+    extractors::{AccessRejection, Auth, Export, ReqScopeModule},
+    ApiResult, Authenticated, CommonState, PageReply, Reply, SortOrderPayload,
+};
 
 pub fn router<S: CommonState>() -> Router<S> {
     Router::new()
-        .route("/", axum::routing::get(get_infos))
-        .route("/:id", axum::routing::get(get_info))
+        .route("/", get(get_infos).post(create))
+        .route("/:id", get(get_info).put(update).delete(remove))
 }
 
-#[debug_handler]
-async fn get_infos(ReqScopeModule(module): ReqScopeModule) -> ApiResult {
-    let repo = module.adapters.resolve::<study_group::BoxedRepo>();
+#[derive(Debug, Clone, Copy, Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum SortKeyPayload {
+    #[default]
+    Id,
+    Name,
+}
+
+impl From<SortKeyPayload> for study_group::SortKey {
+    fn from(value: SortKeyPayload) -> Self {
+        match value {
+            SortKeyPayload::Id => study_group::SortKey::Id,
+            SortKeyPayload::Name => study_group::SortKey::Name,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct ListQuery {
+    limit: Option<u64>,
+    offset: Option<u64>,
+    #[serde(default)]
+    sort: SortKeyPayload,
+    #[serde(default)]
+    order: SortOrderPayload,
+    name_prefix: Option<String>,
+    qualification: Option<QualificationPayload>,
+    training_kind: Option<TrainingKindPayload>,
+    department_id: Option<i32>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum QualificationPayload {
+    Bachelor,
+    Master,
+    Postgraduate,
+    Doctorate,
+}
+
+impl From<QualificationPayload> for Qualification {
+    fn from(value: QualificationPayload) -> Self {
+        match value {
+            QualificationPayload::Bachelor => Qualification::Bachelor,
+            QualificationPayload::Master => Qualification::Master,
+            QualificationPayload::Postgraduate => Qualification::Postgraduate,
+            QualificationPayload::Doctorate => Qualification::Doctorate,
+        }
+    }
+}
+
+impl From<Qualification> for QualificationPayload {
+    fn from(value: Qualification) -> Self {
+        match value {
+            Qualification::Bachelor => QualificationPayload::Bachelor,
+            Qualification::Master => QualificationPayload::Master,
+            Qualification::Postgraduate => QualificationPayload::Postgraduate,
+            Qualification::Doctorate => QualificationPayload::Doctorate,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum TrainingKindPayload {
+    FullTime,
+    Correspondence,
+}
 
-    let entities = match repo.list().await {
-        Ok(entities) => entities
+impl From<TrainingKindPayload> for TrainingKind {
+    fn from(value: TrainingKindPayload) -> Self {
+        match value {
+            TrainingKindPayload::FullTime => TrainingKind::FullTime,
+            TrainingKindPayload::Correspondence => TrainingKind::Correspondence,
+        }
+    }
+}
+
+impl From<TrainingKind> for TrainingKindPayload {
+    fn from(value: TrainingKind) -> Self {
+        match value {
+            TrainingKind::FullTime => TrainingKindPayload::FullTime,
+            TrainingKind::Correspondence => TrainingKindPayload::Correspondence,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct StudyGroupPayload {
+    name: String,
+    studying_qualification: QualificationPayload,
+    training_kind: TrainingKindPayload,
+    department_id: i32,
+    curriculums: Vec<i32>,
+    enrollment_year: Option<i32>,
+}
+
+impl From<StudyGroupPayload> for StudyGroupInfo {
+    fn from(value: StudyGroupPayload) -> Self {
+        Self {
+            name: value.name,
+            studying_qualification: value.studying_qualification.into(),
+            training_kind: value.training_kind.into(),
+            department_id: Id::new(value.department_id),
+            curriculums: value.curriculums.into_iter().map(Id::new).collect(),
+            enrollment_year: value.enrollment_year,
+        }
+    }
+}
+
+struct Exception(StudyGroupException);
+
+impl IntoResponse for Exception {
+    fn into_response(self) -> axum::response::Response {
+        let Self(ex) = self;
+        let code = match ex {
+            StudyGroupException::AccessException(ex) => return AccessRejection(ex).into_response(),
+            StudyGroupException::NotFound => StatusCode::NOT_FOUND,
+            StudyGroupException::InvalidName
+            | StudyGroupException::InvalidEnrollmentYear
+            | StudyGroupException::DepartmentNotFound
+            | StudyGroupException::CurriculumNotFound => StatusCode::BAD_REQUEST,
+            StudyGroupException::AlreadyExist
+            | StudyGroupException::InvalidCurriculum
+            | StudyGroupException::InUse
+            | StudyGroupException::Scheduled => StatusCode::CONFLICT,
+        };
+
+        (code, Reply::from(ex)).into_response()
+    }
+}
+
+fn study_group_to_json(study_group: study_group::Entity) -> serde_json::Value {
+    json!({
+        "id": study_group.id.value,
+        "name": study_group.name,
+        "studyingQualification": QualificationPayload::from(study_group.studying_qualification),
+        "trainingKind": TrainingKindPayload::from(study_group.training_kind),
+        "departmentId": study_group.department_id.value,
+        "curriculums": study_group
+            .curriculums
             .into_iter()
-            .map(|e| json!({ "id": e.id.value.to_string(), "name": e.name.to_string() }))
+            .map(|v| v.value)
             .collect::<Vec<_>>(),
+        "enrollmentYear": study_group.enrollment_year,
+    })
+}
+
+async fn study_groups_table(
+    ReqScopeModule(module): ReqScopeModule,
+    study_groups: Vec<study_group::Entity>,
+) -> Outcome<Table, Exception> {
+    let subdivision_repo = module.adapters.resolve::<subdivision::BoxedRepo>();
+
+    let mut departments = HashMap::new();
+    for id in study_groups
+        .iter()
+        .map(|v| v.department_id)
+        .collect::<HashSet<_>>()
+    {
+        if let Some(department) = subdivision_repo.find(id).await? {
+            departments.insert(id, department.name);
+        }
+    }
+
+    let mut table = Table::new(
+        "study_groups",
+        "Учебные группы",
+        [
+            columns::NAME,
+            columns::QUALIFICATION,
+            columns::TRAINING_KIND,
+            columns::DEPARTMENT,
+            columns::ENROLLMENT_YEAR,
+        ],
+    );
+    for study_group in study_groups {
+        table.push(vec![
+            Cell::from(study_group.name),
+            Cell::from(study_group.studying_qualification.label()),
+            Cell::from(study_group.training_kind.label()),
+            Cell::from(departments.get(&study_group.department_id).cloned()),
+            Cell::from(study_group.enrollment_year),
+        ]);
+    }
+
+    Outcome::Ok(table)
+}
+
+#[debug_handler]
+async fn get_infos(
+    module: ReqScopeModule,
+    _: Auth<Authenticated>,
+    Export(export): Export,
+    Query(query): Query<ListQuery>,
+) -> ApiResult {
+    let repo = module.0.adapters.resolve::<study_group::BoxedRepo>();
+
+    let filter = study_group::ListFilter {
+        name_prefix: query.name_prefix,
+        qualification: query.qualification.map(Into::into),
+        training_kind: query.training_kind.map(Into::into),
+        department_id: query.department_id.map(Id::new),
+    };
+    let sort = Sort {
+        key: query.sort.into(),
+        order: query.order.into(),
+    };
+    let page = Page::new(query.limit, query.offset);
+
+    let Paginated { items, total } = match repo.list(filter, sort, page).await {
+        Ok(entities) => entities,
         Err(_) => {
             let msg = Json(json!({
                 "message": "curriculums not found",
@@ -32,15 +254,30 @@ async fn get_infos(ReqScopeModule(module): ReqScopeModule) -> ApiResult {
         }
     };
 
-    ApiResult::new((StatusCode::OK, Json(entities)))
+    if let Some(exporter) = export {
+        let table = study_groups_table(module, items).await?;
+        return ApiResult::new(exporter.render(table));
+    }
+
+    ApiResult::new(PageReply(Paginated {
+        items: items
+            .into_iter()
+            .map(|e| json!({ "id": e.id.value.to_string(), "name": e.name.to_string() }))
+            .collect::<Vec<_>>(),
+        total,
+    }))
 }
 
 #[debug_handler]
-async fn get_info(module: ReqScopeModule, Path(id): Path<i32>) -> ApiResult {
+async fn get_info(
+    module: ReqScopeModule,
+    _: Auth<Authenticated>,
+    Path(id): Path<i32>,
+) -> ApiResult {
     let val = match load_info(module, id).await {
         Ok(val) => Json(val),
         Err(err) => {
-            dbg!(err);
+            tracing::error!(?err, study_group_id = id, "failed to load study group");
             let msg = Json(json!({
                 "message": "study group not found",
             }));
@@ -62,18 +299,34 @@ async fn load_info(
 
     let study_group = repo.find(Id::new(id)).await?.context("")?;
 
-    let mut curriculums = Vec::new();
-    for curriculum in study_group.curriculums {
-        let val = curriculum_repo.find(curriculum).await?.context("")?;
-        curriculums.push(json!({
-            "id": val.id.value,
-            "name": val.name,
-        }));
-    }
+    let curriculums = curriculum_repo
+        .find_many(study_group.curriculums)
+        .await?
+        .into_iter()
+        .map(|val| {
+            json!({
+                "id": val.id.value,
+                "name": val.name,
+            })
+        })
+        .collect::<Vec<_>>();
+
+    let study_group_students = student_repo
+        .list_by_study_group(study_group.id)
+        .await?
+        .into_iter()
+        .filter(|v| v.status.is_enrolled())
+        .collect::<Vec<_>>();
+    let persons = person_repo
+        .find_many(study_group_students.iter().map(|v| v.person_id).collect())
+        .await?
+        .into_iter()
+        .map(|v| (v.id, v))
+        .collect::<HashMap<_, _>>();
 
     let mut students = Vec::new();
-    for student in student_repo.list_by_study_group(study_group.id).await? {
-        let person = person_repo.find(student.person_id).await?.context("")?;
+    for student in study_group_students {
+        let person = persons.get(&student.person_id).context("")?;
         students.push(json!({
             "personId": student.person_id.value,
             "fullName": person.full_name,
@@ -142,3 +395,67 @@ async fn load_info(
     //     "semesters": semesters,
     // }))
 }
+
+#[debug_handler]
+async fn create(
+    ReqScopeModule(module): ReqScopeModule,
+    Auth(claims, _): Auth<Authenticated>,
+    Json(payload): Json<StudyGroupPayload>,
+) -> ApiResult {
+    let entity = module
+        .resolve::<StudyGroupService>()
+        .create(&Actor::from(&claims), payload.into())
+        .await
+        .map_ex(Exception)?;
+
+    ApiResult::new((
+        StatusCode::OK,
+        Reply {
+            message: "study group created successfully",
+            data: study_group_to_json(entity),
+        },
+    ))
+}
+
+#[debug_handler]
+async fn update(
+    ReqScopeModule(module): ReqScopeModule,
+    Auth(claims, _): Auth<Authenticated>,
+    Path(id): Path<i32>,
+    Json(payload): Json<StudyGroupPayload>,
+) -> ApiResult {
+    let entity = module
+        .resolve::<StudyGroupService>()
+        .update(&Actor::from(&claims), Id::new(id), payload.into())
+        .await
+        .map_ex(Exception)?;
+
+    ApiResult::new((
+        StatusCode::OK,
+        Reply {
+            message: "study group updated successfully",
+            data: study_group_to_json(entity),
+        },
+    ))
+}
+
+#[debug_handler]
+async fn remove(
+    ReqScopeModule(module): ReqScopeModule,
+    Auth(claims, _): Auth<Authenticated>,
+    Path(id): Path<i32>,
+) -> ApiResult {
+    let entity = module
+        .resolve::<StudyGroupService>()
+        .delete(&Actor::from(&claims), Id::new(id))
+        .await
+        .map_ex(Exception)?;
+
+    ApiResult::new((
+        StatusCode::OK,
+        Reply {
+            message: "study group deleted successfully",
+            data: study_group_to_json(entity),
+        },
+    ))
+}
@@ -1,29 +1,171 @@
 use std::collections::{HashMap, HashSet};
 
 use anyhow::Context;
-use app::{curriculum, curriculum_module, discipline, person, student, study_group, subdivision};
-use axum::{debug_handler, extract::Path, response::IntoResponse, routing::post, Json, Router};
+use app::{
+    access_policy::Actor,
+    curriculum, curriculum_module, discipline,
+    paging::{Page, Paginated, Sort},
+    person, student, study_group,
+    subdivision::{self, Member},
+    subdivision_service::{SubdivisionException, SubdivisionInfo, SubdivisionService},
+};
+use axum::{
+    debug_handler,
+    extract::{Path, Query},
+    response::IntoResponse,
+    routing::get,
+    Json, Router,
+};
 use http::StatusCode;
+use serde::Deserialize;
 use serde_json::json;
 use utils::{di::Module, entity::Id};
 
-use crate::utils::{extractors::ReqScopeModule, ApiResult, CommonState};
+use crate::utils::{
+    export::{columns, Cell, Table},
+    extractors::{AccessRejection, Auth, Export, ReqScopeModule},
+    ApiResult, Authenticated, CommonState, PageReply, Reply, SortOrderPayload,
+};
 
 pub fn router<S: CommonState>() -> Router<S> {
     Router::new()
-        .route("/", axum::routing::get(get_infos))
-        .route("/:id", axum::routing::get(get_info))
+        .route("/", get(get_infos).post(create))
+        .route("/:id", get(get_info).put(update).delete(remove))
 }
 
-#[debug_handler]
-async fn get_infos(ReqScopeModule(module): ReqScopeModule) -> ApiResult {
-    let repo = module.adapters.resolve::<subdivision::BoxedRepo>();
+#[derive(Debug, Clone, Copy, Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum SortKeyPayload {
+    #[default]
+    Id,
+    Name,
+}
+
+impl From<SortKeyPayload> for subdivision::SortKey {
+    fn from(value: SortKeyPayload) -> Self {
+        match value {
+            SortKeyPayload::Id => subdivision::SortKey::Id,
+            SortKeyPayload::Name => subdivision::SortKey::Name,
+        }
+    }
+}
 
-    let entities = match repo.list().await {
-        Ok(entities) => entities
+#[derive(Debug, Deserialize)]
+struct ListQuery {
+    limit: Option<u64>,
+    offset: Option<u64>,
+    #[serde(default)]
+    sort: SortKeyPayload,
+    #[serde(default)]
+    order: SortOrderPayload,
+    name_prefix: Option<String>,
+    university_id: Option<i32>,
+    parent_id: Option<i32>,
+    tag: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct MemberPayload {
+    person_id: i32,
+    role: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct SubdivisionPayload {
+    name: String,
+    university_id: i32,
+    parent_id: Option<i32>,
+    tags: Vec<String>,
+    members: Vec<MemberPayload>,
+}
+
+impl From<SubdivisionPayload> for SubdivisionInfo {
+    fn from(value: SubdivisionPayload) -> Self {
+        Self {
+            name: value.name,
+            university_id: Id::new(value.university_id),
+            parent_id: value.parent_id.map(Id::new),
+            tags: value.tags.into_iter().map(Id::new).collect(),
+            members: value
+                .members
+                .into_iter()
+                .map(|v| Member {
+                    person_id: Id::new(v.person_id),
+                    role: v.role,
+                })
+                .collect(),
+        }
+    }
+}
+
+struct Exception(SubdivisionException);
+
+impl IntoResponse for Exception {
+    fn into_response(self) -> axum::response::Response {
+        let Self(ex) = self;
+        let code = match ex {
+            SubdivisionException::AccessException(ex) => {
+                return AccessRejection(ex).into_response()
+            }
+            SubdivisionException::NotFound => StatusCode::NOT_FOUND,
+            SubdivisionException::InvalidName
+            | SubdivisionException::UniversityNotFound
+            | SubdivisionException::ParentNotFound
+            | SubdivisionException::InvalidParent
+            | SubdivisionException::TagNotFound
+            | SubdivisionException::PersonNotFound
+            | SubdivisionException::InvalidMemberRole => StatusCode::BAD_REQUEST,
+            SubdivisionException::AlreadyExist | SubdivisionException::InUse => {
+                StatusCode::CONFLICT
+            }
+        };
+
+        (code, Reply::from(ex)).into_response()
+    }
+}
+
+fn subdivision_to_json(subdivision: subdivision::Entity) -> serde_json::Value {
+    json!({
+        "id": subdivision.id.value,
+        "name": subdivision.name,
+        "universityId": subdivision.university_id.value,
+        "parentId": subdivision.parent_id.map(|v| v.value),
+        "tags": subdivision
+            .tags
             .into_iter()
-            .map(|e| json!({ "id": e.id.value.to_string(), "name": e.name.to_string() }))
+            .map(|v| v.value)
+            .collect::<Vec<_>>(),
+        "members": subdivision
+            .members
+            .into_iter()
+            .map(|v| json!({ "personId": v.person_id.value, "role": v.role }))
             .collect::<Vec<_>>(),
+    })
+}
+
+#[debug_handler]
+async fn get_infos(
+    ReqScopeModule(module): ReqScopeModule,
+    _: Auth<Authenticated>,
+    Export(export): Export,
+    Query(query): Query<ListQuery>,
+) -> ApiResult {
+    let repo = module.adapters.resolve::<subdivision::BoxedRepo>();
+
+    let filter = subdivision::ListFilter {
+        name_prefix: query.name_prefix,
+        university_id: query.university_id.map(Id::new),
+        parent_id: query.parent_id.map(Id::new),
+        tag: query.tag.map(Id::new),
+    };
+    let sort = Sort {
+        key: query.sort.into(),
+        order: query.order.into(),
+    };
+    let page = Page::new(query.limit, query.offset);
+
+    let Paginated { items, total } = match repo.list(filter, sort, page).await {
+        Ok(entities) => entities,
         Err(_) => {
             let msg = Json(json!({
                 "message": "subdivisions not found",
@@ -32,15 +174,34 @@ async fn get_infos(ReqScopeModule(module): ReqScopeModule) -> ApiResult {
         }
     };
 
-    ApiResult::new((StatusCode::OK, Json(entities)))
+    if let Some(exporter) = export {
+        let mut table = Table::new("subdivisions", "Подразделения", [columns::NAME]);
+        for subdivision in items {
+            table.push(vec![Cell::from(subdivision.name)]);
+        }
+
+        return ApiResult::new(exporter.render(table));
+    }
+
+    ApiResult::new(PageReply(Paginated {
+        items: items
+            .into_iter()
+            .map(|e| json!({ "id": e.id.value.to_string(), "name": e.name.to_string() }))
+            .collect::<Vec<_>>(),
+        total,
+    }))
 }
 
 #[debug_handler]
-async fn get_info(module: ReqScopeModule, Path(id): Path<i32>) -> ApiResult {
+async fn get_info(
+    module: ReqScopeModule,
+    _: Auth<Authenticated>,
+    Path(id): Path<i32>,
+) -> ApiResult {
     let val = match load_info(module, id).await {
         Ok(val) => Json(val),
         Err(err) => {
-            dbg!(err);
+            tracing::error!(?err, subdivision_id = id, "failed to load subdivision");
             let msg = Json(json!({
                 "message": "subdivision not found",
             }));
@@ -75,3 +236,67 @@ async fn load_info(
         "members": members,
     }))
 }
+
+#[debug_handler]
+async fn create(
+    ReqScopeModule(module): ReqScopeModule,
+    Auth(claims, _): Auth<Authenticated>,
+    Json(payload): Json<SubdivisionPayload>,
+) -> ApiResult {
+    let entity = module
+        .resolve::<SubdivisionService>()
+        .create(&Actor::from(&claims), payload.into())
+        .await
+        .map_ex(Exception)?;
+
+    ApiResult::new((
+        StatusCode::OK,
+        Reply {
+            message: "subdivision created successfully",
+            data: subdivision_to_json(entity),
+        },
+    ))
+}
+
+#[debug_handler]
+async fn update(
+    ReqScopeModule(module): ReqScopeModule,
+    Auth(claims, _): Auth<Authenticated>,
+    Path(id): Path<i32>,
+    Json(payload): Json<SubdivisionPayload>,
+) -> ApiResult {
+    let entity = module
+        .resolve::<SubdivisionService>()
+        .update(&Actor::from(&claims), Id::new(id), payload.into())
+        .await
+        .map_ex(Exception)?;
+
+    ApiResult::new((
+        StatusCode::OK,
+        Reply {
+            message: "subdivision updated successfully",
+            data: subdivision_to_json(entity),
+        },
+    ))
+}
+
+#[debug_handler]
+async fn remove(
+    ReqScopeModule(module): ReqScopeModule,
+    Auth(claims, _): Auth<Authenticated>,
+    Path(id): Path<i32>,
+) -> ApiResult {
+    let entity = module
+        .resolve::<SubdivisionService>()
+        .delete(&Actor::from(&claims), Id::new(id))
+        .await
+        .map_ex(Exception)?;
+
+    ApiResult::new((
+        StatusCode::OK,
+        Reply {
+            message: "subdivision deleted successfully",
+            data: subdivision_to_json(entity),
+        },
+    ))
+}
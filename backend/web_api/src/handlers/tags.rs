@@ -0,0 +1,96 @@
+use app::{
+    access_policy::Actor,
+    tag,
+    tag_service::{TagException, TagService},
+};
+use axum::{
+    debug_handler,
+    extract::Path,
+    response::IntoResponse,
+    routing::{delete, post},
+    Json, Router,
+};
+use http::StatusCode;
+use serde::Deserialize;
+use serde_json::json;
+use utils::{di::Module, entity::Id};
+
+use crate::utils::{
+    extractors::{AccessRejection, Auth, ReqScopeModule},
+    ApiResult, Authenticated, CommonState, Reply,
+};
+
+pub fn router<S: CommonState>() -> Router<S> {
+    Router::new()
+        .route("/", post(create))
+        .route("/:name", delete(remove))
+}
+
+#[derive(Debug, Deserialize)]
+struct TagPayload {
+    name: String,
+}
+
+struct Exception(TagException);
+
+impl IntoResponse for Exception {
+    fn into_response(self) -> axum::response::Response {
+        let Self(ex) = self;
+        let code = match ex {
+            TagException::AccessException(ex) => return AccessRejection(ex).into_response(),
+            TagException::NotFound => StatusCode::NOT_FOUND,
+            TagException::InvalidName => StatusCode::BAD_REQUEST,
+            TagException::AlreadyExist | TagException::InUse => StatusCode::CONFLICT,
+        };
+
+        (code, Reply::from(ex)).into_response()
+    }
+}
+
+fn tag_to_json(tag: tag::Entity) -> serde_json::Value {
+    json!({
+        "name": tag.name.value,
+    })
+}
+
+#[debug_handler]
+async fn create(
+    ReqScopeModule(module): ReqScopeModule,
+    Auth(claims, _): Auth<Authenticated>,
+    Json(payload): Json<TagPayload>,
+) -> ApiResult {
+    let entity = module
+        .resolve::<TagService>()
+        .create(&Actor::from(&claims), payload.name)
+        .await
+        .map_ex(Exception)?;
+
+    ApiResult::new((
+        StatusCode::OK,
+        Reply {
+            message: "tag created successfully",
+            data: tag_to_json(entity),
+        },
+    ))
+}
+
+#[debug_handler]
+async fn remove(
+    ReqScopeModule(module): ReqScopeModule,
+    Auth(claims, _): Auth<Authenticated>,
+    Path(name): Path<String>,
+) -> ApiResult {
+    let entity = module
+        .resolve::<TagService>()
+        .delete(&Actor::from(&claims), Id::new(name))
+        .await
+        .map_ex(Exception)?;
+
+    ApiResult::new((
+        StatusCode::OK,
+        Reply {
+            message: "tag deleted successfully",
+            data: tag_to_json(entity),
+        },
+    ))
+}
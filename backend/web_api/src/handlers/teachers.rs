@@ -0,0 +1,278 @@
+use app::{
+    access_policy::Actor,
+    teacher::{self, TeacherClass, TeacherKind},
+    teacher_service::{TeacherException, TeacherInfo, TeacherService},
+};
+use axum::{
+    debug_handler,
+    extract::Path,
+    response::IntoResponse,
+    routing::{get, post},
+    Json, Router,
+};
+use http::StatusCode;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use utils::{di::Module, entity::Id};
+
+use super::timetable::{time_slot_to_json, TimeSlotPayload};
+use crate::utils::{
+    extractors::{AccessRejection, Auth, ReqScopeModule},
+    ApiResult, Authenticated, CommonState, Reply,
+};
+
+pub fn router<S: CommonState>() -> Router<S> {
+    Router::new()
+        .route("/", post(create))
+        .route("/:id", get(get_info).put(update).delete(remove))
+        .route(
+            "/:id/availability",
+            get(get_availability).put(set_availability),
+        )
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(super) enum TeacherKindPayload {
+    Assistant,
+    RegularTeacher,
+    SeniorTeacher,
+    AssociateProfessor,
+    Professor,
+}
+
+impl From<TeacherKindPayload> for TeacherKind {
+    fn from(value: TeacherKindPayload) -> Self {
+        match value {
+            TeacherKindPayload::Assistant => TeacherKind::Assistant,
+            TeacherKindPayload::RegularTeacher => TeacherKind::RegularTeacher,
+            TeacherKindPayload::SeniorTeacher => TeacherKind::SeniorTeacher,
+            TeacherKindPayload::AssociateProfessor => TeacherKind::AssociateProfessor,
+            TeacherKindPayload::Professor => TeacherKind::Professor,
+        }
+    }
+}
+
+impl From<TeacherKind> for TeacherKindPayload {
+    fn from(value: TeacherKind) -> Self {
+        match value {
+            TeacherKind::Assistant => TeacherKindPayload::Assistant,
+            TeacherKind::RegularTeacher => TeacherKindPayload::RegularTeacher,
+            TeacherKind::SeniorTeacher => TeacherKindPayload::SeniorTeacher,
+            TeacherKind::AssociateProfessor => TeacherKindPayload::AssociateProfessor,
+            TeacherKind::Professor => TeacherKindPayload::Professor,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct TeacherClassPayload {
+    class_id: i32,
+    study_group_id: i32,
+}
+
+#[derive(Debug, Deserialize)]
+struct TeacherPayload {
+    person_id: i32,
+    kind: TeacherKindPayload,
+    department_id: i32,
+    classes: Vec<TeacherClassPayload>,
+}
+
+impl From<TeacherPayload> for TeacherInfo {
+    fn from(value: TeacherPayload) -> Self {
+        Self {
+            person_id: Id::new(value.person_id),
+            kind: value.kind.into(),
+            department_id: Id::new(value.department_id),
+            classes: value
+                .classes
+                .into_iter()
+                .map(|v| TeacherClass {
+                    class_id: Id::new(v.class_id),
+                    study_group_id: Id::new(v.study_group_id),
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Windows of a week the teacher is available for classes
+#[derive(Debug, Deserialize)]
+struct AvailabilityPayload {
+    windows: Vec<TimeSlotPayload>,
+}
+
+struct Exception(TeacherException);
+
+impl IntoResponse for Exception {
+    fn into_response(self) -> axum::response::Response {
+        let Self(ex) = self;
+        let code = match ex {
+            TeacherException::AccessException(ex) => return AccessRejection(ex).into_response(),
+            TeacherException::NotFound => StatusCode::NOT_FOUND,
+            TeacherException::PersonNotFound
+            | TeacherException::DepartmentNotFound
+            | TeacherException::ClassNotFound
+            | TeacherException::StudyGroupNotFound
+            | TeacherException::InvalidAvailability => StatusCode::BAD_REQUEST,
+            TeacherException::AlreadyExist
+            | TeacherException::InUse
+            | TeacherException::Scheduled => StatusCode::CONFLICT,
+        };
+
+        (code, Reply::from(ex)).into_response()
+    }
+}
+
+fn teacher_to_json(teacher: teacher::Entity) -> serde_json::Value {
+    json!({
+        "id": teacher.id.value,
+        "personId": teacher.person_id.value,
+        "kind": TeacherKindPayload::from(teacher.kind),
+        "departmentId": teacher.department_id.value,
+        "classes": teacher
+            .classes
+            .into_iter()
+            .map(|v| json!({ "classId": v.class_id.value, "studyGroupId": v.study_group_id.value }))
+            .collect::<Vec<_>>(),
+    })
+}
+
+#[debug_handler]
+async fn get_info(
+    ReqScopeModule(module): ReqScopeModule,
+    _: Auth<Authenticated>,
+    Path(id): Path<i32>,
+) -> ApiResult {
+    let entity = module
+        .resolve::<TeacherService>()
+        .get(Id::new(id))
+        .await
+        .map_ex(Exception)?;
+
+    ApiResult::new((
+        StatusCode::OK,
+        Reply {
+            message: "teacher found successfully",
+            data: teacher_to_json(entity),
+        },
+    ))
+}
+
+#[debug_handler]
+async fn create(
+    ReqScopeModule(module): ReqScopeModule,
+    Auth(claims, _): Auth<Authenticated>,
+    Json(payload): Json<TeacherPayload>,
+) -> ApiResult {
+    let entity = module
+        .resolve::<TeacherService>()
+        .create(&Actor::from(&claims), payload.into())
+        .await
+        .map_ex(Exception)?;
+
+    ApiResult::new((
+        StatusCode::OK,
+        Reply {
+            message: "teacher created successfully",
+            data: teacher_to_json(entity),
+        },
+    ))
+}
+
+#[debug_handler]
+async fn update(
+    ReqScopeModule(module): ReqScopeModule,
+    Auth(claims, _): Auth<Authenticated>,
+    Path(id): Path<i32>,
+    Json(payload): Json<TeacherPayload>,
+) -> ApiResult {
+    let entity = module
+        .resolve::<TeacherService>()
+        .update(&Actor::from(&claims), Id::new(id), payload.into())
+        .await
+        .map_ex(Exception)?;
+
+    ApiResult::new((
+        StatusCode::OK,
+        Reply {
+            message: "teacher updated successfully",
+            data: teacher_to_json(entity),
+        },
+    ))
+}
+
+#[debug_handler]
+async fn remove(
+    ReqScopeModule(module): ReqScopeModule,
+    Auth(claims, _): Auth<Authenticated>,
+    Path(id): Path<i32>,
+) -> ApiResult {
+    let entity = module
+        .resolve::<TeacherService>()
+        .delete(&Actor::from(&claims), Id::new(id))
+        .await
+        .map_ex(Exception)?;
+
+    ApiResult::new((
+        StatusCode::OK,
+        Reply {
+            message: "teacher deleted successfully",
+            data: teacher_to_json(entity),
+        },
+    ))
+}
+
+#[debug_handler]
+async fn get_availability(
+    ReqScopeModule(module): ReqScopeModule,
+    _: Auth<Authenticated>,
+    Path(id): Path<i32>,
+) -> ApiResult {
+    let windows = module
+        .resolve::<TeacherService>()
+        .availability(Id::new(id))
+        .await
+        .map_ex(Exception)?;
+
+    ApiResult::new((
+        StatusCode::OK,
+        Reply {
+            message: "teacher availability found successfully",
+            data: windows
+                .into_iter()
+                .map(time_slot_to_json)
+                .collect::<Vec<_>>(),
+        },
+    ))
+}
+
+#[debug_handler]
+async fn set_availability(
+    ReqScopeModule(module): ReqScopeModule,
+    Auth(claims, _): Auth<Authenticated>,
+    Path(id): Path<i32>,
+    Json(payload): Json<AvailabilityPayload>,
+) -> ApiResult {
+    let windows = module
+        .resolve::<TeacherService>()
+        .set_availability(
+            &Actor::from(&claims),
+            Id::new(id),
+            payload.windows.into_iter().map(Into::into).collect(),
+        )
+        .await
+        .map_ex(Exception)?;
+
+    ApiResult::new((
+        StatusCode::OK,
+        Reply {
+            message: "teacher availability updated successfully",
+            data: windows
+                .into_iter()
+                .map(time_slot_to_json)
+                .collect::<Vec<_>>(),
+        },
+    ))
+}
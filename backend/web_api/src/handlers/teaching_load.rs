@@ -0,0 +1,352 @@
+use app::{
+    access_policy::Actor,
+    class_kind,
+    load_limit::{self, LoadStatus},
+    person, study_group, subdivision, teacher,
+    teaching_load_service::{
+        DepartmentLoad, LoadLimitInfo, SemesterLoad, TeacherLoad, TeachingLoadException,
+        TeachingLoadService,
+    },
+};
+use axum::{
+    debug_handler,
+    extract::Path,
+    response::IntoResponse,
+    routing::{get, put},
+    Json, Router,
+};
+use http::StatusCode;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use utils::{di::Module, entity::Id, outcome::Outcome};
+
+use super::teachers::TeacherKindPayload;
+use crate::utils::{
+    export::{columns, Cell, Label, Table},
+    extractors::{AccessRejection, Auth, Export, ReqScopeModule},
+    ApiResult, Authenticated, CommonState, Reply,
+};
+
+pub fn router<S: CommonState>() -> Router<S> {
+    Router::new()
+        .route("/limits/:university_id", get(get_limits))
+        .route(
+            "/limits/:university_id/:teacher_kind",
+            put(set_limit).delete(remove_limit),
+        )
+        .route("/teachers/:id", get(teacher_load))
+        .route("/departments/:id", get(department_load))
+}
+
+#[derive(Debug, Clone, Copy, Serialize)]
+#[serde(rename_all = "snake_case")]
+enum LoadStatusPayload {
+    Underload,
+    Normal,
+    Overload,
+}
+
+impl From<LoadStatus> for LoadStatusPayload {
+    fn from(value: LoadStatus) -> Self {
+        match value {
+            LoadStatus::Underload => LoadStatusPayload::Underload,
+            LoadStatus::Normal => LoadStatusPayload::Normal,
+            LoadStatus::Overload => LoadStatusPayload::Overload,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct LoadLimitPayload {
+    min_hours: i32,
+    max_hours: i32,
+}
+
+impl From<LoadLimitPayload> for LoadLimitInfo {
+    fn from(value: LoadLimitPayload) -> Self {
+        Self {
+            min_hours: value.min_hours,
+            max_hours: value.max_hours,
+        }
+    }
+}
+
+struct Exception(TeachingLoadException);
+
+impl IntoResponse for Exception {
+    fn into_response(self) -> axum::response::Response {
+        let Self(ex) = self;
+        let code = match ex {
+            TeachingLoadException::AccessException(ex) => {
+                return AccessRejection(ex).into_response()
+            }
+            TeachingLoadException::UniversityNotFound
+            | TeachingLoadException::DepartmentNotFound
+            | TeachingLoadException::TeacherNotFound
+            | TeachingLoadException::LimitNotFound => StatusCode::NOT_FOUND,
+            TeachingLoadException::InvalidLimit => StatusCode::BAD_REQUEST,
+        };
+
+        (code, Reply::from(ex)).into_response()
+    }
+}
+
+fn limit_to_json(limit: load_limit::Entity) -> serde_json::Value {
+    json!({
+        "id": limit.id.value,
+        "universityId": limit.university_id.value,
+        "teacherKind": TeacherKindPayload::from(limit.teacher_kind),
+        "minHours": limit.min_hours.0,
+        "maxHours": limit.max_hours.0,
+    })
+}
+
+fn class_kinds_to_json(hours: Vec<(class_kind::EntityId, i32)>) -> serde_json::Value {
+    hours
+        .into_iter()
+        .map(|(kind, hours)| json!({ "classKind": kind.value, "hours": hours }))
+        .collect()
+}
+
+fn study_groups_to_json(hours: Vec<(study_group::EntityId, i32)>) -> serde_json::Value {
+    hours
+        .into_iter()
+        .map(|(id, hours)| json!({ "studyGroupId": id.value, "hours": hours }))
+        .collect()
+}
+
+fn semester_to_json(semester: SemesterLoad) -> serde_json::Value {
+    json!({
+        "semester": semester.semester,
+        "hours": semester.hours,
+        "byClassKind": class_kinds_to_json(semester.by_class_kind),
+        "byStudyGroup": study_groups_to_json(semester.by_study_group),
+        "minHours": semester.limit.as_ref().map(|v| v.min_hours.0),
+        "maxHours": semester.limit.as_ref().map(|v| v.max_hours.0),
+        "status": semester.status.map(LoadStatusPayload::from),
+    })
+}
+
+fn teacher_load_to_json(load: TeacherLoad) -> serde_json::Value {
+    json!({
+        "teacherId": load.teacher_id.value,
+        "kind": TeacherKindPayload::from(load.kind),
+        "semesters": load
+            .semesters
+            .into_iter()
+            .map(semester_to_json)
+            .collect::<Vec<_>>(),
+    })
+}
+
+/// A row for every semester of every teacher, the same for a teacher and for a department
+async fn load_table(
+    ReqScopeModule(module): ReqScopeModule,
+    name: &'static str,
+    title: String,
+    loads: Vec<TeacherLoad>,
+) -> Outcome<Table, Exception> {
+    let teacher_repo = module.adapters.resolve::<teacher::BoxedRepo>();
+    let person_repo = module.adapters.resolve::<person::BoxedRepo>();
+
+    let mut table = Table::new(
+        name,
+        title,
+        [
+            columns::FULL_NAME,
+            columns::TEACHER_KIND,
+            columns::SEMESTER,
+            columns::HOURS,
+            columns::MIN_HOURS,
+            columns::MAX_HOURS,
+            columns::STATUS,
+        ],
+    );
+    for load in loads {
+        let person = match teacher_repo.find(load.teacher_id).await? {
+            Some(teacher) => person_repo.find(teacher.person_id).await?,
+            None => None,
+        };
+        let full_name = person.map(|v| v.full_name).unwrap_or_default();
+
+        for semester in load.semesters {
+            table.push(vec![
+                Cell::from(full_name.as_str()),
+                Cell::from(load.kind.label()),
+                Cell::from(semester.semester),
+                Cell::from(semester.hours),
+                Cell::from(semester.limit.as_ref().map(|v| v.min_hours.0)),
+                Cell::from(semester.limit.as_ref().map(|v| v.max_hours.0)),
+                Cell::from(semester.status.map(|v| v.label())),
+            ]);
+        }
+    }
+
+    Outcome::Ok(table)
+}
+
+async fn department_load_table(
+    module: ReqScopeModule,
+    load: DepartmentLoad,
+) -> Outcome<Table, Exception> {
+    let department = module
+        .0
+        .adapters
+        .resolve::<subdivision::BoxedRepo>()
+        .find(load.department_id)
+        .await?;
+    let title = match department {
+        Some(department) => format!("Учебная нагрузка кафедры {}", department.name),
+        None => "Учебная нагрузка кафедры".to_owned(),
+    };
+
+    load_table(module, "department_load", title, load.teachers).await
+}
+
+#[debug_handler]
+async fn get_limits(
+    ReqScopeModule(module): ReqScopeModule,
+    _: Auth<Authenticated>,
+    Path(university_id): Path<i32>,
+) -> ApiResult {
+    let limits = module
+        .resolve::<TeachingLoadService>()
+        .limits(Id::new(university_id))
+        .await
+        .map_ex(Exception)?;
+
+    ApiResult::new((
+        StatusCode::OK,
+        Reply {
+            message: "load limits found successfully",
+            data: limits.into_iter().map(limit_to_json).collect::<Vec<_>>(),
+        },
+    ))
+}
+
+#[debug_handler]
+async fn set_limit(
+    ReqScopeModule(module): ReqScopeModule,
+    Auth(claims, _): Auth<Authenticated>,
+    Path((university_id, teacher_kind)): Path<(i32, TeacherKindPayload)>,
+    Json(payload): Json<LoadLimitPayload>,
+) -> ApiResult {
+    let limit = module
+        .resolve::<TeachingLoadService>()
+        .set_limit(
+            &Actor::from(&claims),
+            Id::new(university_id),
+            teacher_kind.into(),
+            payload.into(),
+        )
+        .await
+        .map_ex(Exception)?;
+
+    ApiResult::new((
+        StatusCode::OK,
+        Reply {
+            message: "load limit updated successfully",
+            data: limit_to_json(limit),
+        },
+    ))
+}
+
+#[debug_handler]
+async fn remove_limit(
+    ReqScopeModule(module): ReqScopeModule,
+    Auth(claims, _): Auth<Authenticated>,
+    Path((university_id, teacher_kind)): Path<(i32, TeacherKindPayload)>,
+) -> ApiResult {
+    let limit = module
+        .resolve::<TeachingLoadService>()
+        .remove_limit(
+            &Actor::from(&claims),
+            Id::new(university_id),
+            teacher_kind.into(),
+        )
+        .await
+        .map_ex(Exception)?;
+
+    ApiResult::new((
+        StatusCode::OK,
+        Reply {
+            message: "load limit deleted successfully",
+            data: limit_to_json(limit),
+        },
+    ))
+}
+
+#[debug_handler]
+async fn teacher_load(
+    module: ReqScopeModule,
+    Auth(claims, _): Auth<Authenticated>,
+    Export(export): Export,
+    Path(id): Path<i32>,
+) -> ApiResult {
+    let load = module
+        .0
+        .resolve::<TeachingLoadService>()
+        .teacher_load(&Actor::from(&claims), Id::new(id))
+        .await
+        .map_ex(Exception)?;
+
+    if let Some(exporter) = export {
+        let title = "Учебная нагрузка преподавателя".to_owned();
+        let table = load_table(module, "teacher_load", title, vec![load]).await?;
+        return ApiResult::new(exporter.render(table));
+    }
+
+    ApiResult::new((
+        StatusCode::OK,
+        Reply {
+            message: "teaching load found successfully",
+            data: teacher_load_to_json(load),
+        },
+    ))
+}
+
+#[debug_handler]
+async fn department_load(
+    module: ReqScopeModule,
+    Auth(claims, _): Auth<Authenticated>,
+    Export(export): Export,
+    Path(id): Path<i32>,
+) -> ApiResult {
+    let load = module
+        .0
+        .resolve::<TeachingLoadService>()
+        .department_load(&Actor::from(&claims), Id::new(id))
+        .await
+        .map_ex(Exception)?;
+
+    if let Some(exporter) = export {
+        let table = department_load_table(module, load).await?;
+        return ApiResult::new(exporter.render(table));
+    }
+
+    ApiResult::new((
+        StatusCode::OK,
+        Reply {
+            message: "teaching load found successfully",
+            data: json!({
+                "departmentId": load.department_id.value,
+                "teachers": load
+                    .teachers
+                    .into_iter()
+                    .map(teacher_load_to_json)
+                    .collect::<Vec<_>>(),
+                "semesters": load
+                    .semesters
+                    .into_iter()
+                    .map(|v| json!({
+                        "semester": v.semester,
+                        "hours": v.hours,
+                        "byClassKind": class_kinds_to_json(v.by_class_kind),
+                        "underloaded": v.underloaded,
+                        "overloaded": v.overloaded,
+                    }))
+                    .collect::<Vec<_>>(),
+            }),
+        },
+    ))
+}
@@ -0,0 +1,408 @@
+use app::{
+    access_policy::Actor,
+    schedule::{self, Recurrence, TimeSlot},
+    schedule_service::{Occurrence, ScheduleException, ScheduleInfo, ScheduleService},
+};
+use axum::{
+    debug_handler,
+    extract::{Path, Query},
+    response::IntoResponse,
+    routing::{get, post},
+    Json, Router,
+};
+use http::StatusCode;
+use serde::{Deserialize, Deserializer, Serialize};
+use serde_json::json;
+use time::{Date, OffsetDateTime, Time, Weekday};
+use utils::{di::Module, entity::Id};
+
+use crate::utils::{
+    extractors::{AccessRejection, Auth, ReqScopeModule},
+    ApiResult, Authenticated, CommonState, Reply,
+};
+
+pub fn router<S: CommonState>() -> Router<S> {
+    Router::new()
+        .route("/entries", post(create))
+        .route("/entries/:id", get(get_info).put(update).delete(remove))
+        .route("/study_groups/:id", get(week_of_study_group))
+        .route("/teachers/:id", get(week_of_teacher))
+        .route("/rooms/:id", get(week_of_room))
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(super) enum WeekdayPayload {
+    Monday,
+    Tuesday,
+    Wednesday,
+    Thursday,
+    Friday,
+    Saturday,
+    Sunday,
+}
+
+impl From<WeekdayPayload> for Weekday {
+    fn from(value: WeekdayPayload) -> Self {
+        match value {
+            WeekdayPayload::Monday => Weekday::Monday,
+            WeekdayPayload::Tuesday => Weekday::Tuesday,
+            WeekdayPayload::Wednesday => Weekday::Wednesday,
+            WeekdayPayload::Thursday => Weekday::Thursday,
+            WeekdayPayload::Friday => Weekday::Friday,
+            WeekdayPayload::Saturday => Weekday::Saturday,
+            WeekdayPayload::Sunday => Weekday::Sunday,
+        }
+    }
+}
+
+impl From<Weekday> for WeekdayPayload {
+    fn from(value: Weekday) -> Self {
+        match value {
+            Weekday::Monday => WeekdayPayload::Monday,
+            Weekday::Tuesday => WeekdayPayload::Tuesday,
+            Weekday::Wednesday => WeekdayPayload::Wednesday,
+            Weekday::Thursday => WeekdayPayload::Thursday,
+            Weekday::Friday => WeekdayPayload::Friday,
+            Weekday::Saturday => WeekdayPayload::Saturday,
+            Weekday::Sunday => WeekdayPayload::Sunday,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(super) enum RecurrencePayload {
+    Weekly,
+    BiWeekly,
+}
+
+impl From<RecurrencePayload> for Recurrence {
+    fn from(value: RecurrencePayload) -> Self {
+        match value {
+            RecurrencePayload::Weekly => Recurrence::Weekly,
+            RecurrencePayload::BiWeekly => Recurrence::BiWeekly,
+        }
+    }
+}
+
+impl From<Recurrence> for RecurrencePayload {
+    fn from(value: Recurrence) -> Self {
+        match value {
+            Recurrence::Weekly => RecurrencePayload::Weekly,
+            Recurrence::BiWeekly => RecurrencePayload::BiWeekly,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub(super) struct TimeSlotPayload {
+    weekday: WeekdayPayload,
+    #[serde(deserialize_with = "deserialize_clock_time")]
+    starts_at: Time,
+    #[serde(deserialize_with = "deserialize_clock_time")]
+    ends_at: Time,
+}
+
+impl From<TimeSlotPayload> for TimeSlot {
+    fn from(value: TimeSlotPayload) -> Self {
+        Self {
+            weekday: value.weekday.into(),
+            starts_at: value.starts_at,
+            ends_at: value.ends_at,
+        }
+    }
+}
+
+pub(super) fn time_slot_to_json(slot: TimeSlot) -> serde_json::Value {
+    json!({
+        "weekday": WeekdayPayload::from(slot.weekday),
+        "startsAt": clock_time_to_json(slot.starts_at),
+        "endsAt": clock_time_to_json(slot.ends_at),
+    })
+}
+
+#[derive(Debug, Deserialize)]
+struct EntryPayload {
+    class_id: i32,
+    study_group_id: i32,
+    teacher_id: i32,
+    room_id: i32,
+    weekday: WeekdayPayload,
+    #[serde(deserialize_with = "deserialize_clock_time")]
+    starts_at: Time,
+    #[serde(deserialize_with = "deserialize_clock_time")]
+    ends_at: Time,
+    recurrence: RecurrencePayload,
+    valid_from: Date,
+    valid_until: Date,
+}
+
+impl From<EntryPayload> for ScheduleInfo {
+    fn from(value: EntryPayload) -> Self {
+        Self {
+            class_id: Id::new(value.class_id),
+            study_group_id: Id::new(value.study_group_id),
+            teacher_id: Id::new(value.teacher_id),
+            room_id: Id::new(value.room_id),
+            slot: TimeSlot {
+                weekday: value.weekday.into(),
+                starts_at: value.starts_at,
+                ends_at: value.ends_at,
+            },
+            recurrence: value.recurrence.into(),
+            valid_from: value.valid_from,
+            valid_until: value.valid_until,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct WeekQuery {
+    /// Any day of the week, the current week if omitted
+    week: Option<Date>,
+}
+
+impl WeekQuery {
+    fn date(&self) -> Date {
+        self.week
+            .unwrap_or_else(|| OffsetDateTime::now_utc().date())
+    }
+}
+
+/// Parses `HH:MM` time of a day
+pub(super) fn deserialize_clock_time<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Time, D::Error> {
+    let value = String::deserialize(deserializer)?;
+
+    let invalid = || serde::de::Error::custom(format!("invalid time \"{value}\", expected HH:MM"));
+    let (hour, minute) = value.split_once(':').ok_or_else(invalid)?;
+    let hour = hour.parse::<u8>().map_err(|_| invalid())?;
+    let minute = minute.parse::<u8>().map_err(|_| invalid())?;
+
+    Time::from_hms(hour, minute, 0).map_err(|_| invalid())
+}
+
+pub(super) fn clock_time_to_json(time: Time) -> String {
+    format!("{:02}:{:02}", time.hour(), time.minute())
+}
+
+pub(super) struct Exception(pub(super) ScheduleException);
+
+impl IntoResponse for Exception {
+    fn into_response(self) -> axum::response::Response {
+        let Self(ex) = self;
+        let code = match ex {
+            ScheduleException::AccessException(ex) => return AccessRejection(ex).into_response(),
+            ScheduleException::NotFound => StatusCode::NOT_FOUND,
+            ScheduleException::ClassNotFound
+            | ScheduleException::StudyGroupNotFound
+            | ScheduleException::TeacherNotFound
+            | ScheduleException::RoomNotFound
+            | ScheduleException::NotAssigned
+            | ScheduleException::InvalidTimeSlot
+            | ScheduleException::InvalidPeriod => StatusCode::BAD_REQUEST,
+            ScheduleException::TeacherBusy
+            | ScheduleException::RoomBusy
+            | ScheduleException::StudyGroupBusy => StatusCode::CONFLICT,
+        };
+
+        (code, Reply::from(ex)).into_response()
+    }
+}
+
+/// Week lookups answer 404 for a missing group, teacher or room
+struct WeekException(ScheduleException);
+
+impl IntoResponse for WeekException {
+    fn into_response(self) -> axum::response::Response {
+        let Self(ex) = self;
+        match ex {
+            ScheduleException::StudyGroupNotFound
+            | ScheduleException::TeacherNotFound
+            | ScheduleException::RoomNotFound => {
+                (StatusCode::NOT_FOUND, Reply::from(ex)).into_response()
+            }
+            ex => Exception(ex).into_response(),
+        }
+    }
+}
+
+pub(super) fn entry_to_json(entry: schedule::Entity) -> serde_json::Value {
+    json!({
+        "id": entry.id.value,
+        "classId": entry.class_id.value,
+        "studyGroupId": entry.study_group_id.value,
+        "teacherId": entry.teacher_id.value,
+        "roomId": entry.room_id.value,
+        "weekday": WeekdayPayload::from(entry.slot.weekday),
+        "startsAt": clock_time_to_json(entry.slot.starts_at),
+        "endsAt": clock_time_to_json(entry.slot.ends_at),
+        "recurrence": RecurrencePayload::from(entry.recurrence),
+        "validFrom": entry.valid_from,
+        "validUntil": entry.valid_until,
+    })
+}
+
+fn week_to_json(occurrences: Vec<Occurrence>) -> serde_json::Value {
+    occurrences
+        .into_iter()
+        .map(|v| {
+            let mut json = entry_to_json(v.entry);
+            json["date"] = json!(v.date);
+            json
+        })
+        .collect()
+}
+
+#[debug_handler]
+async fn get_info(
+    ReqScopeModule(module): ReqScopeModule,
+    _: Auth<Authenticated>,
+    Path(id): Path<i32>,
+) -> ApiResult {
+    let entity = module
+        .resolve::<ScheduleService>()
+        .get(Id::new(id))
+        .await
+        .map_ex(Exception)?;
+
+    ApiResult::new((
+        StatusCode::OK,
+        Reply {
+            message: "schedule entry found successfully",
+            data: entry_to_json(entity),
+        },
+    ))
+}
+
+#[debug_handler]
+async fn create(
+    ReqScopeModule(module): ReqScopeModule,
+    Auth(claims, _): Auth<Authenticated>,
+    Json(payload): Json<EntryPayload>,
+) -> ApiResult {
+    let entity = module
+        .resolve::<ScheduleService>()
+        .create(&Actor::from(&claims), payload.into())
+        .await
+        .map_ex(Exception)?;
+
+    ApiResult::new((
+        StatusCode::OK,
+        Reply {
+            message: "schedule entry created successfully",
+            data: entry_to_json(entity),
+        },
+    ))
+}
+
+#[debug_handler]
+async fn update(
+    ReqScopeModule(module): ReqScopeModule,
+    Auth(claims, _): Auth<Authenticated>,
+    Path(id): Path<i32>,
+    Json(payload): Json<EntryPayload>,
+) -> ApiResult {
+    let entity = module
+        .resolve::<ScheduleService>()
+        .update(&Actor::from(&claims), Id::new(id), payload.into())
+        .await
+        .map_ex(Exception)?;
+
+    ApiResult::new((
+        StatusCode::OK,
+        Reply {
+            message: "schedule entry updated successfully",
+            data: entry_to_json(entity),
+        },
+    ))
+}
+
+#[debug_handler]
+async fn remove(
+    ReqScopeModule(module): ReqScopeModule,
+    Auth(claims, _): Auth<Authenticated>,
+    Path(id): Path<i32>,
+) -> ApiResult {
+    let entity = module
+        .resolve::<ScheduleService>()
+        .delete(&Actor::from(&claims), Id::new(id))
+        .await
+        .map_ex(Exception)?;
+
+    ApiResult::new((
+        StatusCode::OK,
+        Reply {
+            message: "schedule entry deleted successfully",
+            data: entry_to_json(entity),
+        },
+    ))
+}
+
+#[debug_handler]
+async fn week_of_study_group(
+    ReqScopeModule(module): ReqScopeModule,
+    _: Auth<Authenticated>,
+    Path(id): Path<i32>,
+    Query(query): Query<WeekQuery>,
+) -> ApiResult {
+    let occurrences = module
+        .resolve::<ScheduleService>()
+        .week_of_study_group(Id::new(id), query.date())
+        .await
+        .map_ex(WeekException)?;
+
+    ApiResult::new((
+        StatusCode::OK,
+        Reply {
+            message: "timetable found successfully",
+            data: week_to_json(occurrences),
+        },
+    ))
+}
+
+#[debug_handler]
+async fn week_of_teacher(
+    ReqScopeModule(module): ReqScopeModule,
+    _: Auth<Authenticated>,
+    Path(id): Path<i32>,
+    Query(query): Query<WeekQuery>,
+) -> ApiResult {
+    let occurrences = module
+        .resolve::<ScheduleService>()
+        .week_of_teacher(Id::new(id), query.date())
+        .await
+        .map_ex(WeekException)?;
+
+    ApiResult::new((
+        StatusCode::OK,
+        Reply {
+            message: "timetable found successfully",
+            data: week_to_json(occurrences),
+        },
+    ))
+}
+
+#[debug_handler]
+async fn week_of_room(
+    ReqScopeModule(module): ReqScopeModule,
+    _: Auth<Authenticated>,
+    Path(id): Path<i32>,
+    Query(query): Query<WeekQuery>,
+) -> ApiResult {
+    let occurrences = module
+        .resolve::<ScheduleService>()
+        .week_of_room(Id::new(id), query.date())
+        .await
+        .map_ex(WeekException)?;
+
+    ApiResult::new((
+        StatusCode::OK,
+        Reply {
+            message: "timetable found successfully",
+            data: week_to_json(occurrences),
+        },
+    ))
+}
@@ -0,0 +1,305 @@
+use std::convert::Infallible;
+
+use app::{
+    access_policy::Actor,
+    timetable_draft::{self, DraftStatus, UnplacedReason},
+    timetable_draft_service::{DraftRequest, TimetableDraftException, TimetableDraftService},
+    timetable_generator,
+};
+use axum::{
+    debug_handler,
+    extract::Path,
+    response::IntoResponse,
+    routing::{get, post},
+    Extension, Json, Router,
+};
+use http::StatusCode;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use time::Date;
+use utils::{di::Module, entity::Id, outcome::Outcome};
+
+use super::timetable::{
+    entry_to_json, time_slot_to_json, Exception as ScheduleExceptionResponse, RecurrencePayload,
+};
+use crate::{
+    api_state::ApiState,
+    utils::{
+        extractors::{AccessRejection, Auth, ReqScopeModule},
+        AfterCommit, ApiResult, Authenticated, CommonState, Reply,
+    },
+};
+
+pub fn router<S: CommonState>() -> Router<S> {
+    Router::new()
+        .route("/", post(create))
+        .route("/:id", get(get_info).delete(remove))
+        .route("/:id/apply", post(apply))
+}
+
+#[derive(Debug, Clone, Copy, Serialize)]
+#[serde(rename_all = "snake_case")]
+enum DraftStatusPayload {
+    Pending,
+    Ready,
+    Failed,
+    Applied,
+}
+
+impl From<DraftStatus> for DraftStatusPayload {
+    fn from(value: DraftStatus) -> Self {
+        match value {
+            DraftStatus::Pending => DraftStatusPayload::Pending,
+            DraftStatus::Ready => DraftStatusPayload::Ready,
+            DraftStatus::Failed => DraftStatusPayload::Failed,
+            DraftStatus::Applied => DraftStatusPayload::Applied,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize)]
+#[serde(rename_all = "snake_case")]
+enum UnplacedReasonPayload {
+    Unassigned,
+    NoRoom,
+    NoSlot,
+}
+
+impl From<UnplacedReason> for UnplacedReasonPayload {
+    fn from(value: UnplacedReason) -> Self {
+        match value {
+            UnplacedReason::Unassigned => UnplacedReasonPayload::Unassigned,
+            UnplacedReason::NoRoom => UnplacedReasonPayload::NoRoom,
+            UnplacedReason::NoSlot => UnplacedReasonPayload::NoSlot,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct DraftPayload {
+    study_groups: Vec<i32>,
+    semester: i32,
+    valid_from: Date,
+    valid_until: Date,
+}
+
+impl From<DraftPayload> for DraftRequest {
+    fn from(value: DraftPayload) -> Self {
+        Self {
+            study_groups: value.study_groups.into_iter().map(Id::new).collect(),
+            semester: value.semester,
+            valid_from: value.valid_from,
+            valid_until: value.valid_until,
+        }
+    }
+}
+
+struct Exception(TimetableDraftException);
+
+impl IntoResponse for Exception {
+    fn into_response(self) -> axum::response::Response {
+        let Self(ex) = self;
+        let code = match ex {
+            TimetableDraftException::AccessException(ex) => {
+                return AccessRejection(ex).into_response()
+            }
+            TimetableDraftException::ScheduleException(ex) => {
+                return ScheduleExceptionResponse(ex).into_response()
+            }
+            TimetableDraftException::NotFound => StatusCode::NOT_FOUND,
+            TimetableDraftException::StudyGroupNotFound
+            | TimetableDraftException::NoStudyGroups
+            | TimetableDraftException::InvalidSemester
+            | TimetableDraftException::InvalidPeriod => StatusCode::BAD_REQUEST,
+            TimetableDraftException::NotReady | TimetableDraftException::AlreadyApplied => {
+                StatusCode::CONFLICT
+            }
+        };
+
+        (code, Reply::from(ex)).into_response()
+    }
+}
+
+fn draft_to_json(draft: timetable_draft::Entity) -> serde_json::Value {
+    let mut study_groups = draft
+        .study_groups
+        .iter()
+        .map(|v| v.value)
+        .collect::<Vec<_>>();
+    study_groups.sort();
+
+    json!({
+        "id": draft.id.value,
+        "authorId": draft.author_id.value,
+        "studyGroups": study_groups,
+        "semester": draft.semester,
+        "validFrom": draft.valid_from,
+        "validUntil": draft.valid_until,
+        "status": DraftStatusPayload::from(draft.status),
+        "score": draft.score,
+        "entries": draft
+            .entries
+            .into_iter()
+            .map(|v| {
+                let mut json = time_slot_to_json(v.slot);
+                json["classId"] = json!(v.class_id.value);
+                json["studyGroupId"] = json!(v.study_group_id.value);
+                json["teacherId"] = json!(v.teacher_id.value);
+                json["roomId"] = json!(v.room_id.value);
+                json["recurrence"] = json!(RecurrencePayload::from(v.recurrence));
+                json["validFrom"] = json!(v.valid_from);
+                json
+            })
+            .collect::<Vec<_>>(),
+        "unplaced": draft
+            .unplaced
+            .into_iter()
+            .map(|v| json!({
+                "classId": v.class_id.value,
+                "studyGroupId": v.study_group_id.value,
+                "reason": UnplacedReasonPayload::from(v.reason),
+            }))
+            .collect::<Vec<_>>(),
+    })
+}
+
+/// Generates the draft outside of the request: the input is loaded and the solution stored in
+/// transactions of their own, while the solver runs on a blocking thread without holding any
+async fn generate(state: ApiState, id: timetable_draft::EntityId) {
+    let Err(err) = try_generate(&state, id).await else {
+        return;
+    };
+    tracing::error!(%err, draft_id = id.value, "failed to generate timetable draft");
+
+    let failed = state
+        .run_in_scope(|module| async move {
+            module.resolve::<TimetableDraftService>().fail(id).await?;
+            Outcome::<(), Infallible>::Ok(())
+        })
+        .await
+        .collapse();
+
+    if let Err(err) = failed {
+        tracing::error!(%err, draft_id = id.value, "failed to mark timetable draft as failed");
+    }
+}
+
+async fn try_generate(
+    state: &ApiState,
+    id: timetable_draft::EntityId,
+) -> Result<(), anyhow::Error> {
+    let input = state
+        .run_in_scope(|module| async move {
+            let input = module
+                .resolve::<TimetableDraftService>()
+                .prepare(id)
+                .await?;
+            Outcome::<_, Infallible>::Ok(input)
+        })
+        .await
+        .collapse()?;
+
+    let Some(input) = input else {
+        return Ok(());
+    };
+
+    let solution =
+        tokio::task::spawn_blocking(move || timetable_generator::generate(&input)).await?;
+
+    state
+        .run_in_scope(|module| async move {
+            module
+                .resolve::<TimetableDraftService>()
+                .complete(id, solution)
+                .await?;
+            Outcome::<(), Infallible>::Ok(())
+        })
+        .await
+        .collapse()
+}
+
+#[debug_handler]
+async fn create(
+    ReqScopeModule(module): ReqScopeModule,
+    Auth(claims, _): Auth<Authenticated>,
+    Json(payload): Json<DraftPayload>,
+) -> ApiResult {
+    let draft = module
+        .resolve::<TimetableDraftService>()
+        .request(&Actor::from(&claims), payload.into())
+        .await
+        .map_ex(Exception)?;
+
+    let id = draft.id;
+    ApiResult::new((
+        StatusCode::ACCEPTED,
+        Extension(AfterCommit::new(move |state| generate(state, id))),
+        Reply {
+            message: "timetable draft requested successfully",
+            data: draft_to_json(draft),
+        },
+    ))
+}
+
+#[debug_handler]
+async fn get_info(
+    ReqScopeModule(module): ReqScopeModule,
+    Auth(claims, _): Auth<Authenticated>,
+    Path(id): Path<i32>,
+) -> ApiResult {
+    let draft = module
+        .resolve::<TimetableDraftService>()
+        .get(&Actor::from(&claims), Id::new(id))
+        .await
+        .map_ex(Exception)?;
+
+    ApiResult::new((
+        StatusCode::OK,
+        Reply {
+            message: "timetable draft found successfully",
+            data: draft_to_json(draft),
+        },
+    ))
+}
+
+#[debug_handler]
+async fn apply(
+    ReqScopeModule(module): ReqScopeModule,
+    Auth(claims, _): Auth<Authenticated>,
+    Path(id): Path<i32>,
+) -> ApiResult {
+    let entries = module
+        .resolve::<TimetableDraftService>()
+        .apply(&Actor::from(&claims), Id::new(id))
+        .await
+        .map_ex(Exception)?;
+
+    ApiResult::new((
+        StatusCode::OK,
+        Reply {
+            message: "timetable draft applied successfully",
+            data: entries.into_iter().map(entry_to_json).collect::<Vec<_>>(),
+        },
+    ))
+}
+
+#[debug_handler]
+async fn remove(
+    ReqScopeModule(module): ReqScopeModule,
+    Auth(claims, _): Auth<Authenticated>,
+    Path(id): Path<i32>,
+) -> ApiResult {
+    let draft = module
+        .resolve::<TimetableDraftService>()
+        .delete(&Actor::from(&claims), Id::new(id))
+        .await
+        .map_ex(Exception)?;
+
+    ApiResult::new((
+        StatusCode::OK,
+        Reply {
+            message: "timetable draft deleted successfully",
+            data: draft_to_json(draft),
+        },
+    ))
+}
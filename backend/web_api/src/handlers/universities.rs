@@ -1,19 +1,69 @@
-use app::university;
-use axum::{debug_handler, extract::Path, response::IntoResponse, routing::post, Json, Router};
+use app::{
+    access_policy::Actor,
+    university,
+    university_service::{UniversityException, UniversityInfo, UniversityService},
+};
+use axum::{debug_handler, extract::Path, response::IntoResponse, routing::get, Json, Router};
 use http::StatusCode;
+use serde::Deserialize;
 use serde_json::json;
-use utils::di::Module;
+use utils::{di::Module, entity::Id};
 
-use crate::utils::{extractors::ReqScopeModule, ApiResult, CommonState};
+use crate::utils::{
+    extractors::{AccessRejection, Auth, ReqScopeModule},
+    ApiResult, Authenticated, CommonState, Reply,
+};
 
 pub fn router<S: CommonState>() -> Router<S> {
     Router::new()
-        .route("/", axum::routing::get(get_infos))
-        .route("/:id", axum::routing::get(get_info))
+        .route("/", get(get_infos).post(create))
+        .route("/:id", get(get_info).put(update).delete(remove))
+}
+
+#[derive(Debug, Deserialize)]
+struct UniversityPayload {
+    name: String,
+    /// Offset of the local time of the timetable, e.g. 180 for Moscow
+    utc_offset_minutes: i32,
+}
+
+impl From<UniversityPayload> for UniversityInfo {
+    fn from(value: UniversityPayload) -> Self {
+        Self {
+            name: value.name,
+            utc_offset_minutes: value.utc_offset_minutes,
+        }
+    }
+}
+
+struct Exception(UniversityException);
+
+impl IntoResponse for Exception {
+    fn into_response(self) -> axum::response::Response {
+        let Self(ex) = self;
+        let code = match ex {
+            UniversityException::AccessException(ex) => return AccessRejection(ex).into_response(),
+            UniversityException::NotFound => StatusCode::NOT_FOUND,
+            UniversityException::InvalidName | UniversityException::InvalidUtcOffset => {
+                StatusCode::BAD_REQUEST
+            }
+            UniversityException::AlreadyExist | UniversityException::InUse => StatusCode::CONFLICT,
+        };
+
+        (code, Reply::from(ex)).into_response()
+    }
+}
+
+fn university_to_json(university: university::Entity) -> serde_json::Value {
+    json!({
+        "id": university.id.value,
+        "name": university.name,
+        "utcOffsetMinutes": university.utc_offset.whole_minutes(),
+    })
 }
 
 #[debug_handler]
-async fn get_infos(ReqScopeModule(module): ReqScopeModule) -> ApiResult {
+async fn get_infos(ReqScopeModule(module): ReqScopeModule, _: Auth<Authenticated>) -> ApiResult {
     let repo = module.adapters.resolve::<university::BoxedRepo>();
 
     let entities = match repo.list().await {
@@ -33,11 +83,18 @@ async fn get_infos(ReqScopeModule(module): ReqScopeModule) -> ApiResult {
 }
 
 #[debug_handler]
-async fn get_info(ReqScopeModule(module): ReqScopeModule, Path(id): Path<i32>) -> ApiResult {
+async fn get_info(
+    ReqScopeModule(module): ReqScopeModule,
+    _: Auth<Authenticated>,
+    Path(id): Path<i32>,
+) -> ApiResult {
     let repo = module.adapters.resolve::<university::BoxedRepo>();
 
     let val = match repo.find(university::EntityId::new(id)).await {
-        Ok(Some(entity)) => json!({"name": entity.name.to_string() }),
+        Ok(Some(entity)) => json!({
+            "name": entity.name.to_string(),
+            "utcOffsetMinutes": entity.utc_offset.whole_minutes(),
+        }),
         Ok(None) => {
             let msg = Json(json!({
                 "message": "university not found",
@@ -54,3 +111,67 @@ async fn get_info(ReqScopeModule(module): ReqScopeModule, Path(id): Path<i32>) -
 
     ApiResult::new((StatusCode::OK, Json(val)))
 }
+
+#[debug_handler]
+async fn create(
+    ReqScopeModule(module): ReqScopeModule,
+    Auth(claims, _): Auth<Authenticated>,
+    Json(payload): Json<UniversityPayload>,
+) -> ApiResult {
+    let university = module
+        .resolve::<UniversityService>()
+        .create(&Actor::from(&claims), payload.into())
+        .await
+        .map_ex(Exception)?;
+
+    ApiResult::new((
+        StatusCode::OK,
+        Reply {
+            message: "university created successfully",
+            data: university_to_json(university),
+        },
+    ))
+}
+
+#[debug_handler]
+async fn update(
+    ReqScopeModule(module): ReqScopeModule,
+    Auth(claims, _): Auth<Authenticated>,
+    Path(id): Path<i32>,
+    Json(payload): Json<UniversityPayload>,
+) -> ApiResult {
+    let university = module
+        .resolve::<UniversityService>()
+        .update(&Actor::from(&claims), Id::new(id), payload.into())
+        .await
+        .map_ex(Exception)?;
+
+    ApiResult::new((
+        StatusCode::OK,
+        Reply {
+            message: "university updated successfully",
+            data: university_to_json(university),
+        },
+    ))
+}
+
+#[debug_handler]
+async fn remove(
+    ReqScopeModule(module): ReqScopeModule,
+    Auth(claims, _): Auth<Authenticated>,
+    Path(id): Path<i32>,
+) -> ApiResult {
+    let university = module
+        .resolve::<UniversityService>()
+        .delete(&Actor::from(&claims), Id::new(id))
+        .await
+        .map_ex(Exception)?;
+
+    ApiResult::new((
+        StatusCode::OK,
+        Reply {
+            message: "university deleted successfully",
+            data: university_to_json(university),
+        },
+    ))
+}
@@ -1,23 +1,92 @@
-use app::user_service::{UserException, UserService};
+use app::{
+    account_service::AccountService,
+    login_throttle_service::LoginThrottleService,
+    user::Role,
+    user_service::{UserException, UserService},
+    user_session,
+};
 use axum::response::IntoResponse;
-use axum::{routing::post, Json, Router};
-use http::StatusCode;
-use serde::Deserialize;
+use axum::{
+    extract::Path,
+    middleware,
+    routing::{delete, get, post, put},
+    Json, Router,
+};
+use http::{header, StatusCode};
+use serde::{Deserialize, Serialize};
 use serde_json::json;
-use utils::di::Module;
+use utils::{di::Module, entity::Id};
 
-use crate::utils::{extractors::ReqScopeModule, Reply};
+use crate::utils::{
+    extractors::{Auth, DeviceId, ReqScopeModule},
+    rollback_on_failure, Admin, Authenticated, EmptyData, Reply,
+};
 
 use crate::utils::{ApiResult, CommonState};
 
 pub fn router<S: CommonState>() -> Router<S> {
-    Router::new().route("/", post(create))
+    Router::new()
+        // the user isn't kept if the verification mail can't be sent
+        .route(
+            "/",
+            post(create).layer(middleware::map_response(rollback_on_failure)),
+        )
+        .route("/password", put(change_password))
+        .route("/sessions", get(list_sessions).delete(revoke_session))
+        .route("/sessions/others", delete(revoke_other_sessions))
+        .route("/:id/unlock", post(unlock))
 }
 
 #[derive(Debug, Deserialize)]
 struct CreatePayload {
     email: String,
     password: String,
+    role: RolePayload,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChangePasswordPayload {
+    old_password: String,
+    new_password: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct SessionPayload {
+    device_id: String,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RolePayload {
+    Admin,
+    UniversityStaff,
+    DepartmentHead,
+    Teacher,
+    Student,
+}
+
+impl From<RolePayload> for Role {
+    fn from(value: RolePayload) -> Self {
+        match value {
+            RolePayload::Admin => Role::Admin,
+            RolePayload::UniversityStaff => Role::UniversityStaff,
+            RolePayload::DepartmentHead => Role::DepartmentHead,
+            RolePayload::Teacher => Role::Teacher,
+            RolePayload::Student => Role::Student,
+        }
+    }
+}
+
+impl From<Role> for RolePayload {
+    fn from(value: Role) -> Self {
+        match value {
+            Role::Admin => RolePayload::Admin,
+            Role::UniversityStaff => RolePayload::UniversityStaff,
+            Role::DepartmentHead => RolePayload::DepartmentHead,
+            Role::Teacher => RolePayload::Teacher,
+            Role::Student => RolePayload::Student,
+        }
+    }
 }
 
 #[derive(Debug)]
@@ -26,34 +95,57 @@ pub struct Exception(pub UserException);
 impl IntoResponse for Exception {
     fn into_response(self) -> axum::response::Response {
         let Self(ex) = self;
-        let ex = dbg!(ex);
+        tracing::debug!(?ex, "exception was thrown");
 
-        // tracing::info!(?ex, "exception was thrown");
         let code = match ex {
             UserException::UserNotFound => StatusCode::BAD_REQUEST,
             UserException::EmailAlreadyInUse => StatusCode::BAD_REQUEST,
             UserException::InvalidEmailOrPassword => StatusCode::UNAUTHORIZED,
+            UserException::InvalidPassword => StatusCode::BAD_REQUEST,
+            UserException::InvalidNewPassword => StatusCode::BAD_REQUEST,
+            UserException::InvalidDeviceId => StatusCode::BAD_REQUEST,
             UserException::SessionExpired => StatusCode::UNAUTHORIZED,
             UserException::SessionNotFound => StatusCode::UNAUTHORIZED,
             UserException::InvalidRefreshToken => StatusCode::UNAUTHORIZED,
+            UserException::RefreshTokenReused => StatusCode::UNAUTHORIZED,
             UserException::SessionsLimitReached => StatusCode::UNAUTHORIZED,
+            UserException::AccountLocked { .. } => StatusCode::TOO_MANY_REQUESTS,
+            UserException::TooManyLoginAttempts { .. } => StatusCode::TOO_MANY_REQUESTS,
         };
 
-        (code, Reply::from(ex)).into_response()
+        let retry_after = match ex {
+            UserException::AccountLocked { retry_after }
+            | UserException::TooManyLoginAttempts { retry_after } => Some(retry_after),
+            _ => None,
+        };
+        let response = (code, Reply::from(ex));
+
+        match retry_after {
+            Some(retry_after) => {
+                ([(header::RETRY_AFTER, retry_after.to_string())], response).into_response()
+            }
+            None => response.into_response(),
+        }
     }
 }
 
 #[axum::debug_handler]
 async fn create(
     ReqScopeModule(module): ReqScopeModule,
+    _: Auth<Admin>,
     Json(payload): Json<CreatePayload>,
 ) -> ApiResult {
     let user = module
         .resolve::<UserService>()
-        .create(payload.email, payload.password)
+        .create(payload.email, payload.password, payload.role.into())
         .await
-        .map_ex(Exception);
-    let user = dbg!(user)?;
+        .map_ex(Exception)?;
+
+    module
+        .resolve::<AccountService>()
+        .request_email_verification(user.id)
+        .await
+        .map_ex(super::account::Exception)?;
 
     ApiResult::new((
         StatusCode::OK,
@@ -65,3 +157,135 @@ async fn create(
         },
     ))
 }
+
+#[axum::debug_handler]
+async fn change_password(
+    ReqScopeModule(module): ReqScopeModule,
+    Auth(claims, _): Auth<Authenticated>,
+    DeviceId(device_id): DeviceId,
+    Json(payload): Json<ChangePasswordPayload>,
+) -> ApiResult {
+    module
+        .resolve::<UserService>()
+        .change_password(
+            Id::new(claims.user_id),
+            device_id,
+            payload.old_password,
+            payload.new_password,
+        )
+        .await
+        .map_ex(Exception)?;
+
+    ApiResult::new((
+        StatusCode::OK,
+        Reply {
+            message: "password changed successfully",
+            data: EmptyData,
+        },
+    ))
+}
+
+#[axum::debug_handler]
+async fn list_sessions(
+    ReqScopeModule(module): ReqScopeModule,
+    Auth(claims, _): Auth<Authenticated>,
+    device_id: Option<DeviceId>,
+) -> ApiResult {
+    let sessions = module
+        .resolve::<UserService>()
+        .list_sessions(Id::new(claims.user_id))
+        .await
+        .map_ex(Exception)?;
+
+    ApiResult::new((
+        StatusCode::OK,
+        Reply {
+            message: "sessions",
+            data: sessions
+                .into_iter()
+                .map(|v| session_to_json(v, device_id.as_ref()))
+                .collect::<Vec<_>>(),
+        },
+    ))
+}
+
+/// The session the request is made from is marked as current
+fn session_to_json(
+    session: user_session::Entity,
+    current_device_id: Option<&DeviceId>,
+) -> serde_json::Value {
+    json!({
+        "current": current_device_id.is_some_and(|DeviceId(v)| *v == session.id.value.device_id),
+        "deviceId": session.id.value.device_id,
+        "userAgent": session.client.user_agent,
+        "ip": session.client.ip,
+        "lastSeenAt": session.last_seen_at.seconds.val,
+        "expiresAt": session.expires_at.seconds.val,
+    })
+}
+
+#[axum::debug_handler]
+async fn revoke_session(
+    ReqScopeModule(module): ReqScopeModule,
+    Auth(claims, _): Auth<Authenticated>,
+    Json(payload): Json<SessionPayload>,
+) -> ApiResult {
+    module
+        .resolve::<UserService>()
+        .revoke_session(Id::new(claims.user_id), payload.device_id)
+        .await
+        .map_ex(Exception)?;
+
+    ApiResult::new((
+        StatusCode::OK,
+        Reply {
+            message: "session revoked successfully",
+            data: EmptyData,
+        },
+    ))
+}
+
+#[axum::debug_handler]
+async fn revoke_other_sessions(
+    ReqScopeModule(module): ReqScopeModule,
+    Auth(claims, _): Auth<Authenticated>,
+    DeviceId(device_id): DeviceId,
+) -> ApiResult {
+    let revoked = module
+        .resolve::<UserService>()
+        .revoke_other_sessions(Id::new(claims.user_id), device_id)
+        .await
+        .map_ex(Exception)?;
+
+    ApiResult::new((
+        StatusCode::OK,
+        Reply {
+            message: "other sessions revoked successfully",
+            data: json!({
+                "revoked": revoked
+            }),
+        },
+    ))
+}
+
+/// Clears the failed logins of the account, the address counters are left as they are
+#[axum::debug_handler]
+async fn unlock(
+    ReqScopeModule(module): ReqScopeModule,
+    _: Auth<Admin>,
+    Path(id): Path<i32>,
+) -> ApiResult {
+    module
+        .resolve::<LoginThrottleService>()
+        .unlock(Id::new(id))
+        .await
+        .map_ex(Exception)?;
+
+    ApiResult::new((
+        StatusCode::OK,
+        Reply {
+            message: "account unlocked successfully, address lockouts are kept",
+            data: EmptyData,
+        },
+    ))
+}
@@ -1,14 +1,22 @@
 #![feature(iterator_try_collect)]
 #![feature(try_trait_v2)]
 
+use std::net::SocketAddr;
+
 use tower_http::cors::{Any, CorsLayer};
 use tracing_subscriber::{prelude::__tracing_subscriber_SubscriberExt, util::SubscriberInitExt};
 
 mod api_state;
+mod cli;
 mod config;
 mod handlers;
 mod utils;
 
+/// `web_api migrate` applies pending schema migrations and exits without serving requests
+const MIGRATE_COMMAND: &str = "migrate";
+/// `web_api import-students <file> <email of the acting user> [--dry-run]` imports admissions spreadsheets
+const IMPORT_STUDENTS_COMMAND: &str = "import-students";
+
 #[tokio::main]
 async fn main() {
     dotenv::from_path(".env").unwrap();
@@ -43,10 +51,24 @@ async fn main() {
         }
     };
 
+    if std::env::args().nth(1).as_deref() == Some(MIGRATE_COMMAND) {
+        tracing::info!("database schema is up to date");
+        return;
+    }
+
+    if std::env::args().nth(1).as_deref() == Some(IMPORT_STUDENTS_COMMAND) {
+        let args = std::env::args().skip(2).collect::<Vec<_>>();
+        if let Err(cause) = cli::import_students(&api_state, &args).await {
+            tracing::error!(%cause, "failed to import students");
+            std::process::exit(1);
+        }
+        return;
+    }
+
     let api = handlers::router(api_state).layer(cors_middleware);
 
     axum::Server::bind(&"127.0.0.1:4000".parse().unwrap())
-        .serve(api.into_make_service())
+        .serve(api.into_make_service_with_connect_info::<SocketAddr>())
         .await
         .unwrap();
 }
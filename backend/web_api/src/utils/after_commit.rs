@@ -0,0 +1,24 @@
+use std::{future::Future, pin::Pin, sync::Arc};
+
+use crate::api_state::ApiState;
+
+type Job = dyn Fn(ApiState) -> Pin<Box<dyn Future<Output = ()> + Send>> + Send + Sync;
+
+/// Background work started once the request transaction is committed, so it sees everything
+/// the request wrote. Handlers attach it to their response as an `Extension`
+#[derive(Clone)]
+pub struct AfterCommit(Arc<Job>);
+
+impl AfterCommit {
+    pub fn new<F, Fut>(job: F) -> Self
+    where
+        F: Fn(ApiState) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        Self(Arc::new(move |state| Box::pin(job(state))))
+    }
+
+    pub fn spawn(self, state: ApiState) {
+        tokio::spawn((self.0)(state));
+    }
+}
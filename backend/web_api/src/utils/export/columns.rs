@@ -0,0 +1,20 @@
+//! Column headers shared by the exports, so the same data is titled the same in every file
+
+pub const NUMBER: &str = "№";
+pub const NAME: &str = "Название";
+pub const FULL_NAME: &str = "ФИО";
+pub const STATUS: &str = "Статус";
+pub const SINCE: &str = "Дата приказа";
+pub const QUALIFICATION: &str = "Квалификация";
+pub const TRAINING_KIND: &str = "Форма обучения";
+pub const DEPARTMENT: &str = "Кафедра";
+pub const ENROLLMENT_YEAR: &str = "Год набора";
+pub const TEACHER_KIND: &str = "Должность";
+pub const DISCIPLINE: &str = "Дисциплина";
+pub const ATTESTATION_KIND: &str = "Форма контроля";
+pub const SCORE: &str = "Баллы";
+pub const GRADE: &str = "Оценка";
+pub const SEMESTER: &str = "Семестр";
+pub const HOURS: &str = "Часы";
+pub const MIN_HOURS: &str = "Минимум часов";
+pub const MAX_HOURS: &str = "Максимум часов";
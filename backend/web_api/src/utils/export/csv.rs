@@ -0,0 +1,18 @@
+use super::Table;
+
+/// Lets excel detect the encoding, it falls back to the system code page otherwise
+const UTF8_BOM: &[u8] = b"\xEF\xBB\xBF";
+
+/// Semicolon separated, which is what excel expects with the russian locale
+pub(super) fn render(table: &Table) -> Result<Vec<u8>, anyhow::Error> {
+    let mut writer = ::csv::WriterBuilder::new()
+        .delimiter(b';')
+        .from_writer(UTF8_BOM.to_vec());
+
+    writer.write_record(&table.columns)?;
+    for row in &table.rows {
+        writer.write_record(row.iter().map(ToString::to_string))?;
+    }
+
+    Ok(writer.into_inner()?)
+}
@@ -0,0 +1,89 @@
+use app::{
+    attestation::AttestationKind,
+    grading_scale::Grade,
+    load_limit::LoadStatus,
+    student::StudentStatus,
+    study_group::{Qualification, TrainingKind},
+    teacher::TeacherKind,
+};
+
+/// Russian name of an enum value, exported files are read by people rather than clients
+pub trait Label {
+    fn label(&self) -> &'static str;
+}
+
+impl Label for Qualification {
+    fn label(&self) -> &'static str {
+        match self {
+            Qualification::Bachelor => "Бакалавриат",
+            Qualification::Master => "Магистратура",
+            Qualification::Postgraduate => "Аспирантура",
+            Qualification::Doctorate => "Докторантура",
+        }
+    }
+}
+
+impl Label for TrainingKind {
+    fn label(&self) -> &'static str {
+        match self {
+            TrainingKind::FullTime => "Очная",
+            TrainingKind::Correspondence => "Заочная",
+        }
+    }
+}
+
+impl Label for TeacherKind {
+    fn label(&self) -> &'static str {
+        match self {
+            TeacherKind::Assistant => "Ассистент",
+            TeacherKind::RegularTeacher => "Преподаватель",
+            TeacherKind::SeniorTeacher => "Старший преподаватель",
+            TeacherKind::AssociateProfessor => "Доцент",
+            TeacherKind::Professor => "Профессор",
+        }
+    }
+}
+
+impl Label for StudentStatus {
+    fn label(&self) -> &'static str {
+        match self {
+            StudentStatus::Active => "Обучается",
+            StudentStatus::OnLeave => "Академический отпуск",
+            StudentStatus::Expelled => "Отчислен",
+            StudentStatus::Graduated => "Выпускник",
+        }
+    }
+}
+
+impl Label for LoadStatus {
+    fn label(&self) -> &'static str {
+        match self {
+            LoadStatus::Underload => "Недогрузка",
+            LoadStatus::Normal => "Норма",
+            LoadStatus::Overload => "Перегрузка",
+        }
+    }
+}
+
+impl Label for AttestationKind {
+    fn label(&self) -> &'static str {
+        match self {
+            AttestationKind::Test => "Зачёт",
+            AttestationKind::DiffTest => "Дифференцированный зачёт",
+            AttestationKind::Exam => "Экзамен",
+        }
+    }
+}
+
+impl Label for Grade {
+    fn label(&self) -> &'static str {
+        match self {
+            Grade::Pass => "Зачтено",
+            Grade::Fail => "Не зачтено",
+            Grade::Excellent => "Отлично",
+            Grade::Good => "Хорошо",
+            Grade::Satisfactory => "Удовлетворительно",
+            Grade::Unsatisfactory => "Неудовлетворительно",
+        }
+    }
+}
@@ -0,0 +1,204 @@
+mod csv;
+mod labels;
+mod pdf;
+mod xlsx;
+
+pub mod columns;
+
+use std::{fmt, sync::Arc};
+
+use axum::response::{IntoResponse, Response};
+use http::{
+    header::{CONTENT_DISPOSITION, CONTENT_TYPE},
+    StatusCode,
+};
+use time::Date;
+
+pub use labels::Label;
+
+use super::api_result::anyhow_error_into_response;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    Csv,
+    Xlsx,
+    Pdf,
+}
+
+impl ExportFormat {
+    pub fn from_extension(value: &str) -> Option<Self> {
+        match value.to_ascii_lowercase().as_str() {
+            "csv" => Some(Self::Csv),
+            "xlsx" => Some(Self::Xlsx),
+            "pdf" => Some(Self::Pdf),
+            _ => None,
+        }
+    }
+
+    pub fn from_mime(value: &str) -> Option<Self> {
+        match value.to_ascii_lowercase().as_str() {
+            "text/csv" => Some(Self::Csv),
+            "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet" => Some(Self::Xlsx),
+            "application/pdf" => Some(Self::Pdf),
+            _ => None,
+        }
+    }
+
+    pub fn mime(self) -> &'static str {
+        match self {
+            Self::Csv => "text/csv; charset=utf-8",
+            Self::Xlsx => "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet",
+            Self::Pdf => "application/pdf",
+        }
+    }
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Csv => "csv",
+            Self::Xlsx => "xlsx",
+            Self::Pdf => "pdf",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Cell {
+    Empty,
+    Text(String),
+    Number(f64),
+    Date(Date),
+}
+
+impl fmt::Display for Cell {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Cell::Empty => Ok(()),
+            Cell::Text(value) => f.write_str(value),
+            Cell::Number(value) if value.fract() == 0.0 => write!(f, "{value:.0}"),
+            Cell::Number(value) => write!(f, "{value}"),
+            // The way dates are written in russian documents
+            Cell::Date(value) => write!(
+                f,
+                "{:02}.{:02}.{}",
+                value.day(),
+                u8::from(value.month()),
+                value.year()
+            ),
+        }
+    }
+}
+
+impl From<String> for Cell {
+    fn from(value: String) -> Self {
+        Cell::Text(value)
+    }
+}
+
+impl From<&str> for Cell {
+    fn from(value: &str) -> Self {
+        Cell::Text(value.to_owned())
+    }
+}
+
+impl From<i32> for Cell {
+    fn from(value: i32) -> Self {
+        Cell::Number(value.into())
+    }
+}
+
+impl From<Date> for Cell {
+    fn from(value: Date) -> Self {
+        Cell::Date(value)
+    }
+}
+
+impl<T: Into<Cell>> From<Option<T>> for Cell {
+    fn from(value: Option<T>) -> Self {
+        value.map_or(Cell::Empty, Into::into)
+    }
+}
+
+/// Rows of a list or a report, the same table is rendered into every export format
+#[derive(Debug, Clone)]
+pub struct Table {
+    /// Ascii file name, the title is sent as the utf-8 one
+    pub name: &'static str,
+    pub title: String,
+    pub columns: Vec<String>,
+    pub rows: Vec<Vec<Cell>>,
+}
+
+impl Table {
+    pub fn new<C: Into<String>>(
+        name: &'static str,
+        title: impl Into<String>,
+        columns: impl IntoIterator<Item = C>,
+    ) -> Self {
+        Self {
+            name,
+            title: title.into(),
+            columns: columns.into_iter().map(Into::into).collect(),
+            rows: vec![],
+        }
+    }
+
+    pub fn push(&mut self, row: Vec<Cell>) {
+        self.rows.push(row);
+    }
+}
+
+/// Renders tables into the format the client asked for
+#[derive(Debug, Clone)]
+pub struct Exporter {
+    pub format: ExportFormat,
+    /// Font embedded into pdf files, the standard pdf fonts have no cyrillic glyphs
+    pub pdf_font_path: Arc<str>,
+}
+
+impl Exporter {
+    pub fn render(&self, table: Table) -> Response {
+        let file = match self.format {
+            ExportFormat::Csv => csv::render(&table),
+            ExportFormat::Xlsx => xlsx::render(&table),
+            ExportFormat::Pdf => pdf::render(&table, &self.pdf_font_path),
+        };
+
+        match file {
+            Ok(file) => (
+                StatusCode::OK,
+                [
+                    (CONTENT_TYPE, self.format.mime().to_owned()),
+                    (CONTENT_DISPOSITION, self.content_disposition(&table)),
+                ],
+                file,
+            )
+                .into_response(),
+            Err(err) => anyhow_error_into_response(err),
+        }
+    }
+
+    fn content_disposition(&self, table: &Table) -> String {
+        let extension = self.format.extension();
+
+        format!(
+            "attachment; filename=\"{}.{extension}\"; filename*=UTF-8''{}.{extension}",
+            table.name,
+            percent_encode(&table.title),
+        )
+    }
+}
+
+/// RFC 5987 encoding of the utf-8 file name
+fn percent_encode(value: &str) -> String {
+    let mut encoded = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        match byte {
+            b'a'..=b'z' | b'A'..=b'Z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                encoded.push(byte as char)
+            }
+            _ => encoded.push_str(&format!("%{byte:02X}")),
+        }
+    }
+
+    encoded
+}
@@ -0,0 +1,176 @@
+use anyhow::Context;
+use printpdf::{IndirectFontRef, Line, Mm, PdfDocument, PdfLayerReference, Point};
+
+use super::Table;
+
+const LAYER_NAME: &str = "table";
+
+const A4_SHORT_SIDE: f32 = 210.0;
+const A4_LONG_SIDE: f32 = 297.0;
+const MARGIN: f32 = 10.0;
+const MM_PER_PT: f32 = 0.3528;
+
+const TITLE_FONT_SIZE: f32 = 12.0;
+const TITLE_HEIGHT: f32 = 10.0;
+const FONT_SIZE: f32 = 9.0;
+const ROW_HEIGHT: f32 = 6.0;
+const CELL_PADDING: f32 = 1.5;
+const LINE_THICKNESS: f32 = 0.5;
+/// Average glyph width of the font size in mm, text is not measured by the glyph widths
+const CHAR_WIDTH: f32 = FONT_SIZE * 0.55 * MM_PER_PT;
+/// Longer values are cut, so a single long cell does not squeeze the other columns
+const COLUMN_MAX_CHARS: usize = 40;
+
+/// Portrait A4 when the table fits in it, landscape otherwise,
+/// with the font shrunk when even the landscape page is too narrow
+pub(super) fn render(table: &Table, font_path: &str) -> Result<Vec<u8>, anyhow::Error> {
+    let font_data =
+        std::fs::read(font_path).with_context(|| format!("failed to read pdf font {font_path}"))?;
+
+    let chars = column_chars(table);
+    let natural_width = chars
+        .iter()
+        .map(|v| *v as f32 * CHAR_WIDTH + 2.0 * CELL_PADDING)
+        .sum::<f32>();
+
+    let (page_width, page_height) = if natural_width <= A4_SHORT_SIDE - 2.0 * MARGIN {
+        (A4_SHORT_SIDE, A4_LONG_SIDE)
+    } else {
+        (A4_LONG_SIDE, A4_SHORT_SIDE)
+    };
+    let scale = ((page_width - 2.0 * MARGIN) / natural_width).min(1.0);
+
+    let layout = Layout {
+        edges: chars.iter().fold(vec![MARGIN], |mut edges, v| {
+            let last = edges[edges.len() - 1];
+            edges.push(last + (*v as f32 * CHAR_WIDTH + 2.0 * CELL_PADDING) * scale);
+            edges
+        }),
+        chars,
+        font_size: FONT_SIZE * scale,
+        padding: CELL_PADDING * scale,
+    };
+
+    let (doc, page, layer) = PdfDocument::new(
+        table.title.as_str(),
+        Mm(page_width),
+        Mm(page_height),
+        LAYER_NAME,
+    );
+    let font = doc.add_external_font(font_data.as_slice())?;
+
+    let mut layer = doc.get_page(page).get_layer(layer);
+    layer.set_outline_thickness(LINE_THICKNESS);
+    layer.use_text(
+        table.title.as_str(),
+        TITLE_FONT_SIZE,
+        Mm(MARGIN),
+        Mm(page_height - MARGIN - TITLE_FONT_SIZE * MM_PER_PT),
+        &font,
+    );
+
+    let mut top = page_height - MARGIN - TITLE_HEIGHT;
+    let mut y = layout.draw_row(&layer, &font, top, table.columns.iter());
+    for row in &table.rows {
+        if y - ROW_HEIGHT < MARGIN {
+            layout.draw_grid(&layer, top, y);
+
+            let (page, new_layer) = doc.add_page(Mm(page_width), Mm(page_height), LAYER_NAME);
+            layer = doc.get_page(page).get_layer(new_layer);
+            layer.set_outline_thickness(LINE_THICKNESS);
+            top = page_height - MARGIN;
+            y = layout.draw_row(&layer, &font, top, table.columns.iter());
+        }
+
+        let cells = row.iter().map(ToString::to_string).collect::<Vec<_>>();
+        y = layout.draw_row(&layer, &font, y, cells.iter());
+    }
+    layout.draw_grid(&layer, top, y);
+
+    Ok(doc.save_to_bytes()?)
+}
+
+/// Width of every column in characters
+fn column_chars(table: &Table) -> Vec<usize> {
+    table
+        .columns
+        .iter()
+        .enumerate()
+        .map(|(i, column)| {
+            table
+                .rows
+                .iter()
+                .filter_map(|row| row.get(i))
+                .map(|v| v.to_string().chars().count())
+                .fold(column.chars().count(), usize::max)
+                .clamp(1, COLUMN_MAX_CHARS)
+        })
+        .collect()
+}
+
+struct Layout {
+    chars: Vec<usize>,
+    /// Horizontal positions of the column borders
+    edges: Vec<f32>,
+    font_size: f32,
+    padding: f32,
+}
+
+impl Layout {
+    /// Returns the bottom of the drawn row
+    fn draw_row<'a>(
+        &self,
+        layer: &PdfLayerReference,
+        font: &IndirectFontRef,
+        top: f32,
+        cells: impl Iterator<Item = &'a String>,
+    ) -> f32 {
+        let bottom = top - ROW_HEIGHT;
+        let baseline = bottom + (ROW_HEIGHT - self.font_size * MM_PER_PT) / 2.0 + 0.5;
+
+        for ((cell, chars), x) in cells.zip(&self.chars).zip(&self.edges) {
+            layer.use_text(
+                truncate(cell, *chars),
+                self.font_size,
+                Mm(x + self.padding),
+                Mm(baseline),
+                font,
+            );
+        }
+
+        let right = self.edges[self.edges.len() - 1];
+        draw_line(layer, (self.edges[0], bottom), (right, bottom));
+
+        bottom
+    }
+
+    /// Top border and the column borders of the rows drawn on a page
+    fn draw_grid(&self, layer: &PdfLayerReference, top: f32, bottom: f32) {
+        let right = self.edges[self.edges.len() - 1];
+        draw_line(layer, (self.edges[0], top), (right, top));
+
+        for x in &self.edges {
+            draw_line(layer, (*x, top), (*x, bottom));
+        }
+    }
+}
+
+fn draw_line(layer: &PdfLayerReference, from: (f32, f32), to: (f32, f32)) {
+    layer.add_line(Line {
+        points: vec![
+            (Point::new(Mm(from.0), Mm(from.1)), false),
+            (Point::new(Mm(to.0), Mm(to.1)), false),
+        ],
+        is_closed: false,
+    });
+}
+
+fn truncate(value: &str, chars: usize) -> String {
+    if value.chars().count() <= chars {
+        return value.to_owned();
+    }
+
+    let mut value = value.chars().take(chars - 1).collect::<String>();
+    value.push('…');
+    value
+}
@@ -0,0 +1,62 @@
+use rust_xlsxwriter::{ExcelDateTime, Format, Workbook};
+
+use super::{Cell, Table};
+
+const DATE_FORMAT: &str = "dd.mm.yyyy";
+const SHEET_NAME_MAX_LEN: usize = 31;
+
+pub(super) fn render(table: &Table) -> Result<Vec<u8>, anyhow::Error> {
+    let header = Format::new().set_bold();
+    let date = Format::new().set_num_format(DATE_FORMAT);
+
+    let mut workbook = Workbook::new();
+    let sheet = workbook.add_worksheet();
+    sheet.set_name(sheet_name(&table.title))?;
+
+    for (col, name) in table.columns.iter().enumerate() {
+        sheet.write_string_with_format(0, col as u16, name, &header)?;
+    }
+
+    for (row, cells) in table.rows.iter().enumerate() {
+        let row = row as u32 + 1;
+        for (col, cell) in cells.iter().enumerate() {
+            let col = col as u16;
+            match cell {
+                Cell::Empty => {}
+                Cell::Text(value) => {
+                    sheet.write_string(row, col, value)?;
+                }
+                Cell::Number(value) => {
+                    sheet.write_number(row, col, *value)?;
+                }
+                Cell::Date(value) => {
+                    let value = ExcelDateTime::from_ymd(
+                        value.year() as u16,
+                        value.month().into(),
+                        value.day(),
+                    )?;
+                    sheet.write_datetime_with_format(row, col, &value, &date)?;
+                }
+            }
+        }
+    }
+
+    sheet.set_freeze_panes(1, 0)?;
+    sheet.autofit();
+
+    Ok(workbook.save_to_buffer()?)
+}
+
+/// Excel limits sheet names in length and forbids some characters in them
+fn sheet_name(title: &str) -> String {
+    let name = title
+        .chars()
+        .filter(|v| !matches!(v, '[' | ']' | ':' | '*' | '?' | '/' | '\\' | '\''))
+        .take(SHEET_NAME_MAX_LEN)
+        .collect::<String>();
+
+    match name.trim() {
+        "" => "Sheet1".to_owned(),
+        name => name.to_owned(),
+    }
+}
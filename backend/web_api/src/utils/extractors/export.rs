@@ -0,0 +1,95 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use axum::{
+    extract::{FromRequestParts, Query},
+    response::{IntoResponse, Response},
+    RequestPartsExt,
+};
+use http::{header::ACCEPT, request::Parts, StatusCode};
+use serde::Deserialize;
+
+use super::ReqScopeModule;
+use crate::utils::{
+    export::{ExportFormat, Exporter},
+    CommonState, Reply,
+};
+
+/// Export asked by `?format=` or, without it, by the `Accept` header.
+/// None means the usual json reply
+pub struct Export(pub Option<Exporter>);
+
+#[derive(Debug, Deserialize)]
+struct FormatQuery {
+    format: Option<String>,
+}
+
+#[async_trait]
+impl<S: CommonState> FromRequestParts<S> for Export {
+    type Rejection = Response;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let Query(query) = parts
+            .extract::<Query<FormatQuery>>()
+            .await
+            .map_err(IntoResponse::into_response)?;
+
+        let format = match query.format {
+            Some(format) if format.eq_ignore_ascii_case("json") => None,
+            Some(format) => match ExportFormat::from_extension(&format) {
+                Some(format) => Some(format),
+                None => {
+                    let reply = Reply {
+                        message: "unknown export format",
+                        data: serde_json::json!({ "format": format }),
+                    };
+                    return Err((StatusCode::BAD_REQUEST, reply).into_response());
+                }
+            },
+            None => accepted_format(parts),
+        };
+
+        let Some(format) = format else {
+            return Ok(Self(None));
+        };
+
+        let ReqScopeModule(module) = ReqScopeModule::from_request_parts(parts, state)
+            .await
+            .map_err(IntoResponse::into_response)?;
+
+        Ok(Self(Some(Exporter {
+            format,
+            pdf_font_path: Arc::clone(&module.adapters.config.pdf_font_path),
+        })))
+    }
+}
+
+/// The most preferred media type wins, json and wildcards keep the json reply
+fn accepted_format(parts: &Parts) -> Option<ExportFormat> {
+    let mut ranges = parts
+        .headers
+        .get_all(ACCEPT)
+        .iter()
+        .filter_map(|v| v.to_str().ok())
+        .flat_map(|v| v.split(','))
+        .map(|range| {
+            let mut params = range.split(';').map(str::trim);
+            let mime = params.next().unwrap_or_default();
+            let quality = params
+                .filter_map(|v| v.strip_prefix("q="))
+                .find_map(|v| v.parse::<f32>().ok())
+                .unwrap_or(1.0);
+
+            (mime, quality)
+        })
+        .collect::<Vec<_>>();
+    ranges.sort_by(|a, b| b.1.total_cmp(&a.1));
+
+    ranges
+        .into_iter()
+        .find_map(|(mime, _)| match mime {
+            "application/json" | "*/*" => Some(None),
+            mime => ExportFormat::from_mime(mime).map(Some),
+        })
+        .flatten()
+}
@@ -1,3 +1,5 @@
+use std::marker::PhantomData;
+
 use anyhow::Context;
 use app::token::{BoxedAccessTokenEngine, Claims};
 use axum::extract::FromRequestParts;
@@ -7,13 +9,12 @@ use http::{header, StatusCode};
 use utils::outcome::Outcome;
 
 use crate::utils::api_result::anyhow_error_into_response;
-// use crate::utils::RoleChecker;
-use crate::utils::{CommonState, Reply};
+use crate::utils::{CommonState, Reply, RoleChecker};
 use utils::di::Module;
 
 use super::ReqScopeModule;
 
-pub struct Auth(pub Claims);
+pub struct Auth<C: RoleChecker>(pub Claims, pub PhantomData<C>);
 
 #[derive(Clone, Copy, Debug, thiserror::Error)]
 pub enum AuthException {
@@ -23,13 +24,19 @@ pub enum AuthException {
     MissingHeader,
     #[error("unsupported scheme")]
     UnsupportedScheme,
+    #[error("invalid access token")]
+    InvalidToken,
     // #[error(transparent)]
     // FailedToExtractJwtClaims(#[from] ExtractClaimsException),
 }
 
 impl IntoResponse for AuthException {
     fn into_response(self) -> axum::response::Response {
-        let response = (StatusCode::UNAUTHORIZED, Reply::from(self));
+        let code = match self {
+            Self::NoRights => StatusCode::FORBIDDEN,
+            _ => StatusCode::UNAUTHORIZED,
+        };
+        let response = (code, Reply::from(self));
 
         match self {
             Self::MissingHeader => {
@@ -41,7 +48,7 @@ impl IntoResponse for AuthException {
 }
 
 #[async_trait::async_trait]
-impl<S: CommonState> FromRequestParts<S> for Auth {
+impl<S: CommonState, C: RoleChecker> FromRequestParts<S> for Auth<C> {
     type Rejection = Response;
 
     async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
@@ -53,10 +60,10 @@ impl<S: CommonState> FromRequestParts<S> for Auth {
     }
 }
 
-async fn extract_jwt_claims<S: CommonState>(
+async fn extract_jwt_claims<S: CommonState, C: RoleChecker>(
     req_parts: &mut Parts,
     state: &S,
-) -> Outcome<Auth, AuthException> {
+) -> Outcome<Auth<C>, AuthException> {
     let ReqScopeModule(module) = ReqScopeModule::from_request_parts(req_parts, state)
         .await
         .context("failed to extract DiContainer")?;
@@ -76,15 +83,13 @@ async fn extract_jwt_claims<S: CommonState>(
     let bearer_token = &auth_header[7..];
 
     let token_engine = module.adapters.resolve::<BoxedAccessTokenEngine>();
-    let claims = token_engine.decode(bearer_token).await?;
-    // let claims = module
-    //     .resolve::<TokenService>()
-    //     .extract_claims(bearer_token)
-    //     .await?;
+    let Ok(claims) = token_engine.decode(bearer_token).await else {
+        return Outcome::Ex(AuthException::InvalidToken);
+    };
 
-    // if !C::can_access(claims.role) {
-    //     return Outcome::Exception(AuthException::NoRights.into());
-    // }
+    if !C::can_access(claims.role) {
+        return Outcome::Ex(AuthException::NoRights);
+    }
 
-    Outcome::Ok(Auth(claims))
+    Outcome::Ok(Auth(claims, PhantomData))
 }
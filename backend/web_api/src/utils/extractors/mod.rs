@@ -1,9 +1,13 @@
 // mod di_container;
+mod export;
 mod jwt_claims;
 mod req_scope_module;
+mod scoped;
 mod session_metadata;
 
 // pub use di_container::DiContainer;
+pub use export::Export;
 pub use jwt_claims::Auth;
 pub use req_scope_module::ReqScopeModule;
-pub use session_metadata::SessionMetadata;
+pub use scoped::{AccessRejection, Scoped, StudyGroupScope, SubdivisionScope, UniversityScope};
+pub use session_metadata::{DeviceId, SessionMetadata};
@@ -0,0 +1,107 @@
+use std::{collections::HashMap, marker::PhantomData};
+
+use anyhow::Context;
+use app::{
+    access_policy::{AccessException, AccessPolicy, Actor},
+    grant::Scope,
+    token::Claims,
+};
+use axum::{
+    extract::{FromRequestParts, Path},
+    response::{IntoResponse, Response},
+};
+use http::{request::Parts, StatusCode};
+use utils::{di::Module, entity::Id, outcome::Outcome};
+
+use crate::utils::{api_result::anyhow_error_into_response, Authenticated, CommonState, Reply};
+
+use super::{Auth, ReqScopeModule};
+
+/// Builds the scope from the `:id` path parameter of the route
+pub trait ScopeTarget {
+    fn scope(id: i32) -> Scope;
+}
+
+pub struct UniversityScope;
+
+impl ScopeTarget for UniversityScope {
+    fn scope(id: i32) -> Scope {
+        Scope::University(Id::new(id))
+    }
+}
+
+pub struct SubdivisionScope;
+
+impl ScopeTarget for SubdivisionScope {
+    fn scope(id: i32) -> Scope {
+        Scope::Subdivision(Id::new(id))
+    }
+}
+
+pub struct StudyGroupScope;
+
+impl ScopeTarget for StudyGroupScope {
+    fn scope(id: i32) -> Scope {
+        Scope::StudyGroup(Id::new(id))
+    }
+}
+
+/// Authenticated user that is allowed by `AccessPolicy` to manage the scope from the path
+pub struct Scoped<T: ScopeTarget>(pub Claims, pub Scope, pub PhantomData<T>);
+
+#[derive(Debug)]
+pub struct AccessRejection(pub AccessException);
+
+impl IntoResponse for AccessRejection {
+    fn into_response(self) -> Response {
+        let Self(ex) = self;
+        let code = match ex {
+            AccessException::NoRights => StatusCode::FORBIDDEN,
+            AccessException::TargetNotFound => StatusCode::NOT_FOUND,
+        };
+
+        (code, Reply::from(ex)).into_response()
+    }
+}
+
+#[async_trait::async_trait]
+impl<S: CommonState, T: ScopeTarget> FromRequestParts<S> for Scoped<T> {
+    type Rejection = Response;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let Auth(claims, _) = Auth::<Authenticated>::from_request_parts(parts, state).await?;
+
+        match authorize::<S, T>(parts, state, claims).await {
+            Outcome::Ok(val) => Ok(val),
+            Outcome::Ex(val) => Err(AccessRejection(val).into_response()),
+            Outcome::Error(val) => Err(anyhow_error_into_response(val)),
+        }
+    }
+}
+
+async fn authorize<S: CommonState, T: ScopeTarget>(
+    parts: &mut Parts,
+    state: &S,
+    claims: Claims,
+) -> Outcome<Scoped<T>, AccessException> {
+    let ReqScopeModule(module) = ReqScopeModule::from_request_parts(parts, state)
+        .await
+        .context("failed to extract DiContainer")?;
+
+    let Ok(Path(params)) = Path::<HashMap<String, String>>::from_request_parts(parts, state).await
+    else {
+        return Outcome::Ex(AccessException::TargetNotFound);
+    };
+
+    let Some(Ok(id)) = params.get("id").map(|id| id.parse::<i32>()) else {
+        return Outcome::Ex(AccessException::TargetNotFound);
+    };
+
+    let scope = T::scope(id);
+    module
+        .resolve::<AccessPolicy>()
+        .authorize(&Actor::from(&claims), scope)
+        .await?;
+
+    Outcome::Ok(Scoped(claims, scope, PhantomData))
+}
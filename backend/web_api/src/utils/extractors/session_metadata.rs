@@ -1,21 +1,105 @@
+use std::{
+    convert::Infallible,
+    net::{IpAddr, SocketAddr},
+};
+
+use app::user_session::ClientInfo;
 use async_trait::async_trait;
-use axum::{extract::FromRequestParts, headers::UserAgent, RequestPartsExt, TypedHeader};
-use http::request::Parts;
+use axum::{
+    extract::{ConnectInfo, FromRequestParts},
+    response::{IntoResponse, Response},
+};
+use http::{header, request::Parts, StatusCode};
+
+use crate::{
+    config::TrustedProxies,
+    utils::{CommonState, Reply},
+};
+
+const DEVICE_ID_HEADER: &str = "x-device-id";
+const FORWARDED_FOR_HEADER: &str = "x-forwarded-for";
+/// Length of the `user_agent` column, longer values are cut
+const USER_AGENT_MAX_LEN: usize = 1024;
 
-use crate::{api_state::ApiState, utils::CommonState};
+/// Identifies the session of the user, the client keeps it next to the refresh token
+pub struct DeviceId(pub String);
 
-pub struct SessionMetadata(pub String);
+#[derive(Clone, Copy, Debug, thiserror::Error)]
+#[error("missing x-device-id header")]
+pub struct MissingDeviceId;
+
+impl IntoResponse for MissingDeviceId {
+    fn into_response(self) -> Response {
+        (StatusCode::BAD_REQUEST, Reply::from(self)).into_response()
+    }
+}
+
+#[async_trait]
+impl<S: CommonState> FromRequestParts<S> for DeviceId {
+    type Rejection = MissingDeviceId;
+
+    async fn from_request_parts(parts: &mut Parts, _: &S) -> Result<Self, Self::Rejection> {
+        parts
+            .headers
+            .get(DEVICE_ID_HEADER)
+            .and_then(|v| v.to_str().ok())
+            .map(|v| Self(v.to_owned()))
+            .ok_or(MissingDeviceId)
+    }
+}
+
+/// Describes the client for the session list, missing or malformed values are left empty.
+/// The address is the peer one unless the peer is a trusted proxy, then `x-forwarded-for` is
+/// read from the right, skipping trusted hops, since anything left of them came from the client
+pub struct SessionMetadata(pub ClientInfo);
 
 #[async_trait]
 impl<S: CommonState> FromRequestParts<S> for SessionMetadata {
-    type Rejection = <TypedHeader<UserAgent> as FromRequestParts<ApiState>>::Rejection;
+    type Rejection = Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, _: &S) -> Result<Self, Self::Rejection> {
+        let header = |name| {
+            parts
+                .headers
+                .get(name)
+                .and_then(|v| v.to_str().ok())
+                .map(str::trim)
+                .filter(|v| !v.is_empty())
+        };
 
-    async fn from_request_parts(
-        parts: &mut Parts,
-        _: &S,
-    ) -> std::result::Result<Self, Self::Rejection> {
-        let TypedHeader(user_agent) = parts.extract::<TypedHeader<UserAgent>>().await?;
+        let user_agent = header(header::USER_AGENT.as_str())
+            .map(|v| v.chars().take(USER_AGENT_MAX_LEN).collect());
 
-        Ok(Self(user_agent.as_str().to_owned()))
+        let peer = parts
+            .extensions
+            .get::<ConnectInfo<SocketAddr>>()
+            .map(|ConnectInfo(addr)| addr.ip());
+        let ip = match (peer, parts.extensions.get::<TrustedProxies>()) {
+            (Some(peer), Some(proxies)) => Some(client_ip(
+                peer,
+                header(FORWARDED_FOR_HEADER).unwrap_or_default(),
+                proxies,
+            )),
+            (peer, _) => peer,
+        }
+        .map(|v| v.to_string());
+
+        Ok(Self(ClientInfo { user_agent, ip }))
     }
 }
+
+fn client_ip(peer: IpAddr, forwarded_for: &str, proxies: &TrustedProxies) -> IpAddr {
+    let mut ip = peer;
+    for hop in forwarded_for.rsplit(',') {
+        if !proxies.contains(&ip) {
+            break;
+        }
+
+        let Ok(hop) = hop.trim().parse::<IpAddr>() else {
+            break;
+        };
+        ip = hop;
+    }
+
+    ip
+}
@@ -1,13 +1,22 @@
+mod after_commit;
 mod api_result;
+pub mod export;
 pub mod extractors;
+mod paging;
 mod provide_req_scope_module;
 mod reply;
-// mod role_checkers;
+mod role_checkers;
+mod rollback;
 
+pub use after_commit::AfterCommit;
 pub use api_result::ApiResult;
+pub use paging::{PageReply, SortOrderPayload};
 pub use provide_req_scope_module::provide_req_scope_module;
 pub use reply::{EmptyData, Reply};
-// pub use role_checkers::{Admin, RoleChecker};
+pub use role_checkers::{
+    Admin, Authenticated, DepartmentHead, RoleChecker, Teacher, UniversityStaff,
+};
+pub use rollback::{rollback_on_failure, Rollback};
 
 pub trait CommonState: Clone + std::fmt::Debug + Send + Sync + 'static {}
 impl<T: Clone + std::fmt::Debug + Send + Sync + 'static> CommonState for T {}
@@ -0,0 +1,42 @@
+use app::paging::{Paginated, SortOrder};
+use axum::{
+    response::{IntoResponse, Response},
+    Json,
+};
+use http::StatusCode;
+use serde::{Deserialize, Serialize};
+
+const TOTAL_COUNT_HEADER: &str = "x-total-count";
+
+#[derive(Debug, Clone, Copy, Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SortOrderPayload {
+    #[default]
+    Asc,
+    Desc,
+}
+
+impl From<SortOrderPayload> for SortOrder {
+    fn from(value: SortOrderPayload) -> Self {
+        match value {
+            SortOrderPayload::Asc => SortOrder::Asc,
+            SortOrderPayload::Desc => SortOrder::Desc,
+        }
+    }
+}
+
+/// List responses stay plain json arrays, the number of matching items is sent in a header
+pub struct PageReply<T>(pub Paginated<T>);
+
+impl<T: Serialize> IntoResponse for PageReply<T> {
+    fn into_response(self) -> Response {
+        let Paginated { items, total } = self.0;
+
+        (
+            StatusCode::OK,
+            [(TOTAL_COUNT_HEADER, total.to_string())],
+            Json(items),
+        )
+            .into_response()
+    }
+}
@@ -4,7 +4,7 @@ use http::{Request, StatusCode};
 
 use crate::api_state::ApiState;
 
-use super::extractors::ReqScopeModule;
+use super::{extractors::ReqScopeModule, AfterCommit, Rollback};
 
 #[tracing::instrument(skip(request, next))]
 pub async fn provide_req_scope_module<B>(
@@ -26,10 +26,25 @@ pub async fn provide_req_scope_module<B>(
         return Err(StatusCode::INTERNAL_SERVER_ERROR);
     };
 
-    let response = next.run(request).await;
+    let mut response = next.run(request).await;
+
+    if response.extensions().get::<Rollback>().is_some() {
+        if let Err(err) = adapters.rollback().await {
+            tracing::error!(%err);
+            return Err(StatusCode::INTERNAL_SERVER_ERROR);
+        }
+
+        return Ok(response);
+    }
 
     match adapters.commit().await {
-        Ok(()) => Ok(response),
+        Ok(()) => {
+            if let Some(job) = response.extensions_mut().remove::<AfterCommit>() {
+                job.spawn(app_state);
+            }
+
+            Ok(response)
+        }
         Err(err) => {
             tracing::error!(%err);
             return Err(StatusCode::INTERNAL_SERVER_ERROR);
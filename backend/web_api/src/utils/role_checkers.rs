@@ -44,3 +44,36 @@ impl RoleChecker for Admin {
         matches!(role, Role::Admin)
     }
 }
+
+pub struct UniversityStaff;
+
+impl RoleChecker for UniversityStaff {
+    fn can_access(role: Role) -> bool {
+        matches!(role, Role::UniversityStaff)
+    }
+}
+
+pub struct DepartmentHead;
+
+impl RoleChecker for DepartmentHead {
+    fn can_access(role: Role) -> bool {
+        matches!(role, Role::DepartmentHead)
+    }
+}
+
+pub struct Teacher;
+
+impl RoleChecker for Teacher {
+    fn can_access(role: Role) -> bool {
+        matches!(role, Role::Teacher)
+    }
+}
+
+/// Accepts any authenticated user regardless of the role
+pub struct Authenticated;
+
+impl RoleChecker for Authenticated {
+    fn can_access(_: Role) -> bool {
+        true
+    }
+}
@@ -0,0 +1,15 @@
+use axum::response::Response;
+
+/// Makes the request scope roll its transaction back instead of committing it
+#[derive(Clone, Copy)]
+pub struct Rollback;
+
+/// Route layer for handlers whose writes only make sense together,
+/// nothing a failed request wrote is committed
+pub async fn rollback_on_failure(mut response: Response) -> Response {
+    if !response.status().is_success() {
+        response.extensions_mut().insert(Rollback);
+    }
+
+    response
+}
@@ -8,8 +8,10 @@ delete from curriculum_modules;
 delete from disciplines;
 delete from study_group_curriculums;
 delete from curriculums;
+delete from student_orders;
 delete from students;
 delete from teachers;
+delete from user_grants;
 delete from study_groups;
 delete from subdivision_members;
 delete from subdivision_tags;
@@ -21,40 +23,59 @@ delete from persons;
 delete from user_sessions;
 delete from users;
 
-insert into users (id, email, password) values (0, 'd.churikov@stud.nstu.ru', 'user');
-insert into users (id, email, password) values (1, 'tomilov@corp.nstu.ru', 'user');
-insert into users (id, email, password) values (2, 'reva@corp.nstu.ru', 'user');
-insert into user_sessions (user_id, metadata, refresh_token, expires_at) values (0, 'chrome', 'token', 234234);
-insert into persons (id, user_id, full_name) values (0, 0, 'danil churickov');
-insert into persons (id, user_id, full_name) values (1, 1, 'tomilov ivan nokolaevich');
-insert into persons (id, user_id, full_name) values (2, 2, 'reva ivan nikolaevich');
+insert into users (id, email, password, role) values (1, 'd.churikov@stud.nstu.ru', 'user', 'student');
+insert into users (id, email, password, role) values (2, 'tomilov@corp.nstu.ru', 'user', 'teacher');
+insert into users (id, email, password, role) values (3, 'reva@corp.nstu.ru', 'user', 'department_head');
+insert into user_sessions (user_id, device_id, refresh_token_hash, family_id, expires_at, user_agent, ip, last_seen_at)
+  values (1, 'chrome', '3c469e9d6c5875d37a43f353d4f88e61fcf812c66eee3457465a40b0da4153e0', '3c469e9d6c5875d37a43f353d4f88e61fcf812c66eee3457465a40b0da4153e0', 234234, 'chrome', '127.0.0.1', 230634);
+insert into persons (id, user_id, full_name) values (1, 1, 'danil churickov');
+insert into persons (id, user_id, full_name) values (2, 2, 'tomilov ivan nokolaevich');
+insert into persons (id, user_id, full_name) values (3, 3, 'reva ivan nikolaevich');
 insert into passports (id, person_id, first_name, last_name, patronymic, date_of_birth, date_of_issue, number, series, gender)
-  values (0, 0, 'danil', 'churikov', 'igorevich', '2002-12-31 22:00:00', '2022-03-01 12:00:00', '444444', '4444', 'male');
+  values (1, 1, 'danil', 'churikov', 'igorevich', '2002-12-31', '2022-03-01', '444444', '4444', 'male');
 
-insert into universities (id, name) values (0, 'nstu');
+insert into universities (id, name) values (1, 'nstu');
 insert into tags (name) values ('faculty');
 insert into tags (name) values ('department');
-insert into subdivisions (id, university_id, name) values (0, 0, 'asu');
-insert into subdivisions (id, university_id, name) values (1, 0, 'avtf');
-insert into subdivision_tags (tag_name, subdivision_id) values ('department', 0);
-insert into subdivision_tags (tag_name, subdivision_id) values ('faculty', 1);
-insert into subdivision_members (subdivision_id, person_id, role) values (0, 1, 'deputy department');
-insert into subdivision_members (subdivision_id, person_id, role) values (1, 2, 'dean');
+insert into subdivisions (id, university_id, parent_id, name) values (2, 1, null, 'avtf');
+insert into subdivisions (id, university_id, parent_id, name) values (1, 1, 2, 'asu');
+insert into subdivision_tags (tag_name, subdivision_id) values ('department', 1);
+insert into subdivision_tags (tag_name, subdivision_id) values ('faculty', 2);
+insert into subdivision_members (subdivision_id, person_id, role) values (1, 2, 'deputy department');
+insert into subdivision_members (subdivision_id, person_id, role) values (2, 3, 'dean');
 
-insert into study_groups (id, name, department_id, studying_qualification, training_kind) values (0, 'avt-113', 0, 'bachelor', 'full_time');
-insert into teachers (id, person_id, kind, department_id) values (0, 1, 'associate_professor', 0);
-insert into students (id, person_id, study_group_id) values (0, 0, 0);
+insert into study_groups (id, name, department_id, studying_qualification, training_kind) values (1, 'avt-113', 1, 'bachelor', 'full_time');
+insert into user_grants (id, user_id, role, subdivision_id) values (1, 3, 'department_head', 2);
+insert into teachers (id, person_id, kind, department_id) values (1, 2, 'associate_professor', 1);
+insert into students (id, person_id, study_group_id) values (1, 1, 1);
+insert into student_orders (id, student_id, kind, study_group_id, date, reason) values (1, 1, 'enrollment', 1, '2021-09-01', 'enrolled after entrance exams');
 
-insert into curriculums (id, name) values (0, 'avt-113 2021');
-insert into study_group_curriculums (study_group_id, curriculum_id) values (0, 0);
-insert into disciplines (id, department_id, name) values (0, 0, 'informatics');
-insert into curriculum_modules (id, curriculum_id, discipline_id, semester) values (0, 0, 0, 1);
+insert into curriculums (id, name, version, status, department_id) values (1, 'avt-113 2021', 1, 'published', 1);
+insert into study_group_curriculums (study_group_id, curriculum_id) values (1, 1);
+insert into disciplines (id, department_id, name) values (1, 1, 'informatics');
+insert into curriculum_modules (id, curriculum_id, discipline_id, semester) values (1, 1, 1, 1);
 
-insert into attestations (id, curriculum_module_id, kind, duration_in_hours) values (0, 0, 'exam', 100);
-insert into attestation_examiners (examiner_id, attestation_id) values (0, 0);
+insert into attestations (id, curriculum_module_id, kind, duration_in_hours) values (1, 1, 'exam', 100);
+insert into attestation_examiners (examiner_id, attestation_id) values (1, 1);
 insert into student_attestations (student_id, attestation_id, score)
-  values (0, 0, 99);
+  values (1, 1, 99);
 
 insert into class_kinds (name) values ('lection');
-insert into classes (id, curriculum_module_id, kind_name, duration_in_hours) values (0, 0, 'lection', 2);
-insert into class_teachers (teacher_id, class_id, study_group_id) values (0, 0, 0);
+insert into classes (id, curriculum_module_id, kind_name, duration_in_hours) values (1, 1, 'lection', 2);
+insert into class_teachers (teacher_id, class_id, study_group_id) values (1, 1, 1);
+
+select setval(pg_get_serial_sequence('users', 'id'), (select max(id) from users));
+select setval(pg_get_serial_sequence('persons', 'id'), (select max(id) from persons));
+select setval(pg_get_serial_sequence('passports', 'id'), (select max(id) from passports));
+select setval(pg_get_serial_sequence('universities', 'id'), (select max(id) from universities));
+select setval(pg_get_serial_sequence('subdivisions', 'id'), (select max(id) from subdivisions));
+select setval(pg_get_serial_sequence('study_groups', 'id'), (select max(id) from study_groups));
+select setval(pg_get_serial_sequence('user_grants', 'id'), (select max(id) from user_grants));
+select setval(pg_get_serial_sequence('teachers', 'id'), (select max(id) from teachers));
+select setval(pg_get_serial_sequence('students', 'id'), (select max(id) from students));
+select setval(pg_get_serial_sequence('student_orders', 'id'), (select max(id) from student_orders));
+select setval(pg_get_serial_sequence('curriculums', 'id'), (select max(id) from curriculums));
+select setval(pg_get_serial_sequence('disciplines', 'id'), (select max(id) from disciplines));
+select setval(pg_get_serial_sequence('curriculum_modules', 'id'), (select max(id) from curriculum_modules));
+select setval(pg_get_serial_sequence('attestations', 'id'), (select max(id) from attestations));
+select setval(pg_get_serial_sequence('classes', 'id'), (select max(id) from classes));
@@ -1,3 +1,2 @@
-. ../.env
-
-psql --host=localhost --port=5432 --username=$PG_USERNAME --password --dbname=$PG_DBNAME --file ../db.sql
+# the schema is owned by the application, this applies pending migrations
+cd .. && cargo run --manifest-path backend/Cargo.toml --bin web_api -- migrate